@@ -215,6 +215,14 @@ lazy_static! {
       install_loc: InstallLoc::DIR_USR_SBIN,
       usage: std::include_str!("../usage/brctl"),
     });
+    #[cfg(feature = "bridge")]
+    appy_mcappface.push(applet {
+      name: "bridge",
+      main: "bridge",
+      entrypoint: Entrypoint::CStyle(crate::networking::bridge::bridge_main),
+      install_loc: InstallLoc::DIR_SBIN,
+      usage: std::include_str!("../usage/bridge"),
+    });
     #[cfg(feature = "bunzip2")]
     appy_mcappface.push(applet {
       name: "bunzip2",
@@ -482,8 +490,8 @@ lazy_static! {
     #[cfg(feature = "depmod")]
     appy_mcappface.push(applet {
       name: "depmod",
-      main: "modprobe",
-      entrypoint: Entrypoint::CStyle(crate::modutils::modprobe_small::modprobe_main),
+      main: "depmod",
+      entrypoint: Entrypoint::CStyle(crate::modutils::depmod::depmod_main),
       install_loc: InstallLoc::DIR_SBIN,
       usage: std::include_str!("../usage/depmod"),
     });
@@ -1138,8 +1146,8 @@ lazy_static! {
     #[cfg(feature = "insmod")]
     appy_mcappface.push(applet {
       name: "insmod",
-      main: "modprobe",
-      entrypoint: Entrypoint::CStyle(crate::modutils::modprobe_small::modprobe_main),
+      main: "insmod",
+      entrypoint: Entrypoint::CStyle(crate::modutils::insmod::insmod_main),
       install_loc: InstallLoc::DIR_SBIN,
       usage: std::include_str!("../usage/insmod"),
     });
@@ -1659,7 +1667,7 @@ lazy_static! {
     appy_mcappface.push(applet {
       name: "modprobe",
       main: "modprobe",
-      entrypoint: Entrypoint::CStyle(crate::modutils::modprobe_small::modprobe_main),
+      entrypoint: Entrypoint::CStyle(crate::modutils::modprobe::modprobe_main),
       install_loc: InstallLoc::DIR_SBIN,
       usage: std::include_str!("../usage/modprobe"),
     });
@@ -1759,6 +1767,14 @@ lazy_static! {
       install_loc: InstallLoc::DIR_BIN,
       usage: std::include_str!("../usage/netstat"),
     });
+    #[cfg(feature = "nft")]
+    appy_mcappface.push(applet {
+      name: "nft",
+      main: "nft",
+      entrypoint: Entrypoint::SafeStyle(crate::networking::nft::nft_main),
+      install_loc: InstallLoc::DIR_USR_SBIN,
+      usage: std::include_str!("../usage/nft"),
+    });
     #[cfg(feature = "nice")]
     appy_mcappface.push(applet {
       name: "nice",
@@ -1815,6 +1831,14 @@ lazy_static! {
       install_loc: InstallLoc::DIR_USR_BIN,
       usage: std::include_str!("../usage/nslookup"),
     });
+    #[cfg(feature = "ntpctl")]
+    appy_mcappface.push(applet {
+      name: "ntpctl",
+      main: "ntpctl",
+      entrypoint: Entrypoint::CStyle(crate::networking::ntpd::ntpctl_main),
+      install_loc: InstallLoc::DIR_USR_SBIN,
+      usage: std::include_str!("../usage/ntpctl"),
+    });
     #[cfg(feature = "ntpd")]
     appy_mcappface.push(applet {
       name: "ntpd",
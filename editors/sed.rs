@@ -11,8 +11,11 @@ use c2rust_bitfields::BitfieldStruct;
 use libc;
 use libc::fchmod;
 use libc::fclose;
+use libc::fflush;
+use libc::fileno;
 use libc::fprintf;
 use libc::free;
+use libc::fwrite;
 use libc::gid_t;
 use libc::puts;
 use libc::stat;
@@ -52,6 +55,21 @@ extern "C" {
   #[no_mangle]
   fn ferror_unlocked(__stream: *mut FILE) -> libc::c_int;
   #[no_mangle]
+  fn feof_unlocked(__stream: *mut FILE) -> libc::c_int;
+  #[no_mangle]
+  fn setvbuf(
+    __stream: *mut FILE,
+    __buf: *mut libc::c_char,
+    __modes: libc::c_int,
+    __n: size_t,
+  ) -> libc::c_int;
+  #[no_mangle]
+  fn popen(__command: *const libc::c_char, __modes: *const libc::c_char) -> *mut FILE;
+  #[no_mangle]
+  fn pclose(__stream: *mut FILE) -> libc::c_int;
+  #[no_mangle]
+  fn open_memstream(__bufloc: *mut *mut libc::c_char, __sizeloc: *mut size_t) -> *mut FILE;
+  #[no_mangle]
   fn fchown(__fd: libc::c_int, __owner: uid_t, __group: gid_t) -> libc::c_int;
 
   #[no_mangle]
@@ -145,6 +163,15 @@ pub struct globals {
   pub append_head: *mut llist_t,
   pub add_cmd_line: *mut libc::c_char,
   pub pipeline: pipeline,
+  pub delim: libc::c_char,
+  pub last_puts_char: libc::c_char,
+  pub unbuffered: smallint,
+  /* -u: the line after the current one is not read yet */
+  pub next_line_pending: smallint,
+  pub debug: smallint,
+  pub quit: smallint,
+  pub line_file: libc::c_int,
+  pub block_depth: libc::c_int,
 }
 
 #[repr(C)]
@@ -176,6 +203,7 @@ pub struct sed_cmd_s {
   pub invert_in_match_sub_p: [u8; 1],
   pub sw_last_char: libc::c_char,
   pub cmd: libc::c_char,
+  pub debug_text: *mut libc::c_char,
 }
 pub type regex_t = re_pattern_buffer;
 
@@ -207,7 +235,12 @@ pub const REG_NOERROR: C2RustUnnamed = 0;
 pub const REG_ENOSYS: C2RustUnnamed = -1;
 pub type C2RustUnnamed_0 = libc::c_uint;
 pub const OPT_in_place: C2RustUnnamed_0 = 1;
-pub const IDX_nul: C2RustUnnamed_1 = 27;
+pub const OPT_separate: C2RustUnnamed_0 = 64;
+pub const OPT_null_data: C2RustUnnamed_0 = 128;
+pub const OPT_unbuffered: C2RustUnnamed_0 = 256;
+pub const OPT_debug: C2RustUnnamed_0 = 512;
+pub const IDX_nul: C2RustUnnamed_1 = 28;
+pub const IDX_e: C2RustUnnamed_1 = 27;
 pub const IDX_y: C2RustUnnamed_1 = 10;
 pub const IDX_T: C2RustUnnamed_1 = 9;
 pub const IDX_w: C2RustUnnamed_1 = 5;
@@ -604,9 +637,9 @@ unsafe fn parse_cmd_args(
   mut sed_cmd: *mut sed_cmd_t,
   mut cmdstr: *const libc::c_char,
 ) -> *const libc::c_char {
-  static mut cmd_letters: [libc::c_char; 28] = [
+  static mut cmd_letters: [libc::c_char; 29] = [
     115, 97, 105, 99, 114, 119, 58, 98, 116, 84, 121, 100, 68, 103, 71, 104, 72, 108, 110, 78, 112,
-    80, 113, 120, 61, 123, 125, 0,
+    80, 113, 120, 61, 123, 125, 101, 0,
   ];
   let mut idx: libc::c_uint = 0;
   idx = strchrnul(cmd_letters.as_ptr(), (*sed_cmd).cmd as libc::c_int)
@@ -717,11 +750,27 @@ unsafe fn parse_cmd_args(
     }
     free(match_0 as *mut libc::c_void);
     free(replace as *mut libc::c_void);
+  } else if idx == IDX_e as libc::c_int as libc::c_uint {
+    /* e [command]: the command runs to end of line, escapes as in a/i/c text.
+     * Without one, the pattern space is executed instead. */
+    cmdstr = skip_whitespace(cmdstr);
+    if *cmdstr != 0 {
+      let mut len_0: libc::c_int = strlen(cmdstr) as libc::c_int;
+      (*sed_cmd).string = copy_parsing_escapes(cmdstr, len_0);
+      cmdstr = cmdstr.offset(len_0 as isize);
+      parse_escapes(
+        (*sed_cmd).string,
+        (*sed_cmd).string,
+        -1i32,
+        '\u{0}' as i32 as libc::c_char,
+        '\u{0}' as i32 as libc::c_char,
+      );
+    }
   } else if idx >= IDX_nul as libc::c_int as libc::c_uint {
     /* if it wasn't a single-letter command that takes no arguments
      * then it must be an invalid command.
      */
-    /* not d,D,g,G,h,H,l,n,N,p,P,q,x,=,{,} */
+    /* not d,D,g,G,h,H,l,n,N,p,P,q,x,=,{,},e */
     crate::libbb::verror_msg::bb_error_msg_and_die(
       b"unsupported command %c\x00" as *const u8 as *const libc::c_char,
       (*sed_cmd).cmd as libc::c_int,
@@ -730,6 +779,253 @@ unsafe fn parse_cmd_args(
   /* give back whatever's left over */
   return cmdstr;
 }
+/* --debug output follows GNU sed 4.8: control chars are shown escaped,
+ * and so is delim (for "/regex/") unless it is NUL. */
+unsafe fn debug_escape(mut fp: *mut FILE, mut s: *const libc::c_char, mut delim: libc::c_int) {
+  while *s != 0 {
+    let mut c: libc::c_int = *s as libc::c_uchar as libc::c_int;
+    let mut e: libc::c_int = 0;
+    match c {
+      92 => e = '\\' as i32,
+      10 => e = 'n' as i32,
+      9 => e = 't' as i32,
+      13 => e = 'r' as i32,
+      7 => e = 'a' as i32,
+      11 => e = 'v' as i32,
+      12 => e = 'f' as i32,
+      _ => {
+        if c == delim {
+          e = c
+        }
+      }
+    }
+    if e != 0 {
+      putc_unlocked('\\' as i32, fp);
+      putc_unlocked(e, fp);
+    } else if c >= 0x20i32 && c < 0x7fi32 {
+      putc_unlocked(c, fp);
+    } else {
+      /* GNU prints the sign-extended char */
+      fprintf(
+        fp,
+        b"\\o%03o\x00" as *const u8 as *const libc::c_char,
+        *s as libc::c_schar as libc::c_int as libc::c_uint,
+      );
+    }
+    s = s.offset(1)
+  }
+}
+/* "PATTERN: ..." / "HOLD:    ..." */
+unsafe fn debug_print(mut label: *const libc::c_char, mut s: *const libc::c_char) {
+  fputs_unlocked(label, stdout);
+  if !s.is_null() {
+    debug_escape(stdout, s, 0);
+  }
+  putc_unlocked('\n' as i32, stdout);
+}
+/* Regex source as GNU shows it: always /re/, with \n, \t and \delim resolved */
+unsafe fn debug_regex(
+  mut fp: *mut FILE,
+  mut re: *const libc::c_char,
+  mut len: libc::c_int,
+  mut delim: libc::c_char,
+) {
+  let mut s: *mut libc::c_char = copy_parsing_escapes(re, len);
+  parse_escapes(s, s, -1i32, delim, delim);
+  putc_unlocked('/' as i32, fp);
+  debug_escape(fp, s, '/' as i32);
+  putc_unlocked('/' as i32, fp);
+  free(s as *mut libc::c_void);
+}
+unsafe fn debug_address(
+  mut fp: *mut FILE,
+  mut addr: *const libc::c_char,
+  mut end: *const libc::c_char,
+) {
+  if *addr as libc::c_int == '/' as i32 || *addr as libc::c_int == '\\' as i32 {
+    let mut re: *const libc::c_char = addr.offset(1);
+    let mut delim: libc::c_char = '/' as i32 as libc::c_char;
+    if *addr as libc::c_int == '\\' as i32 {
+      delim = *re;
+      re = re.offset(1)
+    }
+    /* end is just past the closing delimiter */
+    debug_regex(fp, re, (end.offset_from(re) - 1) as libc::c_int, delim);
+  } else {
+    fprintf(
+      fp,
+      b"%.*s\x00" as *const u8 as *const libc::c_char,
+      end.offset_from(addr) as libc::c_int,
+      addr,
+    );
+  }
+}
+/* Text of a parsed command for "SED PROGRAM:" and "COMMAND:" lines.
+ * addr[] holds the start and end of both addresses in the script
+ * (addr[2] is NULL if there is no second one), args..args_end
+ * is what parse_cmd_args() consumed. */
+unsafe fn debug_cmd_text(
+  mut sed_cmd: *mut sed_cmd_t,
+  mut addr: *const *const libc::c_char,
+  mut args: *const libc::c_char,
+  mut args_end: *const libc::c_char,
+) -> *mut libc::c_char {
+  let mut text: *mut libc::c_char = std::ptr::null_mut();
+  let mut size: size_t = 0;
+  let mut fp: *mut FILE = open_memstream(&mut text, &mut size);
+  let mut cmd: libc::c_int = (*sed_cmd).cmd as libc::c_int;
+  let mut string: *mut libc::c_char = (*sed_cmd).string;
+  let mut i: libc::c_int = 0;
+  if fp.is_null() {
+    crate::libbb::xfuncs_printf::bb_die_memory_exhausted();
+  }
+  let ref mut depth = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).block_depth;
+  if cmd == '}' as i32 && *depth > 0 {
+    *depth -= 1
+  }
+  while i < *depth {
+    fputs_unlocked(b"  \x00" as *const u8 as *const libc::c_char, fp);
+    i += 1
+  }
+  if cmd == '{' as i32 {
+    *depth += 1
+  }
+  if *addr.offset(0) != *addr.offset(1) {
+    debug_address(fp, *addr.offset(0), *addr.offset(1));
+    if !(*addr.offset(2)).is_null() {
+      putc_unlocked(',' as i32, fp);
+      debug_address(fp, *addr.offset(2), *addr.offset(3));
+    }
+  }
+  if (*sed_cmd).invert() != 0 {
+    putc_unlocked('!' as i32, fp);
+  }
+  if *addr.offset(0) != *addr.offset(1) {
+    putc_unlocked(' ' as i32, fp);
+  }
+  putc_unlocked(cmd, fp);
+  match cmd as u8 {
+    b'a' | b'i' | b'c' => {
+      putc_unlocked('\\' as i32, fp);
+      if !string.is_null() {
+        fputs_unlocked(string, fp);
+      }
+      putc_unlocked('\n' as i32, fp);
+    }
+    b'b' | b't' | b'T' | b'r' => {
+      if !string.is_null() {
+        fprintf(fp, b" %s\x00" as *const u8 as *const libc::c_char, string);
+      }
+    }
+    b':' | b'w' => {
+      if !string.is_null() {
+        fputs_unlocked(string, fp);
+      }
+    }
+    b'e' => {
+      putc_unlocked(' ' as i32, fp);
+      if !string.is_null() {
+        fprintf(fp, b"%s\n\x00" as *const u8 as *const libc::c_char, string);
+      }
+    }
+    b'y' => {
+      /* GNU prints the translation table, ordered by source char */
+      let mut map: [libc::c_uchar; 256] = [0; 256];
+      let mut seen: [libc::c_uchar; 256] = [0; 256];
+      let mut p: *const libc::c_uchar = string as *const libc::c_uchar;
+      i = 0;
+      while i < 256i32 {
+        map[i as usize] = i as libc::c_uchar;
+        i += 1
+      }
+      while *p != 0 {
+        if seen[*p as usize] == 0 {
+          seen[*p as usize] = 1;
+          map[*p as usize] = *p.offset(1)
+        }
+        p = p.offset(2)
+      }
+      putc_unlocked('/' as i32, fp);
+      i = 1;
+      while i < 256i32 {
+        if map[i as usize] as libc::c_int != i {
+          putc_unlocked(i, fp);
+        }
+        i += 1
+      }
+      putc_unlocked('/' as i32, fp);
+      i = 1;
+      while i < 256i32 {
+        if map[i as usize] as libc::c_int != i {
+          putc_unlocked(map[i as usize] as libc::c_int, fp);
+        }
+        i += 1
+      }
+      putc_unlocked('/' as i32, fp);
+    }
+    b's' => {
+      let mut delim: libc::c_char = *args;
+      let mut p: *const libc::c_char = args.offset(1);
+      let mut n: libc::c_int = index_of_next_unescaped_regexp_delim(delim as libc::c_int, p);
+      let mut repl: *mut libc::c_char = crate::libbb::xfuncs_printf::xstrdup(string);
+      let mut icase: smallint = 0;
+      let mut global: smallint = 0;
+      let mut print: smallint = 0;
+      let mut num: libc::c_long = 0;
+      let mut wfile: *const libc::c_char = std::ptr::null();
+      debug_regex(fp, p, n, delim);
+      parse_escapes(repl, repl, -1i32, delim, delim);
+      fprintf(fp, b"%s/\x00" as *const u8 as *const libc::c_char, repl);
+      free(repl as *mut libc::c_void);
+      p = p.offset((n + 1i32) as isize);
+      n = index_of_next_unescaped_regexp_delim(-(delim as libc::c_int), p);
+      p = p.offset((n + 1i32) as isize);
+      while p < args_end && wfile.is_null() {
+        match *p as u8 {
+          b'i' | b'I' => icase = 1,
+          b'g' => global = 1,
+          b'p' => print = 1,
+          b'0'..=b'9' => {
+            num = strtol(
+              p,
+              &mut p as *mut *const libc::c_char as *mut *mut libc::c_char,
+              10i32,
+            );
+            continue;
+          }
+          b'w' => wfile = skip_whitespace(p.offset(1)),
+          b'#' => break,
+          _ => {}
+        }
+        p = p.offset(1)
+      }
+      /* GNU order of flags */
+      if icase != 0 {
+        putc_unlocked('i' as i32, fp);
+      }
+      if global != 0 {
+        putc_unlocked('g' as i32, fp);
+      }
+      if print != 0 {
+        putc_unlocked('p' as i32, fp);
+      }
+      if num != 0 {
+        fprintf(fp, b"%ld\x00" as *const u8 as *const libc::c_char, num);
+      }
+      if !wfile.is_null() {
+        fprintf(
+          fp,
+          b"w%.*s\x00" as *const u8 as *const libc::c_char,
+          args_end.offset_from(wfile) as libc::c_int,
+          wfile,
+        );
+      }
+    }
+    _ => {}
+  }
+  fclose(fp);
+  return text;
+}
 /* Parse address+command sets, skipping comment lines. */
 unsafe fn add_cmd(mut cmdstr: *const libc::c_char) {
   let mut sed_cmd: *mut sed_cmd_t = std::ptr::null_mut();
@@ -801,15 +1097,18 @@ unsafe fn add_cmd(mut cmdstr: *const libc::c_char) {
         crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<sed_cmd_t>() as libc::c_ulong)
           as *mut sed_cmd_t;
       /* first part (if present) is an address: either a '$', a number or a /regex/ */
+      let mut addr: [*const libc::c_char; 4] = [cmdstr, cmdstr, std::ptr::null(), std::ptr::null()];
       cmdstr =
         cmdstr.offset(
           get_address(cmdstr, &mut (*sed_cmd).beg_line, &mut (*sed_cmd).beg_match) as isize,
         );
+      addr[1] = cmdstr;
       (*sed_cmd).beg_line_orig = (*sed_cmd).beg_line;
       /* second part (if present) will begin with a comma */
       if *cmdstr as libc::c_int == ',' as i32 {
         let mut idx: libc::c_int = 0;
         cmdstr = cmdstr.offset(1);
+        addr[2] = cmdstr;
         if *cmdstr as libc::c_int == '+' as i32
           && (*cmdstr.offset(1) as libc::c_int - '0' as i32) as libc::c_uchar as libc::c_int <= 9i32
         {
@@ -838,7 +1137,8 @@ unsafe fn add_cmd(mut cmdstr: *const libc::c_char) {
             b"no address after comma\x00" as *const u8 as *const libc::c_char,
           );
         }
-        (*sed_cmd).end_line_orig = (*sed_cmd).end_line
+        (*sed_cmd).end_line_orig = (*sed_cmd).end_line;
+        addr[3] = cmdstr
       }
       /* skip whitespace before the command */
       cmdstr = skip_whitespace(cmdstr);
@@ -858,7 +1158,16 @@ unsafe fn add_cmd(mut cmdstr: *const libc::c_char) {
       let fresh7 = cmdstr;
       cmdstr = cmdstr.offset(1);
       (*sed_cmd).cmd = *fresh7;
+      let mut args: *const libc::c_char = cmdstr;
       cmdstr = parse_cmd_args(sed_cmd, cmdstr);
+      if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0 {
+        (*sed_cmd).debug_text = debug_cmd_text(
+          sed_cmd,
+          addr.as_mut_ptr() as *const *const libc::c_char,
+          args,
+          cmdstr,
+        )
+      }
       /* cmdstr now points past args.
        * GNU sed requires a separator, if there are more commands,
        * else it complains "char N: extra characters after command".
@@ -993,6 +1302,9 @@ unsafe fn do_subst_command(
   {
     return 0;
   }
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0 {
+    debug_registers(line);
+  }
   /* Initialize temporary output buffer. */
   let ref mut fresh17 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
     .pipeline
@@ -1137,11 +1449,11 @@ unsafe fn puts_maybe_newline(
   mut last_gets_char: libc::c_char,
 ) {
   let mut lpc: libc::c_char = *last_puts_char;
-  /* Need to insert a '\n' between two files because first file's
-   * last line wasn't terminated? */
+  /* Need to insert a '\n' (or '\0' with -z) between two files
+   * because first file's last line wasn't terminated? */
   if lpc as libc::c_int != '\n' as i32 && lpc as libc::c_int != '\u{0}' as i32 {
-    putc_unlocked('\n' as i32, file);
-    lpc = '\n' as i32 as libc::c_char
+    lpc = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
+    putc_unlocked(lpc as libc::c_int, file);
   }
   fputs_unlocked(s, file);
   /* 'x' - just something which is not '\n', '\0' or NO_EOL_CHAR */
@@ -1157,6 +1469,9 @@ unsafe fn puts_maybe_newline(
     putc_unlocked(last_gets_char as libc::c_int, file);
     lpc = last_gets_char
   }
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).unbuffered != 0 {
+    fflush(file);
+  }
   if ferror_unlocked(file) != 0 {
     /* had trailing '\n' or '\0'? */
     xfunc_error_retval = 4i32 as u8; /* It's what gnu sed exits with... */
@@ -1236,6 +1551,10 @@ unsafe fn get_next_line(
         _ => {
           let ref mut fresh21 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).current_fp;
           *fresh21 = fp;
+          /* -u: don't read ahead of the lines we need */
+          if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).unbuffered != 0 {
+            setvbuf(fp, std::ptr::null_mut(), libc::_IONBF, 0 as size_t);
+          }
           current_block_17 = 3512920355445576850;
         }
       }
@@ -1247,23 +1566,38 @@ unsafe fn get_next_line(
         /* Read line up to a newline or NUL byte, inclusive,
          * return malloc'ed char[]. length of the chunk read
          * is stored in len. NULL if EOF/error */
-        temp = crate::libbb::get_line_from_file::bb_get_chunk_from_file(fp, &mut len);
-        if !temp.is_null() {
-          /* len > 0 here, it's ok to do temp[len-1] */
-          let mut c: libc::c_char = *temp.offset(len.wrapping_sub(1i32 as libc::c_ulong) as isize);
-          if c as libc::c_int == '\n' as i32 || c as libc::c_int == '\u{0}' as i32 {
-            *temp.offset(len.wrapping_sub(1i32 as libc::c_ulong) as isize) =
-              '\u{0}' as i32 as libc::c_char;
-            gc = c;
-            if c as libc::c_int == '\u{0}' as i32 {
-              let mut ch: libc::c_int = getc_unlocked(fp);
-              if ch != -1i32 {
-                ungetc(ch, fp);
-              } else {
-                gc = LAST_IS_NUL as libc::c_int as libc::c_char
+        if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim == 0 {
+          /* -z: only NUL ends a line, '\n' is ordinary data */
+          temp =
+            crate::libbb::get_line_from_file::bb_get_chunk_from_file(fp, std::ptr::null_mut());
+          if !temp.is_null() && feof_unlocked(fp) == 0 {
+            gc = '\u{0}' as i32 as libc::c_char
+          }
+        } else {
+          temp = crate::libbb::get_line_from_file::bb_get_chunk_from_file(fp, &mut len);
+          if !temp.is_null() {
+            /* len > 0 here, it's ok to do temp[len-1] */
+            let mut c: libc::c_char =
+              *temp.offset(len.wrapping_sub(1i32 as libc::c_ulong) as isize);
+            if c as libc::c_int == '\n' as i32 || c as libc::c_int == '\u{0}' as i32 {
+              *temp.offset(len.wrapping_sub(1i32 as libc::c_ulong) as isize) =
+                '\u{0}' as i32 as libc::c_char;
+              gc = c;
+              if c as libc::c_int == '\u{0}' as i32 {
+                let mut ch: libc::c_int = getc_unlocked(fp);
+                if ch != -1i32 {
+                  ungetc(ch, fp);
+                } else {
+                  gc = LAST_IS_NUL as libc::c_int as libc::c_char
+                }
               }
             }
           }
+        }
+        if !temp.is_null() {
+          /* --debug wants to know where the line came from */
+          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).line_file =
+            (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).current_input_file;
           break;
         /* NB: I had the idea of peeking next file(s) and returning
          * NO_EOL_CHAR only if it is the *last* non-empty
@@ -1305,42 +1639,166 @@ unsafe fn beg_match(
   }
   return retval;
 }
+unsafe fn debug_command(sed_cmd: *mut sed_cmd_t) {
+  fprintf(
+    stdout,
+    b"COMMAND: %s\n\x00" as *const u8 as *const libc::c_char,
+    (*sed_cmd).debug_text,
+  );
+}
+/* Show what an executed command changed */
+unsafe fn debug_after(sed_cmd: *mut sed_cmd_t, pattern_space: *const libc::c_char) {
+  let mut hold: *const libc::c_char =
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
+  match (*sed_cmd).cmd as u8 {
+    /* sic: GNU shows the hold space after 'g' too */
+    b'g' | b'h' | b'H' => debug_print(b"HOLD:    \x00" as *const u8 as *const libc::c_char, hold),
+    b'x' => {
+      debug_print(b"PATTERN: \x00" as *const u8 as *const libc::c_char, pattern_space);
+      debug_print(b"HOLD:    \x00" as *const u8 as *const libc::c_char, hold);
+    }
+    b'G' | b'n' | b'N' | b's' | b'y' => {
+      debug_print(b"PATTERN: \x00" as *const u8 as *const libc::c_char, pattern_space)
+    }
+    _ => {}
+  }
+}
+unsafe fn debug_registers(line: *const libc::c_char) {
+  let mut i: libc::c_int = 0;
+  puts(b"MATCHED REGEX REGISTERS\x00" as *const u8 as *const libc::c_char);
+  while i < 10i32 {
+    let mut m: regmatch_t =
+      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).regmatch[i as usize];
+    if m.rm_so != -1i32 {
+      fprintf(
+        stdout,
+        b"  regex[%d] = %d-%d \'%.*s\'\n\x00" as *const u8 as *const libc::c_char,
+        i,
+        m.rm_so,
+        m.rm_eo,
+        m.rm_eo - m.rm_so,
+        line.offset(m.rm_so as isize),
+      );
+    }
+    i += 1
+  }
+}
+/* Run cmd through the shell, return all of its output */
+unsafe fn read_cmd_output(cmd: *const libc::c_char, len: *mut size_t) -> *mut libc::c_char {
+  let mut fp: *mut FILE = std::ptr::null_mut();
+  let mut buf: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  crate::libbb::xfuncs_printf::fflush_all();
+  fp = popen(cmd, b"r\x00" as *const u8 as *const libc::c_char);
+  if fp.is_null() {
+    crate::libbb::perror_msg::bb_simple_perror_msg_and_die(
+      b"popen\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  *len = (2147483647i32 - 4095i32) as size_t;
+  buf = crate::libbb::read_printf::xmalloc_read(fileno(fp), len) as *mut libc::c_char;
+  pclose(fp);
+  if buf.is_null() {
+    *len = 0 as size_t;
+    buf = crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t) as *mut libc::c_char
+  }
+  return buf;
+}
+/* The line after the current one, for '$', n and N.
+ * With -u it is read only when needed: reading ahead would hold
+ * back the output of the current line until more input arrives.
+ * Appended text waits for the end of the cycle as usual */
+unsafe fn peek_next_line(
+  mut next_line: *mut *mut libc::c_char,
+  mut gets_char: *mut libc::c_char,
+  mut last_puts_char: *mut libc::c_char,
+) -> *mut libc::c_char {
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).next_line_pending != 0 {
+    let mut append: *mut llist_t = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).append_head;
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).append_head = std::ptr::null_mut();
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).next_line_pending = 0 as smallint;
+    *next_line = get_next_line(gets_char, last_puts_char);
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).append_head = append
+  }
+  return *next_line;
+}
+/* Read the line after the current one, or with -u note that it's due */
+unsafe fn read_ahead(
+  mut gets_char: *mut libc::c_char,
+  mut last_puts_char: *mut libc::c_char,
+) -> *mut libc::c_char {
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).unbuffered != 0 {
+    /* what get_next_line() would have done */
+    flush_append(last_puts_char);
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).next_line_pending = 1 as smallint;
+    return std::ptr::null_mut();
+  }
+  return get_next_line(gets_char, last_puts_char);
+}
 /* Process all the lines in all the files */
 unsafe fn process_files() {
   let mut current_block: u64;
   let mut pattern_space: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut next_line: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut linenum: libc::c_int = 0;
-  let mut last_puts_char: libc::c_char = '\n' as i32 as libc::c_char;
+  /* carried over from the previous file with -s */
+  let mut last_puts_char: libc::c_char =
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).last_puts_char;
   let mut last_gets_char: libc::c_char = 0;
   let mut next_gets_char: libc::c_char = 0;
   let mut sed_cmd: *mut sed_cmd_t = std::ptr::null_mut();
   let mut substituted: libc::c_int = 0;
+  /* 'q' with -i or -s: don't go on to the next file */
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit != 0 {
+    return;
+  }
   /* Prime the pump */
-  next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).next_line_pending = 0 as smallint;
+  next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
   loop
   /* Go through every line in each file */
   {
     substituted = 0;
     /* Advance to next line.  Stop if out of lines. */
-    pattern_space = next_line;
+    pattern_space = peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char);
     if pattern_space.is_null() {
+      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).last_puts_char = last_puts_char;
       return;
     }
     last_gets_char = next_gets_char;
+    let mut line_file: libc::c_int = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).line_file;
     /* Read one line in advance so we can act on the last line,
      * the '$' address */
-    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
     linenum += 1;
+    if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0 {
+      let mut name: *const libc::c_char = *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+        .input_file_list
+        .offset(line_file as isize);
+      if name == bb_msg_standard_input.as_ptr() {
+        name = b"STDIN\x00" as *const u8 as *const libc::c_char
+      }
+      fprintf(
+        stdout,
+        b"INPUT:   \'%s\' line %d\n\x00" as *const u8 as *const libc::c_char,
+        name,
+        linenum,
+      );
+    }
     's_54: loop
     /* For every line, go through all the commands */
     {
+      if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0 {
+        debug_print(b"PATTERN: \x00" as *const u8 as *const libc::c_char, pattern_space);
+      }
       sed_cmd = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).sed_cmd_head; /* for each cmd */
       loop {
         if sed_cmd.is_null() {
           current_block = 8563197331115798083;
           break 's_54;
         }
+        if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0 {
+          debug_command(sed_cmd);
+        }
         let mut old_matched: libc::c_int = 0;
         let mut matched: libc::c_int = 0;
         old_matched = (*sed_cmd).in_match() as libc::c_int;
@@ -1363,7 +1821,9 @@ unsafe fn process_files() {
                 ((*sed_cmd).beg_line == linenum) as libc::c_int
               }) != 0
             || beg_match(sed_cmd, pattern_space) != 0
-            || (*sed_cmd).beg_line == -1i32 && next_line.is_null()) as libc::c_int
+            || (*sed_cmd).beg_line == -1i32
+              && peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                .is_null()) as libc::c_int
             as libc::c_uint,
         );
         /* Snapshot the value */
@@ -1381,7 +1841,8 @@ unsafe fn process_files() {
           (*sed_cmd).set_in_match(
             !((if (*sed_cmd).end_line != 0 {
               if (*sed_cmd).end_line == -1i32 {
-                next_line.is_null() as libc::c_int
+                peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char).is_null()
+                  as libc::c_int
               } else {
                 ((*sed_cmd).end_line <= linenum) as libc::c_int
               }
@@ -1425,6 +1886,10 @@ unsafe fn process_files() {
                 );
               }
             }
+            /* GNU still shows the closing brace */
+            if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0 {
+              debug_command(sed_cmd);
+            }
           }
         } else if !(if (*sed_cmd).invert() as libc::c_int != 0 {
           matched
@@ -1530,7 +1995,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -1593,7 +2058,9 @@ unsafe fn process_files() {
                    * unless of course the -n command switch has been
                    * specified. This choice is by design."
                    */
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     //goto discard_line;
                     current_block = 8563197331115798083;
                     break 's_54;
@@ -1610,7 +2077,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -1626,7 +2093,9 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     /* If no next line, jump to end of script and exit. */
                     current_block = 4142149688065477410;
                     break 's_54;
@@ -1634,7 +2103,7 @@ unsafe fn process_files() {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -1696,7 +2165,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -1723,6 +2192,7 @@ unsafe fn process_files() {
                 {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -1739,7 +2209,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -1815,7 +2285,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -1930,7 +2400,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -1978,7 +2448,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -1992,7 +2464,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -2006,14 +2478,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -2067,7 +2541,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -2088,6 +2562,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -2102,7 +2577,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -2161,7 +2636,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -2270,7 +2745,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -2318,7 +2793,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -2332,7 +2809,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -2346,14 +2823,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -2407,7 +2886,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -2428,6 +2907,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -2442,7 +2922,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -2501,7 +2981,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -2610,7 +3090,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -2658,7 +3138,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -2672,7 +3154,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -2686,14 +3168,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -2747,7 +3231,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -2768,6 +3252,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -2782,7 +3267,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -2841,7 +3326,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -2954,7 +3439,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -3002,7 +3487,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -3016,7 +3503,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -3030,14 +3517,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -3091,7 +3580,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -3112,6 +3601,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -3126,7 +3616,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -3185,7 +3675,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -3294,7 +3784,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -3342,7 +3832,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -3356,7 +3848,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -3370,14 +3862,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -3431,7 +3925,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -3452,6 +3946,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -3466,7 +3961,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -3525,7 +4020,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -3634,7 +4129,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -3682,7 +4177,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -3696,7 +4193,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -3710,14 +4207,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -3771,7 +4270,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -3792,6 +4291,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -3806,7 +4306,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -3865,7 +4365,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -3974,7 +4474,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -4022,7 +4522,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -4036,7 +4538,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -4050,14 +4552,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -4111,7 +4615,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -4132,6 +4636,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -4146,7 +4651,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -4205,7 +4710,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -4314,7 +4819,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -4362,7 +4867,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -4376,7 +4883,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -4390,14 +4897,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -4451,7 +4960,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -4472,6 +4981,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -4486,7 +4996,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -4545,7 +5055,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -4654,7 +5164,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -4702,7 +5212,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -4716,7 +5228,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -4730,14 +5242,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -4791,7 +5305,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -4812,6 +5326,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -4826,7 +5341,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -4885,7 +5400,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -4994,7 +5509,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -5042,7 +5557,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -5056,7 +5573,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -5070,14 +5587,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -5131,7 +5650,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -5152,6 +5671,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -5166,7 +5686,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -5225,7 +5745,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -5334,7 +5854,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -5382,7 +5902,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -5396,7 +5918,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -5410,14 +5932,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -5471,7 +5995,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -5492,6 +6016,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -5506,7 +6031,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -5565,7 +6090,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -5674,7 +6199,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -5722,7 +6247,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -5736,7 +6263,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -5750,14 +6277,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -5811,7 +6340,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -5832,6 +6361,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -5846,7 +6376,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -5905,7 +6435,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -6014,7 +6544,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -6062,7 +6592,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -6076,7 +6608,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -6090,14 +6622,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -6151,7 +6685,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -6172,6 +6706,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -6186,7 +6721,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -6245,7 +6780,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -6354,7 +6889,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -6402,7 +6937,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -6416,7 +6953,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -6430,14 +6967,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -6491,7 +7030,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -6512,6 +7051,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -6526,7 +7066,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -6585,7 +7125,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -6694,7 +7234,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -6742,7 +7282,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -6756,7 +7298,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -6770,14 +7312,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -6831,7 +7375,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -6852,6 +7396,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -6866,7 +7411,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -6925,7 +7470,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -7034,7 +7579,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -7082,7 +7627,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -7096,7 +7643,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -7110,14 +7657,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -7171,7 +7720,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -7192,6 +7741,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -7206,7 +7756,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -7265,7 +7815,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -7374,7 +7924,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -7422,7 +7972,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -7436,7 +7988,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -7450,14 +8002,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -7511,7 +8065,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -7532,6 +8086,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -7546,7 +8101,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -7605,7 +8160,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -7714,7 +8269,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -7762,7 +8317,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -7776,7 +8333,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -7790,14 +8347,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -7851,7 +8410,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -7872,6 +8431,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -7886,7 +8446,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -7945,7 +8505,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -8054,7 +8614,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -8102,7 +8662,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -8116,7 +8678,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -8130,14 +8692,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -8191,7 +8755,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -8212,6 +8776,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -8226,7 +8791,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -8285,7 +8850,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -8394,7 +8959,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -8442,7 +9007,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -8456,7 +9023,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -8470,14 +9037,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -8531,7 +9100,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -8552,6 +9121,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -8566,7 +9136,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -8625,7 +9195,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -8734,7 +9304,7 @@ unsafe fn process_files() {
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space,
                     );
                   }
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   current_block = 17965632435239708295;
                 }
                 14127364983570718321 => {
@@ -8782,7 +9352,9 @@ unsafe fn process_files() {
                 }
                 7297078374430259003 => {
                   let mut len: libc::c_int = 0;
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 8563197331115798083;
                     break 's_54;
                   } else {
@@ -8796,7 +9368,7 @@ unsafe fn process_files() {
                     *pattern_space.offset(len as isize) = '\n' as i32 as libc::c_char;
                     strcpy(pattern_space.offset(len as isize).offset(1), next_line);
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     linenum += 1
                   }
                   current_block = 17965632435239708295;
@@ -8810,14 +9382,16 @@ unsafe fn process_files() {
                       last_gets_char,
                     );
                   }
-                  if next_line.is_null() {
+                  if peek_next_line(&mut next_line, &mut next_gets_char, &mut last_puts_char)
+                    .is_null()
+                  {
                     current_block = 4142149688065477410;
                     break 's_54;
                   } else {
                     free(pattern_space as *mut libc::c_void);
                     pattern_space = next_line;
                     last_gets_char = next_gets_char;
-                    next_line = get_next_line(&mut next_gets_char, &mut last_puts_char);
+                    next_line = read_ahead(&mut next_gets_char, &mut last_puts_char);
                     substituted = 0;
                     linenum += 1
                   }
@@ -8871,7 +9445,7 @@ unsafe fn process_files() {
                       pattern_space,
                       (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                       &mut last_puts_char,
-                      '\n' as i32 as libc::c_char,
+                      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                     );
                     *tmp = '\n' as i32 as libc::c_char;
                     current_block = 17965632435239708295;
@@ -8892,6 +9466,7 @@ unsafe fn process_files() {
                 10499578865704240760 => {
                   free(next_line as *mut libc::c_void);
                   next_line = std::ptr::null_mut::<libc::c_char>();
+                  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit = 1i32 as smallint;
                   current_block = 8563197331115798083;
                   break 's_54;
                 }
@@ -8906,7 +9481,7 @@ unsafe fn process_files() {
                   } else {
                     crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t)
                   } as *mut libc::c_char;
-                  last_gets_char = '\n' as i32 as libc::c_char;
+                  last_gets_char = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
                   let ref mut fresh28 =
                     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).hold_space;
                   *fresh28 = tmp_1;
@@ -8965,7 +9540,7 @@ unsafe fn process_files() {
                         pattern_space,
                         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout,
                         &mut last_puts_char,
-                        '\n' as i32 as libc::c_char,
+                        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim,
                       );
                       current_block = 17965632435239708295;
                     }
@@ -8991,12 +9566,56 @@ unsafe fn process_files() {
                 }
               }
             }
+            101 => {
+              /* Execute command and output its result, or execute
+               * pattern space and replace it with the result (GNU extension) */
+              let mut len: size_t = 0;
+              let mut out: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+              let mut nonstdout: *mut FILE =
+                (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout;
+              let mut delim: libc::c_char =
+                (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim;
+              if (*sed_cmd).string.is_null() {
+                out = read_cmd_output(pattern_space, &mut len);
+                /* Drop one trailing line terminator */
+                if len != 0
+                  && *out.offset(len as isize - 1) as libc::c_int == delim as libc::c_int
+                {
+                  *out.offset(len as isize - 1) = '\u{0}' as i32 as libc::c_char
+                }
+                free(pattern_space as *mut libc::c_void);
+                pattern_space = out
+              } else {
+                if last_puts_char as libc::c_int != '\n' as i32
+                  && last_puts_char as libc::c_int != '\u{0}' as i32
+                {
+                  putc_unlocked(delim as libc::c_int, nonstdout);
+                }
+                out = read_cmd_output((*sed_cmd).string, &mut len);
+                fwrite(out as *const libc::c_void, 1, len as usize, nonstdout);
+                free(out as *mut libc::c_void);
+                /* Output is copied as is, without adding a newline */
+                last_puts_char = '\n' as i32 as libc::c_char
+              }
+            }
             _ => {}
           }
         }
+        if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0
+          && (matched != 0) != ((*sed_cmd).invert() != 0)
+        {
+          debug_after(sed_cmd, pattern_space);
+        }
         sed_cmd = (*sed_cmd).next
       }
     }
+    if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0
+      && (sed_cmd.is_null()
+        || (*sed_cmd).cmd as libc::c_int != 'q' as i32
+          && (*sed_cmd).cmd as libc::c_int != 'D' as i32)
+    {
+      puts(b"END-OF-CYCLE:\x00" as *const u8 as *const libc::c_char);
+    }
     match current_block {
       8563197331115798083 =>
       /*
@@ -9018,9 +9637,24 @@ unsafe fn process_files() {
     }
     /* Delete and such jump here. */
     flush_append(&mut last_puts_char);
+    if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).unbuffered != 0 {
+      fflush((*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout);
+    }
     free(pattern_space as *mut libc::c_void);
   }
 }
+/* -i and -s: each file starts afresh.
+ * Fix disabled range matches and mangled ",+N" ranges */
+unsafe fn reset_ranges() {
+  let mut sed_cmd: *mut sed_cmd_t =
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).sed_cmd_head;
+  while !sed_cmd.is_null() {
+    (*sed_cmd).beg_line = (*sed_cmd).beg_line_orig;
+    (*sed_cmd).end_line = (*sed_cmd).end_line_orig;
+    (*sed_cmd).set_in_match(0 as libc::c_uint);
+    sed_cmd = (*sed_cmd).next
+  }
+}
 /* It is possible to have a command line argument with embedded
  * newlines.  This counts as multiple command lines.
  * However, newline can be escaped: 's/e/z\<newline>z/'
@@ -9049,12 +9683,9 @@ pub unsafe fn sed_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char)
   let mut opt_e: *mut llist_t = std::ptr::null_mut();
   let mut opt_f: *mut llist_t = std::ptr::null_mut();
   let mut opt_i: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
-  static mut sed_longopts: [libc::c_char; 67] = [
-    105, 110, 45, 112, 108, 97, 99, 101, 0, 2, 105, 114, 101, 103, 101, 120, 112, 45, 101, 120,
-    116, 101, 110, 100, 101, 100, 0, 0, 114, 113, 117, 105, 101, 116, 0, 0, 110, 115, 105, 108,
-    101, 110, 116, 0, 0, 110, 101, 120, 112, 114, 101, 115, 115, 105, 111, 110, 0, 1, 101, 102,
-    105, 108, 101, 0, 1, 102, 0,
-  ];
+  static mut sed_longopts: [u8; 129] = *b"in-place\x00\x02iregexp-extended\x00\x00rquiet\x00\x00n\
+    silent\x00\x00nexpression\x00\x01efile\x00\x01fseparate\x00\x00snull-data\x00\x00z\
+    zero-terminated\x00\x00zunbuffered\x00\x00udebug\x00\x00\xff\x00";
   let ref mut fresh29 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).sed_cmd_tail;
   *fresh29 = &mut (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).sed_cmd_head;
   /* destroy command strings on exit */
@@ -9079,8 +9710,8 @@ pub unsafe fn sed_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char)
    */
   opt = crate::libbb::getopt32::getopt32long(
     argv,
-    b"^i::rEne:*f:*\x00nn\x00" as *const u8 as *const libc::c_char,
-    sed_longopts.as_ptr(),
+    b"^i::rEne:*f:*szu\x00nn\x00" as *const u8 as *const libc::c_char,
+    sed_longopts.as_ptr() as *const libc::c_char,
     &mut opt_i as *mut *mut libc::c_char,
     &mut opt_e as *mut *mut llist_t,
     &mut opt_f as *mut *mut llist_t,
@@ -9095,6 +9726,16 @@ pub unsafe fn sed_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char)
   if opt & (2i32 | 4i32) as libc::c_uint != 0 {
     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).regex_type |= 1i32
   }
+  if opt & OPT_null_data as libc::c_int as libc::c_uint == 0 {
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).delim = '\n' as i32 as libc::c_char
+  }
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).last_puts_char = '\n' as i32 as libc::c_char;
+  if opt & OPT_unbuffered as libc::c_int as libc::c_uint != 0 {
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).unbuffered = 1i32 as smallint
+  }
+  if opt & OPT_debug as libc::c_int as libc::c_uint != 0 {
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug = 1i32 as smallint
+  }
   //if (opt & 8)
   //	G.be_quiet++; // -n (implemented with a counter instead)
   while !opt_e.is_null() {
@@ -9129,6 +9770,19 @@ pub unsafe fn sed_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char)
   }
   /* Flush any unfinished commands. */
   add_cmd(b"\x00" as *const u8 as *const libc::c_char);
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).debug != 0 {
+    let mut sed_cmd: *mut sed_cmd_t =
+      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).sed_cmd_head;
+    puts(b"SED PROGRAM:\x00" as *const u8 as *const libc::c_char);
+    while !sed_cmd.is_null() {
+      fprintf(
+        stdout,
+        b"  %s\n\x00" as *const u8 as *const libc::c_char,
+        (*sed_cmd).debug_text,
+      );
+      sed_cmd = (*sed_cmd).next
+    }
+  }
   /* By default, we write to stdout */
   let ref mut fresh31 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout;
   *fresh31 = stdout;
@@ -9150,12 +9804,17 @@ pub unsafe fn sed_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char)
   } else {
     let mut statbuf: stat = std::mem::zeroed();
     let mut nonstdoutfd: libc::c_int = 0;
-    let mut sed_cmd: *mut sed_cmd_t = std::ptr::null_mut();
     loop {
       if opt & OPT_in_place as libc::c_int as libc::c_uint == 0 {
         if *(*argv).offset(0) as libc::c_int == '-' as i32 && *(*argv).offset(1) == 0 {
           *argv = bb_msg_standard_input.as_ptr() as *mut libc::c_char;
           process_files();
+        } else if opt & OPT_separate as libc::c_int as libc::c_uint != 0 {
+          /* -s: like -i, but without redirecting output */
+          process_files();
+        }
+        if opt & OPT_separate as libc::c_int as libc::c_uint != 0 {
+          reset_ranges();
         }
       } else if stat(*argv, &mut statbuf) != 0 {
         crate::libbb::perror_msg::bb_simple_perror_msg(*argv);
@@ -9184,6 +9843,8 @@ pub unsafe fn sed_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char)
         fclose((*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout);
         let ref mut fresh38 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).nonstdout;
         *fresh38 = stdout;
+        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).last_puts_char =
+          '\n' as i32 as libc::c_char;
         if !opt_i.is_null() {
           let mut backupname: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
             b"%s%s\x00" as *const u8 as *const libc::c_char,
@@ -9201,21 +9862,14 @@ pub unsafe fn sed_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char)
         free((*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).outname as *mut libc::c_void);
         let ref mut fresh39 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).outname;
         *fresh39 = std::ptr::null_mut::<libc::c_char>();
-        /* Fix disabled range matches and mangled ",+N" ranges */
-        sed_cmd = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).sed_cmd_head;
-        while !sed_cmd.is_null() {
-          (*sed_cmd).beg_line = (*sed_cmd).beg_line_orig;
-          (*sed_cmd).end_line = (*sed_cmd).end_line_orig;
-          sed_cmd = (*sed_cmd).next
-        }
+        reset_ranges();
       }
       argv = argv.offset(1);
-      if (*argv).is_null() {
+      if (*argv).is_null() || (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).quit != 0 {
         break;
       }
       statbuf = std::mem::zeroed();
       nonstdoutfd = 0;
-      sed_cmd = std::ptr::null_mut();
       let ref mut fresh34 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).last_input_file;
       *fresh34 += 1
       /* Here, to handle "sed 'cmds' nonexistent_file" case we did:
@@ -22,7 +22,9 @@ use libc::sscanf;
 use libc::stat;
 use libc::strcasecmp;
 use libc::strchr;
+use libc::strcmp;
 use libc::strcpy;
+use libc::strstr;
 use libc::system;
 use libc::termios;
 use libc::FILE;
@@ -180,7 +182,7 @@ pub struct globals {
   pub last_status_cksum: libc::c_int,
   pub current_filename: *mut libc::c_char,
   pub screenbegin: *mut libc::c_char,
-  pub screen: *mut libc::c_char,
+  pub screen: *mut libc::c_int,
   pub screensize: libc::c_int,
   pub tabstop: libc::c_int,
   pub last_forward_char: libc::c_int,
@@ -204,12 +206,30 @@ pub struct globals {
   pub status_buffer: [libc::c_char; 200],
   pub last_modifying_cmd: [libc::c_char; 128],
   pub get_input_line__buf: [libc::c_char; 128],
-  pub scr_out_buf: [libc::c_char; 4160],
+  pub scr_out_buf: [libc::c_int; 4160],
+  // a char and the combining chars shown with it, in UTF-8.
+  // A cell with MB_CLUSTER set holds an index here
+  pub mb_clusters: *mut *mut libc::c_char,
+  pub mb_nclusters: libc::c_int,
   pub undo_queue_state: libc::c_char,
   pub undo_q: libc::c_int,
   pub undo_queue_spos: *mut libc::c_char,
   pub undo_queue: [libc::c_char; 256],
   pub undo_stack_tail: *mut undo_object,
+  pub bufs: *mut vi_buf,
+  pub curbuf: *mut vi_buf,
+  pub buf_last_num: libc::c_int,
+  pub win_root: *mut vi_win,
+  pub curwin: *mut vi_win,
+  pub win_top: libc::c_int,
+  pub win_left: libc::c_int,
+  pub term_rows: libc::c_uint,
+  pub term_cols: libc::c_uint,
+  pub syntax_on: smallint,
+  pub syntax: libc::c_int,
+  pub syn_state: libc::c_int,
+  pub syn_attr: *mut u8,
+  pub syn_attr_size: libc::c_int,
 }
 
 #[repr(C)]
@@ -221,6 +241,52 @@ pub struct undo_object {
   pub u_type: u8,
   pub undo_text: [libc::c_char; 1],
 }
+
+// An edit buffer. The current one lives in the globals,
+// the others are parked here.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct vi_buf {
+  pub next: *mut vi_buf,
+  pub num: libc::c_int,
+  pub text: *mut libc::c_char,
+  pub end: *mut libc::c_char,
+  pub text_size: libc::c_int,
+  pub dot: libc::c_int,
+  pub readonly_mode: smallint,
+  pub modified_count: libc::c_int,
+  pub last_modified_count: libc::c_int,
+  pub format_edit_status__tot: libc::c_int,
+  pub current_filename: *mut libc::c_char,
+  pub mark: [*mut libc::c_char; 28],
+  pub context_start: *mut libc::c_char,
+  pub context_end: *mut libc::c_char,
+  pub undo_stack_tail: *mut undo_object,
+  pub syntax: libc::c_int,
+}
+
+// A window: either a leaf showing a buffer, or a split in two.
+// Text positions are kept as offsets, other windows may have
+// edited the buffer meanwhile.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct vi_win {
+  pub parent: *mut vi_win,
+  pub child: [*mut vi_win; 2],
+  pub vertical: smallint,
+  pub buf: *mut vi_buf,
+  pub top: libc::c_int,
+  pub left: libc::c_int,
+  pub height: libc::c_int,
+  pub width: libc::c_int,
+  pub dot: libc::c_int,
+  pub screenbegin: libc::c_int,
+  pub offset: libc::c_int,
+  pub old_offset: libc::c_int,
+  pub screen: *mut libc::c_int,
+  pub screensize: libc::c_int,
+  pub status_cksum: libc::c_int,
+}
 pub type C2RustUnnamed_0 = libc::c_uint;
 pub const MAX_SCR_ROWS: C2RustUnnamed_0 = 4096;
 pub const MAX_SCR_COLS: C2RustUnnamed_0 = 4096;
@@ -271,7 +337,7 @@ static mut modifying_cmds: [libc::c_char; 22] = [
   97, 65, 99, 67, 100, 68, 105, 73, 74, 111, 79, 112, 80, 114, 82, 115, 120, 88, 60, 62, 126, 0,
 ];
 unsafe extern "C" fn show_help() {
-  puts(b"These features are available:\n\tPattern searches with / and ?\n\tLast command repeat with .\n\tLine marking with \'x\n\tNamed buffers with \"x\n\tSome colon mode commands with :\n\tSettable options with \":set\"\n\tSignal catching- ^C\n\tJob suspend and resume with ^Z\n\tAdapt to window re-sizes\x00"
+  puts(b"These features are available:\n\tPattern searches with / and ?\n\tLast command repeat with .\n\tLine marking with \'x\n\tNamed buffers with \"x\n\tSome colon mode commands with :\n\tSettable options with \":set\"\n\tSignal catching- ^C\n\tJob suspend and resume with ^Z\n\tAdapt to window re-sizes\n\tBuffers and split windows with :ls, :b, :sp, :vs, ^W\x00"
              as *const u8 as *const libc::c_char);
 }
 unsafe extern "C" fn write1(mut out: *const libc::c_char) {
@@ -280,14 +346,14 @@ unsafe extern "C" fn write1(mut out: *const libc::c_char) {
 unsafe extern "C" fn query_screen_dimensions() -> libc::c_int {
   let mut err: libc::c_int = crate::libbb::xfuncs::get_terminal_width_height(
     0,
-    &mut (*ptr_to_globals).columns,
-    &mut (*ptr_to_globals).rows,
+    &mut (*ptr_to_globals).term_cols,
+    &mut (*ptr_to_globals).term_rows,
   );
-  if (*ptr_to_globals).rows > MAX_SCR_ROWS as libc::c_int as libc::c_uint {
-    (*ptr_to_globals).rows = MAX_SCR_ROWS as libc::c_int as libc::c_uint
+  if (*ptr_to_globals).term_rows > MAX_SCR_ROWS as libc::c_int as libc::c_uint {
+    (*ptr_to_globals).term_rows = MAX_SCR_ROWS as libc::c_int as libc::c_uint
   }
-  if (*ptr_to_globals).columns > MAX_SCR_COLS as libc::c_int as libc::c_uint {
-    (*ptr_to_globals).columns = MAX_SCR_COLS as libc::c_int as libc::c_uint
+  if (*ptr_to_globals).term_cols > MAX_SCR_COLS as libc::c_int as libc::c_uint {
+    (*ptr_to_globals).term_cols = MAX_SCR_COLS as libc::c_int as libc::c_uint
   }
   return err;
 }
@@ -330,8 +396,8 @@ unsafe extern "C" fn cookmode() {
 //  22,0    ...     22,79
 //  23,0    ...     23,79   <- status line
 //----- Move the cursor to row x col (count from 0, not 1) -------
+// position is relative to the current window
 unsafe extern "C" fn place_cursor(mut row: libc::c_int, mut col: libc::c_int) {
-  let mut cm1: [libc::c_char; 33] = [0; 33];
   if row < 0 {
     row = 0
   }
@@ -344,6 +410,14 @@ unsafe extern "C" fn place_cursor(mut row: libc::c_int, mut col: libc::c_int) {
   if col as libc::c_uint >= (*ptr_to_globals).columns {
     col = (*ptr_to_globals).columns.wrapping_sub(1i32 as libc::c_uint) as libc::c_int
   }
+  place_cursor_abs(
+    row + (*ptr_to_globals).win_top,
+    col + (*ptr_to_globals).win_left,
+  );
+}
+// position is relative to the terminal
+unsafe extern "C" fn place_cursor_abs(mut row: libc::c_int, mut col: libc::c_int) {
+  let mut cm1: [libc::c_char; 33] = [0; 33];
   sprintf(
     cm1.as_mut_ptr(),
     b"\x1b[%u;%uH\x00" as *const u8 as *const libc::c_char,
@@ -357,8 +431,8 @@ unsafe extern "C" fn clear_to_eol() {
   write1(b"\x1b[K\x00" as *const u8 as *const libc::c_char);
 }
 unsafe extern "C" fn go_bottom_and_clear_to_eol() {
-  place_cursor(
-    (*ptr_to_globals).rows.wrapping_sub(1i32 as libc::c_uint) as libc::c_int,
+  place_cursor_abs(
+    (*ptr_to_globals).term_rows.wrapping_sub(1i32 as libc::c_uint) as libc::c_int,
     0,
   );
   clear_to_eol();
@@ -484,20 +558,340 @@ unsafe extern "C" fn find_line(mut li: libc::c_int) -> *mut libc::c_char
 unsafe extern "C" fn next_tabstop(mut col: libc::c_int) -> libc::c_int {
   return col + ((*ptr_to_globals).tabstop - 1i32 - col % (*ptr_to_globals).tabstop);
 }
+//----- UTF-8 characters in text[] -----------------------------
+// Bytes which do not form a valid UTF-8 sequence are characters
+// of their own, shown as '.'
+// Returns the character at p (-1 if p is not at a valid sequence)
+// and its length in bytes in *len
+unsafe extern "C" fn mb_char(
+  mut p: *const libc::c_char,
+  mut len: *mut libc::c_int,
+) -> libc::c_int {
+  return mb_decode(p, (*ptr_to_globals).end, len);
+}
+// Same, for a char which ends before lim
+unsafe extern "C" fn mb_decode(
+  mut p: *const libc::c_char,
+  mut lim: *const libc::c_char,
+  mut len: *mut libc::c_int,
+) -> libc::c_int {
+  let mut c: libc::c_int = *p as libc::c_uchar as libc::c_int;
+  let mut wc: libc::c_int = 0;
+  let mut n: libc::c_int = 0;
+  let mut i: libc::c_int = 0;
+  *len = 1i32;
+  if c < 0x80i32 {
+    return c;
+  }
+  // 0xf5..0xf7 would start a char past U+10FFFF
+  if c > 0xf4i32 {
+    return -1i32;
+  }
+  n = mb_follow(c);
+  if n == 0 || p.offset(n as isize) >= lim {
+    return -1i32;
+  }
+  wc = c & (0x3fi32 >> n);
+  i = 1i32;
+  while i <= n {
+    c = *p.offset(i as isize) as libc::c_uchar as libc::c_int;
+    if c & 0xc0i32 != 0x80i32 {
+      return -1i32;
+    }
+    wc = wc << 6i32 | c & 0x3fi32;
+    i += 1
+  }
+  // overlong forms are not valid, nor is anything past U+10FFFF
+  if wc < [0x80i32, 0x800i32, 0x10000i32][(n - 1i32) as usize] || wc > 0x10ffffi32 {
+    return -1i32;
+  }
+  *len = n + 1i32;
+  return wc;
+}
+// How many continuation bytes follow lead byte c
+unsafe extern "C" fn mb_follow(mut c: libc::c_int) -> libc::c_int {
+  if c & 0xe0i32 == 0xc0i32 {
+    return 1i32;
+  }
+  if c & 0xf0i32 == 0xe0i32 {
+    return 2i32;
+  }
+  if c & 0xf8i32 == 0xf0i32 {
+    return 3i32;
+  }
+  return 0;
+}
+// Start of the character p points into
+unsafe extern "C" fn mb_start(mut p: *mut libc::c_char) -> *mut libc::c_char {
+  let mut q: *mut libc::c_char = p;
+  let mut len: libc::c_int = 0;
+  while q > (*ptr_to_globals).text
+    && p.offset_from(q) < 3
+    && *q as libc::c_int & 0xc0i32 == 0x80i32
+  {
+    q = q.offset(-1);
+    if mb_char(q, &mut len) >= 0 && q.offset(len as isize) > p {
+      return q;
+    }
+  }
+  return p;
+}
+// Start of the character before p
+unsafe extern "C" fn mb_prev(mut p: *mut libc::c_char) -> *mut libc::c_char {
+  if p <= (*ptr_to_globals).text {
+    return p;
+  }
+  return mb_start(p.offset(-1));
+}
+// Last byte of the character at p
+unsafe extern "C" fn mb_last(mut p: *mut libc::c_char) -> *mut libc::c_char {
+  let mut len: libc::c_int = 0;
+  mb_char(p, &mut len);
+  return p.offset((len - 1i32) as isize);
+}
+// Screen columns taken by non-ASCII character wc, -1 if not printable.
+// unicode.rs knows the widths up to its last supported char; past it,
+// East Asian wide and fullwidth chars take two columns, the rest one
+unsafe extern "C" fn mb_width(mut wc: libc::c_int) -> libc::c_int {
+  let mut w: libc::c_int = crate::libbb::unicode::bb_wcwidth(wc as libc::c_uint);
+  if w >= 0 {
+    return w;
+  }
+  if wc < 0xa0i32 {
+    return -1i32;
+  }
+  if wc >= 0x1100i32 && wc <= 0x115fi32
+    || wc >= 0x2e80i32 && wc <= 0xa4cfi32 && wc != 0x303fi32
+    || wc >= 0xac00i32 && wc <= 0xd7a3i32
+    || wc >= 0xf900i32 && wc <= 0xfaffi32
+    || wc >= 0xfe30i32 && wc <= 0xfe6fi32
+    || wc >= 0xff00i32 && wc <= 0xff60i32
+    || wc >= 0xffe0i32 && wc <= 0xffe6i32
+    || wc >= 0x1f300i32 && wc <= 0x1f64fi32
+    || wc >= 0x20000i32 && wc <= 0x3fffdi32
+  {
+    return 2i32;
+  }
+  return 1i32;
+}
+// Screen columns taken by the non-ASCII char at p, its length in *len
+unsafe extern "C" fn mb_cols(
+  mut p: *const libc::c_char,
+  mut len: *mut libc::c_int,
+) -> libc::c_int {
+  let mut wc: libc::c_int = mb_char(p, len);
+  let mut w: libc::c_int = if wc < 0 { 1i32 } else { mb_width(wc) };
+  return if w < 0 { 1i32 } else { w };
+}
+// Cell bit above the last unicode char: the cell is a cluster
+pub const MB_CLUSTER: libc::c_int = 0x800000;
+pub const MB_CLUSTER_MAX: libc::c_int = 1024;
+// Add the combining char at p (len bytes) to cell, which keeps its
+// highlight. Clusters are kept unique, so that cells can be compared
+unsafe extern "C" fn mb_cluster(
+  mut cell: libc::c_int,
+  mut p: *const libc::c_char,
+  mut len: libc::c_int,
+) -> libc::c_int {
+  let mut buf: [libc::c_char; 24] = [0; 24];
+  let mut n: libc::c_int = 0;
+  let mut i: libc::c_int = 0;
+  if cell & MB_CLUSTER != 0 {
+    strcpy(
+      buf.as_mut_ptr(),
+      *(*ptr_to_globals)
+        .mb_clusters
+        .offset((cell & (MB_CLUSTER - 1i32)) as isize),
+    );
+    n = strlen(buf.as_mut_ptr()) as libc::c_int
+  } else {
+    n = crate::libbb::unicode::bb_wcrtomb(
+      buf.as_mut_ptr(),
+      cell & 0xffffffi32,
+      std::ptr::null_mut(),
+    ) as libc::c_int
+  }
+  // too many marks: show the first ones only
+  if n + len >= 24i32 {
+    return cell;
+  }
+  memcpy(
+    buf.as_mut_ptr().offset(n as isize) as *mut libc::c_void,
+    p as *const libc::c_void,
+    len as libc::c_ulong,
+  );
+  buf[(n + len) as usize] = '\u{0}' as i32 as libc::c_char;
+  while i < (*ptr_to_globals).mb_nclusters {
+    if strcmp(*(*ptr_to_globals).mb_clusters.offset(i as isize), buf.as_mut_ptr()) == 0 {
+      return cell & !0xffffffi32 | MB_CLUSTER | i;
+    }
+    i += 1
+  }
+  if i == MB_CLUSTER_MAX {
+    return cell;
+  }
+  (*ptr_to_globals).mb_clusters = crate::libbb::xrealloc_vector::xrealloc_vector_helper(
+    (*ptr_to_globals).mb_clusters as *mut libc::c_void,
+    ((::std::mem::size_of::<*mut libc::c_char>() as libc::c_ulong) << 8i32)
+      .wrapping_add(4i32 as libc::c_ulong) as libc::c_uint,
+    i,
+  ) as *mut *mut libc::c_char;
+  let ref mut fresh0 = *(*ptr_to_globals).mb_clusters.offset(i as isize);
+  *fresh0 = crate::libbb::xfuncs_printf::xstrdup(buf.as_mut_ptr());
+  (*ptr_to_globals).mb_nclusters += 1;
+  return cell & !0xffffffi32 | MB_CLUSTER | i;
+}
+// Write screen cells to the terminal.
+// 0 is the right half of a wide char, it was written with the left one
+// bits 24+ of a cell hold its highlight class
+unsafe extern "C" fn put_cells(mut cell: *const libc::c_int, mut n: libc::c_int) {
+  let mut buf: [libc::c_char; 256] = [0; 256];
+  let mut len: libc::c_int = 0;
+  let mut hl: libc::c_int = 0;
+  while n > 0 {
+    if *cell != 0 {
+      if *cell >> 24i32 != hl {
+        let mut sgr: *const libc::c_char =
+          crate::libbb::syntax::syntax_sgr((*cell >> 24i32) as u8);
+        hl = *cell >> 24i32;
+        strcpy(buf.as_mut_ptr().offset(len as isize), sgr);
+        len += strlen(sgr) as libc::c_int
+      }
+      if *cell & MB_CLUSTER != 0 {
+        let mut cl: *const libc::c_char = *(*ptr_to_globals)
+          .mb_clusters
+          .offset((*cell & (MB_CLUSTER - 1i32)) as isize);
+        strcpy(buf.as_mut_ptr().offset(len as isize), cl);
+        len += strlen(cl) as libc::c_int
+      } else {
+        len += crate::libbb::unicode::bb_wcrtomb(
+          buf.as_mut_ptr().offset(len as isize),
+          *cell & 0xffffffi32,
+          std::ptr::null_mut(),
+        ) as libc::c_int
+      }
+    }
+    if len > 256i32 - 24i32 || n == 1i32 {
+      libc::fwrite(buf.as_mut_ptr() as *const libc::c_void, len as usize, 1, stdout);
+      len = 0
+    }
+    cell = cell.offset(1);
+    n -= 1
+  }
+  if hl != 0 {
+    write1(crate::libbb::syntax::syntax_sgr(crate::libbb::syntax::HL_NORMAL));
+  }
+}
+// highlight the text line at src, advancing syn_state
+unsafe extern "C" fn syn_line_attr(mut src: *mut libc::c_char) -> *mut u8 {
+  let mut t: *mut libc::c_char = memchr(
+    src as *const libc::c_void,
+    '\n' as i32,
+    (*ptr_to_globals).end.offset_from(src) as libc::c_ulong,
+  ) as *mut libc::c_char;
+  let mut len: libc::c_int = if t.is_null() {
+    (*ptr_to_globals).end.offset_from(src) as libc::c_int
+  } else {
+    t.offset_from(src) as libc::c_int + 1i32
+  };
+  if len > (*ptr_to_globals).syn_attr_size {
+    (*ptr_to_globals).syn_attr_size = len + 256i32;
+    (*ptr_to_globals).syn_attr = crate::libbb::xfuncs_printf::xrealloc(
+      (*ptr_to_globals).syn_attr as *mut libc::c_void,
+      (*ptr_to_globals).syn_attr_size as libc::c_ulong,
+    ) as *mut u8
+  }
+  crate::libbb::syntax::syntax_line(
+    (*ptr_to_globals).syntax,
+    src,
+    len,
+    crate::libbb::syntax::SYN_BOL | crate::libbb::syntax::SYN_EOL,
+    &mut (*ptr_to_globals).syn_state,
+    (*ptr_to_globals).syn_attr,
+  );
+  return (*ptr_to_globals).syn_attr;
+}
+// highlighter state at the start of line p.
+// Comments and strings can span lines, so this scans from the top.
+unsafe extern "C" fn syn_state_at(mut p: *mut libc::c_char) -> libc::c_int {
+  let mut st: libc::c_int = 0;
+  let mut q: *mut libc::c_char = (*ptr_to_globals).text;
+  if crate::libbb::syntax::syntax_multiline((*ptr_to_globals).syntax) == 0 {
+    return 0;
+  }
+  while q < p {
+    let mut t: *mut libc::c_char = memchr(
+      q as *const libc::c_void,
+      '\n' as i32,
+      p.offset_from(q) as libc::c_ulong,
+    ) as *mut libc::c_char;
+    let mut n: libc::c_int = if t.is_null() {
+      p.offset_from(q) as libc::c_int
+    } else {
+      t.offset_from(q) as libc::c_int + 1i32
+    };
+    crate::libbb::syntax::syntax_line(
+      (*ptr_to_globals).syntax,
+      q,
+      n,
+      crate::libbb::syntax::SYN_BOL | crate::libbb::syntax::SYN_EOL,
+      &mut st,
+      std::ptr::null_mut(),
+    );
+    q = q.offset(n as isize)
+  }
+  return st;
+}
+// language of the current buffer: by file name, else from
+// the first line or a modeline in the first or last 5 lines
+unsafe extern "C" fn syntax_detect() -> libc::c_int {
+  let mut lang: libc::c_int =
+    crate::libbb::syntax::syntax_for_file((*ptr_to_globals).current_filename);
+  let mut p: *mut libc::c_char = (*ptr_to_globals).text;
+  let mut n: libc::c_int = 0;
+  while lang == 0 && n < 5i32 && p < (*ptr_to_globals).end {
+    lang = crate::libbb::syntax::syntax_for_line(
+      p,
+      (*ptr_to_globals).end.offset_from(p) as libc::c_int,
+      (n == 0) as libc::c_int,
+    );
+    p = next_line(p);
+    if p >= (*ptr_to_globals).end.offset(-1) {
+      break;
+    }
+    n += 1
+  }
+  p = begin_line((*ptr_to_globals).end.offset(-1));
+  n = 0;
+  while lang == 0 && n < 5i32 && p > (*ptr_to_globals).text {
+    p = prev_line(p);
+    lang = crate::libbb::syntax::syntax_for_line(
+      p,
+      (*ptr_to_globals).end.offset_from(p) as libc::c_int,
+      0,
+    );
+    n += 1
+  }
+  return lang;
+}
 //----- Erase the Screen[] memory ------------------------------
 unsafe extern "C" fn screen_erase() {
-  memset(
-    (*ptr_to_globals).screen as *mut libc::c_void,
-    ' ' as i32,
-    (*ptr_to_globals).screensize as libc::c_ulong,
-  );
+  let mut i: libc::c_int = 0;
+  while i < (*ptr_to_globals).screensize {
+    *(*ptr_to_globals).screen.offset(i as isize) = ' ' as i32;
+    i += 1
+  }
   // clear new screen
 }
 unsafe extern "C" fn new_screen(mut ro: libc::c_int, mut co: libc::c_int) {
-  let mut s: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut s: *mut libc::c_int = std::ptr::null_mut::<libc::c_int>();
   free((*ptr_to_globals).screen as *mut libc::c_void);
   (*ptr_to_globals).screensize = ro * co + 8i32;
-  (*ptr_to_globals).screen = xmalloc((*ptr_to_globals).screensize as size_t) as *mut libc::c_char;
+  // one cell per column, holding a unicode char
+  (*ptr_to_globals).screen = xmalloc(
+    ((*ptr_to_globals).screensize as libc::c_ulong)
+      .wrapping_mul(::std::mem::size_of::<libc::c_int>() as libc::c_ulong),
+  ) as *mut libc::c_int;
   s = (*ptr_to_globals).screen;
   // initialize the new screen. assume this will be a empty file.
   screen_erase();
@@ -513,7 +907,7 @@ unsafe extern "C" fn new_screen(mut ro: libc::c_int, mut co: libc::c_int) {
       break;
     }
     s = s.offset(co as isize);
-    *s = '~' as i32 as libc::c_char
+    *s = '~' as i32
   }
 }
 //----- Synchronize the cursor to Dot --------------------------
@@ -603,6 +997,7 @@ unsafe extern "C" fn sync_cursor(
   // find out what col "d" is on
   co = 0;
   while tp < d {
+    let mut len: libc::c_int = 1i32;
     // drive "co" to correct column
     if *tp as libc::c_int == '\n' as i32 {
       break;
@@ -616,9 +1011,11 @@ unsafe extern "C" fn sync_cursor(
     } else if (*tp as libc::c_uchar as libc::c_int) < ' ' as i32 || *tp as libc::c_int == 0x7fi32 {
       co += 1
       // display as ^X, use 2 columns
+    } else if *tp as libc::c_uchar as libc::c_int >= 0x80i32 {
+      co += mb_cols(tp, &mut len) - 1i32
     }
     co += 1;
-    tp = tp.offset(1)
+    tp = tp.offset(len as isize)
   }
   // "co" is the column where "dot" is.
   // The screen has "columns" columns.
@@ -654,49 +1051,82 @@ unsafe extern "C" fn sync_cursor(
   *col = co;
 }
 //----- Format a text[] line into a buffer ---------------------
-unsafe extern "C" fn format_line(mut src: *mut libc::c_char) -> *mut libc::c_char
+// The buffer has one cell per screen column, holding a unicode char.
+// The right half of a wide char is a 0 cell
+unsafe extern "C" fn format_line(mut src: *mut libc::c_char) -> *mut libc::c_int
 /*, int li*/ {
-  let mut c: libc::c_uchar = 0; // [MAX_SCR_COLS + MAX_TABSTOP * 2]
+  let mut c: libc::c_int = 0; // [MAX_SCR_COLS + MAX_TABSTOP * 2]
   let mut co: libc::c_int = 0; // char in col 0 in non-existent lines is '~'
   let mut ofs: libc::c_int = (*ptr_to_globals).offset;
-  let mut dest: *mut libc::c_char = (*ptr_to_globals).scr_out_buf.as_mut_ptr();
-  c = '~' as i32 as libc::c_uchar;
+  let mut dest: *mut libc::c_int = (*ptr_to_globals).scr_out_buf.as_mut_ptr();
+  let mut line: *mut libc::c_char = src;
+  let mut attr: *mut u8 = std::ptr::null_mut();
+  if (*ptr_to_globals).syntax_on as libc::c_int != 0
+    && (*ptr_to_globals).syntax != 0
+    && src < (*ptr_to_globals).end
+  {
+    attr = syn_line_attr(src)
+  }
+  c = '~' as i32;
   co = 0;
   while (co as libc::c_uint)
     < (*ptr_to_globals)
       .columns
       .wrapping_add((*ptr_to_globals).tabstop as libc::c_uint)
   {
+    let mut w: libc::c_int = 1i32;
+    let mut hl: libc::c_int = 0;
     // have we gone past the end?
     if src < (*ptr_to_globals).end {
-      let fresh0 = src;
-      src = src.offset(1);
-      c = *fresh0 as libc::c_uchar;
-      if c as libc::c_int == '\n' as i32 {
+      let mut len: libc::c_int = 0;
+      if !attr.is_null() {
+        hl = (*attr.offset(src.offset_from(line)) as libc::c_int) << 24i32
+      }
+      c = mb_char(src, &mut len);
+      src = src.offset(len as isize);
+      if c == '\n' as i32 {
         break;
       }
-      if c as libc::c_int & 0x80i32 != 0
-        && !(c as libc::c_int >= ' ' as i32 && (c as libc::c_int) < 0x7fi32)
-      {
-        c = '.' as i32 as libc::c_uchar
+      if c >= 0x80i32 {
+        w = mb_width(c)
       }
-      if (c as libc::c_int) < ' ' as i32 || c as libc::c_int == 0x7fi32 {
-        if c as libc::c_int == '\t' as i32 {
-          c = ' ' as i32 as libc::c_uchar;
+      // combining chars take no column: they go to the cell of the
+      // char they modify (the left one of a wide char)
+      if w == 0 {
+        if co > 0 {
+          let mut k: libc::c_int = co - 1i32;
+          if *dest.offset(k as isize) == 0 && k > 0 {
+            k -= 1
+          }
+          *dest.offset(k as isize) =
+            mb_cluster(*dest.offset(k as isize), src.offset(-len as isize), len)
+        }
+        if src >= (*ptr_to_globals).end {
+          break;
+        }
+        continue;
+      }
+      if c < 0 || w < 0 {
+        c = '.' as i32;
+        w = 1i32
+      }
+      if c < ' ' as i32 || c == 0x7fi32 {
+        if c == '\t' as i32 {
+          c = ' ' as i32;
           //      co %    8     !=     7
           while co % (*ptr_to_globals).tabstop != (*ptr_to_globals).tabstop - 1i32 {
             let fresh1 = co;
             co = co + 1;
-            *dest.offset(fresh1 as isize) = c as libc::c_char
+            *dest.offset(fresh1 as isize) = c | hl
           }
         } else {
           let fresh2 = co;
           co = co + 1;
-          *dest.offset(fresh2 as isize) = '^' as i32 as libc::c_char;
-          if c as libc::c_int == 0x7fi32 {
-            c = '?' as i32 as libc::c_uchar
+          *dest.offset(fresh2 as isize) = '^' as i32 | hl;
+          if c == 0x7fi32 {
+            c = '?' as i32
           } else {
-            c = (c as libc::c_int + '@' as i32) as libc::c_uchar
+            c = c + '@' as i32
           }
           // Ctrl-X -> 'X'
         }
@@ -704,14 +1134,20 @@ unsafe extern "C" fn format_line(mut src: *mut libc::c_char) -> *mut libc::c_cha
     }
     let fresh3 = co;
     co = co + 1;
-    *dest.offset(fresh3 as isize) = c as libc::c_char;
+    *dest.offset(fresh3 as isize) = c | hl;
+    while w > 1i32 {
+      let fresh4 = co;
+      co = co + 1;
+      *dest.offset(fresh4 as isize) = 0;
+      w -= 1
+    }
     // discard scrolled-off-to-the-left portion,
     // in tabstop-sized pieces
     if ofs >= (*ptr_to_globals).tabstop && co >= (*ptr_to_globals).tabstop {
       memmove(
         dest as *mut libc::c_void,
         dest.offset((*ptr_to_globals).tabstop as isize) as *const libc::c_void,
-        co as libc::c_ulong,
+        (co as libc::c_ulong).wrapping_mul(::std::mem::size_of::<libc::c_int>() as libc::c_ulong),
       );
       co -= (*ptr_to_globals).tabstop;
       ofs -= (*ptr_to_globals).tabstop
@@ -727,13 +1163,20 @@ unsafe extern "C" fn format_line(mut src: *mut libc::c_char) -> *mut libc::c_cha
   // discard last scrolled off part
   co -= ofs;
   dest = dest.offset(ofs as isize);
+  // wide chars cut in half by the screen edges
+  if co > 0 && *dest.offset(0) == 0 {
+    *dest.offset(0) = ' ' as i32
+  }
+  if co as libc::c_uint > (*ptr_to_globals).columns
+    && *dest.offset((*ptr_to_globals).columns as isize) == 0
+  {
+    *dest.offset((*ptr_to_globals).columns.wrapping_sub(1i32 as libc::c_uint) as isize) =
+      ' ' as i32
+  }
   // fill the rest with spaces
-  if (co as libc::c_uint) < (*ptr_to_globals).columns {
-    memset(
-      &mut *dest.offset(co as isize) as *mut libc::c_char as *mut libc::c_void,
-      ' ' as i32,
-      (*ptr_to_globals).columns.wrapping_sub(co as libc::c_uint) as libc::c_ulong,
-    );
+  while (co as libc::c_uint) < (*ptr_to_globals).columns {
+    *dest.offset(co as isize) = ' ' as i32;
+    co += 1
   }
   return dest;
 }
@@ -743,31 +1186,61 @@ unsafe extern "C" fn format_line(mut src: *mut libc::c_char) -> *mut libc::c_cha
 // If they differ then that line needs redrawing on the terminal.
 //
 unsafe extern "C" fn refresh(mut full_screen: libc::c_int) {
-  let mut li: libc::c_int = 0; // pointer into text[] and screen[]
-  let mut changed: libc::c_int = 0; // where cursor will be (on "dot")
-  let mut tp: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>(); // index into text[] of top line
-  let mut sp: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut cur: *mut vi_win = (*ptr_to_globals).curwin;
+  let mut w: *mut vi_win = std::ptr::null_mut();
   if 1i32 != 0 && (*ptr_to_globals).get_rowcol_error == 0 {
-    let mut c: libc::c_uint = (*ptr_to_globals).columns;
-    let mut r: libc::c_uint = (*ptr_to_globals).rows;
+    let mut c: libc::c_uint = (*ptr_to_globals).term_cols;
+    let mut r: libc::c_uint = (*ptr_to_globals).term_rows;
     query_screen_dimensions();
-    full_screen = (full_screen as libc::c_uint
-      | (c.wrapping_sub((*ptr_to_globals).columns) | r.wrapping_sub((*ptr_to_globals).rows)))
-      as libc::c_int
+    if c != (*ptr_to_globals).term_cols || r != (*ptr_to_globals).term_rows {
+      win_save(cur);
+      win_resize();
+      full_screen = 1i32
+    }
   }
+  if win_single() != 0 {
+    refresh_window(full_screen);
+    return;
+  }
+  // draw the other windows first, the cursor ends up in the current one
+  win_save(cur);
+  w = win_next(cur);
+  while w != cur {
+    win_load(w);
+    refresh_window(full_screen);
+    win_status(w, full_screen);
+    win_save(w);
+    w = win_next(w)
+  }
+  win_load(cur);
+  if full_screen != 0 {
+    win_separators((*ptr_to_globals).win_root);
+  }
+  win_status(cur, full_screen);
+  refresh_window(full_screen);
+}
+// refresh the current window
+unsafe extern "C" fn refresh_window(mut full_screen: libc::c_int) {
+  let mut li: libc::c_int = 0; // pointer into text[] and screen[]
+  let mut changed: libc::c_int = 0; // where cursor will be (on "dot")
+  let mut tp: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>(); // index into text[] of top line
+  let mut sp: *mut libc::c_int = std::ptr::null_mut::<libc::c_int>();
   sync_cursor(
     (*ptr_to_globals).dot,
     &mut (*ptr_to_globals).crow,
     &mut (*ptr_to_globals).ccol,
   );
   tp = (*ptr_to_globals).screenbegin;
+  if (*ptr_to_globals).syntax_on as libc::c_int != 0 && (*ptr_to_globals).syntax != 0 {
+    (*ptr_to_globals).syn_state = syn_state_at(tp)
+  }
   // compare text[] to screen[] and mark screen[] lines that need updating
   li = 0; // column start & end
   while (li as libc::c_uint) < (*ptr_to_globals).rows.wrapping_sub(1i32 as libc::c_uint) {
     let mut current_block_34: u64;
     let mut cs: libc::c_int = 0;
     let mut ce: libc::c_int = 0;
-    let mut out_buf: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut out_buf: *mut libc::c_int = std::ptr::null_mut::<libc::c_int>();
     // format current text line
     out_buf = format_line(tp);
     // skip to the end of the current text[] line
@@ -789,14 +1262,14 @@ unsafe extern "C" fn refresh(mut full_screen: libc::c_int) {
     sp = &mut *(*ptr_to_globals)
       .screen
       .offset((li as libc::c_uint).wrapping_mul((*ptr_to_globals).columns) as isize)
-      as *mut libc::c_char;
+      as *mut libc::c_int;
     if full_screen != 0 {
       current_block_34 = 4283963503649295902;
     } else {
       // compare newly formatted buffer with virtual screen
       // look forward for first difference between buf and screen
       while cs <= ce {
-        if *out_buf.offset(cs as isize) as libc::c_int != *sp.offset(cs as isize) as libc::c_int {
+        if *out_buf.offset(cs as isize) != *sp.offset(cs as isize) {
           changed = 1i32; // mark for redraw
           break;
         } else {
@@ -805,7 +1278,7 @@ unsafe extern "C" fn refresh(mut full_screen: libc::c_int) {
       }
       // look backward for last difference between out_buf and screen
       while ce >= cs {
-        if *out_buf.offset(ce as isize) as libc::c_int != *sp.offset(ce as isize) as libc::c_int {
+        if *out_buf.offset(ce as isize) != *sp.offset(ce as isize) {
           changed = 1i32; // mark for redraw
           break;
         } else {
@@ -839,22 +1312,27 @@ unsafe extern "C" fn refresh(mut full_screen: libc::c_int) {
       cs = 0;
       ce = (*ptr_to_globals).columns.wrapping_sub(1i32 as libc::c_uint) as libc::c_int
     }
+    // don't redraw half of a wide char, old or new
+    while cs > 0 && (*out_buf.offset(cs as isize) == 0 || *sp.offset(cs as isize) == 0) {
+      cs -= 1
+    }
+    while (ce as libc::c_uint) < (*ptr_to_globals).columns.wrapping_sub(1i32 as libc::c_uint)
+      && (*out_buf.offset((ce + 1i32) as isize) == 0 || *sp.offset((ce + 1i32) as isize) == 0)
+    {
+      ce += 1
+    }
     // is there a change between virtual screen and out_buf
     if changed != 0 {
       // copy changed part of buffer to virtual screen
       memcpy(
         sp.offset(cs as isize) as *mut libc::c_void,
         out_buf.offset(cs as isize) as *const libc::c_void,
-        (ce - cs + 1i32) as libc::c_ulong,
+        ((ce - cs + 1i32) as libc::c_ulong)
+          .wrapping_mul(::std::mem::size_of::<libc::c_int>() as libc::c_ulong),
       );
       place_cursor(li, cs);
       // write line out to terminal
-      libc::fwrite(
-        &mut *sp.offset(cs as isize) as *mut libc::c_char as *const libc::c_void,
-        (ce - cs + 1) as usize,
-        1,
-        stdout,
-      );
+      put_cells(sp.offset(cs as isize), ce - cs + 1i32);
     }
     li += 1
   }
@@ -866,8 +1344,12 @@ unsafe extern "C" fn redraw(mut full_screen: libc::c_int) {
   // cursor to top,left; clear to the end of screen
   write1(b"\x1b[H\x1b[J\x00" as *const u8 as *const libc::c_char); // erase the internal screen buffer
   screen_erase(); // force status update
-  (*ptr_to_globals).last_status_cksum = 0; // this will redraw the entire display
-  refresh(full_screen);
+  (*ptr_to_globals).last_status_cksum = 0;
+  // screen[] of the other windows still holds what was erased
+  if win_single() == 0 {
+    full_screen = 1i32
+  }
+  refresh(full_screen); // this will redraw the entire display
   show_status_line();
 }
 //----- Flash the screen  --------------------------------------
@@ -972,15 +1454,34 @@ unsafe extern "C" fn get_input_line(mut prompt: *const libc::c_char) -> *mut lib
     }
     if c == (*ptr_to_globals).term_orig.c_cc[2] as libc::c_int || c == 8i32 || c == 127i32 {
       // user wants to erase prev char
-      i -= 1; // erase char on screen
+      let mut w: libc::c_int = 1i32;
+      i -= 1;
+      // all of a multibyte char
+      while i > 0
+        && (*ptr_to_globals).get_input_line__buf[i as usize] as libc::c_int & 0xc0i32 == 0x80i32
+      {
+        i -= 1
+      }
+      if (*ptr_to_globals).get_input_line__buf[i as usize] as libc::c_int & 0x80i32 != 0 {
+        let mut len: libc::c_int = 0;
+        let mut s: *mut libc::c_char =
+          (*ptr_to_globals).get_input_line__buf.as_mut_ptr().offset(i as isize);
+        let mut wc: libc::c_int = mb_decode(s, s.offset(strlen(s) as isize), &mut len);
+        if wc > 0 && mb_width(wc) > 1i32 {
+          w = mb_width(wc)
+        }
+      }
       (*ptr_to_globals).get_input_line__buf[i as usize] = '\u{0}' as i32 as libc::c_char;
-      write1(b"\x08 \x08\x00" as *const u8 as *const libc::c_char);
+      while w > 0 {
+        // erase char on screen
+        write1(b"\x08 \x08\x00" as *const u8 as *const libc::c_char);
+        w -= 1
+      }
       if i <= 0 {
         break;
       }
     } else if c > 0 && c < 256i32 {
-      // exclude Unicode
-      // (TODO: need to handle Unicode)
+      // bytes of UTF-8 chars come one by one
       (*ptr_to_globals).get_input_line__buf[i as usize] = c as libc::c_char;
       i += 1;
       (*ptr_to_globals).get_input_line__buf[i as usize] = '\u{0}' as i32 as libc::c_char;
@@ -1038,8 +1539,8 @@ unsafe extern "C" fn format_edit_status() -> libc::c_int {
     cur = (*ptr_to_globals).format_edit_status__tot;
     percent = 100i32
   }
-  trunc_at = if (*ptr_to_globals).columns < (200i32 - 1i32) as libc::c_uint {
-    (*ptr_to_globals).columns
+  trunc_at = if (*ptr_to_globals).term_cols < (200i32 - 1i32) as libc::c_uint {
+    (*ptr_to_globals).term_cols
   } else {
     (200i32 - 1i32) as libc::c_uint
   } as libc::c_int;
@@ -1104,7 +1605,7 @@ unsafe extern "C" fn show_status_line() {
     if (*ptr_to_globals).have_status_msg != 0 {
       if (strlen((*ptr_to_globals).status_buffer.as_mut_ptr()) as libc::c_int
         - ((*ptr_to_globals).have_status_msg - 1i32)) as libc::c_uint
-        > (*ptr_to_globals).columns.wrapping_sub(1i32 as libc::c_uint)
+        > (*ptr_to_globals).term_cols.wrapping_sub(1i32 as libc::c_uint)
       {
         (*ptr_to_globals).have_status_msg = 0;
         Hit_Return();
@@ -1221,6 +1722,54 @@ unsafe extern "C" fn print_literal(mut buf: *mut libc::c_char, mut s: *const lib
     s = s.offset(1)
   }
 }
+// ga- show the value of the char under the cursor, as vim does
+unsafe extern "C" fn show_char_info() {
+  let mut buf: [libc::c_char; 8] = [0; 8];
+  let mut len: libc::c_int = 0;
+  let mut c: libc::c_int = 0;
+  if (*ptr_to_globals).dot >= (*ptr_to_globals).end
+    || *(*ptr_to_globals).dot as libc::c_int == '\n' as i32
+  {
+    status_line(b"NUL\x00" as *const u8 as *const libc::c_char);
+    return;
+  }
+  c = mb_char((*ptr_to_globals).dot, &mut len);
+  if c < 0 {
+    // not UTF-8, show the byte
+    c = *(*ptr_to_globals).dot as libc::c_uchar as libc::c_int;
+    status_line(
+      b"<%02x> %d, Hex %02x, Oct %03o\x00" as *const u8 as *const libc::c_char,
+      c,
+      c,
+      c,
+      c,
+    );
+    return;
+  }
+  if c < ' ' as i32 || c == 0x7fi32 {
+    buf[0] = '^' as i32 as libc::c_char;
+    buf[1] = if c == 0x7fi32 { '?' as i32 } else { c + '@' as i32 } as libc::c_char
+  } else if c >= 0x80i32 && mb_width(c) < 0 {
+    buf[0] = '.' as i32 as libc::c_char
+  } else {
+    memcpy(
+      buf.as_mut_ptr() as *mut libc::c_void,
+      (*ptr_to_globals).dot as *const libc::c_void,
+      len as libc::c_ulong,
+    );
+  }
+  status_line(
+    if c < 0x100i32 {
+      b"<%s> %d, Hex %02x, Oct %03o\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"<%s> %d, Hex %04x, Oct %o\x00" as *const u8 as *const libc::c_char
+    },
+    buf.as_mut_ptr(),
+    c,
+    c,
+    c,
+  );
+}
 unsafe extern "C" fn not_implemented(mut s: *const libc::c_char) {
   let mut buf: [libc::c_char; 128] = [0; 128];
   print_literal(buf.as_mut_ptr(), s);
@@ -1661,7 +2210,7 @@ unsafe extern "C" fn dot_left() {
   if (*ptr_to_globals).dot > (*ptr_to_globals).text
     && *(*ptr_to_globals).dot.offset(-1i32 as isize) as libc::c_int != '\n' as i32
   {
-    (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+    (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
   };
 }
 unsafe extern "C" fn dot_right() {
@@ -1669,7 +2218,10 @@ unsafe extern "C" fn dot_right() {
   if (*ptr_to_globals).dot < (*ptr_to_globals).end.offset(-1)
     && *(*ptr_to_globals).dot as libc::c_int != '\n' as i32
   {
-    (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(1)
+    (*ptr_to_globals).dot = mb_last((*ptr_to_globals).dot).offset(1);
+    if (*ptr_to_globals).dot > (*ptr_to_globals).end.offset(-1) {
+      (*ptr_to_globals).dot = (*ptr_to_globals).end.offset(-1)
+    }
   };
 }
 unsafe extern "C" fn dot_begin() {
@@ -1690,17 +2242,20 @@ unsafe extern "C" fn move_to_col(
   p = begin_line(p);
   co = 0;
   while co < l && p < (*ptr_to_globals).end {
+    let mut len: libc::c_int = 1i32;
     if *p as libc::c_int == '\n' as i32 {
       break;
     }
     if *p as libc::c_int == '\t' as i32 {
       co = next_tabstop(co)
-    } else if (*p as libc::c_int) < ' ' as i32 || *p as libc::c_int == 127i32 {
+    } else if (*p as libc::c_uchar as libc::c_int) < ' ' as i32 || *p as libc::c_int == 127i32 {
       co += 1
       // display as ^X, use 2 columns
+    } else if *p as libc::c_uchar as libc::c_int >= 0x80i32 {
+      co += mb_cols(p, &mut len) - 1i32
     }
     co += 1;
-    p = p.offset(1)
+    p = p.offset(len as isize)
   }
   return p;
 }
@@ -1980,7 +2535,7 @@ unsafe extern "C" fn char_insert(
     if *p.offset(-1i32 as isize) as libc::c_int != '\n' as i32
       && (*ptr_to_globals).dot > (*ptr_to_globals).text
     {
-      p = p.offset(-1)
+      p = mb_prev(p)
     }
   } else if c as libc::c_int == (*ptr_to_globals).term_orig.c_cc[2] as libc::c_int
     || c as libc::c_int == 8i32
@@ -1988,8 +2543,9 @@ unsafe extern "C" fn char_insert(
   {
     // Is this a BS
     if p > (*ptr_to_globals).text {
-      p = p.offset(-1);
-      p = text_hole_delete(p, p, 3i32)
+      let mut q: *mut libc::c_char = p.offset(-1);
+      p = mb_prev(p);
+      p = text_hole_delete(p, q, 3i32)
       // shrink buffer 1 char
     }
   } else {
@@ -2052,6 +2608,7 @@ unsafe extern "C" fn init_text_buffer(mut fn_0: *mut libc::c_char) -> libc::c_in
     // file doesnt exist. Start empty buf with dummy line
     char_insert((*ptr_to_globals).text, '\n' as i32 as libc::c_char, 0);
   }
+  (*ptr_to_globals).syntax = syntax_detect();
   flush_undo_data();
   (*ptr_to_globals).modified_count = 0;
   (*ptr_to_globals).last_modified_count = -1i32;
@@ -2063,6 +2620,573 @@ unsafe extern "C" fn init_text_buffer(mut fn_0: *mut libc::c_char) -> libc::c_in
   );
   return rc;
 }
+//----- Buffers and windows ------------------------------------
+unsafe extern "C" fn buf_save(mut b: *mut vi_buf) {
+  (*b).text = (*ptr_to_globals).text;
+  (*b).end = (*ptr_to_globals).end;
+  (*b).text_size = (*ptr_to_globals).text_size;
+  (*b).dot = (*ptr_to_globals).dot.offset_from((*ptr_to_globals).text) as libc::c_int;
+  (*b).readonly_mode = (*ptr_to_globals).readonly_mode;
+  (*b).modified_count = (*ptr_to_globals).modified_count;
+  (*b).last_modified_count = (*ptr_to_globals).last_modified_count;
+  (*b).format_edit_status__tot = (*ptr_to_globals).format_edit_status__tot;
+  (*b).current_filename = (*ptr_to_globals).current_filename;
+  (*b).mark = (*ptr_to_globals).mark;
+  (*b).context_start = (*ptr_to_globals).context_start;
+  (*b).context_end = (*ptr_to_globals).context_end;
+  (*b).undo_stack_tail = (*ptr_to_globals).undo_stack_tail;
+  (*b).syntax = (*ptr_to_globals).syntax;
+}
+unsafe extern "C" fn buf_load(mut b: *mut vi_buf) {
+  (*ptr_to_globals).text = (*b).text;
+  (*ptr_to_globals).end = (*b).end;
+  (*ptr_to_globals).text_size = (*b).text_size;
+  (*ptr_to_globals).dot = (*b).text.offset((*b).dot as isize);
+  (*ptr_to_globals).readonly_mode = (*b).readonly_mode;
+  (*ptr_to_globals).modified_count = (*b).modified_count;
+  (*ptr_to_globals).last_modified_count = (*b).last_modified_count;
+  (*ptr_to_globals).format_edit_status__tot = (*b).format_edit_status__tot;
+  (*ptr_to_globals).current_filename = (*b).current_filename;
+  (*ptr_to_globals).mark = (*b).mark;
+  (*ptr_to_globals).context_start = (*b).context_start;
+  (*ptr_to_globals).context_end = (*b).context_end;
+  (*ptr_to_globals).undo_stack_tail = (*b).undo_stack_tail;
+  (*ptr_to_globals).syntax = (*b).syntax;
+}
+unsafe extern "C" fn buf_new() -> *mut vi_buf {
+  let mut b: *mut vi_buf =
+    crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<vi_buf>() as libc::c_ulong)
+      as *mut vi_buf;
+  let mut pp: *mut *mut vi_buf = &mut (*ptr_to_globals).bufs;
+  (*ptr_to_globals).buf_last_num += 1;
+  (*b).num = (*ptr_to_globals).buf_last_num;
+  while !(*pp).is_null() {
+    pp = &mut (**pp).next
+  }
+  *pp = b;
+  return b;
+}
+// the first buffer other than the current one with unsaved changes
+unsafe extern "C" fn buf_modified_other() -> *mut vi_buf {
+  let mut b: *mut vi_buf = (*ptr_to_globals).bufs;
+  while !b.is_null() {
+    if b != (*ptr_to_globals).curbuf && (*b).modified_count != 0 {
+      break;
+    }
+    b = (*b).next
+  }
+  return b;
+}
+unsafe extern "C" fn win_save(mut w: *mut vi_win) {
+  let mut text: *mut libc::c_char = (*ptr_to_globals).text;
+  (*w).dot = (*ptr_to_globals).dot.offset_from(text) as libc::c_int;
+  (*w).screenbegin = (*ptr_to_globals).screenbegin.offset_from(text) as libc::c_int;
+  (*w).offset = (*ptr_to_globals).offset;
+  (*w).old_offset = (*ptr_to_globals).refresh__old_offset;
+  (*w).screen = (*ptr_to_globals).screen;
+  (*w).screensize = (*ptr_to_globals).screensize;
+}
+// make w the window rows/columns/screen[] etc. refer to
+unsafe extern "C" fn win_load(mut w: *mut vi_win) {
+  let mut last: libc::c_int = 0;
+  if (*w).buf != (*ptr_to_globals).curbuf {
+    buf_save((*ptr_to_globals).curbuf);
+    buf_load((*w).buf);
+    (*ptr_to_globals).curbuf = (*w).buf
+  }
+  last = (*ptr_to_globals).end.offset_from((*ptr_to_globals).text) as libc::c_int - 1i32;
+  if last < 0 {
+    last = 0
+  }
+  (*ptr_to_globals).dot = (*ptr_to_globals).text.offset((*w).dot.min(last) as isize);
+  (*ptr_to_globals).screenbegin =
+    begin_line((*ptr_to_globals).text.offset((*w).screenbegin.min(last) as isize));
+  (*ptr_to_globals).offset = (*w).offset;
+  (*ptr_to_globals).refresh__old_offset = (*w).old_offset;
+  (*ptr_to_globals).screen = (*w).screen;
+  (*ptr_to_globals).screensize = (*w).screensize;
+  (*ptr_to_globals).rows = (*w).height as libc::c_uint;
+  (*ptr_to_globals).columns = (*w).width as libc::c_uint;
+  (*ptr_to_globals).win_top = (*w).top;
+  (*ptr_to_globals).win_left = (*w).left;
+}
+unsafe extern "C" fn win_single() -> libc::c_int {
+  let mut r: *mut vi_win = (*ptr_to_globals).win_root;
+  return (r.is_null() || !(*r).buf.is_null()) as libc::c_int;
+}
+unsafe extern "C" fn win_first(mut w: *mut vi_win) -> *mut vi_win {
+  while (*w).buf.is_null() {
+    w = (*w).child[0]
+  }
+  return w;
+}
+unsafe extern "C" fn win_last(mut w: *mut vi_win) -> *mut vi_win {
+  while (*w).buf.is_null() {
+    w = (*w).child[1]
+  }
+  return w;
+}
+// next/previous leaf in screen order, wrapping around
+unsafe extern "C" fn win_next(mut w: *mut vi_win) -> *mut vi_win {
+  loop {
+    let mut p: *mut vi_win = (*w).parent;
+    if p.is_null() {
+      return win_first(w);
+    }
+    if (*p).child[0] == w {
+      return win_first((*p).child[1]);
+    }
+    w = p
+  }
+}
+unsafe extern "C" fn win_prev(mut w: *mut vi_win) -> *mut vi_win {
+  loop {
+    let mut p: *mut vi_win = (*w).parent;
+    if p.is_null() {
+      return win_last(w);
+    }
+    if (*p).child[1] == w {
+      return win_last((*p).child[0]);
+    }
+    w = p
+  }
+}
+unsafe extern "C" fn win_layout(
+  mut w: *mut vi_win,
+  mut top: libc::c_int,
+  mut left: libc::c_int,
+  mut height: libc::c_int,
+  mut width: libc::c_int,
+) {
+  (*w).top = top;
+  (*w).left = left;
+  if !(*w).buf.is_null() {
+    // at least one text line and the status line
+    if height < 2 {
+      height = 2
+    }
+    if width < 1 {
+      width = 1
+    }
+    if (*w).screen.is_null() || (*w).height != height || (*w).width != width {
+      let mut s: *mut libc::c_int = (*ptr_to_globals).screen;
+      let mut size: libc::c_int = (*ptr_to_globals).screensize;
+      (*ptr_to_globals).screen = (*w).screen;
+      new_screen(height, width);
+      (*w).screen = (*ptr_to_globals).screen;
+      (*w).screensize = (*ptr_to_globals).screensize;
+      (*ptr_to_globals).screen = s;
+      (*ptr_to_globals).screensize = size;
+      (*w).status_cksum = 0
+    }
+    (*w).height = height;
+    (*w).width = width;
+    return;
+  }
+  (*w).height = height;
+  (*w).width = width;
+  if (*w).vertical != 0 {
+    // one column for the separator
+    let mut w0: libc::c_int = width / 2i32;
+    win_layout((*w).child[0], top, left, height, w0);
+    win_layout((*w).child[1], top, left + w0 + 1i32, height, width - w0 - 1i32);
+  } else {
+    let mut h0: libc::c_int = (height + 1i32) / 2i32;
+    win_layout((*w).child[0], top, left, h0, width);
+    win_layout((*w).child[1], top + h0, left, height - h0, width);
+  };
+}
+// lay the windows out over the terminal.
+// The current window must have been saved by the caller.
+unsafe extern "C" fn win_resize() {
+  let mut r: *mut vi_win = (*ptr_to_globals).win_root;
+  let mut h: libc::c_int = (*ptr_to_globals).term_rows as libc::c_int;
+  if (*r).buf.is_null() {
+    // split windows have status lines, the last line is for commands
+    h -= 1
+  }
+  win_layout(r, 0, 0, h, (*ptr_to_globals).term_cols as libc::c_int);
+  win_load((*ptr_to_globals).curwin);
+}
+unsafe extern "C" fn win_separators(mut w: *mut vi_win) {
+  let mut r: libc::c_int = 0;
+  let mut col: libc::c_int = 0;
+  if !(*w).buf.is_null() {
+    return;
+  }
+  if (*w).vertical != 0 {
+    col = (*(*w).child[0]).left + (*(*w).child[0]).width;
+    r = (*w).top;
+    while r < (*w).top + (*w).height {
+      place_cursor_abs(r, col);
+      write1(b"|\x00" as *const u8 as *const libc::c_char);
+      r += 1
+    }
+  }
+  win_separators((*w).child[0]);
+  win_separators((*w).child[1]);
+}
+// status line of a split window, w must be loaded
+unsafe extern "C" fn win_status(mut w: *mut vi_win, mut full_screen: libc::c_int) {
+  let mut buf: [libc::c_char; 200] = [0; 200];
+  let mut n: libc::c_int = 0;
+  let mut cksum: libc::c_int = 0;
+  n = snprintf(
+    buf.as_mut_ptr(),
+    ::std::mem::size_of::<[libc::c_char; 200]>() as libc::c_ulong,
+    b" %s%s\x00" as *const u8 as *const libc::c_char,
+    if !(*ptr_to_globals).current_filename.is_null() {
+      (*ptr_to_globals).current_filename
+    } else {
+      b"[No Name]\x00" as *const u8 as *const libc::c_char
+    },
+    if (*ptr_to_globals).modified_count != 0 {
+      b" [+]\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"\x00" as *const u8 as *const libc::c_char
+    },
+  );
+  n = n.clamp(0, 199i32).min((*ptr_to_globals).columns as libc::c_int);
+  buf[n as usize] = '\u{0}' as i32 as libc::c_char;
+  cksum = bufsum(buf.as_mut_ptr(), n) + n;
+  if w == (*ptr_to_globals).curwin {
+    cksum += 0x10000i32
+  }
+  if full_screen == 0 && (*w).status_cksum == cksum {
+    return;
+  }
+  (*w).status_cksum = cksum;
+  place_cursor_abs(
+    (*ptr_to_globals).win_top + (*ptr_to_globals).rows as libc::c_int - 1i32,
+    (*ptr_to_globals).win_left,
+  );
+  // the current window's status line is bold
+  if w == (*ptr_to_globals).curwin {
+    write1(b"\x1b[1;7m\x00" as *const u8 as *const libc::c_char);
+  } else {
+    standout_start();
+  }
+  write1(buf.as_mut_ptr());
+  while (n as libc::c_uint) < (*ptr_to_globals).columns {
+    crate::libbb::xfuncs_printf::bb_putchar(' ' as i32);
+    n += 1
+  }
+  standout_end();
+}
+unsafe extern "C" fn win_init() {
+  let mut w: *mut vi_win =
+    crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<vi_win>() as libc::c_ulong)
+      as *mut vi_win;
+  (*ptr_to_globals).curbuf = buf_new();
+  (*w).buf = (*ptr_to_globals).curbuf;
+  (*w).height = (*ptr_to_globals).rows as libc::c_int;
+  (*w).width = (*ptr_to_globals).columns as libc::c_int;
+  (*w).screen = (*ptr_to_globals).screen;
+  (*w).screensize = (*ptr_to_globals).screensize;
+  (*ptr_to_globals).win_root = w;
+  (*ptr_to_globals).curwin = w;
+  (*ptr_to_globals).win_top = 0;
+  (*ptr_to_globals).win_left = 0;
+}
+// free the window tree, except for leaf "keep"
+unsafe extern "C" fn win_free(mut w: *mut vi_win, mut keep: *mut vi_win) {
+  if w == keep {
+    return;
+  }
+  if (*w).buf.is_null() {
+    win_free((*w).child[0], keep);
+    win_free((*w).child[1], keep);
+  } else {
+    free((*w).screen as *mut libc::c_void);
+  }
+  free(w as *mut libc::c_void);
+}
+// drop all buffers but the current one, and all windows
+unsafe extern "C" fn win_done() {
+  let mut b: *mut vi_buf = (*ptr_to_globals).bufs;
+  let mut next: *mut vi_buf = std::ptr::null_mut();
+  // screen[] of the current window is reused by new_screen()
+  win_free((*ptr_to_globals).win_root, (*ptr_to_globals).curwin);
+  free((*ptr_to_globals).curwin as *mut libc::c_void);
+  buf_save((*ptr_to_globals).curbuf);
+  while !b.is_null() {
+    next = (*b).next;
+    if b != (*ptr_to_globals).curbuf {
+      buf_load(b);
+      flush_undo_data();
+      free((*ptr_to_globals).text as *mut libc::c_void);
+      free((*ptr_to_globals).current_filename as *mut libc::c_void);
+      free(b as *mut libc::c_void);
+    }
+    b = next
+  }
+  buf_load((*ptr_to_globals).curbuf);
+  free((*ptr_to_globals).curbuf as *mut libc::c_void);
+  (*ptr_to_globals).bufs = std::ptr::null_mut();
+  (*ptr_to_globals).curbuf = std::ptr::null_mut();
+  (*ptr_to_globals).buf_last_num = 0;
+  (*ptr_to_globals).win_root = std::ptr::null_mut();
+  (*ptr_to_globals).curwin = std::ptr::null_mut();
+}
+unsafe extern "C" fn win_enter(mut w: *mut vi_win) {
+  undo_queue_commit();
+  win_save((*ptr_to_globals).curwin);
+  (*ptr_to_globals).curwin = w;
+  win_load(w);
+  (*ptr_to_globals).edit_file__cur_line = std::ptr::null_mut();
+  (*ptr_to_globals).last_status_cksum = 0;
+}
+// show buffer b in the current window
+unsafe extern "C" fn buf_enter(mut b: *mut vi_buf) {
+  let mut w: *mut vi_win = (*ptr_to_globals).curwin;
+  if b == (*ptr_to_globals).curbuf {
+    return;
+  }
+  undo_queue_commit();
+  win_save(w);
+  (*w).buf = b;
+  (*w).dot = (*b).dot;
+  (*w).screenbegin = (*b).dot;
+  (*w).offset = 0;
+  win_load(w);
+  (*ptr_to_globals).edit_file__cur_line = std::ptr::null_mut();
+  (*ptr_to_globals).last_status_cksum = 0;
+}
+// edit file fn in the current window, in a buffer of its own
+unsafe extern "C" fn buf_open(mut fn_0: *mut libc::c_char) -> libc::c_int {
+  let mut b: *mut vi_buf = (*ptr_to_globals).bufs;
+  let mut rc: libc::c_int = 0;
+  while !b.is_null() {
+    let mut name: *mut libc::c_char = if b == (*ptr_to_globals).curbuf {
+      (*ptr_to_globals).current_filename
+    } else {
+      (*b).current_filename
+    };
+    if !name.is_null() && strcmp(name, fn_0) == 0 {
+      buf_enter(b);
+      return 0;
+    }
+    b = (*b).next
+  }
+  undo_queue_commit();
+  win_save((*ptr_to_globals).curwin);
+  buf_save((*ptr_to_globals).curbuf);
+  b = buf_new();
+  // these now belong to the old buffer
+  (*ptr_to_globals).text = std::ptr::null_mut();
+  (*ptr_to_globals).current_filename = std::ptr::null_mut();
+  (*ptr_to_globals).undo_stack_tail = std::ptr::null_mut();
+  (*ptr_to_globals).readonly_mode =
+    ((*ptr_to_globals).readonly_mode as libc::c_int & 2i32) as smallint;
+  rc = init_text_buffer(fn_0);
+  (*ptr_to_globals).mark[27] = (*ptr_to_globals).text;
+  (*ptr_to_globals).mark[26] = (*ptr_to_globals).mark[27];
+  (*ptr_to_globals).context_start = std::ptr::null_mut();
+  (*ptr_to_globals).context_end = std::ptr::null_mut();
+  (*ptr_to_globals).offset = 0;
+  (*ptr_to_globals).curbuf = b;
+  (*(*ptr_to_globals).curwin).buf = b;
+  (*ptr_to_globals).edit_file__cur_line = std::ptr::null_mut();
+  (*ptr_to_globals).last_status_cksum = 0;
+  return rc;
+}
+// find buffer by number, or by (part of) its name
+unsafe extern "C" fn buf_find(mut arg: *const libc::c_char) -> *mut vi_buf {
+  let mut b: *mut vi_buf = (*ptr_to_globals).bufs;
+  let mut n: libc::c_int = 0;
+  let mut end: libc::c_int = 0;
+  if sscanf(
+    arg,
+    b"%d%n\x00" as *const u8 as *const libc::c_char,
+    &mut n as *mut libc::c_int,
+    &mut end as *mut libc::c_int,
+  ) == 1
+    && *arg.offset(end as isize) == 0
+  {
+    while !b.is_null() && (*b).num != n {
+      b = (*b).next
+    }
+    return b;
+  }
+  buf_save((*ptr_to_globals).curbuf);
+  while !b.is_null() {
+    if !(*b).current_filename.is_null() && !strstr((*b).current_filename, arg).is_null() {
+      break;
+    }
+    b = (*b).next
+  }
+  return b;
+}
+unsafe extern "C" fn win_shows(mut w: *mut vi_win, mut b: *mut vi_buf) -> libc::c_int {
+  if !(*w).buf.is_null() {
+    return ((*w).buf == b) as libc::c_int;
+  }
+  return (win_shows((*w).child[0], b) != 0 || win_shows((*w).child[1], b) != 0) as libc::c_int;
+}
+// ":ls" listing, terminal must be in cooked mode
+unsafe extern "C" fn buf_list() {
+  let mut b: *mut vi_buf = (*ptr_to_globals).bufs;
+  buf_save((*ptr_to_globals).curbuf);
+  while !b.is_null() {
+    printf(
+      b"%3d %c%c %c \"%s\" line %d\n\x00" as *const u8 as *const libc::c_char,
+      (*b).num,
+      if b == (*ptr_to_globals).curbuf { '%' as i32 } else { ' ' as i32 },
+      if win_shows((*ptr_to_globals).win_root, b) != 0 { 'a' as i32 } else { 'h' as i32 },
+      if (*b).modified_count != 0 { '+' as i32 } else { ' ' as i32 },
+      if !(*b).current_filename.is_null() {
+        (*b).current_filename
+      } else {
+        b"[No Name]\x00" as *const u8 as *const libc::c_char as *mut libc::c_char
+      },
+      count_lines((*b).text, (*b).text.offset((*b).dot as isize)),
+    );
+    b = (*b).next
+  }
+}
+// split the current window, the new one goes above or to the left
+unsafe extern "C" fn win_split(mut vertical: libc::c_int, mut fn_0: *mut libc::c_char) {
+  let mut w: *mut vi_win = (*ptr_to_globals).curwin;
+  let mut n: *mut vi_win = std::ptr::null_mut();
+  let mut p: *mut vi_win = std::ptr::null_mut();
+  let mut h: libc::c_int = (*w).height;
+  if w == (*ptr_to_globals).win_root {
+    h = (*ptr_to_globals).term_rows as libc::c_int - 1i32
+  }
+  // both halves need a text line and a status line
+  if vertical != 0 && (*w).width < 3i32 || vertical == 0 && h < 4i32 {
+    status_line_bold(b"Not enough room\x00" as *const u8 as *const libc::c_char);
+    return;
+  }
+  undo_queue_commit();
+  win_save(w);
+  n = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<vi_win>() as libc::c_ulong)
+    as *mut vi_win;
+  *n = *w;
+  (*n).screen = std::ptr::null_mut();
+  (*n).screensize = 0;
+  (*n).status_cksum = 0;
+  p = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<vi_win>() as libc::c_ulong)
+    as *mut vi_win;
+  (*p).vertical = vertical as smallint;
+  (*p).parent = (*w).parent;
+  (*p).child[0] = n;
+  (*p).child[1] = w;
+  if (*w).parent.is_null() {
+    (*ptr_to_globals).win_root = p
+  } else {
+    let mut g: *mut vi_win = (*w).parent;
+    (*g).child[((*g).child[1] == w) as usize] = p
+  }
+  (*n).parent = p;
+  (*w).parent = p;
+  (*ptr_to_globals).curwin = n;
+  win_resize();
+  if !fn_0.is_null() {
+    buf_open(fn_0);
+  }
+  (*ptr_to_globals).edit_file__cur_line = std::ptr::null_mut();
+  redraw(1i32);
+}
+unsafe extern "C" fn win_close(mut w: *mut vi_win) {
+  let mut p: *mut vi_win = (*w).parent;
+  let mut s: *mut vi_win = std::ptr::null_mut();
+  if p.is_null() {
+    status_line_bold(b"Cannot close last window\x00" as *const u8 as *const libc::c_char);
+    return;
+  }
+  s = (*p).child[((*p).child[0] == w) as usize];
+  undo_queue_commit();
+  win_save((*ptr_to_globals).curwin);
+  // the sibling takes the place of the split
+  (*s).parent = (*p).parent;
+  if (*p).parent.is_null() {
+    (*ptr_to_globals).win_root = s
+  } else {
+    let mut g: *mut vi_win = (*p).parent;
+    (*g).child[((*g).child[1] == p) as usize] = s
+  }
+  if w == (*ptr_to_globals).curwin {
+    (*ptr_to_globals).curwin = win_first(s);
+    (*ptr_to_globals).edit_file__cur_line = std::ptr::null_mut()
+  }
+  free((*w).screen as *mut libc::c_void);
+  free(w as *mut libc::c_void);
+  free(p as *mut libc::c_void);
+  win_resize();
+  redraw(1i32);
+}
+unsafe extern "C" fn win_only() {
+  let mut w: *mut vi_win = (*ptr_to_globals).curwin;
+  if win_single() != 0 {
+    return;
+  }
+  win_save(w);
+  win_free((*ptr_to_globals).win_root, w);
+  (*w).parent = std::ptr::null_mut();
+  (*ptr_to_globals).win_root = w;
+  win_resize();
+  redraw(1i32);
+}
+// the window next to the current one in direction of h/j/k/l
+unsafe extern "C" fn win_neighbour(mut dir: libc::c_int) -> *mut vi_win {
+  let mut cur: *mut vi_win = (*ptr_to_globals).curwin;
+  let mut w: *mut vi_win = win_next(cur);
+  let mut row: libc::c_int = (*cur).top + (*ptr_to_globals).crow;
+  let mut col: libc::c_int = (*cur).left + (*ptr_to_globals).ccol;
+  while w != cur {
+    let mut in_rows: libc::c_int = (row >= (*w).top && row < (*w).top + (*w).height) as libc::c_int;
+    let mut in_cols: libc::c_int =
+      (col >= (*w).left && col <= (*w).left + (*w).width) as libc::c_int;
+    if dir == 'h' as i32 && (*w).left + (*w).width + 1i32 == (*cur).left && in_rows != 0
+      || dir == 'l' as i32 && (*w).left == (*cur).left + (*cur).width + 1i32 && in_rows != 0
+      || dir == 'k' as i32 && (*w).top + (*w).height == (*cur).top && in_cols != 0
+      || dir == 'j' as i32 && (*w).top == (*cur).top + (*cur).height && in_cols != 0
+    {
+      return w;
+    }
+    w = win_next(w)
+  }
+  return std::ptr::null_mut();
+}
+// ^W window commands
+unsafe extern "C" fn win_cmd() {
+  let mut c: libc::c_int = get_one_char();
+  let mut w: *mut vi_win = std::ptr::null_mut();
+  if c == 'w' as i32 || c == 0x17i32 {
+    w = win_next((*ptr_to_globals).curwin)
+  } else if c == 'W' as i32 || c == 'p' as i32 {
+    w = win_prev((*ptr_to_globals).curwin)
+  } else if c == 'h' as i32 || c == 'j' as i32 || c == 'k' as i32 || c == 'l' as i32 {
+    w = win_neighbour(c)
+  } else if c == KEYCODE_LEFT as libc::c_int {
+    w = win_neighbour('h' as i32)
+  } else if c == KEYCODE_DOWN as libc::c_int {
+    w = win_neighbour('j' as i32)
+  } else if c == KEYCODE_UP as libc::c_int {
+    w = win_neighbour('k' as i32)
+  } else if c == KEYCODE_RIGHT as libc::c_int {
+    w = win_neighbour('l' as i32)
+  } else if c == 's' as i32 || c == 'S' as i32 || c == 0x13i32 {
+    win_split(0, std::ptr::null_mut());
+    return;
+  } else if c == 'v' as i32 || c == 0x16i32 {
+    win_split(1i32, std::ptr::null_mut());
+    return;
+  } else if c == 'c' as i32 || c == 'q' as i32 {
+    win_close((*ptr_to_globals).curwin);
+    return;
+  } else if c == 'o' as i32 || c == 0xfi32 {
+    win_only();
+    return;
+  } else {
+    indicate_error();
+    return;
+  }
+  if !w.is_null() && w != (*ptr_to_globals).curwin {
+    win_enter(w);
+  }
+}
 // might reallocate text[]! use p += string_insert(p, ...),
 // and be careful to not use pointers into potentially freed text[]!
 unsafe extern "C" fn string_insert(
@@ -2480,8 +3604,13 @@ unsafe extern "C" fn colon(mut buf: *mut libc::c_char) {
     {
       // Edit a file
       let mut size: libc::c_int = 0;
+      // another file goes into a buffer of its own
+      let mut other: libc::c_int = (args[0] as libc::c_int != 0
+        && ((*ptr_to_globals).current_filename.is_null()
+          || strcmp(args.as_mut_ptr(), (*ptr_to_globals).current_filename) != 0))
+        as libc::c_int;
       // don't edit, if the current file has been modified
-      if (*ptr_to_globals).modified_count != 0 && useforce == 0 {
+      if other == 0 && (*ptr_to_globals).modified_count != 0 && useforce == 0 {
         status_line_bold(
           b"No write since last change (:%s! overrides)\x00" as *const u8 as *const libc::c_char,
           cmd.as_mut_ptr(),
@@ -2506,15 +3635,22 @@ unsafe extern "C" fn colon(mut buf: *mut libc::c_char) {
           // no user supplied name- use the current filename
           // fn = current_filename;  was set by default
           {
-            size = init_text_buffer(fn_0); //   free orig line reg- for 'U'
+            if other != 0 {
+              // registers are shared, so yank/put works across buffers
+              size = buf_open(fn_0)
+            } else {
+              size = init_text_buffer(fn_0);
+              /*if (YDreg < 28) - always true*/
+              //   free default yank/delete register
+              free((*ptr_to_globals).reg[(*ptr_to_globals).YDreg as usize] as *mut libc::c_void);
+              (*ptr_to_globals).reg[(*ptr_to_globals).YDreg as usize] =
+                std::ptr::null_mut::<libc::c_char>()
+            }
+            //   free orig line reg- for 'U'
             if 27i32 >= 0 && 27i32 < 28i32 {
               free((*ptr_to_globals).reg[27] as *mut libc::c_void);
               (*ptr_to_globals).reg[27] = std::ptr::null_mut::<libc::c_char>()
             }
-            /*if (YDreg < 28) - always true*/
-            free((*ptr_to_globals).reg[(*ptr_to_globals).YDreg as usize] as *mut libc::c_void); //   free default yank/delete register
-            (*ptr_to_globals).reg[(*ptr_to_globals).YDreg as usize] =
-              std::ptr::null_mut::<libc::c_char>();
             // how many lines in text[]?
             li = count_lines((*ptr_to_globals).text, (*ptr_to_globals).end.offset(-1));
             status_line(
@@ -2632,7 +3768,10 @@ unsafe extern "C" fn colon(mut buf: *mut libc::c_char) {
     {
       // edit previous file
       let mut n: libc::c_int = 0;
-      if useforce != 0 {
+      if *cmd.as_mut_ptr() as libc::c_int == 'q' as i32 && win_single() == 0 {
+        // close the window, its buffer stays in the list
+        win_close((*ptr_to_globals).curwin);
+      } else if useforce != 0 {
         if *cmd.as_mut_ptr() as libc::c_int == 'q' as i32 {
           // force end of argv list
           optind = (*ptr_to_globals).cmdline_filecnt
@@ -2643,6 +3782,13 @@ unsafe extern "C" fn colon(mut buf: *mut libc::c_char) {
           b"No write since last change (:%s! overrides)\x00" as *const u8 as *const libc::c_char,
           cmd.as_mut_ptr(),
         );
+      } else if !buf_modified_other().is_null() {
+        status_line_bold(
+          b"No write since last change for buffer %d (:%s! overrides)\x00" as *const u8
+            as *const libc::c_char,
+          (*buf_modified_other()).num,
+          cmd.as_mut_ptr(),
+        );
       } else {
         // don't exit if the file been modified
         // are there other file to edit
@@ -2744,6 +3890,151 @@ unsafe extern "C" fn colon(mut buf: *mut libc::c_char) {
         optind = -1i32; // start from 0th file
         (*ptr_to_globals).editing = 0 as smallint
       }
+    } else if i >= 2i32
+      && strncmp(
+        cmd.as_mut_ptr(),
+        b"syntax\x00" as *const u8 as *const libc::c_char,
+        i as libc::c_ulong,
+      ) == 0
+    {
+      // syntax highlighting: on, off, or the language to use
+      if args[0] == 0 {
+        status_line(
+          b"syntax %s\x00" as *const u8 as *const libc::c_char,
+          if (*ptr_to_globals).syntax_on as libc::c_int == 0 {
+            b"off\x00" as *const u8 as *const libc::c_char
+          } else {
+            crate::libbb::syntax::syntax_name((*ptr_to_globals).syntax)
+          },
+        );
+      } else if strcmp(args.as_mut_ptr(), b"off\x00" as *const u8 as *const libc::c_char) == 0 {
+        (*ptr_to_globals).syntax_on = 0 as smallint
+      } else if strcmp(args.as_mut_ptr(), b"on\x00" as *const u8 as *const libc::c_char) == 0 {
+        (*ptr_to_globals).syntax_on = 1i32 as smallint;
+        (*ptr_to_globals).syntax = syntax_detect()
+      } else {
+        let mut lang: libc::c_int = crate::libbb::syntax::syntax_find(args.as_mut_ptr());
+        if lang == 0 {
+          status_line_bold(
+            b"Unknown syntax '%s'\x00" as *const u8 as *const libc::c_char,
+            args.as_mut_ptr(),
+          );
+        } else {
+          (*ptr_to_globals).syntax = lang;
+          (*ptr_to_globals).syntax_on = 1i32 as smallint
+        }
+      }
+    } else if i >= 2i32
+      && (strncmp(
+        cmd.as_mut_ptr(),
+        b"split\x00" as *const u8 as *const libc::c_char,
+        i as libc::c_ulong,
+      ) == 0
+        || strncmp(
+          cmd.as_mut_ptr(),
+          b"vsplit\x00" as *const u8 as *const libc::c_char,
+          i as libc::c_ulong,
+        ) == 0)
+    {
+      // split the window, the new one may edit another file
+      win_split(
+        (cmd[0] as libc::c_int == 'v' as i32) as libc::c_int,
+        if args[0] as libc::c_int != 0 {
+          args.as_mut_ptr()
+        } else {
+          std::ptr::null_mut()
+        },
+      );
+    } else if i >= 3i32
+      && strncmp(
+        cmd.as_mut_ptr(),
+        b"close\x00" as *const u8 as *const libc::c_char,
+        i as libc::c_ulong,
+      ) == 0
+    {
+      win_close((*ptr_to_globals).curwin);
+    } else if i >= 2i32
+      && strncmp(
+        cmd.as_mut_ptr(),
+        b"only\x00" as *const u8 as *const libc::c_char,
+        i as libc::c_ulong,
+      ) == 0
+    {
+      win_only();
+    } else if i >= 2i32
+      && strncmp(
+        cmd.as_mut_ptr(),
+        b"qall\x00" as *const u8 as *const libc::c_char,
+        i as libc::c_ulong,
+      ) == 0
+    {
+      // quit, whatever the windows
+      if useforce == 0
+        && ((*ptr_to_globals).modified_count != 0 || !buf_modified_other().is_null())
+      {
+        status_line_bold(
+          b"No write since last change (:%s! overrides)\x00" as *const u8 as *const libc::c_char,
+          cmd.as_mut_ptr(),
+        );
+      } else {
+        optind = (*ptr_to_globals).cmdline_filecnt;
+        (*ptr_to_globals).editing = 0 as smallint
+      }
+    } else if strcmp(cmd.as_mut_ptr(), b"ls\x00" as *const u8 as *const libc::c_char) == 0
+      || strcmp(cmd.as_mut_ptr(), b"files\x00" as *const u8 as *const libc::c_char) == 0
+      || strcmp(cmd.as_mut_ptr(), b"buffers\x00" as *const u8 as *const libc::c_char) == 0
+    {
+      // list the buffers
+      go_bottom_and_clear_to_eol();
+      cookmode();
+      buf_list();
+      rawmode();
+      Hit_Return();
+    } else if i >= 2i32
+      && (strncmp(
+        cmd.as_mut_ptr(),
+        b"bnext\x00" as *const u8 as *const libc::c_char,
+        i as libc::c_ulong,
+      ) == 0
+        || strncmp(
+          cmd.as_mut_ptr(),
+          b"bprevious\x00" as *const u8 as *const libc::c_char,
+          i as libc::c_ulong,
+        ) == 0)
+    {
+      // cycle through the buffer list
+      let mut bp: *mut vi_buf = (*(*ptr_to_globals).curbuf).next;
+      if cmd[1] as libc::c_int == 'p' as i32 {
+        bp = (*ptr_to_globals).bufs;
+        while !(*bp).next.is_null() && (*bp).next != (*ptr_to_globals).curbuf {
+          bp = (*bp).next
+        }
+      }
+      if bp.is_null() {
+        bp = (*ptr_to_globals).bufs
+      }
+      buf_enter(bp);
+    } else if strncmp(
+      cmd.as_mut_ptr(),
+      b"buffer\x00" as *const u8 as *const libc::c_char,
+      i as libc::c_ulong,
+    ) == 0
+    {
+      // edit buffer N
+      let mut bp_0: *mut vi_buf = std::ptr::null_mut();
+      if args[0] == 0 {
+        status_line_bold(b"No buffer given\x00" as *const u8 as *const libc::c_char);
+      } else {
+        bp_0 = buf_find(args.as_mut_ptr());
+        if bp_0.is_null() {
+          status_line_bold(
+            b"No matching buffer for %s\x00" as *const u8 as *const libc::c_char,
+            args.as_mut_ptr(),
+          );
+        } else {
+          buf_enter(bp_0);
+        }
+      }
     } else if strncmp(
       cmd.as_mut_ptr(),
       b"set\x00" as *const u8 as *const libc::c_char,
@@ -3020,7 +4311,20 @@ unsafe extern "C" fn colon(mut buf: *mut libc::c_char) {
               || cmd[1] as libc::c_int == 'Q' as i32
               || cmd[1] as libc::c_int == 'N' as i32
             {
-              (*ptr_to_globals).editing = 0 as smallint
+              if cmd[1] as libc::c_int != 'n' as i32
+                && cmd[1] as libc::c_int != 'N' as i32
+                && win_single() == 0
+              {
+                win_close((*ptr_to_globals).curwin);
+              } else if !buf_modified_other().is_null() {
+                status_line_bold(
+                  b"No write since last change for buffer %d\x00" as *const u8
+                    as *const libc::c_char,
+                  (*buf_modified_other()).num,
+                );
+              } else {
+                (*ptr_to_globals).editing = 0 as smallint
+              }
             }
           }
         }
@@ -3151,13 +4455,12 @@ unsafe extern "C" fn winch_handler(mut _sig: libc::c_int) {
   signal(
     28i32,
     Some(winch_handler as unsafe extern "C" fn(_: libc::c_int) -> ()),
-  ); // get memory for virtual screen
-  query_screen_dimensions(); // re-draw the screen
-  new_screen(
-    (*ptr_to_globals).rows as libc::c_int,
-    (*ptr_to_globals).columns as libc::c_int,
   );
-  redraw(1i32);
+  query_screen_dimensions();
+  // get memory for virtual screens
+  win_save((*ptr_to_globals).curwin);
+  win_resize();
+  redraw(1i32); // re-draw the screen
   *bb_errno = save_errno;
 }
 unsafe extern "C" fn tstp_handler(mut _sig: libc::c_int) {
@@ -3395,10 +4698,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             || c as libc::c_uchar as libc::c_int >= ' ' as i32
               && (c as libc::c_uchar as libc::c_int) < 0x7fi32
           {
-            if c != 27i32 {
+            // the rest of a multibyte char replaces nothing
+            if c != 27i32 && c & 0xc0i32 != 0x80i32 {
               (*ptr_to_globals).dot = yank_delete(
                 (*ptr_to_globals).dot,
-                (*ptr_to_globals).dot,
+                mb_last((*ptr_to_globals).dot),
                 0,
                 YANKDEL as libc::c_int,
                 1i32,
@@ -3522,10 +4826,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             {
               c1 = get_one_char(); // stop adding to q
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -3737,8 +5051,12 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               // 'gg' goto a line number (vim) (default: very first line)
+              // 'ga' show the value of the char under the cursor
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 // c1 < 0 if the key was special. Try "g<up-arrow>"
                 // TODO: if Unicode?
@@ -4383,11 +5701,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -4946,10 +6264,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -5141,7 +6469,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -5624,11 +6955,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -6105,10 +7436,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -6300,7 +7641,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -6783,11 +8127,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -7264,10 +8608,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -7459,7 +8813,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -7942,11 +9299,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -8423,10 +9780,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -8618,7 +9985,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -9101,11 +10471,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -9582,10 +10952,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -9777,7 +11157,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -10260,11 +11643,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -10741,10 +12124,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -10936,7 +12329,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -11419,11 +12815,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -11900,10 +13296,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -12095,7 +13501,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -12578,11 +13987,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -13059,10 +14468,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -13254,7 +14673,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -13737,11 +15159,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -14218,10 +15640,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -14413,7 +15845,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -14896,11 +16331,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -15377,10 +16812,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -15572,7 +17017,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -16055,11 +17503,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -16536,10 +17984,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -16731,7 +18189,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -17214,11 +18675,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -17695,10 +19156,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -17890,7 +19361,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -18373,11 +19847,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -18854,10 +20328,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -19049,7 +20533,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -19532,11 +21019,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -20013,10 +21500,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -20208,7 +21705,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -20691,11 +22191,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -21172,10 +22672,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -21367,7 +22877,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -21850,11 +23363,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -22331,10 +23844,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -22526,7 +24049,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -23009,11 +24535,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -23490,10 +25016,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -23685,7 +25221,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -24168,11 +25707,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -24649,10 +26188,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -24844,7 +26393,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -25327,11 +26879,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -25808,10 +27360,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -26003,7 +27565,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -26486,11 +28051,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -26967,10 +28532,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -27162,7 +28737,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -27645,11 +29223,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -28126,10 +29704,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -28321,7 +29909,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -28804,11 +30395,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -29285,10 +30876,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -29480,7 +31081,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -29963,11 +31567,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -30444,10 +32048,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -30639,7 +32253,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -31122,11 +32739,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -31603,10 +33220,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -31798,7 +33425,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -32281,11 +33911,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -32762,10 +34392,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -32957,7 +34597,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -33440,11 +35083,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -33921,10 +35564,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -34116,7 +35769,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -34599,11 +36255,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -35080,10 +36736,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -35275,7 +36941,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -35758,11 +37427,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -36239,10 +37908,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -36434,7 +38113,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -36917,11 +38599,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -37398,10 +39080,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -37593,7 +39285,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -38076,11 +39771,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -38557,10 +40252,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -38752,7 +40457,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -39235,11 +40943,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -39716,10 +41424,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -39911,7 +41629,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -40394,11 +42115,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -40875,10 +42596,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -41070,7 +42801,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -41553,11 +43287,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -42034,10 +43768,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -42229,7 +43973,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -42712,11 +44459,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -43193,10 +44940,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -43388,7 +45145,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -43871,11 +45631,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -44352,10 +46112,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -44547,7 +46317,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -45030,11 +46803,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -45511,10 +47284,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -45706,7 +47489,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -46189,11 +47975,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -46670,10 +48456,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -46865,7 +48661,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -47348,11 +49147,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -47829,10 +49628,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -48024,7 +49833,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -48507,11 +50319,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -48988,10 +50800,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -49183,7 +51005,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -49666,11 +51491,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -50147,10 +51972,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -50342,7 +52177,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -50825,11 +52663,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -51306,10 +53144,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -51501,7 +53349,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -51984,11 +53835,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -52465,10 +54316,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -52660,7 +54521,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -53143,11 +55007,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -53624,10 +55488,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -53819,7 +55693,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -54302,11 +56179,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -54783,10 +56660,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -54978,7 +56865,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -55461,11 +57351,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -55942,10 +57832,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -56137,7 +58037,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -56620,11 +58523,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -57101,10 +59004,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -57296,7 +59209,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -57779,11 +59695,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -58260,10 +60176,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -58455,7 +60381,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -58938,11 +60867,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -59419,10 +61348,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -59614,7 +61553,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -60097,11 +62039,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -60578,10 +62520,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -60773,7 +62725,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -61256,11 +63211,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -61737,10 +63692,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -61932,7 +63897,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -62415,11 +64383,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -62896,10 +64864,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -63091,7 +65069,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -63574,11 +65555,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -64055,10 +66036,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -64250,7 +66241,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -64733,11 +66727,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -65214,10 +67208,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -65409,7 +67413,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -65892,11 +67899,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -66373,10 +68380,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -66568,7 +68585,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -67051,11 +69071,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -67532,10 +69552,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -67727,7 +69757,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -68210,11 +70243,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -68691,10 +70724,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -68886,7 +70929,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -69369,11 +71415,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -69853,10 +71899,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -70048,7 +72104,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -70531,11 +72590,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -71012,10 +73071,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -71207,7 +73276,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -71690,11 +73762,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -72171,10 +74243,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -72366,7 +74448,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -72849,11 +74934,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -73330,10 +75415,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -73525,7 +75620,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -74008,11 +76106,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -74489,10 +76587,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -74684,7 +76792,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -75167,11 +77278,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -75651,10 +77762,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -75846,7 +77967,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -76329,11 +78453,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -76810,10 +78934,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -77005,7 +79139,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -77488,11 +79625,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -77969,10 +80106,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -78164,7 +80311,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -78647,11 +80797,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -79128,10 +81278,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -79323,7 +81483,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -79806,11 +81969,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -80287,10 +82450,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -80482,7 +82655,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -80965,11 +83141,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -81446,10 +83622,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -81641,7 +83827,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -82124,11 +84313,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -82605,10 +84794,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -82800,7 +84999,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -83283,11 +85485,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -83764,10 +85966,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -83959,7 +86171,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -84442,11 +86657,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -84923,10 +87138,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -85118,7 +87343,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -85601,11 +87829,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -86082,10 +88310,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -86277,7 +88515,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -86760,11 +89001,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -87241,10 +89482,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -87436,7 +89687,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -87919,11 +90173,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -88400,10 +90654,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -88595,7 +90859,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -89078,11 +91345,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -89559,10 +91826,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -89754,7 +92031,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -90237,11 +92517,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -90718,10 +92998,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -90913,7 +93203,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -91396,11 +93689,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -91877,10 +94170,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -92072,7 +94375,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -92555,11 +94861,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -93036,10 +95342,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -93231,7 +95547,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -93714,11 +96033,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -94195,10 +96514,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -94390,7 +96719,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -94873,11 +97205,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -95354,10 +97686,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -95549,7 +97891,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -96032,11 +98377,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -96513,10 +98858,20 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             9051390181078481641 => {
               c1 = get_one_char();
               if *(*ptr_to_globals).dot as libc::c_int != '\n' as i32 {
-                (*ptr_to_globals).dot =
-                  text_hole_delete((*ptr_to_globals).dot, (*ptr_to_globals).dot, 1i32);
+                (*ptr_to_globals).dot = text_hole_delete(
+                  (*ptr_to_globals).dot,
+                  mb_last((*ptr_to_globals).dot),
+                  1i32,
+                );
                 (*ptr_to_globals).dot =
                   char_insert((*ptr_to_globals).dot, c1 as libc::c_char, 2i32);
+                // the rest of a multibyte replacement char
+                i = mb_follow(c1);
+                while i > 0 {
+                  (*ptr_to_globals).dot =
+                    char_insert((*ptr_to_globals).dot, get_one_char() as libc::c_char, 2i32);
+                  i -= 1
+                }
                 dot_left();
               }
               end_cmd_q();
@@ -96708,7 +99063,10 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
             }
             17973101765331640574 => {
               c1 = get_one_char();
-              if c1 != 'g' as i32 {
+              if c1 == 'a' as i32 {
+                show_char_info();
+                current_block = 15129665204879337069;
+              } else if c1 != 'g' as i32 {
                 buf[0] = 'g' as i32 as libc::c_char;
                 buf[1] = if c1 >= 0 { c1 } else { '*' as i32 } as libc::c_char;
                 buf[2] = '\u{0}' as i32 as libc::c_char;
@@ -97191,11 +99549,11 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
                   loop {
                     if *(*ptr_to_globals).dot.offset(dir as isize) as libc::c_int != '\n' as i32 {
                       if c == 'X' as i32 {
-                        (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+                        (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
                       }
                       (*ptr_to_globals).dot = yank_delete(
                         (*ptr_to_globals).dot,
-                        (*ptr_to_globals).dot,
+                        mb_last((*ptr_to_globals).dot),
                         0,
                         YANKDEL as libc::c_int,
                         1i32,
@@ -97639,7 +99997,12 @@ unsafe extern "C" fn do_cmd(mut c: libc::c_int) {
     && cnt > 0
     && (*ptr_to_globals).cmd_mode as libc::c_int == 0
   {
-    (*ptr_to_globals).dot = (*ptr_to_globals).dot.offset(-1)
+    (*ptr_to_globals).dot = mb_prev((*ptr_to_globals).dot)
+  }
+  // and off the middle of a multibyte char
+  if (*ptr_to_globals).cmd_mode as libc::c_int == 0 && (*ptr_to_globals).dot < (*ptr_to_globals).end
+  {
+    (*ptr_to_globals).dot = mb_start((*ptr_to_globals).dot)
   };
 }
 // NB!  the CRASHME code is unmaintained, and doesn't currently build
@@ -97648,8 +100011,8 @@ unsafe extern "C" fn edit_file(mut fn_0: *mut libc::c_char) {
   let mut sig: libc::c_int = 0;
   (*ptr_to_globals).editing = 1i32 as smallint;
   rawmode();
-  (*ptr_to_globals).rows = 24i32 as libc::c_uint;
-  (*ptr_to_globals).columns = 80i32 as libc::c_uint;
+  (*ptr_to_globals).term_rows = 24i32 as libc::c_uint;
+  (*ptr_to_globals).term_cols = 80i32 as libc::c_uint;
   (*ptr_to_globals).get_rowcol_error = query_screen_dimensions();
   if (*ptr_to_globals).get_rowcol_error != 0 {
     /* TODO? && no input on stdin */
@@ -97660,21 +100023,25 @@ unsafe extern "C" fn edit_file(mut fn_0: *mut libc::c_char) {
       as u64;
     if k as i32 == KEYCODE_CURSOR_POS as libc::c_int {
       let mut rc: u32 = (k >> 32i32) as u32;
-      (*ptr_to_globals).columns = rc & 0x7fffi32 as libc::c_uint;
-      if (*ptr_to_globals).columns > MAX_SCR_COLS as libc::c_int as libc::c_uint {
-        (*ptr_to_globals).columns = MAX_SCR_COLS as libc::c_int as libc::c_uint
+      (*ptr_to_globals).term_cols = rc & 0x7fffi32 as libc::c_uint;
+      if (*ptr_to_globals).term_cols > MAX_SCR_COLS as libc::c_int as libc::c_uint {
+        (*ptr_to_globals).term_cols = MAX_SCR_COLS as libc::c_int as libc::c_uint
       }
-      (*ptr_to_globals).rows = rc >> 16i32 & 0x7fffi32 as libc::c_uint;
-      if (*ptr_to_globals).rows > MAX_SCR_ROWS as libc::c_int as libc::c_uint {
-        (*ptr_to_globals).rows = MAX_SCR_ROWS as libc::c_int as libc::c_uint
+      (*ptr_to_globals).term_rows = rc >> 16i32 & 0x7fffi32 as libc::c_uint;
+      if (*ptr_to_globals).term_rows > MAX_SCR_ROWS as libc::c_int as libc::c_uint {
+        (*ptr_to_globals).term_rows = MAX_SCR_ROWS as libc::c_int as libc::c_uint
       }
     }
   }
+  // one window covering the terminal
+  (*ptr_to_globals).rows = (*ptr_to_globals).term_rows;
+  (*ptr_to_globals).columns = (*ptr_to_globals).term_cols;
   new_screen(
     (*ptr_to_globals).rows as libc::c_int,
     (*ptr_to_globals).columns as libc::c_int,
   );
   init_text_buffer(fn_0);
+  win_init();
   (*ptr_to_globals).YDreg = 26i32 as smalluint;
   //	Ureg = 27; - const		// hold orig line for "U" cmd
   (*ptr_to_globals).mark[27] = (*ptr_to_globals).text; // init "previous context"
@@ -97763,7 +100130,24 @@ unsafe extern "C" fn edit_file(mut fn_0: *mut libc::c_char) {
     {
       start_new_cmd_q(c as libc::c_char); // execute the user command
     }
-    do_cmd(c);
+    if c == 0x17i32 && (*ptr_to_globals).cmd_mode as libc::c_int == 0 {
+      win_cmd();
+    } else {
+      do_cmd(c);
+    }
+    // ZZ: with split windows, close just this one
+    if (*ptr_to_globals).editing as libc::c_int == 0 && c == 'Z' as i32 {
+      if win_single() == 0 {
+        win_close((*ptr_to_globals).curwin);
+        (*ptr_to_globals).editing = 1i32 as smallint
+      } else if !buf_modified_other().is_null() {
+        status_line_bold(
+          b"No write since last change for buffer %d\x00" as *const u8 as *const libc::c_char,
+          (*buf_modified_other()).num,
+        );
+        (*ptr_to_globals).editing = 1i32 as smallint
+      }
+    }
     // poll to see if there is input already waiting. if we are
     // not able to display output fast enough to keep up, skip
     // the display update until we catch up with input.
@@ -97774,6 +100158,7 @@ unsafe extern "C" fn edit_file(mut fn_0: *mut libc::c_char) {
     }
   }
   //-------------------------------------------------------------------
+  win_done();
   go_bottom_and_clear_to_eol();
   cookmode();
 }
@@ -97793,6 +100178,7 @@ pub unsafe fn vi_main(mut argc: libc::c_int, mut argv: *mut *mut libc::c_char) -
   //undo_q = 0; - already is
   // autoindent is not default in vim 7.3
   (*ptr_to_globals).vi_setops = (2i32 | 4i32) as smallint;
+  (*ptr_to_globals).syntax_on = 1i32 as smallint;
   //  1-  process $HOME/.exrc file (not inplemented yet)
   //  2-  process EXINIT variable from environment
   //  3-  process command line args
@@ -15,9 +15,7 @@ use libc::fprintf;
 use libc::free;
 use libc::getenv;
 use libc::geteuid;
-use libc::getpid;
 use libc::lstat;
-use libc::off_t;
 use libc::open;
 use libc::opendir;
 use libc::passwd;
@@ -26,7 +24,6 @@ use libc::printf;
 use libc::putchar_unlocked;
 use libc::puts;
 use libc::readdir;
-use libc::rename;
 use libc::sigval;
 use libc::sprintf;
 use libc::ssize_t;
@@ -48,6 +45,31 @@ extern "C" {
   fn memmove(_: *mut libc::c_void, _: *const libc::c_void, _: libc::c_ulong) -> *mut libc::c_void;
   #[no_mangle]
   fn memset(_: *mut libc::c_void, _: libc::c_int, _: libc::c_ulong) -> *mut libc::c_void;
+  #[no_mangle]
+  fn memcpy(_: *mut libc::c_void, _: *const libc::c_void, _: libc::c_ulong) -> *mut libc::c_void;
+  #[no_mangle]
+  fn memcmp(_: *const libc::c_void, _: *const libc::c_void, _: libc::c_ulong) -> libc::c_int;
+  #[no_mangle]
+  fn strncmp(_: *const libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> libc::c_int;
+  #[no_mangle]
+  fn strcasecmp(_: *const libc::c_char, _: *const libc::c_char) -> libc::c_int;
+  #[no_mangle]
+  fn strncasecmp(_: *const libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> libc::c_int;
+  #[no_mangle]
+  fn fnmatch(
+    __pattern: *const libc::c_char,
+    __name: *const libc::c_char,
+    __flags: libc::c_int,
+  ) -> libc::c_int;
+  #[no_mangle]
+  fn strftime(
+    __s: *mut libc::c_char,
+    __maxsize: size_t,
+    __format: *const libc::c_char,
+    __tp: *const libc::tm,
+  ) -> size_t;
+  #[no_mangle]
+  fn strcspn(_: *const libc::c_char, _: *const libc::c_char) -> libc::c_ulong;
 
   #[no_mangle]
   static mut stdin: *mut FILE;
@@ -251,7 +273,7 @@ use crate::librb::line_input_t;
 pub type C2RustUnnamed_11 = libc::c_uint;
 pub const FOR_SHELL: C2RustUnnamed_11 = 7;
 pub const WITH_PATH_LOOKUP: C2RustUnnamed_11 = 16;
-pub const VI_MODE: C2RustUnnamed_11 = 0;
+pub const VI_MODE: C2RustUnnamed_11 = 8;
 pub const USERNAME_COMPLETION: C2RustUnnamed_11 = 4;
 pub const TAB_COMPLETION: C2RustUnnamed_11 = 2;
 pub const DO_HISTORY: C2RustUnnamed_11 = 1;
@@ -279,6 +301,9 @@ pub struct lineedit_statics {
   pub SIGWINCH_count: libc::c_uint,
   pub ok_to_redraw: smallint,
   pub SIGWINCH_handler: sigaction,
+  pub vi_cmdmode: smallint,
+  /* rest of a macro being typed in */
+  pub macro_pending: *const libc::c_char,
 }
 pub const MAX_LINELEN: C2RustUnnamed_13 = 1024;
 pub const UNICODE_ON: C2RustUnnamed_12 = 2;
@@ -502,7 +527,9 @@ unsafe extern "C" fn goto_new_line() {
   };
 }
 unsafe extern "C" fn beep() {
-  crate::libbb::xfuncs_printf::bb_putchar('\u{7}' as i32);
+  if bell_style_none == 0 {
+    crate::libbb::xfuncs_printf::bb_putchar('\u{7}' as i32);
+  }
 }
 /* Full or last/sole prompt line, reset edit cursor, calculate terminal cursor.
  * cmdedit_y is always calculated for the last/sole prompt line.
@@ -892,7 +919,11 @@ unsafe extern "C" fn complete_cmd_dir_file(
           continue;
         }
         /* match? */
-        if crate::libbb::compare_string_array::is_prefixed_with(name_found, pfind).is_null() {
+        if if completion_ignore_case != 0 {
+          strncasecmp(name_found, pfind, pf_len as size_t) != 0
+        } else {
+          crate::libbb::compare_string_array::is_prefixed_with(name_found, pfind).is_null()
+        } {
           continue; /* no */
         }
         found =
@@ -1252,6 +1283,7 @@ unsafe extern "C" fn input_tab(mut lastWasTab: *mut smallint) {
   let mut find_type: libc::c_int = 0;
   /* cursor pos in command converted to multibyte form */
   let mut cursor_mb: libc::c_int = 0;
+  let mut handled: libc::c_int = 0;
   if (*(*lineedit_ptr_to_statics).state).flags & TAB_COMPLETION as libc::c_int == 0 {
     return;
   }
@@ -1294,12 +1326,45 @@ unsafe extern "C" fn input_tab(mut lastWasTab: *mut smallint) {
     .command_ps
     .offset((*lineedit_ptr_to_statics).cursor as isize) = wc;
   cursor_mb = strlen(match_buf) as libc::c_int;
-  find_type = build_match_prefix(match_buf);
   /* Free up any memory already allocated */
   free_tab_completion_data();
+  /* Does the shell have a complete spec for this command? */
+  if (*(*lineedit_ptr_to_statics).state).complete_hook.is_some() {
+    let mut cand: *mut *mut libc::c_char = (*(*lineedit_ptr_to_statics).state)
+      .complete_hook
+      .expect("non-null function pointer")(match_buf);
+    if !cand.is_null() {
+      let mut cp: *mut *mut libc::c_char = cand;
+      let mut len: size_t = 0;
+      find_type = build_match_prefix(match_buf);
+      len = strlen(match_buf);
+      /* we can only insert the tail of a candidate after the cursor */
+      while !(*cp).is_null() {
+        if if completion_ignore_case != 0 {
+          strncasecmp(*cp, match_buf, len)
+        } else {
+          strncmp(*cp, match_buf, len)
+        } == 0
+        {
+          add_match(*cp);
+        } else {
+          free(*cp as *mut libc::c_void);
+        }
+        cp = cp.offset(1)
+      }
+      free(cand as *mut libc::c_void);
+      match_pfx_len = len as libc::c_uint;
+      handled = 1
+    }
+  }
+  if handled == 0 {
+    find_type = build_match_prefix(match_buf)
+  }
   /* If the word starts with ~ and there is no slash in the word,
    * then try completing this word as a username. */
-  if (*(*lineedit_ptr_to_statics).state).flags & USERNAME_COMPLETION as libc::c_int != 0 {
+  if handled == 0
+    && (*(*lineedit_ptr_to_statics).state).flags & USERNAME_COMPLETION as libc::c_int != 0
+  {
     if *match_buf.offset(0) as libc::c_int == '~' as i32 && strchr(match_buf, '/' as i32).is_null()
     {
       match_pfx_len = complete_username(match_buf)
@@ -1307,7 +1372,7 @@ unsafe extern "C" fn input_tab(mut lastWasTab: *mut smallint) {
   }
   /* If complete_username() did not match,
    * try to match a command in $PATH, or a directory, or a file */
-  if (*lineedit_ptr_to_statics).matches.is_null() {
+  if handled == 0 && (*lineedit_ptr_to_statics).matches.is_null() {
     match_pfx_len = complete_cmd_dir_file(match_buf, find_type)
   }
   /* Account for backslashes which will be inserted
@@ -1376,10 +1441,11 @@ unsafe extern "C" fn input_tab(mut lastWasTab: *mut smallint) {
         let mut n_0: libc::c_uint = 0;
         n_0 = 1i32 as libc::c_uint;
         while n_0 < (*lineedit_ptr_to_statics).num_matches {
-          if *(*(*lineedit_ptr_to_statics).matches.offset(n_0 as isize))
-            .offset(cp.wrapping_offset_from(chosen_match) as libc::c_long as isize)
-            as libc::c_int
-            != *cp as libc::c_int
+          let mut c: libc::c_int = *(*(*lineedit_ptr_to_statics).matches.offset(n_0 as isize))
+            .offset(cp.offset_from(chosen_match) as isize)
+            as libc::c_int;
+          if c != *cp as libc::c_int
+            && (completion_ignore_case == 0 || libc::tolower(c) != libc::tolower(*cp as libc::c_int))
           {
             break 's_245;
           }
@@ -1427,6 +1493,21 @@ unsafe extern "C" fn input_tab(mut lastWasTab: *mut smallint) {
         < MAX_LINELEN as libc::c_int
       {
         let mut pos: libc::c_int = 0;
+        /* With completion-ignore-case, what was typed takes the case of the match */
+        if completion_ignore_case != 0
+          && cursor_mb >= match_pfx_len as libc::c_int
+          && strncasecmp(
+            command.offset((cursor_mb - match_pfx_len as libc::c_int) as isize),
+            chosen_match,
+            match_pfx_len as size_t,
+          ) == 0
+        {
+          memcpy(
+            command.offset((cursor_mb - match_pfx_len as libc::c_int) as isize) as *mut libc::c_void,
+            chosen_match as *const libc::c_void,
+            match_pfx_len as libc::c_ulong,
+          );
+        }
         /* save tail */
         strcpy(match_buf, &mut *command.offset(cursor_mb as isize));
         /* where do we want to have cursor after all? */
@@ -1523,15 +1604,36 @@ unsafe extern "C" fn get_next_history() -> libc::c_int {
 }
 /* Lists command history. Used by shell 'history' builtins */
 pub unsafe fn show_history(mut st: *const line_input_t) {
+  show_history_from(st, 0);
+}
+/* Same, starting with entry FIRST ("history N" lists the last N).
+ * If the shell has HISTTIMEFORMAT set, the time stamp of each entry
+ * is shown in front of it, formatted by strftime */
+pub unsafe fn show_history_from(mut st: *const line_input_t, mut first: libc::c_int) {
   let mut i: libc::c_int = 0;
+  let mut tbuf: [libc::c_char; 256] = [0; 256];
   if st.is_null() {
     return;
   }
-  i = 0;
+  i = if first > 0 { first } else { 0 };
   while i < (*st).cnt_history {
+    tbuf[0] = '\u{0}' as i32 as libc::c_char;
+    if !(*st).hist_timefmt.is_null() && (*st).hist_time[i as usize] != 0 {
+      let mut t: time_t = (*st).hist_time[i as usize];
+      let mut tm: *mut libc::tm = libc::localtime(&mut t);
+      if !tm.is_null() {
+        strftime(
+          tbuf.as_mut_ptr(),
+          ::std::mem::size_of::<[libc::c_char; 256]>() as libc::c_ulong,
+          (*st).hist_timefmt,
+          tm,
+        );
+      }
+    }
     printf(
-      b"%4d %s\n\x00" as *const u8 as *const libc::c_char,
+      b"%4d %s%s\n\x00" as *const u8 as *const libc::c_char,
       i,
+      tbuf.as_mut_ptr(),
       (*st).history[i as usize],
     );
     i += 1
@@ -1545,206 +1647,396 @@ pub unsafe fn free_line_input_t(mut n: *mut line_input_t) {
   }
   free(n as *mut libc::c_void);
 }
+/* Appends LINE (malloced) with time stamp T to st->history[],
+ * removing the oldest command if history[] is full */
+unsafe extern "C" fn hist_push(
+  mut st: *mut line_input_t,
+  mut line: *mut libc::c_char,
+  mut t: time_t,
+) {
+  let mut i: libc::c_int = (*st).cnt_history;
+  /* we need to keep history[state->max_history] empty, hence >=, not > */
+  if i >= (*st).max_history {
+    free((*st).history[0] as *mut libc::c_void);
+    i = 0;
+    while i < (*st).max_history - 1i32 {
+      (*st).history[i as usize] = (*st).history[(i + 1i32) as usize];
+      (*st).hist_time[i as usize] = (*st).hist_time[(i + 1i32) as usize];
+      i += 1
+    }
+    /* i == state->max_history-1 */
+    if (*st).cnt_history_saved > 0 {
+      (*st).cnt_history_saved -= 1
+    }
+  } else {
+    /* may hold the line being edited, see save_command_ps_at_cur_history */
+    free((*st).history[i as usize] as *mut libc::c_void);
+  }
+  (*st).history[i as usize] = line;
+  (*st).hist_time[i as usize] = t;
+  (*st).cnt_history = i + 1i32;
+}
+/* Removes entry N from st->history[] */
+unsafe extern "C" fn hist_delete(mut st: *mut line_input_t, mut n: libc::c_int) {
+  let mut i: libc::c_int = n;
+  free((*st).history[n as usize] as *mut libc::c_void);
+  while i < (*st).cnt_history {
+    (*st).history[i as usize] = (*st).history[(i + 1i32) as usize];
+    (*st).hist_time[i as usize] = (*st).hist_time[(i + 1i32) as usize];
+    i += 1
+  }
+  (*st).history[i as usize] = std::ptr::null_mut::<libc::c_char>();
+  (*st).cnt_history -= 1;
+  (*st).cur_history = (*st).cnt_history;
+  if n < (*st).cnt_history_saved {
+    (*st).cnt_history_saved -= 1
+  }
+}
+/* Is WORD one of the elements of colon-separated LIST? */
+unsafe extern "C" fn hist_in_list(
+  mut list: *const libc::c_char,
+  mut word: *const libc::c_char,
+) -> libc::c_int {
+  let mut len: libc::c_ulong = strlen(word);
+  if list.is_null() {
+    return 0;
+  }
+  loop {
+    let mut n: libc::c_ulong = strcspn(list, b":\x00" as *const u8 as *const libc::c_char);
+    if n == len && strncmp(list, word, len) == 0 {
+      return 1i32;
+    }
+    list = list.offset(n as isize);
+    if *list as libc::c_int == '\u{0}' as i32 {
+      return 0;
+    }
+    list = list.offset(1)
+  }
+}
+/* Does STR match one of the HISTIGNORE patterns?
+ * "&" stands for the previous history line */
+unsafe extern "C" fn hist_ignored(
+  mut st: *const line_input_t,
+  mut str: *const libc::c_char,
+) -> libc::c_int {
+  let mut p: *const libc::c_char = (*st).hist_ignore;
+  if p.is_null() {
+    return 0;
+  }
+  loop {
+    let mut n: libc::c_ulong = strcspn(p, b":\x00" as *const u8 as *const libc::c_char);
+    if n != 0 {
+      let mut pat: *mut libc::c_char = crate::libbb::xfuncs_printf::xstrndup(p, n as libc::c_int);
+      let mut r: libc::c_int = 0;
+      if strcmp(pat, b"&\x00" as *const u8 as *const libc::c_char) == 0 {
+        r = ((*st).cnt_history != 0
+          && strcmp((*st).history[((*st).cnt_history - 1i32) as usize], str) == 0)
+          as libc::c_int
+      } else {
+        r = (fnmatch(pat, str, 0) == 0) as libc::c_int
+      }
+      free(pat as *mut libc::c_void);
+      if r != 0 {
+        return 1i32;
+      }
+    }
+    p = p.offset(n as isize);
+    if *p as libc::c_int == '\u{0}' as i32 {
+      return 0;
+    }
+    p = p.offset(1)
+  }
+}
 /* We try to ensure that concurrent additions to the history
  * do not overwrite each other.
  * Otherwise shell users get unhappy.
  *
  * History file is trimmed lazily, when it grows several times longer
  * than configured MAX_HISTORY lines.
+ *
+ * A "#<digits>" line holds the time stamp of the entry following it.
  */
-/* state->flags is already checked to be nonzero */
-unsafe extern "C" fn load_history(mut st_parm: *mut line_input_t) {
+unsafe extern "C" fn load_history_fp(mut st_parm: *mut line_input_t, mut fp: *mut FILE) {
   let mut temp_h: [*mut libc::c_char; 255] = [0 as *mut libc::c_char; 255];
+  let mut temp_t: [time_t; 255] = [0; 255];
+  let mut stamp: time_t = 0;
   let mut line: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
-  let mut fp: *mut FILE = std::ptr::null_mut();
   let mut idx: libc::c_uint = 0;
   let mut i: libc::c_uint = 0;
   let mut line_len: libc::c_uint = 0;
-  /* NB: do not trash old history if file can't be opened */
-  fp = crate::libbb::wfopen::fopen_for_read((*st_parm).hist_file);
-  if !fp.is_null() {
-    /* clean up old history */
-    idx = (*st_parm).cnt_history as libc::c_uint;
-    while idx > 0 as libc::c_uint {
-      idx = idx.wrapping_sub(1);
-      free((*st_parm).history[idx as usize] as *mut libc::c_void);
-      (*st_parm).history[idx as usize] = std::ptr::null_mut::<libc::c_char>()
-    }
-    /* fill temp_h[], retaining only last MAX_HISTORY lines */
-    memset(
-      temp_h.as_mut_ptr() as *mut libc::c_void,
-      0,
-      ::std::mem::size_of::<[*mut libc::c_char; 255]>() as libc::c_ulong,
-    );
-    idx = 0 as libc::c_uint;
-    (*st_parm).cnt_history_in_file = 0 as libc::c_uint;
-    loop {
-      line = crate::libbb::get_line_from_file::xmalloc_fgetline(fp);
-      if line.is_null() {
-        break;
-      }
-      if *line.offset(0) as libc::c_int == '\u{0}' as i32 {
-        free(line as *mut libc::c_void);
-      } else {
-        free(temp_h[idx as usize] as *mut libc::c_void);
-        temp_h[idx as usize] = line;
-        (*st_parm).cnt_history_in_file = (*st_parm).cnt_history_in_file.wrapping_add(1);
-        idx = idx.wrapping_add(1);
-        if idx == (*st_parm).max_history as libc::c_uint {
-          idx = 0 as libc::c_uint
-        }
-      }
+  /* clean up old history */
+  idx = (*st_parm).cnt_history as libc::c_uint;
+  while idx > 0 as libc::c_uint {
+    idx = idx.wrapping_sub(1);
+    free((*st_parm).history[idx as usize] as *mut libc::c_void);
+    (*st_parm).history[idx as usize] = std::ptr::null_mut::<libc::c_char>()
+  }
+  /* fill temp_h[], retaining only last MAX_HISTORY lines */
+  idx = 0 as libc::c_uint;
+  (*st_parm).cnt_history_in_file = 0 as libc::c_uint;
+  loop {
+    line = crate::libbb::get_line_from_file::xmalloc_fgetline(fp);
+    if line.is_null() {
+      break;
     }
-    fclose(fp);
-    /* find first non-NULL temp_h[], if any */
-    if (*st_parm).cnt_history_in_file != 0 {
-      while temp_h[idx as usize].is_null() {
-        idx = idx.wrapping_add(1);
-        if idx == (*st_parm).max_history as libc::c_uint {
-          idx = 0 as libc::c_uint
-        }
+    if *line.offset(0) as libc::c_int == '\u{0}' as i32 {
+      free(line as *mut libc::c_void);
+    } else if *line.offset(0) as libc::c_int == '#' as i32
+      && (*line.offset(1) as libc::c_int - '0' as i32) as libc::c_uint <= 9i32 as libc::c_uint
+    {
+      stamp = strtoul(line.offset(1), std::ptr::null_mut(), 10i32) as time_t;
+      free(line as *mut libc::c_void);
+    } else {
+      free(temp_h[idx as usize] as *mut libc::c_void);
+      temp_h[idx as usize] = line;
+      temp_t[idx as usize] = stamp;
+      stamp = 0;
+      (*st_parm).cnt_history_in_file = (*st_parm).cnt_history_in_file.wrapping_add(1);
+      idx = idx.wrapping_add(1);
+      if idx == (*st_parm).max_history as libc::c_uint {
+        idx = 0 as libc::c_uint
       }
     }
-    /* copy temp_h[] to st_parm->history[] */
-    i = 0 as libc::c_uint; /* paranoia */
-    while i < (*st_parm).max_history as libc::c_uint {
-      line = temp_h[idx as usize]; /* we (try to) do atomic write */
-      if line.is_null() {
-        break; /* "wtf?" */
-      }
+  }
+  /* find first non-NULL temp_h[], if any */
+  if (*st_parm).cnt_history_in_file != 0 {
+    while temp_h[idx as usize].is_null() {
       idx = idx.wrapping_add(1);
       if idx == (*st_parm).max_history as libc::c_uint {
         idx = 0 as libc::c_uint
       }
-      line_len = strlen(line) as libc::c_uint;
-      if line_len >= MAX_LINELEN as libc::c_int as libc::c_uint {
-        *line.offset((MAX_LINELEN as libc::c_int - 1i32) as isize) = '\u{0}' as i32 as libc::c_char
-      }
-      let fresh17 = i;
-      i = i.wrapping_add(1);
-      (*st_parm).history[fresh17 as usize] = line
     }
-    (*st_parm).cnt_history = i as libc::c_int
+  }
+  /* copy temp_h[] to st_parm->history[] */
+  i = 0 as libc::c_uint;
+  while i < (*st_parm).max_history as libc::c_uint {
+    line = temp_h[idx as usize];
+    if line.is_null() {
+      break;
+    }
+    (*st_parm).hist_time[i as usize] = temp_t[idx as usize];
+    idx = idx.wrapping_add(1);
+    if idx == (*st_parm).max_history as libc::c_uint {
+      idx = 0 as libc::c_uint
+    }
+    line_len = strlen(line) as libc::c_uint;
+    if line_len >= MAX_LINELEN as libc::c_int as libc::c_uint {
+      *line.offset((MAX_LINELEN as libc::c_int - 1i32) as isize) = '\u{0}' as i32 as libc::c_char
+    }
+    let fresh17 = i;
+    i = i.wrapping_add(1);
+    (*st_parm).history[fresh17 as usize] = line
+  }
+  (*st_parm).cnt_history = i as libc::c_int;
+  (*st_parm).cnt_history_saved = i as libc::c_int;
+}
+/* state->flags is already checked to be nonzero */
+unsafe extern "C" fn load_history(mut st_parm: *mut line_input_t) {
+  let mut fp: *mut FILE = std::ptr::null_mut();
+  /* NB: do not trash old history if file can't be opened */
+  fp = crate::libbb::wfopen::fopen_for_read((*st_parm).hist_file);
+  if !fp.is_null() {
+    /* don't read it while another shell is rewriting it */
+    libc::flock(libc::fileno(fp), libc::LOCK_SH);
+    load_history_fp(st_parm, fp);
+    fclose(fp);
   };
 }
-unsafe extern "C" fn save_history(mut str: *mut libc::c_char) {
+/* Writes history[from..] to FP. Entries are preceded by their
+ * "#<epoch>" time stamp lines if the shell has HISTTIMEFORMAT set */
+unsafe extern "C" fn write_history_entries(
+  mut st: *const line_input_t,
+  mut fp: *mut FILE,
+  mut from: libc::c_int,
+) {
+  let mut i: libc::c_int = from;
+  while i < (*st).cnt_history {
+    if !(*st).hist_timefmt.is_null() && (*st).hist_time[i as usize] != 0 {
+      fprintf(
+        fp,
+        b"#%lu\n\x00" as *const u8 as *const libc::c_char,
+        (*st).hist_time[i as usize] as libc::c_ulong,
+      );
+    }
+    fprintf(
+      fp,
+      b"%s\n\x00" as *const u8 as *const libc::c_char,
+      (*st).history[i as usize],
+    );
+    i += 1
+  }
+  libc::fflush(fp);
+}
+/* Appends the commands entered since the last save to the history file.
+ * Used for every command entered, by the shells on exit and by "history -a".
+ * The file stays locked while we write and trim it, thus shells exiting
+ * at the same time merge their commands instead of losing some.
+ */
+pub unsafe fn save_history(mut st: *mut line_input_t) {
   let mut fd: libc::c_int = 0;
-  let mut len: libc::c_int = 0;
-  let mut len2: libc::c_int = 0;
-  if (*(*lineedit_ptr_to_statics).state).hist_file.is_null() {
+  let mut fp: *mut FILE = std::ptr::null_mut();
+  if st.is_null() || (*st).hist_file.is_null() || (*st).flags & DO_HISTORY as libc::c_int == 0 {
     return;
   }
-  fd = open(
-    (*(*lineedit_ptr_to_statics).state).hist_file,
-    0o1i32 | 0o100i32 | 0o2000i32,
-    0o600i32,
-  );
-  if fd < 0 {
+  if (*st).cnt_history_saved >= (*st).cnt_history {
     return;
   }
-  crate::libbb::xfuncs_printf::xlseek(fd, 0 as off_t, 2i32);
-  len = strlen(str) as libc::c_int;
-  *str.offset(len as isize) = '\n' as i32 as libc::c_char;
-  len2 =
-    crate::libbb::full_write::full_write(fd, str as *const libc::c_void, (len + 1i32) as size_t)
-      as libc::c_int;
-  *str.offset(len as isize) = '\u{0}' as i32 as libc::c_char;
-  close(fd);
-  if len2 != len + 1i32 {
+  fd = open((*st).hist_file, 0o1i32 | 0o100i32 | 0o2000i32, 0o600i32);
+  if fd < 0 {
     return;
   }
-  /* did we write so much that history file needs trimming? */
-  (*(*lineedit_ptr_to_statics).state).cnt_history_in_file = (*(*lineedit_ptr_to_statics).state)
+  libc::flock(fd, libc::LOCK_EX);
+  fp = crate::libbb::wfopen::xfdopen_for_write(fd);
+  write_history_entries(st, fp, (*st).cnt_history_saved);
+  (*st).cnt_history_in_file = (*st)
     .cnt_history_in_file
-    .wrapping_add(1);
-  if (*(*lineedit_ptr_to_statics).state).cnt_history_in_file
-    > ((*(*lineedit_ptr_to_statics).state).max_history * 4i32) as libc::c_uint
-  {
-    let mut new_name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    .wrapping_add(((*st).cnt_history - (*st).cnt_history_saved) as libc::c_uint);
+  (*st).cnt_history_saved = (*st).cnt_history;
+  /* did we write so much that history file needs trimming? */
+  if (*st).cnt_history_in_file > ((*st).max_history * 4i32) as libc::c_uint {
     let mut st_temp: *mut line_input_t = std::ptr::null_mut();
+    let mut rfp: *mut FILE = std::ptr::null_mut();
     /* we may have concurrently written entries from others.
      * load them */
-    st_temp = new_line_input_t((*(*lineedit_ptr_to_statics).state).flags);
-    (*st_temp).hist_file = (*(*lineedit_ptr_to_statics).state).hist_file;
-    (*st_temp).max_history = (*(*lineedit_ptr_to_statics).state).max_history;
-    load_history(st_temp);
-    /* write out temp file and replace hist_file atomically */
-    new_name = crate::libbb::xfuncs_printf::xasprintf(
-      b"%s.%u.new\x00" as *const u8 as *const libc::c_char,
-      (*(*lineedit_ptr_to_statics).state).hist_file,
-      getpid(),
-    );
-    fd = open(new_name, 0o1i32 | 0o100i32 | 0o1000i32, 0o600i32);
-    if fd >= 0 {
-      let mut fp: *mut FILE = std::ptr::null_mut();
-      let mut i: libc::c_int = 0;
-      fp = crate::libbb::wfopen::xfdopen_for_write(fd);
-      i = 0;
-      while i < (*st_temp).cnt_history {
-        fprintf(
-          fp,
-          b"%s\n\x00" as *const u8 as *const libc::c_char,
-          (*st_temp).history[i as usize],
-        );
-        i += 1
-      }
-      fclose(fp);
-      if rename(new_name, (*(*lineedit_ptr_to_statics).state).hist_file) == 0 {
-        (*(*lineedit_ptr_to_statics).state).cnt_history_in_file =
-          (*st_temp).cnt_history as libc::c_uint
+    st_temp = new_line_input_t((*st).flags);
+    (*st_temp).max_history = (*st).max_history;
+    /* keep the time stamps which are in the file */
+    (*st_temp).hist_timefmt = b"\x00" as *const u8 as *const libc::c_char;
+    rfp = crate::libbb::wfopen::fopen_for_read((*st).hist_file);
+    if !rfp.is_null() {
+      load_history_fp(st_temp, rfp);
+      fclose(rfp);
+      /* Rewrite it in place: we hold the lock. After a rename,
+       * shells waiting for the lock would append to the old file */
+      if libc::ftruncate(fd, 0) == 0 {
+        write_history_entries(st_temp, fp, 0);
+        (*st).cnt_history_in_file = (*st_temp).cnt_history as libc::c_uint
       }
     }
-    free(new_name as *mut libc::c_void);
     free_line_input_t(st_temp);
+  }
+  fclose(fp); /* releases the lock */
+}
+/* "history -w": replaces the history file with the current history */
+pub unsafe fn write_history(mut st: *mut line_input_t) {
+  let mut fd: libc::c_int = 0;
+  let mut fp: *mut FILE = std::ptr::null_mut();
+  if st.is_null() || (*st).hist_file.is_null() {
+    return;
+  }
+  fd = open((*st).hist_file, 0o1i32 | 0o100i32, 0o600i32);
+  if fd < 0 {
+    return;
+  }
+  libc::flock(fd, libc::LOCK_EX);
+  if libc::ftruncate(fd, 0) == 0 {
+    fp = crate::libbb::wfopen::xfdopen_for_write(fd);
+    write_history_entries(st, fp, 0);
+    fclose(fp);
+    (*st).cnt_history_in_file = (*st).cnt_history as libc::c_uint;
+    (*st).cnt_history_saved = (*st).cnt_history
+  } else {
+    close(fd);
   };
 }
+/* "history -r": appends the contents of the history file to the history */
+pub unsafe fn read_history(mut st: *mut line_input_t) {
+  let mut st_temp: *mut line_input_t = std::ptr::null_mut();
+  let mut i: libc::c_int = 0;
+  if st.is_null() || (*st).hist_file.is_null() {
+    return;
+  }
+  st_temp = new_line_input_t((*st).flags);
+  (*st_temp).hist_file = (*st).hist_file;
+  (*st_temp).max_history = (*st).max_history;
+  load_history(st_temp);
+  while i < (*st_temp).cnt_history {
+    hist_push(st, (*st_temp).history[i as usize], (*st_temp).hist_time[i as usize]);
+    i += 1
+  }
+  (*st_temp).cnt_history = 0;
+  (*st).cnt_history_in_file = (*st_temp).cnt_history_in_file;
+  /* what we have now is in the file */
+  (*st).cnt_history_saved = (*st).cnt_history;
+  (*st).cur_history = (*st).cnt_history;
+  free_line_input_t(st_temp);
+}
+/* "history -c" */
+pub unsafe fn clear_history(mut st: *mut line_input_t) {
+  let mut i: libc::c_int = 0;
+  if st.is_null() {
+    return;
+  }
+  /* history[cnt_history] may hold the line being edited */
+  while i <= (*st).cnt_history {
+    free((*st).history[i as usize] as *mut libc::c_void);
+    (*st).history[i as usize] = std::ptr::null_mut::<libc::c_char>();
+    i += 1
+  }
+  (*st).cnt_history = 0;
+  (*st).cur_history = 0;
+  (*st).cnt_history_saved = 0;
+}
+/* "history -d N". Returns -1 if there is no entry N */
+pub unsafe fn delete_history(mut st: *mut line_input_t, mut n: libc::c_int) -> libc::c_int {
+  if st.is_null() || n < 0 || n >= (*st).cnt_history {
+    return -1i32;
+  }
+  hist_delete(st, n);
+  return 0;
+}
 /* FEATURE_COMMAND_SAVEHISTORY */
 unsafe extern "C" fn remember_in_history(mut str: *mut libc::c_char) {
+  let mut st: *mut line_input_t = (*lineedit_ptr_to_statics).state;
   let mut i: libc::c_int = 0;
-  if (*(*lineedit_ptr_to_statics).state).flags & DO_HISTORY as libc::c_int == 0 {
+  if (*st).flags & DO_HISTORY as libc::c_int == 0 {
     return;
   }
   if *str.offset(0) as libc::c_int == '\u{0}' as i32 {
     return;
   }
-  i = (*(*lineedit_ptr_to_statics).state).cnt_history;
-  /* Don't save dupes */
-  if i != 0
-    && strcmp(
-      (*(*lineedit_ptr_to_statics).state).history[(i - 1i32) as usize],
-      str,
-    ) == 0
+  if *str.offset(0) as libc::c_int == ' ' as i32
+    && (hist_in_list(
+      (*st).hist_control,
+      b"ignorespace\x00" as *const u8 as *const libc::c_char,
+    ) != 0
+      || hist_in_list(
+        (*st).hist_control,
+        b"ignoreboth\x00" as *const u8 as *const libc::c_char,
+      ) != 0)
   {
     return;
-  } /* redundant, paranoia */
-  free(
-    (*(*lineedit_ptr_to_statics).state).history
-      [(*(*lineedit_ptr_to_statics).state).max_history as usize] as *mut libc::c_void,
-  ); /* redundant, paranoia */
-  (*(*lineedit_ptr_to_statics).state).history
-    [(*(*lineedit_ptr_to_statics).state).max_history as usize] =
-    std::ptr::null_mut::<libc::c_char>();
-  /* If history[] is full, remove the oldest command */
-  /* we need to keep history[state->max_history] empty, hence >=, not > */
-  if i >= (*(*lineedit_ptr_to_statics).state).max_history {
-    free((*(*lineedit_ptr_to_statics).state).history[0] as *mut libc::c_void);
-    i = 0;
-    while i < (*(*lineedit_ptr_to_statics).state).max_history - 1i32 {
-      (*(*lineedit_ptr_to_statics).state).history[i as usize] =
-        (*(*lineedit_ptr_to_statics).state).history[(i + 1i32) as usize];
-      i += 1
-    }
-    /* i == state->max_history-1 */
   }
-  /* i <= state->max_history-1 */
-  let fresh18 = i;
-  i = i + 1;
-  (*(*lineedit_ptr_to_statics).state).history[fresh18 as usize] =
-    crate::libbb::xfuncs_printf::xstrdup(str);
-  /* i <= state->max_history */
-  (*(*lineedit_ptr_to_statics).state).cur_history = i;
-  (*(*lineedit_ptr_to_statics).state).cnt_history = i;
-  save_history(str);
+  i = (*st).cnt_history;
+  /* Don't save dupes (HISTCONTROL=ignoredups is always in effect) */
+  if i != 0 && strcmp((*st).history[(i - 1i32) as usize], str) == 0 {
+    return;
+  }
+  if hist_ignored(st, str) != 0 {
+    return;
+  }
+  if hist_in_list(
+    (*st).hist_control,
+    b"erasedups\x00" as *const u8 as *const libc::c_char,
+  ) != 0
+  {
+    while i > 0 {
+      i -= 1;
+      if strcmp((*st).history[i as usize], str) == 0 {
+        hist_delete(st, i);
+      }
+    }
+  } /* redundant, paranoia */
+  free((*st).history[(*st).max_history as usize] as *mut libc::c_void); /* redundant, paranoia */
+  (*st).history[(*st).max_history as usize] = std::ptr::null_mut::<libc::c_char>();
+  hist_push(
+    st,
+    crate::libbb::xfuncs_printf::xstrdup(str),
+    libc::time(std::ptr::null_mut()),
+  );
+  (*st).cur_history = (*st).cnt_history;
+  /* Write it out now: a shell killed by SIGHUP never gets to exit */
+  save_history(st);
 }
 /* MAX_HISTORY == 0 */
 /* MAX_HISTORY */
@@ -3087,6 +3379,23 @@ unsafe extern "C" fn lineedit_read_key(
   let mut unicode_idx: libc::c_int = 0;
   crate::libbb::xfuncs_printf::fflush_all();
   let mut wc: wchar_t = 0;
+  /* A bound macro is typed in as if it came from the keyboard */
+  if !(*lineedit_ptr_to_statics).macro_pending.is_null() && *read_key_buffer == 0 {
+    let mut len: libc::c_uint = strlen((*lineedit_ptr_to_statics).macro_pending) as libc::c_uint;
+    if len > (KEYCODE_BUFFER_SIZE as libc::c_int - 1i32) as libc::c_uint {
+      len = (KEYCODE_BUFFER_SIZE as libc::c_int - 1i32) as libc::c_uint
+    }
+    crate::libbb::read_key::read_key_ungets(
+      read_key_buffer,
+      (*lineedit_ptr_to_statics).macro_pending,
+      len,
+    );
+    (*lineedit_ptr_to_statics).macro_pending =
+      (*lineedit_ptr_to_statics).macro_pending.offset(len as isize);
+    if *(*lineedit_ptr_to_statics).macro_pending == 0 {
+      (*lineedit_ptr_to_statics).macro_pending = std::ptr::null()
+    }
+  }
   loop {
     /* Wait for input. TIMEOUT = -1 makes read_key wait even
      * on nonblocking stdin, TIMEOUT = 50 makes sure we won't
@@ -3823,6 +4132,1159 @@ unsafe extern "C" fn sigaction2(mut sig: libc::c_int, mut act: *mut sigaction) {
  * (in both cases the cursor remains on the input line, '\n' is not printed)
  * >0 length of input string, including terminating '\n'
  */
+/*
+ * Key bindings from inputrc and the "bind" builtin.
+ * They are looked at before the built-in keys of read_line_input(),
+ * per keymap: emacs, vi insert mode and vi command mode.
+ */
+pub const KEYMAP_EMACS: libc::c_int = 0;
+pub const KEYMAP_VI_INSERT: libc::c_int = 1;
+pub const KEYMAP_VI_COMMAND: libc::c_int = 2;
+/* Bindable functions which have no key in read_line_input()'s switch.
+ * They are out of KEYCODE_xxx range and are never self-inserted */
+pub const LE_FN_NOP: libc::c_int = -1024;
+pub const LE_FN_SELF_INSERT: libc::c_int = -1025;
+pub const LE_FN_EDIT_AND_EXECUTE: libc::c_int = -1026;
+pub const LE_FN_VI_EDITING_MODE: libc::c_int = -1027;
+pub const LE_FN_EMACS_EDITING_MODE: libc::c_int = -1028;
+pub const LE_FN_VI_MOVEMENT_MODE: libc::c_int = -1029;
+pub const LE_FN_VI_INSERTION_MODE: libc::c_int = -1030;
+pub const LE_FN_ABORT: libc::c_int = -1031;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct key_binding {
+  pub next: *mut key_binding,
+  pub keymap: libc::c_int,
+  pub nkeys: libc::c_int,
+  /* as returned by lineedit_read_key() */
+  pub keys: [libc::c_int; 8],
+  /* key code to act as, or LE_FN_xxx; 0: type in macro */
+  pub func: libc::c_int,
+  pub macro_0: *mut libc::c_char,
+  /* "KEYSEQ: ACTION" as given, for bind -p */
+  pub line: *mut libc::c_char,
+}
+static mut key_bindings: *mut key_binding = 0 as *mut key_binding;
+/* where inputrc lines and bind put new bindings */
+static mut cur_keymap: libc::c_int = KEYMAP_EMACS;
+/* "set editing-mode" not yet applied to line_input_t: -1 none, 0 emacs, 1 vi */
+static mut pending_editing_mode: libc::c_int = -1i32;
+static mut completion_ignore_case: smallint = 0 as smallint;
+static mut bell_style_none: smallint = 0 as smallint;
+static mut inputrc_loaded: smallint = 0 as smallint;
+static mut bind_fn_names: [libc::c_char; 392] = unsafe {
+  *::std::mem::transmute::<&[u8; 392], &[libc::c_char; 392]>(
+    b"accept-line\x00beginning-of-line\x00end-of-line\x00backward-char\x00forward-char\x00backward-word\x00forward-word\x00backward-delete-char\x00delete-char\x00complete\x00kill-line\x00unix-line-discard\x00unix-word-rubout\x00kill-word\x00backward-kill-word\x00clear-screen\x00previous-history\x00next-history\x00reverse-search-history\x00self-insert\x00edit-and-execute-command\x00vi-editing-mode\x00emacs-editing-mode\x00vi-movement-mode\x00vi-insertion-mode\x00abort\x00\x00",
+  )
+};
+/* what each of bind_fn_names[] is in read_line_input() */
+static mut bind_fn_codes: [libc::c_int; 26] = [
+  13i32,
+  1i32,
+  5i32,
+  2i32,
+  6i32,
+  KEYCODE_CTRL_LEFT as libc::c_int,
+  KEYCODE_CTRL_RIGHT as libc::c_int,
+  127i32,
+  KEYCODE_DELETE as libc::c_int,
+  9i32,
+  11i32,
+  21i32,
+  23i32,
+  KEYCODE_ALT_D as libc::c_int,
+  KEYCODE_ALT_BACKSPACE as libc::c_int,
+  12i32,
+  16i32,
+  14i32,
+  18i32,
+  LE_FN_SELF_INSERT,
+  LE_FN_EDIT_AND_EXECUTE,
+  LE_FN_VI_EDITING_MODE,
+  LE_FN_EMACS_EDITING_MODE,
+  LE_FN_VI_MOVEMENT_MODE,
+  LE_FN_VI_INSERTION_MODE,
+  LE_FN_ABORT,
+];
+static mut keymap_names: [libc::c_char; 54] = unsafe {
+  *::std::mem::transmute::<&[u8; 54], &[libc::c_char; 54]>(
+    b"emacs\x00emacs-standard\x00vi-insert\x00vi\x00vi-command\x00vi-move\x00\x00",
+  )
+};
+static mut keymap_codes: [libc::c_int; 6] = [
+  KEYMAP_EMACS,
+  KEYMAP_EMACS,
+  KEYMAP_VI_INSERT,
+  KEYMAP_VI_COMMAND,
+  KEYMAP_VI_COMMAND,
+  KEYMAP_VI_COMMAND,
+];
+static mut key_names: [libc::c_char; 60] = unsafe {
+  *::std::mem::transmute::<&[u8; 60], &[libc::c_char; 60]>(
+    b"DEL\x00ESC\x00ESCAPE\x00LFD\x00NEWLINE\x00RET\x00RETURN\x00RUBOUT\x00SPACE\x00SPC\x00TAB\x00\x00",
+  )
+};
+static mut key_name_codes: [libc::c_int; 11] =
+  [127i32, 27i32, 27i32, 10i32, 10i32, 13i32, 13i32, 127i32, 32i32, 32i32, 9i32];
+unsafe extern "C" fn BB_isalnum_or_underscore(mut c: wchar_t) -> bool {
+  return (c as libc::c_uint) < 256i32 as libc::c_uint && libc::isalnum(c) != 0 || c == '_' as i32;
+}
+unsafe extern "C" fn ctrl_key(mut c: libc::c_int) -> libc::c_int {
+  if c == '?' as i32 {
+    return 127i32;
+  }
+  return c & 0x1fi32;
+}
+/* Unescape readline's "\C-x", "\M-x", "\e", "\n", "\177", "\x7f"...
+ * from S up to the closing QUOTE into BUF. Returns the length,
+ * or -1 if the quote is not closed. *END_P is set past the quote.
+ */
+unsafe extern "C" fn unescape_keyseq(
+  mut s: *const libc::c_char,
+  mut quote: libc::c_int,
+  mut buf: *mut libc::c_char,
+  mut bufsize: libc::c_int,
+  mut end_p: *mut *const libc::c_char,
+) -> libc::c_int {
+  let mut n: libc::c_int = 0;
+  while *s as libc::c_int != 0 && *s as libc::c_int != quote && n < bufsize - 1i32 {
+    let mut c: libc::c_int = *s as libc::c_uchar as libc::c_int;
+    s = s.offset(1);
+    if c == '\\' as i32 && *s as libc::c_int != 0 {
+      c = *s as libc::c_uchar as libc::c_int;
+      s = s.offset(1);
+      if (c == 'C' as i32 || c == 'M' as i32)
+        && *s as libc::c_int == '-' as i32
+        && *s.offset(1) as libc::c_int != 0
+      {
+        s = s.offset(1);
+        if c == 'M' as i32 {
+          /* meta is ESC prefix */
+          *buf.offset(n as isize) = 27i32 as libc::c_char;
+          n += 1;
+          continue;
+        }
+        c = ctrl_key(*s as libc::c_uchar as libc::c_int);
+        s = s.offset(1)
+      } else {
+        match c {
+          101 => c = 27i32,
+          97 => c = 7i32,
+          98 => c = 8i32,
+          100 => c = 127i32,
+          102 => c = 12i32,
+          110 => c = 10i32,
+          114 => c = 13i32,
+          116 => c = 9i32,
+          118 => c = 11i32,
+          48..=55 => {
+            let mut i: libc::c_int = 1i32;
+            c -= '0' as i32;
+            while i < 3i32 && (*s as libc::c_int - '0' as i32) as libc::c_uint <= 7i32 as libc::c_uint
+            {
+              c = c * 8i32 + (*s as libc::c_int - '0' as i32);
+              s = s.offset(1);
+              i += 1
+            }
+          }
+          120 => {
+            let mut i_0: libc::c_int = 0;
+            c = 0;
+            while i_0 < 2i32 && libc::isxdigit(*s as libc::c_int) != 0 {
+              c = c * 16i32
+                + if (*s as libc::c_int) <= '9' as i32 {
+                  *s as libc::c_int - '0' as i32
+                } else {
+                  (*s as libc::c_int | 0x20i32) - 'a' as i32 + 10i32
+                };
+              s = s.offset(1);
+              i_0 += 1
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+    *buf.offset(n as isize) = c as libc::c_char;
+    n += 1
+  }
+  if *s as libc::c_int != quote {
+    return -1i32;
+  }
+  *end_p = s.offset(1);
+  *buf.offset(n as isize) = '\u{0}' as i32 as libc::c_char;
+  return n;
+}
+/* Parse a key sequence, "\C-x\C-e" or a key name like Control-u or M-DEL,
+ * into what lineedit_read_key() returns for it. Returns the number
+ * of keys, 0 if it's bad. *END_P is set past the sequence.
+ */
+unsafe extern "C" fn parse_keyseq(
+  mut s: *const libc::c_char,
+  mut end_p: *mut *const libc::c_char,
+  mut keys: *mut libc::c_int,
+) -> libc::c_int {
+  let mut buf: [libc::c_char; 32] = [0; 32];
+  let mut len: libc::c_int = 0;
+  let mut i: libc::c_int = 0;
+  let mut n: libc::c_int = 0;
+  if *s as libc::c_int == '\"' as i32 {
+    len = unescape_keyseq(s.offset(1), '\"' as i32, buf.as_mut_ptr(), 32i32, end_p);
+    if len <= 0 {
+      return 0;
+    }
+  } else {
+    let mut ctrl: libc::c_int = 0;
+    let mut c: libc::c_int = 0;
+    let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    loop {
+      if strncasecmp(s, b"Control-\x00" as *const u8 as *const libc::c_char, 8i32 as size_t) == 0 {
+        ctrl = 1i32;
+        s = s.offset(8)
+      } else if strncasecmp(s, b"Meta-\x00" as *const u8 as *const libc::c_char, 5i32 as size_t)
+        == 0
+      {
+        buf[len as usize] = 27i32 as libc::c_char;
+        len += 1;
+        s = s.offset(5)
+      } else if (*s as libc::c_int | 0x20i32 == 'c' as i32 || *s as libc::c_int | 0x20i32 == 'm' as i32)
+        && *s.offset(1) as libc::c_int == '-' as i32
+        && *s.offset(2) as libc::c_int != 0
+      {
+        if *s as libc::c_int | 0x20i32 == 'c' as i32 {
+          ctrl = 1i32
+        } else {
+          buf[len as usize] = 27i32 as libc::c_char;
+          len += 1
+        }
+        s = s.offset(2)
+      } else {
+        break;
+      }
+      if len > 4i32 {
+        return 0;
+      }
+    }
+    if *s == 0 {
+      return 0;
+    }
+    /* the key itself may be ':' */
+    n = strcspn(s.offset(1), b": \t\x00" as *const u8 as *const libc::c_char) as libc::c_int + 1i32;
+    name = crate::libbb::xfuncs_printf::xstrndup(s, n);
+    if n == 1i32 {
+      c = *name as libc::c_uchar as libc::c_int
+    } else {
+      i = crate::libbb::compare_string_array::index_in_strings(key_names.as_ptr(), name);
+      if i < 0 {
+        /* key names are case insensitive */
+        let mut p: *mut libc::c_char = name;
+        while *p != 0 {
+          *p = libc::toupper(*p as libc::c_int) as libc::c_char;
+          p = p.offset(1)
+        }
+        i = crate::libbb::compare_string_array::index_in_strings(key_names.as_ptr(), name)
+      }
+      c = if i < 0 { -1i32 } else { key_name_codes[i as usize] }
+    }
+    free(name as *mut libc::c_void);
+    if c < 0 {
+      return 0;
+    }
+    if ctrl != 0 {
+      c = ctrl_key(c)
+    }
+    buf[len as usize] = c as libc::c_char;
+    len += 1;
+    buf[len as usize] = '\u{0}' as i32 as libc::c_char;
+    *end_p = s.offset(n as isize)
+  }
+  /* ESC sequences of cursor and function keys come as one keycode */
+  while i < len && n < 8i32 {
+    let mut seqlen: libc::c_int = 0;
+    let mut c_0: libc::c_int = buf[i as usize] as libc::c_uchar as libc::c_int;
+    i += 1;
+    if c_0 == 27i32 && i < len {
+      let mut k: libc::c_int =
+        crate::libbb::read_key::read_key_decode_esc(buf.as_mut_ptr().offset(i as isize), &mut seqlen);
+      if k != -1i32 {
+        c_0 = k;
+        i += seqlen
+      }
+    }
+    *keys.offset(n as isize) = c_0;
+    n += 1
+  }
+  return n;
+}
+unsafe extern "C" fn bind_key(
+  mut keymap: libc::c_int,
+  mut keys: *const libc::c_int,
+  mut nkeys: libc::c_int,
+  mut func: libc::c_int,
+  mut macro_0: *mut libc::c_char,
+  mut line: *mut libc::c_char,
+) {
+  let mut kbp: *mut *mut key_binding = &mut key_bindings;
+  let mut kb: *mut key_binding = std::ptr::null_mut();
+  /* a new binding replaces the old one */
+  while !(*kbp).is_null() {
+    kb = *kbp;
+    if (*kb).keymap == keymap
+      && (*kb).nkeys == nkeys
+      && memcmp(
+        (*kb).keys.as_mut_ptr() as *const libc::c_void,
+        keys as *const libc::c_void,
+        (nkeys as libc::c_ulong).wrapping_mul(::std::mem::size_of::<libc::c_int>() as libc::c_ulong),
+      ) == 0
+    {
+      *kbp = (*kb).next;
+      free((*kb).macro_0 as *mut libc::c_void);
+      free((*kb).line as *mut libc::c_void);
+      free(kb as *mut libc::c_void);
+      break;
+    } else {
+      kbp = &mut (*kb).next
+    }
+  }
+  if line.is_null() {
+    /* unbind */
+    return;
+  }
+  kb = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<key_binding>() as libc::c_ulong)
+    as *mut key_binding;
+  (*kb).keymap = keymap;
+  (*kb).nkeys = nkeys;
+  memcpy(
+    (*kb).keys.as_mut_ptr() as *mut libc::c_void,
+    keys as *const libc::c_void,
+    (nkeys as libc::c_ulong).wrapping_mul(::std::mem::size_of::<libc::c_int>() as libc::c_ulong),
+  );
+  (*kb).func = func;
+  (*kb).macro_0 = macro_0;
+  (*kb).line = line;
+  (*kb).next = key_bindings;
+  key_bindings = kb;
+}
+/* Process one inputrc line: "set VARIABLE VALUE",
+ * "KEYSEQ: FUNCTION-NAME" or "KEYSEQ: "MACRO"".
+ * Returns 0, or -1 (after a message) if it makes no sense.
+ */
+pub unsafe fn lineedit_parse_and_bind(mut line: *const libc::c_char) -> libc::c_int {
+  let mut keys: [libc::c_int; 8] = [0; 8];
+  let mut nkeys: libc::c_int = 0;
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut end: *const libc::c_char = std::ptr::null();
+  let mut func: libc::c_int = 0;
+  let mut macro_0: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  line = crate::libbb::skip_whitespace::skip_whitespace(line);
+  if *line == 0 || *line as libc::c_int == '#' as i32 {
+    return 0;
+  }
+  if strncmp(line, b"set\x00" as *const u8 as *const libc::c_char, 3i32 as size_t) == 0
+    && (*line.offset(3) as libc::c_int == ' ' as i32 || *line.offset(3) as libc::c_int == '\t' as i32)
+  {
+    let mut var: *const libc::c_char = crate::libbb::skip_whitespace::skip_whitespace(line.offset(3));
+    let mut val: *const libc::c_char = crate::libbb::skip_whitespace::skip_non_whitespace(var);
+    let mut name: *mut libc::c_char =
+      crate::libbb::xfuncs_printf::xstrndup(var, val.offset_from(var) as libc::c_int);
+    let mut value: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut on: libc::c_int = 0;
+    let mut rc: libc::c_int = 0;
+    val = crate::libbb::skip_whitespace::skip_whitespace(val);
+    value = crate::libbb::xfuncs_printf::xstrndup(
+      val,
+      crate::libbb::skip_whitespace::skip_non_whitespace(val).offset_from(val) as libc::c_int,
+    );
+    on = (*value == 0
+      || strcasecmp(value, b"on\x00" as *const u8 as *const libc::c_char) == 0
+      || strcmp(value, b"1\x00" as *const u8 as *const libc::c_char) == 0) as libc::c_int;
+    match crate::libbb::compare_string_array::index_in_strings(
+      b"editing-mode\x00completion-ignore-case\x00keymap\x00bell-style\x00\x00" as *const u8
+        as *const libc::c_char,
+      name,
+    ) {
+      0 => {
+        if strcmp(value, b"vi\x00" as *const u8 as *const libc::c_char) == 0 {
+          pending_editing_mode = 1i32;
+          cur_keymap = KEYMAP_VI_INSERT
+        } else if strcmp(value, b"emacs\x00" as *const u8 as *const libc::c_char) == 0 {
+          pending_editing_mode = 0;
+          cur_keymap = KEYMAP_EMACS
+        } else {
+          rc = -1i32
+        }
+      }
+      1 => completion_ignore_case = on as smallint,
+      2 => {
+        let mut i: libc::c_int =
+          crate::libbb::compare_string_array::index_in_strings(keymap_names.as_ptr(), value);
+        if i < 0 {
+          rc = -1i32
+        } else {
+          cur_keymap = keymap_codes[i as usize]
+        }
+      }
+      3 => {
+        bell_style_none =
+          (strcmp(value, b"none\x00" as *const u8 as *const libc::c_char) == 0) as libc::c_int
+            as smallint
+      }
+      _ => {}
+    }
+    /* unknown variables are ignored, there are too many in readline */
+    if rc != 0 {
+      crate::libbb::verror_msg::bb_error_msg(
+        b"%s: bad value \'%s\'\x00" as *const u8 as *const libc::c_char,
+        name,
+        value,
+      );
+    }
+    free(name as *mut libc::c_void);
+    free(value as *mut libc::c_void);
+    return rc;
+  }
+  nkeys = parse_keyseq(line, &mut end, keys.as_mut_ptr());
+  if nkeys == 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"bad key sequence: %s\x00" as *const u8 as *const libc::c_char,
+      line,
+    );
+    return -1i32;
+  }
+  p = crate::libbb::skip_whitespace::skip_whitespace(end);
+  if *p as libc::c_int != ':' as i32 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"no \':\' after key sequence: %s\x00" as *const u8 as *const libc::c_char,
+      line,
+    );
+    return -1i32;
+  }
+  p = crate::libbb::skip_whitespace::skip_whitespace(p.offset(1));
+  if *p as libc::c_int == '\"' as i32 || *p as libc::c_int == '\'' as i32 {
+    let mut len: libc::c_int = strlen(p) as libc::c_int;
+    macro_0 = xmalloc(len as size_t) as *mut libc::c_char;
+    len = unescape_keyseq(p.offset(1), *p as libc::c_int, macro_0, len, &mut end);
+    if len <= 0 {
+      free(macro_0 as *mut libc::c_void);
+      crate::libbb::verror_msg::bb_error_msg(
+        b"bad macro: %s\x00" as *const u8 as *const libc::c_char,
+        line,
+      );
+      return -1i32;
+    }
+  } else {
+    let mut fname: *mut libc::c_char = crate::libbb::xfuncs_printf::xstrndup(
+      p,
+      crate::libbb::skip_whitespace::skip_non_whitespace(p).offset_from(p) as libc::c_int,
+    );
+    let mut i_0: libc::c_int =
+      crate::libbb::compare_string_array::index_in_strings(bind_fn_names.as_ptr(), fname);
+    if i_0 < 0 {
+      crate::libbb::verror_msg::bb_error_msg(
+        b"%s: unknown function name\x00" as *const u8 as *const libc::c_char,
+        fname,
+      );
+      free(fname as *mut libc::c_void);
+      return -1i32;
+    }
+    free(fname as *mut libc::c_void);
+    func = bind_fn_codes[i_0 as usize]
+  }
+  bind_key(
+    cur_keymap,
+    keys.as_mut_ptr(),
+    nkeys,
+    func,
+    macro_0,
+    crate::libbb::xfuncs_printf::xstrdup(line),
+  );
+  return 0;
+}
+/* Remove the binding of KEYSEQ ("\C-x\C-e" with no quotes) */
+pub unsafe fn lineedit_unbind(mut keyseq: *const libc::c_char) -> libc::c_int {
+  let mut keys: [libc::c_int; 8] = [0; 8];
+  let mut end: *const libc::c_char = std::ptr::null();
+  let mut q: *mut libc::c_char =
+    crate::libbb::xfuncs_printf::xasprintf(b"\"%s\"\x00" as *const u8 as *const libc::c_char, keyseq);
+  let mut nkeys: libc::c_int = parse_keyseq(q, &mut end, keys.as_mut_ptr());
+  free(q as *mut libc::c_void);
+  if nkeys == 0 {
+    return -1i32;
+  }
+  bind_key(
+    cur_keymap,
+    keys.as_mut_ptr(),
+    nkeys,
+    0,
+    std::ptr::null_mut::<libc::c_char>(),
+    std::ptr::null_mut::<libc::c_char>(),
+  );
+  return 0;
+}
+/* Make bind work on keymap NAME, or (if NULL) on the one of
+ * ST's editing mode. -1 if there is no such keymap */
+pub unsafe fn lineedit_set_keymap(
+  mut st: *const line_input_t,
+  mut name: *const libc::c_char,
+) -> libc::c_int {
+  let mut i: libc::c_int = 0;
+  if name.is_null() {
+    cur_keymap = if !st.is_null() && (*st).flags & VI_MODE as libc::c_int != 0 {
+      KEYMAP_VI_INSERT
+    } else {
+      KEYMAP_EMACS
+    };
+    return 0;
+  }
+  i = crate::libbb::compare_string_array::index_in_strings(keymap_names.as_ptr(), name);
+  if i < 0 {
+    return -1i32;
+  }
+  cur_keymap = keymap_codes[i as usize];
+  return 0;
+}
+/* bind -l */
+pub unsafe fn lineedit_list_functions() {
+  let mut p: *const libc::c_char = bind_fn_names.as_ptr();
+  while *p != 0 {
+    puts(p);
+    p = p.offset(strlen(p).wrapping_add(1i32 as libc::c_ulong) as isize)
+  }
+}
+/* bind -p (inputrc format) and bind -P, for the current keymap */
+pub unsafe fn lineedit_print_bindings(mut readable: libc::c_int) {
+  let mut kb: *mut key_binding = key_bindings;
+  while !kb.is_null() {
+    if (*kb).keymap == cur_keymap {
+      if readable != 0 {
+        puts((*kb).line);
+      } else {
+        let mut colon: *const libc::c_char = std::ptr::null();
+        let mut i: libc::c_int = 0;
+        let mut p: *const libc::c_char = bind_fn_names.as_ptr();
+        if (*kb).func == 0 {
+          p = b"macro\x00" as *const u8 as *const libc::c_char
+        } else {
+          while bind_fn_codes[i as usize] != (*kb).func {
+            p = p.offset(strlen(p).wrapping_add(1i32 as libc::c_ulong) as isize);
+            i += 1
+          }
+        }
+        /* the key sequence is up to the last ':' before the action */
+        colon = strrchr((*kb).line, ':' as i32);
+        if (*kb).func == 0 {
+          let mut end: *const libc::c_char = std::ptr::null();
+          let mut keys: [libc::c_int; 8] = [0; 8];
+          parse_keyseq((*kb).line, &mut end, keys.as_mut_ptr());
+          colon = end
+        }
+        printf(
+          b"%s can be found on %.*s.\n\x00" as *const u8 as *const libc::c_char,
+          p,
+          colon.offset_from((*kb).line) as libc::c_int,
+          (*kb).line,
+        );
+      }
+    }
+    kb = (*kb).next
+  }
+}
+/* $if mode=vi, $if term=xterm, $if APPLICATION */
+unsafe extern "C" fn inputrc_cond(mut cond: *const libc::c_char) -> libc::c_int {
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut r: libc::c_int = 0;
+  let mut word: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  cond = crate::libbb::skip_whitespace::skip_whitespace(cond);
+  word = crate::libbb::xfuncs_printf::xstrndup(
+    cond,
+    crate::libbb::skip_whitespace::skip_non_whitespace(cond).offset_from(cond) as libc::c_int,
+  );
+  p = crate::libbb::compare_string_array::is_prefixed_with(
+    word,
+    b"mode=\x00" as *const u8 as *const libc::c_char,
+  );
+  if !p.is_null() {
+    r = (strcmp(
+      p,
+      if cur_keymap == KEYMAP_EMACS {
+        b"emacs\x00" as *const u8 as *const libc::c_char
+      } else {
+        b"vi\x00" as *const u8 as *const libc::c_char
+      },
+    ) == 0) as libc::c_int
+  } else {
+    p = crate::libbb::compare_string_array::is_prefixed_with(
+      word,
+      b"term=\x00" as *const u8 as *const libc::c_char,
+    );
+    if !p.is_null() {
+      let mut term: *const libc::c_char = getenv(b"TERM\x00" as *const u8 as *const libc::c_char);
+      /* "xterm" matches TERM=xterm-256color too */
+      r = (!term.is_null()
+        && (strcmp(term, p) == 0
+          || strncmp(term, p, strlen(p)) == 0 && *term.offset(strlen(p) as isize) as libc::c_int == '-' as i32))
+        as libc::c_int
+    } else {
+      r = (strcasecmp(word, crate::libbb::appletlib::applet_name) == 0) as libc::c_int
+    }
+  }
+  free(word as *mut libc::c_void);
+  return r;
+}
+unsafe extern "C" fn read_inputrc(
+  mut file: *const libc::c_char,
+  mut depth: libc::c_int,
+) -> libc::c_int {
+  let mut line: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  /* nesting level of $if's, and the one we are skipping, if any */
+  let mut level: libc::c_int = 0;
+  let mut skip: libc::c_int = 0;
+  let mut fp: *mut FILE = crate::libbb::wfopen::fopen_for_read(file);
+  if fp.is_null() {
+    return -1i32;
+  }
+  loop {
+    let mut p: *const libc::c_char = std::ptr::null();
+    line = crate::libbb::get_line_from_file::xmalloc_fgetline(fp);
+    if line.is_null() {
+      break;
+    }
+    p = crate::libbb::skip_whitespace::skip_whitespace(line);
+    if *p as libc::c_int == '$' as i32 {
+      if strncmp(p, b"$if\x00" as *const u8 as *const libc::c_char, 3i32 as size_t) == 0 {
+        level += 1;
+        if skip == 0 && inputrc_cond(p.offset(3)) == 0 {
+          skip = level
+        }
+      } else if strncmp(p, b"$else\x00" as *const u8 as *const libc::c_char, 5i32 as size_t) == 0
+      {
+        if skip == level {
+          skip = 0
+        } else if skip == 0 {
+          skip = level
+        }
+      } else if strncmp(p, b"$endif\x00" as *const u8 as *const libc::c_char, 6i32 as size_t) == 0
+      {
+        if skip == level {
+          skip = 0
+        }
+        if level > 0 {
+          level -= 1
+        }
+      } else if skip == 0
+        && depth < 8i32
+        && strncmp(p, b"$include\x00" as *const u8 as *const libc::c_char, 8i32 as size_t) == 0
+      {
+        let mut inc: *const libc::c_char =
+          crate::libbb::skip_whitespace::skip_whitespace(p.offset(8));
+        let mut home: *const libc::c_char = getenv(b"HOME\x00" as *const u8 as *const libc::c_char);
+        if *inc as libc::c_int == '~' as i32
+          && *inc.offset(1) as libc::c_int == '/' as i32
+          && !home.is_null()
+        {
+          let mut path: *mut libc::c_char =
+            crate::libbb::concat_path_file::concat_path_file(home, inc.offset(2));
+          read_inputrc(path, depth + 1i32);
+          free(path as *mut libc::c_void);
+        } else {
+          read_inputrc(inc, depth + 1i32);
+        }
+      }
+    } else if skip == 0 {
+      lineedit_parse_and_bind(p);
+    }
+    free(line as *mut libc::c_void);
+  }
+  fclose(fp);
+  return 0;
+}
+/* bind -f FILE */
+pub unsafe fn lineedit_read_inputrc(mut file: *const libc::c_char) -> libc::c_int {
+  return read_inputrc(file, 0);
+}
+/* $INPUTRC, or ~/.inputrc, or /etc/inputrc */
+unsafe extern "C" fn load_inputrc() {
+  let mut file: *const libc::c_char = getenv(b"INPUTRC\x00" as *const u8 as *const libc::c_char);
+  inputrc_loaded = 1i32 as smallint;
+  if !file.is_null() {
+    read_inputrc(file, 0);
+    return;
+  }
+  file = getenv(b"HOME\x00" as *const u8 as *const libc::c_char);
+  if !file.is_null() {
+    let mut path: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+      file,
+      b".inputrc\x00" as *const u8 as *const libc::c_char,
+    );
+    let mut r: libc::c_int = read_inputrc(path, 0);
+    free(path as *mut libc::c_void);
+    if r == 0 {
+      return;
+    }
+  }
+  read_inputrc(b"/etc/inputrc\x00" as *const u8 as *const libc::c_char, 0);
+}
+/* vi command mode motions */
+unsafe extern "C" fn vi_Word_motion(mut eat: libc::c_int) {
+  let mut command: *mut wchar_t = (*lineedit_ptr_to_statics).command_ps;
+  while (*lineedit_ptr_to_statics).cursor < (*lineedit_ptr_to_statics).command_len as libc::c_uint
+    && !BB_isspace(*command.offset((*lineedit_ptr_to_statics).cursor as isize))
+  {
+    input_forward();
+  }
+  if eat != 0 {
+    while (*lineedit_ptr_to_statics).cursor < (*lineedit_ptr_to_statics).command_len as libc::c_uint
+      && BB_isspace(*command.offset((*lineedit_ptr_to_statics).cursor as isize)) as libc::c_int != 0
+    {
+      input_forward();
+    }
+  };
+}
+unsafe extern "C" fn vi_word_motion(mut eat: libc::c_int) {
+  let mut command: *mut wchar_t = (*lineedit_ptr_to_statics).command_ps;
+  let mut cursor: *mut libc::c_uint = &mut (*lineedit_ptr_to_statics).cursor;
+  let mut len: libc::c_uint = (*lineedit_ptr_to_statics).command_len as libc::c_uint;
+  if BB_isalnum_or_underscore(*command.offset(*cursor as isize)) {
+    while *cursor < len
+      && BB_isalnum_or_underscore(*command.offset((*cursor).wrapping_add(1) as isize)) as libc::c_int
+        != 0
+    {
+      input_forward();
+    }
+  } else if BB_ispunct(*command.offset(*cursor as isize)) {
+    while *cursor < len
+      && BB_ispunct(*command.offset((*cursor).wrapping_add(1) as isize)) as libc::c_int != 0
+    {
+      input_forward();
+    }
+  }
+  if *cursor < len {
+    input_forward();
+  }
+  if eat != 0 {
+    while *cursor < len && BB_isspace(*command.offset(*cursor as isize)) as libc::c_int != 0 {
+      input_forward();
+    }
+  };
+}
+unsafe extern "C" fn vi_End_motion() {
+  let mut command: *mut wchar_t = (*lineedit_ptr_to_statics).command_ps;
+  let mut cursor: *mut libc::c_uint = &mut (*lineedit_ptr_to_statics).cursor;
+  let mut len: libc::c_int = (*lineedit_ptr_to_statics).command_len;
+  input_forward();
+  while (*cursor as libc::c_int) < len && BB_isspace(*command.offset(*cursor as isize)) as libc::c_int != 0
+  {
+    input_forward();
+  }
+  while (*cursor as libc::c_int) < len - 1i32
+    && !BB_isspace(*command.offset((*cursor).wrapping_add(1) as isize))
+  {
+    input_forward();
+  }
+}
+unsafe extern "C" fn vi_end_motion() {
+  let mut command: *mut wchar_t = (*lineedit_ptr_to_statics).command_ps;
+  let mut cursor: *mut libc::c_uint = &mut (*lineedit_ptr_to_statics).cursor;
+  let mut len: libc::c_int = (*lineedit_ptr_to_statics).command_len;
+  if *cursor as libc::c_int >= len - 1i32 {
+    return;
+  }
+  input_forward();
+  while (*cursor as libc::c_int) < len - 1i32
+    && BB_isspace(*command.offset(*cursor as isize)) as libc::c_int != 0
+  {
+    input_forward();
+  }
+  if *cursor as libc::c_int >= len - 1i32 {
+    return;
+  }
+  if BB_isalnum_or_underscore(*command.offset(*cursor as isize)) {
+    while (*cursor as libc::c_int) < len - 1i32
+      && BB_isalnum_or_underscore(*command.offset((*cursor).wrapping_add(1) as isize)) as libc::c_int
+        != 0
+    {
+      input_forward();
+    }
+  } else if BB_ispunct(*command.offset(*cursor as isize)) {
+    while (*cursor as libc::c_int) < len - 1i32
+      && BB_ispunct(*command.offset((*cursor).wrapping_add(1) as isize)) as libc::c_int != 0
+    {
+      input_forward();
+    }
+  };
+}
+unsafe extern "C" fn vi_Back_motion() {
+  let mut command: *mut wchar_t = (*lineedit_ptr_to_statics).command_ps;
+  let mut cursor: *mut libc::c_uint = &mut (*lineedit_ptr_to_statics).cursor;
+  while *cursor > 0 as libc::c_uint
+    && BB_isspace(*command.offset((*cursor).wrapping_sub(1) as isize)) as libc::c_int != 0
+  {
+    input_backward(1i32 as libc::c_uint);
+  }
+  while *cursor > 0 as libc::c_uint && !BB_isspace(*command.offset((*cursor).wrapping_sub(1) as isize))
+  {
+    input_backward(1i32 as libc::c_uint);
+  }
+}
+unsafe extern "C" fn vi_back_motion() {
+  let mut command: *mut wchar_t = (*lineedit_ptr_to_statics).command_ps;
+  let mut cursor: *mut libc::c_uint = &mut (*lineedit_ptr_to_statics).cursor;
+  if *cursor == 0 as libc::c_uint {
+    return;
+  }
+  input_backward(1i32 as libc::c_uint);
+  while *cursor > 0 as libc::c_uint && BB_isspace(*command.offset(*cursor as isize)) as libc::c_int != 0
+  {
+    input_backward(1i32 as libc::c_uint);
+  }
+  if *cursor == 0 as libc::c_uint {
+    return;
+  }
+  if BB_isalnum_or_underscore(*command.offset(*cursor as isize)) {
+    while *cursor > 0 as libc::c_uint
+      && BB_isalnum_or_underscore(*command.offset((*cursor).wrapping_sub(1) as isize)) as libc::c_int
+        != 0
+    {
+      input_backward(1i32 as libc::c_uint);
+    }
+  } else if BB_ispunct(*command.offset(*cursor as isize)) {
+    while *cursor > 0 as libc::c_uint
+      && BB_ispunct(*command.offset((*cursor).wrapping_sub(1) as isize)) as libc::c_int != 0
+    {
+      input_backward(1i32 as libc::c_uint);
+    }
+  };
+}
+unsafe extern "C" fn vi_clear_to_eol() {
+  while (*lineedit_ptr_to_statics).cursor < (*lineedit_ptr_to_statics).command_len as libc::c_uint {
+    input_delete();
+  }
+}
+/* Key C in vi command mode. Returns the key code read_line_input()
+ * should act upon, LE_FN_xxx, or -1 on read error */
+unsafe extern "C" fn vi_command_key(
+  mut c: libc::c_int,
+  mut read_key_buffer: *mut libc::c_char,
+  mut timeout: libc::c_int,
+) -> libc::c_int {
+  let mut sc: libc::c_int = 0;
+  let mut nc: libc::c_int = 0;
+  let mut ic: libc::c_int = 0;
+  match c {
+    104 => return 2i32,          /* h */
+    108 | 32 => return 6i32,     /* l, space */
+    48 | 94 => return 1i32,      /* 0, ^ */
+    36 => return 5i32,           /* $ */
+    10 | 13 => return 13i32,     /* Enter */
+    106 | 43 => return 14i32,    /* j, + */
+    107 | 45 => return 16i32,    /* k, - */
+    118 => return LE_FN_EDIT_AND_EXECUTE, /* v */
+    105 => return LE_FN_VI_INSERTION_MODE, /* i */
+    73 => {
+      /* I */
+      input_backward((*lineedit_ptr_to_statics).cursor);
+      return LE_FN_VI_INSERTION_MODE;
+    }
+    97 => {
+      /* a */
+      input_forward();
+      return LE_FN_VI_INSERTION_MODE;
+    }
+    65 => {
+      /* A */
+      put_till_end_and_adv_cursor();
+      return LE_FN_VI_INSERTION_MODE;
+    }
+    120 => input_delete(), /* x */
+    88 => {
+      /* X */
+      if (*lineedit_ptr_to_statics).cursor > 0 as libc::c_uint {
+        input_backward(1i32 as libc::c_uint);
+        input_delete();
+      }
+    }
+    87 => vi_Word_motion(1i32),
+    119 => vi_word_motion(1i32),
+    69 => vi_End_motion(),
+    101 => vi_end_motion(),
+    66 => vi_Back_motion(),
+    98 => vi_back_motion(),
+    67 => {
+      /* C */
+      vi_clear_to_eol();
+      return LE_FN_VI_INSERTION_MODE;
+    }
+    68 => vi_clear_to_eol(), /* D */
+    99 | 100 => {
+      /* c, d and a motion */
+      ic = lineedit_read_key(read_key_buffer, timeout);
+      if ic == -1i32 {
+        return -1i32;
+      }
+      if ic == c {
+        /* "cc", "dd" */
+        input_backward((*lineedit_ptr_to_statics).cursor);
+        vi_clear_to_eol();
+      } else {
+        sc = (*lineedit_ptr_to_statics).cursor as libc::c_int;
+        match ic {
+          119 | 87 | 101 | 69 => {
+            match ic {
+              119 => vi_word_motion((c == 'd' as i32) as libc::c_int),
+              87 => vi_Word_motion((c == 'd' as i32) as libc::c_int),
+              101 => {
+                vi_end_motion();
+                input_forward();
+              }
+              _ => {
+                vi_End_motion();
+                input_forward();
+              }
+            }
+            nc = (*lineedit_ptr_to_statics).cursor as libc::c_int;
+            input_backward(((*lineedit_ptr_to_statics).cursor).wrapping_sub(sc as libc::c_uint));
+            loop {
+              nc -= 1;
+              if nc < (*lineedit_ptr_to_statics).cursor as libc::c_int {
+                break;
+              }
+              input_delete();
+            }
+          }
+          98 | 66 => {
+            if ic == 'b' as i32 {
+              vi_back_motion();
+            } else {
+              vi_Back_motion();
+            }
+            loop {
+              sc -= 1;
+              if sc < (*lineedit_ptr_to_statics).cursor as libc::c_int {
+                break;
+              }
+              input_delete();
+            }
+          }
+          32 => input_delete(),
+          36 => vi_clear_to_eol(),
+          _ => {
+            beep();
+            return LE_FN_NOP;
+          }
+        }
+      }
+      if c == 'c' as i32 {
+        return LE_FN_VI_INSERTION_MODE;
+      }
+    }
+    114 => {
+      /* r */
+      ic = lineedit_read_key(read_key_buffer, timeout);
+      if ic == -1i32 {
+        return -1i32;
+      }
+      if ic < ' ' as i32
+        || (*lineedit_ptr_to_statics).cursor >= (*lineedit_ptr_to_statics).command_len as libc::c_uint
+      {
+        beep();
+      } else {
+        *(*lineedit_ptr_to_statics)
+          .command_ps
+          .offset((*lineedit_ptr_to_statics).cursor as isize) = ic;
+        put_cur_glyph_and_inc_cursor();
+        input_backward(1i32 as libc::c_uint);
+      }
+    }
+    126 => {
+      /* ~ */
+      if (*lineedit_ptr_to_statics).cursor < (*lineedit_ptr_to_statics).command_len as libc::c_uint {
+        let mut wc: *mut wchar_t = (*lineedit_ptr_to_statics)
+          .command_ps
+          .offset((*lineedit_ptr_to_statics).cursor as isize);
+        if (*wc as libc::c_uint) < 128i32 as libc::c_uint {
+          if libc::islower(*wc) != 0 {
+            *wc = libc::toupper(*wc)
+          } else {
+            *wc = libc::tolower(*wc)
+          }
+        }
+        put_cur_glyph_and_inc_cursor();
+      }
+    }
+    27 => {}
+    _ => {
+      /* cursor keys and control chars work as in insert mode */
+      if c < ' ' as i32 {
+        return c;
+      }
+      beep();
+    }
+  }
+  return LE_FN_NOP;
+}
+/* edit-and-execute-command: edit the line in $VISUAL or $EDITOR,
+ * then run what was saved */
+unsafe extern "C" fn edit_and_execute(
+  mut initial_settings: *mut termios,
+  mut new_settings: *mut termios,
+) -> libc::c_int {
+  let mut tmpname: [libc::c_char; 20] = [0; 20];
+  let mut editor: *const libc::c_char = std::ptr::null();
+  let mut cmd: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut text: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut len: libc::c_int = 0;
+  let mut fd: libc::c_int = 0;
+  strcpy(
+    tmpname.as_mut_ptr(),
+    b"/tmp/bbedit.XXXXXX\x00" as *const u8 as *const libc::c_char,
+  );
+  fd = libc::mkstemp(tmpname.as_mut_ptr());
+  if fd < 0 {
+    beep();
+    return LE_FN_NOP;
+  }
+  text = xmalloc(
+    ((*lineedit_ptr_to_statics).maxsize as libc::c_ulong).wrapping_mul(4i32 as libc::c_ulong),
+  ) as *mut libc::c_char;
+  len = save_string(
+    text,
+    ((*lineedit_ptr_to_statics).maxsize * 4i32) as libc::c_uint,
+  ) as libc::c_int;
+  *text.offset(len as isize) = '\n' as i32 as libc::c_char;
+  crate::libbb::full_write::full_write(fd, text as *const libc::c_void, (len + 1i32) as size_t);
+  close(fd);
+  free(text as *mut libc::c_void);
+  editor = getenv(b"VISUAL\x00" as *const u8 as *const libc::c_char);
+  if editor.is_null() || *editor == 0 {
+    editor = getenv(b"EDITOR\x00" as *const u8 as *const libc::c_char)
+  }
+  if editor.is_null() || *editor == 0 {
+    editor = b"vi\x00" as *const u8 as *const libc::c_char
+  }
+  cmd = crate::libbb::xfuncs_printf::xasprintf(
+    b"%s %s\x00" as *const u8 as *const libc::c_char,
+    editor,
+    tmpname.as_mut_ptr(),
+  );
+  goto_new_line();
+  crate::libbb::xfuncs::tcsetattr_stdin_TCSANOW(initial_settings);
+  libc::system(cmd);
+  crate::libbb::xfuncs::tcsetattr_stdin_TCSANOW(new_settings);
+  free(cmd as *mut libc::c_void);
+  text = crate::libbb::read_printf::xmalloc_open_read_close(tmpname.as_mut_ptr(), std::ptr::null_mut())
+    as *mut libc::c_char;
+  libc::unlink(tmpname.as_mut_ptr());
+  if !text.is_null() {
+    len = strlen(text) as libc::c_int;
+    while len > 0 && *text.offset((len - 1i32) as isize) as libc::c_int == '\n' as i32 {
+      len -= 1;
+      *text.offset(len as isize) = '\u{0}' as i32 as libc::c_char
+    }
+    (*lineedit_ptr_to_statics).command_len = load_string(text) as libc::c_int;
+    free(text as *mut libc::c_void);
+  }
+  /* show what is going to be run */
+  draw_custom(0, 0, 1i32 != 0);
+  return 13i32;
+}
+/* What key IC, and the keys after it if it starts a longer binding,
+ * does in read_line_input(): a key code for its switch, LE_FN_NOP
+ * if it's already done, or -1 on read error */
+unsafe extern "C" fn lineedit_dispatch(
+  mut ic: libc::c_int,
+  mut read_key_buffer: *mut libc::c_char,
+  mut timeout: libc::c_int,
+  mut initial_settings: *mut termios,
+  mut new_settings: *mut termios,
+) -> libc::c_int {
+  let mut st: *mut line_input_t = (*lineedit_ptr_to_statics).state;
+  let mut keys: [libc::c_int; 8] = [0; 8];
+  let mut n: libc::c_int = 1i32;
+  let mut kb: *mut key_binding = std::ptr::null_mut();
+  let mut keymap: libc::c_int = KEYMAP_EMACS;
+  if ic == -1i32 {
+    return ic;
+  }
+  if (*st).flags & VI_MODE as libc::c_int != 0 {
+    keymap = if (*lineedit_ptr_to_statics).vi_cmdmode as libc::c_int != 0 {
+      KEYMAP_VI_COMMAND
+    } else {
+      KEYMAP_VI_INSERT
+    }
+  }
+  keys[0] = ic;
+  loop {
+    let mut longer: libc::c_int = 0;
+    let mut b: *mut key_binding = key_bindings;
+    kb = std::ptr::null_mut();
+    while !b.is_null() {
+      if (*b).keymap == keymap
+        && (*b).nkeys >= n
+        && memcmp(
+          (*b).keys.as_mut_ptr() as *const libc::c_void,
+          keys.as_mut_ptr() as *const libc::c_void,
+          (n as libc::c_ulong).wrapping_mul(::std::mem::size_of::<libc::c_int>() as libc::c_ulong),
+        ) == 0
+      {
+        if (*b).nkeys == n {
+          kb = b
+        } else {
+          longer = 1i32
+        }
+      }
+      b = (*b).next
+    }
+    if longer == 0 || n == 8i32 {
+      break;
+    }
+    /* A prefix of a longer binding, need the next key */
+    ic = lineedit_read_key(read_key_buffer, timeout);
+    if ic == -1i32 {
+      return ic;
+    }
+    keys[n as usize] = ic;
+    n += 1
+  }
+  if !kb.is_null() {
+    if (*kb).func == 0 {
+      /* type in the macro */
+      (*lineedit_ptr_to_statics).macro_pending = (*kb).macro_0;
+      return LE_FN_NOP;
+    }
+    ic = (*kb).func;
+    if ic == LE_FN_SELF_INSERT {
+      ic = keys[(n - 1i32) as usize]
+    }
+  } else if n > 1i32 {
+    beep();
+    return LE_FN_NOP;
+  } else if keymap == KEYMAP_VI_INSERT && ic == 27i32 {
+    ic = LE_FN_VI_MOVEMENT_MODE
+  } else if keymap == KEYMAP_VI_COMMAND {
+    ic = vi_command_key(ic, read_key_buffer, timeout)
+  }
+  match ic {
+    LE_FN_VI_EDITING_MODE => {
+      (*st).flags |= VI_MODE as libc::c_int;
+      (*lineedit_ptr_to_statics).vi_cmdmode = 0 as smallint
+    }
+    LE_FN_EMACS_EDITING_MODE => {
+      (*st).flags &= !(VI_MODE as libc::c_int);
+      (*lineedit_ptr_to_statics).vi_cmdmode = 0 as smallint
+    }
+    LE_FN_VI_MOVEMENT_MODE => {
+      if (*st).flags & VI_MODE as libc::c_int != 0 {
+        (*lineedit_ptr_to_statics).vi_cmdmode = 1i32 as smallint;
+        input_backward(1i32 as libc::c_uint);
+      }
+    }
+    LE_FN_VI_INSERTION_MODE => (*lineedit_ptr_to_statics).vi_cmdmode = 0 as smallint,
+    LE_FN_ABORT => beep(),
+    LE_FN_EDIT_AND_EXECUTE => return edit_and_execute(initial_settings, new_settings),
+    _ => return ic,
+  }
+  return LE_FN_NOP;
+}
 pub unsafe fn read_line_input(
   mut st: *mut line_input_t,
   mut prompt: *const libc::c_char,
@@ -3901,11 +5363,24 @@ pub unsafe fn read_line_input(
   (*lineedit_ptr_to_statics).state = &const_int_0 as *const libc::c_int as *mut line_input_t;
   if !st.is_null() {
     (*lineedit_ptr_to_statics).state = st;
-    timeout = (*st).timeout
+    timeout = (*st).timeout;
+    if inputrc_loaded == 0 {
+      load_inputrc();
+    }
+    /* "set editing-mode" from inputrc or bind */
+    if pending_editing_mode == 1i32 {
+      (*st).flags |= VI_MODE as libc::c_int
+    } else if pending_editing_mode == 0 {
+      (*st).flags &= !(VI_MODE as libc::c_int)
+    }
+    pending_editing_mode = -1i32
   }
   if (*(*lineedit_ptr_to_statics).state).flags & DO_HISTORY as libc::c_int != 0 {
     if !(*(*lineedit_ptr_to_statics).state).hist_file.is_null() {
-      if (*(*lineedit_ptr_to_statics).state).cnt_history == 0 {
+      /* not after "history -c" */
+      if (*(*lineedit_ptr_to_statics).state).cnt_history == 0
+        && (*(*lineedit_ptr_to_statics).state).cnt_history_in_file == 0
+      {
         load_history((*lineedit_ptr_to_statics).state);
       }
     }
@@ -3957,6 +5432,15 @@ pub unsafe fn read_line_input(
       cmdedit_setwidth();
     }
     ic_raw = lineedit_read_key(read_key_buffer.as_mut_ptr(), timeout);
+    if !st.is_null() {
+      ic_raw = lineedit_dispatch(
+        ic_raw,
+        read_key_buffer.as_mut_ptr(),
+        timeout,
+        &mut initial_settings,
+        &mut new_settings,
+      )
+    }
     ic = ic_raw;
     loop {
       match ic {
@@ -50,6 +50,116 @@ pub const KEYCODE_UP: C2RustUnnamed = -2;
  *
  * Licensed under GPLv2, see file LICENSE in this source tree.
  */
+/* Known escape sequences for cursor and function keys.
+ * See "Xterm Control Sequences"
+ * http://invisible-island.net/xterm/ctlseqs/ctlseqs.html
+ * Array should be sorted from shortest to longest.
+ */
+static mut esccmds: [libc::c_char; 103] = [
+  ('\u{7f}' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_ALT_BACKSPACE as libc::c_int as libc::c_char,
+  ('\u{8}' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_ALT_BACKSPACE as libc::c_int as libc::c_char,
+  ('d' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_ALT_D as libc::c_int as libc::c_char,
+  ('f' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_ALT_RIGHT as libc::c_int as libc::c_char,
+  ('b' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_ALT_LEFT as libc::c_int as libc::c_char,
+  'O' as i32 as libc::c_char,
+  ('A' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_UP as libc::c_int as libc::c_char,
+  'O' as i32 as libc::c_char,
+  ('B' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_DOWN as libc::c_int as libc::c_char,
+  'O' as i32 as libc::c_char,
+  ('C' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_RIGHT as libc::c_int as libc::c_char,
+  'O' as i32 as libc::c_char,
+  ('D' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_LEFT as libc::c_int as libc::c_char,
+  'O' as i32 as libc::c_char,
+  ('H' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_HOME as libc::c_int as libc::c_char,
+  'O' as i32 as libc::c_char,
+  ('F' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_END as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  ('A' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_UP as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  ('B' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_DOWN as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  ('C' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_RIGHT as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  ('D' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_LEFT as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  ('H' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_HOME as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  ('F' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_END as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '1' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_HOME as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '2' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_INSERT as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '3' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_DELETE as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '4' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_END as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '5' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_PAGEUP as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '6' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_PAGEDOWN as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '7' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_HOME as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '8' as i32 as libc::c_char,
+  ('~' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_END as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '1' as i32 as libc::c_char,
+  ';' as i32 as libc::c_char,
+  '5' as i32 as libc::c_char,
+  ('C' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_CTRL_RIGHT as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '1' as i32 as libc::c_char,
+  ';' as i32 as libc::c_char,
+  '5' as i32 as libc::c_char,
+  ('D' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_CTRL_LEFT as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '1' as i32 as libc::c_char,
+  ';' as i32 as libc::c_char,
+  '3' as i32 as libc::c_char,
+  ('C' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_ALT_RIGHT as libc::c_int as libc::c_char,
+  '[' as i32 as libc::c_char,
+  '1' as i32 as libc::c_char,
+  ';' as i32 as libc::c_char,
+  '3' as i32 as libc::c_char,
+  ('D' as i32 | 0x80i32) as libc::c_char,
+  KEYCODE_ALT_LEFT as libc::c_int as libc::c_char,
+  0 as libc::c_char,
+];
 pub unsafe fn read_key(
   mut fd: libc::c_int,
   mut buffer: *mut libc::c_char,
@@ -63,116 +173,7 @@ pub unsafe fn read_key(
   };
   let mut seq: *const libc::c_char = std::ptr::null();
   let mut n: libc::c_int = 0;
-  /* Known escape sequences for cursor and function keys.
-   * See "Xterm Control Sequences"
-   * http://invisible-island.net/xterm/ctlseqs/ctlseqs.html
-   * Array should be sorted from shortest to longest.
-   */
-  static mut esccmds: [libc::c_char; 103] = [
-    ('\u{7f}' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_ALT_BACKSPACE as libc::c_int as libc::c_char,
-    ('\u{8}' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_ALT_BACKSPACE as libc::c_int as libc::c_char,
-    ('d' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_ALT_D as libc::c_int as libc::c_char,
-    ('f' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_ALT_RIGHT as libc::c_int as libc::c_char,
-    ('b' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_ALT_LEFT as libc::c_int as libc::c_char,
-    'O' as i32 as libc::c_char,
-    ('A' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_UP as libc::c_int as libc::c_char,
-    'O' as i32 as libc::c_char,
-    ('B' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_DOWN as libc::c_int as libc::c_char,
-    'O' as i32 as libc::c_char,
-    ('C' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_RIGHT as libc::c_int as libc::c_char,
-    'O' as i32 as libc::c_char,
-    ('D' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_LEFT as libc::c_int as libc::c_char,
-    'O' as i32 as libc::c_char,
-    ('H' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_HOME as libc::c_int as libc::c_char,
-    'O' as i32 as libc::c_char,
-    ('F' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_END as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    ('A' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_UP as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    ('B' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_DOWN as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    ('C' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_RIGHT as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    ('D' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_LEFT as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    ('H' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_HOME as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    ('F' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_END as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '1' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_HOME as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '2' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_INSERT as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '3' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_DELETE as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '4' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_END as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '5' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_PAGEUP as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '6' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_PAGEDOWN as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '7' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_HOME as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '8' as i32 as libc::c_char,
-    ('~' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_END as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '1' as i32 as libc::c_char,
-    ';' as i32 as libc::c_char,
-    '5' as i32 as libc::c_char,
-    ('C' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_CTRL_RIGHT as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '1' as i32 as libc::c_char,
-    ';' as i32 as libc::c_char,
-    '5' as i32 as libc::c_char,
-    ('D' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_CTRL_LEFT as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '1' as i32 as libc::c_char,
-    ';' as i32 as libc::c_char,
-    '3' as i32 as libc::c_char,
-    ('C' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_ALT_RIGHT as libc::c_int as libc::c_char,
-    '[' as i32 as libc::c_char,
-    '1' as i32 as libc::c_char,
-    ';' as i32 as libc::c_char,
-    '3' as i32 as libc::c_char,
-    ('D' as i32 | 0x80i32) as libc::c_char,
-    KEYCODE_ALT_LEFT as libc::c_int as libc::c_char,
-    0 as libc::c_char,
-  ]; /* saved chars counter is in buffer[-1] now */
+  /* saved chars counter is in buffer[-1] now */
   pfd.fd = fd;
   pfd.events = 0x1i32 as libc::c_short;
   buffer = buffer.offset(1);
@@ -895,3 +896,34 @@ pub unsafe fn read_key_ungets(
   let ref mut fresh0 = *buffer.offset(0);
   *fresh0 = (*fresh0 as libc::c_uint).wrapping_add(len) as libc::c_char as libc::c_char;
 }
+/* Keycode read_key() would return for the ESC sequence STR
+ * (the part after ESC), or -1 if it is not one we know.
+ * *LEN_P is set to the number of chars the sequence takes.
+ * For translating key sequences in inputrc.
+ */
+pub unsafe fn read_key_decode_esc(
+  mut str: *const libc::c_char,
+  mut len_p: *mut libc::c_int,
+) -> libc::c_int {
+  let mut seq: *const libc::c_char = esccmds.as_ptr();
+  while *seq as libc::c_int != '\u{0}' as i32 {
+    let mut i: libc::c_int = 0;
+    loop {
+      if *str.offset(i as isize) as libc::c_int != *seq.offset(i as isize) as libc::c_int & 0x7fi32
+      {
+        /* Forward to last char, skip it and the keycode */
+        while *seq as libc::c_int & 0x80i32 == 0 {
+          seq = seq.offset(1)
+        }
+        seq = seq.offset(2);
+        break;
+      }
+      if *seq.offset(i as isize) as libc::c_int & 0x80i32 != 0 {
+        *len_p = i + 1i32;
+        return *seq.offset((i + 1i32) as isize) as libc::c_schar as libc::c_int;
+      }
+      i += 1
+    }
+  }
+  return -1i32;
+}
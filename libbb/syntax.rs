@@ -0,0 +1,597 @@
+use libc;
+use libc::strchr;
+use libc::strstr;
+extern "C" {
+  #[no_mangle]
+  fn strlen(__s: *const libc::c_char) -> libc::c_ulong;
+  #[no_mangle]
+  fn strncmp(_: *const libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> libc::c_int;
+  #[no_mangle]
+  fn memset(_: *mut libc::c_void, _: libc::c_int, _: libc::c_ulong) -> *mut libc::c_void;
+}
+
+/*
+ * Minimal table-driven syntax highlighting, shared by vi and less.
+ *
+ * Licensed under GPLv2, see file LICENSE in this source tree.
+ */
+
+/* Highlight classes, one per byte of text */
+pub const HL_NORMAL: u8 = 0;
+pub const HL_COMMENT: u8 = 1;
+pub const HL_STRING: u8 = 2;
+pub const HL_NUMBER: u8 = 3;
+pub const HL_KEYWORD: u8 = 4;
+pub const HL_PREPROC: u8 = 5;
+pub const HL_VAR: u8 = 6;
+pub const HL_KEY: u8 = 7;
+pub const HL_SECTION: u8 = 8;
+pub const HL_ADDED: u8 = 9;
+pub const HL_REMOVED: u8 = 10;
+pub const HL_HEADER: u8 = 11;
+
+/* Flags for syntax_line() */
+pub const SYN_BOL: libc::c_int = 1;
+pub const SYN_EOL: libc::c_int = 2;
+
+/* Language traits */
+const SF_NUMBERS: libc::c_int = 1;
+const SF_PREPROC: libc::c_int = 2;
+const SF_VARS: libc::c_int = 4;
+const SF_MLSTRING: libc::c_int = 8;
+const SF_JSONKEY: libc::c_int = 0x10;
+const SF_DIFF: libc::c_int = 0x20;
+const SF_INI: libc::c_int = 0x40;
+
+/* Scanner state carried between lines: mode | arg << 8 */
+const ST_NORMAL: libc::c_int = 0;
+const ST_BLOCK: libc::c_int = 1;
+const ST_EOL: libc::c_int = 2;
+const ST_STRING: libc::c_int = 3;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct syntax_def {
+  /* all lists are space separated, with a space at both ends */
+  pub names: *const libc::c_char,
+  pub suffixes: *const libc::c_char,
+  pub keywords: *const libc::c_char,
+  pub line_comment: *const libc::c_char,
+  pub block_start: *const libc::c_char,
+  pub block_end: *const libc::c_char,
+  pub quotes: *const libc::c_char,
+  pub flags: libc::c_int,
+}
+
+/* Languages are numbered from 1, 0 means no highlighting */
+const NUM_SYNTAXES: libc::c_int = 5;
+static mut syntaxes: [syntax_def; 5] = [
+  syntax_def {
+    names: b" sh bash ksh ash zsh shell \x00" as *const u8 as *const libc::c_char,
+    suffixes: b" .sh .bash .ksh .ash .zsh .profile .bashrc .bash_profile .bash_logout .ashrc \x00"
+      as *const u8 as *const libc::c_char,
+    keywords: b" if then else elif fi case esac for while until do done in function select \
+time return exit break continue local export readonly set unset shift trap eval exec \
+source alias \x00" as *const u8 as *const libc::c_char,
+    line_comment: b"#\x00" as *const u8 as *const libc::c_char,
+    block_start: 0 as *const libc::c_char,
+    block_end: 0 as *const libc::c_char,
+    quotes: b"\"\'`\x00" as *const u8 as *const libc::c_char,
+    flags: SF_VARS | SF_MLSTRING,
+  },
+  syntax_def {
+    names: b" c cpp h \x00" as *const u8 as *const libc::c_char,
+    suffixes: b" .c .h .cc .cpp .cxx .hh .hpp .hxx \x00" as *const u8 as *const libc::c_char,
+    keywords: b" auto break case char const continue default do double else enum extern \
+float for goto if inline int long register restrict return short signed sizeof static \
+struct switch typedef union unsigned void volatile while bool true false NULL \x00"
+      as *const u8 as *const libc::c_char,
+    line_comment: b"//\x00" as *const u8 as *const libc::c_char,
+    block_start: b"/*\x00" as *const u8 as *const libc::c_char,
+    block_end: b"*/\x00" as *const u8 as *const libc::c_char,
+    quotes: b"\"\'\x00" as *const u8 as *const libc::c_char,
+    flags: SF_NUMBERS | SF_PREPROC,
+  },
+  syntax_def {
+    names: b" json \x00" as *const u8 as *const libc::c_char,
+    suffixes: b" .json \x00" as *const u8 as *const libc::c_char,
+    keywords: b" true false null \x00" as *const u8 as *const libc::c_char,
+    line_comment: 0 as *const libc::c_char,
+    block_start: 0 as *const libc::c_char,
+    block_end: 0 as *const libc::c_char,
+    quotes: b"\"\x00" as *const u8 as *const libc::c_char,
+    flags: SF_NUMBERS | SF_JSONKEY,
+  },
+  syntax_def {
+    names: b" ini dosini conf cfg \x00" as *const u8 as *const libc::c_char,
+    suffixes: b" .ini .conf .cfg .desktop .service .socket .timer .mount .gitconfig \x00"
+      as *const u8 as *const libc::c_char,
+    keywords: b" \x00" as *const u8 as *const libc::c_char,
+    line_comment: 0 as *const libc::c_char,
+    block_start: 0 as *const libc::c_char,
+    block_end: 0 as *const libc::c_char,
+    quotes: b"\"\x00" as *const u8 as *const libc::c_char,
+    flags: SF_INI,
+  },
+  syntax_def {
+    names: b" diff patch \x00" as *const u8 as *const libc::c_char,
+    suffixes: b" .diff .patch .rej \x00" as *const u8 as *const libc::c_char,
+    keywords: b" \x00" as *const u8 as *const libc::c_char,
+    line_comment: 0 as *const libc::c_char,
+    block_start: 0 as *const libc::c_char,
+    block_end: 0 as *const libc::c_char,
+    quotes: b"\x00" as *const u8 as *const libc::c_char,
+    flags: SF_DIFF,
+  },
+];
+
+/* SGR sequence to switch to a highlight class */
+pub unsafe fn syntax_sgr(mut hl: u8) -> *const libc::c_char {
+  static mut sgr: [*const libc::c_char; 12] = [
+    b"\x1b[m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;36m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;32m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;31m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;33m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;35m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;35m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;34m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;1;36m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;32m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;31m\x00" as *const u8 as *const libc::c_char,
+    b"\x1b[0;1m\x00" as *const u8 as *const libc::c_char,
+  ];
+  if hl as libc::c_int > HL_HEADER as libc::c_int {
+    hl = HL_NORMAL
+  }
+  return sgr[hl as usize];
+}
+
+#[inline(always)]
+unsafe fn bb_ascii_isalnum(mut a: libc::c_uchar) -> libc::c_int {
+  let mut b: libc::c_uchar = (a as libc::c_int - '0' as i32) as libc::c_uchar;
+  if b as libc::c_int <= 9i32 {
+    return (b as libc::c_int <= 9i32) as libc::c_int;
+  }
+  b = ((a as libc::c_int | 0x20i32) - 'a' as i32) as libc::c_uchar;
+  return (b as libc::c_int <= 'z' as i32 - 'a' as i32) as libc::c_int;
+}
+#[inline(always)]
+unsafe fn isdigit(mut a: libc::c_int) -> libc::c_int {
+  return ((a - '0' as i32) as libc::c_uint <= 9i32 as libc::c_uint) as libc::c_int;
+}
+/* Set n bytes of attr[] (if there is one) from position from */
+unsafe fn mark(mut attr: *mut u8, mut from: libc::c_int, mut n: libc::c_int, mut hl: libc::c_int) {
+  if !attr.is_null() {
+    memset(
+      attr.offset(from as isize) as *mut libc::c_void,
+      hl,
+      n as libc::c_ulong,
+    );
+  }
+}
+/* Is word (len bytes) in a space separated list? */
+unsafe fn in_list(
+  mut list: *const libc::c_char,
+  mut word: *const libc::c_char,
+  mut len: libc::c_int,
+) -> libc::c_int {
+  let mut p: *const libc::c_char = list;
+  while *p != 0 {
+    let mut n: libc::c_int = 0;
+    while *p as libc::c_int == ' ' as i32 {
+      p = p.offset(1)
+    }
+    while *p.offset(n as isize) as libc::c_int != 0
+      && *p.offset(n as isize) as libc::c_int != ' ' as i32
+    {
+      n += 1
+    }
+    if n != 0 && n == len && strncmp(p, word, len as libc::c_ulong) == 0 {
+      return 1i32;
+    }
+    p = p.offset(n as isize)
+  }
+  return 0;
+}
+
+/* Language number by name, as in "ft=sh" or ":syntax c" */
+pub unsafe fn syntax_find(mut name: *const libc::c_char) -> libc::c_int {
+  let mut i: libc::c_int = 0;
+  let mut len: libc::c_int = 0;
+  while *name.offset(len as isize) as libc::c_int != 0
+    && (*name.offset(len as isize) as libc::c_int == '_' as i32
+      || bb_ascii_isalnum(*name.offset(len as isize) as libc::c_uchar) != 0)
+  {
+    len += 1
+  }
+  while i < NUM_SYNTAXES {
+    if in_list(syntaxes[i as usize].names, name, len) != 0 {
+      return i + 1i32;
+    }
+    i += 1
+  }
+  return 0;
+}
+
+/* The primary name of a language */
+pub unsafe fn syntax_name(mut lang: libc::c_int) -> *const libc::c_char {
+  static mut name: [libc::c_char; 16] = [0; 16];
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut n: libc::c_int = 0;
+  if lang <= 0 || lang > NUM_SYNTAXES {
+    return b"off\x00" as *const u8 as *const libc::c_char;
+  }
+  p = syntaxes[(lang - 1i32) as usize].names.offset(1);
+  while *p.offset(n as isize) as libc::c_int != ' ' as i32 && n < 15i32 {
+    name[n as usize] = *p.offset(n as isize);
+    n += 1
+  }
+  name[n as usize] = '\u{0}' as i32 as libc::c_char;
+  return name.as_mut_ptr();
+}
+
+/* Language for a file name, by its suffix */
+pub unsafe fn syntax_for_file(mut fname: *const libc::c_char) -> libc::c_int {
+  let mut i: libc::c_int = 0;
+  let mut base: *const libc::c_char = std::ptr::null();
+  let mut dot: *const libc::c_char = std::ptr::null();
+  if fname.is_null() {
+    return 0;
+  }
+  base = crate::libbb::get_last_path_component::bb_basename(fname);
+  /* try ".tar.gz"-like double suffixes from the longest */
+  dot = strchr(base, '.' as i32);
+  while !dot.is_null() {
+    i = 0;
+    while i < NUM_SYNTAXES {
+      if in_list(syntaxes[i as usize].suffixes, dot, strlen(dot) as libc::c_int) != 0 {
+        return i + 1i32;
+      }
+      i += 1
+    }
+    dot = strchr(dot.offset(1), '.' as i32)
+  }
+  return 0;
+}
+
+/* Language from text: a vim-style modeline ("vim: set ft=sh:"),
+ * and on the first line also "#!...sh" or a diff header */
+pub unsafe fn syntax_for_line(
+  mut line: *const libc::c_char,
+  mut len: libc::c_int,
+  mut first: libc::c_int,
+) -> libc::c_int {
+  static mut opts: [*const libc::c_char; 4] = [
+    b"ft=\x00" as *const u8 as *const libc::c_char,
+    b"filetype=\x00" as *const u8 as *const libc::c_char,
+    b"syntax=\x00" as *const u8 as *const libc::c_char,
+    b"syn=\x00" as *const u8 as *const libc::c_char,
+  ];
+  let mut buf: [libc::c_char; 256] = [0; 256];
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut i: libc::c_int = 0;
+  if len > 255i32 {
+    len = 255i32
+  }
+  /* work on a NUL terminated copy, lines in vi are not */
+  while i < len && *line.offset(i as isize) as libc::c_int != '\n' as i32 {
+    buf[i as usize] = *line.offset(i as isize);
+    i += 1
+  }
+  buf[i as usize] = '\u{0}' as i32 as libc::c_char;
+  if first != 0 {
+    if buf[0] as libc::c_int == '#' as i32 && buf[1] as libc::c_int == '!' as i32 {
+      p = strstr(buf.as_ptr(), b"sh\x00" as *const u8 as *const libc::c_char);
+      if !p.is_null() {
+        return syntax_find(b"sh\x00" as *const u8 as *const libc::c_char);
+      }
+    }
+    if strncmp(buf.as_ptr(), b"diff \x00" as *const u8 as *const libc::c_char, 5) == 0
+      || strncmp(buf.as_ptr(), b"--- \x00" as *const u8 as *const libc::c_char, 4) == 0
+      || strncmp(buf.as_ptr(), b"Index: \x00" as *const u8 as *const libc::c_char, 7) == 0
+    {
+      return syntax_find(b"diff\x00" as *const u8 as *const libc::c_char);
+    }
+  }
+  p = buf.as_ptr();
+  loop {
+    let mut m: *const libc::c_char = strstr(p, b"vi\x00" as *const u8 as *const libc::c_char);
+    let mut e: *const libc::c_char = strstr(p, b"ex:\x00" as *const u8 as *const libc::c_char);
+    if m.is_null() || !e.is_null() && e < m {
+      m = e
+    }
+    if m.is_null() {
+      return 0;
+    }
+    p = m.offset(1);
+    /* "vi:", "vim:" or "ex:" at the start or after a blank */
+    if m != buf.as_ptr() && *m.offset(-1) as libc::c_int != ' ' as i32
+      && *m.offset(-1) as libc::c_int != '\t' as i32
+    {
+      continue;
+    }
+    m = m.offset(2);
+    if *m as libc::c_int == 'm' as i32 {
+      m = m.offset(1)
+    }
+    if *m as libc::c_int != ':' as i32 {
+      continue;
+    }
+    i = 0;
+    while i < 4i32 {
+      let mut o: *const libc::c_char = strstr(m, opts[i as usize]);
+      if !o.is_null()
+        && (*o.offset(-1) as libc::c_int == ' ' as i32
+          || *o.offset(-1) as libc::c_int == ':' as i32
+          || *o.offset(-1) as libc::c_int == '\t' as i32)
+      {
+        return syntax_find(o.offset(strlen(opts[i as usize]) as isize));
+      }
+      i += 1
+    }
+  }
+}
+
+unsafe fn is_ident(mut c: libc::c_int) -> libc::c_int {
+  return (c == '_' as i32 || c >= 0 && c < 0x80i32 && bb_ascii_isalnum(c as libc::c_uchar) != 0)
+    as libc::c_int;
+}
+
+unsafe fn starts(
+  mut s: *const libc::c_char,
+  mut left: libc::c_int,
+  mut t: *const libc::c_char,
+) -> libc::c_int {
+  let mut n: libc::c_int = 0;
+  if t.is_null() {
+    return 0;
+  }
+  n = strlen(t) as libc::c_int;
+  return (n <= left && strncmp(s, t, n as libc::c_ulong) == 0) as libc::c_int;
+}
+
+/* Highlight len bytes of a line into attr[] (may be NULL to only
+ * advance the state). SYN_BOL: s is the start of a line, SYN_EOL:
+ * the line ends after s. *state carries comments and strings
+ * from one call to the next, start with 0.
+ */
+pub unsafe fn syntax_line(
+  mut lang: libc::c_int,
+  mut s: *const libc::c_char,
+  mut len: libc::c_int,
+  mut flags: libc::c_int,
+  mut state: *mut libc::c_int,
+  mut attr: *mut u8,
+) {
+  let mut def: *const syntax_def = std::ptr::null();
+  let mut mode: libc::c_int = *state & 0xff;
+  let mut arg: libc::c_int = *state >> 8 & 0xff;
+  let mut i: libc::c_int = 0;
+  let mut first: libc::c_int = 0;
+  let mut str_start: libc::c_int = -1;
+  let mut hl: u8 = HL_NORMAL;
+  if lang <= 0 || lang > NUM_SYNTAXES {
+    mark(attr, 0, len, HL_NORMAL as libc::c_int);
+    return;
+  }
+  def = syntaxes.as_mut_ptr().offset((lang - 1i32) as isize);
+  if flags & SYN_BOL != 0 {
+    while first < len
+      && (*s.offset(first as isize) as libc::c_int == ' ' as i32
+        || *s.offset(first as isize) as libc::c_int == '\t' as i32)
+    {
+      first += 1
+    }
+  }
+  /* line oriented languages decide at the start of the line */
+  if flags & SYN_BOL != 0 && (*def).flags & (SF_DIFF | SF_INI) != 0 {
+    mode = ST_NORMAL;
+    hl = HL_NORMAL;
+    if (*def).flags & SF_DIFF != 0 {
+      let mut c: libc::c_int = if len > 0 { *s as libc::c_int } else { 0 };
+      if starts(s, len, b"+++\x00" as *const u8 as *const libc::c_char) != 0
+        || starts(s, len, b"---\x00" as *const u8 as *const libc::c_char) != 0
+        || starts(s, len, b"diff \x00" as *const u8 as *const libc::c_char) != 0
+        || starts(s, len, b"index \x00" as *const u8 as *const libc::c_char) != 0
+        || starts(s, len, b"Index: \x00" as *const u8 as *const libc::c_char) != 0
+      {
+        hl = HL_HEADER
+      } else if starts(s, len, b"@@\x00" as *const u8 as *const libc::c_char) != 0 {
+        hl = HL_SECTION
+      } else if c == '+' as i32 || c == '>' as i32 {
+        hl = HL_ADDED
+      } else if c == '-' as i32 || c == '<' as i32 {
+        hl = HL_REMOVED
+      }
+      mark(attr, 0, len, hl as libc::c_int);
+      mode = ST_EOL;
+      arg = hl as libc::c_int;
+      i = len
+    } else if first < len {
+      let mut c: libc::c_int = *s.offset(first as isize) as libc::c_int;
+      if c == ';' as i32 || c == '#' as i32 {
+        hl = HL_COMMENT
+      } else if c == '[' as i32 {
+        hl = HL_SECTION
+      }
+      if hl != HL_NORMAL {
+        mark(attr, 0, len, hl as libc::c_int);
+        mode = ST_EOL;
+        arg = hl as libc::c_int;
+        i = len
+      } else {
+        /* key = value */
+        i = first;
+        while i < len
+          && *s.offset(i as isize) as libc::c_int != '=' as i32
+          && *s.offset(i as isize) as libc::c_int != ':' as i32
+        {
+          i += 1
+        }
+        if i < len {
+          mark(attr, 0, i, HL_KEY as libc::c_int);
+          mark(attr, i, len - i, HL_NORMAL as libc::c_int);
+        } else {
+          mark(attr, 0, len, HL_NORMAL as libc::c_int);
+        }
+        i = len
+      }
+    } else {
+      mark(attr, 0, len, HL_NORMAL as libc::c_int);
+      i = len
+    }
+  }
+  while i < len {
+    let mut c: libc::c_int = *s.offset(i as isize) as libc::c_int;
+    let mut left: libc::c_int = len - i;
+    if mode == ST_EOL {
+      mark(attr, i, left, arg as libc::c_int);
+      break;
+    }
+    if mode == ST_BLOCK {
+      if starts(s.offset(i as isize), left, (*def).block_end) != 0 {
+        let mut n: libc::c_int = strlen((*def).block_end) as libc::c_int;
+        mark(attr, i, n, HL_COMMENT as libc::c_int);
+        i += n;
+        mode = ST_NORMAL
+      } else {
+        mark(attr, i, 1, HL_COMMENT as libc::c_int);
+        i += 1
+      }
+      continue;
+    }
+    if mode == ST_STRING {
+      if c == '\\' as i32 && arg != '\'' as i32 && left > 1 {
+        mark(attr, i, 2, HL_STRING as libc::c_int);
+        i += 2;
+        continue;
+      }
+      mark(attr, i, 1, HL_STRING as libc::c_int);
+      i += 1;
+      if c == arg {
+        mode = ST_NORMAL;
+        /* a JSON string followed by a colon is a key */
+        if (*def).flags & SF_JSONKEY != 0 && str_start >= 0 {
+          let mut j: libc::c_int = i;
+          while j < len
+            && (*s.offset(j as isize) as libc::c_int == ' ' as i32
+              || *s.offset(j as isize) as libc::c_int == '\t' as i32)
+          {
+            j += 1
+          }
+          if j < len && *s.offset(j as isize) as libc::c_int == ':' as i32 {
+            mark(attr, str_start, i - str_start, HL_KEY as libc::c_int);
+          }
+        }
+      }
+      continue;
+    }
+    /* ST_NORMAL */
+    if flags & SYN_BOL != 0 && (*def).flags & SF_PREPROC != 0 && c == '#' as i32 && i == first {
+      mode = ST_EOL;
+      arg = HL_PREPROC as libc::c_int;
+      continue;
+    }
+    if !(*def).line_comment.is_null()
+      && starts(s.offset(i as isize), left, (*def).line_comment) != 0
+      /* "#" starts a shell comment only at the start of a word */
+      && ((*def).flags & SF_VARS == 0
+        || i == 0 && flags & SYN_BOL != 0
+        || i > 0 && !strchr(
+          b" \t;|&()\x00" as *const u8 as *const libc::c_char,
+          *s.offset((i - 1) as isize) as libc::c_int,
+        )
+        .is_null())
+    {
+      mode = ST_EOL;
+      arg = HL_COMMENT as libc::c_int;
+      continue;
+    }
+    if !(*def).block_start.is_null()
+      && starts(s.offset(i as isize), left, (*def).block_start) != 0
+    {
+      let mut n: libc::c_int = strlen((*def).block_start) as libc::c_int;
+      mark(attr, i, n, HL_COMMENT as libc::c_int);
+      i += n;
+      mode = ST_BLOCK;
+      continue;
+    }
+    if c != 0 && !strchr((*def).quotes, c).is_null() {
+      mark(attr, i, 1, HL_STRING as libc::c_int);
+      str_start = i;
+      mode = ST_STRING;
+      arg = c;
+      i += 1;
+      continue;
+    }
+    if (*def).flags & SF_VARS != 0 && c == '$' as i32 && left > 1 {
+      let mut n: libc::c_int = 1;
+      let mut d: libc::c_int = *s.offset((i + 1) as isize) as libc::c_int;
+      if d == '{' as i32 {
+        while n < left && *s.offset((i + n) as isize) as libc::c_int != '}' as i32 {
+          n += 1
+        }
+        if n < left {
+          n += 1
+        }
+      } else if is_ident(d) != 0 {
+        while n < left && is_ident(*s.offset((i + n) as isize) as libc::c_int) != 0 {
+          n += 1
+        }
+      } else if !strchr(b"#?$!@*-0\x00" as *const u8 as *const libc::c_char, d).is_null() {
+        n = 2
+      }
+      mark(attr, i, n, (if n > 1 { HL_VAR } else { HL_NORMAL }) as libc::c_int);
+      i += n;
+      continue;
+    }
+    if is_ident(c) != 0 {
+      let mut n: libc::c_int = 1;
+      let mut number: libc::c_int = isdigit(c);
+      while n < left
+        && (is_ident(*s.offset((i + n) as isize) as libc::c_int) != 0
+          || number != 0 && *s.offset((i + n) as isize) as libc::c_int == '.' as i32)
+      {
+        n += 1
+      }
+      if number != 0 && (*def).flags & SF_NUMBERS != 0 {
+        hl = HL_NUMBER
+      } else if in_list((*def).keywords, s.offset(i as isize), n) != 0 {
+        hl = HL_KEYWORD
+      } else {
+        hl = HL_NORMAL
+      }
+      mark(attr, i, n, hl as libc::c_int);
+      i += n;
+      continue;
+    }
+    if c == '-' as i32
+      && (*def).flags & SF_JSONKEY != 0
+      && left > 1
+      && isdigit(*s.offset((i + 1) as isize) as libc::c_int) != 0
+    {
+      mark(attr, i, 1, HL_NUMBER as libc::c_int);
+      i += 1;
+      continue;
+    }
+    mark(attr, i, 1, HL_NORMAL as libc::c_int);
+    i += 1
+  }
+  if flags & SYN_EOL != 0 {
+    if mode == ST_EOL || mode == ST_STRING && (*def).flags & SF_MLSTRING == 0 {
+      mode = ST_NORMAL;
+      arg = 0
+    }
+  }
+  *state = mode | arg << 8;
+}
+
+/* Does the state of a line depend on the lines before it? */
+pub unsafe fn syntax_multiline(mut lang: libc::c_int) -> libc::c_int {
+  if lang <= 0 || lang > NUM_SYNTAXES {
+    return 0;
+  }
+  return (!syntaxes[(lang - 1i32) as usize].block_start.is_null()
+    || syntaxes[(lang - 1i32) as usize].flags & SF_MLSTRING != 0) as libc::c_int;
+}
@@ -102,6 +102,17 @@ pub struct line_input_t {
   pub cnt_history_in_file: libc::c_uint,
   pub hist_file: *const libc::c_char,
   pub history: [*mut libc::c_char; 256],
+  pub hist_time: [libc::time_t; 256],
+  /* history[cnt_history_saved..] are not in hist_file yet */
+  pub cnt_history_saved: libc::c_int,
+  /* The shell's HISTTIMEFORMAT, HISTCONTROL and HISTIGNORE, or NULL */
+  pub hist_timefmt: *const libc::c_char,
+  pub hist_control: *const libc::c_char,
+  pub hist_ignore: *const libc::c_char,
+  /* Shell's programmable completion: given the line up to the cursor,
+   * returns a malloced NULL-terminated vector of candidates, or NULL
+   * if the default completion should be done */
+  pub complete_hook: Option<unsafe extern "C" fn(_: *const libc::c_char) -> *mut *mut libc::c_char>,
 }
 
 #[repr(C)]
@@ -258,6 +269,8 @@ pub struct tls_state {
   pub aes_encrypt: tls_aes,
   pub aes_decrypt: tls_aes,
   pub H: [u8; 16],
+  /* If set, SHA-256 the server's certificate must have */
+  pub cert_sha256: *const u8,
 }
 // ... end libbb.h stuff
 
@@ -6,6 +6,7 @@ use crate::librb::size_t;
 use crate::librb::smallint;
 use libc;
 use libc::access;
+use libc::atoi;
 use libc::close;
 use libc::fclose;
 use libc::fprintf;
@@ -21,6 +22,7 @@ use libc::ssize_t;
 use libc::stat;
 use libc::strchr;
 use libc::strcpy;
+use libc::strncmp;
 use libc::termios;
 use libc::time;
 use libc::time_t;
@@ -160,6 +162,12 @@ pub struct globals {
   pub term_orig: termios,
   pub term_less: termios,
   pub kbd_input: [libc::c_char; 16],
+  pub syn_lang: libc::c_int,
+  pub syn_pos: libc::c_uint,
+  pub syn_state: libc::c_int,
+  pub syn_line: *const libc::c_char,
+  pub shift: libc::c_int,
+  pub shift_step: libc::c_int,
 }
 pub type regex_t = re_pattern_buffer;
 
@@ -178,6 +186,8 @@ pub const TILDES: C2RustUnnamed_2 = 1;
 pub const MAXLINES: C2RustUnnamed_2 = 9999999;
 pub type C2RustUnnamed_3 = libc::c_uint;
 pub const LESS_STATE_MATCH_BACKWARDS: C2RustUnnamed_3 = 32768;
+pub const FLAG_SHIFT: C2RustUnnamed_3 = 2048;
+pub const FLAG_Y: C2RustUnnamed_3 = 1024;
 pub const FLAG_R: C2RustUnnamed_3 = 256;
 pub const FLAG_S: C2RustUnnamed_3 = 128;
 pub const FLAG_F: C2RustUnnamed_3 = 64;
@@ -256,12 +266,33 @@ unsafe fn re_wrap() {
   let mut dst_idx: libc::c_int = 0;
   let mut new_cur_fline: libc::c_int = 0;
   let mut lineno: u32 = 0;
-  let vla = (w + 1i32) as usize;
-  let mut linebuf: Vec<libc::c_char> = ::std::vec::from_elem(0, vla);
+  let mut longest: size_t = 0;
   let mut old_flines: *mut *const libc::c_char = (*ptr_to_globals).flines;
   let mut s: *const libc::c_char = std::ptr::null();
   let mut new_flines: *mut *mut libc::c_char = std::ptr::null_mut();
   let mut d: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  if option_mask32 & FLAG_R as libc::c_int as libc::c_uint != 0 {
+    /* colour sequences make lines longer than the screen is wide,
+     * a new line can't get more of them than its whole old line has */
+    let mut run: size_t = 0;
+    src_idx = 0;
+    while src_idx as libc::c_uint <= (*ptr_to_globals).max_fline {
+      s = *old_flines.offset(src_idx as isize);
+      if src_idx == 0
+        || *(s.offset(-4) as *mut u32)
+          != *((*old_flines.offset(src_idx as isize - 1)).offset(-4) as *mut u32)
+      {
+        run = 0
+      }
+      run = run.wrapping_add(strlen(s));
+      if run > longest {
+        longest = run
+      }
+      src_idx += 1
+    }
+  }
+  let vla = (w as size_t).wrapping_add(longest).wrapping_add(1) as usize;
+  let mut linebuf: Vec<libc::c_char> = ::std::vec::from_elem(0, vla);
   if option_mask32 & FLAG_N as libc::c_int as libc::c_uint != 0 {
     w -= 8i32
   }
@@ -272,6 +303,14 @@ unsafe fn re_wrap() {
   d = linebuf.as_mut_ptr();
   new_line_pos = 0;
   loop {
+    sz = sgr_len(s);
+    if sz != 0 {
+      /* takes no room on screen */
+      memcpy(d as *mut libc::c_void, s as *const libc::c_void, sz as libc::c_ulong);
+      s = s.offset(sz as isize);
+      d = d.offset(sz as isize);
+      continue;
+    }
     *d = *s;
     if *d as libc::c_int != '\u{0}' as i32 {
       new_line_pos += 1;
@@ -285,7 +324,17 @@ unsafe fn re_wrap() {
       if !(new_line_pos >= w) {
         continue;
       }
-      sz = 0;
+      /* colour changes right after the last char stay with it,
+       * as read_lines() does */
+      loop {
+        sz = sgr_len(s);
+        if sz == 0 {
+          break;
+        }
+        memcpy(d as *mut libc::c_void, s as *const libc::c_void, sz as libc::c_ulong);
+        s = s.offset(sz as isize);
+        d = d.offset(sz as isize)
+      }
       /* new line is full, create next one */
       *d = '\u{0}' as i32 as libc::c_char
     } else {
@@ -358,6 +407,54 @@ unsafe fn at_end() -> libc::c_int {
         .wrapping_add((*ptr_to_globals).max_displayed_line)) as libc::c_int
   };
 }
+/* Make room for one more char at p in the line being read,
+ * returns the (possibly moved) p */
+unsafe fn line_room(
+  mut line: *mut *mut libc::c_char,
+  mut p: *mut libc::c_char,
+  mut cap: *mut libc::c_int,
+) -> *mut libc::c_char {
+  let mut len: libc::c_int = p.offset_from(*line) as libc::c_int;
+  if len + 2i32 > *cap {
+    *cap *= 2i32;
+    *line = (crate::libbb::xfuncs_printf::xrealloc(
+      (*line).offset(-4) as *mut libc::c_void,
+      (*cap + 4i32) as size_t,
+    ) as *mut libc::c_char)
+      .offset(4);
+  }
+  return (*line).offset(len as isize);
+}
+/* Length of the colour sequence at s (only with -R), 0 if there is none.
+ * An unfinished one at the end of the (last, incomplete) line counts
+ * as a sequence too. */
+unsafe fn sgr_len(mut s: *const libc::c_char) -> libc::c_int {
+  let mut n: libc::c_int = 2;
+  if option_mask32 & FLAG_R as libc::c_int as libc::c_uint == 0
+    || *s as libc::c_int != '\u{1b}' as i32
+  {
+    return 0;
+  }
+  if *s.offset(1) as libc::c_int == '\u{0}' as i32 {
+    return 1;
+  }
+  if *s.offset(1) as libc::c_int != '[' as i32 {
+    return 0;
+  }
+  while *s.offset(n as isize) as libc::c_uchar as libc::c_int >= 0x20i32
+    && *s.offset(n as isize) as libc::c_uchar as libc::c_int <= 0x3fi32
+  {
+    n += 1
+  }
+  if *s.offset(n as isize) as libc::c_int == 'm' as i32 {
+    return n + 1i32;
+  }
+  return if *s.offset(n as isize) as libc::c_int == '\u{0}' as i32 {
+    n
+  } else {
+    0
+  };
+}
 /* Devilishly complex routine.
  *
  * Has to deal with EOF and EPIPE on input,
@@ -392,6 +489,7 @@ unsafe fn read_lines() {
   let mut last_time: time_t = 0 as time_t;
   let mut retry_EAGAIN: libc::c_int = 2i32;
   let mut old_max_fline: libc::c_uint = (*ptr_to_globals).max_fline;
+  let mut cap: libc::c_int = 0;
   /* (careful: max_fline can be -1) */
   if (*ptr_to_globals)
     .max_fline
@@ -403,20 +501,25 @@ unsafe fn read_lines() {
   if option_mask32 & FLAG_N as libc::c_int as libc::c_uint != 0 {
     w -= 8i32
   }
-  current_line = (xmalloc((w + 5i32) as size_t) as *mut libc::c_char).offset(4);
-  p = current_line;
+  cap = w + 1i32;
   if last_terminated == 0 {
     let mut cp: *const libc::c_char = *(*ptr_to_globals)
       .flines
       .offset((*ptr_to_globals).max_fline as isize);
-    p = stpcpy(p, cp);
+    /* with -R, it can hold more chars than fit on screen */
+    if strlen(cp) as libc::c_int >= cap {
+      cap = strlen(cp) as libc::c_int + 1i32
+    }
+    current_line = (xmalloc((cap + 4i32) as size_t) as *mut libc::c_char).offset(4);
+    p = stpcpy(current_line, cp);
     free((cp as *mut libc::c_char).offset(-4) as *mut libc::c_void);
   /* last_line_pos is still valid from previous read_lines() */
   } else {
+    current_line = (xmalloc((cap + 4i32) as size_t) as *mut libc::c_char).offset(4);
+    p = current_line;
     (*ptr_to_globals).max_fline = (*ptr_to_globals).max_fline.wrapping_add(1); /* end of "read lines until we reach cur_fline" loop */
     (*ptr_to_globals).last_line_pos = 0 as size_t
   }
-  let mut current_block_58: u64;
   loop
   /* read lines until we reach cur_fline or wanted_match */
   {
@@ -466,6 +569,7 @@ unsafe fn read_lines() {
       /* (b) harder to do correctly, so we refuse to do it */
       if c as libc::c_int == '\u{8}' as i32
         && (*ptr_to_globals).last_line_pos != 0
+        && (*ptr_to_globals).in_escape == 0
         && *p.offset(-1i32 as isize) as libc::c_int != '\t' as i32
       {
         (*ptr_to_globals).readpos += 1; /* eat it */
@@ -475,31 +579,68 @@ unsafe fn read_lines() {
         *p = '\u{0}' as i32 as libc::c_char
       } else {
         if option_mask32 & FLAG_R as libc::c_int as libc::c_uint != 0 {
+          /* Colour sequences ("ESC [ N ; N m") are kept in the line
+           * but take no room on screen, other CSI sequences are
+           * dropped. in_escape counts the chars of the one
+           * we are in the middle of. */
+          let mut n: libc::c_int = (*ptr_to_globals).in_escape as libc::c_int;
+          let mut keep: libc::c_int = 1i32;
           if c as libc::c_int == '\u{1b}' as i32 {
-            current_block_58 = 8727269132939964787;
-          } else if (*ptr_to_globals).in_escape != 0 {
-            if (c as libc::c_int - '0' as i32) as libc::c_uchar as libc::c_int <= 9i32
-              || c as libc::c_int == '[' as i32
-              || c as libc::c_int == ';' as i32
-              || c as libc::c_int == 'm' as i32
-            {
-              current_block_58 = 8727269132939964787;
-            } else {
-              /* Hmm, unexpected end of "ESC [ N ; N m" sequence */
+            /* unfinished one before it: drop */
+            p = p.offset(-(n as isize));
+            *p = '\u{0}' as i32 as libc::c_char;
+            n = 0
+          } else if n == 0 {
+            keep = 0
+          } else if n == 1i32 && c as libc::c_int != '[' as i32 {
+            /* not a CSI: drop the ESC, show c as usual */
+            p = p.offset(-1);
+            *p = '\u{0}' as i32 as libc::c_char;
+            (*ptr_to_globals).in_escape = 0 as smallint;
+            keep = 0
+          } else if n == 1i32
+            || c as libc::c_uchar as libc::c_int >= 0x20i32
+              && c as libc::c_uchar as libc::c_int <= 0x3fi32
+          {
+            if n >= 64i32 {
+              /* overlong, not something a terminal would take */
+              p = p.offset(-(n as isize));
+              *p = '\u{0}' as i32 as libc::c_char;
               (*ptr_to_globals).in_escape = 0 as smallint;
-              current_block_58 = 5141539773904409130;
+              keep = 0
             }
           } else {
-            current_block_58 = 5141539773904409130;
-          }
-          match current_block_58 {
-            5141539773904409130 => {}
-            _ => {
-              (*ptr_to_globals).in_escape =
-                (c as libc::c_int != 'm' as i32) as libc::c_int as smallint;
+            (*ptr_to_globals).in_escape = 0 as smallint;
+            if c as libc::c_uchar as libc::c_int >= 0x40i32
+              && c as libc::c_uchar as libc::c_int <= 0x7ei32
+            {
+              /* final char: keep SGR, drop the rest */
               (*ptr_to_globals).readpos += 1;
+              if c as libc::c_int == 'm' as i32 {
+                p = line_room(&mut current_line, p, &mut cap);
+                let fresh15 = p;
+                p = p.offset(1);
+                *fresh15 = c;
+              } else {
+                p = p.offset(-(n as isize));
+              }
+              *p = '\u{0}' as i32 as libc::c_char;
               continue;
             }
+            /* garbage in the middle: drop it, show c as usual */
+            p = p.offset(-(n as isize));
+            *p = '\u{0}' as i32 as libc::c_char;
+            keep = 0
+          }
+          if keep != 0 {
+            (*ptr_to_globals).in_escape = (n + 1i32) as smallint;
+            (*ptr_to_globals).readpos += 1;
+            p = line_room(&mut current_line, p, &mut cap);
+            let fresh16 = p;
+            p = p.offset(1);
+            *fresh16 = c;
+            *p = '\u{0}' as i32 as libc::c_char;
+            continue;
           }
         }
         let mut new_last_line_pos: size_t = (*ptr_to_globals)
@@ -525,6 +666,7 @@ unsafe fn read_lines() {
           if c as libc::c_int == '\u{0}' as i32 {
             c = '\n' as i32 as libc::c_char
           } /* Pretend we saw EOF */
+          p = line_room(&mut current_line, p, &mut cap);
           let fresh1 = p;
           p = p.offset(1);
           *fresh1 = c;
@@ -574,7 +716,8 @@ unsafe fn read_lines() {
         break;
       }
       (*ptr_to_globals).max_fline = (*ptr_to_globals).max_fline.wrapping_add(1);
-      current_line = (xmalloc((w + 5i32) as size_t) as *mut libc::c_char).offset(4);
+      cap = w + 1i32;
+      current_line = (xmalloc((cap + 4i32) as size_t) as *mut libc::c_char).offset(4);
       p = current_line;
       (*ptr_to_globals).last_line_pos = 0 as size_t
     }
@@ -796,7 +939,8 @@ unsafe fn print_found(mut line: *const libc::c_char) {
   let mut eflags: libc::c_int = 0;
   let mut growline: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut match_structs: regmatch_t = regmatch_t { rm_so: 0, rm_eo: 0 };
-  let vla = (*ptr_to_globals).width.wrapping_add(1i32 as libc::c_uint) as usize;
+  /* (-R lines can be longer than the screen is wide) */
+  let vla = strlen(line).wrapping_add(1) as usize;
   let mut buf: Vec<libc::c_char> = ::std::vec::from_elem(0, vla);
   let mut str: *const libc::c_char = line;
   let mut p: *mut libc::c_char = buf.as_mut_ptr();
@@ -811,7 +955,26 @@ unsafe fn print_found(mut line: *const libc::c_char) {
       p = p.offset(n as isize);
       str = str.offset(n as isize)
     }
+    n = sgr_len(str) as size_t;
+    if n != 0 {
+      /* -R: colour sequences are let through */
+      memcpy(p as *mut libc::c_void, str as *const libc::c_void, n);
+      p = p.offset(n as isize);
+      str = str.offset(n as isize);
+      continue;
+    }
     n = strspn(str, controls.as_ptr());
+    if option_mask32 & FLAG_R as libc::c_int as libc::c_uint != 0 {
+      /* stop at the next one */
+      let mut e: size_t = strcspn(
+        str.offset(1),
+        b"\x1b\x00" as *const u8 as *const libc::c_char,
+      )
+      .wrapping_add(1);
+      if e < n {
+        n = e
+      }
+    }
     memset(p as *mut libc::c_void, '.' as i32, n);
     p = p.offset(n as isize);
     str = str.offset(n as isize)
@@ -860,13 +1023,18 @@ unsafe fn print_found(mut line: *const libc::c_char) {
     eflags = 1i32
   }
   printf(
-    b"%s%s\n\x00" as *const u8 as *const libc::c_char,
+    b"%s%s%s\n\x00" as *const u8 as *const libc::c_char,
     if !growline.is_null() {
       growline
     } else {
       b"\x00" as *const u8 as *const libc::c_char
     },
     str,
+    if option_mask32 & FLAG_R as libc::c_int as libc::c_uint != 0 {
+      b"\x1b[m\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"\x00" as *const u8 as *const libc::c_char
+    },
   );
   free(growline as *mut libc::c_void);
 }
@@ -917,20 +1085,358 @@ unsafe fn print_ascii(mut str: *const libc::c_char) {
   }
   puts(str);
 }
+/* How print_ascii() shows control char c */
+unsafe fn ctrl_char(mut c: libc::c_char) -> libc::c_char {
+  if c as libc::c_int == 0x7fi32 {
+    return '?' as i32 as libc::c_char;
+  }
+  if c as libc::c_int == 0x9bi32 as libc::c_char as libc::c_int {
+    return '{' as i32 as libc::c_char;
+  }
+  return ctrlconv[c as libc::c_uchar as usize];
+}
+/* Keep track of the colour the sequence s[0..n] leaves us with */
+unsafe fn sgr_add(
+  mut colour: &mut Vec<libc::c_char>,
+  mut s: *const libc::c_char,
+  mut n: libc::c_int,
+) {
+  let mut reset: libc::c_int = 0;
+  if n < 3 || *s.offset((n - 1i32) as isize) as libc::c_int != 'm' as i32 {
+    /* unfinished */
+    return;
+  }
+  /* "ESC[m", "ESC[0m" and "ESC[0;..m" start from scratch */
+  if n == 3 || *s.offset(2) as libc::c_int == '0' as i32 && n == 4 {
+    reset = 2
+  } else if *s.offset(2) as libc::c_int == '0' as i32 && *s.offset(3) as libc::c_int == ';' as i32 {
+    reset = 1
+  }
+  if reset != 0 {
+    colour.clear()
+  }
+  if reset != 2 {
+    colour.extend_from_slice(std::slice::from_raw_parts(s, n as usize))
+  }
+}
+/* Colour in effect where continuation line flines[i] starts */
+unsafe fn sgr_carry(mut i: libc::c_uint) -> Vec<libc::c_char> {
+  let mut colour: Vec<libc::c_char> = Vec::new();
+  let mut lineno: u32 = *((*(*ptr_to_globals).flines.offset(i as isize)).offset(-4) as *mut u32);
+  let mut j: libc::c_uint = i;
+  while j != 0
+    && *((*(*ptr_to_globals).flines.offset(j as isize - 1)).offset(-4) as *mut u32) == lineno
+  {
+    j = j.wrapping_sub(1)
+  }
+  while j < i {
+    let mut s: *const libc::c_char = *(*ptr_to_globals).flines.offset(j as isize);
+    while *s != 0 {
+      let mut n: libc::c_int = sgr_len(s);
+      if n != 0 {
+        sgr_add(&mut colour, s, n);
+        s = s.offset(n as isize)
+      } else {
+        s = s.offset(1)
+      }
+    }
+    j = j.wrapping_add(1)
+  }
+  return colour;
+}
+/* -R: like print_ascii(), but colour sequences go to the terminal as is */
+unsafe fn print_raw(mut str: *const libc::c_char, mut colour: Vec<libc::c_char>) {
+  let mut coloured: libc::c_int = (colour.len() != 0) as libc::c_int;
+  if coloured != 0 {
+    printf(
+      b"%.*s\x00" as *const u8 as *const libc::c_char,
+      colour.len() as libc::c_int,
+      colour.as_ptr(),
+    );
+  }
+  while *str != 0 {
+    let mut n: libc::c_int = sgr_len(str);
+    if n != 0 {
+      printf(b"%.*s\x00" as *const u8 as *const libc::c_char, n, str);
+      sgr_add(&mut colour, str, n);
+      str = str.offset(n as isize);
+      coloured = 1;
+      continue;
+    }
+    if strchr(controls.as_ptr(), *str as libc::c_int).is_null() {
+      crate::libbb::xfuncs_printf::bb_putchar(*str as libc::c_int);
+    } else {
+      /* highlighting resets the colour, set it again */
+      let mut buf: [libc::c_char; 2] = [ctrl_char(*str), 0];
+      print_hilite(buf.as_mut_ptr());
+      printf(
+        b"%.*s\x00" as *const u8 as *const libc::c_char,
+        colour.len() as libc::c_int,
+        colour.as_ptr(),
+      );
+    }
+    str = str.offset(1)
+  }
+  /* don't let it leak into the next line or the status line */
+  printf(if coloured != 0 {
+    b"\x1b[m\n\x00" as *const u8 as *const libc::c_char
+  } else {
+    b"\n\x00" as *const u8 as *const libc::c_char
+  });
+}
+/* -S: the part of the line starting at flines[i] that is visible
+ * when scrolled right by shift columns. Tabs become spaces,
+ * colour sequences to the left of the screen are kept. */
+unsafe fn shift_line(mut i: libc::c_uint) -> Vec<libc::c_char> {
+  let mut out: Vec<libc::c_char> = Vec::new();
+  let mut lineno: u32 = *((*(*ptr_to_globals).flines.offset(i as isize)).offset(-4) as *mut u32);
+  let mut w: libc::c_int = (*ptr_to_globals).width as libc::c_int;
+  let mut col: libc::c_int = 0;
+  let mut end: libc::c_int = 0;
+  if option_mask32 & FLAG_N as libc::c_int as libc::c_uint != 0 {
+    w -= 8i32
+  }
+  end = (*ptr_to_globals).shift + w;
+  's_10: while i <= (*ptr_to_globals).max_fline
+    && *((*(*ptr_to_globals).flines.offset(i as isize)).offset(-4) as *mut u32) == lineno
+  {
+    let mut s: *const libc::c_char = *(*ptr_to_globals).flines.offset(i as isize);
+    while *s != 0 {
+      let mut n: libc::c_int = sgr_len(s);
+      if n != 0 {
+        out.extend_from_slice(std::slice::from_raw_parts(s, n as usize));
+        s = s.offset(n as isize);
+        continue;
+      }
+      if *s as libc::c_int == '\t' as i32 {
+        n = (col + 8i32) & !7i32;
+        while col < n && col < end {
+          if col >= (*ptr_to_globals).shift {
+            out.push(' ' as i32 as libc::c_char)
+          }
+          col += 1
+        }
+      } else {
+        if col >= (*ptr_to_globals).shift {
+          out.push(*s)
+        }
+        col += 1
+      }
+      if col >= end {
+        break 's_10;
+      }
+      s = s.offset(1)
+    }
+    i = i.wrapping_add(1)
+  }
+  out.push(0);
+  return out;
+}
+/* Highlighting language of the current file, 0: none */
+unsafe fn syntax_lang() -> libc::c_int {
+  let mut i: libc::c_uint = 0;
+  if option_mask32 & FLAG_Y as libc::c_int as libc::c_uint != 0
+    || (*ptr_to_globals).pattern_valid != 0
+  {
+    return 0;
+  }
+  /* (careful: max_fline can be -1) */
+  if (*ptr_to_globals).syn_lang < 0 && (*ptr_to_globals).max_fline.wrapping_add(1) != 0 {
+    /* https://no-color.org: no highlighting if NO_COLOR is set and not empty */
+    let mut nc: *const libc::c_char = getenv(b"NO_COLOR\x00" as *const u8 as *const libc::c_char);
+    if !nc.is_null() && *nc as libc::c_int != 0 {
+      (*ptr_to_globals).syn_lang = 0;
+      return 0;
+    }
+    (*ptr_to_globals).syn_lang =
+      crate::libbb::syntax::syntax_for_file((*ptr_to_globals).filename);
+    /* -R and input coloured already (git, ls, grep...): leave it be */
+    while option_mask32 & FLAG_R as libc::c_int as libc::c_uint != 0
+      && i < 5
+      && i <= (*ptr_to_globals).max_fline
+    {
+      if !strchr(*(*ptr_to_globals).flines.offset(i as isize), 0x1bi32).is_null() {
+        (*ptr_to_globals).syn_lang = 0;
+        return 0;
+      }
+      i = i.wrapping_add(1)
+    }
+    i = 0;
+    /* else look at the first line, and for a modeline in the first five */
+    while (*ptr_to_globals).syn_lang == 0 && i < 5 && i <= (*ptr_to_globals).max_fline {
+      let mut line: *const libc::c_char = *(*ptr_to_globals).flines.offset(i as isize);
+      (*ptr_to_globals).syn_lang = crate::libbb::syntax::syntax_for_line(
+        line,
+        strlen(line) as libc::c_int,
+        (i == 0) as libc::c_int,
+      );
+      i = i.wrapping_add(1)
+    }
+  }
+  return if (*ptr_to_globals).syn_lang > 0 {
+    (*ptr_to_globals).syn_lang
+  } else {
+    0
+  };
+}
+/* Does fline i start and/or end a line of the file? */
+unsafe fn syntax_flags(mut i: libc::c_uint) -> libc::c_int {
+  let mut flags: libc::c_int = 0;
+  let mut lineno: u32 = *((*(*ptr_to_globals).flines.offset(i as isize)).offset(-4) as *mut u32);
+  if i == 0
+    || *((*(*ptr_to_globals).flines.offset(i as isize - 1)).offset(-4) as *mut u32) != lineno
+  {
+    flags |= crate::libbb::syntax::SYN_BOL
+  }
+  if i >= (*ptr_to_globals).max_fline
+    || *((*(*ptr_to_globals).flines.offset(i as isize + 1)).offset(-4) as *mut u32) != lineno
+  {
+    flags |= crate::libbb::syntax::SYN_EOL
+  }
+  return flags;
+}
+/* Advance the highlighter over fline i without printing it */
+unsafe fn syntax_skip(mut lang: libc::c_int, mut i: libc::c_uint, mut state: *mut libc::c_int) {
+  let mut line: *const libc::c_char = *(*ptr_to_globals).flines.offset(i as isize);
+  crate::libbb::syntax::syntax_line(
+    lang,
+    line,
+    strlen(line) as libc::c_int,
+    syntax_flags(i),
+    state,
+    std::ptr::null_mut(),
+  );
+}
+unsafe fn print_syntax(mut lang: libc::c_int, mut i: libc::c_uint, mut state: *mut libc::c_int) {
+  let mut str: *const libc::c_char = *(*ptr_to_globals).flines.offset(i as isize);
+  let mut len: libc::c_int = strlen(str) as libc::c_int;
+  let mut attr: Vec<u8> = ::std::vec::from_elem(0, len as usize + 1);
+  let mut hl: u8 = crate::libbb::syntax::HL_NORMAL;
+  let mut n: libc::c_int = 0;
+  crate::libbb::syntax::syntax_line(lang, str, len, syntax_flags(i), state, attr.as_mut_ptr());
+  while n < len {
+    let mut c: libc::c_char = *str.offset(n as isize);
+    if attr[n as usize] != hl {
+      hl = attr[n as usize];
+      printf(
+        b"%s\x00" as *const u8 as *const libc::c_char,
+        crate::libbb::syntax::syntax_sgr(hl),
+      );
+    }
+    if strchr(controls.as_ptr(), c as libc::c_int).is_null() {
+      crate::libbb::xfuncs_printf::bb_putchar(c as libc::c_int);
+    } else {
+      /* same as print_ascii(), then back to our colour */
+      let mut buf: [libc::c_char; 2] = [ctrl_char(c), 0];
+      print_hilite(buf.as_mut_ptr());
+      printf(
+        b"%s\x00" as *const u8 as *const libc::c_char,
+        crate::libbb::syntax::syntax_sgr(hl),
+      );
+    }
+    n += 1
+  }
+  if hl != crate::libbb::syntax::HL_NORMAL {
+    printf(
+      b"%s\x00" as *const u8 as *const libc::c_char,
+      crate::libbb::syntax::syntax_sgr(crate::libbb::syntax::HL_NORMAL),
+    );
+  }
+  crate::libbb::xfuncs_printf::bb_putchar('\n' as i32);
+}
 /* Print the buffer */
 unsafe fn buffer_print() {
   let mut i: libc::c_uint = 0;
+  let mut lang: libc::c_int = syntax_lang();
+  let mut sp: libc::c_uint = 0;
+  let mut st: libc::c_int = 0;
+  /* do we know where in flines[] the displayed lines are? */
+  let mut known: libc::c_int = 0;
+  let mut first: *const libc::c_char = *(*ptr_to_globals).buffer.offset(0);
+  if first != (*ptr_to_globals).empty_line_marker {
+    sp = (*ptr_to_globals).cur_fline as libc::c_uint;
+    while sp != 0 && *(*ptr_to_globals).flines.offset(sp as isize) != first {
+      sp = sp.wrapping_sub(1)
+    }
+    known = (*(*ptr_to_globals).flines.offset(sp as isize) == first) as libc::c_int
+  }
+  if known == 0 {
+    lang = 0
+  }
+  if lang != 0 {
+    if crate::libbb::syntax::syntax_multiline(lang) != 0 {
+      /* the state there depends on everything above, resume
+       * from where the previous screen started if we can */
+      let mut from: libc::c_uint = 0;
+      if !(*ptr_to_globals).syn_line.is_null()
+        && (*ptr_to_globals).syn_pos <= sp
+        && *(*ptr_to_globals)
+          .flines
+          .offset((*ptr_to_globals).syn_pos as isize)
+          == (*ptr_to_globals).syn_line
+      {
+        from = (*ptr_to_globals).syn_pos;
+        st = (*ptr_to_globals).syn_state
+      }
+      while from < sp {
+        syntax_skip(lang, from, &mut st);
+        from = from.wrapping_add(1)
+      }
+      (*ptr_to_globals).syn_pos = sp;
+      (*ptr_to_globals).syn_state = st;
+      (*ptr_to_globals).syn_line = first
+    }
+  }
   move_cursor(0i32, 0);
   i = 0 as libc::c_uint;
   while i <= (*ptr_to_globals).max_displayed_line {
+    let mut line: *const libc::c_char = *(*ptr_to_globals).buffer.offset(i as isize);
+    let mut shifted: Vec<libc::c_char> = Vec::new();
+    let mut fline: libc::c_int = 0;
     printf(b"\x1b[K\x00" as *const u8 as *const libc::c_char);
     if option_mask32 & FLAG_N as libc::c_int as libc::c_uint != 0 {
-      print_lineno(*(*ptr_to_globals).buffer.offset(i as isize));
+      print_lineno(line);
+    }
+    if known != 0 && line != (*ptr_to_globals).empty_line_marker {
+      /* with -S, continuation lines are not shown but still scanned */
+      while sp < (*ptr_to_globals).max_fline
+        && *(*ptr_to_globals).flines.offset(sp as isize) != line
+      {
+        if lang != 0 {
+          syntax_skip(lang, sp, &mut st);
+        }
+        sp = sp.wrapping_add(1)
+      }
+      fline = 1;
+      if option_mask32 & FLAG_S as libc::c_int as libc::c_uint != 0
+        && (*ptr_to_globals).shift != 0
+      {
+        shifted = shift_line(sp);
+        line = shifted.as_ptr();
+        /* not highlighted, but the state has to move on */
+        if lang != 0 {
+          syntax_skip(lang, sp, &mut st);
+        }
+      }
     }
     if (*ptr_to_globals).pattern_valid != 0 {
-      print_found(*(*ptr_to_globals).buffer.offset(i as isize));
+      print_found(line);
+    } else if lang != 0 && fline != 0 && shifted.len() == 0 {
+      print_syntax(lang, sp, &mut st);
+    } else if option_mask32 & FLAG_R as libc::c_int as libc::c_uint != 0 && fline != 0 {
+      print_raw(
+        line,
+        if shifted.len() == 0 {
+          sgr_carry(sp)
+        } else {
+          Vec::new()
+        },
+      );
     } else {
-      print_ascii(*(*ptr_to_globals).buffer.offset(i as isize));
+      print_ascii(line);
+    }
+    if fline != 0 {
+      sp = sp.wrapping_add(1)
     }
     i = i.wrapping_add(1)
   }
@@ -1150,6 +1656,8 @@ unsafe fn open_file_and_read_lines() {
 /* Reinitialize everything for a new file - free the memory and start over */
 unsafe fn reinitialize() {
   let mut i: libc::c_uint = 0;
+  (*ptr_to_globals).syn_lang = -1i32;
+  (*ptr_to_globals).syn_line = std::ptr::null();
   if !(*ptr_to_globals).flines.is_null() {
     i = 0 as libc::c_uint;
     while i <= (*ptr_to_globals).max_fline {
@@ -1622,6 +2130,12 @@ unsafe fn number_process(mut first_digit: libc::c_int) {
     -2 | 98 | 119 | 121 | 117 => {
       buffer_up(num);
     }
+    -4 => {
+      shift_by(1i32, num);
+    }
+    -5 => {
+      shift_by(-1i32, num);
+    }
     103 | 60 | 71 | 62 => {
       buffer_lineno(num - 1i32);
     }
@@ -1662,6 +2176,7 @@ unsafe fn flag_change() {
     126 => option_mask32 ^= FLAG_TILDE as libc::c_int as libc::c_uint,
     83 => {
       option_mask32 ^= FLAG_S as libc::c_int as libc::c_uint;
+      (*ptr_to_globals).shift = 0;
       buffer_fill_and_print();
     }
     78 => {
@@ -1669,6 +2184,10 @@ unsafe fn flag_change() {
       re_wrap();
       buffer_fill_and_print();
     }
+    89 => {
+      option_mask32 ^= FLAG_Y as libc::c_int as libc::c_uint;
+      buffer_fill_and_print();
+    }
     _ => {}
   };
 }
@@ -1848,6 +2367,111 @@ unsafe fn match_left_bracket(mut bracket: libc::c_char) {
   print_statusline(b"No matching bracket found\x00" as *const u8 as *const libc::c_char);
 }
 /* FEATURE_LESS_BRACKETS */
+static mut less_longopts: [u8; 181] = *b"\
+  QUIT-AT-EOF\x00\x00Equit-if-one-screen\x00\x00FIGNORE-CASE\x00\x00ILONG-PROMPT\x00\x00M\
+  long-prompt\x00\x00mLINE-NUMBERS\x00\x00Nchop-long-lines\x00\x00SRAW-CONTROL-CHARS\x00\x00R\
+  squeeze-blank-lines\x00\x00stilde\x00\x00~no-syntax\x00\x00Yshift\x00\x01#\x00";
+/* Tools typically pass LESS="FRSXMK", people "-R -#8 --shift=4" and such.
+ * "-+X" turns X off again. The options we don't understand are ignored,
+ * including the values some of them take. */
+unsafe fn less_env(mut c: *const libc::c_char) {
+  while *c != 0 {
+    let mut opt: libc::c_int = *c as libc::c_int;
+    let mut off: libc::c_int = 0;
+    let mut bit: libc::c_uint = 0;
+    c = c.offset(1);
+    if opt == '-' as i32 && *c as libc::c_int == '-' as i32 {
+      /* --name[=value] */
+      let mut p: *const libc::c_char = less_longopts.as_ptr() as *const libc::c_char;
+      let mut len: size_t = 0;
+      c = c.offset(1);
+      len = strcspn(c, b"= \t\x00" as *const u8 as *const libc::c_char);
+      opt = 0;
+      while *p != 0 {
+        if strlen(p) == len && strncmp(p, c, len as usize) == 0 {
+          opt = *p.offset(len.wrapping_add(2) as isize) as libc::c_int;
+          break;
+        }
+        p = p.offset(strlen(p).wrapping_add(3) as isize)
+      }
+      c = c.offset(len as isize);
+      if *c as libc::c_int == '=' as i32 {
+        c = c.offset(1)
+      }
+      if opt == 0 {
+        c = c.offset(strcspn(c, b" \t\x00" as *const u8 as *const libc::c_char) as isize);
+        continue;
+      }
+    } else if opt == '-' as i32 && *c as libc::c_int == '+' as i32 {
+      off = 1;
+      opt = *c.offset(1) as libc::c_int;
+      if opt == 0 {
+        break;
+      }
+      c = c.offset(2)
+    }
+    match opt {
+      69 => bit = FLAG_E as libc::c_int as libc::c_uint,
+      70 => bit = FLAG_F as libc::c_int as libc::c_uint,
+      73 => bit = FLAG_I as libc::c_int as libc::c_uint,
+      77 => bit = FLAG_M as libc::c_int as libc::c_uint,
+      109 => bit = FLAG_m as libc::c_int as libc::c_uint,
+      78 => bit = FLAG_N as libc::c_int as libc::c_uint,
+      82 | 114 => bit = FLAG_R as libc::c_int as libc::c_uint,
+      83 => bit = FLAG_S as libc::c_int as libc::c_uint,
+      89 => bit = FLAG_Y as libc::c_int as libc::c_uint,
+      126 => bit = FLAG_TILDE as libc::c_int as libc::c_uint,
+      35 => {
+        /* -#N, --shift=N */
+        if off != 0 {
+          (*ptr_to_globals).shift_step = 0
+        } else {
+          (*ptr_to_globals).shift_step = atoi(c)
+        }
+        c = c.offset(strspn(c, b"0123456789.\x00" as *const u8 as *const libc::c_char) as isize)
+      }
+      98 | 104 | 106 | 120 | 121 | 122 => {
+        /* -bN -hN -jN -xN -yN -zN */
+        c = c.offset(strspn(c, b"0123456789.,-\x00" as *const u8 as *const libc::c_char) as isize)
+      }
+      68 | 107 | 111 | 79 | 112 | 116 | 84 => {
+        /* -Dxcolor -kfile -ofile -Ofile -ppattern -ttag -Ttagsfile */
+        c = c.offset(strcspn(c, b" \t\x00" as *const u8 as *const libc::c_char) as isize)
+      }
+      80 => {
+        /* -Pprompt, up to '$' */
+        c = c.offset(strcspn(c, b"$\x00" as *const u8 as *const libc::c_char) as isize);
+        if *c as libc::c_int == '$' as i32 {
+          c = c.offset(1)
+        }
+      }
+      _ => {}
+    }
+    if off != 0 {
+      option_mask32 &= !bit
+    } else {
+      option_mask32 |= bit
+    }
+  }
+}
+/* -S: scroll sideways, dir < 0: to the left, by cols or else
+ * by the -# amount (0: half a screen) */
+unsafe fn shift_by(mut dir: libc::c_int, mut cols: libc::c_int) {
+  if option_mask32 & FLAG_S as libc::c_int as libc::c_uint == 0 {
+    return;
+  }
+  if cols <= 0 {
+    cols = (*ptr_to_globals).shift_step;
+    if cols <= 0 {
+      cols = ((*ptr_to_globals).width / 2i32 as libc::c_uint) as libc::c_int
+    }
+  }
+  (*ptr_to_globals).shift += dir * cols;
+  if (*ptr_to_globals).shift < 0 {
+    (*ptr_to_globals).shift = 0
+  }
+  buffer_print();
+}
 unsafe fn keypress_process(mut keypress: libc::c_int) {
   match keypress {
     -3 | 101 | 106 | 13 => {
@@ -1856,6 +2480,12 @@ unsafe fn keypress_process(mut keypress: libc::c_int) {
     -2 | 121 | 107 => {
       buffer_up(1i32);
     }
+    -4 => {
+      shift_by(1i32, 0);
+    }
+    -5 => {
+      shift_by(-1i32, 0);
+    }
     -11 | 32 | 122 | 102 => {
       buffer_down(
         (*ptr_to_globals)
@@ -1984,26 +2614,22 @@ pub unsafe fn less_main(mut argc: libc::c_int, mut argv: *mut *mut libc::c_char)
    * -s: condense many empty lines to one
    *     (used by some setups for manpage display)
    */
-  crate::libbb::getopt32::getopt32(argv, b"EMmN~IFSRs\x00" as *const u8 as *const libc::c_char);
+  /* $LESS first, the command line can add to it */
+  let mut c: *mut libc::c_char = getenv(b"LESS\x00" as *const u8 as *const libc::c_char);
+  if !c.is_null() {
+    less_env(c);
+  }
+  let mut env_opts: libc::c_uint = option_mask32;
+  crate::libbb::getopt32::getopt32long(
+    argv,
+    b"EMmN~IFSRsY#:+\x00" as *const u8 as *const libc::c_char,
+    less_longopts.as_ptr() as *const libc::c_char,
+    &mut (*ptr_to_globals).shift_step as *mut libc::c_int,
+  );
+  option_mask32 |= env_opts;
   argv = argv.offset(optind as isize);
   (*ptr_to_globals).num_files = (argc - optind) as libc::c_uint;
   (*ptr_to_globals).files = argv;
-  /* Tools typically pass LESS="FRSXMK".
-   * The options we don't understand are ignored. */
-  let mut c: *mut libc::c_char = getenv(b"LESS\x00" as *const u8 as *const libc::c_char);
-  if !c.is_null() {
-    while *c != 0 {
-      let fresh14 = c;
-      c = c.offset(1);
-      match *fresh14 as libc::c_int {
-        70 => option_mask32 |= FLAG_F as libc::c_int as libc::c_uint,
-        77 => option_mask32 |= FLAG_M as libc::c_int as libc::c_uint,
-        82 => option_mask32 |= FLAG_R as libc::c_int as libc::c_uint,
-        83 => option_mask32 |= FLAG_S as libc::c_int as libc::c_uint,
-        _ => {}
-      }
-    }
-  }
   /* Another popular pager, most, detects when stdout
    * is not a tty and turns into cat. This makes sense. */
   if isatty(1i32) == 0 {
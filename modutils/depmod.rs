@@ -0,0 +1,770 @@
+use crate::libbb::llist::llist_t;
+use crate::librb::__compar_fn_t;
+use crate::librb::size_t;
+use crate::modutils::modutils::module_db;
+use crate::modutils::modutils::module_entry;
+use libc;
+use libc::fclose;
+use libc::fprintf;
+use libc::free;
+use libc::stat;
+use libc::strcmp;
+use libc::strstr;
+use libc::FILE;
+extern "C" {
+  #[no_mangle]
+  static mut optind: libc::c_int;
+  #[no_mangle]
+  static mut option_mask32: u32;
+  #[no_mangle]
+  static mut stdout: *mut FILE;
+  #[no_mangle]
+  fn strlen(__s: *const libc::c_char) -> size_t;
+  #[no_mangle]
+  fn memset(_: *mut libc::c_void, _: libc::c_int, _: libc::c_ulong) -> *mut libc::c_void;
+  #[no_mangle]
+  fn memcpy(_: *mut libc::c_void, _: *const libc::c_void, _: libc::c_ulong) -> *mut libc::c_void;
+  #[no_mangle]
+  fn sscanf(_: *const libc::c_char, _: *const libc::c_char, _: ...) -> libc::c_int;
+  #[no_mangle]
+  fn qsort(__base: *mut libc::c_void, __nmemb: size_t, __size: size_t, __compar: __compar_fn_t);
+  #[no_mangle]
+  fn uname(__name: *mut utsname) -> libc::c_int;
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct utsname {
+  pub sysname: [libc::c_char; 65],
+  pub nodename: [libc::c_char; 65],
+  pub release: [libc::c_char; 65],
+  pub version: [libc::c_char; 65],
+  pub machine: [libc::c_char; 65],
+  pub domainname: [libc::c_char; 65],
+}
+
+/* One key -> value pair of a kmod-style binary index */
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct index_value {
+  pub key: *mut libc::c_char,
+  pub value: *mut libc::c_char,
+  pub priority: libc::c_uint,
+  pub seq: libc::c_uint,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct index_t {
+  pub values: *mut index_value,
+  pub count: libc::c_uint,
+  pub buf: *mut u8,
+  pub len: libc::c_uint,
+  pub size: libc::c_uint,
+}
+
+pub const ACTION_RECURSE: C2RustUnnamed = 1;
+pub type C2RustUnnamed = libc::c_uint;
+
+pub const OPT_n: C2RustUnnamed_0 = 32;
+pub type C2RustUnnamed_0 = libc::c_uint;
+
+/* Format of modules.*.bin, as read by kmod's libkmod-index.c:
+ * header: magic, version, offset of root node (all big-endian u32).
+ * Node offsets carry flags telling which parts the node has.
+ * A node is: [prefix NUL] [first last child_offset[last-first+1]]
+ * [value_count {priority value NUL}...]. Children precede parents.
+ */
+pub const INDEX_MAGIC: u32 = 0xb007f457;
+pub const INDEX_VERSION: u32 = 0x00020001;
+pub const INDEX_NODE_PREFIX: u32 = 0x80000000;
+pub const INDEX_NODE_VALUES: u32 = 0x40000000;
+pub const INDEX_NODE_CHILDS: u32 = 0x20000000;
+pub const INDEX_CHILDMAX: libc::c_int = 128;
+
+/*
+ * depmod - generate modules.dep
+ * Copyright (c) 2008 Bernhard Reutner-Fischer
+ * Copyrihgt (c) 2008 Timo Teras <timo.teras@iki.fi>
+ * Copyright (c) 2008 Vladimir Dronnikov
+ *
+ * Licensed under GPLv2 or later, see file LICENSE in this source tree.
+ */
+/* Is basename a .ko, .ko.gz, .ko.xz or .ko.zst? */
+unsafe extern "C" fn is_module_file(mut fname: *const libc::c_char) -> bool {
+  let mut ext: *const libc::c_char = strstr(
+    crate::libbb::get_last_path_component::bb_basename(fname),
+    b".ko\x00" as *const u8 as *const libc::c_char,
+  );
+  if ext.is_null() {
+    return false;
+  }
+  ext = ext.offset(3);
+  return *ext == 0
+    || strcmp(ext, b".gz\x00" as *const u8 as *const libc::c_char) == 0
+    || strcmp(ext, b".xz\x00" as *const u8 as *const libc::c_char) == 0
+    || strcmp(ext, b".zst\x00" as *const u8 as *const libc::c_char) == 0;
+}
+unsafe fn parse_module(
+  mut fname: *const libc::c_char,
+  mut _sb: *mut stat,
+  mut data: *mut libc::c_void,
+  mut _depth: libc::c_int,
+) -> libc::c_int {
+  let mut modules: *mut module_db = data as *mut module_db;
+  let mut image: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut ptr: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut e: *mut module_entry = std::ptr::null_mut();
+  /* Arbitrary. Was sb->st_size, but that breaks .gz etc */
+  let mut len: size_t = (64i32 * 1024i32 * 1024i32 - 4096i32) as size_t;
+  if !is_module_file(fname) {
+    return 1i32;
+  }
+  e = crate::modutils::modutils::moddb_get_or_create(
+    modules,
+    crate::libbb::get_last_path_component::bb_get_last_path_component_nostrip(fname),
+  );
+  if !crate::libbb::compare_string_array::is_prefixed_with(
+    fname,
+    b"./\x00" as *const u8 as *const libc::c_char,
+  )
+  .is_null()
+  {
+    fname = fname.offset(2)
+  }
+  (*e).name = crate::libbb::xfuncs_printf::xstrdup(fname);
+  if !crate::libbb::compare_string_array::is_suffixed_with(
+    fname,
+    b".zst\x00" as *const u8 as *const libc::c_char,
+  )
+  .is_null()
+  {
+    /* Still list it in modules.dep: the kernel can unpack it */
+    crate::libbb::verror_msg::bb_error_msg(
+      b"%s: can\'t read zstd compressed module, dependencies not scanned\x00" as *const u8
+        as *const libc::c_char,
+      fname,
+    );
+    return 1i32;
+  }
+  image =
+    crate::archival::libarchive::open_transformer::xmalloc_open_zipped_read_close(fname, &mut len)
+      as *mut libc::c_char;
+  if image.is_null() {
+    return 1i32;
+  }
+  ptr = image;
+  while ptr < image.offset(len as isize).offset(-10) {
+    if !crate::libbb::compare_string_array::is_prefixed_with(
+      ptr,
+      b"depends=\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      ptr = ptr.offset(8);
+      crate::modutils::modutils::replace(
+        ptr,
+        '-' as i32 as libc::c_char,
+        '_' as i32 as libc::c_char,
+      );
+      ptr = ptr.offset(crate::modutils::modutils::string_to_llist(
+        ptr,
+        &mut (*e).deps,
+        b",\x00" as *const u8 as *const libc::c_char,
+      ) as isize)
+    } else if !crate::libbb::compare_string_array::is_prefixed_with(
+      ptr,
+      b"alias=\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      crate::libbb::llist::llist_add_to(
+        &mut (*e).aliases,
+        crate::libbb::xfuncs_printf::xstrdup(ptr.offset(6)) as *mut libc::c_void,
+      );
+      ptr = ptr.offset(strlen(ptr) as isize)
+    } else if !crate::libbb::compare_string_array::is_prefixed_with(
+      ptr,
+      b"softdep=\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      /* "softdep=pre: mod1 mod2 post: mod3" */
+      crate::libbb::llist::llist_add_to_end(
+        &mut (*e).softdeps,
+        crate::libbb::xfuncs_printf::xstrdup(ptr.offset(8)) as *mut libc::c_void,
+      );
+      ptr = ptr.offset(strlen(ptr) as isize)
+    } else if !crate::libbb::compare_string_array::is_prefixed_with(
+      ptr,
+      b"__ksymtab_\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      ptr = ptr.offset(10);
+      if !crate::libbb::compare_string_array::is_prefixed_with(
+        ptr,
+        b"gpl\x00" as *const u8 as *const libc::c_char,
+      )
+      .is_null()
+        || strcmp(ptr, b"strings\x00" as *const u8 as *const libc::c_char) == 0
+      {
+        ptr = ptr.offset(1);
+        continue;
+      }
+      crate::libbb::llist::llist_add_to(
+        &mut (*e).symbols,
+        crate::libbb::xfuncs_printf::xstrdup(ptr) as *mut libc::c_void,
+      );
+      ptr = ptr.offset(strlen(ptr) as isize)
+    }
+    ptr = ptr.offset(1)
+  }
+  free(image as *mut libc::c_void);
+  return 1i32;
+}
+unsafe extern "C" fn order_dep_list(
+  mut modules: *mut module_db,
+  mut start: *mut module_entry,
+  mut add: *mut llist_t,
+) {
+  let mut m: *mut module_entry = std::ptr::null_mut();
+  let mut n: *mut llist_t = std::ptr::null_mut();
+  n = add;
+  while !n.is_null() {
+    m = crate::modutils::modutils::moddb_get(modules, (*n).data as *const libc::c_char);
+    if !m.is_null() {
+      /* unlink current entry */
+      (*(*m).dnext).dprev = (*m).dprev;
+      (*(*m).dprev).dnext = (*m).dnext;
+      /* and add it to tail */
+      (*m).dnext = start;
+      (*m).dprev = (*start).dprev;
+      (*(*start).dprev).dnext = m;
+      (*start).dprev = m;
+      /* recurse */
+      order_dep_list(modules, start, (*m).deps);
+    }
+    n = (*n).link
+  }
+}
+unsafe extern "C" fn index_add(
+  mut idx: *mut index_t,
+  mut key: *const libc::c_char,
+  mut value: *const libc::c_char,
+  mut priority: libc::c_uint,
+) {
+  let mut v: *mut index_value = std::ptr::null_mut();
+  let mut p: *const libc::c_char = key;
+  /* kmod's trie only has room for 7-bit characters */
+  while *p != 0 {
+    if *p as u8 as libc::c_int >= INDEX_CHILDMAX {
+      return;
+    }
+    p = p.offset(1)
+  }
+  (*idx).values = crate::libbb::xrealloc_vector::xrealloc_vector_helper(
+    (*idx).values as *mut libc::c_void,
+    ((::std::mem::size_of::<index_value>() as libc::c_ulong) << 8i32)
+      .wrapping_add(6i32 as libc::c_ulong) as libc::c_uint,
+    (*idx).count as libc::c_int,
+  ) as *mut index_value;
+  v = (*idx).values.offset((*idx).count as isize);
+  (*v).key = crate::libbb::xfuncs_printf::xstrdup(key);
+  (*v).value = crate::libbb::xfuncs_printf::xstrdup(value);
+  (*v).priority = priority;
+  (*v).seq = (*idx).count;
+  (*idx).count = (*idx).count.wrapping_add(1);
+}
+unsafe extern "C" fn index_value_cmp(
+  mut a: *const libc::c_void,
+  mut b: *const libc::c_void,
+) -> libc::c_int {
+  let mut va: *const index_value = a as *const index_value;
+  let mut vb: *const index_value = b as *const index_value;
+  let mut r: libc::c_int = strcmp((*va).key, (*vb).key);
+  if r != 0 {
+    return r;
+  }
+  if (*va).priority != (*vb).priority {
+    return if (*va).priority < (*vb).priority {
+      -1i32
+    } else {
+      1i32
+    };
+  }
+  return (*va).seq as libc::c_int - (*vb).seq as libc::c_int;
+}
+unsafe extern "C" fn index_put(
+  mut idx: *mut index_t,
+  mut data: *const libc::c_void,
+  mut n: libc::c_uint,
+) {
+  if (*idx).len.wrapping_add(n) > (*idx).size {
+    (*idx).size = (*idx).len.wrapping_add(n).wrapping_add(64 * 1024);
+    (*idx).buf =
+      crate::libbb::xfuncs_printf::xrealloc((*idx).buf as *mut libc::c_void, (*idx).size as size_t)
+        as *mut u8
+  }
+  memcpy(
+    (*idx).buf.offset((*idx).len as isize) as *mut libc::c_void,
+    data,
+    n as libc::c_ulong,
+  );
+  (*idx).len = (*idx).len.wrapping_add(n);
+}
+unsafe extern "C" fn index_put_u32(mut idx: *mut index_t, mut v: u32) {
+  let mut be: [u8; 4] = v.to_be_bytes();
+  index_put(
+    idx,
+    be.as_mut_ptr() as *const libc::c_void,
+    4i32 as libc::c_uint,
+  );
+}
+/* Writes the trie node holding values[lo..hi), all of which share
+ * the first depth chars of the key. Returns its (flagged) offset. */
+unsafe extern "C" fn index_write_node(
+  mut idx: *mut index_t,
+  mut lo: libc::c_uint,
+  mut hi: libc::c_uint,
+  mut depth: libc::c_uint,
+) -> u32 {
+  let mut child_offs: [u32; 128] = [0; 128];
+  let mut first_ch: libc::c_int = INDEX_CHILDMAX;
+  let mut last_ch: libc::c_int = -1i32;
+  let mut pos: libc::c_uint = depth;
+  let mut vend: libc::c_uint = lo;
+  let mut i: libc::c_uint = 0;
+  let mut offset: u32 = 0;
+  let mut first: *const libc::c_char = std::ptr::null();
+  let mut last: *const libc::c_char = std::ptr::null();
+  if lo < hi {
+    /* Keys are sorted: common prefix of first and last is common to all */
+    first = (*(*idx).values.offset(lo as isize)).key;
+    last = (*(*idx).values.offset(hi.wrapping_sub(1) as isize)).key;
+    while *first.offset(pos as isize) as libc::c_int != 0
+      && *first.offset(pos as isize) as libc::c_int == *last.offset(pos as isize) as libc::c_int
+    {
+      pos = pos.wrapping_add(1)
+    }
+    while vend < hi
+      && *(*(*idx).values.offset(vend as isize))
+        .key
+        .offset(pos as isize)
+        == 0
+    {
+      vend = vend.wrapping_add(1)
+    }
+  }
+  /* Children first, so that their offsets are known */
+  i = vend;
+  while i < hi {
+    let mut ch: libc::c_int =
+      *(*(*idx).values.offset(i as isize)).key.offset(pos as isize) as u8 as libc::c_int;
+    let mut j: libc::c_uint = i;
+    while j < hi
+      && *(*(*idx).values.offset(j as isize)).key.offset(pos as isize) as u8 as libc::c_int == ch
+    {
+      j = j.wrapping_add(1)
+    }
+    child_offs[ch as usize] = index_write_node(idx, i, j, pos.wrapping_add(1));
+    if ch < first_ch {
+      first_ch = ch
+    }
+    last_ch = ch;
+    i = j
+  }
+  offset = (*idx).len;
+  if pos > depth {
+    index_put(
+      idx,
+      first.offset(depth as isize) as *const libc::c_void,
+      pos.wrapping_sub(depth),
+    );
+    index_put(
+      idx,
+      b"\x00" as *const u8 as *const libc::c_void,
+      1i32 as libc::c_uint,
+    );
+    offset |= INDEX_NODE_PREFIX
+  }
+  if last_ch >= 0 {
+    let mut c: [u8; 2] = [first_ch as u8, last_ch as u8];
+    index_put(
+      idx,
+      c.as_mut_ptr() as *const libc::c_void,
+      2i32 as libc::c_uint,
+    );
+    let mut ch_0: libc::c_int = first_ch;
+    while ch_0 <= last_ch {
+      index_put_u32(idx, child_offs[ch_0 as usize]);
+      ch_0 += 1
+    }
+    offset |= INDEX_NODE_CHILDS
+  }
+  if vend > lo {
+    index_put_u32(idx, vend.wrapping_sub(lo));
+    i = lo;
+    while i < vend {
+      let mut v: *mut index_value = (*idx).values.offset(i as isize);
+      index_put_u32(idx, (*v).priority);
+      index_put(
+        idx,
+        (*v).value as *const libc::c_void,
+        (strlen((*v).value) as libc::c_uint).wrapping_add(1),
+      );
+      i = i.wrapping_add(1)
+    }
+    offset |= INDEX_NODE_VALUES
+  }
+  return offset;
+}
+unsafe extern "C" fn index_write(mut idx: *mut index_t, mut filename: *const libc::c_char) {
+  let mut fd: libc::c_int = 0;
+  let mut root: u32 = 0;
+  let mut i: libc::c_uint = 0;
+  qsort(
+    (*idx).values as *mut libc::c_void,
+    (*idx).count as size_t,
+    ::std::mem::size_of::<index_value>() as libc::c_ulong,
+    Some(
+      index_value_cmp
+        as unsafe extern "C" fn(_: *const libc::c_void, _: *const libc::c_void) -> libc::c_int,
+    ),
+  );
+  index_put_u32(idx, INDEX_MAGIC);
+  index_put_u32(idx, INDEX_VERSION);
+  index_put_u32(idx, 0 as u32);
+  root = index_write_node(idx, 0 as libc::c_uint, (*idx).count, 0 as libc::c_uint);
+  let mut be: [u8; 4] = root.to_be_bytes();
+  memcpy(
+    (*idx).buf.offset(8) as *mut libc::c_void,
+    be.as_mut_ptr() as *const libc::c_void,
+    4i32 as libc::c_ulong,
+  );
+  fd = crate::libbb::xfuncs_printf::xopen3(filename, 0o1 | 0o100 | 0o1000, 0o644);
+  crate::libbb::xfuncs_printf::xwrite(fd, (*idx).buf as *const libc::c_void, (*idx).len as size_t);
+  libc::close(fd);
+  while i < (*idx).count {
+    free((*(*idx).values.offset(i as isize)).key as *mut libc::c_void);
+    free((*(*idx).values.offset(i as isize)).value as *mut libc::c_void);
+    i = i.wrapping_add(1)
+  }
+  free((*idx).values as *mut libc::c_void);
+  free((*idx).buf as *mut libc::c_void);
+  memset(
+    idx as *mut libc::c_void,
+    0,
+    ::std::mem::size_of::<index_t>() as libc::c_ulong,
+  );
+}
+/* kmod looks aliases up with '-' turned into '_', except inside [] */
+unsafe extern "C" fn alias_normalize(mut alias: *const libc::c_char) -> *mut libc::c_char {
+  let mut s: *mut libc::c_char = crate::libbb::xfuncs_printf::xstrdup(alias);
+  let mut p: *mut libc::c_char = s;
+  let mut in_brackets: bool = false;
+  while *p != 0 {
+    if *p as libc::c_int == '[' as i32 {
+      in_brackets = true
+    } else if *p as libc::c_int == ']' as i32 {
+      in_brackets = false
+    } else if *p as libc::c_int == '-' as i32 && !in_brackets {
+      *p = '_' as i32 as libc::c_char
+    }
+    p = p.offset(1)
+  }
+  return s;
+}
+unsafe extern "C" fn open_output(mut file: *const libc::c_char) -> *mut FILE {
+  if option_mask32 & OPT_n as libc::c_int as libc::c_uint != 0 {
+    return stdout;
+  }
+  return crate::libbb::wfopen::xfopen_for_write(file);
+}
+unsafe extern "C" fn close_output(mut fp: *mut FILE) {
+  if fp != stdout {
+    fclose(fp);
+  }
+}
+pub unsafe fn depmod_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  let mut modules: module_db = module_db {
+    buckets: [std::ptr::null_mut(); 256],
+  };
+  let mut idx: index_t = index_t {
+    values: std::ptr::null_mut(),
+    count: 0,
+    buf: std::ptr::null_mut(),
+    len: 0,
+    size: 0,
+  };
+  let mut m: *mut module_entry = std::ptr::null_mut();
+  let mut dep: *mut module_entry = std::ptr::null_mut();
+  let mut moddir_base: *const libc::c_char = b"/\x00" as *const u8 as *const libc::c_char;
+  let mut moddir: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut version: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut uts: utsname = utsname {
+    sysname: [0; 65],
+    nodename: [0; 65],
+    release: [0; 65],
+    version: [0; 65],
+    machine: [0; 65],
+    domainname: [0; 65],
+  };
+  let mut fp: *mut FILE = std::ptr::null_mut();
+  let mut i: libc::c_uint = 0;
+  let mut tmp: libc::c_int = 0;
+  let mut modidx: libc::c_uint = 0;
+  let mut write_bin: bool = false;
+  crate::libbb::getopt32::getopt32(
+    argv,
+    b"aAb:eF:nruqC:vw\x00" as *const u8 as *const libc::c_char,
+    &mut moddir_base as *mut *const libc::c_char,
+    0 as *mut libc::c_void,
+    0 as *mut libc::c_void,
+  );
+  argv = argv.offset(optind as isize);
+  write_bin = option_mask32 & OPT_n as libc::c_int as libc::c_uint == 0;
+  /* goto modules location */
+  crate::libbb::xfuncs_printf::xchdir(moddir_base);
+  /* If a version is provided, then that kernel version's module directory
+   * is used, rather than the current kernel version (as returned by
+   * "uname -r").  */
+  if !(*argv).is_null()
+    && sscanf(
+      *argv,
+      b"%u.%u.%u\x00" as *const u8 as *const libc::c_char,
+      &mut tmp as *mut libc::c_int,
+      &mut tmp as *mut libc::c_int,
+      &mut tmp as *mut libc::c_int,
+    ) == 3i32
+  {
+    let fresh0 = argv;
+    argv = argv.offset(1);
+    version = *fresh0
+  } else {
+    uname(&mut uts);
+    version = uts.release.as_mut_ptr()
+  }
+  moddir = crate::libbb::concat_path_file::concat_path_file(
+    b"lib/modules\x00" as *const u8 as *const libc::c_char,
+    version,
+  );
+  crate::libbb::xfuncs_printf::xchdir(moddir);
+  free(moddir as *mut libc::c_void);
+  /* Scan modules */
+  if !(*argv).is_null() {
+    loop {
+      parse_module(
+        *argv,
+        std::ptr::null_mut(),
+        &mut modules as *mut module_db as *mut libc::c_void,
+        0,
+      );
+      argv = argv.offset(1);
+      if (*argv).is_null() {
+        break;
+      }
+    }
+  } else {
+    crate::libbb::recursive_action::recursive_action(
+      b".\x00" as *const u8 as *const libc::c_char,
+      ACTION_RECURSE as libc::c_int as libc::c_uint,
+      Some(parse_module),
+      None,
+      &mut modules as *mut module_db as *mut libc::c_void,
+      0 as libc::c_uint,
+    );
+  }
+  /* Generate dependency and alias files */
+  fp = open_output(b"modules.dep\x00" as *const u8 as *const libc::c_char);
+  i = 0 as libc::c_uint;
+  while i < 256i32 as libc::c_uint {
+    m = modules.buckets[i as usize];
+    while !m.is_null() {
+      let mut line: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+        b"%s:\x00" as *const u8 as *const libc::c_char,
+        (*m).name,
+      );
+      order_dep_list(&mut modules, m, (*m).deps);
+      while (*m).dnext != m {
+        dep = (*m).dnext;
+        let mut s: *mut libc::c_char = line;
+        line = crate::libbb::xfuncs_printf::xasprintf(
+          b"%s %s\x00" as *const u8 as *const libc::c_char,
+          s,
+          (*dep).name,
+        );
+        free(s as *mut libc::c_void);
+        /* unlink current entry */
+        (*(*dep).dnext).dprev = (*dep).dprev;
+        (*(*dep).dprev).dnext = (*dep).dnext;
+        (*dep).dprev = dep;
+        (*dep).dnext = (*dep).dprev
+      }
+      fprintf(fp, b"%s\n\x00" as *const u8 as *const libc::c_char, line);
+      if write_bin {
+        index_add(&mut idx, (*m).modname, line, 0 as libc::c_uint);
+      }
+      free(line as *mut libc::c_void);
+      /* Remember module order: it is the priority in the indexes */
+      (*m).dep_order = modidx;
+      modidx = modidx.wrapping_add(1);
+      m = (*m).next
+    }
+    i = i.wrapping_add(1)
+  }
+  close_output(fp);
+  if write_bin {
+    index_write(
+      &mut idx,
+      b"modules.dep.bin\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  fp = open_output(b"modules.alias\x00" as *const u8 as *const libc::c_char);
+  fprintf(
+    fp,
+    b"# Aliases extracted from modules themselves.\n\x00" as *const u8 as *const libc::c_char,
+  );
+  i = 0 as libc::c_uint;
+  while i < 256i32 as libc::c_uint {
+    m = modules.buckets[i as usize];
+    while !m.is_null() {
+      /*
+       * Last word used to be a basename
+       * (filename with path and .ko.* stripped)
+       * at the time of module-init-tools 3.4.
+       * kmod v.12 uses module name, i.e., s/-/_/g.
+       */
+      while !(*m).aliases.is_null() {
+        let mut alias: *mut libc::c_char =
+          crate::libbb::llist::llist_pop(&mut (*m).aliases) as *mut libc::c_char;
+        fprintf(
+          fp,
+          b"alias %s %s\n\x00" as *const u8 as *const libc::c_char,
+          alias,
+          (*m).modname,
+        );
+        if write_bin {
+          let mut key: *mut libc::c_char = alias_normalize(alias);
+          index_add(&mut idx, key, (*m).modname, (*m).dep_order);
+          free(key as *mut libc::c_void);
+        }
+        free(alias as *mut libc::c_void);
+      }
+      m = (*m).next
+    }
+    i = i.wrapping_add(1)
+  }
+  close_output(fp);
+  if write_bin {
+    index_write(
+      &mut idx,
+      b"modules.alias.bin\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  fp = open_output(b"modules.symbols\x00" as *const u8 as *const libc::c_char);
+  fprintf(
+    fp,
+    b"# Aliases for symbols, used by symbol_request().\n\x00" as *const u8 as *const libc::c_char,
+  );
+  i = 0 as libc::c_uint;
+  while i < 256i32 as libc::c_uint {
+    m = modules.buckets[i as usize];
+    while !m.is_null() {
+      while !(*m).symbols.is_null() {
+        let mut sym: *mut libc::c_char =
+          crate::libbb::llist::llist_pop(&mut (*m).symbols) as *mut libc::c_char;
+        fprintf(
+          fp,
+          b"alias symbol:%s %s\n\x00" as *const u8 as *const libc::c_char,
+          sym,
+          (*m).modname,
+        );
+        if write_bin {
+          let mut key_0: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+            b"symbol:%s\x00" as *const u8 as *const libc::c_char,
+            sym,
+          );
+          index_add(&mut idx, key_0, (*m).modname, (*m).dep_order);
+          free(key_0 as *mut libc::c_void);
+        }
+        free(sym as *mut libc::c_void);
+      }
+      m = (*m).next
+    }
+    i = i.wrapping_add(1)
+  }
+  close_output(fp);
+  if write_bin {
+    index_write(
+      &mut idx,
+      b"modules.symbols.bin\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  /* kmod reads modules.softdep as a config file, there is no .bin */
+  fp = open_output(b"modules.softdep\x00" as *const u8 as *const libc::c_char);
+  fprintf(
+    fp,
+    b"# Soft dependencies extracted from modules themselves.\n\x00" as *const u8
+      as *const libc::c_char,
+  );
+  i = 0 as libc::c_uint;
+  while i < 256i32 as libc::c_uint {
+    m = modules.buckets[i as usize];
+    while !m.is_null() {
+      while !(*m).softdeps.is_null() {
+        let mut sd: *mut libc::c_char =
+          crate::libbb::llist::llist_pop(&mut (*m).softdeps) as *mut libc::c_char;
+        fprintf(
+          fp,
+          b"softdep %s %s\n\x00" as *const u8 as *const libc::c_char,
+          (*m).modname,
+          sd,
+        );
+        free(sd as *mut libc::c_void);
+      }
+      m = (*m).next
+    }
+    i = i.wrapping_add(1)
+  }
+  close_output(fp);
+  /* modules.builtin comes from the kernel build, we only index it */
+  if write_bin {
+    let mut s: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut parser: *mut crate::libbb::parse_config::parser_t =
+      crate::libbb::parse_config::config_open2(
+        b"modules.builtin\x00" as *const u8 as *const libc::c_char,
+        Some(crate::libbb::wfopen::fopen_for_read),
+      );
+    if !parser.is_null() {
+      while crate::libbb::parse_config::config_read(
+        parser,
+        &mut s,
+        (crate::libbb::parse_config::PARSE_NORMAL as libc::c_int
+          | (1i32 & 0xffi32) << 8i32
+          | 1i32 & 0xffi32) as libc::c_uint,
+        b"# \t\x00" as *const u8 as *const libc::c_char,
+      ) != 0
+      {
+        let mut modname: [libc::c_char; 256] = [0; 256];
+        crate::modutils::modutils::filename2modname(
+          crate::libbb::get_last_path_component::bb_get_last_path_component_nostrip(s),
+          modname.as_mut_ptr(),
+        );
+        index_add(
+          &mut idx,
+          modname.as_mut_ptr(),
+          b"\x00" as *const u8 as *const libc::c_char,
+          0 as libc::c_uint,
+        );
+      }
+      crate::libbb::parse_config::config_close(parser);
+      index_write(
+        &mut idx,
+        b"modules.builtin.bin\x00" as *const u8 as *const libc::c_char,
+      );
+    }
+  }
+  crate::modutils::modutils::moddb_free(&mut modules);
+  return 0;
+}
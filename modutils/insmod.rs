@@ -0,0 +1,35 @@
+use libc;
+/*
+ * Mini insmod implementation for busybox
+ *
+ * Copyright (C) 2008 Timo Teras <timo.teras@iki.fi>
+ *
+ * Licensed under GPLv2 or later, see file LICENSE in this source tree.
+ */
+pub unsafe fn insmod_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  let mut filename: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut rc: libc::c_int = 0;
+  /* Compat note:
+   * 2.6 style insmod has no options and required filename
+   * (not module name - .ko can't be omitted).
+   * Compressed .ko.gz/.ko.xz/.ko.zst are handed to the kernel as is,
+   * or unpacked here if the kernel can't do it.
+   */
+  argv = argv.offset(1);
+  filename = *argv;
+  if filename.is_null() {
+    crate::libbb::appletlib::bb_show_usage();
+  }
+  rc = crate::modutils::modutils::bb_init_module(
+    filename,
+    crate::modutils::modutils::parse_cmdline_module_options(argv, 0),
+  );
+  if rc != 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"can\'t insert \'%s\': %s\x00" as *const u8 as *const libc::c_char,
+      filename,
+      crate::modutils::modutils::moderror(rc),
+    );
+  }
+  return rc;
+}
@@ -0,0 +1,1134 @@
+use crate::libbb::llist::llist_t;
+use crate::libbb::parse_config::parser_t;
+use crate::libbb::parse_config::PARSE_GREEDY;
+use crate::libbb::parse_config::PARSE_NORMAL;
+use crate::librb::size_t;
+use crate::librb::smallint;
+use crate::modutils::modutils::module_db;
+use crate::modutils::modutils::module_entry;
+use libc;
+use libc::free;
+use libc::printf;
+use libc::puts;
+use libc::sprintf;
+use libc::stat;
+use libc::strchr;
+use libc::strcmp;
+use libc::strrchr;
+extern "C" {
+  #[no_mangle]
+  static mut optind: libc::c_int;
+  #[no_mangle]
+  static mut option_mask32: u32;
+  #[no_mangle]
+  static mut logmode: smallint;
+  /* '*const' ptr makes gcc optimize code much better.
+   * Magic prevents ptr_to_globals from going into rodata.
+   * If you want to assign a value, use SET_PTR_TO_GLOBALS(x) */
+  #[no_mangle]
+  static ptr_to_globals: *mut globals;
+  #[no_mangle]
+  fn strlen(__s: *const libc::c_char) -> size_t;
+  #[no_mangle]
+  fn strnlen(__string: *const libc::c_char, __maxlen: size_t) -> size_t;
+  #[no_mangle]
+  fn strchrnul(__s: *const libc::c_char, __c: libc::c_int) -> *mut libc::c_char;
+  #[no_mangle]
+  fn uname(__name: *mut utsname) -> libc::c_int;
+  #[no_mangle]
+  fn fnmatch(
+    __pattern: *const libc::c_char,
+    __name: *const libc::c_char,
+    __flags: libc::c_int,
+  ) -> libc::c_int;
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct utsname {
+  pub sysname: [libc::c_char; 65],
+  pub nodename: [libc::c_char; 65],
+  pub release: [libc::c_char; 65],
+  pub version: [libc::c_char; 65],
+  pub machine: [libc::c_char; 65],
+  pub domainname: [libc::c_char; 65],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct globals {
+  pub db: module_db,
+  pub probes: *mut llist_t,
+  pub cmdline_mopts: *mut libc::c_char,
+  pub num_unresolved_deps: libc::c_int,
+  pub need_symbols: smallint,
+  pub have_softdeps: smallint,
+  pub moddir: *mut libc::c_char,
+}
+
+pub const LOGMODE_SYSLOG: C2RustUnnamed = 2;
+pub type C2RustUnnamed = libc::c_uint;
+
+pub const ACTION_QUIET: C2RustUnnamed_0 = 32;
+pub const ACTION_RECURSE: C2RustUnnamed_0 = 1;
+pub type C2RustUnnamed_0 = libc::c_uint;
+
+pub const OPT_DIRNAME: C2RustUnnamed_1 = 1024;
+pub const OPT_SET_VERSION: C2RustUnnamed_1 = 512;
+pub const OPT_DRY_RUN: C2RustUnnamed_1 = 256;
+pub const OPT_BLACKLIST: C2RustUnnamed_1 = 128;
+pub const OPT_SHOW_DEPS: C2RustUnnamed_1 = 64;
+pub const OPT_REMOVE: C2RustUnnamed_1 = 32;
+pub const OPT_LIST_ONLY: C2RustUnnamed_1 = 16;
+pub const OPT_INSERT_ALL: C2RustUnnamed_1 = 8;
+pub const INSMOD_OPT_SYSLOG: C2RustUnnamed_1 = 4;
+pub const INSMOD_OPT_SILENT: C2RustUnnamed_1 = 2;
+pub const INSMOD_OPT_VERBOSE: C2RustUnnamed_1 = 1;
+pub type C2RustUnnamed_1 = libc::c_uint;
+
+pub const MODULE_FLAG_SOFTDEP_BUSY: C2RustUnnamed_2 = 64;
+pub const MODULE_FLAG_SOFTDEPS_SEEN: C2RustUnnamed_2 = 32;
+pub const MODULE_FLAG_BUILTIN: C2RustUnnamed_2 = 16;
+pub const MODULE_FLAG_BLACKLISTED: C2RustUnnamed_2 = 8;
+pub const MODULE_FLAG_FOUND_IN_MODDEP: C2RustUnnamed_2 = 4;
+pub const MODULE_FLAG_NEED_DEPS: C2RustUnnamed_2 = 2;
+pub const MODULE_FLAG_LOADED: C2RustUnnamed_2 = 1;
+pub type C2RustUnnamed_2 = libc::c_uint;
+
+/* A kmod index (modules.*.bin) read into memory, see depmod.rs */
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct index_file {
+  pub buf: *mut u8,
+  pub len: size_t,
+}
+
+#[inline(always)]
+unsafe extern "C" fn not_const_pp(mut p: *const libc::c_void) -> *mut libc::c_void {
+  return p as *mut libc::c_void;
+}
+
+static mut modprobe_longopts: [u8; 115] = *b"all\x00\x00aremove\x00\x00rlist\x00\x00lshow-depends\x00\x00Duse-blacklist\x00\x00bquiet\x00\x00qverbose\x00\x00vsyslog\x00\x00sdry-run\x00\x00nset-version\x00\x01Sdirname\x00\x01d\x00";
+
+/*
+ * Modprobe written from scratch for BusyBox
+ *
+ * Copyright (c) 2008 Timo Teras <timo.teras@iki.fi>
+ * Copyright (c) 2008 Vladimir Dronnikov
+ *
+ * Licensed under GPLv2 or later, see file LICENSE in this source tree.
+ */
+unsafe extern "C" fn gather_options_str(
+  mut opts: *mut libc::c_char,
+  mut append: *const libc::c_char,
+) -> *mut libc::c_char {
+  /* Speed-optimized. We call gather_options_str many times. */
+  if !append.is_null() {
+    if opts.is_null() {
+      opts = crate::libbb::xfuncs_printf::xstrdup(append)
+    } else {
+      let mut optlen: libc::c_int = strlen(opts) as libc::c_int;
+      opts = crate::libbb::xfuncs_printf::xrealloc(
+        opts as *mut libc::c_void,
+        (optlen as libc::c_ulong)
+          .wrapping_add(strlen(append))
+          .wrapping_add(2i32 as libc::c_ulong),
+      ) as *mut libc::c_char;
+      sprintf(
+        opts.offset(optlen as isize),
+        b" %s\x00" as *const u8 as *const libc::c_char,
+        append,
+      );
+    }
+  }
+  return opts;
+}
+unsafe extern "C" fn get_or_add_modentry(mut module: *const libc::c_char) -> *mut module_entry {
+  return crate::modutils::modutils::moddb_get_or_create(&mut (*ptr_to_globals).db, module);
+}
+unsafe extern "C" fn add_probe(mut name: *const libc::c_char) {
+  let mut m: *mut module_entry = std::ptr::null_mut();
+  m = get_or_add_modentry(name);
+  if option_mask32 & (OPT_REMOVE as libc::c_int | OPT_SHOW_DEPS as libc::c_int) as libc::c_uint == 0
+    && (*m).flags
+      & (MODULE_FLAG_LOADED as libc::c_int | MODULE_FLAG_BUILTIN as libc::c_int) as libc::c_uint
+      != 0
+  {
+    return;
+  }
+  (*m).probed_name = name;
+  (*m).flags |= MODULE_FLAG_NEED_DEPS as libc::c_int as libc::c_uint;
+  crate::libbb::llist::llist_add_to_end(&mut (*ptr_to_globals).probes, m as *mut libc::c_void);
+  (*ptr_to_globals).num_unresolved_deps += 1;
+  if !crate::libbb::compare_string_array::is_prefixed_with(
+    (*m).modname,
+    b"symbol:\x00" as *const u8 as *const libc::c_char,
+  )
+  .is_null()
+  {
+    (*ptr_to_globals).need_symbols = 1i32 as smallint
+  }
+}
+/* Probing m means loading rmod instead (alias or symbol) */
+unsafe fn add_realname(mut m: *mut module_entry, mut rmod: *mut libc::c_char) {
+  crate::libbb::llist::llist_add_to(&mut (*m).realnames, rmod as *mut libc::c_void);
+  if (*m).flags & MODULE_FLAG_NEED_DEPS as libc::c_int as libc::c_uint != 0 {
+    (*m).flags &= !(MODULE_FLAG_NEED_DEPS as libc::c_int) as libc::c_uint;
+    (*ptr_to_globals).num_unresolved_deps -= 1
+  }
+  m = get_or_add_modentry(rmod);
+  if (*m).flags & MODULE_FLAG_NEED_DEPS as libc::c_int as libc::c_uint == 0 {
+    (*m).flags |= MODULE_FLAG_NEED_DEPS as libc::c_int as libc::c_uint;
+    (*ptr_to_globals).num_unresolved_deps += 1
+  }
+}
+unsafe fn config_file_action(
+  mut filename: *const libc::c_char,
+  mut _statbuf: *mut stat,
+  mut _userdata: *mut libc::c_void,
+  mut depth: libc::c_int,
+) -> libc::c_int {
+  let mut tokens: [*mut libc::c_char; 3] = [std::ptr::null_mut::<libc::c_char>(); 3];
+  let mut p: *mut parser_t = std::ptr::null_mut();
+  let mut m: *mut module_entry = std::ptr::null_mut();
+  let mut base: *const libc::c_char = std::ptr::null();
+  let mut ext: *const libc::c_char = std::ptr::null();
+  /* Skip files that begin with a "." */
+  base = crate::libbb::get_last_path_component::bb_basename(filename);
+  if *base.offset(0) as libc::c_int == '.' as i32 {
+    return 1i32;
+  }
+  /* In dir recursion, skip files that do not end with a ".conf"
+   * depth==0: read_config("modules.{symbols,alias}") must work,
+   * "include FILE_NOT_ENDING_IN_CONF" must work too.
+   */
+  if depth != 0 {
+    ext = strrchr(base, '.' as i32);
+    if ext.is_null()
+      || strcmp(
+        ext.offset(1),
+        b"conf\x00" as *const u8 as *const libc::c_char,
+      ) != 0
+    {
+      return 1i32;
+    }
+  }
+  p =
+    crate::libbb::parse_config::config_open2(filename, Some(crate::libbb::wfopen::fopen_for_read));
+  if p.is_null() {
+    return 0;
+  }
+  while crate::libbb::parse_config::config_read(
+    p,
+    tokens.as_mut_ptr(),
+    (PARSE_NORMAL as libc::c_int | (2i32 & 0xffi32) << 8i32 | 3i32 & 0xffi32) as libc::c_uint,
+    b"# \t\x00" as *const u8 as *const libc::c_char,
+  ) != 0
+  {
+    if strcmp(tokens[0], b"alias\x00" as *const u8 as *const libc::c_char) == 0 {
+      /* alias <wildcard> <modulename> */
+      let mut l: *mut llist_t = std::ptr::null_mut();
+      let mut wildcard: [libc::c_char; 256] = [0; 256];
+      let mut rmod: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+      if tokens[2].is_null() {
+        continue;
+      }
+      crate::modutils::modutils::filename2modname(tokens[1], wildcard.as_mut_ptr());
+      l = (*ptr_to_globals).probes;
+      while !l.is_null() {
+        m = (*l).data as *mut module_entry;
+        if fnmatch(wildcard.as_mut_ptr(), (*m).modname, 0) == 0 {
+          rmod = crate::modutils::modutils::filename2modname(
+            tokens[2],
+            std::ptr::null_mut::<libc::c_char>(),
+          );
+          add_realname(m, rmod);
+        }
+        l = (*l).link
+      }
+    } else if strcmp(
+      tokens[0],
+      b"options\x00" as *const u8 as *const libc::c_char,
+    ) == 0
+    {
+      /* options <modulename> <option...> */
+      if tokens[2].is_null() {
+        continue;
+      }
+      m = get_or_add_modentry(tokens[1]);
+      (*m).options = gather_options_str((*m).options, tokens[2])
+    } else if strcmp(
+      tokens[0],
+      b"softdep\x00" as *const u8 as *const libc::c_char,
+    ) == 0
+    {
+      /* softdep <modulename> [pre: <mod>...] [post: <mod>...] */
+      if tokens[2].is_null() {
+        continue;
+      }
+      m = get_or_add_modentry(tokens[1]);
+      crate::libbb::llist::llist_add_to_end(
+        &mut (*m).softdeps,
+        crate::libbb::xfuncs_printf::xstrdup(tokens[2]) as *mut libc::c_void,
+      );
+      (*ptr_to_globals).have_softdeps = 1i32 as smallint
+    } else if strcmp(
+      tokens[0],
+      b"include\x00" as *const u8 as *const libc::c_char,
+    ) == 0
+    {
+      /* include <filename>/<dirname> (yes, directories also must work) */
+      read_config(tokens[1]);
+    } else if strcmp(
+      tokens[0],
+      b"blacklist\x00" as *const u8 as *const libc::c_char,
+    ) == 0
+    {
+      /* blacklist <modulename> */
+      (*get_or_add_modentry(tokens[1])).flags |=
+        MODULE_FLAG_BLACKLISTED as libc::c_int as libc::c_uint
+    }
+  }
+  crate::libbb::parse_config::config_close(p);
+  return 1i32;
+}
+unsafe extern "C" fn read_config(mut path: *const libc::c_char) -> libc::c_int {
+  return crate::libbb::recursive_action::recursive_action(
+    path,
+    (ACTION_RECURSE as libc::c_int | ACTION_QUIET as libc::c_int) as libc::c_uint,
+    Some(config_file_action),
+    None,
+    std::ptr::null_mut(),
+    0 as libc::c_uint,
+  );
+}
+unsafe extern "C" fn humanly_readable_name(mut m: *mut module_entry) -> *const libc::c_char {
+  /* probed_name may be NULL. modname always exists. */
+  return if !(*m).probed_name.is_null() {
+    (*m).probed_name
+  } else {
+    (*m).modname
+  };
+}
+/* Like strsep(&stringp, "\n\t ") but quoted text goes to single token
+ * even if it contains whitespace.
+ */
+unsafe extern "C" fn strsep_quotes(mut stringp: *mut *mut libc::c_char) -> *mut libc::c_char {
+  let mut s: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut start: *mut libc::c_char = *stringp;
+  if start.is_null() {
+    return std::ptr::null_mut::<libc::c_char>();
+  }
+  s = start;
+  loop {
+    let mut c: libc::c_int = *s as libc::c_int;
+    if c == '\"' as i32 {
+      s = strchrnul(s.offset(1), '\"' as i32); /* find trailing quote */
+      if *s as libc::c_int != '\u{0}' as i32 {
+        s = s.offset(1)
+        /* skip trailing quote */
+      }
+      c = *s as libc::c_int
+      /* fall through */
+    }
+    if c == '\u{0}' as i32 || c == '\n' as i32 || c == '\t' as i32 || c == ' ' as i32 {
+      if *s as libc::c_int != '\u{0}' as i32 {
+        *s = '\u{0}' as i32 as libc::c_char;
+        *stringp = s.offset(1)
+      } else {
+        *stringp = std::ptr::null_mut::<libc::c_char>()
+      }
+      return start;
+    }
+    s = s.offset(1)
+  }
+}
+unsafe extern "C" fn parse_and_add_kcmdline_module_options(
+  mut options: *mut libc::c_char,
+  mut modulename: *const libc::c_char,
+) -> *mut libc::c_char {
+  let mut kcmdline_buf: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut kcmdline: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut kptr: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  kcmdline_buf = crate::libbb::read_printf::xmalloc_open_read_close(
+    b"/proc/cmdline\x00" as *const u8 as *const libc::c_char,
+    std::ptr::null_mut(),
+  ) as *mut libc::c_char;
+  if kcmdline_buf.is_null() {
+    return options;
+  }
+  kcmdline = kcmdline_buf;
+  loop {
+    kptr = strsep_quotes(&mut kcmdline);
+    if kptr.is_null() {
+      break;
+    }
+    let mut after_modulename: *mut libc::c_char =
+      crate::libbb::compare_string_array::is_prefixed_with(kptr, modulename);
+    if after_modulename.is_null() || *after_modulename as libc::c_int != '.' as i32 {
+      continue;
+    }
+    /* It is "modulename.xxxx" */
+    kptr = after_modulename.offset(1);
+    if !strchr(kptr, '=' as i32).is_null() {
+      /* It is "modulename.opt=[val]" */
+      options = gather_options_str(options, kptr)
+    }
+  }
+  free(kcmdline_buf as *mut libc::c_void);
+  return options;
+}
+/* Return: similar to bb_init_module:
+ * 0 on success,
+ * -errno on open/read error,
+ * errno on init_module() error
+ */
+/* NB: INSMOD_OPT_SILENT bit suppresses ONLY non-existent modules,
+ * not deleted ones (those are still listed in modules.dep).
+ * module-init-tools version 3.4:
+ * # modprobe bogus
+ * FATAL: Module bogus not found. [exitcode 1]
+ * # modprobe -q bogus            [silent, exitcode still 1]
+ * but:
+ * # rm kernel/drivers/net/dummy.ko
+ * # modprobe -q dummy
+ * FATAL: Could not open '/lib/modules/xxx/kernel/drivers/net/dummy.ko': No such file or directory
+ * [exitcode 1]
+ */
+unsafe extern "C" fn do_modprobe(mut m: *mut module_entry) -> libc::c_int {
+  let mut rc: libc::c_int = 0;
+  let mut first: libc::c_int = 0;
+  if (*m).flags & MODULE_FLAG_FOUND_IN_MODDEP as libc::c_int as libc::c_uint == 0 {
+    if option_mask32 & INSMOD_OPT_SILENT as libc::c_int as libc::c_uint == 0 {
+      crate::libbb::verror_msg::bb_error_msg(
+        if (*m).flags & MODULE_FLAG_BUILTIN as libc::c_int as libc::c_uint != 0 {
+          b"module %s is builtin\x00" as *const u8 as *const libc::c_char
+        } else {
+          b"module %s not found in modules.dep\x00" as *const u8 as *const libc::c_char
+        },
+        humanly_readable_name(m),
+      );
+    }
+    return -2i32;
+  }
+  if option_mask32 & OPT_REMOVE as libc::c_int as libc::c_uint == 0 {
+    (*m).deps = crate::libbb::llist::llist_rev((*m).deps)
+  }
+  first = 1i32;
+  rc = 0;
+  while !(*m).deps.is_null() {
+    let mut m2: *mut module_entry = std::ptr::null_mut();
+    let mut fn_0: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut options: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    rc = 0;
+    fn_0 = crate::libbb::llist::llist_pop(&mut (*m).deps) as *mut libc::c_char; /* we leak it */
+    m2 = get_or_add_modentry(
+      crate::libbb::get_last_path_component::bb_get_last_path_component_nostrip(fn_0),
+    );
+    if option_mask32 & OPT_REMOVE as libc::c_int as libc::c_uint != 0 {
+      /* modprobe -r */
+      if (*m2).flags & MODULE_FLAG_LOADED as libc::c_int as libc::c_uint != 0 {
+        if option_mask32
+          & (OPT_DRY_RUN as libc::c_int | INSMOD_OPT_VERBOSE as libc::c_int) as libc::c_uint
+          != 0
+        {
+          if option_mask32 & INSMOD_OPT_VERBOSE as libc::c_int as libc::c_uint != 0 {
+            printf(
+              b"rmmod %s\n\x00" as *const u8 as *const libc::c_char,
+              (*m2).modname,
+            );
+          }
+          if option_mask32 & OPT_DRY_RUN as libc::c_int as libc::c_uint != 0 {
+            first = 0;
+            continue;
+          }
+        }
+        rc = crate::modutils::modutils::bb_delete_module((*m2).modname, 0o200 as libc::c_uint);
+        if rc != 0 {
+          if first != 0 {
+            crate::libbb::perror_msg::bb_perror_msg(
+              b"can\'t unload module \'%s\'\x00" as *const u8 as *const libc::c_char,
+              humanly_readable_name(m2),
+            );
+            break;
+          }
+        } else {
+          (*m2).flags &= !(MODULE_FLAG_LOADED as libc::c_int) as libc::c_uint
+        }
+      }
+      /* do not error out if *deps* fail to unload */
+      first = 0;
+      continue;
+    }
+    options = (*m2).options;
+    (*m2).options = std::ptr::null_mut::<libc::c_char>();
+    options = parse_and_add_kcmdline_module_options(options, (*m2).modname);
+    if m == m2 {
+      options = gather_options_str(options, (*ptr_to_globals).cmdline_mopts)
+    }
+    if option_mask32 & OPT_SHOW_DEPS as libc::c_int as libc::c_uint != 0 {
+      printf(
+        if !options.is_null() {
+          b"insmod %s/%s %s\n\x00" as *const u8 as *const libc::c_char
+        } else {
+          b"insmod %s/%s\n\x00" as *const u8 as *const libc::c_char
+        },
+        (*ptr_to_globals).moddir,
+        fn_0,
+        options,
+      );
+      free(options as *mut libc::c_void);
+      continue;
+    }
+    if (*m2).flags & MODULE_FLAG_LOADED as libc::c_int as libc::c_uint != 0 {
+      free(options as *mut libc::c_void);
+      continue;
+    }
+    if option_mask32 & INSMOD_OPT_VERBOSE as libc::c_int as libc::c_uint != 0 {
+      printf(
+        b"insmod %s/%s %s\n\x00" as *const u8 as *const libc::c_char,
+        (*ptr_to_globals).moddir,
+        fn_0,
+        if !options.is_null() {
+          options as *const libc::c_char
+        } else {
+          b"\x00" as *const u8 as *const libc::c_char
+        },
+      );
+    }
+    if option_mask32 & OPT_DRY_RUN as libc::c_int as libc::c_uint != 0 {
+      free(options as *mut libc::c_void);
+      continue;
+    }
+    /* .ko.xz and friends are unpacked by bb_init_module */
+    rc = crate::modutils::modutils::bb_init_module(fn_0, options);
+    if rc == 17i32 {
+      /* EEXIST */
+      rc = 0
+    }
+    free(options as *mut libc::c_void);
+    if rc != 0 {
+      crate::libbb::verror_msg::bb_error_msg(
+        b"can\'t load module %s (%s): %s\x00" as *const u8 as *const libc::c_char,
+        humanly_readable_name(m2),
+        fn_0,
+        crate::modutils::modutils::moderror(rc),
+      );
+      break;
+    } else {
+      (*m2).flags |= MODULE_FLAG_LOADED as libc::c_int as libc::c_uint
+    }
+  }
+  return rc;
+}
+/* Collects module names from the "pre:" (post == 0) or "post:" part
+ * of m's softdep lines */
+unsafe extern "C" fn softdep_names(
+  mut m: *mut module_entry,
+  mut post: libc::c_int,
+) -> *mut llist_t {
+  let mut names: *mut llist_t = std::ptr::null_mut();
+  let mut l: *mut llist_t = (*m).softdeps;
+  while !l.is_null() {
+    let mut in_post: libc::c_int = -1i32;
+    let mut words: *mut llist_t = std::ptr::null_mut();
+    let mut copy: *mut libc::c_char = crate::libbb::xfuncs_printf::xstrdup((*l).data);
+    crate::modutils::modutils::string_to_llist(
+      copy,
+      &mut words,
+      b" \t\x00" as *const u8 as *const libc::c_char,
+    );
+    free(copy as *mut libc::c_void);
+    while !words.is_null() {
+      let mut w: *mut libc::c_char =
+        crate::libbb::llist::llist_pop(&mut words) as *mut libc::c_char;
+      if strcmp(w, b"pre:\x00" as *const u8 as *const libc::c_char) == 0 {
+        in_post = 0
+      } else if strcmp(w, b"post:\x00" as *const u8 as *const libc::c_char) == 0 {
+        in_post = 1i32
+      } else if in_post == post {
+        crate::libbb::llist::llist_add_to_end(&mut names, w as *mut libc::c_void);
+        continue;
+      }
+      free(w as *mut libc::c_void);
+    }
+    l = (*l).link
+  }
+  return names;
+}
+/* Marks softdeps of m (recursively) so that load_modules_dep resolves them */
+unsafe extern "C" fn mark_softdeps(mut m: *mut module_entry) {
+  let mut post: libc::c_int = 0;
+  if (*m).flags & MODULE_FLAG_SOFTDEPS_SEEN as libc::c_int as libc::c_uint != 0 {
+    return;
+  }
+  (*m).flags |= MODULE_FLAG_SOFTDEPS_SEEN as libc::c_int as libc::c_uint;
+  while post < 2i32 {
+    let mut names: *mut llist_t = softdep_names(m, post);
+    while !names.is_null() {
+      let mut name: *mut libc::c_char =
+        crate::libbb::llist::llist_pop(&mut names) as *mut libc::c_char;
+      let mut m2: *mut module_entry = get_or_add_modentry(name);
+      if (*m2).flags
+        & (MODULE_FLAG_NEED_DEPS as libc::c_int | MODULE_FLAG_FOUND_IN_MODDEP as libc::c_int)
+          as libc::c_uint
+        == 0
+      {
+        (*m2).flags |= MODULE_FLAG_NEED_DEPS as libc::c_int as libc::c_uint;
+        (*ptr_to_globals).num_unresolved_deps += 1
+      }
+      mark_softdeps(m2);
+      free(name as *mut libc::c_void);
+    }
+    post += 1
+  }
+}
+unsafe extern "C" fn do_softdeps(mut m: *mut module_entry, mut post: libc::c_int) {
+  let mut names: *mut llist_t = softdep_names(m, post);
+  while !names.is_null() {
+    let mut name: *mut libc::c_char =
+      crate::libbb::llist::llist_pop(&mut names) as *mut libc::c_char;
+    let mut m2: *mut module_entry = get_or_add_modentry(name);
+    /* Soft dependencies are optional: missing ones are not an error */
+    if (*m2).flags & MODULE_FLAG_FOUND_IN_MODDEP as libc::c_int as libc::c_uint != 0
+      && ((*m2).flags & MODULE_FLAG_LOADED as libc::c_int as libc::c_uint == 0
+        || option_mask32
+          & (OPT_REMOVE as libc::c_int | OPT_SHOW_DEPS as libc::c_int) as libc::c_uint
+          != 0)
+    {
+      modprobe_with_softdeps(m2);
+    }
+    free(name as *mut libc::c_void);
+  }
+}
+/* "pre:" softdeps go in before the module and out after it,
+ * "post:" ones go in after it and out before it */
+unsafe extern "C" fn modprobe_with_softdeps(mut m: *mut module_entry) -> libc::c_int {
+  let mut rc: libc::c_int = 0;
+  let mut remove: libc::c_int =
+    (option_mask32 & OPT_REMOVE as libc::c_int as libc::c_uint != 0) as libc::c_int;
+  if (*m).flags & MODULE_FLAG_SOFTDEP_BUSY as libc::c_int as libc::c_uint != 0 {
+    /* softdep loop */
+    return 0;
+  }
+  (*m).flags |= MODULE_FLAG_SOFTDEP_BUSY as libc::c_int as libc::c_uint;
+  if remove == 0 {
+    do_softdeps(m, 0);
+  } else {
+    do_softdeps(m, 1i32);
+  }
+  rc = do_modprobe(m);
+  if rc == 0 {
+    if remove == 0 {
+      do_softdeps(m, 1i32);
+    } else {
+      do_softdeps(m, 0);
+    }
+  }
+  (*m).flags &= !(MODULE_FLAG_SOFTDEP_BUSY as libc::c_int) as libc::c_uint;
+  return rc;
+}
+/* m is listed in modules.dep as "path: deps" (deps may be NULL) */
+unsafe fn add_moddep(
+  mut m: *mut module_entry,
+  mut path: *const libc::c_char,
+  mut deps: *mut libc::c_char,
+) {
+  /* Optimization... */
+  if (*m).flags & MODULE_FLAG_LOADED as libc::c_int as libc::c_uint != 0
+    && option_mask32 & (OPT_REMOVE as libc::c_int | OPT_SHOW_DEPS as libc::c_int) as libc::c_uint
+      == 0
+  {
+    return;
+  }
+  (*m).flags |= MODULE_FLAG_FOUND_IN_MODDEP as libc::c_int as libc::c_uint;
+  if (*m).flags & MODULE_FLAG_NEED_DEPS as libc::c_int as libc::c_uint != 0 && (*m).deps.is_null() {
+    (*ptr_to_globals).num_unresolved_deps -= 1;
+    crate::libbb::llist::llist_add_to(
+      &mut (*m).deps,
+      crate::libbb::xfuncs_printf::xstrdup(path) as *mut libc::c_void,
+    );
+    if !deps.is_null() {
+      crate::modutils::modutils::string_to_llist(
+        deps,
+        &mut (*m).deps,
+        b" \t\x00" as *const u8 as *const libc::c_char,
+      );
+    }
+  }
+}
+/* Returns 0 if name is missing or is not a kmod index */
+unsafe fn index_open(mut idx: *mut index_file, mut name: *const libc::c_char) -> libc::c_int {
+  (*idx).len = (2147483647i32 - 4095i32) as size_t;
+  (*idx).buf = crate::libbb::read_printf::xmalloc_open_read_close(name, &mut (*idx).len) as *mut u8;
+  if (*idx).buf.is_null() {
+    return 0;
+  }
+  if (*idx).len < 12
+    || index_get_u32(idx, 0) != crate::modutils::depmod::INDEX_MAGIC
+    || index_get_u32(idx, 4) >> 16 != crate::modutils::depmod::INDEX_VERSION >> 16
+  {
+    free((*idx).buf as *mut libc::c_void);
+    (*idx).buf = std::ptr::null_mut();
+    return 0;
+  }
+  return 1;
+}
+/* Big-endian; reads past the end give 0 ("no child", "no values") */
+unsafe fn index_get_u32(mut idx: *const index_file, mut off: size_t) -> u32 {
+  let mut p: *const u8 = std::ptr::null();
+  if off.wrapping_add(4) > (*idx).len {
+    return 0;
+  }
+  p = (*idx).buf.offset(off as isize);
+  return u32::from_be_bytes([*p, *p.offset(1), *p.offset(2), *p.offset(3)]);
+}
+/* Walks the trie below node, key[0..len) holding the key so far.
+ * Keys of modules.alias are wildcards, so only the part before the
+ * first wildcard char prunes the walk, fnmatch() decides the rest. */
+unsafe fn index_search_node(
+  mut idx: *const index_file,
+  mut node: u32,
+  mut key: *mut libc::c_char,
+  mut len: libc::c_int,
+  mut name: *const libc::c_char,
+  mut found: *mut *mut llist_t,
+) {
+  let mut pos: size_t = (node & 0xfffffff) as size_t;
+  let mut first: libc::c_int = 0;
+  let mut last: libc::c_int = 0;
+  let mut childs: size_t = 0;
+  let mut i: libc::c_int = 0;
+  if pos == 0 || pos >= (*idx).len {
+    return;
+  }
+  if node & crate::modutils::depmod::INDEX_NODE_PREFIX != 0 {
+    while pos < (*idx).len && *(*idx).buf.offset(pos as isize) as libc::c_int != 0 {
+      if len >= 1023 {
+        return;
+      }
+      *key.offset(len as isize) = *(*idx).buf.offset(pos as isize) as libc::c_char;
+      len += 1;
+      pos = pos.wrapping_add(1)
+    }
+    pos = pos.wrapping_add(1)
+  }
+  *key.offset(len as isize) = '\u{0}' as i32 as libc::c_char;
+  i = 0;
+  while i < len
+    && strchr(
+      b"*?[\x00" as *const u8 as *const libc::c_char,
+      *key.offset(i as isize) as libc::c_int,
+    )
+    .is_null()
+  {
+    if *key.offset(i as isize) as libc::c_int != *name.offset(i as isize) as libc::c_int {
+      return;
+    }
+    i += 1
+  }
+  if node & crate::modutils::depmod::INDEX_NODE_CHILDS != 0 {
+    if pos.wrapping_add(2) > (*idx).len {
+      return;
+    }
+    first = *(*idx).buf.offset(pos as isize) as libc::c_int;
+    last = *(*idx).buf.offset(pos as isize + 1) as libc::c_int;
+    childs = pos.wrapping_add(2);
+    pos = childs.wrapping_add((4 * (last - first + 1)) as size_t)
+  }
+  if node & crate::modutils::depmod::INDEX_NODE_VALUES != 0 && fnmatch(key, name, 0) == 0 {
+    let mut count: u32 = index_get_u32(idx, pos);
+    pos = pos.wrapping_add(4);
+    while count != 0 && pos.wrapping_add(4) < (*idx).len {
+      let mut value: *const libc::c_char = std::ptr::null();
+      let mut vlen: size_t = 0;
+      /* skip the priority */
+      pos = pos.wrapping_add(4);
+      value = (*idx).buf.offset(pos as isize) as *const libc::c_char;
+      vlen = strnlen(value, (*idx).len.wrapping_sub(pos));
+      if pos.wrapping_add(vlen) >= (*idx).len {
+        break;
+      }
+      crate::libbb::llist::llist_add_to_end(
+        found,
+        crate::libbb::xfuncs_printf::xstrdup(value) as *mut libc::c_void,
+      );
+      pos = pos.wrapping_add(vlen).wrapping_add(1);
+      count = count.wrapping_sub(1)
+    }
+  }
+  if childs != 0 {
+    i = first;
+    while i <= last {
+      let mut child: u32 = index_get_u32(idx, childs.wrapping_add((4 * (i - first)) as size_t));
+      if child != 0 {
+        *key.offset(len as isize) = i as libc::c_char;
+        index_search_node(idx, child, key, len + 1, name, found);
+      }
+      i += 1
+    }
+  }
+}
+/* Returns the values of all keys matching name, xstrdup'ed */
+unsafe fn index_search(mut idx: *const index_file, mut name: *const libc::c_char) -> *mut llist_t {
+  let mut found: *mut llist_t = std::ptr::null_mut();
+  let mut key: [libc::c_char; 1024] = [0; 1024];
+  index_search_node(idx, index_get_u32(idx, 8), key.as_mut_ptr(), 0, name, &mut found);
+  return found;
+}
+/* modules.{alias,symbols}.bin, returns 0 if there is no such index */
+unsafe fn read_alias_index(mut name: *const libc::c_char) -> libc::c_int {
+  let mut idx: index_file = index_file {
+    buf: std::ptr::null_mut(),
+    len: 0,
+  };
+  let mut l: *mut llist_t = std::ptr::null_mut();
+  if index_open(&mut idx, name) == 0 {
+    return 0;
+  }
+  l = (*ptr_to_globals).probes;
+  while !l.is_null() {
+    let mut m: *mut module_entry = (*l).data as *mut module_entry;
+    let mut found: *mut llist_t = index_search(&mut idx, (*m).modname);
+    while !found.is_null() {
+      let mut value: *mut libc::c_char =
+        crate::libbb::llist::llist_pop(&mut found) as *mut libc::c_char;
+      add_realname(
+        m,
+        crate::modutils::modutils::filename2modname(value, std::ptr::null_mut::<libc::c_char>()),
+      );
+      free(value as *mut libc::c_void);
+    }
+    l = (*l).link
+  }
+  free(idx.buf as *mut libc::c_void);
+  return 1;
+}
+/* modules.dep.bin: key is the module name, value the modules.dep line.
+ * Returns 0 if there is no such index */
+unsafe fn load_modules_dep_bin() -> libc::c_int {
+  let mut idx: index_file = index_file {
+    buf: std::ptr::null_mut(),
+    len: 0,
+  };
+  let mut i: libc::c_int = 0;
+  if index_open(
+    &mut idx,
+    b"modules.dep.bin\x00" as *const u8 as *const libc::c_char,
+  ) == 0
+  {
+    return 0;
+  }
+  while i < 256 && (*ptr_to_globals).num_unresolved_deps != 0 {
+    let mut m: *mut module_entry = (*ptr_to_globals).db.buckets[i as usize];
+    while !m.is_null() {
+      if (*m).flags & MODULE_FLAG_NEED_DEPS as libc::c_int as libc::c_uint != 0
+        && (*m).deps.is_null()
+      {
+        let mut found: *mut llist_t = index_search(&mut idx, (*m).modname);
+        while !found.is_null() {
+          let mut line: *mut libc::c_char =
+            crate::libbb::llist::llist_pop(&mut found) as *mut libc::c_char;
+          let mut colon: *mut libc::c_char = strchr(line, ':' as i32);
+          if !colon.is_null() && (*m).deps.is_null() {
+            *colon = '\u{0}' as i32 as libc::c_char;
+            add_moddep(m, line, colon.offset(1));
+          }
+          free(line as *mut libc::c_void);
+        }
+      }
+      m = (*m).next
+    }
+    i += 1
+  }
+  free(idx.buf as *mut libc::c_void);
+  return 1;
+}
+unsafe extern "C" fn load_modules_dep() {
+  let mut m: *mut module_entry = std::ptr::null_mut();
+  let mut colon: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut tokens: [*mut libc::c_char; 2] = [std::ptr::null_mut::<libc::c_char>(); 2];
+  let mut p: *mut parser_t = std::ptr::null_mut();
+  /* depmod writes the same data as a trie, prefer it (like kmod) */
+  if load_modules_dep_bin() != 0 {
+    return;
+  }
+  /* Modprobe does not work at all without modules.dep,
+   * even if the full module name is given. Returning error here
+   * was making us later confuse user with this message:
+   * "module /full/path/to/existing/file/module.ko not found".
+   * It's better to die immediately, with good message.
+   * xfopen_for_read provides that. */
+  p = crate::libbb::parse_config::config_open2(
+    b"modules.dep\x00" as *const u8 as *const libc::c_char,
+    Some(crate::libbb::wfopen::xfopen_for_read),
+  );
+  while (*ptr_to_globals).num_unresolved_deps != 0
+    && crate::libbb::parse_config::config_read(
+      p,
+      tokens.as_mut_ptr(),
+      (PARSE_NORMAL as libc::c_int | (1i32 & 0xffi32) << 8i32 | 2i32 & 0xffi32) as libc::c_uint,
+      b"# \t\x00" as *const u8 as *const libc::c_char,
+    ) != 0
+  {
+    colon = crate::libbb::last_char_is::last_char_is(tokens[0], ':' as i32);
+    if colon.is_null() {
+      continue;
+    }
+    *colon = '\u{0}' as i32 as libc::c_char;
+    /* "kernel/drivers/net/foo.ko.xz" -> "foo" */
+    m = crate::modutils::modutils::moddb_get(
+      &mut (*ptr_to_globals).db,
+      crate::libbb::get_last_path_component::bb_get_last_path_component_nostrip(tokens[0]),
+    );
+    if m.is_null() {
+      continue;
+    }
+    add_moddep(m, tokens[0], tokens[1]);
+  }
+  crate::libbb::parse_config::config_close(p);
+}
+pub unsafe fn modprobe_main(
+  mut _argc: libc::c_int,
+  mut argv: *mut *mut libc::c_char,
+) -> libc::c_int {
+  let mut rc: libc::c_int = 0;
+  let mut opt: libc::c_uint = 0;
+  let mut me: *mut module_entry = std::ptr::null_mut();
+  let mut uts: utsname = utsname {
+    sysname: [0; 65],
+    nodename: [0; 65],
+    release: [0; 65],
+    version: [0; 65],
+    machine: [0; 65],
+    domainname: [0; 65],
+  };
+  let mut version: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut basedir: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let ref mut fresh0 = *(not_const_pp(&ptr_to_globals as *const *mut globals as *const libc::c_void)
+    as *mut *mut globals);
+  *fresh0 = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<globals>() as libc::c_ulong)
+    as *mut globals;
+  opt = crate::libbb::getopt32::getopt32long(
+    argv,
+    b"^vqsalrDbnS:d:\x00q-v:v-q:l--arD:r--alD:a--lr:D--rl\x00" as *const u8 as *const libc::c_char,
+    modprobe_longopts.as_ptr() as *const libc::c_char,
+    &mut version as *mut *mut libc::c_char,
+    &mut basedir as *mut *mut libc::c_char,
+  );
+  argv = argv.offset(optind as isize);
+  /* Goto modules location */
+  if opt & OPT_SET_VERSION as libc::c_int as libc::c_uint == 0 {
+    uname(&mut uts);
+    version = uts.release.as_mut_ptr()
+  }
+  (*ptr_to_globals).moddir = crate::libbb::xfuncs_printf::xasprintf(
+    b"%s/lib/modules/%s\x00" as *const u8 as *const libc::c_char,
+    if opt & OPT_DIRNAME as libc::c_int as libc::c_uint != 0 {
+      basedir as *const libc::c_char
+    } else {
+      b"\x00" as *const u8 as *const libc::c_char
+    },
+    version,
+  );
+  crate::libbb::xfuncs_printf::xchdir((*ptr_to_globals).moddir);
+  if opt & OPT_LIST_ONLY as libc::c_int as libc::c_uint != 0 {
+    let mut i: libc::c_int = 0;
+    let mut colon: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut tokens: [*mut libc::c_char; 2] = [std::ptr::null_mut::<libc::c_char>(); 2];
+    let mut p: *mut parser_t = crate::libbb::parse_config::config_open2(
+      b"modules.dep\x00" as *const u8 as *const libc::c_char,
+      Some(crate::libbb::wfopen::xfopen_for_read),
+    );
+    i = 0;
+    while !(*argv.offset(i as isize)).is_null() {
+      crate::modutils::modutils::replace(
+        *argv.offset(i as isize),
+        '-' as i32 as libc::c_char,
+        '_' as i32 as libc::c_char,
+      );
+      i += 1
+    }
+    while crate::libbb::parse_config::config_read(
+      p,
+      tokens.as_mut_ptr(),
+      (PARSE_NORMAL as libc::c_int | (1i32 & 0xffi32) << 8i32 | 2i32 & 0xffi32) as libc::c_uint,
+      b"# \t\x00" as *const u8 as *const libc::c_char,
+    ) != 0
+    {
+      colon = crate::libbb::last_char_is::last_char_is(tokens[0], ':' as i32);
+      if colon.is_null() {
+        continue;
+      }
+      *colon = '\u{0}' as i32 as libc::c_char;
+      if (*argv.offset(0)).is_null() {
+        puts(tokens[0]);
+      } else {
+        let mut name: [libc::c_char; 256] = [0; 256];
+        crate::modutils::modutils::filename2modname(
+          crate::libbb::get_last_path_component::bb_get_last_path_component_nostrip(tokens[0]),
+          name.as_mut_ptr(),
+        );
+        i = 0;
+        while !(*argv.offset(i as isize)).is_null() {
+          if fnmatch(*argv.offset(i as isize), name.as_mut_ptr(), 0) == 0 {
+            puts(tokens[0]);
+          }
+          i += 1
+        }
+      }
+    }
+    return 0;
+  }
+  /* Yes, for some reason -l ignores -s... */
+  if opt & INSMOD_OPT_SYSLOG as libc::c_int as libc::c_uint != 0 {
+    logmode = LOGMODE_SYSLOG as libc::c_int as smallint
+  }
+  if (*argv.offset(0)).is_null() {
+    if opt & OPT_REMOVE as libc::c_int as libc::c_uint != 0 {
+      /* "modprobe -r" (w/o params).
+       * "If name is NULL, all unused modules marked
+       * autoclean will be removed".
+       */
+      if crate::modutils::modutils::bb_delete_module(
+        std::ptr::null(),
+        (0o4000i32 | 0o200i32) as libc::c_uint,
+      ) != 0
+      {
+        crate::libbb::perror_nomsg_and_die::bb_perror_nomsg_and_die();
+      }
+    }
+    return 0;
+  }
+  /* Retrieve module names of already loaded modules */
+  let mut s: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut parser: *mut parser_t = crate::libbb::parse_config::config_open2(
+    b"/proc/modules\x00" as *const u8 as *const libc::c_char,
+    Some(crate::libbb::wfopen::fopen_for_read),
+  );
+  while crate::libbb::parse_config::config_read(
+    parser,
+    &mut s,
+    (PARSE_NORMAL as libc::c_int & !(PARSE_GREEDY as libc::c_int)
+      | (1i32 & 0xffi32) << 8i32
+      | 1i32 & 0xffi32) as libc::c_uint,
+    b"# \t\x00" as *const u8 as *const libc::c_char,
+  ) != 0
+  {
+    (*get_or_add_modentry(s)).flags |= MODULE_FLAG_LOADED as libc::c_int as libc::c_uint
+  }
+  crate::libbb::parse_config::config_close(parser);
+  /* modules.builtin lists paths: "kernel/fs/ext4/ext4.ko" */
+  parser = crate::libbb::parse_config::config_open2(
+    b"modules.builtin\x00" as *const u8 as *const libc::c_char,
+    Some(crate::libbb::wfopen::fopen_for_read),
+  );
+  while crate::libbb::parse_config::config_read(
+    parser,
+    &mut s,
+    (PARSE_NORMAL as libc::c_int | (1i32 & 0xffi32) << 8i32 | 1i32 & 0xffi32) as libc::c_uint,
+    b"# \t\x00" as *const u8 as *const libc::c_char,
+  ) != 0
+  {
+    (*get_or_add_modentry(
+      crate::libbb::get_last_path_component::bb_get_last_path_component_nostrip(s),
+    ))
+    .flags |= MODULE_FLAG_BUILTIN as libc::c_int as libc::c_uint
+  }
+  crate::libbb::parse_config::config_close(parser);
+  if opt & (OPT_INSERT_ALL as libc::c_int | OPT_REMOVE as libc::c_int) as libc::c_uint != 0 {
+    loop
+    /* Each argument is a module name */
+    {
+      let fresh1 = argv;
+      argv = argv.offset(1);
+      add_probe(*fresh1);
+      if (*argv).is_null() {
+        break;
+      }
+    }
+  } else {
+    /* First argument is module name, rest are parameters */
+    add_probe(*argv.offset(0));
+    (*ptr_to_globals).cmdline_mopts =
+      crate::modutils::modutils::parse_cmdline_module_options(argv, 1i32)
+  }
+  /* Happens if all requested modules are already loaded */
+  if (*ptr_to_globals).probes.is_null() {
+    return 0;
+  }
+  /* Later files override nothing: options and blacklists accumulate */
+  read_config(b"/etc/modprobe.conf\x00" as *const u8 as *const libc::c_char);
+  read_config(b"/etc/modprobe.d\x00" as *const u8 as *const libc::c_char);
+  read_config(b"/run/modprobe.d\x00" as *const u8 as *const libc::c_char);
+  read_config(b"/lib/modprobe.d\x00" as *const u8 as *const libc::c_char);
+  read_config(b"modules.softdep\x00" as *const u8 as *const libc::c_char);
+  if (*ptr_to_globals).need_symbols != 0
+    && read_alias_index(b"modules.symbols.bin\x00" as *const u8 as *const libc::c_char) == 0
+  {
+    read_config(b"modules.symbols\x00" as *const u8 as *const libc::c_char);
+  }
+  load_modules_dep();
+  if (*ptr_to_globals).num_unresolved_deps != 0 {
+    if read_alias_index(b"modules.alias.bin\x00" as *const u8 as *const libc::c_char) == 0 {
+      read_config(b"modules.alias\x00" as *const u8 as *const libc::c_char);
+    }
+    load_modules_dep();
+  }
+  if (*ptr_to_globals).have_softdeps != 0 {
+    let mut l: *mut llist_t = (*ptr_to_globals).probes;
+    while !l.is_null() {
+      let mut r: *mut llist_t = std::ptr::null_mut();
+      me = (*l).data as *mut module_entry;
+      mark_softdeps(me);
+      r = (*me).realnames;
+      while !r.is_null() {
+        mark_softdeps(get_or_add_modentry((*r).data));
+        r = (*r).link
+      }
+      l = (*l).link
+    }
+    if (*ptr_to_globals).num_unresolved_deps != 0 {
+      load_modules_dep();
+    }
+  }
+  rc = 0;
+  loop {
+    me = crate::libbb::llist::llist_pop(&mut (*ptr_to_globals).probes) as *mut module_entry;
+    if me.is_null() {
+      break;
+    }
+    if (*me).realnames.is_null() {
+      /* This is not an alias. Literal names are blacklisted
+       * only if '-b' is given.
+       */
+      if opt & OPT_BLACKLIST as libc::c_int as libc::c_uint == 0
+        || (*me).flags & MODULE_FLAG_BLACKLISTED as libc::c_int as libc::c_uint == 0
+      {
+        rc |= modprobe_with_softdeps(me)
+      }
+    } else {
+      loop
+      /* Probe all real names for the alias */
+      {
+        let mut realname: *mut libc::c_char =
+          crate::libbb::llist::llist_pop(&mut (*me).realnames) as *mut libc::c_char;
+        let mut m2: *mut module_entry = std::ptr::null_mut();
+        m2 = get_or_add_modentry(realname);
+        if (*m2).flags & MODULE_FLAG_BLACKLISTED as libc::c_int as libc::c_uint == 0
+          && ((*m2).flags & MODULE_FLAG_LOADED as libc::c_int as libc::c_uint == 0
+            || opt & (OPT_REMOVE as libc::c_int | OPT_SHOW_DEPS as libc::c_int) as libc::c_uint
+              != 0)
+        {
+          rc |= modprobe_with_softdeps(m2)
+        }
+        free(realname as *mut libc::c_void);
+        if (*me).realnames.is_null() {
+          break;
+        }
+      }
+    }
+  }
+  crate::modutils::modutils::moddb_free(&mut (*ptr_to_globals).db);
+  return (rc != 0) as libc::c_int;
+}
@@ -20,6 +20,8 @@ extern "C" {
   fn strsep(__stringp: *mut *mut libc::c_char, __delim: *const libc::c_char) -> *mut libc::c_char;
   #[no_mangle]
   fn munmap(__addr: *mut libc::c_void, __len: size_t) -> libc::c_int;
+  #[no_mangle]
+  fn strrchr(__s: *const libc::c_char, __c: libc::c_int) -> *mut libc::c_char;
 
 }
 use crate::libbb::llist::llist_t;
@@ -38,8 +40,11 @@ pub struct module_entry {
   pub options: *mut libc::c_char,
   pub aliases: *mut llist_t,
   pub symbols: *mut llist_t,
+  pub softdeps: *mut llist_t,
   pub dnext: *mut module_entry,
   pub dprev: *mut module_entry,
+  /* depmod: position in modules.dep, the priority in the .bin indexes */
+  pub dep_order: libc::c_uint,
 }
 
 #[repr(C)]
@@ -240,6 +245,18 @@ pub unsafe fn parse_cmdline_module_options(
   /* if (optlen != 0) options[optlen-1] = '\0'; */
   return options;
 }
+/* finit_module() flag: the file is compressed, kernel should unpack it */
+pub const MODULE_INIT_COMPRESSED_FILE: libc::c_int = 4;
+/* Is it a .ko.gz, .ko.xz or .ko.zst? */
+pub unsafe fn is_compressed_module(mut filename: *const libc::c_char) -> bool {
+  let mut ext: *const libc::c_char = strrchr(filename, '.' as i32);
+  if ext.is_null() {
+    return false;
+  }
+  return strcmp(ext, b".gz\x00" as *const u8 as *const libc::c_char) == 0
+    || strcmp(ext, b".xz\x00" as *const u8 as *const libc::c_char) == 0
+    || strcmp(ext, b".zst\x00" as *const u8 as *const libc::c_char) == 0;
+}
 /* Return:
  * 0 on success,
  * -errno on open/read error,
@@ -265,11 +282,35 @@ pub unsafe fn bb_init_module(
    */
   let mut fd: libc::c_int = open(filename, 0 | 0o2000000i32); /* may be changed by e.g. open errors below */
   if fd >= 0 {
-    rc = (syscall(313i32 as libc::c_long, fd, options, 0) != 0) as libc::c_int;
+    /* Kernels >= 6.4 can decompress .ko.{gz,xz,zst} themselves if
+     * asked to. Older ones reject the flag with EINVAL, and we fall
+     * back to decompressing in userspace below. */
+    rc = (syscall(
+      313i32 as libc::c_long,
+      fd,
+      options,
+      if is_compressed_module(filename) {
+        MODULE_INIT_COMPRESSED_FILE
+      } else {
+        0
+      },
+    ) != 0) as libc::c_int;
+    if rc != 0 {
+      rc = *bb_errno
+    }
     close(fd);
     if rc == 0 {
       return rc;
     }
+    /* We have no userspace zstd decompressor */
+    if !crate::libbb::compare_string_array::is_suffixed_with(
+      filename,
+      b".zst\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      return rc;
+    }
   }
   image_size = (2147483647i32 - 4095i32) as size_t;
   mmaped = 0 != 0;
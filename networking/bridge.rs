@@ -0,0 +1,1077 @@
+use crate::libbb::appletlib::applet_name;
+use crate::libbb::default_error_retval::xfunc_error_retval;
+use crate::librb::rtattr;
+use crate::networking::libiproute::libnetlink::rtnl_handle;
+use libc;
+use libc::nlmsghdr;
+use libc::printf;
+use libc::sockaddr_nl;
+use libc::strchr;
+
+/*
+ * Licensed under GPLv2 or later, see file LICENSE in this source tree.
+ *
+ * A small subset of iproute2's "bridge" tool. Unlike brctl, which goes
+ * through the legacy ioctls and sysfs, everything here is rtnetlink
+ * (AF_BRIDGE family), so per-port VLAN tables and static FDB entries can
+ * be managed.
+ */
+//config:config BRIDGE
+//config:	bool "bridge (9 kb)"
+//config:	default y
+//config:	select PLATFORM_LINUX
+//config:	help
+//config:	Manage bridge port VLANs, forwarding database entries
+//config:	and port settings over rtnetlink.
+//applet:IF_BRIDGE(APPLET_NOEXEC(bridge, bridge, BB_DIR_SBIN, SUID_DROP, bridge))
+//kbuild:lib-$(CONFIG_BRIDGE) += bridge.o
+//usage:#define bridge_trivial_usage
+//usage:       "link|fdb|vlan CMD [ARGS]"
+//usage:#define bridge_full_usage "\n\n"
+//usage:       "bridge link [show] [dev IFACE]\n"
+//usage:       "bridge link set dev IFACE [state STATE] [cost N] [priority N]\n"
+//usage:       "	[learning on|off] [flood on|off] [hairpin on|off]\n"
+//usage:       "	[guard on|off] [vlan_filtering on|off]\n"
+//usage:       "bridge fdb add|append|replace|del LLADDR dev IFACE [vlan VID]\n"
+//usage:       "	[self] [master] [local|permanent|static|dynamic]\n"
+//usage:       "bridge fdb [show] [br BRIDGE] [dev IFACE]\n"
+//usage:       "bridge vlan add|del vid VID[-VID] dev IFACE [pvid] [untagged]\n"
+//usage:       "	[self] [master]\n"
+//usage:       "bridge vlan [show] [dev IFACE]"
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ifinfomsg {
+  pub ifi_family: libc::c_uchar,
+  pub __ifi_pad: libc::c_uchar,
+  pub ifi_type: libc::c_ushort,
+  pub ifi_index: libc::c_int,
+  pub ifi_flags: libc::c_uint,
+  pub ifi_change: libc::c_uint,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ndmsg {
+  pub ndm_family: u8,
+  pub ndm_pad1: u8,
+  pub ndm_pad2: u16,
+  pub ndm_ifindex: i32,
+  pub ndm_state: u16,
+  pub ndm_flags: u8,
+  pub ndm_type: u8,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct bridge_vlan_info {
+  pub flags: u16,
+  pub vid: u16,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct link_req {
+  n: nlmsghdr,
+  i: ifinfomsg,
+  buf: [libc::c_char; 1024],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct neigh_req {
+  n: nlmsghdr,
+  ndm: ndmsg,
+  buf: [libc::c_char; 256],
+}
+
+/* Payload for RTM_GETLINK dumps which also want the per-port VLAN table */
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct link_dump_req {
+  i: ifinfomsg,
+  ext: rtattr,
+  ext_mask: u32,
+}
+
+const AF_UNSPEC: libc::c_uchar = 0;
+const AF_BRIDGE: libc::c_uchar = 7;
+
+const RTM_NEWLINK: u16 = 16;
+const RTM_DELLINK: u16 = 17;
+const RTM_GETLINK: u16 = 18;
+const RTM_SETLINK: u16 = 19;
+const RTM_NEWNEIGH: u16 = 28;
+const RTM_DELNEIGH: u16 = 29;
+const RTM_GETNEIGH: u16 = 30;
+
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_REPLACE: u16 = 0x100;
+const NLM_F_EXCL: u16 = 0x200;
+const NLM_F_CREATE: u16 = 0x400;
+const NLM_F_APPEND: u16 = 0x800;
+
+const IFLA_IFNAME: libc::c_int = 3;
+const IFLA_MASTER: libc::c_int = 10;
+const IFLA_PROTINFO: libc::c_int = 12;
+const IFLA_LINKINFO: libc::c_int = 18;
+const IFLA_AF_SPEC: libc::c_int = 26;
+const IFLA_EXT_MASK: libc::c_ushort = 29;
+const IFLA_MAX: libc::c_int = 29;
+const IFLA_INFO_KIND: libc::c_int = 1;
+const IFLA_INFO_DATA: libc::c_int = 2;
+const IFLA_BR_VLAN_FILTERING: libc::c_int = 7;
+const RTEXT_FILTER_BRVLAN: u32 = 1 << 1;
+
+const IFLA_BRIDGE_FLAGS: libc::c_int = 0;
+const IFLA_BRIDGE_VLAN_INFO: libc::c_int = 2;
+const BRIDGE_FLAGS_MASTER: u16 = 1;
+const BRIDGE_FLAGS_SELF: u16 = 2;
+const BRIDGE_VLAN_INFO_PVID: u16 = 1 << 1;
+const BRIDGE_VLAN_INFO_UNTAGGED: u16 = 1 << 2;
+const BRIDGE_VLAN_INFO_RANGE_BEGIN: u16 = 1 << 3;
+const BRIDGE_VLAN_INFO_RANGE_END: u16 = 1 << 4;
+
+const IFLA_BRPORT_STATE: libc::c_int = 1;
+const IFLA_BRPORT_PRIORITY: libc::c_int = 2;
+const IFLA_BRPORT_COST: libc::c_int = 3;
+const IFLA_BRPORT_MODE: libc::c_int = 4;
+const IFLA_BRPORT_GUARD: libc::c_int = 5;
+const IFLA_BRPORT_LEARNING: libc::c_int = 8;
+const IFLA_BRPORT_UNICAST_FLOOD: libc::c_int = 9;
+const IFLA_BRPORT_MAX: libc::c_int = 9;
+
+const NDA_LLADDR: libc::c_int = 2;
+const NDA_VLAN: libc::c_int = 5;
+const NDA_MASTER: libc::c_int = 9;
+const NDA_MAX: libc::c_int = 9;
+const NUD_REACHABLE: u16 = 0x02;
+const NUD_NOARP: u16 = 0x40;
+const NUD_PERMANENT: u16 = 0x80;
+const NTF_SELF: u8 = 0x02;
+const NTF_MASTER: u8 = 0x04;
+
+/* Bridge (or port) index to restrict "show" output to, 0 = all */
+static mut filter_index: libc::c_int = 0;
+static mut filter_master: libc::c_int = 0;
+
+static port_states: [&[u8]; 5] = [
+  b"disabled\x00",
+  b"listening\x00",
+  b"learning\x00",
+  b"forwarding\x00",
+  b"blocking\x00",
+];
+
+#[inline(always)]
+unsafe fn nlmsg_data(n: *mut nlmsghdr) -> *mut libc::c_void {
+  (n as *mut libc::c_char).offset(16) as *mut libc::c_void
+}
+#[inline(always)]
+unsafe fn rta_data(rta: *const rtattr) -> *mut libc::c_void {
+  (rta as *mut libc::c_char).offset(4) as *mut libc::c_void
+}
+#[inline(always)]
+unsafe fn rta_payload(rta: *const rtattr) -> libc::c_int {
+  (*rta).rta_len as libc::c_int - 4
+}
+#[inline(always)]
+unsafe fn rta_getattr_u32(rta: *const rtattr) -> u32 {
+  *(rta_data(rta) as *const u32)
+}
+
+unsafe fn parse_nested(tb: *mut *mut rtattr, max: libc::c_int, rta: *mut rtattr) {
+  crate::networking::libiproute::libnetlink::parse_rtattr(
+    tb,
+    max,
+    rta_data(rta) as *mut rtattr,
+    rta_payload(rta),
+  );
+}
+
+unsafe fn die_must_be_on_off(msg: *const libc::c_char) -> ! {
+  crate::libbb::verror_msg::bb_error_msg_and_die(
+    b"argument of \"%s\" must be \"on\" or \"off\"\x00" as *const u8 as *const libc::c_char,
+    msg,
+  );
+}
+
+unsafe fn parse_on_off(kw: *const libc::c_char, arg: *const libc::c_char) -> u8 {
+  let param = crate::libbb::compare_string_array::index_in_strings(
+    b"on\x00off\x00\x00" as *const u8 as *const libc::c_char,
+    arg,
+  );
+  if param < 0 {
+    die_must_be_on_off(kw);
+  }
+  (param == 0) as u8
+}
+
+unsafe fn xname_to_index(name: *const libc::c_char) -> libc::c_int {
+  crate::networking::libiproute::ll_map::xll_name_to_index(name)
+}
+
+/*
+ * bridge link
+ */
+unsafe fn print_linkinfo(
+  _who: *const sockaddr_nl,
+  n: *mut nlmsghdr,
+  _arg: *mut libc::c_void,
+) -> libc::c_int {
+  let ifi = nlmsg_data(n) as *mut ifinfomsg;
+  let len = (*n).nlmsg_len as libc::c_int - 16 - std::mem::size_of::<ifinfomsg>() as libc::c_int;
+  let mut tb: [*mut rtattr; (IFLA_MAX + 1) as usize] =
+    [std::ptr::null_mut(); (IFLA_MAX + 1) as usize];
+
+  if (*n).nlmsg_type != RTM_NEWLINK || len < 0 {
+    return 0;
+  }
+  if (*ifi).ifi_family != AF_BRIDGE {
+    return 0;
+  }
+  if filter_index != 0 && filter_index != (*ifi).ifi_index {
+    return 0;
+  }
+  crate::networking::libiproute::libnetlink::parse_rtattr(
+    tb.as_mut_ptr(),
+    IFLA_MAX,
+    (ifi as *mut libc::c_char).offset(std::mem::size_of::<ifinfomsg>() as isize) as *mut rtattr,
+    len,
+  );
+  if tb[IFLA_IFNAME as usize].is_null() {
+    return 0;
+  }
+  printf(
+    b"%d: %s\x00" as *const u8 as *const libc::c_char,
+    (*ifi).ifi_index,
+    rta_data(tb[IFLA_IFNAME as usize]) as *const libc::c_char,
+  );
+  if !tb[IFLA_MASTER as usize].is_null() {
+    printf(
+      b" master %s\x00" as *const u8 as *const libc::c_char,
+      crate::networking::libiproute::ll_map::ll_index_to_name(rta_getattr_u32(
+        tb[IFLA_MASTER as usize],
+      ) as libc::c_int),
+    );
+  }
+  if !tb[IFLA_PROTINFO as usize].is_null() {
+    let mut prtb: [*mut rtattr; (IFLA_BRPORT_MAX + 1) as usize] =
+      [std::ptr::null_mut(); (IFLA_BRPORT_MAX + 1) as usize];
+    parse_nested(
+      prtb.as_mut_ptr(),
+      IFLA_BRPORT_MAX,
+      tb[IFLA_PROTINFO as usize],
+    );
+    let state = prtb[IFLA_BRPORT_STATE as usize];
+    if !state.is_null() {
+      let st = *(rta_data(state) as *const u8) as usize;
+      if st < port_states.len() {
+        printf(
+          b" state %s\x00" as *const u8 as *const libc::c_char,
+          port_states[st].as_ptr() as *const libc::c_char,
+        );
+      } else {
+        printf(
+          b" state %u\x00" as *const u8 as *const libc::c_char,
+          st as libc::c_uint,
+        );
+      }
+    }
+    let prio = prtb[IFLA_BRPORT_PRIORITY as usize];
+    if !prio.is_null() {
+      printf(
+        b" priority %u\x00" as *const u8 as *const libc::c_char,
+        *(rta_data(prio) as *const u16) as libc::c_uint,
+      );
+    }
+    let cost = prtb[IFLA_BRPORT_COST as usize];
+    if !cost.is_null() {
+      printf(
+        b" cost %u\x00" as *const u8 as *const libc::c_char,
+        rta_getattr_u32(cost),
+      );
+    }
+    for &(attr, name) in [
+      (IFLA_BRPORT_MODE, b"hairpin\x00" as &[u8]),
+      (IFLA_BRPORT_GUARD, b"guard\x00"),
+      (IFLA_BRPORT_LEARNING, b"learning\x00"),
+      (IFLA_BRPORT_UNICAST_FLOOD, b"flood\x00"),
+    ]
+    .iter()
+    {
+      let rta = prtb[attr as usize];
+      if !rta.is_null() {
+        printf(
+          b" %s %s\x00" as *const u8 as *const libc::c_char,
+          name.as_ptr() as *const libc::c_char,
+          if *(rta_data(rta) as *const u8) != 0 {
+            b"on\x00" as *const u8 as *const libc::c_char
+          } else {
+            b"off\x00" as *const u8 as *const libc::c_char
+          },
+        );
+      }
+    }
+  }
+  printf(b"\n\x00" as *const u8 as *const libc::c_char);
+  return 0;
+}
+
+unsafe fn dump_links(rth: *mut rtnl_handle, ext_mask: u32) {
+  let mut req: link_dump_req = std::mem::zeroed();
+  req.i.ifi_family = AF_BRIDGE;
+  req.ext.rta_type = IFLA_EXT_MASK;
+  req.ext.rta_len = (std::mem::size_of::<rtattr>() + std::mem::size_of::<u32>()) as libc::c_ushort;
+  req.ext_mask = ext_mask;
+  if crate::networking::libiproute::libnetlink::rtnl_dump_request(
+    rth,
+    RTM_GETLINK as libc::c_int,
+    &mut req as *mut link_dump_req as *mut libc::c_void,
+    std::mem::size_of::<link_dump_req>() as libc::c_int,
+  ) < 0
+  {
+    crate::libbb::perror_msg::bb_simple_perror_msg_and_die(
+      b"can\'t send dump request\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+}
+
+unsafe fn link_show(mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  let mut rth: rtnl_handle = std::mem::zeroed();
+
+  while !(*argv).is_null() {
+    if crate::libbb::compare_string_array::index_in_strings(
+      b"dev\x00\x00" as *const u8 as *const libc::c_char,
+      *argv,
+    ) == 0
+    {
+      argv = crate::networking::libiproute::utils::next_arg(argv);
+    }
+    filter_index = xname_to_index(*argv);
+    argv = argv.offset(1);
+  }
+  crate::networking::libiproute::libnetlink::xrtnl_open(&mut rth);
+  crate::networking::libiproute::ll_map::ll_init_map(&mut rth);
+  dump_links(&mut rth, 0);
+  crate::networking::libiproute::libnetlink::xrtnl_dump_filter(
+    &mut rth,
+    Some(print_linkinfo),
+    std::ptr::null_mut(),
+  );
+  return 0;
+}
+
+/* Turning VLAN filtering on is a property of the bridge device itself,
+ * not of its ports: it goes out as IFLA_LINKINFO/"bridge" data. */
+unsafe fn set_vlan_filtering(rth: *mut rtnl_handle, ifindex: libc::c_int, on: u8) -> libc::c_int {
+  let mut req: link_req = std::mem::zeroed();
+  let size = std::mem::size_of::<link_req>() as libc::c_int;
+  let kind = b"bridge\x00";
+
+  req.n.nlmsg_len = (16 + std::mem::size_of::<ifinfomsg>()) as u32;
+  req.n.nlmsg_flags = NLM_F_REQUEST;
+  req.n.nlmsg_type = RTM_NEWLINK;
+  req.i.ifi_family = AF_UNSPEC;
+  req.i.ifi_index = ifindex;
+  let linkinfo =
+    crate::networking::libiproute::libnetlink::addattr_nest(&mut req.n, size, IFLA_LINKINFO);
+  crate::networking::libiproute::libnetlink::addattr_l(
+    &mut req.n,
+    size,
+    IFLA_INFO_KIND,
+    kind.as_ptr() as *mut libc::c_void,
+    (kind.len() - 1) as libc::c_int,
+  );
+  let data =
+    crate::networking::libiproute::libnetlink::addattr_nest(&mut req.n, size, IFLA_INFO_DATA);
+  crate::networking::libiproute::libnetlink::addattr8(&mut req.n, size, IFLA_BR_VLAN_FILTERING, on);
+  crate::networking::libiproute::libnetlink::addattr_nest_end(&mut req.n, data);
+  crate::networking::libiproute::libnetlink::addattr_nest_end(&mut req.n, linkinfo);
+  if crate::networking::libiproute::libnetlink::rtnl_talk(rth, &mut req.n, std::ptr::null_mut()) < 0
+  {
+    return 2;
+  }
+  return 0;
+}
+
+unsafe fn link_set(mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  let keywords = b"dev\x00state\x00cost\x00priority\x00learning\x00flood\x00hairpin\x00guard\x00vlan_filtering\x00\x00";
+  const ARG_dev: libc::c_int = 0;
+  const ARG_state: libc::c_int = 1;
+  const ARG_cost: libc::c_int = 2;
+  const ARG_priority: libc::c_int = 3;
+  const ARG_learning: libc::c_int = 4;
+  const ARG_flood: libc::c_int = 5;
+  const ARG_hairpin: libc::c_int = 6;
+  const ARG_guard: libc::c_int = 7;
+  const ARG_vlan_filtering: libc::c_int = 8;
+  let mut rth: rtnl_handle = std::mem::zeroed();
+  let mut req: link_req = std::mem::zeroed();
+  let size = std::mem::size_of::<link_req>() as libc::c_int;
+  let mut dev: *mut libc::c_char = std::ptr::null_mut();
+  let mut vlan_filtering: libc::c_int = -1;
+
+  req.n.nlmsg_len = (16 + std::mem::size_of::<ifinfomsg>()) as u32;
+  req.n.nlmsg_flags = NLM_F_REQUEST;
+  req.n.nlmsg_type = RTM_SETLINK;
+  req.i.ifi_family = AF_BRIDGE;
+  let protinfo =
+    crate::networking::libiproute::libnetlink::addattr_nest(&mut req.n, size, IFLA_PROTINFO);
+  let empty_len = req.n.nlmsg_len;
+
+  while !(*argv).is_null() {
+    let key = crate::libbb::compare_string_array::index_in_strings(
+      keywords.as_ptr() as *const libc::c_char,
+      *argv,
+    );
+    let kw = *argv;
+    if key < 0 {
+      crate::networking::libiproute::utils::invarg_1_to_2(
+        *argv,
+        b"bridge link set\x00" as *const u8 as *const libc::c_char,
+      );
+    }
+    if key == ARG_dev {
+      argv = crate::networking::libiproute::utils::next_arg(argv);
+      if !dev.is_null() {
+        crate::networking::libiproute::utils::duparg2(
+          b"dev\x00" as *const u8 as *const libc::c_char,
+          *argv,
+        );
+      }
+      dev = *argv;
+    } else if key == ARG_state {
+      argv = crate::networking::libiproute::utils::next_arg(argv);
+      let mut state = crate::libbb::compare_string_array::index_in_strings(
+        b"disabled\x00listening\x00learning\x00forwarding\x00blocking\x00\x00" as *const u8
+          as *const libc::c_char,
+        *argv,
+      );
+      if state < 0 {
+        state = crate::networking::libiproute::utils::get_unsigned(*argv, kw) as libc::c_int;
+        if state > 4 {
+          crate::networking::libiproute::utils::invarg_1_to_2(*argv, kw);
+        }
+      }
+      crate::networking::libiproute::libnetlink::addattr8(
+        &mut req.n,
+        size,
+        IFLA_BRPORT_STATE,
+        state as u8,
+      );
+    } else if key == ARG_cost {
+      argv = crate::networking::libiproute::utils::next_arg(argv);
+      crate::networking::libiproute::libnetlink::addattr32(
+        &mut req.n,
+        size,
+        IFLA_BRPORT_COST,
+        crate::networking::libiproute::utils::get_u32(*argv, kw),
+      );
+    } else if key == ARG_priority {
+      argv = crate::networking::libiproute::utils::next_arg(argv);
+      crate::networking::libiproute::libnetlink::addattr16(
+        &mut req.n,
+        size,
+        IFLA_BRPORT_PRIORITY,
+        crate::networking::libiproute::utils::get_u16(*argv, kw),
+      );
+    } else {
+      /* "on|off" options */
+      argv = crate::networking::libiproute::utils::next_arg(argv);
+      let on = parse_on_off(kw, *argv);
+      if key == ARG_vlan_filtering {
+        vlan_filtering = on as libc::c_int;
+      } else {
+        let attr = match key {
+          ARG_learning => IFLA_BRPORT_LEARNING,
+          ARG_flood => IFLA_BRPORT_UNICAST_FLOOD,
+          ARG_hairpin => IFLA_BRPORT_MODE,
+          _ => IFLA_BRPORT_GUARD,
+        };
+        crate::networking::libiproute::libnetlink::addattr8(&mut req.n, size, attr, on);
+      }
+    }
+    argv = argv.offset(1);
+  }
+  if dev.is_null() {
+    crate::libbb::verror_msg::bb_error_msg_and_die(
+      b"%s is required\x00" as *const u8 as *const libc::c_char,
+      b"\"dev\"\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+
+  crate::networking::libiproute::libnetlink::xrtnl_open(&mut rth);
+  req.i.ifi_index = xname_to_index(dev);
+  if vlan_filtering >= 0 && set_vlan_filtering(&mut rth, req.i.ifi_index, vlan_filtering as u8) != 0
+  {
+    return 2;
+  }
+  if req.n.nlmsg_len == empty_len {
+    /* only vlan_filtering (or nothing at all) was given */
+    return 0;
+  }
+  crate::networking::libiproute::libnetlink::addattr_nest_end(&mut req.n, protinfo);
+  if crate::networking::libiproute::libnetlink::rtnl_talk(
+    &mut rth,
+    &mut req.n,
+    std::ptr::null_mut(),
+  ) < 0
+  {
+    return 2;
+  }
+  return 0;
+}
+
+unsafe fn do_link(argv: *mut *mut libc::c_char) -> libc::c_int {
+  if !(*argv).is_null() {
+    let key = crate::libbb::compare_string_array::index_in_strings(
+      b"set\x00show\x00list\x00lst\x00\x00" as *const u8 as *const libc::c_char,
+      *argv,
+    );
+    if key < 0 {
+      crate::networking::libiproute::utils::invarg_1_to_2(*argv, applet_name);
+    }
+    if key == 0 {
+      return link_set(argv.offset(1));
+    }
+    return link_show(argv.offset(1));
+  }
+  return link_show(argv);
+}
+
+/*
+ * bridge fdb
+ */
+unsafe fn print_fdb(
+  _who: *const sockaddr_nl,
+  n: *mut nlmsghdr,
+  _arg: *mut libc::c_void,
+) -> libc::c_int {
+  let r = nlmsg_data(n) as *mut ndmsg;
+  let len = (*n).nlmsg_len as libc::c_int - 16 - std::mem::size_of::<ndmsg>() as libc::c_int;
+  let mut tb: [*mut rtattr; (NDA_MAX + 1) as usize] =
+    [std::ptr::null_mut(); (NDA_MAX + 1) as usize];
+  let mut abuf: [libc::c_char; 64] = [0; 64];
+  let mut master: libc::c_int = 0;
+
+  if (*n).nlmsg_type != RTM_NEWNEIGH || len < 0 {
+    return 0;
+  }
+  if (*r).ndm_family != AF_BRIDGE {
+    return 0;
+  }
+  crate::networking::libiproute::libnetlink::parse_rtattr(
+    tb.as_mut_ptr(),
+    NDA_MAX,
+    (r as *mut libc::c_char).offset(std::mem::size_of::<ndmsg>() as isize) as *mut rtattr,
+    len,
+  );
+  if !tb[NDA_MASTER as usize].is_null() {
+    master = rta_getattr_u32(tb[NDA_MASTER as usize]) as libc::c_int;
+  }
+  if filter_index != 0 && filter_index != (*r).ndm_ifindex {
+    return 0;
+  }
+  if filter_master != 0 && filter_master != master && filter_master != (*r).ndm_ifindex {
+    return 0;
+  }
+
+  if !tb[NDA_LLADDR as usize].is_null() {
+    printf(
+      b"%s \x00" as *const u8 as *const libc::c_char,
+      crate::networking::libiproute::ll_addr::ll_addr_n2a(
+        rta_data(tb[NDA_LLADDR as usize]) as *mut libc::c_uchar,
+        rta_payload(tb[NDA_LLADDR as usize]),
+        0,
+        abuf.as_mut_ptr(),
+        abuf.len() as libc::c_int,
+      ),
+    );
+  }
+  printf(
+    b"dev %s\x00" as *const u8 as *const libc::c_char,
+    crate::networking::libiproute::ll_map::ll_index_to_name((*r).ndm_ifindex),
+  );
+  if !tb[NDA_VLAN as usize].is_null() {
+    printf(
+      b" vlan %u\x00" as *const u8 as *const libc::c_char,
+      *(rta_data(tb[NDA_VLAN as usize]) as *const u16) as libc::c_uint,
+    );
+  }
+  if master != 0 {
+    printf(
+      b" master %s\x00" as *const u8 as *const libc::c_char,
+      crate::networking::libiproute::ll_map::ll_index_to_name(master),
+    );
+  }
+  if (*r).ndm_state & NUD_PERMANENT != 0 {
+    printf(b" permanent\x00" as *const u8 as *const libc::c_char);
+  } else if (*r).ndm_state & NUD_NOARP != 0 {
+    printf(b" static\x00" as *const u8 as *const libc::c_char);
+  }
+  if (*r).ndm_flags & NTF_SELF != 0 {
+    printf(b" self\x00" as *const u8 as *const libc::c_char);
+  }
+  if (*r).ndm_flags & NTF_MASTER != 0 {
+    printf(b" master\x00" as *const u8 as *const libc::c_char);
+  }
+  printf(b"\n\x00" as *const u8 as *const libc::c_char);
+  return 0;
+}
+
+unsafe fn fdb_show(mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  let mut rth: rtnl_handle = std::mem::zeroed();
+  let mut req: ndmsg = std::mem::zeroed();
+  let mut br: *mut libc::c_char = std::ptr::null_mut();
+  let mut dev: *mut libc::c_char = std::ptr::null_mut();
+
+  while !(*argv).is_null() {
+    let key = crate::libbb::compare_string_array::index_in_strings(
+      b"br\x00brport\x00dev\x00\x00" as *const u8 as *const libc::c_char,
+      *argv,
+    );
+    if key < 0 {
+      crate::networking::libiproute::utils::invarg_1_to_2(*argv, applet_name);
+    }
+    argv = crate::networking::libiproute::utils::next_arg(argv);
+    if key == 0 {
+      br = *argv;
+    } else {
+      dev = *argv;
+    }
+    argv = argv.offset(1);
+  }
+
+  crate::networking::libiproute::libnetlink::xrtnl_open(&mut rth);
+  crate::networking::libiproute::ll_map::ll_init_map(&mut rth);
+  if !br.is_null() {
+    filter_master = xname_to_index(br);
+  }
+  if !dev.is_null() {
+    filter_index = xname_to_index(dev);
+  }
+  req.ndm_family = AF_BRIDGE;
+  if crate::networking::libiproute::libnetlink::rtnl_dump_request(
+    &mut rth,
+    RTM_GETNEIGH as libc::c_int,
+    &mut req as *mut ndmsg as *mut libc::c_void,
+    std::mem::size_of::<ndmsg>() as libc::c_int,
+  ) < 0
+  {
+    crate::libbb::perror_msg::bb_simple_perror_msg_and_die(
+      b"can\'t send dump request\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  crate::networking::libiproute::libnetlink::xrtnl_dump_filter(
+    &mut rth,
+    Some(print_fdb),
+    std::ptr::null_mut(),
+  );
+  return 0;
+}
+
+unsafe fn fdb_modify(mut argv: *mut *mut libc::c_char, cmd: u16, flags: u16) -> libc::c_int {
+  let keywords =
+    b"dev\x00vlan\x00self\x00master\x00local\x00permanent\x00static\x00dynamic\x00\x00";
+  const ARG_dev: libc::c_int = 0;
+  const ARG_vlan: libc::c_int = 1;
+  const ARG_self: libc::c_int = 2;
+  const ARG_master: libc::c_int = 3;
+  const ARG_local: libc::c_int = 4;
+  const ARG_permanent: libc::c_int = 5;
+  const ARG_static: libc::c_int = 6;
+  const ARG_dynamic: libc::c_int = 7;
+  let mut rth: rtnl_handle = std::mem::zeroed();
+  let mut req: neigh_req = std::mem::zeroed();
+  let size = std::mem::size_of::<neigh_req>() as libc::c_int;
+  let mut addr: *mut libc::c_char = std::ptr::null_mut();
+  let mut dev: *mut libc::c_char = std::ptr::null_mut();
+  let mut vid: libc::c_int = -1;
+  let mut abuf: [libc::c_uchar; 32] = [0; 32];
+
+  req.n.nlmsg_len = (16 + std::mem::size_of::<ndmsg>()) as u32;
+  req.n.nlmsg_flags = NLM_F_REQUEST | flags;
+  req.n.nlmsg_type = cmd;
+  req.ndm.ndm_family = AF_BRIDGE;
+  req.ndm.ndm_state = NUD_NOARP;
+
+  while !(*argv).is_null() {
+    let key = crate::libbb::compare_string_array::index_in_strings(
+      keywords.as_ptr() as *const libc::c_char,
+      *argv,
+    );
+    match key {
+      ARG_dev => {
+        argv = crate::networking::libiproute::utils::next_arg(argv);
+        dev = *argv;
+      }
+      ARG_vlan => {
+        argv = crate::networking::libiproute::utils::next_arg(argv);
+        if vid >= 0 {
+          crate::networking::libiproute::utils::duparg2(
+            b"vlan\x00" as *const u8 as *const libc::c_char,
+            *argv,
+          );
+        }
+        vid = crate::networking::libiproute::utils::get_u16(
+          *argv,
+          b"vlan\x00" as *const u8 as *const libc::c_char,
+        ) as libc::c_int;
+      }
+      ARG_self => req.ndm.ndm_flags |= NTF_SELF,
+      ARG_master => req.ndm.ndm_flags |= NTF_MASTER,
+      ARG_local | ARG_permanent => req.ndm.ndm_state |= NUD_PERMANENT,
+      ARG_static => req.ndm.ndm_state |= NUD_NOARP,
+      ARG_dynamic => req.ndm.ndm_state = NUD_REACHABLE,
+      _ => {
+        if !addr.is_null() {
+          crate::networking::libiproute::utils::duparg2(
+            b"lladdr\x00" as *const u8 as *const libc::c_char,
+            *argv,
+          );
+        }
+        addr = *argv;
+      }
+    }
+    argv = argv.offset(1);
+  }
+  if addr.is_null() || dev.is_null() {
+    crate::libbb::verror_msg::bb_simple_error_msg_and_die(
+      b"lladdr and dev are required\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  /* Neither given: program the device itself, as iproute2 does */
+  if req.ndm.ndm_flags & (NTF_SELF | NTF_MASTER) == 0 {
+    req.ndm.ndm_flags |= NTF_SELF;
+  }
+
+  let alen = crate::networking::libiproute::ll_addr::ll_addr_a2n(
+    abuf.as_mut_ptr(),
+    abuf.len() as libc::c_int,
+    addr,
+  );
+  if alen != 6 {
+    crate::networking::libiproute::utils::invarg_1_to_2(
+      addr,
+      b"lladdr\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  crate::networking::libiproute::libnetlink::addattr_l(
+    &mut req.n,
+    size,
+    NDA_LLADDR,
+    abuf.as_mut_ptr() as *mut libc::c_void,
+    alen,
+  );
+  if vid >= 0 {
+    crate::networking::libiproute::libnetlink::addattr16(&mut req.n, size, NDA_VLAN, vid as u16);
+  }
+
+  crate::networking::libiproute::libnetlink::xrtnl_open(&mut rth);
+  req.ndm.ndm_ifindex = xname_to_index(dev);
+  if crate::networking::libiproute::libnetlink::rtnl_talk(
+    &mut rth,
+    &mut req.n,
+    std::ptr::null_mut(),
+  ) < 0
+  {
+    return 2;
+  }
+  return 0;
+}
+
+unsafe fn do_fdb(argv: *mut *mut libc::c_char) -> libc::c_int {
+  if !(*argv).is_null() {
+    let key = crate::libbb::compare_string_array::index_in_strings(
+      b"add\x00append\x00replace\x00delete\x00del\x00show\x00list\x00lst\x00\x00" as *const u8
+        as *const libc::c_char,
+      *argv,
+    );
+    match key {
+      0 => return fdb_modify(argv.offset(1), RTM_NEWNEIGH, NLM_F_CREATE | NLM_F_EXCL),
+      1 => return fdb_modify(argv.offset(1), RTM_NEWNEIGH, NLM_F_CREATE | NLM_F_APPEND),
+      2 => return fdb_modify(argv.offset(1), RTM_NEWNEIGH, NLM_F_CREATE | NLM_F_REPLACE),
+      3 | 4 => return fdb_modify(argv.offset(1), RTM_DELNEIGH, 0),
+      5 | 6 | 7 => return fdb_show(argv.offset(1)),
+      _ => crate::networking::libiproute::utils::invarg_1_to_2(*argv, applet_name),
+    }
+  }
+  return fdb_show(argv);
+}
+
+/*
+ * bridge vlan
+ */
+unsafe fn print_vlan(
+  _who: *const sockaddr_nl,
+  n: *mut nlmsghdr,
+  _arg: *mut libc::c_void,
+) -> libc::c_int {
+  let ifi = nlmsg_data(n) as *mut ifinfomsg;
+  let len = (*n).nlmsg_len as libc::c_int - 16 - std::mem::size_of::<ifinfomsg>() as libc::c_int;
+  let mut tb: [*mut rtattr; (IFLA_MAX + 1) as usize] =
+    [std::ptr::null_mut(); (IFLA_MAX + 1) as usize];
+  let mut range_start: u16 = 0;
+  let mut first = true;
+
+  if (*n).nlmsg_type != RTM_NEWLINK || len < 0 {
+    return 0;
+  }
+  if (*ifi).ifi_family != AF_BRIDGE {
+    return 0;
+  }
+  if filter_index != 0 && filter_index != (*ifi).ifi_index {
+    return 0;
+  }
+  crate::networking::libiproute::libnetlink::parse_rtattr(
+    tb.as_mut_ptr(),
+    IFLA_MAX,
+    (ifi as *mut libc::c_char).offset(std::mem::size_of::<ifinfomsg>() as isize) as *mut rtattr,
+    len,
+  );
+  let af_spec = tb[IFLA_AF_SPEC as usize];
+  if af_spec.is_null() {
+    return 0;
+  }
+
+  /* IFLA_AF_SPEC carries a flat list of IFLA_BRIDGE_VLAN_INFO entries */
+  let mut rem = rta_payload(af_spec);
+  let mut i = rta_data(af_spec) as *mut rtattr;
+  while rem >= std::mem::size_of::<rtattr>() as libc::c_int
+    && (*i).rta_len as libc::c_int >= std::mem::size_of::<rtattr>() as libc::c_int
+    && (*i).rta_len as libc::c_int <= rem
+  {
+    if (*i).rta_type as libc::c_int == IFLA_BRIDGE_VLAN_INFO {
+      let vinfo = rta_data(i) as *const bridge_vlan_info;
+      if (*vinfo).flags & BRIDGE_VLAN_INFO_RANGE_BEGIN != 0 {
+        range_start = (*vinfo).vid;
+      } else {
+        printf(
+          b"%s\t\x00" as *const u8 as *const libc::c_char,
+          if first {
+            crate::networking::libiproute::ll_map::ll_index_to_name((*ifi).ifi_index)
+          } else {
+            b"\x00" as *const u8 as *const libc::c_char
+          },
+        );
+        first = false;
+        if (*vinfo).flags & BRIDGE_VLAN_INFO_RANGE_END != 0 {
+          printf(
+            b" %u-%u\x00" as *const u8 as *const libc::c_char,
+            range_start as libc::c_uint,
+            (*vinfo).vid as libc::c_uint,
+          );
+        } else {
+          printf(
+            b" %u\x00" as *const u8 as *const libc::c_char,
+            (*vinfo).vid as libc::c_uint,
+          );
+        }
+        if (*vinfo).flags & BRIDGE_VLAN_INFO_PVID != 0 {
+          printf(b" PVID\x00" as *const u8 as *const libc::c_char);
+        }
+        if (*vinfo).flags & BRIDGE_VLAN_INFO_UNTAGGED != 0 {
+          printf(b" Egress Untagged\x00" as *const u8 as *const libc::c_char);
+        }
+        printf(b"\n\x00" as *const u8 as *const libc::c_char);
+      }
+    }
+    let step = ((*i).rta_len as libc::c_int + 3) & !3;
+    rem -= step;
+    i = (i as *mut libc::c_char).offset(step as isize) as *mut rtattr;
+  }
+  if !first {
+    printf(b"\n\x00" as *const u8 as *const libc::c_char);
+  }
+  return 0;
+}
+
+unsafe fn vlan_show(mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  let mut rth: rtnl_handle = std::mem::zeroed();
+
+  while !(*argv).is_null() {
+    if crate::libbb::compare_string_array::index_in_strings(
+      b"dev\x00\x00" as *const u8 as *const libc::c_char,
+      *argv,
+    ) == 0
+    {
+      argv = crate::networking::libiproute::utils::next_arg(argv);
+    }
+    filter_index = xname_to_index(*argv);
+    argv = argv.offset(1);
+  }
+  crate::networking::libiproute::libnetlink::xrtnl_open(&mut rth);
+  crate::networking::libiproute::ll_map::ll_init_map(&mut rth);
+  printf(b"port\tvlan ids\n\x00" as *const u8 as *const libc::c_char);
+  dump_links(&mut rth, RTEXT_FILTER_BRVLAN);
+  crate::networking::libiproute::libnetlink::xrtnl_dump_filter(
+    &mut rth,
+    Some(print_vlan),
+    std::ptr::null_mut(),
+  );
+  return 0;
+}
+
+unsafe fn vlan_modify(mut argv: *mut *mut libc::c_char, cmd: u16) -> libc::c_int {
+  let keywords = b"dev\x00vid\x00pvid\x00untagged\x00self\x00master\x00\x00";
+  const ARG_dev: libc::c_int = 0;
+  const ARG_vid: libc::c_int = 1;
+  const ARG_pvid: libc::c_int = 2;
+  const ARG_untagged: libc::c_int = 3;
+  const ARG_self: libc::c_int = 4;
+  const ARG_master: libc::c_int = 5;
+  let mut rth: rtnl_handle = std::mem::zeroed();
+  let mut req: link_req = std::mem::zeroed();
+  let size = std::mem::size_of::<link_req>() as libc::c_int;
+  let mut dev: *mut libc::c_char = std::ptr::null_mut();
+  let mut vinfo: bridge_vlan_info = std::mem::zeroed();
+  let mut vid_end: u16 = 0;
+  let mut flags: u16 = 0;
+
+  req.n.nlmsg_len = (16 + std::mem::size_of::<ifinfomsg>()) as u32;
+  req.n.nlmsg_flags = NLM_F_REQUEST;
+  req.n.nlmsg_type = cmd;
+  req.i.ifi_family = AF_BRIDGE;
+
+  while !(*argv).is_null() {
+    let key = crate::libbb::compare_string_array::index_in_strings(
+      keywords.as_ptr() as *const libc::c_char,
+      *argv,
+    );
+    match key {
+      ARG_dev => {
+        argv = crate::networking::libiproute::utils::next_arg(argv);
+        dev = *argv;
+      }
+      ARG_vid => {
+        argv = crate::networking::libiproute::utils::next_arg(argv);
+        /* "VID" or "VID-VID" */
+        let dash = strchr(*argv, b'-' as libc::c_int);
+        if !dash.is_null() {
+          *dash = 0;
+          vid_end = crate::networking::libiproute::utils::get_u16(
+            dash.offset(1),
+            b"vid\x00" as *const u8 as *const libc::c_char,
+          );
+        }
+        vinfo.vid = crate::networking::libiproute::utils::get_u16(
+          *argv,
+          b"vid\x00" as *const u8 as *const libc::c_char,
+        );
+      }
+      ARG_pvid => vinfo.flags |= BRIDGE_VLAN_INFO_PVID,
+      ARG_untagged => vinfo.flags |= BRIDGE_VLAN_INFO_UNTAGGED,
+      ARG_self => flags |= BRIDGE_FLAGS_SELF,
+      ARG_master => flags |= BRIDGE_FLAGS_MASTER,
+      _ => crate::networking::libiproute::utils::invarg_1_to_2(
+        *argv,
+        b"bridge vlan\x00" as *const u8 as *const libc::c_char,
+      ),
+    }
+    argv = argv.offset(1);
+  }
+  if dev.is_null() || vinfo.vid == 0 {
+    crate::libbb::verror_msg::bb_simple_error_msg_and_die(
+      b"vid and dev are required\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  if vinfo.vid >= 4095 || vid_end >= 4095 || (vid_end != 0 && vid_end < vinfo.vid) {
+    crate::libbb::verror_msg::bb_simple_error_msg_and_die(
+      b"invalid VLAN range\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  if vid_end != 0 && vinfo.flags & BRIDGE_VLAN_INFO_PVID != 0 {
+    crate::libbb::verror_msg::bb_simple_error_msg_and_die(
+      b"pvid cannot be set on a range\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+
+  crate::networking::libiproute::libnetlink::xrtnl_open(&mut rth);
+  req.i.ifi_index = xname_to_index(dev);
+
+  let afspec =
+    crate::networking::libiproute::libnetlink::addattr_nest(&mut req.n, size, IFLA_AF_SPEC);
+  if flags != 0 {
+    crate::networking::libiproute::libnetlink::addattr16(
+      &mut req.n,
+      size,
+      IFLA_BRIDGE_FLAGS,
+      flags,
+    );
+  }
+  if vid_end > vinfo.vid {
+    let mut range: bridge_vlan_info = vinfo;
+    range.flags |= BRIDGE_VLAN_INFO_RANGE_BEGIN;
+    crate::networking::libiproute::libnetlink::addattr_l(
+      &mut req.n,
+      size,
+      IFLA_BRIDGE_VLAN_INFO,
+      &mut range as *mut bridge_vlan_info as *mut libc::c_void,
+      std::mem::size_of::<bridge_vlan_info>() as libc::c_int,
+    );
+    range.vid = vid_end;
+    range.flags = vinfo.flags | BRIDGE_VLAN_INFO_RANGE_END;
+    crate::networking::libiproute::libnetlink::addattr_l(
+      &mut req.n,
+      size,
+      IFLA_BRIDGE_VLAN_INFO,
+      &mut range as *mut bridge_vlan_info as *mut libc::c_void,
+      std::mem::size_of::<bridge_vlan_info>() as libc::c_int,
+    );
+  } else {
+    crate::networking::libiproute::libnetlink::addattr_l(
+      &mut req.n,
+      size,
+      IFLA_BRIDGE_VLAN_INFO,
+      &mut vinfo as *mut bridge_vlan_info as *mut libc::c_void,
+      std::mem::size_of::<bridge_vlan_info>() as libc::c_int,
+    );
+  }
+  crate::networking::libiproute::libnetlink::addattr_nest_end(&mut req.n, afspec);
+
+  if crate::networking::libiproute::libnetlink::rtnl_talk(
+    &mut rth,
+    &mut req.n,
+    std::ptr::null_mut(),
+  ) < 0
+  {
+    return 2;
+  }
+  return 0;
+}
+
+unsafe fn do_vlan(argv: *mut *mut libc::c_char) -> libc::c_int {
+  if !(*argv).is_null() {
+    let key = crate::libbb::compare_string_array::index_in_strings(
+      b"add\x00delete\x00del\x00show\x00list\x00lst\x00\x00" as *const u8
+        as *const libc::c_char,
+      *argv,
+    );
+    match key {
+      0 => return vlan_modify(argv.offset(1), RTM_SETLINK),
+      1 | 2 => return vlan_modify(argv.offset(1), RTM_DELLINK),
+      3 | 4 | 5 => return vlan_show(argv.offset(1)),
+      _ => crate::networking::libiproute::utils::invarg_1_to_2(*argv, applet_name),
+    }
+  }
+  return vlan_show(argv);
+}
+
+pub unsafe fn bridge_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  xfunc_error_retval = 2;
+  argv = argv.offset(1);
+  if (*argv).is_null() {
+    crate::libbb::appletlib::bb_show_usage();
+  }
+  let key = crate::libbb::compare_string_array::index_in_strings(
+    b"link\x00fdb\x00vlan\x00\x00" as *const u8 as *const libc::c_char,
+    *argv,
+  );
+  match key {
+    0 => do_link(argv.offset(1)),
+    1 => do_fdb(argv.offset(1)),
+    2 => do_vlan(argv.offset(1)),
+    _ => crate::networking::libiproute::utils::invarg_1_to_2(*argv, applet_name),
+  }
+}
@@ -778,6 +778,54 @@ pub unsafe fn addattr_l(
     & !4u32.wrapping_sub(1i32 as libc::c_uint);
   return 0;
 }
+pub unsafe fn addattr8(
+  mut n: *mut nlmsghdr,
+  mut maxlen: libc::c_int,
+  mut type_0: libc::c_int,
+  mut data: u8,
+) -> libc::c_int {
+  return addattr_l(
+    n,
+    maxlen,
+    type_0,
+    &mut data as *mut u8 as *mut libc::c_void,
+    1i32,
+  );
+}
+pub unsafe fn addattr16(
+  mut n: *mut nlmsghdr,
+  mut maxlen: libc::c_int,
+  mut type_0: libc::c_int,
+  mut data: u16,
+) -> libc::c_int {
+  return addattr_l(
+    n,
+    maxlen,
+    type_0,
+    &mut data as *mut u16 as *mut libc::c_void,
+    2i32,
+  );
+}
+/* Opens a nested attribute; its length is fixed up by addattr_nest_end()
+ * once all the members have been appended. NLA_F_NESTED is set for the
+ * benefit of kernels which validate nesting strictly. */
+pub unsafe fn addattr_nest(
+  mut n: *mut nlmsghdr,
+  mut maxlen: libc::c_int,
+  mut type_0: libc::c_int,
+) -> *mut rtattr {
+  let mut nest: *mut rtattr = (n as *mut libc::c_char)
+    .offset(((*n).nlmsg_len.wrapping_add(3u32) & !3u32) as isize)
+    as *mut rtattr;
+  addattr_l(n, maxlen, type_0 | 0x8000i32, 0 as *mut libc::c_void, 0);
+  return nest;
+}
+pub unsafe fn addattr_nest_end(mut n: *mut nlmsghdr, mut nest: *mut rtattr) -> libc::c_int {
+  let mut tail: *mut libc::c_char =
+    (n as *mut libc::c_char).offset(((*n).nlmsg_len.wrapping_add(3u32) & !3u32) as isize);
+  (*nest).rta_len = (tail as usize - nest as usize) as libc::c_ushort;
+  return (*n).nlmsg_len as libc::c_int;
+}
 pub unsafe fn rta_addattr32(
   mut rta: *mut rtattr,
   mut maxlen: libc::c_int,
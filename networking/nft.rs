@@ -0,0 +1,1872 @@
+/*
+ * Licensed under GPLv2 or later, see file LICENSE in this source tree.
+ *
+ * A compact subset of nftables' "nft": loads rulesets (tables, chains and
+ * rules built from address/port/interface/conntrack-state matches with
+ * accept/drop/jump/masquerade/dnat/snat verdicts) and lists what the kernel
+ * currently has. Talks NETLINK_NETFILTER directly, no libnftnl.
+ */
+//config:config NFT
+//config:	bool "nft (18 kb)"
+//config:	default y
+//config:	select PLATFORM_LINUX
+//config:	help
+//config:	Packet filtering and NAT configuration through nftables.
+//config:	Understands a subset of the nft ruleset syntax.
+//applet:IF_NFT(APPLET(nft, BB_DIR_USR_SBIN, SUID_DROP))
+//kbuild:lib-$(CONFIG_NFT) += nft.o
+//usage:#define nft_trivial_usage
+//usage:       "[-c] -f FILE | COMMAND"
+//usage:#define nft_full_usage "\n\n"
+//usage:       "	-f FILE	Load ruleset from FILE (- for stdin)\n"
+//usage:       "	-c	Check syntax only, don't apply\n"
+//usage:       "\nCOMMAND := list ruleset|tables | list table [FAMILY] NAME\n"
+//usage:       "	| flush ruleset | flush table|chain ...\n"
+//usage:       "	| add|delete table [FAMILY] NAME\n"
+//usage:       "	| add|delete chain [FAMILY] TABLE NAME [{ type T hook H priority P; policy P; }]\n"
+//usage:       "	| add rule [FAMILY] TABLE CHAIN RULE"
+
+use std::io::Read;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+
+const NETLINK_NETFILTER: libc::c_int = 12;
+
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_ACK: u16 = 0x4;
+const NLM_F_DUMP: u16 = 0x300;
+const NLM_F_CREATE: u16 = 0x400;
+const NLM_F_APPEND: u16 = 0x800;
+const NLA_TYPE_MASK: u16 = 0x3fff;
+const NLA_F_NESTED: u16 = 0x8000;
+
+const NFNL_SUBSYS_NFTABLES: u16 = 10;
+const NFNL_MSG_BATCH_BEGIN: u16 = 0x10;
+const NFNL_MSG_BATCH_END: u16 = 0x11;
+
+const NFT_MSG_NEWTABLE: u16 = 0;
+const NFT_MSG_GETTABLE: u16 = 1;
+const NFT_MSG_DELTABLE: u16 = 2;
+const NFT_MSG_NEWCHAIN: u16 = 3;
+const NFT_MSG_GETCHAIN: u16 = 4;
+const NFT_MSG_DELCHAIN: u16 = 5;
+const NFT_MSG_NEWRULE: u16 = 6;
+const NFT_MSG_GETRULE: u16 = 7;
+const NFT_MSG_DELRULE: u16 = 8;
+
+const NFTA_TABLE_NAME: u16 = 1;
+const NFTA_CHAIN_TABLE: u16 = 1;
+const NFTA_CHAIN_NAME: u16 = 3;
+const NFTA_CHAIN_HOOK: u16 = 4;
+const NFTA_CHAIN_POLICY: u16 = 5;
+const NFTA_CHAIN_TYPE: u16 = 7;
+const NFTA_HOOK_HOOKNUM: u16 = 1;
+const NFTA_HOOK_PRIORITY: u16 = 2;
+const NFTA_RULE_TABLE: u16 = 1;
+const NFTA_RULE_CHAIN: u16 = 2;
+const NFTA_RULE_EXPRESSIONS: u16 = 4;
+const NFTA_LIST_ELEM: u16 = 1;
+const NFTA_EXPR_NAME: u16 = 1;
+const NFTA_EXPR_DATA: u16 = 2;
+const NFTA_DATA_VALUE: u16 = 1;
+const NFTA_DATA_VERDICT: u16 = 2;
+const NFTA_VERDICT_CODE: u16 = 1;
+const NFTA_VERDICT_CHAIN: u16 = 2;
+
+const NFT_REG_VERDICT: u32 = 0;
+const NFT_REG_1: u32 = 1;
+const NFT_REG_2: u32 = 2;
+
+const NFT_CMP_EQ: u32 = 0;
+const NFT_CMP_NEQ: u32 = 1;
+
+const NFT_PAYLOAD_NETWORK_HEADER: u32 = 1;
+const NFT_PAYLOAD_TRANSPORT_HEADER: u32 = 2;
+
+const NFT_META_IIFNAME: u32 = 6;
+const NFT_META_OIFNAME: u32 = 7;
+const NFT_META_NFPROTO: u32 = 15;
+const NFT_META_L4PROTO: u32 = 16;
+
+const NFT_CT_STATE: u32 = 0;
+
+const NFT_NAT_SNAT: u32 = 0;
+const NFT_NAT_DNAT: u32 = 1;
+
+const NF_DROP: i32 = 0;
+const NF_ACCEPT: i32 = 1;
+const NFT_CONTINUE: i32 = -1;
+const NFT_JUMP: i32 = -3;
+const NFT_GOTO: i32 = -4;
+const NFT_RETURN: i32 = -5;
+
+const NFPROTO_INET: u8 = 1;
+const NFPROTO_IPV4: u8 = 2;
+const NFPROTO_IPV6: u8 = 10;
+
+static families: [(&str, u8); 6] = [
+  ("ip", NFPROTO_IPV4),
+  ("ip6", NFPROTO_IPV6),
+  ("inet", NFPROTO_INET),
+  ("arp", 3),
+  ("bridge", 7),
+  ("netdev", 5),
+];
+static hooks: [&str; 5] = ["prerouting", "input", "forward", "output", "postrouting"];
+static prio_names: [(&str, i32); 6] = [
+  ("raw", -300),
+  ("mangle", -150),
+  ("dstnat", -100),
+  ("filter", 0),
+  ("security", 50),
+  ("srcnat", 100),
+];
+static ct_states: [(&str, u32); 5] = [
+  ("invalid", 1),
+  ("established", 2),
+  ("related", 4),
+  ("new", 8),
+  ("untracked", 64),
+];
+static l4protos: [(&str, u8); 5] = [
+  ("icmp", 1),
+  ("tcp", 6),
+  ("udp", 17),
+  ("icmpv6", 58),
+  ("sctp", 132),
+];
+
+fn die(msg: &str) -> ! {
+  eprintln!("nft: {}", msg);
+  std::process::exit(1);
+}
+
+fn family_by_name(name: &str) -> Option<u8> {
+  families.iter().find(|f| f.0 == name).map(|f| f.1)
+}
+
+fn family_name(family: u8) -> String {
+  match families.iter().find(|f| f.1 == family) {
+    Some(f) => f.0.to_string(),
+    None => family.to_string(),
+  }
+}
+
+fn l4proto_name(proto: u8) -> String {
+  match l4protos.iter().find(|p| p.1 == proto) {
+    Some(p) => p.0.to_string(),
+    None => proto.to_string(),
+  }
+}
+
+/*
+ * Expressions: the unit both the rule compiler and the lister work in.
+ * They map 1:1 onto the kernel's nft_expr types.
+ */
+#[derive(Clone, Debug)]
+enum Expr {
+  Meta {
+    key: u32,
+    dreg: u32,
+  },
+  Payload {
+    base: u32,
+    offset: u32,
+    len: u32,
+    dreg: u32,
+  },
+  Ct {
+    key: u32,
+    dreg: u32,
+  },
+  Bitwise {
+    sreg: u32,
+    dreg: u32,
+    mask: Vec<u8>,
+    xor: Vec<u8>,
+  },
+  Cmp {
+    sreg: u32,
+    op: u32,
+    data: Vec<u8>,
+  },
+  Immediate {
+    dreg: u32,
+    data: Vec<u8>,
+  },
+  Verdict {
+    code: i32,
+    chain: Option<String>,
+  },
+  Counter,
+  Masq,
+  Nat {
+    nat_type: u32,
+    family: u32,
+    reg_addr: Option<u32>,
+    reg_proto: Option<u32>,
+  },
+  Unknown(String),
+}
+
+#[derive(Debug)]
+struct Chain {
+  family: u8,
+  table: String,
+  name: String,
+  /* type, hook number, priority */
+  base: Option<(String, u32, i32)>,
+  policy: Option<u32>,
+}
+
+#[derive(Debug)]
+enum Cmd {
+  FlushRuleset,
+  AddTable {
+    family: u8,
+    name: String,
+  },
+  DelTable {
+    family: u8,
+    name: String,
+  },
+  FlushTable {
+    family: u8,
+    name: String,
+  },
+  AddChain(Chain),
+  DelChain {
+    family: u8,
+    table: String,
+    name: String,
+  },
+  FlushChain {
+    family: u8,
+    table: String,
+    name: String,
+  },
+  AddRule {
+    family: u8,
+    table: String,
+    chain: String,
+    exprs: Vec<Expr>,
+  },
+}
+
+/*
+ * Netlink message construction
+ */
+struct NlBuf {
+  buf: Vec<u8>,
+  seq: u32,
+}
+
+impl NlBuf {
+  fn new() -> NlBuf {
+    NlBuf {
+      buf: Vec::new(),
+      seq: unsafe { libc::time(std::ptr::null_mut()) } as u32,
+    }
+  }
+
+  fn align(&mut self) {
+    while self.buf.len() % 4 != 0 {
+      self.buf.push(0);
+    }
+  }
+
+  /* Starts nlmsghdr + nfgenmsg, returns offset to pass to end() */
+  fn begin(&mut self, msg_type: u16, flags: u16, family: u8, res_id: u16) -> usize {
+    self.align();
+    let start = self.buf.len();
+    self.seq = self.seq.wrapping_add(1);
+    self.buf.extend_from_slice(&0u32.to_ne_bytes());
+    self.buf.extend_from_slice(&msg_type.to_ne_bytes());
+    self.buf.extend_from_slice(&flags.to_ne_bytes());
+    self.buf.extend_from_slice(&self.seq.to_ne_bytes());
+    self.buf.extend_from_slice(&0u32.to_ne_bytes());
+    self.buf.push(family);
+    self.buf.push(0); /* NFNETLINK_V0 */
+    self.buf.extend_from_slice(&res_id.to_be_bytes());
+    start
+  }
+
+  fn end(&mut self, start: usize) {
+    self.align();
+    let len = (self.buf.len() - start) as u32;
+    self.buf[start..start + 4].copy_from_slice(&len.to_ne_bytes());
+  }
+
+  fn put(&mut self, attr: u16, data: &[u8]) {
+    self.align();
+    self
+      .buf
+      .extend_from_slice(&((4 + data.len()) as u16).to_ne_bytes());
+    self.buf.extend_from_slice(&attr.to_ne_bytes());
+    self.buf.extend_from_slice(data);
+    self.align();
+  }
+
+  fn put_str(&mut self, attr: u16, s: &str) {
+    let mut data = s.as_bytes().to_vec();
+    data.push(0);
+    self.put(attr, &data);
+  }
+
+  fn put_be32(&mut self, attr: u16, v: u32) {
+    self.put(attr, &v.to_be_bytes());
+  }
+
+  fn nest(&mut self, attr: u16) -> usize {
+    self.align();
+    let start = self.buf.len();
+    self.buf.extend_from_slice(&0u16.to_ne_bytes());
+    self
+      .buf
+      .extend_from_slice(&(attr | NLA_F_NESTED).to_ne_bytes());
+    start
+  }
+
+  fn nest_end(&mut self, start: usize) {
+    let len = (self.buf.len() - start) as u16;
+    self.buf[start..start + 2].copy_from_slice(&len.to_ne_bytes());
+  }
+
+  fn put_data_value(&mut self, attr: u16, value: &[u8]) {
+    let n = self.nest(attr);
+    self.put(NFTA_DATA_VALUE, value);
+    self.nest_end(n);
+  }
+
+  fn put_expr(&mut self, e: &Expr) {
+    let elem = self.nest(NFTA_LIST_ELEM);
+    let name = match e {
+      Expr::Meta { .. } => "meta",
+      Expr::Payload { .. } => "payload",
+      Expr::Ct { .. } => "ct",
+      Expr::Bitwise { .. } => "bitwise",
+      Expr::Cmp { .. } => "cmp",
+      Expr::Immediate { .. } | Expr::Verdict { .. } => "immediate",
+      Expr::Counter => "counter",
+      Expr::Masq => "masq",
+      Expr::Nat { .. } => "nat",
+      Expr::Unknown(name) => name,
+    };
+    self.put_str(NFTA_EXPR_NAME, name);
+    let data = self.nest(NFTA_EXPR_DATA);
+    match e {
+      Expr::Meta { key, dreg } | Expr::Ct { key, dreg } => {
+        /* NFTA_META_DREG/NFTA_CT_DREG = 1, NFTA_*_KEY = 2 */
+        self.put_be32(1, *dreg);
+        self.put_be32(2, *key);
+      }
+      Expr::Payload {
+        base,
+        offset,
+        len,
+        dreg,
+      } => {
+        self.put_be32(1, *dreg);
+        self.put_be32(2, *base);
+        self.put_be32(3, *offset);
+        self.put_be32(4, *len);
+      }
+      Expr::Bitwise {
+        sreg,
+        dreg,
+        mask,
+        xor,
+      } => {
+        self.put_be32(1, *sreg);
+        self.put_be32(2, *dreg);
+        self.put_be32(3, mask.len() as u32);
+        self.put_data_value(4, mask);
+        self.put_data_value(5, xor);
+      }
+      Expr::Cmp { sreg, op, data } => {
+        self.put_be32(1, *sreg);
+        self.put_be32(2, *op);
+        self.put_data_value(3, data);
+      }
+      Expr::Immediate { dreg, data } => {
+        self.put_be32(1, *dreg);
+        self.put_data_value(2, data);
+      }
+      Expr::Verdict { code, chain } => {
+        self.put_be32(1, NFT_REG_VERDICT);
+        let d = self.nest(2);
+        let v = self.nest(NFTA_DATA_VERDICT);
+        self.put_be32(NFTA_VERDICT_CODE, *code as u32);
+        if let Some(chain) = chain {
+          self.put_str(NFTA_VERDICT_CHAIN, chain);
+        }
+        self.nest_end(v);
+        self.nest_end(d);
+      }
+      Expr::Nat {
+        nat_type,
+        family,
+        reg_addr,
+        reg_proto,
+      } => {
+        self.put_be32(1, *nat_type);
+        self.put_be32(2, *family);
+        if let Some(r) = reg_addr {
+          self.put_be32(3, *r);
+          self.put_be32(4, *r);
+        }
+        if let Some(r) = reg_proto {
+          self.put_be32(5, *r);
+          self.put_be32(6, *r);
+        }
+      }
+      Expr::Counter | Expr::Masq | Expr::Unknown(_) => {}
+    }
+    self.nest_end(data);
+    self.nest_end(elem);
+  }
+}
+
+fn nft_type(msg: u16) -> u16 {
+  (NFNL_SUBSYS_NFTABLES << 8) | msg
+}
+
+/* Appends one command to the batch; each command is exactly one message */
+fn encode_cmd(nl: &mut NlBuf, cmd: &Cmd) {
+  let create = NLM_F_REQUEST | NLM_F_CREATE | NLM_F_ACK;
+  let del = NLM_F_REQUEST | NLM_F_ACK;
+  match cmd {
+    Cmd::FlushRuleset => {
+      /* DELTABLE without a name and family removes everything */
+      let m = nl.begin(nft_type(NFT_MSG_DELTABLE), del, 0, 0);
+      nl.end(m);
+    }
+    Cmd::AddTable { family, name } => {
+      let m = nl.begin(nft_type(NFT_MSG_NEWTABLE), create, *family, 0);
+      nl.put_str(NFTA_TABLE_NAME, name);
+      nl.end(m);
+    }
+    Cmd::DelTable { family, name } => {
+      let m = nl.begin(nft_type(NFT_MSG_DELTABLE), del, *family, 0);
+      nl.put_str(NFTA_TABLE_NAME, name);
+      nl.end(m);
+    }
+    Cmd::FlushTable { family, name } => {
+      let m = nl.begin(nft_type(NFT_MSG_DELRULE), del, *family, 0);
+      nl.put_str(NFTA_RULE_TABLE, name);
+      nl.end(m);
+    }
+    Cmd::AddChain(c) => {
+      let m = nl.begin(nft_type(NFT_MSG_NEWCHAIN), create, c.family, 0);
+      nl.put_str(NFTA_CHAIN_TABLE, &c.table);
+      nl.put_str(NFTA_CHAIN_NAME, &c.name);
+      if let Some((ty, hooknum, prio)) = &c.base {
+        let h = nl.nest(NFTA_CHAIN_HOOK);
+        nl.put_be32(NFTA_HOOK_HOOKNUM, *hooknum);
+        nl.put_be32(NFTA_HOOK_PRIORITY, *prio as u32);
+        nl.nest_end(h);
+        nl.put_str(NFTA_CHAIN_TYPE, ty);
+      }
+      if let Some(policy) = c.policy {
+        nl.put_be32(NFTA_CHAIN_POLICY, policy);
+      }
+      nl.end(m);
+    }
+    Cmd::DelChain {
+      family,
+      table,
+      name,
+    } => {
+      let m = nl.begin(nft_type(NFT_MSG_DELCHAIN), del, *family, 0);
+      nl.put_str(NFTA_CHAIN_TABLE, table);
+      nl.put_str(NFTA_CHAIN_NAME, name);
+      nl.end(m);
+    }
+    Cmd::FlushChain {
+      family,
+      table,
+      name,
+    } => {
+      let m = nl.begin(nft_type(NFT_MSG_DELRULE), del, *family, 0);
+      nl.put_str(NFTA_RULE_TABLE, table);
+      nl.put_str(NFTA_RULE_CHAIN, name);
+      nl.end(m);
+    }
+    Cmd::AddRule {
+      family,
+      table,
+      chain,
+      exprs,
+    } => {
+      let m = nl.begin(nft_type(NFT_MSG_NEWRULE), create | NLM_F_APPEND, *family, 0);
+      nl.put_str(NFTA_RULE_TABLE, table);
+      nl.put_str(NFTA_RULE_CHAIN, chain);
+      let list = nl.nest(NFTA_RULE_EXPRESSIONS);
+      for e in exprs {
+        nl.put_expr(e);
+      }
+      nl.nest_end(list);
+      nl.end(m);
+    }
+  }
+}
+
+/*
+ * Netlink I/O
+ */
+fn nl_open() -> libc::c_int {
+  unsafe {
+    let fd = libc::socket(libc::AF_NETLINK, libc::SOCK_RAW, NETLINK_NETFILTER);
+    if fd < 0 {
+      die(&format!("socket: {}", std::io::Error::last_os_error()));
+    }
+    fd
+  }
+}
+
+fn nl_send(fd: libc::c_int, buf: &[u8]) {
+  let mut sa: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+  sa.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+  let r = unsafe {
+    libc::sendto(
+      fd,
+      buf.as_ptr() as *const libc::c_void,
+      buf.len(),
+      0,
+      &sa as *const libc::sockaddr_nl as *const libc::sockaddr,
+      std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+    )
+  };
+  if r < 0 {
+    die(&format!(
+      "can't send to netlink: {}",
+      std::io::Error::last_os_error()
+    ));
+  }
+}
+
+/* Returns None on timeout */
+fn nl_recv(fd: libc::c_int, buf: &mut [u8], timeout_ms: libc::c_int) -> Option<usize> {
+  let mut pfd = libc::pollfd {
+    fd,
+    events: libc::POLLIN,
+    revents: 0,
+  };
+  if unsafe { libc::poll(&mut pfd, 1, timeout_ms) } <= 0 {
+    return None;
+  }
+  let r = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+  if r < 0 {
+    die(&format!("netlink: {}", std::io::Error::last_os_error()));
+  }
+  Some(r as usize)
+}
+
+/* Walks the nlmsghdr's in buf: (type, payload after nlmsghdr) */
+fn nl_messages(buf: &[u8]) -> Vec<(u16, &[u8])> {
+  let mut v = Vec::new();
+  let mut off = 0;
+  while off + 16 <= buf.len() {
+    let len = u32::from_ne_bytes([buf[off], buf[off + 1], buf[off + 2], buf[off + 3]]) as usize;
+    if len < 16 || off + len > buf.len() {
+      break;
+    }
+    let ty = u16::from_ne_bytes([buf[off + 4], buf[off + 5]]);
+    v.push((ty, &buf[off + 16..off + len]));
+    off += (len + 3) & !3;
+  }
+  v
+}
+
+fn nl_error(payload: &[u8]) -> i32 {
+  if payload.len() < 4 {
+    return 0;
+  }
+  i32::from_ne_bytes([payload[0], payload[1], payload[2], payload[3]])
+}
+
+fn attrs(mut data: &[u8]) -> Vec<(u16, &[u8])> {
+  let mut v = Vec::new();
+  while data.len() >= 4 {
+    let len = u16::from_ne_bytes([data[0], data[1]]) as usize;
+    let ty = u16::from_ne_bytes([data[2], data[3]]) & NLA_TYPE_MASK;
+    if len < 4 || len > data.len() {
+      break;
+    }
+    v.push((ty, &data[4..len]));
+    let step = std::cmp::min((len + 3) & !3, data.len());
+    data = &data[step..];
+  }
+  v
+}
+
+fn attr<'a>(list: &[(u16, &'a [u8])], ty: u16) -> Option<&'a [u8]> {
+  list.iter().find(|a| a.0 == ty).map(|a| a.1)
+}
+
+fn attr_str(list: &[(u16, &[u8])], ty: u16) -> String {
+  match attr(list, ty) {
+    Some(d) => {
+      let end = d.iter().position(|&c| c == 0).unwrap_or(d.len());
+      String::from_utf8_lossy(&d[..end]).into_owned()
+    }
+    None => String::new(),
+  }
+}
+
+fn be32(d: &[u8]) -> u32 {
+  if d.len() < 4 {
+    return 0;
+  }
+  u32::from_be_bytes([d[0], d[1], d[2], d[3]])
+}
+
+fn attr_be32(list: &[(u16, &[u8])], ty: u16) -> Option<u32> {
+  attr(list, ty).map(be32)
+}
+
+fn data_value(list: &[(u16, &[u8])], ty: u16) -> Vec<u8> {
+  match attr(list, ty) {
+    Some(nested) => attr(&attrs(nested), NFTA_DATA_VALUE)
+      .map(|v| v.to_vec())
+      .unwrap_or_default(),
+    None => Vec::new(),
+  }
+}
+
+/* Sends a whole batch and waits for every ack; dies on the first error */
+fn nl_commit(cmds: &[Cmd]) {
+  let mut nl = NlBuf::new();
+  let fd = nl_open();
+
+  let b = nl.begin(NFNL_MSG_BATCH_BEGIN, NLM_F_REQUEST, 0, NFNL_SUBSYS_NFTABLES);
+  nl.end(b);
+  for cmd in cmds {
+    encode_cmd(&mut nl, cmd);
+  }
+  let e = nl.begin(NFNL_MSG_BATCH_END, NLM_F_REQUEST, 0, NFNL_SUBSYS_NFTABLES);
+  nl.end(e);
+  nl_send(fd, &nl.buf);
+
+  let mut buf = vec![0u8; 64 * 1024];
+  let mut seen = 0;
+  let mut first_err = 0;
+  /* Block for the first reply, then drain whatever else arrives */
+  let mut timeout = 5000;
+  while seen < cmds.len() {
+    let n = match nl_recv(fd, &mut buf, timeout) {
+      Some(n) => n,
+      None => break,
+    };
+    timeout = 100;
+    for (ty, payload) in nl_messages(&buf[..n]) {
+      if ty == NLMSG_ERROR {
+        seen += 1;
+        let err = nl_error(payload);
+        if err != 0 && first_err == 0 {
+          first_err = err;
+        }
+      }
+    }
+  }
+  unsafe { libc::close(fd) };
+  if first_err != 0 {
+    die(&format!(
+      "netlink: {}",
+      std::io::Error::from_raw_os_error(-first_err)
+    ));
+  }
+}
+
+fn nl_dump(fd: libc::c_int, msg: u16, family: u8) -> Vec<(u8, Vec<(u16, Vec<u8>)>)> {
+  let mut nl = NlBuf::new();
+  let m = nl.begin(nft_type(msg), NLM_F_REQUEST | NLM_F_DUMP, family, 0);
+  nl.end(m);
+  nl_send(fd, &nl.buf);
+
+  let mut out = Vec::new();
+  let mut buf = vec![0u8; 64 * 1024];
+  loop {
+    let n = match nl_recv(fd, &mut buf, 5000) {
+      Some(n) => n,
+      None => die("netlink: timed out"),
+    };
+    for (ty, payload) in nl_messages(&buf[..n]) {
+      if ty == NLMSG_DONE {
+        return out;
+      }
+      if ty == NLMSG_ERROR {
+        let err = nl_error(payload);
+        if err == 0 {
+          continue;
+        }
+        die(&format!(
+          "netlink: {}",
+          std::io::Error::from_raw_os_error(-err)
+        ));
+      }
+      if payload.len() < 4 {
+        continue;
+      }
+      let list = attrs(&payload[4..])
+        .into_iter()
+        .map(|(t, d)| (t, d.to_vec()))
+        .collect();
+      out.push((payload[0], list));
+    }
+  }
+}
+
+/*
+ * Ruleset parser
+ */
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+  Word(String),
+  Open,
+  Close,
+  Sep,
+}
+
+fn tokenize(src: &str) -> Vec<(Tok, usize)> {
+  let mut toks = Vec::new();
+  let mut line = 1;
+  let mut chars = src.chars().peekable();
+  while let Some(&c) = chars.peek() {
+    match c {
+      '\n' | ';' => {
+        toks.push((Tok::Sep, line));
+        if c == '\n' {
+          line += 1;
+        }
+        chars.next();
+      }
+      ' ' | '\t' | '\r' => {
+        chars.next();
+      }
+      '\\' => {
+        /* line continuation */
+        chars.next();
+        if chars.peek() == Some(&'\n') {
+          chars.next();
+          line += 1;
+        }
+      }
+      '#' => {
+        while let Some(&c) = chars.peek() {
+          if c == '\n' {
+            break;
+          }
+          chars.next();
+        }
+      }
+      '{' => {
+        toks.push((Tok::Open, line));
+        chars.next();
+      }
+      '}' => {
+        toks.push((Tok::Close, line));
+        chars.next();
+      }
+      '"' => {
+        chars.next();
+        let mut w = String::new();
+        while let Some(c) = chars.next() {
+          if c == '"' {
+            break;
+          }
+          w.push(c);
+        }
+        toks.push((Tok::Word(w), line));
+      }
+      _ => {
+        let mut w = String::new();
+        while let Some(&c) = chars.peek() {
+          if c.is_whitespace() || c == ';' || c == '{' || c == '}' || c == '#' {
+            break;
+          }
+          w.push(c);
+          chars.next();
+        }
+        toks.push((Tok::Word(w), line));
+      }
+    }
+  }
+  toks
+}
+
+struct Parser<'a> {
+  toks: Vec<(Tok, usize)>,
+  pos: usize,
+  file: &'a str,
+  cmds: Vec<Cmd>,
+}
+
+impl<'a> Parser<'a> {
+  fn error(&self, msg: &str) -> ! {
+    let line = match self.toks.get(self.pos.saturating_sub(1)) {
+      Some(t) => t.1,
+      None => self.toks.last().map(|t| t.1).unwrap_or(1),
+    };
+    die(&format!("{}:{}: {}", self.file, line, msg));
+  }
+
+  fn peek(&self) -> Option<&Tok> {
+    self.toks.get(self.pos).map(|t| &t.0)
+  }
+
+  fn next(&mut self) -> Option<Tok> {
+    let t = self.toks.get(self.pos).map(|t| t.0.clone());
+    self.pos += 1;
+    t
+  }
+
+  fn skip_seps(&mut self) {
+    while self.peek() == Some(&Tok::Sep) {
+      self.pos += 1;
+    }
+  }
+
+  fn word(&mut self, what: &str) -> String {
+    match self.next() {
+      Some(Tok::Word(w)) => w,
+      _ => self.error(&format!("{} expected", what)),
+    }
+  }
+
+  fn expect(&mut self, tok: Tok) {
+    if self.next() != Some(tok) {
+      self.error("syntax error");
+    }
+  }
+
+  /* Words up to the end of the statement (not consuming '{' or '}') */
+  fn statement(&mut self) -> Vec<String> {
+    let mut v = Vec::new();
+    while let Some(Tok::Word(w)) = self.peek() {
+      v.push(w.clone());
+      self.pos += 1;
+    }
+    v
+  }
+
+  /* "[FAMILY] NAME" */
+  fn family(&mut self) -> u8 {
+    if let Some(Tok::Word(w)) = self.peek() {
+      if let Some(f) = family_by_name(w) {
+        if let Some((Tok::Word(_), _)) = self.toks.get(self.pos + 1) {
+          self.pos += 1;
+          return f;
+        }
+      }
+    }
+    NFPROTO_IPV4
+  }
+
+  fn parse(&mut self) {
+    loop {
+      self.skip_seps();
+      let w = match self.next() {
+        None => break,
+        Some(Tok::Word(w)) => w,
+        Some(_) => self.error("syntax error"),
+      };
+      match w.as_str() {
+        "table" => self.table_block(),
+        "flush" => self.flush(),
+        "add" | "create" => self.add(),
+        "delete" => self.delete(),
+        _ => self.error(&format!("unknown command '{}'", w)),
+      }
+    }
+  }
+
+  fn table_block(&mut self) {
+    let family = self.family();
+    let name = self.word("table name");
+    self.table_body(family, name);
+  }
+
+  /* "{ chain ... }" after "table [FAMILY] NAME" */
+  fn table_body(&mut self, family: u8, name: String) {
+    self.cmds.push(Cmd::AddTable {
+      family,
+      name: name.clone(),
+    });
+    self.expect(Tok::Open);
+    /* Rules go after every chain, so jumps to chains defined later resolve */
+    let mut rules = Vec::new();
+    loop {
+      self.skip_seps();
+      match self.next() {
+        Some(Tok::Close) => break,
+        Some(Tok::Word(ref w)) if w == "chain" => {
+          let chain = self.word("chain name");
+          self.expect(Tok::Open);
+          rules.append(&mut self.chain_body(family, &name, chain));
+        }
+        _ => self.error("'chain' or '}' expected"),
+      }
+    }
+    self.cmds.append(&mut rules);
+  }
+
+  /* Everything between "chain NAME {" and "}"; queues the chain, returns its rules */
+  fn chain_body(&mut self, family: u8, table: &str, name: String) -> Vec<Cmd> {
+    let mut chain = Chain {
+      family,
+      table: table.to_string(),
+      name: name.clone(),
+      base: None,
+      policy: None,
+    };
+    let mut rules = Vec::new();
+    loop {
+      self.skip_seps();
+      if self.peek() == Some(&Tok::Close) {
+        self.pos += 1;
+        break;
+      }
+      let words = self.statement();
+      if words.is_empty() {
+        self.error("syntax error");
+      }
+      match words[0].as_str() {
+        "type" => chain.base = Some(self.chain_type(&words)),
+        "policy" => chain.policy = Some(self.policy(&words)),
+        _ => match compile_rule(family, &words) {
+          Ok(exprs) => rules.push(Cmd::AddRule {
+            family,
+            table: table.to_string(),
+            chain: name.clone(),
+            exprs,
+          }),
+          Err(e) => self.error(&e),
+        },
+      }
+    }
+    self.cmds.push(Cmd::AddChain(chain));
+    rules
+  }
+
+  /* type filter hook input priority 0 */
+  fn chain_type(&self, w: &[String]) -> (String, u32, i32) {
+    if w.len() != 6 || w[2] != "hook" || w[4] != "priority" {
+      self.error("expected 'type TYPE hook HOOK priority PRIO'");
+    }
+    let hook = match hooks.iter().position(|&h| h == w[3]) {
+      Some(h) => h as u32,
+      /* netdev's only hook */
+      None if w[3] == "ingress" => 0,
+      None => self.error(&format!("unknown hook '{}'", w[3])),
+    };
+    let prio = match prio_names.iter().find(|p| p.0 == w[5]) {
+      Some(p) => p.1,
+      None => match w[5].parse::<i32>() {
+        Ok(p) => p,
+        Err(_) => self.error(&format!("bad priority '{}'", w[5])),
+      },
+    };
+    (w[1].clone(), hook, prio)
+  }
+
+  fn policy(&self, w: &[String]) -> u32 {
+    match w.get(1).map(|s| s.as_str()) {
+      Some("accept") => NF_ACCEPT as u32,
+      Some("drop") => NF_DROP as u32,
+      _ => self.error("policy must be 'accept' or 'drop'"),
+    }
+  }
+
+  fn flush(&mut self) {
+    let what = self.word("'ruleset', 'table' or 'chain'");
+    match what.as_str() {
+      "ruleset" => self.cmds.push(Cmd::FlushRuleset),
+      "table" => {
+        let family = self.family();
+        let name = self.word("table name");
+        self.cmds.push(Cmd::FlushTable { family, name });
+      }
+      "chain" => {
+        let family = self.family();
+        let table = self.word("table name");
+        let name = self.word("chain name");
+        self.cmds.push(Cmd::FlushChain {
+          family,
+          table,
+          name,
+        });
+      }
+      _ => self.error(&format!("can't flush '{}'", what)),
+    }
+  }
+
+  fn add(&mut self) {
+    let what = self.word("'table', 'chain' or 'rule'");
+    let family = self.family();
+    let table = self.word("table name");
+    match what.as_str() {
+      "table" => {
+        if self.peek() == Some(&Tok::Open) {
+          self.table_body(family, table);
+        } else {
+          self.cmds.push(Cmd::AddTable {
+            family,
+            name: table,
+          });
+        }
+      }
+      "chain" => {
+        let name = self.word("chain name");
+        if self.peek() == Some(&Tok::Open) {
+          self.pos += 1;
+          let mut rules = self.chain_body(family, &table, name);
+          self.cmds.append(&mut rules);
+        } else {
+          self.cmds.push(Cmd::AddChain(Chain {
+            family,
+            table,
+            name,
+            base: None,
+            policy: None,
+          }));
+        }
+      }
+      "rule" => {
+        let chain = self.word("chain name");
+        let words = self.statement();
+        match compile_rule(family, &words) {
+          Ok(exprs) => self.cmds.push(Cmd::AddRule {
+            family,
+            table,
+            chain,
+            exprs,
+          }),
+          Err(e) => self.error(&e),
+        }
+      }
+      _ => self.error(&format!("can't add '{}'", what)),
+    }
+  }
+
+  fn delete(&mut self) {
+    let what = self.word("'table' or 'chain'");
+    let family = self.family();
+    let table = self.word("table name");
+    match what.as_str() {
+      "table" => self.cmds.push(Cmd::DelTable {
+        family,
+        name: table,
+      }),
+      "chain" => {
+        let name = self.word("chain name");
+        self.cmds.push(Cmd::DelChain {
+          family,
+          table,
+          name,
+        });
+      }
+      _ => self.error(&format!("can't delete '{}'", what)),
+    }
+  }
+}
+
+/*
+ * Rule compiler: turns "tcp dport 22 accept" into expressions
+ */
+fn parse_port(s: &str) -> Result<u16, String> {
+  match s {
+    "ssh" => Ok(22),
+    "http" => Ok(80),
+    "https" => Ok(443),
+    "domain" => Ok(53),
+    _ => s.parse::<u16>().map_err(|_| format!("bad port '{}'", s)),
+  }
+}
+
+/* ADDR[/PREFIX] -> (address bytes, mask bytes if a prefix was given) */
+fn parse_addr(s: &str, v6: bool) -> Result<(Vec<u8>, Option<Vec<u8>>), String> {
+  let (addr, prefix) = match s.find('/') {
+    Some(i) => (&s[..i], Some(&s[i + 1..])),
+    None => (s, None),
+  };
+  let bytes = if v6 {
+    addr
+      .parse::<Ipv6Addr>()
+      .map(|a| a.octets().to_vec())
+      .map_err(|_| format!("bad address '{}'", s))?
+  } else {
+    addr
+      .parse::<Ipv4Addr>()
+      .map(|a| a.octets().to_vec())
+      .map_err(|_| format!("bad address '{}'", s))?
+  };
+  let prefix = match prefix {
+    None => return Ok((bytes, None)),
+    Some(p) => p
+      .parse::<usize>()
+      .ok()
+      .filter(|&p| p <= bytes.len() * 8)
+      .ok_or_else(|| format!("bad prefix '{}'", s))?,
+  };
+  let mut mask = vec![0u8; bytes.len()];
+  for i in 0..prefix {
+    mask[i / 8] |= 0x80 >> (i % 8);
+  }
+  let masked = bytes.iter().zip(mask.iter()).map(|(a, m)| a & m).collect();
+  Ok((masked, Some(mask)))
+}
+
+fn parse_nat_target(s: &str, v6: bool) -> Result<(Option<Vec<u8>>, Option<u16>), String> {
+  /* ADDR, ADDR:PORT or :PORT; IPv6 addresses take a port as [ADDR]:PORT */
+  let (addr, port) = if let Some(rest) = s.strip_prefix('[') {
+    let i = rest.find(']').ok_or_else(|| format!("bad address '{}'", s))?;
+    match &rest[i + 1..] {
+      "" => (&rest[..i], None),
+      p if p.starts_with(':') => (&rest[..i], Some(parse_port(&p[1..])?)),
+      _ => return Err(format!("bad address '{}'", s)),
+    }
+  } else if v6 && !s.starts_with(':') {
+    (s, None)
+  } else {
+    match s.rfind(':') {
+      Some(i) => (&s[..i], Some(parse_port(&s[i + 1..])?)),
+      None => (s, None),
+    }
+  };
+  let addr = if addr.is_empty() {
+    None
+  } else if v6 {
+    Some(
+      addr
+        .parse::<Ipv6Addr>()
+        .map_err(|_| format!("bad address '{}'", s))?
+        .octets()
+        .to_vec(),
+    )
+  } else {
+    Some(
+      addr
+        .parse::<Ipv4Addr>()
+        .map_err(|_| format!("bad address '{}'", s))?
+        .octets()
+        .to_vec(),
+    )
+  };
+  Ok((addr, port))
+}
+
+fn compile_rule(family: u8, words: &[String]) -> Result<Vec<Expr>, String> {
+  let mut exprs = Vec::new();
+  let mut i = 0;
+  let mut l4proto: Option<u8> = None;
+  let mut nfproto: Option<u8> = None;
+
+  /* Pulls the next word, accepting an optional "!=" before values */
+  fn value<'b>(words: &'b [String], i: &mut usize, op: &mut u32) -> Result<&'b str, String> {
+    *i += 1;
+    if words.get(*i).map(|s| s.as_str()) == Some("!=") {
+      *op = NFT_CMP_NEQ;
+      *i += 1;
+    }
+    match words.get(*i) {
+      Some(w) => Ok(w.as_str()),
+      None => Err(format!("value expected after '{}'", words[*i - 1])),
+    }
+  }
+
+  while i < words.len() {
+    let w = words[i].as_str();
+    let mut op = NFT_CMP_EQ;
+    match w {
+      "ip" | "ip6" => {
+        let v6 = w == "ip6";
+        let proto = if v6 { NFPROTO_IPV6 } else { NFPROTO_IPV4 };
+        if family == NFPROTO_IPV4 && v6 || family == NFPROTO_IPV6 && !v6 {
+          return Err(format!("'{}' match in wrong family table", w));
+        }
+        if family != proto && nfproto != Some(proto) {
+          exprs.push(Expr::Meta {
+            key: NFT_META_NFPROTO,
+            dreg: NFT_REG_1,
+          });
+          exprs.push(Expr::Cmp {
+            sreg: NFT_REG_1,
+            op: NFT_CMP_EQ,
+            data: vec![proto],
+          });
+          nfproto = Some(proto);
+        }
+        i += 1;
+        let field = words.get(i).map(|s| s.as_str()).unwrap_or("");
+        match field {
+          "saddr" | "daddr" => {
+            let (addr, mask) = parse_addr(value(words, &mut i, &mut op)?, v6)?;
+            let offset = match (v6, field) {
+              (false, "saddr") => 12,
+              (false, _) => 16,
+              (true, "saddr") => 8,
+              (true, _) => 24,
+            };
+            exprs.push(Expr::Payload {
+              base: NFT_PAYLOAD_NETWORK_HEADER,
+              offset,
+              len: addr.len() as u32,
+              dreg: NFT_REG_1,
+            });
+            if let Some(mask) = mask {
+              exprs.push(Expr::Bitwise {
+                sreg: NFT_REG_1,
+                dreg: NFT_REG_1,
+                xor: vec![0; mask.len()],
+                mask,
+              });
+            }
+            exprs.push(Expr::Cmp {
+              sreg: NFT_REG_1,
+              op,
+              data: addr,
+            });
+          }
+          "protocol" | "nexthdr" => {
+            let v = value(words, &mut i, &mut op)?;
+            let p = match l4protos.iter().find(|p| p.0 == v) {
+              Some(p) => p.1,
+              None => v
+                .parse::<u8>()
+                .map_err(|_| format!("bad protocol '{}'", v))?,
+            };
+            exprs.push(Expr::Meta {
+              key: NFT_META_L4PROTO,
+              dreg: NFT_REG_1,
+            });
+            exprs.push(Expr::Cmp {
+              sreg: NFT_REG_1,
+              op,
+              data: vec![p],
+            });
+            if op == NFT_CMP_EQ {
+              l4proto = Some(p);
+            }
+          }
+          _ => return Err(format!("unknown {} field '{}'", w, field)),
+        }
+      }
+      "tcp" | "udp" | "sctp" => {
+        let proto = l4protos.iter().find(|p| p.0 == w).unwrap().1;
+        if l4proto != Some(proto) {
+          exprs.push(Expr::Meta {
+            key: NFT_META_L4PROTO,
+            dreg: NFT_REG_1,
+          });
+          exprs.push(Expr::Cmp {
+            sreg: NFT_REG_1,
+            op: NFT_CMP_EQ,
+            data: vec![proto],
+          });
+          l4proto = Some(proto);
+        }
+        i += 1;
+        let field = words.get(i).map(|s| s.as_str()).unwrap_or("");
+        let offset = match field {
+          "sport" => 0,
+          "dport" => 2,
+          _ => return Err(format!("unknown {} field '{}'", w, field)),
+        };
+        let port = parse_port(value(words, &mut i, &mut op)?)?;
+        exprs.push(Expr::Payload {
+          base: NFT_PAYLOAD_TRANSPORT_HEADER,
+          offset,
+          len: 2,
+          dreg: NFT_REG_1,
+        });
+        exprs.push(Expr::Cmp {
+          sreg: NFT_REG_1,
+          op,
+          data: port.to_be_bytes().to_vec(),
+        });
+      }
+      "iifname" | "oifname" | "iif" | "oif" => {
+        let name = value(words, &mut i, &mut op)?;
+        if name.len() >= 16 {
+          return Err(format!("interface name '{}' too long", name));
+        }
+        let mut data = name.as_bytes().to_vec();
+        data.resize(16, 0);
+        exprs.push(Expr::Meta {
+          key: if w.starts_with('i') {
+            NFT_META_IIFNAME
+          } else {
+            NFT_META_OIFNAME
+          },
+          dreg: NFT_REG_1,
+        });
+        exprs.push(Expr::Cmp {
+          sreg: NFT_REG_1,
+          op,
+          data,
+        });
+      }
+      "ct" => {
+        i += 1;
+        if words.get(i).map(|s| s.as_str()) != Some("state") {
+          return Err("only 'ct state' is supported".to_string());
+        }
+        let mut mask = 0u32;
+        for s in value(words, &mut i, &mut op)?.split(',') {
+          match ct_states.iter().find(|c| c.0 == s) {
+            Some(c) => mask |= c.1,
+            None => return Err(format!("unknown ct state '{}'", s)),
+          }
+        }
+        exprs.push(Expr::Ct {
+          key: NFT_CT_STATE,
+          dreg: NFT_REG_1,
+        });
+        exprs.push(Expr::Bitwise {
+          sreg: NFT_REG_1,
+          dreg: NFT_REG_1,
+          mask: mask.to_ne_bytes().to_vec(),
+          xor: vec![0; 4],
+        });
+        /* "ct state != x" means none of the bits is set */
+        exprs.push(Expr::Cmp {
+          sreg: NFT_REG_1,
+          op: if op == NFT_CMP_EQ {
+            NFT_CMP_NEQ
+          } else {
+            NFT_CMP_EQ
+          },
+          data: vec![0; 4],
+        });
+      }
+      "counter" => exprs.push(Expr::Counter),
+      "accept" | "drop" | "return" | "continue" => exprs.push(Expr::Verdict {
+        code: match w {
+          "accept" => NF_ACCEPT,
+          "drop" => NF_DROP,
+          "return" => NFT_RETURN,
+          _ => NFT_CONTINUE,
+        },
+        chain: None,
+      }),
+      "jump" | "goto" => {
+        i += 1;
+        let target = words
+          .get(i)
+          .ok_or_else(|| format!("chain expected after '{}'", w))?;
+        exprs.push(Expr::Verdict {
+          code: if w == "jump" { NFT_JUMP } else { NFT_GOTO },
+          chain: Some(target.clone()),
+        });
+      }
+      "masquerade" => exprs.push(Expr::Masq),
+      "dnat" | "snat" => {
+        i += 1;
+        /* inet tables name the NAT family: "dnat ip6 to ..." */
+        let mut nat_family = match words.get(i).map(|s| s.as_str()) {
+          Some("ip") => {
+            i += 1;
+            Some(NFPROTO_IPV4)
+          }
+          Some("ip6") => {
+            i += 1;
+            Some(NFPROTO_IPV6)
+          }
+          _ => None,
+        };
+        if words.get(i).map(|s| s.as_str()) == Some("to") {
+          i += 1;
+        }
+        let target = words
+          .get(i)
+          .ok_or_else(|| format!("address expected after '{}'", w))?;
+        if family == NFPROTO_IPV4 || family == NFPROTO_IPV6 {
+          if nat_family.is_some() && nat_family != Some(family) {
+            return Err(format!("'{}' family doesn't match the table", w));
+          }
+          nat_family = Some(family);
+        }
+        let nat_family = match nat_family.or(nfproto) {
+          Some(f) => f,
+          None if target.starts_with('[') || target.matches(':').count() > 1 => NFPROTO_IPV6,
+          None => NFPROTO_IPV4,
+        };
+        let (addr, port) = parse_nat_target(target, nat_family == NFPROTO_IPV6)?;
+        if let Some(addr) = &addr {
+          exprs.push(Expr::Immediate {
+            dreg: NFT_REG_1,
+            data: addr.clone(),
+          });
+        }
+        if let Some(port) = port {
+          exprs.push(Expr::Immediate {
+            dreg: NFT_REG_2,
+            data: port.to_be_bytes().to_vec(),
+          });
+        }
+        exprs.push(Expr::Nat {
+          nat_type: if w == "dnat" {
+            NFT_NAT_DNAT
+          } else {
+            NFT_NAT_SNAT
+          },
+          family: nat_family as u32,
+          reg_addr: addr.map(|_| NFT_REG_1),
+          reg_proto: port.map(|_| NFT_REG_2),
+        });
+      }
+      _ => return Err(format!("unsupported rule syntax near '{}'", w)),
+    }
+    i += 1;
+  }
+  Ok(exprs)
+}
+
+/*
+ * Listing: turns kernel expressions back into the syntax above
+ */
+fn expr_from_attrs(list: &[(u16, &[u8])]) -> Expr {
+  let name = attr_str(list, NFTA_EXPR_NAME);
+  let d = match attr(list, NFTA_EXPR_DATA) {
+    Some(d) => attrs(d),
+    None => Vec::new(),
+  };
+  let u = |ty| attr_be32(&d, ty).unwrap_or(0);
+  match name.as_str() {
+    "meta" => Expr::Meta {
+      dreg: u(1),
+      key: u(2),
+    },
+    "ct" => Expr::Ct {
+      dreg: u(1),
+      key: u(2),
+    },
+    "payload" => Expr::Payload {
+      dreg: u(1),
+      base: u(2),
+      offset: u(3),
+      len: u(4),
+    },
+    "bitwise" => Expr::Bitwise {
+      sreg: u(1),
+      dreg: u(2),
+      mask: data_value(&d, 4),
+      xor: data_value(&d, 5),
+    },
+    "cmp" => Expr::Cmp {
+      sreg: u(1),
+      op: u(2),
+      data: data_value(&d, 3),
+    },
+    "immediate" => {
+      let dreg = u(1);
+      let data = match attr(&d, 2) {
+        Some(nested) => attrs(nested),
+        None => Vec::new(),
+      };
+      match attr(&data, NFTA_DATA_VERDICT) {
+        Some(v) => {
+          let v = attrs(v);
+          let chain = attr_str(&v, NFTA_VERDICT_CHAIN);
+          Expr::Verdict {
+            code: attr_be32(&v, NFTA_VERDICT_CODE).unwrap_or(0) as i32,
+            chain: if chain.is_empty() { None } else { Some(chain) },
+          }
+        }
+        None => Expr::Immediate {
+          dreg,
+          data: attr(&data, NFTA_DATA_VALUE)
+            .map(|v| v.to_vec())
+            .unwrap_or_default(),
+        },
+      }
+    }
+    "counter" => Expr::Counter,
+    "masq" => Expr::Masq,
+    "nat" => Expr::Nat {
+      nat_type: u(1),
+      family: u(2),
+      reg_addr: attr_be32(&d, 3),
+      reg_proto: attr_be32(&d, 5),
+    },
+    _ => Expr::Unknown(name),
+  }
+}
+
+fn hexdump(data: &[u8]) -> String {
+  data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn prefix_len(mask: &[u8]) -> u32 {
+  mask.iter().map(|b| b.count_ones()).sum()
+}
+
+fn render_rule(table_family: u8, exprs: &[Expr]) -> String {
+  /* What was last loaded into each register */
+  #[derive(Clone)]
+  enum Src {
+    None,
+    Meta(u32),
+    Payload(u32, u32, u32),
+    Ct(u32),
+    Value(Vec<u8>),
+  }
+  let mut regs: Vec<Src> = vec![Src::None; 32];
+  let mut masks: Vec<Option<Vec<u8>>> = vec![None; 32];
+  let mut out: Vec<String> = Vec::new();
+  let mut l4: Option<u8> = None;
+
+  fn reg(r: u32) -> usize {
+    std::cmp::min(r as usize, 31)
+  }
+  fn neq(op: u32) -> &'static str {
+    if op == NFT_CMP_NEQ {
+      "!= "
+    } else {
+      ""
+    }
+  }
+
+  for e in exprs {
+    match e {
+      Expr::Meta { key, dreg } => {
+        regs[reg(*dreg)] = Src::Meta(*key);
+        masks[reg(*dreg)] = None;
+      }
+      Expr::Payload {
+        base,
+        offset,
+        len,
+        dreg,
+      } => {
+        regs[reg(*dreg)] = Src::Payload(*base, *offset, *len);
+        masks[reg(*dreg)] = None;
+      }
+      Expr::Ct { key, dreg } => {
+        regs[reg(*dreg)] = Src::Ct(*key);
+        masks[reg(*dreg)] = None;
+      }
+      Expr::Bitwise {
+        sreg, dreg, mask, ..
+      } => {
+        regs[reg(*dreg)] = regs[reg(*sreg)].clone();
+        masks[reg(*dreg)] = Some(mask.clone());
+      }
+      Expr::Immediate { dreg, data } => {
+        regs[reg(*dreg)] = Src::Value(data.clone());
+      }
+      Expr::Cmp { sreg, op, data } => {
+        let mask = masks[reg(*sreg)].clone();
+        let text = match &regs[reg(*sreg)] {
+          Src::Meta(NFT_META_NFPROTO) => None,
+          Src::Meta(NFT_META_L4PROTO) if data.len() == 1 => {
+            if *op == NFT_CMP_EQ {
+              l4 = Some(data[0]);
+              None
+            } else {
+              Some(format!("meta l4proto != {}", l4proto_name(data[0])))
+            }
+          }
+          Src::Meta(k) if *k == NFT_META_IIFNAME || *k == NFT_META_OIFNAME => {
+            let end = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+            Some(format!(
+              "{} {}\"{}\"",
+              if *k == NFT_META_IIFNAME {
+                "iifname"
+              } else {
+                "oifname"
+              },
+              neq(*op),
+              String::from_utf8_lossy(&data[..end])
+            ))
+          }
+          Src::Payload(NFT_PAYLOAD_NETWORK_HEADER, off, len)
+            if (*len == 4 || *len == 16) && [8, 12, 16, 24].contains(off) =>
+          {
+            let (proto, field) = match (*len, *off) {
+              (4, 12) => ("ip", "saddr"),
+              (4, 16) => ("ip", "daddr"),
+              (16, 8) => ("ip6", "saddr"),
+              (16, 24) => ("ip6", "daddr"),
+              _ => ("ip", "?"),
+            };
+            let addr = if data.len() == 4 {
+              Ipv4Addr::new(data[0], data[1], data[2], data[3]).to_string()
+            } else if data.len() == 16 {
+              let mut a = [0u8; 16];
+              a.copy_from_slice(data);
+              Ipv6Addr::from(a).to_string()
+            } else {
+              hexdump(data)
+            };
+            let suffix = match &mask {
+              Some(m) => format!("/{}", prefix_len(m)),
+              None => String::new(),
+            };
+            Some(format!(
+              "{} {} {}{}{}",
+              proto,
+              field,
+              neq(*op),
+              addr,
+              suffix
+            ))
+          }
+          Src::Payload(NFT_PAYLOAD_TRANSPORT_HEADER, off, 2) if *off == 0 || *off == 2 => {
+            let proto = match l4.take() {
+              Some(p) => l4proto_name(p),
+              None => "th".to_string(),
+            };
+            Some(format!(
+              "{} {} {}{}",
+              proto,
+              if *off == 0 { "sport" } else { "dport" },
+              neq(*op),
+              u16::from_be_bytes([data[0], data[1]])
+            ))
+          }
+          Src::Ct(NFT_CT_STATE) if mask.is_some() => {
+            let m = mask.unwrap();
+            let bits = u32::from_ne_bytes([m[0], m[1], m[2], m[3]]);
+            let names: Vec<&str> = ct_states
+              .iter()
+              .filter(|c| bits & c.1 != 0)
+              .map(|c| c.0)
+              .collect();
+            Some(format!(
+              "ct state {}{}",
+              if *op == NFT_CMP_EQ { "!= " } else { "" },
+              names.join(",")
+            ))
+          }
+          _ => Some(format!("[cmp {}{}]", neq(*op), hexdump(data))),
+        };
+        if let Some(text) = text {
+          out.push(text);
+        }
+      }
+      Expr::Verdict { code, chain } => out.push(match (*code, chain) {
+        (NF_ACCEPT, _) => "accept".to_string(),
+        (NF_DROP, _) => "drop".to_string(),
+        (NFT_RETURN, _) => "return".to_string(),
+        (NFT_CONTINUE, _) => "continue".to_string(),
+        (NFT_JUMP, Some(c)) => format!("jump {}", c),
+        (NFT_GOTO, Some(c)) => format!("goto {}", c),
+        (c, _) => format!("[verdict {}]", c),
+      }),
+      Expr::Counter => out.push("counter".to_string()),
+      Expr::Masq => out.push("masquerade".to_string()),
+      Expr::Nat {
+        nat_type,
+        family,
+        reg_addr,
+        reg_proto,
+      } => {
+        let mut target = String::new();
+        if let Some(r) = reg_addr {
+          if let Src::Value(v) = &regs[reg(*r)] {
+            if v.len() == 4 {
+              target = Ipv4Addr::new(v[0], v[1], v[2], v[3]).to_string();
+            } else if v.len() == 16 {
+              let mut a = [0u8; 16];
+              a.copy_from_slice(v);
+              target = Ipv6Addr::from(a).to_string();
+            }
+          }
+        }
+        if let Some(r) = reg_proto {
+          if let Src::Value(v) = &regs[reg(*r)] {
+            if v.len() == 2 {
+              if *family == NFPROTO_IPV6 as u32 && !target.is_empty() {
+                target = format!("[{}]", target);
+              }
+              target.push_str(&format!(":{}", u16::from_be_bytes([v[0], v[1]])));
+            }
+          }
+        }
+        out.push(format!(
+          "{}{} to {}",
+          if *nat_type == NFT_NAT_DNAT {
+            "dnat"
+          } else {
+            "snat"
+          },
+          if table_family == NFPROTO_INET {
+            format!(" {}", family_name(*family as u8))
+          } else {
+            String::new()
+          },
+          target
+        ));
+      }
+      Expr::Unknown(name) => out.push(format!("[{}]", name)),
+    }
+  }
+  if let Some(p) = l4 {
+    /* l4proto match with no port match after it */
+    out.insert(0, format!("meta l4proto {}", l4proto_name(p)));
+  }
+  out.join(" ")
+}
+
+fn list(family: u8, only_table: Option<&str>, tables_only: bool) {
+  let fd = nl_open();
+  let tables = nl_dump(fd, NFT_MSG_GETTABLE, family);
+  let chains = if tables_only {
+    Vec::new()
+  } else {
+    nl_dump(fd, NFT_MSG_GETCHAIN, family)
+  };
+  let rules = if tables_only {
+    Vec::new()
+  } else {
+    nl_dump(fd, NFT_MSG_GETRULE, family)
+  };
+  unsafe { libc::close(fd) };
+
+  for (tfam, tattrs) in &tables {
+    let tlist: Vec<(u16, &[u8])> = tattrs.iter().map(|(t, d)| (*t, &d[..])).collect();
+    let tname = attr_str(&tlist, NFTA_TABLE_NAME);
+    if only_table.map_or(false, |t| t != tname) {
+      continue;
+    }
+    if tables_only {
+      println!("table {} {}", family_name(*tfam), tname);
+      continue;
+    }
+    println!("table {} {} {{", family_name(*tfam), tname);
+    let mut first = true;
+    for (cfam, cattrs) in &chains {
+      let clist: Vec<(u16, &[u8])> = cattrs.iter().map(|(t, d)| (*t, &d[..])).collect();
+      if cfam != tfam || attr_str(&clist, NFTA_CHAIN_TABLE) != tname {
+        continue;
+      }
+      let cname = attr_str(&clist, NFTA_CHAIN_NAME);
+      if !first {
+        println!();
+      }
+      first = false;
+      println!("\tchain {} {{", cname);
+      if let Some(hook) = attr(&clist, NFTA_CHAIN_HOOK) {
+        let hook = attrs(hook);
+        let hooknum = attr_be32(&hook, NFTA_HOOK_HOOKNUM).unwrap_or(0) as usize;
+        let prio = attr_be32(&hook, NFTA_HOOK_PRIORITY).unwrap_or(0) as i32;
+        let hookname = if *cfam == 5 {
+          "ingress"
+        } else {
+          hooks.get(hooknum).cloned().unwrap_or("?")
+        };
+        let policy = match attr_be32(&clist, NFTA_CHAIN_POLICY) {
+          Some(p) if p == NF_DROP as u32 => "drop",
+          _ => "accept",
+        };
+        println!(
+          "\t\ttype {} hook {} priority {}; policy {};",
+          attr_str(&clist, NFTA_CHAIN_TYPE),
+          hookname,
+          prio,
+          policy
+        );
+      }
+      for (rfam, rattrs) in &rules {
+        let rlist: Vec<(u16, &[u8])> = rattrs.iter().map(|(t, d)| (*t, &d[..])).collect();
+        if rfam != cfam
+          || attr_str(&rlist, NFTA_RULE_TABLE) != tname
+          || attr_str(&rlist, NFTA_RULE_CHAIN) != cname
+        {
+          continue;
+        }
+        let exprs: Vec<Expr> = match attr(&rlist, NFTA_RULE_EXPRESSIONS) {
+          Some(l) => attrs(l)
+            .iter()
+            .filter(|e| e.0 == NFTA_LIST_ELEM)
+            .map(|e| expr_from_attrs(&attrs(e.1)))
+            .collect(),
+          None => Vec::new(),
+        };
+        println!("\t\t{}", render_rule(*rfam, &exprs));
+      }
+      println!("\t}}");
+    }
+    println!("}}");
+  }
+}
+
+pub fn nft_main(args: &[&str]) -> ! {
+  let mut file: Option<String> = None;
+  let mut check = false;
+  let mut i = 1;
+
+  while i < args.len() && args[i].starts_with('-') && args[i].len() > 1 {
+    match args[i] {
+      "-c" | "--check" => check = true,
+      "-f" | "--file" => {
+        i += 1;
+        match args.get(i) {
+          Some(f) => file = Some(f.to_string()),
+          None => unsafe { crate::libbb::appletlib::bb_show_usage() },
+        }
+      }
+      "--" => {
+        i += 1;
+        break;
+      }
+      _ => unsafe { crate::libbb::appletlib::bb_show_usage() },
+    }
+    i += 1;
+  }
+  let rest = &args[i..];
+
+  let (src, name) = match file {
+    Some(f) => {
+      let mut src = String::new();
+      let r = if f == "-" {
+        std::io::stdin().read_to_string(&mut src)
+      } else {
+        std::fs::File::open(&f).and_then(|mut fp| fp.read_to_string(&mut src))
+      };
+      if let Err(e) = r {
+        die(&format!("can't read '{}': {}", f, e));
+      }
+      (src, f)
+    }
+    None => {
+      if rest.is_empty() {
+        unsafe { crate::libbb::appletlib::bb_show_usage() };
+      }
+      if rest[0] == "list" {
+        let what = rest.get(1).cloned().unwrap_or("ruleset");
+        match what {
+          "ruleset" => list(0, None, false),
+          "tables" => list(0, None, true),
+          "table" => {
+            let (family, name) = match (rest.get(2), rest.get(3)) {
+              (Some(f), Some(n)) if family_by_name(f).is_some() => (family_by_name(f).unwrap(), *n),
+              (Some(n), None) => (NFPROTO_IPV4, *n),
+              _ => unsafe { crate::libbb::appletlib::bb_show_usage() },
+            };
+            list(family, Some(name), false);
+          }
+          _ => die(&format!("can't list '{}'", what)),
+        }
+        std::process::exit(0);
+      }
+      (rest.join(" "), "command line".to_string())
+    }
+  };
+
+  let mut p = Parser {
+    toks: tokenize(&src),
+    pos: 0,
+    file: &name,
+    cmds: Vec::new(),
+  };
+  p.parse();
+  if !check && !p.cmds.is_empty() {
+    nl_commit(&p.cmds);
+  }
+  std::process::exit(0);
+}
@@ -5,21 +5,26 @@ use crate::librb::__compar_fn_t;
 use crate::librb::__syscall_slong_t;
 use crate::librb::md5_ctx_t;
 use crate::librb::sha1_ctx_t;
+use crate::librb::sha256_ctx_t;
 use crate::librb::signal::__sighandler_t;
 use crate::librb::size_t;
 use crate::librb::smallint;
 use crate::librb::socklen_t;
+use crate::librb::tls_aes;
 use c2rust_asm_casts;
 use c2rust_asm_casts::AsmCastTrait;
 use c2rust_bitfields;
 use c2rust_bitfields::BitfieldStruct;
 use libc;
+use libc::accept;
 use libc::alarm;
 use libc::close;
 use libc::free;
 use libc::getpid;
 use libc::getuid;
+use libc::open;
 use libc::putenv;
+use libc::rename;
 use libc::sockaddr;
 use libc::sockaddr_in;
 use libc::sockaddr_in6;
@@ -27,9 +32,11 @@ use libc::ssize_t;
 use libc::strchr;
 use libc::strcmp;
 use libc::strcpy;
+use libc::strtod;
 use libc::suseconds_t;
 use libc::time_t;
 use libc::timeval;
+use libc::unlink;
 extern "C" {
   pub type sockaddr_x25;
   pub type sockaddr_un;
@@ -115,6 +122,10 @@ extern "C" {
   #[no_mangle]
   fn strncasecmp(_: *const libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> libc::c_int;
   #[no_mangle]
+  fn dprintf(__fd: libc::c_int, __fmt: *const libc::c_char, _: ...) -> libc::c_int;
+  #[no_mangle]
+  fn strcasecmp(_: *const libc::c_char, _: *const libc::c_char) -> libc::c_int;
+  #[no_mangle]
   fn poll(__fds: *mut pollfd, __nfds: nfds_t, __timeout: libc::c_int) -> libc::c_int;
   #[no_mangle]
   fn setpriority(__which: __priority_which_t, __who: id_t, __prio: libc::c_int) -> libc::c_int;
@@ -403,6 +414,9 @@ pub struct globals {
   pub last_update_recv_time: libc::c_double,
   pub discipline_jitter: libc::c_double,
   pub offset_to_jitter_ratio: libc::c_uint,
+  pub drift_file: *mut libc::c_char,
+  pub last_drift_save: libc::c_double,
+  pub ctl_fd: libc::c_int,
 }
 
 #[repr(C)]
@@ -507,10 +521,14 @@ pub const NTP_MD5_DIGESTSIZE: C2RustUnnamed_6 = 16;
 pub const NTP_MAXSTRATUM: C2RustUnnamed_6 = 15;
 pub const NTP_VERSION: C2RustUnnamed_6 = 4;
 pub type C2RustUnnamed_7 = libc::c_uint;
+pub const HASH_AES_CMAC: C2RustUnnamed_7 = 3;
+pub const HASH_SHA256: C2RustUnnamed_7 = 2;
 pub const HASH_SHA1: C2RustUnnamed_7 = 1;
 pub const HASH_MD5: C2RustUnnamed_7 = 0;
 pub type C2RustUnnamed_8 = libc::c_int;
 pub const OPT_qq: C2RustUnnamed_8 = -2147483648;
+pub const OPT_c: C2RustUnnamed_8 = 524288;
+pub const OPT_f: C2RustUnnamed_8 = 262144;
 pub const OPT_I: C2RustUnnamed_8 = 512;
 pub const OPT_l: C2RustUnnamed_8 = 256;
 pub const OPT_S: C2RustUnnamed_8 = 128;
@@ -534,6 +552,7 @@ pub union C2RustUnnamed_9 {
 pub union C2RustUnnamed_10 {
   pub m: md5_ctx_t,
   pub s: sha1_ctx_t,
+  pub s256: sha256_ctx_t,
 }
 
 #[repr(C)]
@@ -1030,6 +1049,84 @@ unsafe extern "C" fn do_sendto(
   }
   return 0;
 }
+/* AES-CMAC (RFC 4493), the MAC used by "AES128CMAC" keys.
+ * Unlike the digest based MACs, the key is not hashed along with the message:
+ * it keys the cipher.
+ */
+unsafe extern "C" fn cmac_double(mut block: *mut u8) {
+  let mut carry: u8 = 0;
+  let mut i: libc::c_int = 16i32;
+  while i != 0 {
+    i -= 1;
+    let mut b: u8 = *block.offset(i as isize);
+    *block.offset(i as isize) = ((b as libc::c_int) << 1i32 | carry as libc::c_int) as u8;
+    carry = (b as libc::c_int >> 7i32) as u8
+  }
+  if carry != 0 {
+    *block.offset(15) = (*block.offset(15) as libc::c_int ^ 0x87i32) as u8
+  }
+}
+unsafe extern "C" fn aes_cmac(
+  mut key: *const u8,
+  mut key_length: libc::c_uint,
+  mut msg: *const u8,
+  mut len: libc::c_uint,
+  mut output: *mut u8,
+) {
+  let mut aes: tls_aes = tls_aes {
+    key: [0; 60],
+    rounds: 0,
+  };
+  let mut subkey: [u8; 16] = [0; 16];
+  let mut last: [u8; 16] = [0; 16];
+  let mut mac: [u8; 16] = [0; 16];
+  let mut i: libc::c_uint = 0;
+  crate::networking::tls_aes::aes_setkey(&mut aes, key as *const libc::c_void, key_length);
+  /* K1 = dbl(AES(K, 0)), K2 = dbl(K1) */
+  crate::networking::tls_aes::aes_encrypt_one_block(
+    &mut aes,
+    subkey.as_mut_ptr() as *const libc::c_void,
+    subkey.as_mut_ptr() as *mut libc::c_void,
+  );
+  cmac_double(subkey.as_mut_ptr());
+  if len == 0 || len & 15 != 0 {
+    cmac_double(subkey.as_mut_ptr());
+  }
+  /* All blocks but the last one are plain CBC-MAC */
+  while len > 16 {
+    i = 0;
+    while i < 16 {
+      mac[i as usize] ^= *msg.offset(i as isize);
+      i = i.wrapping_add(1)
+    }
+    crate::networking::tls_aes::aes_encrypt_one_block(
+      &mut aes,
+      mac.as_mut_ptr() as *const libc::c_void,
+      mac.as_mut_ptr() as *mut libc::c_void,
+    );
+    msg = msg.offset(16);
+    len = len.wrapping_sub(16)
+  }
+  /* Last block is padded with 0x80,0,0... if incomplete, then masked with subkey */
+  memcpy(
+    last.as_mut_ptr() as *mut libc::c_void,
+    msg as *const libc::c_void,
+    len as libc::c_ulong,
+  );
+  if len < 16 {
+    last[len as usize] = 0x80
+  }
+  i = 0;
+  while i < 16 {
+    mac[i as usize] ^= last[i as usize] ^ subkey[i as usize];
+    i = i.wrapping_add(1)
+  }
+  crate::networking::tls_aes::aes_encrypt_one_block(
+    &mut aes,
+    mac.as_mut_ptr() as *const libc::c_void,
+    output as *mut libc::c_void,
+  );
+}
 unsafe extern "C" fn hash(
   mut key_entry: *mut key_entry_t,
   mut msg: *const msg_t,
@@ -1062,6 +1159,36 @@ unsafe extern "C" fn hash(
       );
       crate::libbb::hash_md5_sha::md5_end(&mut ctx.m, output as *mut libc::c_void);
     }
+    2 => {
+      /* SHA-256 digest is truncated to the 20 bytes which fit into a NTPv4 MAC */
+      let mut digest: [u8; 32] = [0; 32];
+      crate::libbb::hash_md5_sha::sha256_begin(&mut ctx.s256);
+      crate::libbb::hash_md5_sha::md5_hash(
+        &mut ctx.s256,
+        (*key_entry).key.as_mut_ptr() as *const libc::c_void,
+        (*key_entry).key_length as size_t,
+      );
+      crate::libbb::hash_md5_sha::md5_hash(
+        &mut ctx.s256,
+        msg as *const libc::c_void,
+        hash_size as size_t,
+      );
+      crate::libbb::hash_md5_sha::sha1_end(&mut ctx.s256, digest.as_mut_ptr() as *mut libc::c_void);
+      memcpy(
+        output as *mut libc::c_void,
+        digest.as_mut_ptr() as *const libc::c_void,
+        NTP_SHA1_DIGESTSIZE as libc::c_int as libc::c_ulong,
+      );
+    }
+    3 => {
+      aes_cmac(
+        (*key_entry).key.as_mut_ptr() as *const u8,
+        (*key_entry).key_length as libc::c_uint,
+        msg as *const u8,
+        hash_size,
+        output,
+      );
+    }
     _ => {
       /* it's HASH_SHA1 */
       crate::libbb::hash_md5_sha::sha1_begin(&mut ctx.s);
@@ -2129,6 +2256,14 @@ unsafe extern "C" fn update_local_clock(mut p: *mut peer_t) -> libc::c_int {
     );
   }
   (*ptr_to_globals).kernel_freq_drift = tmx.freq / 65536i32 as libc::c_long;
+  /* Save the frequency once an hour, the kernel forgets it on reboot */
+  if !(*ptr_to_globals).drift_file.is_null()
+    && (*ptr_to_globals).cur_time - (*ptr_to_globals).last_drift_save
+      >= (60i32 * 60i32) as libc::c_double
+  {
+    (*ptr_to_globals).last_drift_save = (*ptr_to_globals).cur_time;
+    write_drift_file(tmx.freq);
+  }
   if 3i32 >= 2i32 && (*ptr_to_globals).verbose >= 2i32 as libc::c_uint {
     crate::libbb::verror_msg::bb_error_msg(
       b"update from:%s offset:%+f delay:%f jitter:%f clock drift:%+.3fppm tc:%d\x00" as *const u8
@@ -2789,6 +2924,7 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
   let mut peers: *mut llist_t = std::ptr::null_mut();
   let mut key_entries: *mut llist_t = std::ptr::null_mut();
   let mut key_file_path: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut ctl_path: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   srand(getpid() as libc::c_uint);
   if getuid() != 0 {
     crate::libbb::verror_msg::bb_simple_error_msg_and_die(bb_msg_you_must_be_root.as_ptr());
@@ -2808,16 +2944,19 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
   key_entries = std::ptr::null_mut();
   opts = crate::libbb::getopt32::getopt32(
     argv,
-    b"^nqNxk:wp:*S:lI:d46aAbgL\x00=0:dd:wn:Il\x00" as *const u8 as *const libc::c_char,
+    b"^nqNxk:wp:*S:lI:d46aAbgLf:c:\x00=0:dd:wn:Il\x00" as *const u8 as *const libc::c_char,
     &mut key_file_path as *mut *mut libc::c_char,
     &mut peers as *mut *mut llist_t,
     &mut (*ptr_to_globals).script_name as *mut *mut libc::c_char,
     &mut (*ptr_to_globals).if_name as *mut *mut libc::c_char,
+    &mut (*ptr_to_globals).drift_file as *mut *mut libc::c_char,
+    &mut ctl_path as *mut *mut libc::c_char,
     &mut (*ptr_to_globals).verbose as *mut libc::c_uint,
   );
   //	if (opts & OPT_x) /* disable stepping, only slew is allowed */
   //		G.time_was_stepped = 1;
   (*ptr_to_globals).listen_fd = -1i32;
+  (*ptr_to_globals).ctl_fd = -1i32;
   if opts & OPT_l as libc::c_int as libc::c_uint != 0 {
     (*ptr_to_globals).listen_fd =
       crate::libbb::xconnect::create_and_bind_dgram_or_die(0 as *const libc::c_char, 123i32);
@@ -2846,6 +2985,15 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
     crate::libbb::vfork_daemon_rexec::bb_daemonize_or_rexec(DAEMON_DEVNULL_STDIO as libc::c_int);
     logmode = LOGMODE_NONE as libc::c_int as smallint
   }
+  if opts & OPT_c as libc::c_int as libc::c_uint != 0 {
+    (*ptr_to_globals).ctl_fd = create_ctl_socket(ctl_path);
+  }
+  if opts & OPT_f as libc::c_int as libc::c_uint != 0
+    && opts & OPT_w as libc::c_int as libc::c_uint == 0
+  {
+    read_drift_file();
+  }
+  (*ptr_to_globals).last_drift_save = (*ptr_to_globals).cur_time;
   if opts & OPT_k as libc::c_int as libc::c_uint != 0 {
     let mut tokens: [*mut libc::c_char; 4] = [0 as *mut libc::c_char; 4];
     let mut parser: *mut parser_t = std::ptr::null_mut();
@@ -2869,6 +3017,16 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
       if *tokens[1].offset(0) as libc::c_int | 0x20i32 == 'm' as i32 {
         /* supports 'M' and 'md5' formats */
         hash_type = HASH_MD5 as libc::c_int as smalluint
+      } else if strcasecmp(tokens[1], b"sha256\x00" as *const u8 as *const libc::c_char) == 0 {
+        hash_type = HASH_SHA256 as libc::c_int as smalluint
+      } else if strcasecmp(
+        tokens[1],
+        b"aes128cmac\x00" as *const u8 as *const libc::c_char,
+      ) == 0
+        || strcasecmp(tokens[1], b"cmac\x00" as *const u8 as *const libc::c_char) == 0
+      {
+        /* supports 'AES128CMAC' and 'CMAC' formats */
+        hash_type = HASH_AES_CMAC as libc::c_int as smalluint
       } else if strncasecmp(
         tokens[1],
         b"sha\x00" as *const u8 as *const libc::c_char,
@@ -2879,7 +3037,8 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
         hash_type = HASH_SHA1 as libc::c_int as smalluint
       } else {
         crate::libbb::verror_msg::bb_simple_error_msg_and_die(
-          b"only MD5 and SHA1 keys supported\x00" as *const u8 as *const libc::c_char,
+          b"only MD5, SHA1, SHA256 and AES128CMAC keys supported\x00" as *const u8
+            as *const libc::c_char,
         );
       }
       /* man ntp.keys:
@@ -2889,6 +3048,8 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
        *  SHA1
        *  RMD160 The key is a hex-encoded ASCII string of 40 characters, which
        *         is truncated as necessary.
+       * SHA256 keys are hex-encoded the same way, AES128CMAC keys
+       * are exactly 32 hex characters (16 bytes).
        */
       key_length = strnlen(
         tokens[2],
@@ -2913,6 +3074,15 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
           .is_null()
           {
             current_block = 8105424810531593847;
+          } else if hash_type as libc::c_int == HASH_AES_CMAC as libc::c_int {
+            if key_length as libc::c_int != 16i32 {
+              current_block = 8105424810531593847;
+            } else {
+              key = buffer.as_mut_ptr();
+              /* 16-byte MAC, same packet size as MD5 */
+              msg_size = NTP_MSGSIZE_MD5_AUTH as libc::c_int as smalluint;
+              current_block = 14945149239039849694;
+            }
           } else {
             key = buffer.as_mut_ptr();
             msg_size = NTP_MSGSIZE_SHA1_AUTH as libc::c_int as smalluint;
@@ -3058,6 +3228,203 @@ unsafe extern "C" fn ntp_init(mut argv: *mut *mut libc::c_char) {
   );
   //TODO: free unused elements of key_entries?
 }
+/* Drift file holds kernel frequency correction in ppm,
+ * a single number as in ntp.drift of the reference ntpd.
+ */
+unsafe fn read_drift_file() {
+  let mut buf: [libc::c_char; 32] = [0; 32];
+  let mut tmx: timex = std::mem::zeroed();
+  let mut ppm: libc::c_double = 0.;
+  if crate::libbb::read::open_read_close(
+    (*ptr_to_globals).drift_file,
+    buf.as_mut_ptr() as *mut libc::c_void,
+    (::std::mem::size_of::<[libc::c_char; 32]>() as libc::c_ulong)
+      .wrapping_sub(1i32 as libc::c_ulong),
+  ) <= 0
+  {
+    return;
+  }
+  ppm = strtod(buf.as_mut_ptr(), std::ptr::null_mut());
+  /* Kernel accepts at most +-500 ppm */
+  if !(ppm > -500i32 as libc::c_double && ppm < 500i32 as libc::c_double) {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"ignoring bad drift file %s\x00" as *const u8 as *const libc::c_char,
+      (*ptr_to_globals).drift_file,
+    );
+    return;
+  }
+  tmx.modes = 0x2i32 as libc::c_uint; /* ADJ_FREQUENCY */
+  tmx.freq = (ppm * 65536i32 as libc::c_double) as __syscall_slong_t;
+  if adjtimex(&mut tmx) < 0 {
+    crate::libbb::perror_msg::bb_simple_perror_msg(
+      b"adjtimex\x00" as *const u8 as *const libc::c_char,
+    );
+    return;
+  }
+  (*ptr_to_globals).kernel_freq_drift = tmx.freq / 65536i32 as libc::c_long;
+  if 3i32 != 0 && (*ptr_to_globals).verbose != 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"initial clock drift from %s:%+.3fppm\x00" as *const u8 as *const libc::c_char,
+      (*ptr_to_globals).drift_file,
+      ppm,
+    );
+  }
+}
+unsafe fn write_drift_file(mut freq: libc::c_long) {
+  let mut fd: libc::c_int = 0;
+  let mut tmp: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+    b"%s.tmp\x00" as *const u8 as *const libc::c_char,
+    (*ptr_to_globals).drift_file,
+  );
+  /* Write a new file and rename it over the old one,
+   * so that a crash never leaves a truncated drift file behind */
+  fd = open(tmp, 0o1 | 0o100 | 0o1000, 0o644);
+  if fd >= 0 {
+    dprintf(
+      fd,
+      b"%.3f\n\x00" as *const u8 as *const libc::c_char,
+      freq as libc::c_double / 65536i32 as libc::c_double,
+    );
+    close(fd);
+    if rename(tmp, (*ptr_to_globals).drift_file) == 0 {
+      free(tmp as *mut libc::c_void);
+      return;
+    }
+  }
+  crate::libbb::perror_msg::bb_perror_msg(
+    b"can\'t write \'%s\'\x00" as *const u8 as *const libc::c_char,
+    (*ptr_to_globals).drift_file,
+  );
+  unlink(tmp);
+  free(tmp as *mut libc::c_void);
+}
+unsafe fn ctl_sockaddr(mut sunx: *mut libc::sockaddr_un, mut path: *const libc::c_char) {
+  memset(
+    sunx as *mut libc::c_void,
+    0,
+    ::std::mem::size_of::<libc::sockaddr_un>() as libc::c_ulong,
+  );
+  (*sunx).sun_family = 1i32 as libc::sa_family_t; /* AF_UNIX */
+  crate::libbb::safe_strncpy::safe_strncpy(
+    (*sunx).sun_path.as_mut_ptr(),
+    path,
+    ::std::mem::size_of::<[libc::c_char; 108]>() as libc::c_ulong,
+  );
+}
+/* Status socket: every connection gets one report and is closed.
+ * "ntpctl" is the client.
+ */
+unsafe fn create_ctl_socket(mut path: *const libc::c_char) -> libc::c_int {
+  let mut sunx: libc::sockaddr_un = std::mem::zeroed();
+  let mut fd: libc::c_int = 0;
+  ctl_sockaddr(&mut sunx, path);
+  unlink(sunx.sun_path.as_mut_ptr());
+  fd = crate::libbb::xfuncs_printf::xsocket(1i32, SOCK_STREAM as libc::c_int, 0);
+  crate::libbb::xfuncs_printf::xbind(
+    fd,
+    &mut sunx as *mut libc::sockaddr_un as *mut sockaddr,
+    ::std::mem::size_of::<libc::sockaddr_un>() as socklen_t,
+  );
+  crate::libbb::xfuncs_printf::xlisten(fd, 4i32);
+  crate::libbb::xfuncs::close_on_exec_on(fd);
+  return fd;
+}
+unsafe fn handle_ctl_connection() {
+  let mut fd: libc::c_int = 0;
+  let mut item: *mut llist_t = std::ptr::null_mut();
+  let mut sync_peer: *mut peer_t = std::ptr::null_mut();
+  fd = accept(
+    (*ptr_to_globals).ctl_fd,
+    std::ptr::null_mut(),
+    std::ptr::null_mut(),
+  );
+  if fd < 0 {
+    return;
+  }
+  /* Never block the main loop on a stuck client */
+  crate::libbb::xfuncs::ndelay_on(fd);
+  if ((*ptr_to_globals).stratum as libc::c_int) < 16i32 {
+    sync_peer = (*ptr_to_globals).last_update_peer;
+    dprintf(
+      fd,
+      b"status: synchronized to %s, stratum %u\n\x00" as *const u8 as *const libc::c_char,
+      if !sync_peer.is_null() && !(*sync_peer).p_dotted.is_null() {
+        (*sync_peer).p_dotted as *const libc::c_char
+      } else {
+        b"local clock\x00" as *const u8 as *const libc::c_char
+      },
+      (*ptr_to_globals).stratum as libc::c_int,
+    );
+  } else {
+    dprintf(
+      fd,
+      b"status: unsynchronized, stratum %u\n\x00" as *const u8 as *const libc::c_char,
+      (*ptr_to_globals).stratum as libc::c_int,
+    );
+  }
+  dprintf(
+    fd,
+    b"offset:%+f jitter:%f clock drift:%+ldppm poll:%us\n\x00" as *const u8 as *const libc::c_char,
+    (*ptr_to_globals).last_update_offset,
+    (*ptr_to_globals).discipline_jitter,
+    (*ptr_to_globals).kernel_freq_drift,
+    1i32 << (*ptr_to_globals).poll_exp as libc::c_int,
+  );
+  dprintf(
+    fd,
+    b"%-25s %5s %3s %12s %10s %10s\n\x00" as *const u8 as *const libc::c_char,
+    b" remote\x00" as *const u8 as *const libc::c_char,
+    b"reach\x00" as *const u8 as *const libc::c_char,
+    b"st\x00" as *const u8 as *const libc::c_char,
+    b"offset\x00" as *const u8 as *const libc::c_char,
+    b"delay\x00" as *const u8 as *const libc::c_char,
+    b"jitter\x00" as *const u8 as *const libc::c_char,
+  );
+  item = (*ptr_to_globals).ntp_peers;
+  while !item.is_null() {
+    let mut p: *mut peer_t = (*item).data as *mut peer_t;
+    dprintf(
+      fd,
+      b"%c%-24s  0x%02x %3u %+12f %10f %10f\n\x00" as *const u8 as *const libc::c_char,
+      if p == sync_peer {
+        '*' as i32
+      } else {
+        ' ' as i32
+      },
+      if !(*p).p_dotted.is_null() {
+        (*p).p_dotted
+      } else {
+        (*p).p_hostname.as_mut_ptr()
+      },
+      (*p).reachable_bits as libc::c_int,
+      (*p).lastpkt_stratum as libc::c_int,
+      (*p).filter_offset,
+      (*p).lastpkt_delay,
+      (*p).filter_jitter,
+    );
+    item = (*item).link
+  }
+  close(fd);
+}
+pub unsafe fn ntpctl_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  let mut sunx: libc::sockaddr_un = std::mem::zeroed();
+  let mut path: *mut libc::c_char =
+    b"/var/run/ntpd.ctl\x00" as *const u8 as *const libc::c_char as *mut libc::c_char;
+  let mut fd: libc::c_int = 0;
+  crate::libbb::getopt32::getopt32(
+    argv,
+    b"c:\x00" as *const u8 as *const libc::c_char,
+    &mut path as *mut *mut libc::c_char,
+  );
+  ctl_sockaddr(&mut sunx, path);
+  fd = crate::libbb::xfuncs_printf::xsocket(1i32, SOCK_STREAM as libc::c_int, 0);
+  crate::libbb::xconnect::xconnect(
+    fd,
+    &mut sunx as *mut libc::sockaddr_un as *mut sockaddr,
+    ::std::mem::size_of::<libc::sockaddr_un>() as socklen_t,
+  );
+  return (crate::libbb::copyfd::bb_copyfd_eof(fd, 1i32) < 0) as libc::c_int;
+}
 pub unsafe fn ntpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char) -> libc::c_int {
   let mut current_block: u64;
   let mut G: globals = globals {
@@ -3085,6 +3452,9 @@ pub unsafe fn ntpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char
     last_update_recv_time: 0.,
     discipline_jitter: 0.,
     offset_to_jitter_ratio: 0,
+    drift_file: std::ptr::null_mut::<libc::c_char>(),
+    last_drift_save: 0.,
+    ctl_fd: 0,
   };
   let mut pfd: *mut pollfd = std::ptr::null_mut();
   let mut idx2peer: *mut *mut peer_t = std::ptr::null_mut();
@@ -3100,8 +3470,8 @@ pub unsafe fn ntpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char
   *fresh31 = &mut G as *mut globals as *mut libc::c_void as *mut globals;
   llvm_asm!("" : : : "memory" : "volatile");
   ntp_init(argv);
-  /* If ENABLE_FEATURE_NTPD_SERVER, + 1 for listen_fd: */
-  cnt = G.peer_cnt.wrapping_add(1i32 as libc::c_uint);
+  /* If ENABLE_FEATURE_NTPD_SERVER, + 1 for listen_fd, + 1 for ctl_fd: */
+  cnt = G.peer_cnt.wrapping_add(2i32 as libc::c_uint);
   idx2peer = crate::libbb::xfuncs_printf::xzalloc(
     (::std::mem::size_of::<*mut peer_t>() as libc::c_ulong).wrapping_mul(cnt as libc::c_ulong),
   ) as *mut *mut peer_t;
@@ -3134,6 +3504,11 @@ pub unsafe fn ntpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char
       (*pfd.offset(0)).events = 0x1i32 as libc::c_short;
       i = i.wrapping_add(1)
     }
+    if G.ctl_fd != -1i32 {
+      (*pfd.offset(i as isize)).fd = G.ctl_fd;
+      (*pfd.offset(i as isize)).events = 0x1i32 as libc::c_short;
+      i = i.wrapping_add(1)
+    }
     /* Pass over peer list, send requests, time out on receives */
     item = G.ntp_peers;
     while !item.is_null() {
@@ -3195,7 +3570,10 @@ pub unsafe fn ntpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char
     timeout += 1;
     /* Here we may block */
     if 3i32 >= 2i32 && G.verbose >= 2i32 as libc::c_uint {
-      if i > (1i32 != 0 && G.listen_fd != -1i32) as libc::c_int as libc::c_uint {
+      if i
+        > ((G.listen_fd != -1i32) as libc::c_int + (G.ctl_fd != -1i32) as libc::c_int)
+          as libc::c_uint
+      {
         /* We wait for at least one reply.
          * Poll for it, without wasting time for message.
          * Since replies often come under 1 second, this also
@@ -3294,6 +3672,13 @@ pub unsafe fn ntpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char
         }
         j = 1i32 as libc::c_uint
       }
+      if G.ctl_fd != -1i32 {
+        if (*pfd.offset(j as isize)).revents != 0 {
+          nfds -= 1;
+          handle_ctl_connection();
+        }
+        j = j.wrapping_add(1)
+      }
       while nfds != 0 && j < i {
         if (*pfd.offset(j as isize)).revents != 0 {
           /* & (POLLIN|POLLERR)*/
@@ -3338,6 +3723,12 @@ pub unsafe fn ntpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char
       }
     }
   }
+  if !G.drift_file.is_null() && !G.last_update_peer.is_null() {
+    let mut tmx: timex = std::mem::zeroed();
+    if adjtimex(&mut tmx) >= 0 {
+      write_drift_file(tmx.freq);
+    }
+  }
   crate::libbb::pidfile::remove_pidfile_std_path_and_ext(
     b"ntpd\x00" as *const u8 as *const libc::c_char,
   );
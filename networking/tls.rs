@@ -2100,6 +2100,31 @@ unsafe extern "C" fn send_client_hello_and_alloc_hsd(
     0x1i32 as u8,
     0 as u8,
   ];
+  /* With a pinned certificate, offer only RSA key exchange: we don't check
+   * the server's signature on ECDHE parameters. Unassigned GREASE values
+   * (RFC 8701), which servers ignore, keep the list the same size */
+  static mut rsa_ciphers: [u8; 20] = [
+    0 as u8,
+    16 as u8,
+    0 as u8,
+    0xffi32 as u8,
+    0 as u8,
+    0x3ci32 as u8,
+    0 as u8,
+    0x3di32 as u8,
+    0 as u8,
+    0x9ci32 as u8,
+    0xai32 as u8,
+    0xai32 as u8,
+    0x1ai32 as u8,
+    0x1ai32 as u8,
+    0x2ai32 as u8,
+    0x2ai32 as u8,
+    0x3ai32 as u8,
+    0x3ai32 as u8,
+    0x1i32 as u8,
+    0 as u8,
+  ];
   static mut supported_groups: [u8; 8] = [
     0 as u8,
     0xai32 as u8,
@@ -2152,7 +2177,11 @@ unsafe extern "C" fn send_client_hello_and_alloc_hsd(
   /* record->session_id_len = 0; - already is */
   memcpy(
     &mut (*record).cipherid_len16_hi as *mut u8 as *mut libc::c_void,
-    ciphers.as_ptr() as *const libc::c_void,
+    if (*tls).cert_sha256.is_null() {
+      ciphers.as_ptr()
+    } else {
+      rsa_ciphers.as_ptr()
+    } as *const libc::c_void,
     ::std::mem::size_of::<[u8; 20]>() as libc::c_ulong,
   );
   ptr = record.offset(1) as *mut libc::c_void as *mut u8;
@@ -2271,6 +2300,14 @@ unsafe extern "C" fn get_server_hello(mut tls: *mut tls_state_t) {
   /*tls->IV_size = 0; - already is */
   if *cipherid.offset(0) as libc::c_int == 0xc0i32 {
     /* All C0xx are ECDHE */
+    if !(*tls).cert_sha256.is_null() {
+      /* not offered, see rsa_ciphers */
+      bad_record_die(
+        tls,
+        b"\'server hello\'\x00" as *const u8 as *const libc::c_char,
+        len,
+      );
+    }
     (*tls).flags |= NEED_EC_KEY as libc::c_int as libc::c_uint;
     if cipherid1 as libc::c_int & 1i32 != 0 {
       /* Odd numbered C0xx use AES128 (even ones use AES256) */
@@ -2350,6 +2387,28 @@ unsafe extern "C" fn get_server_cert(mut tls: *mut tls_state_t) {
     tls_error_die(tls, 1823i32);
   }
   len = len1;
+  if !(*tls).cert_sha256.is_null() {
+    let mut ctx: md5_ctx_t = std::mem::zeroed();
+    let mut digest: [u8; 32] = [0; 32];
+    crate::libbb::hash_md5_sha::sha256_begin(&mut ctx);
+    crate::libbb::hash_md5_sha::md5_hash(
+      &mut ctx,
+      certbuf.offset(10) as *const libc::c_void,
+      len as size_t,
+    );
+    crate::libbb::hash_md5_sha::sha1_end(&mut ctx, digest.as_mut_ptr() as *mut libc::c_void);
+    if len == 0
+      || memcmp(
+        digest.as_mut_ptr() as *const libc::c_void,
+        (*tls).cert_sha256 as *const libc::c_void,
+        32i32 as libc::c_ulong,
+      ) != 0
+    {
+      crate::libbb::verror_msg::bb_simple_error_msg_and_die(
+        b"server certificate fingerprint mismatch\x00" as *const u8 as *const libc::c_char,
+      );
+    }
+  }
   if len != 0 {
     find_key_in_der_cert(tls, certbuf.offset(10), len);
   };
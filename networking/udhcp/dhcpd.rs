@@ -18,6 +18,7 @@ use libc::sockaddr;
 use libc::sockaddr_in;
 use libc::sockaddr_in6;
 use libc::strcasecmp;
+use libc::strchr;
 use libc::time;
 use libc::time_t;
 use libc::unlink;
@@ -39,6 +40,10 @@ extern "C" {
   #[no_mangle]
   fn memcmp(_: *const libc::c_void, _: *const libc::c_void, _: libc::c_ulong) -> libc::c_int;
   #[no_mangle]
+  fn strlen(__s: *const libc::c_char) -> size_t;
+  #[no_mangle]
+  fn strncasecmp(_: *const libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> libc::c_int;
+  #[no_mangle]
   fn strncpy(_: *mut libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> *mut libc::c_char;
   #[no_mangle]
   fn strtok_r(
@@ -167,12 +172,12 @@ extern "C" {
   /* ** Logging ***/
   #[no_mangle]
   static mut dhcp_verbose: libc::c_uint;
-/* ** Other shared functions ***/
-/* 2nd param is "u32*" */
+  /* ** Other shared functions ***/
+  /* 2nd param is "u32*" */
 
-/* 2nd param is "struct option_set**" */
+  /* 2nd param is "struct option_set**" */
 
-/* Returns 1 if no reply received */
+  /* Returns 1 if no reply received */
 
 }
 
@@ -374,6 +379,9 @@ pub struct static_lease {
   pub next: *mut static_lease,
   pub nip: u32,
   pub mac: [u8; 6],
+  /* STATIC_KEY_MAC, or client-id/hostname reservation: [len][bytes] in key */
+  pub key_type: u8,
+  pub key: *mut u8,
   pub opt: [u8; 1],
   /* interface to use */
   //TODO: ifindex, server_nip, server_mac
@@ -427,6 +435,25 @@ pub struct server_data_t {
   pub boot_file: *mut libc::c_char,
   pub static_leases: *mut static_lease,
   /* List of ip/mac pairs to assign static leases */
+  pub pools: *mut dhcp_pool,
+  pub lease_script: *mut libc::c_char,
+}
+
+/* Additional address pool, "pool START END [circuit_id=ID|remote_id=ID]".
+ * Options given by following "pool_option" lines override global ones
+ * for clients which get an address from this pool.
+ */
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct dhcp_pool {
+  pub next: *mut dhcp_pool,
+  /* host order, as server_data_t.start_ip/end_ip */
+  pub start_ip: u32,
+  pub end_ip: u32,
+  pub options: *mut option_set,
+  /* relay agent (option 82) sub-option to match: 0 = none */
+  pub relay_subopt: u8,
+  pub relay_id: *mut libc::c_char,
 }
 pub type leasetime_t = u32;
 pub type signed_leasetime_t = i32;
@@ -451,6 +478,12 @@ pub struct config_keyword {
   pub ofs: libc::c_uint,
   pub def: *const libc::c_char,
 }
+pub const STATIC_KEY_HOSTNAME: u8 = 2;
+pub const STATIC_KEY_CLIENTID: u8 = 1;
+pub const STATIC_KEY_MAC: u8 = 0;
+/* Relay agent information option (RFC 3046) sub-options */
+pub const RELAY_REMOTE_ID: u8 = 2;
+pub const RELAY_CIRCUIT_ID: u8 = 1;
 pub type C2RustUnnamed_3 = libc::c_uint;
 pub const KWS_WITH_DEFAULTS: C2RustUnnamed_3 = 12;
 #[inline(always)]
@@ -513,7 +546,7 @@ unsafe extern "C" fn add_static_lease(
   mut mac: *mut u8,
   mut nip: u32,
   mut opts: *const libc::c_char,
-) {
+) -> *mut static_lease {
   let mut st_lease: *mut static_lease = std::ptr::null_mut();
   let mut optlen: libc::c_uint = 0;
   optlen = if !opts.is_null() {
@@ -565,18 +598,62 @@ unsafe extern "C" fn add_static_lease(
       (*st_lease).mac[5] as libc::c_int,
       (*st_lease).nip,
     );
-  };
+  }
+  return st_lease;
 }
 /* Find static lease IP by mac */
 unsafe extern "C" fn get_static_nip_by_mac(mut mac: *mut libc::c_void) -> u32 {
   let mut st_lease: *mut static_lease =
     (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).static_leases;
   while !st_lease.is_null() {
-    if memcmp(
-      (*st_lease).mac.as_mut_ptr() as *const libc::c_void,
-      mac,
-      6i32 as libc::c_ulong,
-    ) == 0
+    if (*st_lease).key_type == STATIC_KEY_MAC
+      && memcmp(
+        (*st_lease).mac.as_mut_ptr() as *const libc::c_void,
+        mac,
+        6i32 as libc::c_ulong,
+      ) == 0
+    {
+      return (*st_lease).nip;
+    }
+    st_lease = (*st_lease).next
+  }
+  return 0 as u32;
+}
+/* Find reserved IP by client-id (option 61) or hostname (option 12) */
+unsafe extern "C" fn get_static_nip_by_id(mut packet: *mut dhcp_packet) -> u32 {
+  let mut st_lease: *mut static_lease =
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).static_leases;
+  let mut client_id: *mut u8 = std::ptr::null_mut();
+  let mut hostname: *mut u8 = std::ptr::null_mut();
+  while !st_lease.is_null() {
+    let mut opt: *mut u8 = std::ptr::null_mut();
+    if (*st_lease).key_type == STATIC_KEY_CLIENTID {
+      if client_id.is_null() {
+        client_id = crate::networking::udhcp::common::udhcp_get_option(packet, 0x3di32)
+      }
+      opt = client_id
+    } else if (*st_lease).key_type == STATIC_KEY_HOSTNAME {
+      if hostname.is_null() {
+        hostname = crate::networking::udhcp::common::udhcp_get_option(packet, 0xci32)
+      }
+      opt = hostname
+    }
+    /* opt[-1] is option length, key[0] is key length */
+    if !opt.is_null()
+      && *opt.offset(-1) as libc::c_int == *(*st_lease).key.offset(0) as libc::c_int
+      && (if (*st_lease).key_type == STATIC_KEY_HOSTNAME {
+        strncasecmp(
+          opt as *const libc::c_char,
+          (*st_lease).key.offset(1) as *const libc::c_char,
+          *opt.offset(-1) as libc::c_ulong,
+        )
+      } else {
+        memcmp(
+          opt as *const libc::c_void,
+          (*st_lease).key.offset(1) as *const libc::c_void,
+          *opt.offset(-1) as libc::c_ulong,
+        )
+      }) == 0
     {
       return (*st_lease).nip;
     }
@@ -595,6 +672,80 @@ unsafe extern "C" fn is_nip_reserved_as_static(mut nip: u32) -> libc::c_int {
   }
   return 0;
 }
+/* Netmask of the pool's subnet, host order: its "subnet" option, or /24 */
+unsafe extern "C" fn pool_netmask(mut pool: *mut dhcp_pool) -> u32 {
+  let mut opt: *mut option_set =
+    crate::networking::udhcp::common::udhcp_find_option((*pool).options, 0x1i32 as u8);
+  if !opt.is_null() && *(*opt).data.offset(1) as libc::c_int == 4i32 {
+    return u32::from_be(*((*opt).data.offset(2) as *mut bb__aliased_u32));
+  }
+  return 0xffffff00u32;
+}
+/* Find the pool whose subnet holds nip (network order), NULL if none */
+unsafe extern "C" fn find_pool_by_nip(mut nip: u32) -> *mut dhcp_pool {
+  let mut pool: *mut dhcp_pool = (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).pools;
+  let mut ip: u32 = u32::from_be(nip);
+  while !pool.is_null() {
+    let mut mask: u32 = pool_netmask(pool);
+    if nip != 0 && ip & mask == (*pool).start_ip & mask {
+      return pool;
+    }
+    pool = (*pool).next
+  }
+  return std::ptr::null_mut();
+}
+/* Is nip (network order) inside the pool's range, or the main range if pool is NULL */
+unsafe extern "C" fn nip_in_range(mut pool: *mut dhcp_pool, mut nip: u32) -> libc::c_int {
+  let mut ip: u32 = u32::from_be(nip);
+  if !pool.is_null() {
+    return (ip >= (*pool).start_ip && ip <= (*pool).end_ip) as libc::c_int;
+  }
+  return (ip >= (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).start_ip
+    && ip <= (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).end_ip)
+    as libc::c_int;
+}
+/* Choose the pool to allocate from: by relay agent information
+ * (option 82) first, then by relay's giaddr.
+ * NULL means the main start..end range.
+ */
+unsafe extern "C" fn select_pool(mut packet: *mut dhcp_packet) -> *mut dhcp_pool {
+  let mut pool: *mut dhcp_pool = std::ptr::null_mut();
+  let mut relay_info: *mut u8 = crate::networking::udhcp::common::udhcp_get_option(packet, 0x52i32);
+  if !relay_info.is_null() {
+    pool = (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).pools;
+    while !pool.is_null() {
+      if (*pool).relay_subopt != 0 {
+        /* sub-options: [code][len][data]... */
+        let mut len: libc::c_int = *relay_info.offset(-1) as libc::c_int;
+        let mut sub: *mut u8 = relay_info;
+        while len >= 2i32 && len >= 2i32 + *sub.offset(1) as libc::c_int {
+          let mut sublen: libc::c_int = *sub.offset(1) as libc::c_int;
+          if *sub.offset(0) == (*pool).relay_subopt
+            && strlen((*pool).relay_id) == sublen as size_t
+            && memcmp(
+              sub.offset(2) as *const libc::c_void,
+              (*pool).relay_id as *const libc::c_void,
+              sublen as libc::c_ulong,
+            ) == 0
+          {
+            return pool;
+          }
+          len -= 2i32 + sublen;
+          sub = sub.offset((2i32 + sublen) as isize)
+        }
+      }
+      pool = (*pool).next
+    }
+  }
+  if (*packet).gateway_nip != 0 {
+    pool = find_pool_by_nip((*packet).gateway_nip);
+    /* pools tied to a circuit/remote id are not chosen by subnet */
+    if !pool.is_null() && (*pool).relay_subopt == 0 {
+      return pool;
+    }
+  }
+  return std::ptr::null_mut();
+}
 /* Find the oldest expired lease, NULL if there are no expired leases */
 unsafe extern "C" fn oldest_expired_lease() -> *mut dyn_lease {
   let mut oldest_lease: *mut dyn_lease = std::ptr::null_mut();
@@ -777,10 +928,17 @@ unsafe extern "C" fn nobody_responds_to_arp(
 unsafe extern "C" fn find_free_or_expired_nip(
   mut safe_mac: *const u8,
   mut arpping_ms: libc::c_uint,
+  mut pool: *mut dhcp_pool,
 ) -> u32 {
   let mut addr: u32 = 0;
+  let mut end_ip: u32 = 0;
   let mut oldest_lease: *mut dyn_lease = std::ptr::null_mut();
   addr = (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).start_ip;
+  end_ip = (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).end_ip;
+  if !pool.is_null() {
+    addr = (*pool).start_ip;
+    end_ip = (*pool).end_ip
+  }
   loop {
     let mut nip: u32 = 0;
     let mut lease: *mut dyn_lease = std::ptr::null_mut();
@@ -824,11 +982,7 @@ unsafe extern "C" fn find_free_or_expired_nip(
       }
     }
     addr = addr.wrapping_add(1);
-    if !(addr
-      != (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t))
-        .end_ip
-        .wrapping_add(1i32 as libc::c_uint))
-    {
+    if !(addr != end_ip.wrapping_add(1i32 as libc::c_uint)) {
       break;
     }
   }
@@ -869,14 +1023,39 @@ unsafe extern "C" fn read_staticlease(
     ether_addr_octet: [0; 6],
   };
   let mut nip: u32 = 0;
-  /* Read mac */
+  let mut st_lease: *mut static_lease = std::ptr::null_mut();
+  let mut key_type: u8 = STATIC_KEY_MAC;
+  let mut key: *mut u8 = std::ptr::null_mut();
+  /* Read mac, or "id:CLIENT_ID" / "host:HOSTNAME" */
   line = const_line as *mut libc::c_char;
   mac_string = strtok_r(
     line,
     b" \t\x00" as *const u8 as *const libc::c_char,
     &mut line,
   );
-  if mac_string.is_null() || ether_aton_r(mac_string, &mut mac_bytes).is_null() {
+  if mac_string.is_null() {
+    return 0;
+  }
+  if strncasecmp(
+    mac_string,
+    b"id:\x00" as *const u8 as *const libc::c_char,
+    3i32 as libc::c_ulong,
+  ) == 0
+  {
+    key_type = STATIC_KEY_CLIENTID;
+    key = read_static_key(mac_string.offset(3), 1i32)
+  } else if strncasecmp(
+    mac_string,
+    b"host:\x00" as *const u8 as *const libc::c_char,
+    5i32 as libc::c_ulong,
+  ) == 0
+  {
+    key_type = STATIC_KEY_HOSTNAME;
+    key = read_static_key(mac_string.offset(5), 0)
+  } else if ether_aton_r(mac_string, &mut mac_bytes).is_null() {
+    return 0;
+  }
+  if key_type != STATIC_KEY_MAC && key.is_null() {
     return 0;
   }
   /* Read ip */
@@ -899,14 +1078,138 @@ unsafe extern "C" fn read_staticlease(
     &mut line,
   );
   /* opts might be NULL, that's not an error */
-  add_static_lease(
+  st_lease = add_static_lease(
     arg as *mut *mut static_lease,
     &mut mac_bytes as *mut ether_addr as *mut u8,
     nip,
     opts,
   );
+  (*st_lease).key_type = key_type;
+  (*st_lease).key = key;
   return 1i32;
 }
+/* "01:02:03:..." client-id is hex, anything else is taken literally.
+ * Returns malloced [len][bytes], NULL if empty or too long.
+ */
+unsafe extern "C" fn read_static_key(
+  mut str: *const libc::c_char,
+  mut allow_hex: libc::c_int,
+) -> *mut u8 {
+  let mut len: size_t = strlen(str);
+  let mut key: *mut u8 = std::ptr::null_mut();
+  if len == 0 || len > 255 {
+    return std::ptr::null_mut();
+  }
+  key = crate::libbb::xfuncs_printf::xmalloc(len.wrapping_add(1i32 as libc::c_ulong)) as *mut u8;
+  if allow_hex != 0 && !strchr(str, ':' as i32).is_null() {
+    let mut end: *mut libc::c_char =
+      crate::libbb::xfuncs::hex2bin(key.offset(1) as *mut libc::c_char, str, 255i32);
+    if !end.is_null() && *bb_errno == 0 {
+      *key.offset(0) = (end as usize - key.offset(1) as usize) as u8;
+      return key;
+    }
+  }
+  *key.offset(0) = len as u8;
+  memcpy(
+    key.offset(1) as *mut libc::c_void,
+    str as *const libc::c_void,
+    len,
+  );
+  return key;
+}
+/* "pool START END [circuit_id=ID|remote_id=ID]" */
+unsafe extern "C" fn read_pool(
+  mut const_line: *const libc::c_char,
+  mut arg: *mut libc::c_void,
+) -> libc::c_int {
+  let mut pool_pp: *mut *mut dhcp_pool = arg as *mut *mut dhcp_pool;
+  let mut line: *mut libc::c_char = const_line as *mut libc::c_char;
+  let mut start: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut end: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut relay: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut pool: *mut dhcp_pool = std::ptr::null_mut();
+  let mut start_nip: u32 = 0;
+  let mut end_nip: u32 = 0;
+  start = strtok_r(
+    line,
+    b" \t\x00" as *const u8 as *const libc::c_char,
+    &mut line,
+  );
+  end = strtok_r(
+    std::ptr::null_mut::<libc::c_char>(),
+    b" \t\x00" as *const u8 as *const libc::c_char,
+    &mut line,
+  );
+  if start.is_null()
+    || end.is_null()
+    || crate::networking::udhcp::common::udhcp_str2nip(
+      start,
+      &mut start_nip as *mut u32 as *mut libc::c_void,
+    ) == 0
+    || crate::networking::udhcp::common::udhcp_str2nip(
+      end,
+      &mut end_nip as *mut u32 as *mut libc::c_void,
+    ) == 0
+    || u32::from_be(start_nip) > u32::from_be(end_nip)
+  {
+    return 0;
+  }
+  pool = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<dhcp_pool>() as libc::c_ulong)
+    as *mut dhcp_pool;
+  (*pool).start_ip = u32::from_be(start_nip);
+  (*pool).end_ip = u32::from_be(end_nip);
+  relay = strtok_r(
+    std::ptr::null_mut::<libc::c_char>(),
+    b" \t\x00" as *const u8 as *const libc::c_char,
+    &mut line,
+  );
+  if !relay.is_null() {
+    if strncasecmp(
+      relay,
+      b"circuit_id=\x00" as *const u8 as *const libc::c_char,
+      11i32 as libc::c_ulong,
+    ) == 0
+    {
+      (*pool).relay_subopt = RELAY_CIRCUIT_ID;
+      relay = relay.offset(11)
+    } else if strncasecmp(
+      relay,
+      b"remote_id=\x00" as *const u8 as *const libc::c_char,
+      10i32 as libc::c_ulong,
+    ) == 0
+    {
+      (*pool).relay_subopt = RELAY_REMOTE_ID;
+      relay = relay.offset(10)
+    } else {
+      free(pool as *mut libc::c_void);
+      return 0;
+    }
+    (*pool).relay_id = crate::libbb::xfuncs_printf::xstrdup(relay)
+  }
+  /* Append, "pool_option" lines refer to the last pool */
+  while !(*pool_pp).is_null() {
+    pool_pp = &mut (**pool_pp).next
+  }
+  *pool_pp = pool;
+  return 1i32;
+}
+unsafe extern "C" fn read_pool_optset(
+  mut line: *const libc::c_char,
+  mut arg: *mut libc::c_void,
+) -> libc::c_int {
+  let mut pool: *mut dhcp_pool = *(arg as *mut *mut dhcp_pool);
+  if pool.is_null() {
+    /* "pool_option" before any "pool" */
+    return 0;
+  }
+  while !(*pool).next.is_null() {
+    pool = (*pool).next
+  }
+  return read_optset(
+    line,
+    &mut (*pool).options as *mut *mut option_set as *mut libc::c_void,
+  );
+}
 unsafe extern "C" fn read_optset(
   mut line: *const libc::c_char,
   mut arg: *mut libc::c_void,
@@ -920,7 +1223,16 @@ unsafe extern "C" fn read_optset(
   );
 }
 
-static mut keywords: [config_keyword; 18] = [
+/* Besides the classic single start..end range:
+ *  static_lease id:01:00:11:22:33:44:55 IP  - reserve IP by client-id (option 61)
+ *  static_lease host:NAME IP                - reserve IP by hostname (option 12)
+ *  pool START END [circuit_id=ID|remote_id=ID]
+ *      - more ranges, picked by relay agent info (option 82) if given,
+ *        else by the relay's giaddr falling into the pool's subnet
+ *  pool_option OPT VALUE                    - option for the last pool
+ *  lease_script FILE                        - "FILE add|old|del MAC IP [HOSTNAME]"
+ */
+static mut keywords: [config_keyword; 21] = [
   {
     let mut init = config_keyword {
       keyword: b"start\x00" as *const u8 as *const libc::c_char,
@@ -1137,6 +1449,42 @@ static mut keywords: [config_keyword; 18] = [
     };
     init
   },
+  {
+    let mut init = config_keyword {
+      keyword: b"pool\x00" as *const u8 as *const libc::c_char,
+      handler: Some(
+        read_pool
+          as unsafe extern "C" fn(_: *const libc::c_char, _: *mut libc::c_void) -> libc::c_int,
+      ),
+      ofs: 120u64 as libc::c_uint,
+      def: b"\x00" as *const u8 as *const libc::c_char,
+    };
+    init
+  },
+  {
+    let mut init = config_keyword {
+      keyword: b"pool_option\x00" as *const u8 as *const libc::c_char,
+      handler: Some(
+        read_pool_optset
+          as unsafe extern "C" fn(_: *const libc::c_char, _: *mut libc::c_void) -> libc::c_int,
+      ),
+      ofs: 120u64 as libc::c_uint,
+      def: b"\x00" as *const u8 as *const libc::c_char,
+    };
+    init
+  },
+  {
+    let mut init = config_keyword {
+      keyword: b"lease_script\x00" as *const u8 as *const libc::c_char,
+      handler: Some(
+        read_str
+          as unsafe extern "C" fn(_: *const libc::c_char, _: *mut libc::c_void) -> libc::c_int,
+      ),
+      ofs: 128u64 as libc::c_uint,
+      def: 0 as *const libc::c_char,
+    };
+    init
+  },
 ];
 
 #[inline(never)]
@@ -1167,7 +1515,7 @@ unsafe extern "C" fn read_config(mut file: *const libc::c_char) {
     k = keywords.as_ptr();
     i = 0 as libc::c_uint;
     while i
-      < (::std::mem::size_of::<[config_keyword; 18]>() as libc::c_ulong)
+      < (::std::mem::size_of::<[config_keyword; 21]>() as libc::c_ulong)
         .wrapping_div(::std::mem::size_of::<config_keyword>() as libc::c_ulong)
         as libc::c_uint
     {
@@ -1601,12 +1949,57 @@ unsafe extern "C" fn init_packet(
     (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).server_nip,
   );
 }
+/* RFC 3046: "DHCP servers claiming to support the Relay Agent Information
+ * option SHALL echo the entire contents of the Relay Agent Information
+ * option in all replies", as the last option.
+ */
+unsafe extern "C" fn add_relay_info(mut packet: *mut dhcp_packet, mut oldpacket: *mut dhcp_packet) {
+  let mut relay_info: *mut u8 =
+    crate::networking::udhcp::common::udhcp_get_option(oldpacket, 0x52i32);
+  if !relay_info.is_null() {
+    crate::networking::udhcp::common::udhcp_add_binary_option(packet, relay_info.offset(-2));
+  }
+}
+/* Run lease_script: "add" for a new binding, "old" for a renewal,
+ * "del" when client releases or declines the address.
+ */
+unsafe extern "C" fn run_lease_script(mut action: *const libc::c_char, mut lease: *mut dyn_lease) {
+  let mut argv: [*mut libc::c_char; 6] = [0 as *mut libc::c_char; 6];
+  let mut addr: in_addr = in_addr { s_addr: 0 };
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t))
+    .lease_script
+    .is_null()
+    || lease.is_null()
+  {
+    return;
+  }
+  addr.s_addr = (*lease).lease_nip;
+  argv[0] = (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).lease_script;
+  argv[1] = action as *mut libc::c_char;
+  argv[2] = crate::libbb::xfuncs_printf::xasprintf(
+    b"%02x:%02x:%02x:%02x:%02x:%02x\x00" as *const u8 as *const libc::c_char,
+    (*lease).lease_mac[0] as libc::c_int,
+    (*lease).lease_mac[1] as libc::c_int,
+    (*lease).lease_mac[2] as libc::c_int,
+    (*lease).lease_mac[3] as libc::c_int,
+    (*lease).lease_mac[4] as libc::c_int,
+    (*lease).lease_mac[5] as libc::c_int,
+  );
+  argv[3] = crate::libbb::xfuncs_printf::xstrdup(inet_ntoa(addr));
+  if (*lease).hostname[0] as libc::c_int != 0 {
+    argv[4] = (*lease).hostname.as_mut_ptr()
+  }
+  crate::libbb::vfork_daemon_rexec::spawn_and_wait(argv.as_mut_ptr());
+  free(argv[2] as *mut libc::c_void);
+  free(argv[3] as *mut libc::c_void);
+}
 /* Fill options field, siaddr_nip, and sname and boot_file fields.
  * TODO: teach this code to use overload option.
  */
 unsafe extern "C" fn add_server_options(mut packet: *mut dhcp_packet) {
   let mut config_opts: *mut option_set = std::ptr::null_mut();
   let mut client_hostname_opt: *mut u8 = std::ptr::null_mut();
+  let mut pool: *mut dhcp_pool = std::ptr::null_mut();
   client_hostname_opt = std::ptr::null_mut();
   if (*packet).yiaddr != 0 {
     /* if we aren't from send_inform()... */
@@ -1623,9 +2016,34 @@ unsafe extern "C" fn add_server_options(mut packet: *mut dhcp_packet) {
       }
     }
   }
+  /* Options of the address's pool take precedence over global ones */
+  pool = find_pool_by_nip(if (*packet).yiaddr != 0 {
+    (*packet).yiaddr
+  } else {
+    (*packet).ciaddr
+  });
+  if !pool.is_null() {
+    config_opts = (*pool).options;
+    while !config_opts.is_null() {
+      if *(*config_opts).data.offset(0) as libc::c_int != 0x33i32
+        && (*(*config_opts).data.offset(0) as libc::c_int != 0xci32
+          || client_hostname_opt.is_null())
+      {
+        crate::networking::udhcp::common::udhcp_add_binary_option(packet, (*config_opts).data);
+      }
+      config_opts = (*config_opts).next
+    }
+  }
   config_opts = (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).options;
   while !config_opts.is_null() {
-    if *(*config_opts).data.offset(0) as libc::c_int != 0x33i32 {
+    if *(*config_opts).data.offset(0) as libc::c_int != 0x33i32
+      && (pool.is_null()
+        || crate::networking::udhcp::common::udhcp_find_option(
+          (*pool).options,
+          *(*config_opts).data.offset(0),
+        )
+        .is_null())
+    {
       /* ^^^^
        * DHCP_LEASE_TIME is already filled, or in case of
        * send_inform(), should not be filled at all.
@@ -1732,66 +2150,35 @@ unsafe extern "C" fn send_offer(
     options: [0; 388],
   };
   let mut lease_time_sec: u32 = 0;
+  let mut pool: *mut dhcp_pool = std::ptr::null_mut();
   init_packet(&mut packet, oldpacket, 2i32 as libc::c_char);
+  pool = select_pool(oldpacket);
   /* If it is a static lease, use its IP */
   packet.yiaddr = static_lease_nip;
   /* Else: */
   if static_lease_nip == 0 {
     /* We have no static lease for client's chaddr */
     let mut p_host_name: *const libc::c_char = std::ptr::null();
-    if !lease.is_null() {
+    if !lease.is_null()
+      && ((*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t))
+        .pools
+        .is_null()
+        || nip_in_range(pool, (*lease).lease_nip) != 0)
+    {
       /* We have a dynamic lease for client's chaddr.
        * Reuse its IP (even if lease is expired).
        * Note that we ignore requested IP in this case.
        */
       packet.yiaddr = (*lease).lease_nip
-    } else if requested_nip != 0 as libc::c_uint
-      && ({
-        let mut __v: libc::c_uint = 0;
-        let mut __x: libc::c_uint = requested_nip;
-        if false {
-          __v = (__x & 0xff000000u32) >> 24i32
-            | (__x & 0xff0000i32 as libc::c_uint) >> 8i32
-            | (__x & 0xff00i32 as libc::c_uint) << 8i32
-            | (__x & 0xffi32 as libc::c_uint) << 24i32
-        } else {
-          let fresh31 = &mut __v;
-          let fresh32;
-          let fresh33 = __x;
-          llvm_asm!("bswap $0" : "=r" (fresh32) : "0"
-     (c2rust_asm_casts::AsmCast::cast_in(fresh31, fresh33)) :);
-          c2rust_asm_casts::AsmCast::cast_out(fresh31, fresh33, fresh32);
-        }
-        __v
-      }) >= (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).start_ip
-      && ({
-        let mut __v: libc::c_uint = 0;
-        let mut __x: libc::c_uint = requested_nip;
-        if false {
-          __v = (__x & 0xff000000u32) >> 24i32
-            | (__x & 0xff0000i32 as libc::c_uint) >> 8i32
-            | (__x & 0xff00i32 as libc::c_uint) << 8i32
-            | (__x & 0xffi32 as libc::c_uint) << 24i32
-        } else {
-          let fresh34 = &mut __v;
-          let fresh35;
-          let fresh36 = __x;
-          llvm_asm!("bswap $0" : "=r" (fresh35) : "0"
-     (c2rust_asm_casts::AsmCast::cast_in(fresh34, fresh36)) :);
-          c2rust_asm_casts::AsmCast::cast_out(fresh34, fresh36, fresh35);
-        }
-        __v
-      }) <= (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).end_ip
-      && {
-        lease = find_lease_by_nip(requested_nip);
-        (lease.is_null()) || is_expired_lease(lease) != 0
-      }
-    {
+    } else if requested_nip != 0 as libc::c_uint && nip_in_range(pool, requested_nip) != 0 && {
+      lease = find_lease_by_nip(requested_nip);
+      (lease.is_null()) || is_expired_lease(lease) != 0
+    } {
       packet.yiaddr = requested_nip
     } else {
       /* Or: if client has requested an IP */
       /* Otherwise, find a free IP */
-      packet.yiaddr = find_free_or_expired_nip((*oldpacket).chaddr.as_mut_ptr(), arpping_ms)
+      packet.yiaddr = find_free_or_expired_nip((*oldpacket).chaddr.as_mut_ptr(), arpping_ms, pool)
     }
     if packet.yiaddr == 0 {
       crate::libbb::verror_msg::bb_simple_error_msg(
@@ -1840,6 +2227,7 @@ unsafe extern "C" fn send_offer(
     __v
   });
   add_server_options(&mut packet);
+  add_relay_info(&mut packet, oldpacket);
   /* send_packet emits error message itself if it detects failure */
   send_packet_verbose(
     &mut packet,
@@ -1868,6 +2256,7 @@ unsafe extern "C" fn send_NAK(mut oldpacket: *mut dhcp_packet) {
     options: [0; 388],
   };
   init_packet(&mut packet, oldpacket, 6i32 as libc::c_char);
+  add_relay_info(&mut packet, oldpacket);
   if dhcp_verbose >= 1i32 as libc::c_uint {
     crate::libbb::verror_msg::bb_info_msg(
       b"sending %s\x00" as *const u8 as *const libc::c_char,
@@ -1899,6 +2288,8 @@ unsafe extern "C" fn send_ACK(mut oldpacket: *mut dhcp_packet, mut yiaddr: u32)
   };
   let mut lease_time_sec: u32 = 0;
   let mut p_host_name: *const libc::c_char = std::ptr::null();
+  let mut lease: *mut dyn_lease = std::ptr::null_mut();
+  let mut action: *const libc::c_char = b"add\x00" as *const u8 as *const libc::c_char;
   init_packet(&mut packet, oldpacket, 5i32 as libc::c_char);
   packet.yiaddr = yiaddr;
   lease_time_sec = select_lease_time(oldpacket);
@@ -1921,13 +2312,25 @@ unsafe extern "C" fn send_ACK(mut oldpacket: *mut dhcp_packet, mut yiaddr: u32)
     __v
   });
   add_server_options(&mut packet);
+  add_relay_info(&mut packet, oldpacket);
   send_packet_verbose(
     &mut packet,
     b"sending ACK to %s\x00" as *const u8 as *const libc::c_char,
   );
   p_host_name =
     crate::networking::udhcp::common::udhcp_get_option(oldpacket, 0xci32) as *const libc::c_char;
-  add_lease(
+  /* Offers are leased for offer_time only, so a lease which outlives
+   * that is a binding the client already had */
+  lease = find_lease_by_mac(packet.chaddr.as_mut_ptr());
+  if !lease.is_null()
+    && (*lease).lease_nip == yiaddr
+    && (*lease).expires as libc::c_long
+      > time(0 as *mut time_t)
+        + (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).offer_time as libc::c_long
+  {
+    action = b"old\x00" as *const u8 as *const libc::c_char
+  }
+  lease = add_lease(
     packet.chaddr.as_mut_ptr(),
     packet.yiaddr,
     lease_time_sec,
@@ -1940,6 +2343,7 @@ unsafe extern "C" fn send_ACK(mut oldpacket: *mut dhcp_packet, mut yiaddr: u32)
   );
   /* rewrite the file with leases at every new acceptance */
   write_leases();
+  run_lease_script(action, lease);
 }
 /* NOINLINE: limit stack usage in caller */
 #[inline(never)]
@@ -1981,6 +2385,7 @@ unsafe extern "C" fn send_inform(mut oldpacket: *mut dhcp_packet) {
   //Better yet: is ciaddr == IP source addr?
   init_packet(&mut packet, oldpacket, 5i32 as libc::c_char);
   add_server_options(&mut packet);
+  add_relay_info(&mut packet, oldpacket);
   send_packet(&mut packet, 0);
   // or maybe? send_packet_verbose(&packet, "sending ACK to %s");
 }
@@ -2084,6 +2489,16 @@ pub unsafe fn udhcpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_ch
     .end_ip
     .wrapping_sub((*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).start_ip)
     .wrapping_add(1i32 as libc::c_uint);
+  let mut pool: *mut dhcp_pool = (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).pools;
+  while !pool.is_null() {
+    num_ips = num_ips.wrapping_add(
+      (*pool)
+        .end_ip
+        .wrapping_sub((*pool).start_ip)
+        .wrapping_add(1i32 as libc::c_uint),
+    );
+    pool = (*pool).next
+  }
   if (*(bb_common_bufsiz1.as_mut_ptr() as *mut server_data_t)).max_leases > num_ips {
     crate::libbb::verror_msg::bb_error_msg(
       b"max_leases=%u is too big, setting to %u\x00" as *const u8 as *const libc::c_char,
@@ -2317,6 +2732,9 @@ pub unsafe fn udhcpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_ch
             /* Look for a static/dynamic lease */
             static_lease_nip =
               get_static_nip_by_mac(&mut packet.chaddr as *mut [u8; 16] as *mut libc::c_void);
+            if static_lease_nip == 0 {
+              static_lease_nip = get_static_nip_by_id(&mut packet)
+            }
             if static_lease_nip != 0 {
               crate::libbb::verror_msg::bb_info_msg(
                 b"found static lease: %x\x00" as *const u8 as *const libc::c_char,
@@ -2508,6 +2926,7 @@ pub unsafe fn udhcpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_ch
                   && !lease.is_null()
                   && requested_nip == (*lease).lease_nip
                 {
+                  run_lease_script(b"del\x00" as *const u8 as *const libc::c_char, lease);
                   memset(
                     (*lease).lease_mac.as_mut_ptr() as *mut libc::c_void,
                     0,
@@ -2537,7 +2956,8 @@ pub unsafe fn udhcpd_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_ch
                   && !lease.is_null()
                   && packet.ciaddr == (*lease).lease_nip
                 {
-                  (*lease).expires = time(0 as *mut time_t) as leasetime_t
+                  (*lease).expires = time(0 as *mut time_t) as leasetime_t;
+                  run_lease_script(b"del\x00" as *const u8 as *const libc::c_char, lease);
                 }
               }
               8 => {
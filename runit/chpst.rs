@@ -5,18 +5,28 @@ use libc;
 use libc::close;
 use libc::closedir;
 use libc::dirent;
+use libc::fclose;
+use libc::free;
 use libc::gid_t;
+use libc::kill;
 use libc::open;
 use libc::readdir;
 use libc::setsid;
+use libc::ssize_t;
 use libc::strchr;
+use libc::strrchr;
 use libc::DIR;
+use libc::FILE;
 extern "C" {
 
   #[no_mangle]
   fn unsetenv(__name: *const libc::c_char) -> libc::c_int;
   #[no_mangle]
   fn nice(__inc: libc::c_int) -> libc::c_int;
+  #[no_mangle]
+  fn atoi(__nptr: *const libc::c_char) -> libc::c_int;
+  #[no_mangle]
+  fn strlen(__s: *const libc::c_char) -> size_t;
 
   #[no_mangle]
   static mut optind: libc::c_int;
@@ -137,7 +147,8 @@ ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 //usage:#define chpst_trivial_usage
 //usage:       "[-vP012] [-u USER[:GRP]] [-U USER[:GRP]] [-e DIR]\n"
 //usage:       "	[-/ DIR] [-n NICE] [-m BYTES] [-d BYTES] [-o N]\n"
-//usage:       "	[-p N] [-f BYTES] [-c BYTES] PROG ARGS"
+//usage:       "	[-p N] [-f BYTES] [-c BYTES]\n"
+//usage:       "	[-C DIR [-M BYTES] [-Q CPU] [-T N] [-W N]] PROG ARGS"
 //usage:#define chpst_full_usage "\n\n"
 //usage:       "Change the process state, run PROG\n"
 //usage:     "\n	-u USER[:GRP]	Set uid and gid"
@@ -152,6 +163,11 @@ ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 //usage:     "\n	-p N		Limit number of processes per uid"
 //usage:     "\n	-f BYTES	Limit output file sizes"
 //usage:     "\n	-c BYTES	Limit core file size"
+//usage:     "\n	-C DIR		Create cgroup v2 DIR if needed, run PROG in it"
+//usage:     "\n	-M BYTES	Set memory.max of DIR"
+//usage:     "\n	-Q QUOTA[/PERIOD] | N%  Set cpu.max of DIR (usec)"
+//usage:     "\n	-T N		Set pids.max of DIR"
+//usage:     "\n	-W N		Set io.weight of DIR"
 //usage:     "\n	-v		Verbose"
 //usage:     "\n	-P		Create new process group"
 //usage:     "\n	-0		Close stdin"
@@ -222,6 +238,12 @@ Only softlimit and chpst are taking options:
 -v              Verbose
 -P              Create new process group
 -0 -1 -2        Close fd 0,1,2
+# chpst, cgroup v2
+-C DIR          Create cgroup DIR if needed and move into it
+-M BYTES        memory.max of DIR ("max" for no limit)
+-Q QUOTA[/PERIOD] or N%  cpu.max of DIR (usec per PERIOD, default 100000)
+-T N            pids.max of DIR
+-W N            io.weight of DIR (1..10000)
 
 Even though we accept all these options for both softlimit and chpst,
 they are not to be advertised on their help texts.
@@ -232,6 +254,11 @@ envdir, envuidgid, setuidgid take no options, but they reuse code which
 handles -e, -U and -u.
 */
 pub type C2RustUnnamed = libc::c_uint;
+pub const OPT_W: C2RustUnnamed = 33554432;
+pub const OPT_T: C2RustUnnamed = 16777216;
+pub const OPT_Q: C2RustUnnamed = 8388608;
+pub const OPT_M: C2RustUnnamed = 4194304;
+pub const OPT_C: C2RustUnnamed = 2097152;
 pub const OPT_2: C2RustUnnamed = 1048576;
 pub const OPT_1: C2RustUnnamed = 524288;
 pub const OPT_0: C2RustUnnamed = 262144;
@@ -343,6 +370,131 @@ unsafe extern "C" fn edir(mut directory_name: *const libc::c_char) {
   crate::libbb::xfuncs_printf::xfchdir(wdir);
   close(wdir);
 }
+/* cgroup v2: write VAL to DIR/FILE. Returns 0 on success */
+pub unsafe fn cgroup_write(
+  mut dir: *const libc::c_char,
+  mut file: *const libc::c_char,
+  mut val: *const libc::c_char,
+) -> libc::c_int {
+  let mut path: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(dir, file);
+  let mut fd: libc::c_int = open(path, 0o1i32);
+  let mut r: libc::c_int = -1i32;
+  free(path as *mut libc::c_void);
+  if fd >= 0 {
+    if crate::libbb::full_write::full_write(fd, val as *const libc::c_void, strlen(val))
+      == strlen(val) as ssize_t
+    {
+      r = 0
+    }
+    close(fd);
+  }
+  return r;
+}
+/* Kill every process in cgroup DIR. cgroup.kill needs Linux 5.14,
+ * older kernels get the members of cgroup.procs signalled one by one.
+ */
+pub unsafe fn cgroup_kill(mut dir: *const libc::c_char) -> libc::c_int {
+  let mut path: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut fp: *mut FILE = std::ptr::null_mut();
+  let mut line: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut n: libc::c_int = 0;
+  if cgroup_write(
+    dir,
+    b"cgroup.kill\x00" as *const u8 as *const libc::c_char,
+    b"1\x00" as *const u8 as *const libc::c_char,
+  ) == 0
+  {
+    return 1i32;
+  }
+  path = crate::libbb::concat_path_file::concat_path_file(
+    dir,
+    b"cgroup.procs\x00" as *const u8 as *const libc::c_char,
+  );
+  fp = crate::libbb::wfopen::fopen_for_read(path);
+  free(path as *mut libc::c_void);
+  if fp.is_null() {
+    return 0;
+  }
+  loop {
+    line = crate::libbb::get_line_from_file::xmalloc_fgetline(fp);
+    if line.is_null() {
+      break;
+    }
+    if kill(atoi(line), 9i32) == 0 {
+      n += 1
+    }
+    free(line as *mut libc::c_void);
+  }
+  fclose(fp);
+  return n;
+}
+/* Enable CONTROLLER for DIR in its parent's cgroup.subtree_control.
+ * Fails harmlessly if it is already enabled or not available.
+ */
+unsafe fn cgroup_enable(mut dir: *const libc::c_char, mut controller: *const libc::c_char) {
+  let mut parent: *mut libc::c_char = crate::libbb::xfuncs_printf::xstrdup(dir);
+  let mut val: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut slash: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  /* dirname, but "/sys/fs/cgroup/app/" -> "/sys/fs/cgroup" */
+  loop {
+    slash = crate::libbb::last_char_is::last_char_is(parent, '/' as i32);
+    if slash.is_null() || slash == parent {
+      break;
+    }
+    *slash = '\u{0}' as i32 as libc::c_char
+  }
+  slash = strrchr(parent, '/' as i32);
+  if !slash.is_null() {
+    *slash.offset((slash == parent) as libc::c_int as isize) = '\u{0}' as i32 as libc::c_char;
+    val = crate::libbb::xfuncs_printf::xasprintf(
+      b"+%s\x00" as *const u8 as *const libc::c_char,
+      controller,
+    );
+    cgroup_write(
+      parent,
+      b"cgroup.subtree_control\x00" as *const u8 as *const libc::c_char,
+      val,
+    );
+    free(val as *mut libc::c_void);
+  }
+  free(parent as *mut libc::c_void);
+}
+unsafe fn cgroup_set(
+  mut dir: *const libc::c_char,
+  mut controller: *const libc::c_char,
+  mut file: *const libc::c_char,
+  mut val: *const libc::c_char,
+) {
+  cgroup_enable(dir, controller);
+  if cgroup_write(dir, file, val) != 0 {
+    crate::libbb::perror_msg::bb_perror_msg_and_die(
+      b"can\'t set %s/%s to \'%s\'\x00" as *const u8 as *const libc::c_char,
+      dir,
+      file,
+      val,
+    );
+  }
+}
+/* -Q: "50%" or "QUOTA[/PERIOD]" -> "QUOTA PERIOD" for cpu.max */
+unsafe fn cpu_max(mut arg: *const libc::c_char) -> *mut libc::c_char {
+  let mut p: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  if !crate::libbb::last_char_is::last_char_is(arg, '%' as i32).is_null() {
+    return crate::libbb::xfuncs_printf::xasprintf(
+      b"%u 100000\x00" as *const u8 as *const libc::c_char,
+      (atoi(arg) as libc::c_uint).wrapping_mul(1000i32 as libc::c_uint),
+    );
+  }
+  p = strchr(arg, '/' as i32);
+  if p.is_null() {
+    return crate::libbb::xfuncs_printf::xasprintf(
+      b"%s 100000\x00" as *const u8 as *const libc::c_char,
+      arg,
+    );
+  }
+  p = crate::libbb::xfuncs_printf::xstrdup(arg);
+  *strchr(p, '/' as i32) = ' ' as i32 as libc::c_char;
+  return p;
+}
 unsafe extern "C" fn limit(mut what: libc::c_int, mut l: libc::c_long) {
   let mut r: rlimit = rlimit {
     rlim_cur: 0,
@@ -369,6 +521,11 @@ pub unsafe fn chpst_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
   env_dir = env_dir;
   let mut root: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut nicestr: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut cgroup: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut cg_mem: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut cg_cpu: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut cg_pids: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut cg_io: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut limita: libc::c_uint = 0;
   let mut limitc: libc::c_uint = 0;
   let mut limitd: libc::c_uint = 0;
@@ -389,7 +546,7 @@ pub unsafe fn chpst_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
     // if yes -> getopt converts strings to numbers for us
     opt = crate::libbb::getopt32::getopt32(
       argv,
-      b"^+a:+c:+d:+f:+l:+m:+o:+p:+r:+s:+t:+u:U:e:/:n:vP012\x00-1\x00" as *const u8
+      b"^+a:+c:+d:+f:+l:+m:+o:+p:+r:+s:+t:+u:U:e:/:n:vP012C:M:Q:T:W:\x00-1\x00" as *const u8
         as *const libc::c_char,
       &mut limita as *mut libc::c_uint,
       &mut limitc as *mut libc::c_uint,
@@ -407,6 +564,11 @@ pub unsafe fn chpst_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
       &mut env_dir as *mut *mut libc::c_char,
       &mut root as *mut *mut libc::c_char,
       &mut nicestr as *mut *mut libc::c_char,
+      &mut cgroup as *mut *mut libc::c_char,
+      &mut cg_mem as *mut *mut libc::c_char,
+      &mut cg_cpu as *mut *mut libc::c_char,
+      &mut cg_pids as *mut *mut libc::c_char,
+      &mut cg_io as *mut *mut libc::c_char,
     );
     argv = argv.offset(optind as isize);
     if opt & OPT_m as libc::c_int as libc::c_uint != 0 {
@@ -489,6 +651,70 @@ pub unsafe fn chpst_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
   if opt & OPT_P as libc::c_int as libc::c_uint != 0 {
     setsid();
   }
+  /* cgroup v2: before chroot (path) and setuid (permissions) */
+  if opt
+    & (OPT_M as libc::c_int | OPT_Q as libc::c_int | OPT_T as libc::c_int | OPT_W as libc::c_int)
+      as libc::c_uint
+    != 0
+    && opt & OPT_C as libc::c_int as libc::c_uint == 0
+  {
+    crate::libbb::verror_msg::bb_error_msg_and_die(
+      b"-M, -Q, -T and -W need -C\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  if opt & OPT_C as libc::c_int as libc::c_uint != 0 {
+    /* FILEUTILS_RECUR */
+    if crate::libbb::make_directory::bb_make_directory(cgroup, 0o755i32 as libc::c_long, 4i32) != 0
+    {
+      crate::libbb::xfunc_die::xfunc_die();
+    }
+    if opt & OPT_M as libc::c_int as libc::c_uint != 0 {
+      cgroup_set(
+        cgroup,
+        b"memory\x00" as *const u8 as *const libc::c_char,
+        b"memory.max\x00" as *const u8 as *const libc::c_char,
+        cg_mem,
+      );
+    }
+    if opt & OPT_Q as libc::c_int as libc::c_uint != 0 {
+      let mut val: *mut libc::c_char = cpu_max(cg_cpu);
+      cgroup_set(
+        cgroup,
+        b"cpu\x00" as *const u8 as *const libc::c_char,
+        b"cpu.max\x00" as *const u8 as *const libc::c_char,
+        val,
+      );
+      free(val as *mut libc::c_void);
+    }
+    if opt & OPT_T as libc::c_int as libc::c_uint != 0 {
+      cgroup_set(
+        cgroup,
+        b"pids\x00" as *const u8 as *const libc::c_char,
+        b"pids.max\x00" as *const u8 as *const libc::c_char,
+        cg_pids,
+      );
+    }
+    if opt & OPT_W as libc::c_int as libc::c_uint != 0 {
+      cgroup_set(
+        cgroup,
+        b"io\x00" as *const u8 as *const libc::c_char,
+        b"io.weight\x00" as *const u8 as *const libc::c_char,
+        cg_io,
+      );
+    }
+    /* "0" means the writing process */
+    if cgroup_write(
+      cgroup,
+      b"cgroup.procs\x00" as *const u8 as *const libc::c_char,
+      b"0\x00" as *const u8 as *const libc::c_char,
+    ) != 0
+    {
+      crate::libbb::perror_msg::bb_perror_msg_and_die(
+        b"can\'t move to cgroup %s\x00" as *const u8 as *const libc::c_char,
+        cgroup,
+      );
+    }
+  }
   if opt & OPT_e as libc::c_int as libc::c_uint != 0 {
     edir(env_dir);
   }
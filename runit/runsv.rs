@@ -14,6 +14,7 @@ use libc::open;
 use libc::pid_t;
 use libc::sleep;
 use libc::sprintf;
+use libc::sscanf;
 use libc::ssize_t;
 use libc::stat;
 use libc::strcpy;
@@ -41,6 +42,12 @@ extern "C" {
   #[no_mangle]
   fn read(__fd: libc::c_int, __buf: *mut libc::c_void, __nbytes: size_t) -> ssize_t;
 
+  #[no_mangle]
+  fn strchrnul(__s: *const libc::c_char, __c: libc::c_int) -> *mut libc::c_char;
+
+  #[no_mangle]
+  fn memchr(_: *const libc::c_void, _: libc::c_int, _: libc::c_ulong) -> *mut libc::c_void;
+
   #[no_mangle]
   fn memset(_: *mut libc::c_void, _: libc::c_int, _: libc::c_ulong) -> *mut libc::c_void;
 
@@ -52,6 +59,10 @@ extern "C" {
   #[no_mangle]
   fn mkdir(__path: *const libc::c_char, __mode: mode_t) -> libc::c_int;
   #[no_mangle]
+  fn rmdir(__path: *const libc::c_char) -> libc::c_int;
+  #[no_mangle]
+  fn write(__fd: libc::c_int, __buf: *const libc::c_void, __n: size_t) -> ssize_t;
+  #[no_mangle]
   fn mkfifo(__path: *const libc::c_char, __mode: mode_t) -> libc::c_int;
   #[no_mangle]
   fn clock_gettime(__clock_id: clockid_t, __tp: *mut timespec) -> libc::c_int;
@@ -93,6 +104,17 @@ pub struct svdir {
   pub fdcontrol: libc::c_int,
   pub fdcontrolwrite: libc::c_int,
   pub wstat: libc::c_int,
+  pub fdnotify: libc::c_int,
+  pub ready: smallint,
+  pub exited: smallint,
+  pub last_wstat: libc::c_int,
+  pub restarts: libc::c_uint,
+  pub crashes: libc::c_uint,
+  pub restart_delay: libc::c_uint,
+  pub restart_at: libc::c_uint,
+  pub reason: *const libc::c_char,
+  pub failed: smallint,
+  pub cgroup: *mut libc::c_char,
 }
 
 #[repr(C)]
@@ -105,6 +127,11 @@ pub struct svstatus_t {
   pub want: u8,
   pub got_term: u8,
   pub run_or_finish: u8,
+  pub ready: u8,
+  pub exited: u8,
+  pub failed: u8,
+  pub wstat_le32: u32,
+  pub restarts_le32: u32,
 }
 unsafe fn gettimeofday_ns(mut ts: *mut timespec) {
   clock_gettime(0i32, ts);
@@ -185,6 +212,11 @@ unsafe fn update_status(mut s: *mut svdir) {
     want: 0,
     got_term: 0,
     run_or_finish: 0,
+    ready: 0,
+    exited: 0,
+    failed: 0,
+    wstat_le32: 0,
+    restarts_le32: 0,
   };
   let mut fstatus: *const libc::c_char =
     b"log/supervise/status\x00" as *const u8 as *const libc::c_char;
@@ -245,6 +277,9 @@ unsafe fn update_status(mut s: *mut svdir) {
   if (*s).ctrl as libc::c_int & 1i32 != 0 {
     p = stpcpy(p, b", got TERM\x00" as *const u8 as *const libc::c_char)
   }
+  if (*s).failed != 0 {
+    p = stpcpy(p, b", failed\x00" as *const u8 as *const libc::c_char)
+  }
   if (*s).state as libc::c_int != 0 {
     match (*s).sd_want as libc::c_int {
       1 => p = stpcpy(p, b", want down\x00" as *const u8 as *const libc::c_char),
@@ -322,6 +357,12 @@ unsafe fn update_status(mut s: *mut svdir) {
     status.got_term = 1i32 as u8
   }
   status.run_or_finish = (*s).state as u8;
+  status.ready = (*s).ready as u8;
+  /* how ./run last exited, and how many times it was started again */
+  status.exited = (*s).exited as u8;
+  status.failed = (*s).failed as u8;
+  status.wstat_le32 = (*s).last_wstat as u32;
+  status.restarts_le32 = (*s).restarts;
   fd = open_trunc_or_warn(fstatusnew);
   if fd < 0 {
     return;
@@ -424,10 +465,262 @@ unsafe fn stopservice(mut s: *mut svdir) {
     custom(s, 'x' as i32 as libc::c_char);
   };
 }
+/* With cgroup v2 mounted, each service (and its log) runs in its own
+ * cgroup: ./cgroup names it, default is /sys/fs/cgroup/runit/SERVICE.
+ * "sv kill" then kills the whole process tree, not just ./run.
+ */
+unsafe fn setup_cgroup() {
+  let mut dir: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut i: libc::c_int = 0;
+  if libc::access(
+    b"/sys/fs/cgroup/cgroup.controllers\x00" as *const u8 as *const libc::c_char,
+    0,
+  ) != 0
+  {
+    return;
+  }
+  dir = crate::libbb::read_printf::xmalloc_open_read_close(
+    b"cgroup\x00" as *const u8 as *const libc::c_char,
+    std::ptr::null_mut(),
+  ) as *mut libc::c_char;
+  if !dir.is_null() {
+    crate::libbb::trim::trim(dir);
+    if *dir == 0 {
+      libc::free(dir as *mut libc::c_void);
+      return;
+    }
+  } else {
+    let mut cwd: *mut libc::c_char =
+      crate::libbb::xreadlink::xmalloc_realpath(b".\x00" as *const u8 as *const libc::c_char);
+    if cwd.is_null() {
+      return;
+    }
+    dir = crate::libbb::xfuncs_printf::xasprintf(
+      b"/sys/fs/cgroup/runit/%s\x00" as *const u8 as *const libc::c_char,
+      crate::libbb::get_last_path_component::bb_basename(cwd),
+    );
+    libc::free(cwd as *mut libc::c_void);
+  }
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].cgroup = dir;
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).haslog != 0 {
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].cgroup =
+      crate::libbb::xfuncs_printf::xasprintf(
+        b"%s-log\x00" as *const u8 as *const libc::c_char,
+        dir,
+      );
+  }
+  while i < 2i32 {
+    let mut cg: *mut libc::c_char =
+      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[i as usize].cgroup;
+    /* FILEUTILS_RECUR */
+    if !cg.is_null()
+      && crate::libbb::make_directory::bb_make_directory(cg, 0o755i32 as libc::c_long, 4i32) != 0
+    {
+      warn2_cannot(
+        b"create cgroup \x00" as *const u8 as *const libc::c_char,
+        cg,
+      );
+      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[i as usize].cgroup =
+        std::ptr::null_mut()
+    }
+    i += 1
+  }
+}
+/* Opens cgroup.procs of the service's cgroup, for the child to join.
+ * The cgroup is removed whenever the service stops: create it again.
+ */
+unsafe fn cgroup_procs_open(mut s: *mut svdir) -> libc::c_int {
+  let mut path: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut fd: libc::c_int = -1i32;
+  if (*s).cgroup.is_null() {
+    return -1i32;
+  }
+  mkdir((*s).cgroup, 0o755i32 as mode_t);
+  path = crate::libbb::concat_path_file::concat_path_file(
+    (*s).cgroup,
+    b"cgroup.procs\x00" as *const u8 as *const libc::c_char,
+  );
+  /* O_WRONLY | O_CLOEXEC */
+  fd = open(path, 0o1i32 | 0o2000000i32);
+  if fd == -1i32 {
+    warn2_cannot(b"open \x00" as *const u8 as *const libc::c_char, path);
+  }
+  libc::free(path as *mut libc::c_void);
+  return fd;
+}
+/* Fails with EBUSY if something ./run started is still running */
+unsafe fn cgroup_remove(mut s: *mut svdir) {
+  if !(*s).cgroup.is_null() {
+    rmdir((*s).cgroup);
+  }
+}
+/* ./notification-fd holds the fd number ./run reports readiness on */
+unsafe fn read_notification_fd() -> libc::c_int {
+  let mut buf: [libc::c_char; 12] = [0; 12];
+  let mut len: ssize_t = crate::libbb::read::open_read_close(
+    b"notification-fd\x00" as *const u8 as *const libc::c_char,
+    buf.as_mut_ptr() as *mut libc::c_void,
+    (::std::mem::size_of::<[libc::c_char; 12]>() as libc::c_ulong)
+      .wrapping_sub(1i32 as libc::c_ulong),
+  );
+  let mut fd: libc::c_int = 0;
+  if len <= 0 {
+    return -1i32;
+  }
+  buf[len as usize] = '\u{0}' as i32 as libc::c_char;
+  *strchrnul(buf.as_mut_ptr(), '\n' as i32) = '\u{0}' as i32 as libc::c_char;
+  fd = crate::libbb::bb_strtonum::bb_strtou(
+    buf.as_mut_ptr(),
+    std::ptr::null_mut::<*mut libc::c_char>(),
+    10i32,
+  ) as libc::c_int;
+  if *bb_errno != 0 || fd < 3i32 {
+    *bb_errno = 0;
+    warn_cannot(b"use notification-fd: bad fd number\x00" as *const u8 as *const libc::c_char);
+    return -1i32;
+  }
+  return fd;
+}
+/* Data on the readiness pipe: a newline means ./run is ready */
+unsafe fn check_ready(mut s: *mut svdir) {
+  let mut buf: [libc::c_char; 64] = [0; 64];
+  let mut r: ssize_t = 0;
+  loop {
+    r = read(
+      (*s).fdnotify,
+      buf.as_mut_ptr() as *mut libc::c_void,
+      ::std::mem::size_of::<[libc::c_char; 64]>() as libc::c_ulong,
+    );
+    if r <= 0 {
+      break;
+    }
+    if !memchr(
+      buf.as_mut_ptr() as *const libc::c_void,
+      '\n' as i32,
+      r as libc::c_ulong,
+    )
+    .is_null()
+    {
+      (*s).ready = 1i32 as smallint;
+      update_status(s);
+      break;
+    }
+  }
+  /* ready, or ./run closed it without saying so: stop listening */
+  if (*s).ready as libc::c_int != 0 || r == 0 {
+    close((*s).fdnotify);
+    (*s).fdnotify = -1i32
+  }
+}
+/* Reads up to two unsigned numbers from a one-line file */
+unsafe fn read_uints(
+  mut fname: *const libc::c_char,
+  mut v1: *mut libc::c_uint,
+  mut v2: *mut libc::c_uint,
+) -> libc::c_int {
+  let mut buf: [libc::c_char; 64] = [0; 64];
+  let mut len: ssize_t = crate::libbb::read::open_read_close(
+    fname,
+    buf.as_mut_ptr() as *mut libc::c_void,
+    (::std::mem::size_of::<[libc::c_char; 64]>() as libc::c_ulong)
+      .wrapping_sub(1i32 as libc::c_ulong),
+  );
+  if len <= 0 {
+    return 0;
+  }
+  buf[len as usize] = '\u{0}' as i32 as libc::c_char;
+  return sscanf(
+    buf.as_mut_ptr(),
+    b"%u %u\x00" as *const u8 as *const libc::c_char,
+    v1,
+    v2,
+  );
+}
+/* ./run has exited. Decide how long to wait before restarting it:
+ * ./restart-delay holds "MIN [MAX]" seconds; the delay starts at MIN and
+ * doubles with every crash in a row, up to MAX. A run that stays up for
+ * MAX seconds resets the count. ./restart-limit holds the number of
+ * crashes in a row after which the service is marked failed and left down.
+ * Without these files a run that dies within a second waits one second.
+ */
+unsafe fn run_exited(mut s: *mut svdir) {
+  let mut now: timespec = timespec {
+    tv_sec: 0,
+    tv_nsec: 0,
+  };
+  let mut dmin: libc::c_uint = 1i32 as libc::c_uint;
+  let mut dmax: libc::c_uint = 0 as libc::c_uint;
+  let mut limit: libc::c_uint = 0 as libc::c_uint;
+  let mut uptime: libc::c_long = 0;
+  let mut delay: libc::c_uint = 0;
+  gettimeofday_ns(&mut now);
+  uptime = now.tv_sec - (*s).start.tv_sec;
+  if (*s).islog == 0 {
+    if read_uints(
+      b"restart-delay\x00" as *const u8 as *const libc::c_char,
+      &mut dmin,
+      &mut dmax,
+    ) < 2i32
+    {
+      dmax = dmin
+    }
+    read_uints(
+      b"restart-limit\x00" as *const u8 as *const libc::c_char,
+      &mut limit,
+      &mut delay,
+    );
+    delay = 0 as libc::c_uint;
+  }
+  if dmax < dmin {
+    dmax = dmin
+  }
+  (*s).reason = if (*s).wstat & 0x7fi32 != 0 {
+    b"signal\x00" as *const u8 as *const libc::c_char
+  } else {
+    b"exit\x00" as *const u8 as *const libc::c_char
+  };
+  if (*s).ctrl as libc::c_int & 1i32 != 0 || (*s).sd_want as libc::c_int != 0 {
+    (*s).reason = b"down\x00" as *const u8 as *const libc::c_char;
+    (*s).crashes = 0 as libc::c_uint
+  } else if uptime >= (if dmax > 1 { dmax } else { 1 }) as libc::c_long {
+    (*s).crashes = 0 as libc::c_uint
+  } else {
+    (*s).crashes = (*s).crashes.wrapping_add(1);
+    delay = dmin;
+    let mut i: libc::c_uint = 1i32 as libc::c_uint;
+    while i < (*s).crashes && delay < dmax {
+      delay = delay.wrapping_mul(2i32 as libc::c_uint);
+      i = i.wrapping_add(1)
+    }
+    if delay > dmax {
+      delay = dmax
+    }
+    if limit != 0 && (*s).crashes >= limit {
+      crate::libbb::verror_msg::bb_error_msg(
+        b"%s: %u crashes in a row, giving up\x00" as *const u8 as *const libc::c_char,
+        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).dir,
+        (*s).crashes,
+      );
+      (*s).reason = b"failed\x00" as *const u8 as *const libc::c_char;
+      (*s).failed = 1i32 as smallint;
+      (*s).sd_want = 1i32 as smallint
+    }
+  }
+  if uptime < 1 && delay < 1 {
+    delay = 1i32 as libc::c_uint
+  }
+  (*s).restart_delay = delay;
+}
 unsafe fn startservice(mut s: *mut svdir) {
   let mut p: libc::c_int = 0;
-  let mut arg: [*const libc::c_char; 4] = [0 as *const libc::c_char; 4];
+  let mut arg: [*const libc::c_char; 5] = [0 as *const libc::c_char; 5];
   let mut exitcode: [libc::c_char; 14] = [0; 14];
+  let mut notify: fd_pair = fd_pair {
+    rd: -1i32,
+    wr: -1i32,
+  };
+  let mut notify_fd: libc::c_int = -1i32;
+  let mut cgroup_fd: libc::c_int = -1i32;
   if (*s).state as libc::c_int == 2i32 {
     /* Two arguments are given to ./finish. The first one is ./run exit code,
      * or -1 if ./run didnt exit normally. The second one is
@@ -450,15 +743,32 @@ unsafe fn startservice(mut s: *mut svdir) {
     //if (WIFSIGNALED(s->wstat)) {
     arg[2] = crate::libbb::xfuncs::utoa(((*s).wstat & 0x7fi32) as libc::c_uint);
     //}
-    arg[3] = std::ptr::null()
+    /* The third one says why: "exit", "signal", "down" (we were asked
+     * to stop it) or "failed" (restart-limit crashes in a row).
+     */
+    arg[3] = (*s).reason;
+    arg[4] = std::ptr::null()
   } else {
     arg[0] = b"./run\x00" as *const u8 as *const libc::c_char; /* should never happen */
     arg[1] = std::ptr::null();
     custom(s, 'u' as i32 as libc::c_char);
+    /* s6-style readiness: ./run writes a newline to fd N when ready */
+    if (*s).islog == 0 {
+      notify_fd = read_notification_fd()
+    }
   }
   if (*s).pid != 0 {
     stopservice(s);
   }
+  if (*s).fdnotify >= 0 {
+    close((*s).fdnotify);
+    (*s).fdnotify = -1i32
+  }
+  if notify_fd >= 0 {
+    crate::libbb::xfuncs_printf::xpipe(&mut notify.rd);
+  }
+  /* vfork child must not malloc: open it here, the child only writes */
+  cgroup_fd = cgroup_procs_open(s);
   loop {
     p = vfork();
     if !(p == -1i32) {
@@ -503,6 +813,21 @@ unsafe fn startservice(mut s: *mut svdir) {
     + (1 << SIGCHLD)
     + (1 << SIGTERM)
     , SIG_DFL);*/
+    if notify_fd >= 0 {
+      close(notify.rd);
+      if notify.wr != notify_fd {
+        crate::libbb::xfuncs_printf::xdup2(notify.wr, notify_fd);
+        close(notify.wr);
+      }
+    }
+    if cgroup_fd >= 0 {
+      /* "0" means the writing process */
+      write(
+        cgroup_fd,
+        b"0\x00" as *const u8 as *const libc::c_char as *const libc::c_void,
+        1i32 as size_t,
+      );
+    }
     crate::libbb::signals::sig_unblock(17i32);
     crate::libbb::signals::sig_unblock(15i32);
     execv(
@@ -519,9 +844,22 @@ unsafe fn startservice(mut s: *mut svdir) {
     );
   }
   /* parent */
+  if cgroup_fd >= 0 {
+    close(cgroup_fd);
+  }
   if (*s).state as libc::c_int != 2i32 {
     gettimeofday_ns(&mut (*s).start);
-    (*s).state = 1i32 as smallint
+    if (*s).exited != 0 {
+      (*s).restarts = (*s).restarts.wrapping_add(1)
+    }
+    (*s).state = 1i32 as smallint;
+    (*s).ready = (notify_fd < 0) as libc::c_int as smallint
+  }
+  if notify_fd >= 0 {
+    close(notify.wr);
+    crate::libbb::xfuncs::close_on_exec_on(notify.rd);
+    crate::libbb::xfuncs::ndelay_on(notify.rd);
+    (*s).fdnotify = notify.rd
   }
   (*s).pid = p;
   (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).pidchanged = 1i32 as smallint;
@@ -544,6 +882,9 @@ unsafe fn ctrl(mut s: *mut svdir, mut c: libc::c_char) -> libc::c_int {
     117 => {
       /* up */
       (*s).sd_want = 0 as smallint;
+      (*s).failed = 0 as smallint;
+      (*s).crashes = 0 as libc::c_uint;
+      (*s).restart_at = 0 as libc::c_uint;
       update_status(s);
       if (*s).state as libc::c_int == 0 {
         startservice(s);
@@ -567,6 +908,10 @@ unsafe fn ctrl(mut s: *mut svdir, mut c: libc::c_char) -> libc::c_int {
       /* sig kill */
       if (*s).state as libc::c_int == 1i32 && custom(s, c) == 0 {
         kill((*s).pid, 9i32);
+        /* and everything it forked */
+        if !(*s).cgroup.is_null() {
+          crate::runit::chpst::cgroup_kill((*s).cgroup);
+        }
       }
       (*s).state = 0 as smallint;
       current_block = 18435049525520518667;
@@ -592,6 +937,8 @@ unsafe fn ctrl(mut s: *mut svdir, mut c: libc::c_char) -> libc::c_int {
     111 => {
       /* once */
       (*s).sd_want = 1i32 as smallint;
+      (*s).failed = 0 as smallint;
+      (*s).crashes = 0 as libc::c_uint;
       update_status(s);
       if (*s).state as libc::c_int == 0 {
         startservice(s);
@@ -712,6 +1059,8 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
   /* otherwise already 0 (bss) */
   /* bss: svd[0].islog = 0; */
   /* bss: svd[1].pid = 0; */
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].fdnotify = -1i32;
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].fdnotify = -1i32;
   gettimeofday_ns(
     &mut (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
       .svd
@@ -761,6 +1110,7 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
         .wr,
     );
   }
+  setup_cgroup();
   if mkdir(
     b"supervise\x00" as *const u8 as *const libc::c_char,
     0o700i32 as mode_t,
@@ -887,12 +1237,14 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
     crate::libbb::xfuncs::close_on_exec_on(fd);
   }
   loop {
-    let mut x: [pollfd; 3] = [pollfd {
+    let mut x: [pollfd; 4] = [pollfd {
       fd: 0,
       events: 0,
       revents: 0,
-    }; 3];
+    }; 4];
+    let mut nfds: libc::c_int = 0;
     let mut deadline: libc::c_uint = 0;
+    let mut timeout: libc::c_int = 3600i32 * 1000i32;
     let mut ch: libc::c_char = 0;
     if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).haslog != 0 {
       if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].pid == 0
@@ -907,7 +1259,17 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
       }
     }
     if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].pid == 0 {
-      if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].sd_want as libc::c_int == 0
+      /* backing off after a crash? */
+      deadline = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0]
+        .restart_at
+        .wrapping_sub(crate::libbb::time::monotonic_sec());
+      if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].state as libc::c_int == 0
+        && (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].sd_want as libc::c_int == 0
+        && deadline as libc::c_int > 0
+      {
+        timeout = deadline as libc::c_int * 1000i32
+      } else if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].sd_want as libc::c_int
+        == 0
         || (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].state as libc::c_int == 2i32
       {
         startservice(
@@ -927,13 +1289,15 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
     /* x[2] is used only if haslog == 1 */
     x[2].fd = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].fdcontrol; /* for (;;) */
     x[2].events = 0x1i32 as libc::c_short;
+    nfds = 2i32 + (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).haslog as libc::c_int;
+    if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].fdnotify >= 0 {
+      x[nfds as usize].fd = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].fdnotify;
+      x[nfds as usize].events = 0x1i32 as libc::c_short;
+      nfds += 1
+    }
     crate::libbb::signals::sig_unblock(15i32);
     crate::libbb::signals::sig_unblock(17i32);
-    poll(
-      x.as_mut_ptr(),
-      (2i32 + (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).haslog as libc::c_int) as nfds_t,
-      3600i32 * 1000i32,
-    );
+    poll(x.as_mut_ptr(), nfds as nfds_t, timeout);
     crate::libbb::signals::sig_block(15i32);
     crate::libbb::signals::sig_block(17i32);
     while read(
@@ -944,6 +1308,14 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
       1i32 as size_t,
     ) == 1
     {}
+    if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].fdnotify >= 0 {
+      check_ready(
+        &mut *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+          .svd
+          .as_mut_ptr()
+          .offset(0),
+      );
+    }
     loop {
       let mut child: pid_t = 0;
       let mut wstat: libc::c_int = 0;
@@ -956,8 +1328,30 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
       }
       if child == (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].pid {
         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].wstat = wstat;
+        if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].state as libc::c_int != 2i32 {
+          /* it was ./run, not ./finish */
+          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].last_wstat = wstat;
+          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].exited = 1i32 as smallint;
+          run_exited(
+            &mut *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+              .svd
+              .as_mut_ptr()
+              .offset(0),
+          );
+        }
         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].pid = 0;
         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).pidchanged = 1i32 as smallint;
+        cgroup_remove(
+          &mut *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+            .svd
+            .as_mut_ptr()
+            .offset(0),
+        );
+        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].ready = 0 as smallint;
+        if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].fdnotify >= 0 {
+          close((*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].fdnotify);
+          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].fdnotify = -1i32
+        }
         let ref mut fresh8 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].ctrl;
         *fresh8 = (*fresh8 as libc::c_int & !1i32) as smallint;
         if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].state as libc::c_int != 2i32 {
@@ -978,10 +1372,9 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
           }
         }
         (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].state = 0 as smallint;
-        deadline = ((*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0]
-          .start
-          .tv_sec
-          + 1) as libc::c_uint;
+        (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].restart_at =
+          crate::libbb::time::monotonic_sec()
+            .wrapping_add((*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].restart_delay);
         gettimeofday_ns(
           &mut (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
             .svd
@@ -995,22 +1388,22 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
             .as_mut_ptr()
             .offset(0),
         );
-        if deadline.wrapping_sub(
-          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0]
-            .start
-            .tv_sec as libc::c_uint,
-        ) as libc::c_int
-          > 0
-        {
-          sleep(1i32 as libc::c_uint);
-        }
       }
       if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).haslog != 0 {
         if child == (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].pid {
           (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].wstat = wstat;
+          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].last_wstat = wstat;
+          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].exited = 1i32 as smallint;
           (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].pid = 0;
           (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).pidchanged = 1i32 as smallint;
+          cgroup_remove(
+            &mut *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+              .svd
+              .as_mut_ptr()
+              .offset(1),
+          );
           (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].state = 0 as smallint;
+          (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].ready = 0 as smallint;
           let ref mut fresh9 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].ctrl;
           *fresh9 = (*fresh9 as libc::c_int & !1i32) as smallint;
           deadline = ((*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1]
@@ -1086,6 +1479,19 @@ pub unsafe fn runsv_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_cha
       && (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[0].state as libc::c_int == 0
     {
       if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].pid == 0 {
+        /* retry: leftovers may have exited since the services stopped */
+        cgroup_remove(
+          &mut *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+            .svd
+            .as_mut_ptr()
+            .offset(0),
+        );
+        cgroup_remove(
+          &mut *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+            .svd
+            .as_mut_ptr()
+            .offset(1),
+        );
         _exit(0i32);
       }
       if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svd[1].sd_want as libc::c_int != 2i32 {
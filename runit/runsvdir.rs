@@ -48,7 +48,9 @@ extern "C" {
   static mut bb_common_bufsiz1: [libc::c_char; 0];
 }
 
+use crate::libbb::llist::llist_t;
 use libc::dirent;
+use libc::free;
 use libc::time_t;
 use libc::DIR;
 
@@ -66,6 +68,9 @@ pub struct service {
   pub ino: ino_t,
   pub pid: pid_t,
   pub isgone: smallint,
+  pub waiting: smallint,
+  pub stopping: smallint,
+  pub name: *mut libc::c_char,
 }
 unsafe extern "C" fn fatal2_cannot(mut m1: *const libc::c_char, mut m2: *const libc::c_char) {
   crate::libbb::perror_msg::bb_perror_msg_and_die(
@@ -131,6 +136,129 @@ unsafe extern "C" fn runsv(mut name: *const libc::c_char) -> pid_t {
   }
   return pid;
 }
+/* Are the services listed in NAME/depends up (and ready)?
+ * We are in svdir, so dependency names are usable as paths.
+ */
+unsafe fn depends_up(mut name: *const libc::c_char, mut waiting: *mut smallint) -> bool {
+  let mut deps: *mut llist_t = crate::runit::sv::svdepends_read(name);
+  let mut up: bool = true;
+  while !deps.is_null() {
+    let mut dep: *mut libc::c_char = crate::libbb::llist::llist_pop(&mut deps) as *mut libc::c_char;
+    if up && !crate::runit::sv::svstatus_up(dep) {
+      if *waiting == 0 {
+        warn3x(
+          name,
+          b": waiting for \x00" as *const u8 as *const libc::c_char,
+          dep,
+        );
+        *waiting = 1i32 as smallint
+      }
+      up = false
+    }
+    free(dep as *mut libc::c_void);
+  }
+  if up {
+    *waiting = 0 as smallint
+  }
+  return up;
+}
+/* SIGHUP: stop services in reverse dependency order. A runsv gets TERM
+ * once no other running service lists it in its depends file.
+ */
+unsafe fn stop_services() {
+  let mut deps: *mut *mut llist_t = std::ptr::null_mut();
+  let mut i: libc::c_int = 0;
+  let mut j: libc::c_int = 0;
+  let mut last_progress: libc::c_uint = crate::libbb::time::monotonic_sec();
+  let mut svnum: libc::c_int = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svnum;
+  let mut sv: *mut service = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).sv;
+  deps = crate::libbb::xfuncs_printf::xzalloc(
+    ((svnum + 1i32) as libc::c_ulong)
+      .wrapping_mul(::std::mem::size_of::<*mut llist_t>() as libc::c_ulong),
+  ) as *mut *mut llist_t;
+  i = 0;
+  while i < svnum {
+    let mut path: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+      (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svdir,
+      (*sv.offset(i as isize)).name,
+    );
+    *deps.offset(i as isize) = crate::runit::sv::svdepends_read(path);
+    free(path as *mut libc::c_void);
+    (*sv.offset(i as isize)).stopping = 0 as smallint;
+    i += 1
+  }
+  loop {
+    let mut live: libc::c_int = 0;
+    let mut busy: bool = false;
+    loop {
+      let mut pid: pid_t = crate::libbb::xfuncs::wait_any_nohang(0 as *mut libc::c_int);
+      if pid <= 0 {
+        break;
+      }
+      i = 0;
+      while i < svnum {
+        if (*sv.offset(i as isize)).pid == pid {
+          (*sv.offset(i as isize)).pid = 0;
+          last_progress = crate::libbb::time::monotonic_sec()
+        }
+        i += 1
+      }
+    }
+    /* No runsv exited for 7 seconds (sv's default -w): stop waiting for
+     * dependents and TERM everything that is left */
+    let mut force: bool =
+      crate::libbb::time::monotonic_sec().wrapping_sub(last_progress) >= 7i32 as libc::c_uint;
+    i = 0;
+    while i < svnum {
+      if (*sv.offset(i as isize)).pid != 0 {
+        live += 1;
+        if (*sv.offset(i as isize)).stopping != 0 {
+          busy = true
+        } else {
+          let mut needed: bool = false;
+          j = 0;
+          while j < svnum && !force {
+            if j != i
+              && (*sv.offset(j as isize)).pid != 0
+              && !crate::libbb::llist::llist_find_str(
+                *deps.offset(j as isize),
+                (*sv.offset(i as isize)).name,
+              )
+              .is_null()
+            {
+              needed = true
+            }
+            j += 1
+          }
+          if !needed {
+            kill((*sv.offset(i as isize)).pid, 15i32);
+            (*sv.offset(i as isize)).stopping = 1i32 as smallint;
+            busy = true
+          }
+        }
+      }
+      i += 1
+    }
+    if live == 0 {
+      break;
+    }
+    if !busy {
+      /* dependency loop: nothing can go first */
+      last_progress = 0 as libc::c_uint
+    }
+    usleep(100000i32 as useconds_t);
+  }
+  i = 0;
+  while i < svnum {
+    while !(*deps.offset(i as isize)).is_null() {
+      free(crate::libbb::llist::llist_pop(
+        &mut *deps.offset(i as isize),
+      ));
+    }
+    i += 1
+  }
+  free(deps as *mut libc::c_void);
+}
 /* gcc 4.3.0 does better with NOINLINE */
 #[inline(never)]
 unsafe extern "C" fn do_rescan() -> libc::c_int {
@@ -231,11 +359,39 @@ unsafe extern "C" fn do_rescan() -> libc::c_int {
             (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
               .sv
               .offset(i as isize))
-            .ino = s.st_ino
+            .ino = s.st_ino;
+            (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+              .sv
+              .offset(i as isize))
+            .pid = 0;
+            (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+              .sv
+              .offset(i as isize))
+            .waiting = 0 as smallint;
+            let ref mut fresh5 = (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+              .sv
+              .offset(i as isize))
+            .name;
+            *fresh5 = crate::libbb::xfuncs_printf::xstrdup((*d).d_name.as_mut_ptr())
           }
         }
         _ => {}
       }
+      /* hold it back until what it depends on is up */
+      if !depends_up(
+        (*d).d_name.as_mut_ptr(),
+        &mut (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+          .sv
+          .offset(i as isize))
+        .waiting,
+      ) {
+        (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+          .sv
+          .offset(i as isize))
+        .isgone = 0 as smallint;
+        need_rescan = 1i32;
+        continue;
+      }
       /* restart if it has died */
       (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
         .sv
@@ -282,6 +438,12 @@ unsafe extern "C" fn do_rescan() -> libc::c_int {
           15i32,
         );
       }
+      free(
+        (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+          .sv
+          .offset(i as isize))
+        .name as *mut libc::c_void,
+      );
       let ref mut fresh2 = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svnum;
       *fresh2 -= 1;
       *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
@@ -444,24 +606,7 @@ pub unsafe fn runsvdir_main(
       }
     }
     if sig == 1i32 as libc::c_uint {
-      i = 0;
-      while i < (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svnum {
-        if (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
-          .sv
-          .offset(i as isize))
-        .pid
-          != 0
-        {
-          kill(
-            (*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
-              .sv
-              .offset(i as isize))
-            .pid,
-            15i32,
-          );
-        }
-        i += 1
-      }
+      stop_services();
     }
     if !i_am_init {
       return if 1i32 as libc::c_uint == sig {
@@ -1,4 +1,7 @@
 use crate::libbb::default_error_retval::xfunc_error_retval;
+use crate::libbb::llist::llist_t;
+use crate::libbb::parse_config::parser_t;
+use crate::libbb::parse_config::PARSE_NORMAL;
 use crate::libbb::ptr_to_globals::bb_errno;
 use crate::librb::size_t;
 use crate::librb::smallint;
@@ -7,15 +10,22 @@ use c2rust_asm_casts::AsmCastTrait;
 use libc;
 use libc::chdir;
 use libc::close;
+use libc::closedir;
+use libc::dirent;
+use libc::free;
 use libc::getenv;
 use libc::open;
+use libc::opendir;
 use libc::printf;
+use libc::readdir;
 use libc::ssize_t;
 use libc::stat;
 use libc::strcmp;
 use libc::time;
 use libc::time_t;
 use libc::useconds_t;
+use libc::DIR;
+use libc::FILE;
 extern "C" {
 
   #[no_mangle]
@@ -68,6 +78,8 @@ pub struct globals {
   pub tnow: u64,
   pub svstatus: svstatus_t,
   pub islog: smallint,
+  pub waitsec: libc::c_uint,
+  pub json: smallint,
 }
 
 #[repr(C)]
@@ -80,6 +92,11 @@ pub struct svstatus_t {
   pub want: u8,
   pub got_term: u8,
   pub run_or_finish: u8,
+  pub ready: u8,
+  pub exited: u8,
+  pub failed: u8,
+  pub wstat_le32: u32,
+  pub restarts_le32: u32,
 }
 /* need to zero out, svc calls sv() repeatedly */
 unsafe extern "C" fn fatal_cannot(mut m1: *const libc::c_char) -> ! {
@@ -89,7 +106,58 @@ unsafe extern "C" fn fatal_cannot(mut m1: *const libc::c_char) -> ! {
   );
   _exit(151i32);
 }
+/* Prints S as a JSON string */
+unsafe fn json_string(mut s: *const libc::c_char) {
+  crate::libbb::xfuncs_printf::bb_putchar('"' as i32);
+  while *s != 0 {
+    let mut c: libc::c_uint = *s as u8 as libc::c_uint;
+    if c == '"' as i32 as libc::c_uint || c == '\\' as i32 as libc::c_uint {
+      printf(b"\\%c\x00" as *const u8 as *const libc::c_char, c);
+    } else if c < ' ' as i32 as libc::c_uint {
+      printf(b"\\u%04x\x00" as *const u8 as *const libc::c_char, c);
+    } else {
+      crate::libbb::xfuncs_printf::bb_putchar(c as libc::c_int);
+    }
+    s = s.offset(1)
+  }
+  crate::libbb::xfuncs_printf::bb_putchar('"' as i32);
+}
+unsafe fn json_service() {
+  printf(b"{\"service\":\x00" as *const u8 as *const libc::c_char);
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).islog != 0 {
+    let mut name: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+      *(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).service,
+      b"log\x00" as *const u8 as *const libc::c_char,
+    );
+    json_string(name);
+    free(name as *mut libc::c_void);
+  } else {
+    json_string(*(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).service);
+  }
+}
 unsafe extern "C" fn out(mut p: *const libc::c_char, mut m1: *const libc::c_char) {
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).json != 0 {
+    /* {"service":"NAME","result":"fail","message":"..."} */
+    json_service();
+    printf(
+      b",\"result\":\"%.*s\",\"message\":\x00" as *const u8 as *const libc::c_char,
+      strlen(p) as libc::c_int - 2i32,
+      p,
+    );
+    if *bb_errno != 0 {
+      let mut msg: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+        b"%s: %m\x00" as *const u8 as *const libc::c_char,
+        m1,
+      );
+      json_string(msg);
+      free(msg as *mut libc::c_void);
+    } else {
+      json_string(m1);
+    }
+    printf(b"}\n\x00" as *const u8 as *const libc::c_char);
+    crate::libbb::xfuncs_printf::fflush_all();
+    return;
+  }
   printf(
     b"%s%s%s: %s\x00" as *const u8 as *const libc::c_char,
     p,
@@ -126,6 +194,206 @@ unsafe extern "C" fn ok(mut m1: *const libc::c_char) {
   *bb_errno = 0;
   out(b"ok: \x00" as *const u8 as *const libc::c_char, m1);
 }
+/* Reads a supervise/status record. Records written by runsv without
+ * readiness support are 20 bytes: treat a running service as ready.
+ * Any other size than that or the full record is a bad format (returns 0).
+ */
+pub unsafe fn svstatus_read(mut fname: *const libc::c_char, mut st: *mut svstatus_t) -> ssize_t {
+  let mut fd: libc::c_int = 0;
+  let mut r: ssize_t = 0;
+  memset(
+    st as *mut libc::c_void,
+    0,
+    ::std::mem::size_of::<svstatus_t>() as libc::c_ulong,
+  );
+  fd = open(fname, 0 | 0o4000i32);
+  if fd == -1i32 {
+    return -1i32 as ssize_t;
+  }
+  r = read(
+    fd,
+    st as *mut libc::c_void,
+    ::std::mem::size_of::<svstatus_t>() as libc::c_ulong,
+  );
+  close(fd);
+  if r == 20 {
+    (*st).ready = ((*st).pid_le32 != 0) as libc::c_int as u8
+  } else if r > 0 && r != ::std::mem::size_of::<svstatus_t>() as ssize_t {
+    r = 0
+  }
+  return r;
+}
+/* Is the service in DIR running ./run (and ready, if it uses notification-fd)? */
+pub unsafe fn svstatus_up(mut dir: *const libc::c_char) -> bool {
+  let mut st: svstatus_t = std::mem::zeroed();
+  let mut fname: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+    dir,
+    b"supervise/status\x00" as *const u8 as *const libc::c_char,
+  );
+  let mut r: ssize_t = svstatus_read(fname, &mut st);
+  free(fname as *mut libc::c_void);
+  return r >= 20 && st.pid_le32 != 0 && st.run_or_finish as libc::c_int == 1i32 && st.ready != 0;
+}
+/* Is the service in DIR down (or its runsv gone)? */
+unsafe fn svstatus_down(mut dir: *const libc::c_char) -> bool {
+  let mut st: svstatus_t = std::mem::zeroed();
+  let mut fname: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+    dir,
+    b"supervise/status\x00" as *const u8 as *const libc::c_char,
+  );
+  let mut r: ssize_t = svstatus_read(fname, &mut st);
+  free(fname as *mut libc::c_void);
+  return r < 20 || st.pid_le32 == 0 && st.run_or_finish as libc::c_int == 0;
+}
+/* Reads the service names listed in DIR/depends, one per line.
+ * Names are relative to the directory DIR lives in.
+ */
+pub unsafe fn svdepends_read(mut dir: *const libc::c_char) -> *mut llist_t {
+  let mut list: *mut llist_t = std::ptr::null_mut();
+  let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut parser: *mut parser_t = std::ptr::null_mut();
+  let mut fname: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+    dir,
+    b"depends\x00" as *const u8 as *const libc::c_char,
+  );
+  parser = crate::libbb::parse_config::config_open2(
+    fname,
+    Some(crate::libbb::wfopen::fopen_for_read as unsafe fn(_: *const libc::c_char) -> *mut FILE),
+  );
+  free(fname as *mut libc::c_void);
+  if parser.is_null() {
+    return list;
+  }
+  while crate::libbb::parse_config::config_read(
+    parser,
+    &mut name,
+    (PARSE_NORMAL as libc::c_int | (1i32 & 0xffi32) << 8i32 | 1i32 & 0xffi32) as libc::c_uint,
+    b"# \t\x00" as *const u8 as *const libc::c_char,
+  ) != 0
+  {
+    crate::libbb::llist::llist_add_to_end(
+      &mut list,
+      crate::libbb::xfuncs_printf::xstrdup(name) as *mut libc::c_void,
+    );
+  }
+  crate::libbb::parse_config::config_close(parser);
+  return list;
+}
+unsafe fn svcontrol_send(mut dir: *const libc::c_char, mut c: libc::c_char) -> bool {
+  let mut fd: libc::c_int = 0;
+  let mut r: ssize_t = 0;
+  let mut fname: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+    dir,
+    b"supervise/control\x00" as *const u8 as *const libc::c_char,
+  );
+  fd = open(fname, 0o1i32 | 0o4000i32);
+  free(fname as *mut libc::c_void);
+  if fd == -1i32 {
+    return false;
+  }
+  r = libc::write(fd, &mut c as *mut libc::c_char as *const libc::c_void, 1);
+  close(fd);
+  return r == 1;
+}
+/* Waits up to -w SEC for the service in DIR to come up (or go down) */
+unsafe fn svdep_wait(mut dir: *const libc::c_char, mut want_up: bool) -> bool {
+  let mut start: time_t = time(0 as *mut time_t);
+  loop {
+    if if want_up {
+      svstatus_up(dir)
+    } else {
+      svstatus_down(dir)
+    } {
+      return true;
+    }
+    if (time(0 as *mut time_t) - start) as libc::c_uint
+      >= (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).waitsec
+    {
+      return false;
+    }
+    usleep(420000i32 as useconds_t);
+  }
+}
+/* up/start: bring up whatever ./depends lists (recursively) first.
+ * We are in a service directory, so dependencies are "../NAME".
+ */
+unsafe fn depends_start(mut dir: *const libc::c_char, mut depth: libc::c_int) -> libc::c_int {
+  let mut deps: *mut llist_t = svdepends_read(dir);
+  let mut r: libc::c_int = 1i32;
+  while !deps.is_null() {
+    let mut name: *mut libc::c_char =
+      crate::libbb::llist::llist_pop(&mut deps) as *mut libc::c_char;
+    let mut path: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+      b"..\x00" as *const u8 as *const libc::c_char,
+      name,
+    );
+    /* depth guards against dependency loops */
+    if r > 0 && depth < 8i32 && !svstatus_up(path) {
+      if depends_start(path, depth + 1i32) < 0 {
+        r = -1i32
+      } else {
+        svcontrol_send(path, 'u' as i32 as libc::c_char);
+        if !svdep_wait(path, true) {
+          let mut msg: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+            b"dependency %s is not up\x00" as *const u8 as *const libc::c_char,
+            name,
+          );
+          failx(msg);
+          free(msg as *mut libc::c_void);
+          r = -1i32
+        }
+      }
+    }
+    free(path as *mut libc::c_void);
+    free(name as *mut libc::c_void);
+  }
+  return r;
+}
+/* down/exit: first take down services whose ./depends lists NAME */
+unsafe fn dependents_stop(mut name: *const libc::c_char, mut depth: libc::c_int) -> libc::c_int {
+  let mut dir: *mut DIR = std::ptr::null_mut();
+  let mut d: *mut dirent = std::ptr::null_mut();
+  let mut r: libc::c_int = 1i32;
+  dir = opendir(b"..\x00" as *const u8 as *const libc::c_char);
+  if dir.is_null() {
+    return r;
+  }
+  loop {
+    d = readdir(dir);
+    if d.is_null() {
+      break;
+    }
+    if (*d).d_name[0] as libc::c_int == '.' as i32 || strcmp((*d).d_name.as_ptr(), name) == 0 {
+      continue;
+    }
+    let mut path: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+      b"..\x00" as *const u8 as *const libc::c_char,
+      (*d).d_name.as_ptr(),
+    );
+    let mut deps: *mut llist_t = svdepends_read(path);
+    if !crate::libbb::llist::llist_find_str(deps, name).is_null() && !svstatus_down(path) {
+      if depth < 8i32 && dependents_stop((*d).d_name.as_ptr(), depth + 1i32) < 0 {
+        r = -1i32
+      }
+      svcontrol_send(path, 'd' as i32 as libc::c_char);
+      if !svdep_wait(path, false) {
+        let mut msg: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+          b"dependent %s is not down\x00" as *const u8 as *const libc::c_char,
+          (*d).d_name.as_ptr(),
+        );
+        failx(msg);
+        free(msg as *mut libc::c_void);
+        r = -1i32
+      }
+    }
+    while !deps.is_null() {
+      free(crate::libbb::llist::llist_pop(&mut deps));
+    }
+    free(path as *mut libc::c_void);
+  }
+  closedir(dir);
+  return r;
+}
 unsafe extern "C" fn svstatus_get() -> libc::c_int {
   let mut fd: libc::c_int = 0;
   let mut r: libc::c_int = 0;
@@ -146,32 +414,18 @@ unsafe extern "C" fn svstatus_get() -> libc::c_int {
     return -1i32;
   }
   close(fd);
-  fd = open(
+  r = svstatus_read(
     b"supervise/status\x00" as *const u8 as *const libc::c_char,
-    0 | 0o4000i32,
-  );
-  if fd == -1i32 {
-    warn(b"can\'t open supervise/status\x00" as *const u8 as *const libc::c_char);
+    &mut (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svstatus,
+  ) as libc::c_int;
+  if r == -1i32 {
+    warn(b"can\'t read supervise/status\x00" as *const u8 as *const libc::c_char);
     return -1i32;
   }
-  r = read(
-    fd,
-    &mut (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svstatus as *mut svstatus_t
-      as *mut libc::c_void,
-    20i32 as size_t,
-  ) as libc::c_int;
-  close(fd);
-  match r {
-    20 => {}
-    -1 => {
-      warn(b"can\'t read supervise/status\x00" as *const u8 as *const libc::c_char);
-      return -1i32;
-    }
-    _ => {
-      *bb_errno = 0;
-      warn(b"can\'t read supervise/status: bad format\x00" as *const u8 as *const libc::c_char);
-      return -1i32;
-    }
+  if r < 20i32 {
+    *bb_errno = 0;
+    warn(b"can\'t read supervise/status: bad format\x00" as *const u8 as *const libc::c_char);
+    return -1i32;
   }
   return 1i32;
 }
@@ -273,10 +527,34 @@ unsafe extern "C" fn svstatus_print(mut m: *const libc::c_char) -> libc::c_uint
     {
       printf(b", got TERM\x00" as *const u8 as *const libc::c_char);
     }
+    if stat(
+      b"notification-fd\x00" as *const u8 as *const libc::c_char,
+      &mut s,
+    ) == 0
+    {
+      printf(
+        if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+          .svstatus
+          .ready as libc::c_int
+          != 0
+        {
+          b", ready\x00" as *const u8 as *const libc::c_char
+        } else {
+          b", not ready\x00" as *const u8 as *const libc::c_char
+        },
+      );
+    }
   } else {
     if normallyup != 0 {
       printf(b", normally up\x00" as *const u8 as *const libc::c_char);
     }
+    if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+      .svstatus
+      .failed
+      != 0
+    {
+      printf(b", failed\x00" as *const u8 as *const libc::c_char);
+    }
     if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
       .svstatus
       .want as libc::c_int
@@ -287,6 +565,98 @@ unsafe extern "C" fn svstatus_print(mut m: *const libc::c_char) -> libc::c_uint
   }
   return if pid != 0 { 1i32 } else { 2i32 } as libc::c_uint;
 }
+/* One JSON object per line:
+ * {"service":"NAME","state":"run","pid":N,"uptime":SEC,"want":"up",
+ *  "normallyup":true,"paused":false,"ready":true,
+ *  "exitcode":N|null,"signal":N|null,"restarts":N,"failed":false}
+ */
+unsafe fn svstatus_json() -> libc::c_uint {
+  let mut st: *mut svstatus_t = &mut (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).svstatus;
+  let mut s: stat = std::mem::zeroed();
+  let mut normallyup: bool = false;
+  let mut diff: libc::c_int = 0;
+  let mut pid: libc::c_uint = (*st).pid_le32;
+  let mut wstat: libc::c_int = (*st).wstat_le32 as libc::c_int;
+  if stat(b"down\x00" as *const u8 as *const libc::c_char, &mut s) == -1i32 {
+    normallyup = *bb_errno == 2i32
+  }
+  diff = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+    .tnow
+    .wrapping_sub(u64::from_be((*st).time_be64)) as libc::c_int;
+  json_service();
+  printf(
+    b",\"state\":\"%s\",\"pid\":%u,\"uptime\":%u,\"want\":\"%s\",\"normallyup\":%s,\"paused\":%s,\"ready\":%s\x00"
+      as *const u8 as *const libc::c_char,
+    match (*st).run_or_finish as libc::c_int {
+      1 => b"run\x00" as *const u8 as *const libc::c_char,
+      2 => b"finish\x00" as *const u8 as *const libc::c_char,
+      _ => b"down\x00" as *const u8 as *const libc::c_char,
+    },
+    pid,
+    if diff < 0 { 0 } else { diff },
+    if (*st).want as libc::c_int == 'u' as i32 {
+      b"up\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"down\x00" as *const u8 as *const libc::c_char
+    },
+    if normallyup {
+      b"true\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"false\x00" as *const u8 as *const libc::c_char
+    },
+    if (*st).paused != 0 {
+      b"true\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"false\x00" as *const u8 as *const libc::c_char
+    },
+    if pid != 0 && (*st).ready != 0 {
+      b"true\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"false\x00" as *const u8 as *const libc::c_char
+    },
+  );
+  if (*st).exited == 0 {
+    printf(b",\"exitcode\":null,\"signal\":null\x00" as *const u8 as *const libc::c_char);
+  } else if wstat & 0x7fi32 == 0 {
+    printf(
+      b",\"exitcode\":%u,\"signal\":null\x00" as *const u8 as *const libc::c_char,
+      ((wstat & 0xff00i32) >> 8i32) as libc::c_uint,
+    );
+  } else {
+    printf(
+      b",\"exitcode\":null,\"signal\":%u\x00" as *const u8 as *const libc::c_char,
+      (wstat & 0x7fi32) as libc::c_uint,
+    );
+  }
+  printf(
+    b",\"restarts\":%u,\"failed\":%s}\n\x00" as *const u8 as *const libc::c_char,
+    (*st).restarts_le32,
+    if (*st).failed != 0 {
+      b"true\x00" as *const u8 as *const libc::c_char
+    } else {
+      b"false\x00" as *const u8 as *const libc::c_char
+    },
+  );
+  return if pid != 0 { 1i32 } else { 2i32 } as libc::c_uint;
+}
+unsafe extern "C" fn status_json(mut _unused: *const libc::c_char) -> libc::c_int {
+  let mut r: libc::c_int = 0;
+  if svstatus_get() <= 0 {
+    return 0;
+  }
+  r = svstatus_json() as libc::c_int;
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).islog = 1i32 as smallint;
+  if chdir(b"log\x00" as *const u8 as *const libc::c_char) == -1i32 {
+    if *bb_errno != 2i32 {
+      warn(b"can\'t change directory\x00" as *const u8 as *const libc::c_char);
+    }
+  } else if svstatus_get() > 0 {
+    svstatus_json();
+  }
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).islog = 0 as smallint;
+  crate::libbb::xfuncs_printf::fflush_all();
+  return r;
+}
 unsafe extern "C" fn status(mut _unused: *const libc::c_char) -> libc::c_int {
   let mut r: libc::c_int = 0;
   if svstatus_get() <= 0 {
@@ -374,6 +744,10 @@ unsafe extern "C" fn check(mut a: *const libc::c_char) -> libc::c_int {
             .svstatus
             .run_or_finish as libc::c_int
             != 1i32
+          || (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+            .svstatus
+            .ready
+            == 0
         {
           return 0;
         }
@@ -522,6 +896,24 @@ unsafe extern "C" fn control(mut a: *const libc::c_char) -> libc::c_int {
   {
     return 0;
   }
+  if strcmp(a, b"u\x00" as *const u8 as *const libc::c_char) == 0
+    && depends_start(b".\x00" as *const u8 as *const libc::c_char, 0) < 0
+  {
+    return -1i32;
+  }
+  if strcmp(a, b"d\x00" as *const u8 as *const libc::c_char) == 0
+    || strcmp(a, b"x\x00" as *const u8 as *const libc::c_char) == 0
+  {
+    let mut cwd: *mut libc::c_char =
+      crate::libbb::xgetcwd::xrealloc_getcwd_or_warn(std::ptr::null_mut::<libc::c_char>());
+    if !cwd.is_null() {
+      r = dependents_stop(crate::libbb::get_last_path_component::bb_basename(cwd), 0);
+      free(cwd as *mut libc::c_void);
+      if r < 0 {
+        return -1i32;
+      }
+    }
+  }
   fd = open(
     b"supervise/control\x00" as *const u8 as *const libc::c_char,
     0o1i32 | 0o4000i32,
@@ -562,6 +954,7 @@ unsafe extern "C" fn control(mut a: *const libc::c_char) -> libc::c_int {
 //usage:       "	runsv exits too\n"
 //usage:       "pause, cont, hup, alarm, interrupt, quit, 1, 2, term, kill: send\n"
 //usage:       "STOP, CONT, HUP, ALRM, INT, QUIT, USR1, USR2, TERM, KILL signal to service"
+static mut sv_longopts: [u8; 8] = *b"json\x00\x00j\x00";
 unsafe extern "C" fn sv(mut argv: *mut *mut libc::c_char) -> libc::c_int {
   let mut current_block: u64;
   let mut x: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
@@ -587,12 +980,18 @@ unsafe extern "C" fn sv(mut argv: *mut *mut libc::c_char) -> libc::c_int {
   if !x.is_null() {
     waitsec = crate::libbb::xatonum::xatou(x)
   }
-  crate::libbb::getopt32::getopt32(
+  if crate::libbb::getopt32::getopt32long(
     argv,
-    b"^w:+v\x00vv\x00" as *const u8 as *const libc::c_char,
+    b"^w:+vj\x00vv\x00" as *const u8 as *const libc::c_char,
+    sv_longopts.as_ptr() as *const libc::c_char,
     &mut waitsec as *mut libc::c_uint,
     &mut verbose as *mut libc::c_int,
-  );
+  ) & (1i32 << 2i32) as libc::c_uint
+    != 0
+  {
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).json = 1i32 as smallint
+  }
+  (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).waitsec = waitsec;
   argv = argv.offset(optind as isize);
   let fresh11 = argv;
   argv = argv.offset(1);
@@ -671,7 +1070,13 @@ unsafe extern "C" fn sv(mut argv: *mut *mut libc::c_char) -> libc::c_int {
         *fresh22 = b"d\x00" as *const u8 as *const libc::c_char
       } else {
         /* "status" */
-        act = Some(status as unsafe extern "C" fn(_: *const libc::c_char) -> libc::c_int);
+        act = Some(
+          if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).json != 0 {
+            status_json as unsafe extern "C" fn(_: *const libc::c_char) -> libc::c_int
+          } else {
+            status as unsafe extern "C" fn(_: *const libc::c_char) -> libc::c_int
+          },
+        );
         cbk = None
       }
       current_block_56 = 14294131666767243020;
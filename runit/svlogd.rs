@@ -1,4 +1,5 @@
 use crate::libbb::ptr_to_globals::bb_errno;
+use crate::librb::len_and_sockaddr;
 use crate::librb::size_t;
 use crate::librb::smallint;
 use c2rust_asm_casts;
@@ -27,6 +28,7 @@ use libc::ssize_t;
 use libc::stat;
 use libc::strchr;
 use libc::strcmp;
+use libc::strcpy;
 use libc::time_t;
 use libc::timeval;
 use libc::unlink;
@@ -184,6 +186,10 @@ pub struct logdir {
   pub fnsave: [libc::c_char; 30],
   pub match_0: libc::c_char,
   pub matcherr: libc::c_char,
+  pub compress: libc::c_char,
+  pub udponly: smallint,
+  pub udpfd: libc::c_int,
+  pub udpaddr: *mut len_and_sockaddr,
 }
 pub type C2RustUnnamed = libc::c_uint;
 pub const COMMON_BUFSIZE: C2RustUnnamed = 1024;
@@ -421,6 +427,76 @@ unsafe fn fmt_time_bernstein_25(mut s: *mut libc::c_char) {
   *fresh11 = '@' as i32 as libc::c_char;
   crate::libbb::xfuncs::bin2hex(s, pack.as_mut_ptr() as *mut libc::c_char, 12i32);
 }
+/* "@TAI64N.s", "@TAI64N.u" or "@TAI64N.t", also with ".gz"/".bz2" appended */
+unsafe fn is_logfile(mut name: *const libc::c_char) -> libc::c_int {
+  let mut len: size_t = strlen(name);
+  return (*name.offset(0) as libc::c_int == '@' as i32
+    && (len == 27i32 as libc::c_ulong
+      || len > 27i32 as libc::c_ulong
+        && len < 32i32 as libc::c_ulong
+        && *name.offset(27) as libc::c_int == '.' as i32)) as libc::c_int;
+}
+/* Compress a rotated logfile in the current directory: f -> f.gz (or .bz2).
+ * Runs in a forked child, reaped by sig_child_handler.
+ */
+unsafe fn compress_rotated(mut ld: *mut logdir, mut fn_0: *const libc::c_char) {
+  let mut argv: [*mut libc::c_char; 4] = [
+    std::ptr::null_mut(),
+    b"-f\x00" as *const u8 as *const libc::c_char as *mut libc::c_char,
+    fn_0 as *mut libc::c_char,
+    std::ptr::null_mut(),
+  ];
+  let mut pid: pid_t = 0;
+  if (*ld).compress == 0 {
+    return;
+  }
+  if (*ptr_to_globals).verbose != 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"info: compress: %s/%s\x00" as *const u8 as *const libc::c_char,
+      (*ld).name,
+      fn_0,
+    );
+  }
+  pid = libc::fork();
+  if pid < 0 {
+    warn2(
+      b"can\'t fork compressor\x00" as *const u8 as *const libc::c_char,
+      (*ld).name,
+    );
+    return;
+  }
+  if pid == 0 {
+    /* child. Our signals stay blocked: the handlers use ptr_to_globals,
+     * which the compressor reuses for its own state */
+    if (*ld).compress as libc::c_int == 'j' as i32 {
+      argv[0] = b"bzip2\x00" as *const u8 as *const libc::c_char as *mut libc::c_char;
+      libc::_exit(crate::archival::bzip2::bzip2_main(3i32, argv.as_mut_ptr()));
+    }
+    argv[0] = b"gzip\x00" as *const u8 as *const libc::c_char as *mut libc::c_char;
+    libc::_exit(crate::archival::gzip::gzip_main(3i32, argv.as_mut_ptr()));
+  }
+}
+/* Ship a selected line to the u/U remote, if any. Best effort, like syslog */
+unsafe fn udp_send(mut ld: *mut logdir, mut s: *const libc::c_char, mut len: libc::c_int) {
+  if (*ld).udpfd == -1i32 {
+    return;
+  }
+  if libc::sendto(
+    (*ld).udpfd,
+    s as *const libc::c_void,
+    len as usize,
+    libc::MSG_DONTWAIT | libc::MSG_NOSIGNAL,
+    &mut (*(*ld).udpaddr).u.sa,
+    (*(*ld).udpaddr).len,
+  ) == -1
+    && (*ptr_to_globals).verbose != 0
+  {
+    warn2(
+      b"can\'t send to remote\x00" as *const u8 as *const libc::c_char,
+      (*ld).name,
+    );
+  }
+}
 unsafe fn processorstart(mut ld: *mut logdir) {
   let mut sv_ch: libc::c_char = 0;
   let mut pid: libc::c_int = 0;
@@ -601,6 +677,7 @@ unsafe fn processorstop(mut ld: *mut logdir) -> libc::c_uint {
       f.as_mut_ptr(),
     );
   }
+  compress_rotated(ld, f.as_mut_ptr());
   while fchdir((*ptr_to_globals).fdwdir) == -1i32 {
     pause1cannot(b"change to initial working directory\x00" as *const u8 as *const libc::c_char);
   }
@@ -609,7 +686,7 @@ unsafe fn processorstop(mut ld: *mut logdir) -> libc::c_uint {
 unsafe fn rmoldest(mut ld: *mut logdir) {
   let mut d: *mut DIR = std::ptr::null_mut();
   let mut f: *mut dirent = std::ptr::null_mut();
-  let mut oldest: [libc::c_char; 30] = [0; 30];
+  let mut oldest: [libc::c_char; 32] = [0; 32];
   let mut n: libc::c_int = 0;
   oldest[0] = 'A' as i32 as libc::c_char;
   oldest[27] = 0 as libc::c_char;
@@ -630,10 +707,8 @@ unsafe fn rmoldest(mut ld: *mut logdir) {
     if f.is_null() {
       break;
     }
-    if (*f).d_name[0] as libc::c_int == '@' as i32
-      && strlen((*f).d_name.as_mut_ptr()) == 27i32 as libc::c_ulong
-    {
-      if (*f).d_name[26] as libc::c_int == 't' as i32 {
+    if is_logfile((*f).d_name.as_mut_ptr()) != 0 {
+      if (*f).d_name[26] as libc::c_int == 't' as i32 && (*f).d_name[27] == 0 {
         if unlink((*f).d_name.as_mut_ptr()) == -1i32 {
           warn2(
             b"can\'t unlink processor leftover\x00" as *const u8 as *const libc::c_char,
@@ -643,11 +718,7 @@ unsafe fn rmoldest(mut ld: *mut logdir) {
       } else {
         n += 1;
         if strcmp((*f).d_name.as_mut_ptr(), oldest.as_mut_ptr()) < 0 {
-          memcpy(
-            oldest.as_mut_ptr() as *mut libc::c_void,
-            (*f).d_name.as_mut_ptr() as *const libc::c_void,
-            27i32 as libc::c_ulong,
-          );
+          strcpy(oldest.as_mut_ptr(), (*f).d_name.as_mut_ptr());
         }
       }
       *bb_errno = 0
@@ -791,6 +862,9 @@ unsafe fn rotate(mut ld: *mut logdir) -> libc::c_uint {
       );
     }
     rmoldest(ld);
+    if (*ld).processor.is_null() {
+      compress_rotated(ld, (*ld).fnsave.as_mut_ptr());
+    }
     processorstart(ld);
   }
   while fchdir((*ptr_to_globals).fdwdir) == -1i32 {
@@ -823,7 +897,7 @@ unsafe fn buffer_pwrite(
     if *bb_errno == 28i32 && (*ld).nmin < (*ld).nmax {
       let mut d: *mut DIR = std::ptr::null_mut();
       let mut f: *mut dirent = std::ptr::null_mut();
-      let mut oldest: [libc::c_char; 30] = [0; 30];
+      let mut oldest: [libc::c_char; 32] = [0; 32];
       let mut j: libc::c_int = 0;
       while fchdir((*ld).fddir) == -1i32 {
         pause2cannot(
@@ -850,16 +924,10 @@ unsafe fn buffer_pwrite(
         if f.is_null() {
           break;
         }
-        if (*f).d_name[0] as libc::c_int == '@' as i32
-          && strlen((*f).d_name.as_mut_ptr()) == 27i32 as libc::c_ulong
-        {
+        if is_logfile((*f).d_name.as_mut_ptr()) != 0 {
           j += 1;
           if strcmp((*f).d_name.as_mut_ptr(), oldest.as_mut_ptr()) < 0 {
-            memcpy(
-              oldest.as_mut_ptr() as *mut libc::c_void,
-              (*f).d_name.as_mut_ptr() as *const libc::c_void,
-              27i32 as libc::c_ulong,
-            );
+            strcpy(oldest.as_mut_ptr(), (*f).d_name.as_mut_ptr());
           }
         }
       }
@@ -927,6 +995,12 @@ unsafe fn logdir_close(mut ld: *mut logdir) {
   }
   close((*ld).fddir);
   (*ld).fddir = -1i32;
+  if (*ld).udpfd != -1i32 {
+    close((*ld).udpfd);
+    (*ld).udpfd = -1i32
+  }
+  free((*ld).udpaddr as *mut libc::c_void);
+  (*ld).udpaddr = std::ptr::null_mut();
   if (*ld).fdcur == -1i32 {
     return;
   }
@@ -1005,6 +1079,8 @@ unsafe fn logdir_open(mut ld: *mut logdir, mut fn_0: *const libc::c_char) -> lib
   (*ld).name = fn_0 as *mut libc::c_char;
   (*ld).ppid = 0;
   (*ld).match_0 = '+' as i32 as libc::c_char;
+  (*ld).compress = 0 as libc::c_char;
+  (*ld).udponly = 0 as smallint;
   free((*ld).inst as *mut libc::c_void);
   (*ld).inst = std::ptr::null_mut::<libc::c_char>();
   free((*ld).processor as *mut libc::c_void);
@@ -1125,6 +1201,54 @@ unsafe fn logdir_open(mut ld: *mut logdir, mut fn_0: *const libc::c_char) -> lib
             (*ld).processor = wstrdup(&mut *s.offset(1))
           }
         }
+        122 => {
+          /* z[gzip|bzip2]: compress rotated files */
+          (*ld).compress = 'z' as i32 as libc::c_char;
+          if strcmp(
+            s.offset(1),
+            b"bzip2\x00" as *const u8 as *const libc::c_char,
+          ) == 0
+          {
+            (*ld).compress = 'j' as i32 as libc::c_char
+          } else if *s.offset(1) as libc::c_int != 0
+            && strcmp(s.offset(1), b"gzip\x00" as *const u8 as *const libc::c_char) != 0
+          {
+            warnx(
+              b"unsupported compressor, using gzip\x00" as *const u8 as *const libc::c_char,
+              s.offset(1),
+            );
+          }
+        }
+        117 | 85 => {
+          /* u/UIP[:PORT]: also/only send selected lines to remote syslog */
+          if (*ld).udpfd != -1i32 {
+            close((*ld).udpfd);
+            (*ld).udpfd = -1i32
+          }
+          free((*ld).udpaddr as *mut libc::c_void);
+          (*ld).udpaddr = crate::libbb::xconnect::host2sockaddr(s.offset(1), 514i32);
+          if (*ld).udpaddr.is_null() {
+            warnx(
+              b"can\'t resolve remote\x00" as *const u8 as *const libc::c_char,
+              s.offset(1),
+            );
+          } else {
+            (*ld).udpfd = libc::socket(
+              (*(*ld).udpaddr).u.sa.sa_family as libc::c_int,
+              libc::SOCK_DGRAM,
+              0,
+            );
+            if (*ld).udpfd == -1i32 {
+              warn2(
+                b"can\'t create socket for\x00" as *const u8 as *const libc::c_char,
+                s.offset(1),
+              );
+            } else {
+              crate::libbb::xfuncs::close_on_exec_on((*ld).udpfd);
+              (*ld).udponly = (*s.offset(0) as libc::c_int == 'U' as i32) as smallint
+            }
+          }
+        }
         _ => {}
       }
       s = np
@@ -1551,6 +1675,7 @@ pub unsafe fn svlogd_main(mut argc: libc::c_int, mut argv: *mut *mut libc::c_cha
   while (i as libc::c_uint) < (*ptr_to_globals).dirn {
     (*(*ptr_to_globals).dir.offset(i as isize)).fddir = -1i32;
     (*(*ptr_to_globals).dir.offset(i as isize)).fdcur = -1i32;
+    (*(*ptr_to_globals).dir.offset(i as isize)).udpfd = -1i32;
     i += 1
     // //dir[i].btmp = xmalloc(buflen);
     /*dir[i].ppid = 0;*/
@@ -1716,7 +1841,10 @@ pub unsafe fn svlogd_main(mut argc: libc::c_int, mut argv: *mut *mut libc::c_cha
                 );
               }
               if !((*ld).match_0 as libc::c_int != '+' as i32) {
-                buffer_pwrite(i, printptr, printlen as libc::c_uint);
+                udp_send(ld, printptr, printlen);
+                if (*ld).udponly == 0 {
+                  buffer_pwrite(i, printptr, printlen as libc::c_uint);
+                }
               }
             }
             i += 1
@@ -1768,6 +1896,7 @@ pub unsafe fn svlogd_main(mut argc: libc::c_int, mut argv: *mut *mut libc::c_cha
                 }
                 if !((*(*ptr_to_globals).dir.offset(i as isize)).match_0 as libc::c_int
                   != '+' as i32)
+                  && (*(*ptr_to_globals).dir.offset(i as isize)).udponly == 0
                 {
                   buffer_pwrite(i, lineptr, (*ptr_to_globals).linelen as libc::c_uint);
                 }
@@ -310,6 +310,7 @@ pub mod libbb {
   pub mod speed_table;
   pub mod str_tolower;
   pub mod strrstr;
+  pub mod syntax;
   pub mod sysconf;
   pub mod time;
   pub mod trim;
@@ -399,7 +400,10 @@ pub mod miscutils {
   pub mod watchdog;
 } // mod miscutils
 pub mod modutils {
+  pub mod depmod;
+  pub mod insmod;
   pub mod modinfo;
+  pub mod modprobe;
   pub mod modprobe_small;
   pub mod modutils;
 } // mod modutils
@@ -407,6 +411,7 @@ pub mod networking {
   pub mod arp;
   pub mod arping;
   pub mod brctl;
+  pub mod bridge;
   pub mod dnsd;
   pub mod ether_wake;
   pub mod ftpd;
@@ -444,6 +449,7 @@ pub mod networking {
   pub mod nbd_client;
   pub mod nc;
   pub mod netstat;
+  pub mod nft;
   pub mod nslookup;
   pub mod ntpd;
   pub mod parse_pasv_epsv;
@@ -722,6 +722,30 @@ pub struct heredoc {
 pub struct localvar_list {
   pub next: *mut localvar_list,
   pub lv: *mut localvar,
+  #[cfg(feature = "ash-bash-compat")]
+  pub arrays: *mut bashvar,
+}
+
+#[cfg(feature = "ash-bash-compat")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct arrelem {
+  pub next: *mut arrelem,
+  pub idx: arith_t,
+  pub key: *mut libc::c_char,
+  /* associative arrays only */
+  pub val: *mut libc::c_char,
+}
+
+#[cfg(feature = "ash-bash-compat")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct bashvar {
+  pub next: *mut bashvar,
+  pub name: *mut libc::c_char,
+  pub attr: libc::c_int,
+  pub elems: *mut arrelem,
+  /* sorted by idx for indexed arrays */
 }
 
 #[repr(C)]
@@ -920,7 +944,7 @@ pub struct C2RustUnnamed_11 {
   pub var_func: Option<unsafe extern "C" fn(_: *const libc::c_char) -> ()>,
 }
 pub type C2RustUnnamed_12 = libc::c_uint;
-pub const VI_MODE: C2RustUnnamed_12 = 0;
+pub const VI_MODE: C2RustUnnamed_12 = 8;
 pub const USERNAME_COMPLETION: C2RustUnnamed_12 = 4;
 pub const TAB_COMPLETION: C2RustUnnamed_12 = 2;
 pub const DO_HISTORY: C2RustUnnamed_12 = 1;
@@ -2180,6 +2204,723 @@ unsafe extern "C" fn listvars(
     (*ash_ptr_to_globals_memstack).g_stacknxt as *mut libc::c_void as *mut libc::c_char,
   ) as libc::c_long as size_t) as *mut *mut libc::c_char;
 }
+/*
+ * Arrays and variable attributes for ash-bash-compat.
+ *
+ * Element 0 of an indexed array, or key "0" of an associative one, is the
+ * ordinary variable of the same name, so $arr, arr=x, export and local keep
+ * working.  The other elements and the -a/-A/-i attributes live here.
+ */
+#[cfg(feature = "ash-bash-compat")]
+const BV_ARRAY: libc::c_int = 0x1;
+#[cfg(feature = "ash-bash-compat")]
+const BV_ASSOC: libc::c_int = 0x2;
+#[cfg(feature = "ash-bash-compat")]
+const BV_INTEGER: libc::c_int = 0x4;
+#[cfg(feature = "ash-bash-compat")]
+static mut bashvars: *mut bashvar = std::ptr::null_mut();
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn bv_lookup(mut name: *const libc::c_char, mut len: size_t) -> *mut bashvar {
+  let mut bv: *mut bashvar = bashvars;
+  while !bv.is_null() {
+    if libc::strncmp((*bv).name, name, len as libc::size_t) == 0
+      && *(*bv).name.offset(len as isize) == 0
+    {
+      break;
+    }
+    bv = (*bv).next
+  }
+  return bv;
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn bv_create(
+  mut name: *const libc::c_char,
+  mut len: size_t,
+  mut attr: libc::c_int,
+) -> *mut bashvar {
+  let mut bv: *mut bashvar = bv_lookup(name, len);
+  if bv.is_null() {
+    bv = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<bashvar>() as libc::c_ulong)
+      as *mut bashvar;
+    (*bv).name = crate::libbb::xfuncs_printf::xstrndup(name, len as libc::c_int);
+    (*bv).next = bashvars;
+    bashvars = bv
+  }
+  (*bv).attr |= attr;
+  return bv;
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn bv_clear(mut bv: *mut bashvar) {
+  let mut e: *mut arrelem = (*bv).elems;
+  (*bv).elems = std::ptr::null_mut();
+  while !e.is_null() {
+    let mut next: *mut arrelem = (*e).next;
+    free((*e).key as *mut libc::c_void);
+    free((*e).val as *mut libc::c_void);
+    free(e as *mut libc::c_void);
+    e = next
+  }
+}
+/* Take the record of NAME out of the table */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn bv_unlink(mut name: *const libc::c_char, mut len: size_t) -> *mut bashvar {
+  let mut bvp: *mut *mut bashvar = &mut bashvars;
+  while !(*bvp).is_null() {
+    let mut bv: *mut bashvar = *bvp;
+    if libc::strncmp((*bv).name, name, len as libc::size_t) == 0
+      && *(*bv).name.offset(len as isize) == 0
+    {
+      *bvp = (*bv).next;
+      (*bv).next = std::ptr::null_mut();
+      return bv;
+    }
+    bvp = &mut (*bv).next
+  }
+  return std::ptr::null_mut();
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn bv_free(mut bv: *mut bashvar) {
+  if bv.is_null() {
+    return;
+  }
+  bv_clear(bv);
+  free((*bv).name as *mut libc::c_void);
+  free(bv as *mut libc::c_void);
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_checkro(mut name: *const libc::c_char) {
+  let mut vp: *mut var = *findvar(hashvar(name), name);
+  if !vp.is_null() && (*vp).flags & 0x2i32 != 0 {
+    (*ash_ptr_to_globals_misc).exitstatus = 1i32 as u8;
+    ash_msg_and_raise_error(
+      b"%s: is read only\x00" as *const u8 as *const libc::c_char,
+      name,
+    );
+  }
+}
+/* Append N bytes of S to the malloced string BUF of length *LEN */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_addstr(
+  mut buf: *mut libc::c_char,
+  mut len: *mut size_t,
+  mut s: *const libc::c_char,
+  mut n: size_t,
+) -> *mut libc::c_char {
+  buf = crate::libbb::xfuncs_printf::xrealloc(
+    buf as *mut libc::c_void,
+    (*len).wrapping_add(n).wrapping_add(1),
+  ) as *mut libc::c_char;
+  memcpy(
+    buf.offset(*len as isize) as *mut libc::c_void,
+    s as *const libc::c_void,
+    n,
+  );
+  *len = (*len).wrapping_add(n);
+  *buf.offset(*len as isize) = '\u{0}' as i32 as libc::c_char;
+  return buf;
+}
+/* P points at '[': return the position just past the matching ']', or NULL */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_skipsub(mut p: *const libc::c_char) -> *const libc::c_char {
+  let mut depth: libc::c_int = 0;
+  loop {
+    let mut c: libc::c_int = *p as libc::c_uchar as libc::c_int;
+    p = p.offset(1);
+    if c == 0 {
+      return std::ptr::null();
+    }
+    if c == '\u{81}' as i32 {
+      if *p == 0 {
+        return std::ptr::null();
+      }
+      p = p.offset(1)
+    } else if c == '[' as i32 {
+      depth += 1
+    } else if c == ']' as i32 {
+      depth -= 1;
+      if depth == 0 {
+        return p;
+      }
+    }
+  }
+}
+/*
+ * Copy the subscript starting at P (just past '[') into a malloced string,
+ * storing the position of the closing ']' in *ENDP.  Subscripts of ${...}
+ * are kept unexpanded by the parser: with EXPAND, quotes are dropped and
+ * $NAME and ${NAME} are replaced by their values.
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_subscript(
+  mut p: *const libc::c_char,
+  mut endp: *mut *const libc::c_char,
+  mut expand: libc::c_int,
+) -> *mut libc::c_char {
+  let mut buf: *mut libc::c_char =
+    crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t) as *mut libc::c_char;
+  let mut len: size_t = 0 as size_t;
+  let mut depth: libc::c_int = 0;
+  loop {
+    let mut c: libc::c_int = *p as libc::c_uchar as libc::c_int;
+    if c == 0 || c == ']' as i32 && depth == 0 {
+      break;
+    }
+    if c == '[' as i32 {
+      depth += 1
+    } else if c == ']' as i32 {
+      depth -= 1
+    } else if c == '\u{81}' as i32 && *p.offset(1) as libc::c_int != 0 {
+      p = p.offset(1);
+      buf = arr_addstr(buf, &mut len, p, 1i32 as size_t);
+      p = p.offset(1);
+      continue;
+    } else if expand != 0 && (c == '\'' as i32 || c == '"' as i32) {
+      p = p.offset(1);
+      continue;
+    } else if expand != 0 && c == '$' as i32 {
+      let mut q: *const libc::c_char = p.offset(1);
+      let mut brace: libc::c_int = (*q as libc::c_int == '{' as i32) as libc::c_int;
+      let mut e: *const libc::c_char = std::ptr::null();
+      q = q.offset(brace as isize);
+      e = crate::libbb::endofname::endofname(q);
+      if e != q && (brace == 0 || *e as libc::c_int == '}' as i32) {
+        let mut name: *mut libc::c_char =
+          crate::libbb::xfuncs_printf::xstrndup(q, e.offset_from(q) as libc::c_int);
+        let mut v: *const libc::c_char = lookupvar(name);
+        free(name as *mut libc::c_void);
+        if !v.is_null() {
+          buf = arr_addstr(buf, &mut len, v, strlen(v));
+        }
+        p = e.offset(brace as isize);
+        continue;
+      }
+    }
+    buf = arr_addstr(buf, &mut len, p, 1i32 as size_t);
+    p = p.offset(1)
+  }
+  *endp = p;
+  return buf;
+}
+/* Highest index in use in indexed array NAME, -1 if it is empty */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_maxidx(mut bv: *mut bashvar, mut name: *const libc::c_char) -> arith_t {
+  let mut max: arith_t = if lookupvar(name).is_null() { -1i32 } else { 0 } as arith_t;
+  let mut e: *mut arrelem = std::ptr::null_mut();
+  if !bv.is_null() {
+    e = (*bv).elems;
+    while !e.is_null() {
+      max = (*e).idx;
+      e = (*e).next
+    }
+  }
+  return max;
+}
+/* Negative subscripts count back from the end of the array */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_index(
+  mut bv: *mut bashvar,
+  mut name: *const libc::c_char,
+  mut sub: *const libc::c_char,
+) -> arith_t {
+  let mut idx: arith_t = ash_arith(sub);
+  if idx < 0 {
+    idx += arr_maxidx(bv, name) + 1;
+    if idx < 0 {
+      ash_msg_and_raise_error(
+        b"%s[%s]: bad array subscript\x00" as *const u8 as *const libc::c_char,
+        name,
+        sub,
+      );
+    }
+  }
+  return idx;
+}
+/* Value of element SUB of NAME, NULL if it is not set */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_get(
+  mut name: *const libc::c_char,
+  mut sub: *const libc::c_char,
+) -> *const libc::c_char {
+  let mut bv: *mut bashvar = bv_lookup(name, strlen(name));
+  let mut e: *mut arrelem = std::ptr::null_mut();
+  if !bv.is_null() {
+    e = (*bv).elems
+  }
+  if !bv.is_null() && (*bv).attr & BV_ASSOC != 0 {
+    if strcmp(sub, b"0\x00" as *const u8 as *const libc::c_char) == 0 {
+      return lookupvar(name);
+    }
+    while !e.is_null() && strcmp((*e).key, sub) != 0 {
+      e = (*e).next
+    }
+  } else {
+    let mut idx: arith_t = arr_index(bv, name, sub);
+    if idx == 0 {
+      return lookupvar(name);
+    }
+    while !e.is_null() && (*e).idx != idx {
+      e = (*e).next
+    }
+  }
+  return if e.is_null() {
+    std::ptr::null()
+  } else {
+    (*e).val
+  };
+}
+/* Store VAL as element IDX (KEY for associative arrays) of BV */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_store(
+  mut bv: *mut bashvar,
+  mut idx: arith_t,
+  mut key: *const libc::c_char,
+  mut val: *const libc::c_char,
+) {
+  let mut ep: *mut *mut arrelem = &mut (*bv).elems;
+  if (*bv).attr & BV_ASSOC != 0 {
+    if strcmp(key, b"0\x00" as *const u8 as *const libc::c_char) == 0 {
+      setvar((*bv).name, val, 0);
+      return;
+    }
+    while !(*ep).is_null() && strcmp((**ep).key, key) != 0 {
+      ep = &mut (**ep).next
+    }
+  } else {
+    if idx == 0 {
+      setvar((*bv).name, val, 0);
+      return;
+    }
+    while !(*ep).is_null() && (**ep).idx < idx {
+      ep = &mut (**ep).next
+    }
+  }
+  arr_checkro((*bv).name);
+  if (*ep).is_null() || (*bv).attr & BV_ASSOC == 0 && (**ep).idx != idx {
+    let mut e: *mut arrelem =
+      crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<arrelem>() as libc::c_ulong)
+        as *mut arrelem;
+    (*e).idx = idx;
+    if (*bv).attr & BV_ASSOC != 0 {
+      (*e).key = crate::libbb::xfuncs_printf::xstrdup(key)
+    }
+    (*e).next = *ep;
+    *ep = e
+  } else {
+    free((**ep).val as *mut libc::c_void);
+  }
+  (**ep).val = crate::libbb::xfuncs_printf::xstrdup(val);
+}
+/*
+ * The value to store for an assignment of VAL, given the OLD one when
+ * appending ("+="): -i variables are evaluated arithmetically.
+ * Returns a malloced string.
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_newval(
+  mut bv: *mut bashvar,
+  mut old: *const libc::c_char,
+  mut val: *const libc::c_char,
+  mut append: libc::c_int,
+) -> *mut libc::c_char {
+  if !bv.is_null() && (*bv).attr & BV_INTEGER != 0 {
+    let mut n: arith_t = ash_arith(val);
+    if append != 0 && !old.is_null() {
+      n += ash_arith(old)
+    }
+    return crate::libbb::xfuncs_printf::xasprintf(
+      b"%lld\x00" as *const u8 as *const libc::c_char,
+      n,
+    );
+  }
+  if append != 0 && !old.is_null() {
+    return crate::libbb::xfuncs_printf::xasprintf(
+      b"%s%s\x00" as *const u8 as *const libc::c_char,
+      old,
+      val,
+    );
+  }
+  return crate::libbb::xfuncs_printf::xstrdup(val);
+}
+/* name[sub]=val, name[sub]+=val */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_set(
+  mut name: *const libc::c_char,
+  mut sub: *const libc::c_char,
+  mut val: *const libc::c_char,
+  mut append: libc::c_int,
+) {
+  let mut bv: *mut bashvar = bv_create(name, strlen(name), 0);
+  let mut idx: arith_t = 0;
+  let mut v: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  if (*bv).attr & BV_ASSOC == 0 {
+    (*bv).attr |= BV_ARRAY;
+    idx = arr_index(bv, name, sub)
+  }
+  v = arr_newval(
+    bv,
+    if append != 0 {
+      arr_get(name, sub)
+    } else {
+      std::ptr::null()
+    },
+    val,
+    append,
+  );
+  arr_store(bv, idx, sub, v);
+  free(v as *mut libc::c_void);
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_unsetelem(mut name: *const libc::c_char, mut sub: *const libc::c_char) {
+  let mut bv: *mut bashvar = bv_lookup(name, strlen(name));
+  let mut ep: *mut *mut arrelem = std::ptr::null_mut();
+  let mut idx: arith_t = 0;
+  if bv.is_null() || (*bv).attr & BV_ASSOC == 0 {
+    idx = arr_index(bv, name, sub)
+  }
+  if if bv.is_null() || (*bv).attr & BV_ASSOC == 0 {
+    idx == 0
+  } else {
+    strcmp(sub, b"0\x00" as *const u8 as *const libc::c_char) == 0
+  } {
+    unsetvar(name);
+    return;
+  }
+  if bv.is_null() {
+    return;
+  }
+  arr_checkro(name);
+  ep = &mut (*bv).elems;
+  while !(*ep).is_null() {
+    let mut e: *mut arrelem = *ep;
+    if if (*bv).attr & BV_ASSOC != 0 {
+      strcmp((*e).key, sub) == 0
+    } else {
+      (*e).idx == idx
+    } {
+      *ep = (*e).next;
+      free((*e).key as *mut libc::c_void);
+      free((*e).val as *mut libc::c_void);
+      free(e as *mut libc::c_void);
+      break;
+    } else {
+      ep = &mut (*e).next
+    }
+  }
+}
+/*
+ * Keys (with KEYS) or values of array NAME in order, as a malloced
+ * NULL-terminated vector of malloced strings.
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_list(
+  mut name: *const libc::c_char,
+  mut keys: libc::c_int,
+) -> *mut *mut libc::c_char {
+  let mut bv: *mut bashvar = bv_lookup(name, strlen(name));
+  let mut p: *const libc::c_char = lookupvar(name);
+  let mut e: *mut arrelem = std::ptr::null_mut();
+  let mut v: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut n: size_t = 2i32 as size_t;
+  if !bv.is_null() {
+    e = (*bv).elems;
+    while !e.is_null() {
+      n = n.wrapping_add(1);
+      e = (*e).next
+    }
+    e = (*bv).elems
+  }
+  v = xmalloc(n.wrapping_mul(::std::mem::size_of::<*mut libc::c_char>() as libc::c_ulong))
+    as *mut *mut libc::c_char;
+  n = 0 as size_t;
+  if !p.is_null() {
+    *v.offset(n as isize) = crate::libbb::xfuncs_printf::xstrdup(if keys != 0 {
+      b"0\x00" as *const u8 as *const libc::c_char
+    } else {
+      p
+    });
+    n = n.wrapping_add(1)
+  }
+  while !e.is_null() {
+    *v.offset(n as isize) = if keys == 0 {
+      crate::libbb::xfuncs_printf::xstrdup((*e).val)
+    } else if (*bv).attr & BV_ASSOC != 0 {
+      crate::libbb::xfuncs_printf::xstrdup((*e).key)
+    } else {
+      crate::libbb::xfuncs_printf::xasprintf(
+        b"%lld\x00" as *const u8 as *const libc::c_char,
+        (*e).idx,
+      )
+    };
+    n = n.wrapping_add(1);
+    e = (*e).next
+  }
+  *v.offset(n as isize) = std::ptr::null_mut::<libc::c_char>();
+  return v;
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_freelist(mut v: *mut *mut libc::c_char) {
+  let mut p: *mut *mut libc::c_char = v;
+  while !(*p).is_null() {
+    free(*p as *mut libc::c_void);
+    p = p.offset(1)
+  }
+  free(v as *mut libc::c_void);
+}
+/*
+ * Handle the bash forms of an assignment word: "name[sub]=val" is done
+ * right away and NULL is returned; "name+=val" and assignments to -i
+ * variables are rewritten to a plain "name=val".
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_assign(mut text: *mut libc::c_char) -> *mut libc::c_char {
+  let mut p: *const libc::c_char = crate::libbb::endofname::endofname(text);
+  let mut len: size_t = p.offset_from(text) as libc::c_long as size_t;
+  let mut bv: *mut bashvar = bv_lookup(text, len);
+  let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut sub: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut v: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut append: libc::c_int = 0;
+  if *p as libc::c_int == '[' as i32 {
+    sub = arr_subscript(p.offset(1), &mut p, 0);
+    p = p.offset(1)
+  }
+  if *p as libc::c_int == '+' as i32 {
+    append = 1i32;
+    p = p.offset(1)
+  }
+  if sub.is_null() && append == 0 && (bv.is_null() || (*bv).attr & BV_INTEGER == 0) {
+    return text;
+  }
+  name = crate::libbb::xfuncs_printf::xstrndup(text, len as libc::c_int);
+  if !sub.is_null() {
+    arr_set(name, sub, p.offset(1), append);
+    free(sub as *mut libc::c_void);
+    free(name as *mut libc::c_void);
+    return std::ptr::null_mut::<libc::c_char>();
+  }
+  v = arr_newval(bv, lookupvar(name), p.offset(1), append);
+  text = stalloc(len.wrapping_add(strlen(v)).wrapping_add(2)) as *mut libc::c_char;
+  sprintf(
+    text,
+    b"%s=%s\x00" as *const u8 as *const libc::c_char,
+    name,
+    v,
+  );
+  free(v as *mut libc::c_void);
+  free(name as *mut libc::c_void);
+  return text;
+}
+/*
+ * name=(...) and name+=(...): ITEMS are the N expanded words of the
+ * literal, plain values or "[sub]=val".
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_assignlit(
+  mut text: *const libc::c_char,
+  mut items: *mut *mut libc::c_char,
+  mut n: libc::c_int,
+) {
+  let mut p: *const libc::c_char = crate::libbb::endofname::endofname(text);
+  let mut name: *mut libc::c_char =
+    crate::libbb::xfuncs_printf::xstrndup(text, p.offset_from(text) as libc::c_int);
+  let mut bv: *mut bashvar = bv_lookup(name, strlen(name));
+  let mut next: arith_t = 0;
+  let mut i: libc::c_int = 0;
+  if *p as libc::c_int == '+' as i32 {
+    next = arr_maxidx(bv, name) + 1
+  } else {
+    unsetvar(name);
+    if !bv.is_null() {
+      bv_clear(bv);
+    }
+  }
+  bv = bv_create(name, strlen(name), 0);
+  if (*bv).attr & BV_ASSOC == 0 {
+    (*bv).attr |= BV_ARRAY
+  }
+  while i < n {
+    let mut item: *mut libc::c_char = *items.offset(i as isize);
+    let mut q: *const libc::c_char = std::ptr::null();
+    if *item as libc::c_int == '[' as i32
+      && {
+        q = arr_skipsub(item);
+        !q.is_null()
+      }
+      && (*q as libc::c_int == '=' as i32
+        || *q as libc::c_int == '+' as i32 && *q.offset(1) as libc::c_int == '=' as i32)
+    {
+      let mut sub: *mut libc::c_char = arr_subscript(item.offset(1), &mut q, 0);
+      let mut append: libc::c_int = (*q.offset(1) as libc::c_int == '+' as i32) as libc::c_int;
+      arr_set(name, sub, q.offset(2 + append as isize), append);
+      if (*bv).attr & BV_ASSOC == 0 {
+        next = arr_index(bv, name, sub) + 1
+      }
+      free(sub as *mut libc::c_void);
+    } else if (*bv).attr & BV_ASSOC != 0 {
+      ash_msg_and_raise_error(
+        b"%s: %s: must use subscript when assigning associative array\x00" as *const u8
+          as *const libc::c_char,
+        name,
+        item,
+      );
+    } else {
+      let mut v: *mut libc::c_char = arr_newval(bv, std::ptr::null(), item, 0);
+      arr_store(bv, next, std::ptr::null(), v);
+      free(v as *mut libc::c_void);
+      next += 1
+    }
+    i += 1
+  }
+  free(name as *mut libc::c_void);
+}
+/* "name=(" starts an array literal, ")" ends it */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_islit(mut text: *const libc::c_char) -> libc::c_int {
+  let mut len: size_t = strlen(text);
+  return (len >= 2
+    && *text.offset(len as isize - 1) as libc::c_int == '(' as i32
+    && *text.offset(len as isize - 2) as libc::c_int == '=' as i32) as libc::c_int;
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_islitend(mut n: *mut node) -> libc::c_int {
+  return (n.is_null() || strcmp((*n).narg.text, b")\x00" as *const u8 as *const libc::c_char) == 0)
+    as libc::c_int;
+}
+/* Expand the words of the array literal following N into LIST */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_expandwords(mut n: *mut node, mut list: *mut arglist) -> *mut node {
+  n = (*n).narg.next;
+  while arr_islitend(n) == 0 {
+    let mut q: *const libc::c_char = std::ptr::null();
+    if *(*n).narg.text as libc::c_int == '[' as i32
+      && {
+        q = arr_skipsub((*n).narg.text);
+        !q.is_null()
+      }
+      && (*q as libc::c_int == '=' as i32 || *q as libc::c_int == '+' as i32)
+    {
+      expandarg(n, list, 0x4i32);
+    } else {
+      expandarg(n, list, 0x1i32 | 0x2i32);
+    }
+    n = (*n).narg.next
+  }
+  return if n.is_null() { n } else { (*n).narg.next };
+}
+/* Command prefix "name=(...)": assign it now, return the node after it */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_evallit(mut n: *mut node) -> *mut node {
+  let mut list: arglist = arglist {
+    list: 0 as *const strlist as *mut strlist,
+    lastp: 0 as *const *mut strlist as *mut *mut strlist,
+  };
+  let mut sp: *mut strlist = std::ptr::null_mut();
+  let mut items: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut i: libc::c_int = 0;
+  let mut next: *mut node = std::ptr::null_mut();
+  list.lastp = &mut list.list;
+  *list.lastp = std::ptr::null_mut();
+  next = arr_expandwords(n, &mut list);
+  sp = list.list;
+  while !sp.is_null() {
+    i += 1;
+    sp = (*sp).next
+  }
+  items = stalloc(
+    (::std::mem::size_of::<*mut libc::c_char>() as libc::c_ulong)
+      .wrapping_mul((i + 1i32) as libc::c_ulong),
+  ) as *mut *mut libc::c_char;
+  i = 0;
+  sp = list.list;
+  while !sp.is_null() {
+    *items.offset(i as isize) = (*sp).text;
+    i += 1;
+    sp = (*sp).next
+  }
+  arr_assignlit((*n).narg.text, items, i);
+  return next;
+}
+/* Markers around an array literal passed to declare/typeset/local */
+#[cfg(feature = "ash-bash-compat")]
+static mut arrlit_open: [libc::c_char; 2] = ['(' as i32 as libc::c_char, 0];
+#[cfg(feature = "ash-bash-compat")]
+static mut arrlit_close: [libc::c_char; 2] = [')' as i32 as libc::c_char, 0];
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_addarg(mut list: *mut arglist, mut text: *mut libc::c_char) {
+  let mut sp: *mut strlist =
+    stzalloc(::std::mem::size_of::<strlist>() as libc::c_ulong) as *mut strlist;
+  (*sp).text = text;
+  *(*list).lastp = sp;
+  (*list).lastp = &mut (*sp).next;
+}
+/* Argument "name=(...)" of a declaration builtin: "name=", markers and words */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_expandlit(mut n: *mut node, mut list: *mut arglist) -> *mut node {
+  let mut text: *mut libc::c_char = sstrdup((*n).narg.text);
+  *text.offset(strlen(text) as isize - 1) = '\u{0}' as i32 as libc::c_char;
+  arr_addarg(list, text);
+  arr_addarg(list, arrlit_open.as_mut_ptr());
+  n = arr_expandwords(n, list);
+  arr_addarg(list, arrlit_close.as_mut_ptr());
+  return n;
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_poplocal(mut saved: *mut bashvar, mut keep: libc::c_int) {
+  while !saved.is_null() {
+    let mut next: *mut bashvar = (*saved).next;
+    if keep == 0 {
+      bv_free(bv_unlink((*saved).name, strlen((*saved).name)));
+      if (*saved).attr != 0 {
+        (*saved).next = bashvars;
+        bashvars = saved;
+        saved = std::ptr::null_mut()
+      }
+    }
+    bv_free(saved);
+    saved = next
+  }
+}
+/* Like mklocal() for the array part of NAME */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_mklocal(mut name: *const libc::c_char) {
+  let mut len: size_t = strlen(name);
+  let mut bv: *mut bashvar = (*localvar_stack).arrays;
+  while !bv.is_null() {
+    if strcmp((*bv).name, name) == 0 {
+      return;
+    }
+    bv = (*bv).next
+  }
+  bv = bv_unlink(name, len);
+  if bv.is_null() {
+    /* attr 0: nothing to restore */
+    bv = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<bashvar>() as libc::c_ulong)
+      as *mut bashvar;
+    (*bv).name = crate::libbb::xfuncs_printf::xstrdup(name)
+  }
+  (*bv).next = (*localvar_stack).arrays;
+  (*localvar_stack).arrays = bv;
+}
+/* The unset builtin: "name[sub]" unsets an element, "name" drops the array */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_unset(mut name: *const libc::c_char) -> libc::c_int {
+  let mut p: *const libc::c_char = crate::libbb::endofname::endofname(name);
+  let mut len: size_t = p.offset_from(name) as libc::c_long as size_t;
+  if len != 0 && *p as libc::c_int == '[' as i32 {
+    let mut base: *mut libc::c_char =
+      crate::libbb::xfuncs_printf::xstrndup(name, len as libc::c_int);
+    let mut sub: *mut libc::c_char = arr_subscript(p.offset(1), &mut p, 0);
+    arr_unsetelem(base, sub);
+    free(sub as *mut libc::c_void);
+    free(base as *mut libc::c_void);
+    return 1i32;
+  }
+  if *p == 0 && !bv_lookup(name, len).is_null() {
+    arr_checkro(name);
+    bv_free(bv_unlink(name, len));
+  }
+  return 0;
+}
 static mut pathopt: *const libc::c_char = std::ptr::null();
 unsafe extern "C" fn path_advance(
   mut path: *mut *const libc::c_char,
@@ -5967,6 +6708,14 @@ unsafe extern "C" fn argstr(mut p: *mut libc::c_char, mut flags: libc::c_int) {
                   break 'c_15354;
                 }
                 136 => {
+                  #[cfg(feature = "ash-bash-compat")]
+                  {
+                    if inquotes == 0 && arr_dolat(p) != 0 {
+                      p = evalvar(p.offset(1), flags | 0x100i32).offset(1);
+                      current_block = 13038395059501912860;
+                      break;
+                    }
+                  }
                   /* "$@" syntax adherence hack */
                   if inquotes == 0
                     && memcmp(
@@ -6605,32 +7354,215 @@ unsafe extern "C" fn subevalvar(
   expdest = expdest.offset(-amount as isize);
   return startp;
 }
-/*
- * Add the value of a specialized variable to the stack string.
- * name parameter (examples):
- * ash -c 'echo $1'      name:'1='
- * ash -c 'echo $qwe'    name:'qwe='
- * ash -c 'echo $$'      name:'$='
- * ash -c 'echo ${$}'    name:'$='
- * ash -c 'echo ${$##q}' name:'$=q'
- * ash -c 'echo ${#$}'   name:'$='
- * note: examples with bad shell syntax:
- * ash -c 'echo ${#$1}'  name:'$=1'
- * ash -c 'echo ${#1#}'  name:'1=#'
- */
-#[inline(never)]
-unsafe extern "C" fn varvalue(
-  mut name: *mut libc::c_char,
-  mut varflags: libc::c_int,
-  mut flags: libc::c_int,
-  mut quoted: libc::c_int,
-) -> ssize_t {
-  let mut ap: *mut *mut libc::c_char = std::ptr::null_mut(); /* number(name) fails on ${N#str} etc */
-  let mut sepc: libc::c_char = 0;
-  let mut c: libc::c_char = 0;
-  let mut current_block: u64;
+/* "arr[sub]", "!name" and "!arr[@]" are handled by arrvalue() */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_isref(mut name: *const libc::c_char) -> libc::c_int {
   let mut p: *const libc::c_char = std::ptr::null();
-  let mut num: libc::c_int = 0;
+  if *name as libc::c_int == '!' as i32 {
+    name = name.offset(1);
+    return (crate::libbb::endofname::endofname(name) != name) as libc::c_int;
+  }
+  p = crate::libbb::endofname::endofname(name);
+  return (p != name && *p as libc::c_int == '[' as i32) as libc::c_int;
+}
+/* Is VAR "arr[@]" or "!arr[@]" with at least one element? */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_isat(mut var: *const libc::c_char) -> libc::c_int {
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut bv: *mut bashvar = std::ptr::null_mut();
+  let mut r: libc::c_int = 0;
+  if *var as libc::c_int == '!' as i32 {
+    var = var.offset(1)
+  }
+  p = crate::libbb::endofname::endofname(var);
+  if p == var || libc::strncmp(p, b"[@]=\x00" as *const u8 as *const libc::c_char, 4) != 0 {
+    return 0;
+  }
+  name = crate::libbb::xfuncs_printf::xstrndup(var, p.offset_from(var) as libc::c_int);
+  bv = bv_lookup(name, strlen(name));
+  r = (!lookupvar(name).is_null() || !bv.is_null() && !(*bv).elems.is_null()) as libc::c_int;
+  free(name as *mut libc::c_void);
+  return r;
+}
+/* P is at CTLVAR: is it a lone "${arr[@]}" in double quotes, like "$@"? */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_dolat(mut p: *const libc::c_char) -> libc::c_int {
+  let mut q: *const libc::c_char = std::ptr::null();
+  if *p as libc::c_uchar as libc::c_int != '\u{82}' as i32 || *p.offset(1) as libc::c_int != 0x1i32
+  {
+    return 0;
+  }
+  p = p.offset(2);
+  if *p as libc::c_int == '!' as i32 {
+    p = p.offset(1)
+  }
+  q = crate::libbb::endofname::endofname(p);
+  return (q != p && libc::strncmp(q, b"[@]=\x88\x00" as *const u8 as *const libc::c_char, 5) == 0)
+    as libc::c_int;
+}
+/* Value of the variable named by REF, which may be "arr[sub]" */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_deref(mut r: *const libc::c_char) -> *const libc::c_char {
+  let mut p: *const libc::c_char = crate::libbb::endofname::endofname(r);
+  let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut sub: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut v: *const libc::c_char = std::ptr::null();
+  if p == r {
+    return std::ptr::null();
+  }
+  if *p == 0 {
+    return lookupvar(r);
+  }
+  if *p as libc::c_int != '[' as i32 {
+    return std::ptr::null();
+  }
+  name = crate::libbb::xfuncs_printf::xstrndup(r, p.offset_from(r) as libc::c_int);
+  sub = arr_subscript(p.offset(1), &mut p, 0);
+  v = arr_get(name, sub);
+  free(sub as *mut libc::c_void);
+  free(name as *mut libc::c_void);
+  return v;
+}
+/*
+ * varvalue() for array references.  name parameter (examples):
+ * ${a[1]}    name:'a[1]='
+ * ${a[@]}    name:'a[@]='
+ * ${#a[@]}   name:'a[@]='  (count of elements)
+ * ${!a[@]}   name:'!a[@]=' (keys)
+ * ${!r}      name:'!r='    (indirection)
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arrvalue(
+  mut name: *mut libc::c_char,
+  mut varflags: libc::c_int,
+  mut flags: libc::c_int,
+  mut quoted: libc::c_int,
+) -> ssize_t {
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut base: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut sub: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut len: ssize_t = 0 as ssize_t;
+  let mut all: libc::c_int = 0;
+  let mut subtype: libc::c_int = varflags & 0xfi32;
+  let mut discard: libc::c_int = (subtype == 0x3i32 || subtype == 0xai32) as libc::c_int;
+  let mut quotes: libc::c_int = (if discard != 0 {
+    0
+  } else {
+    (flags) & (0x1i32 | 0x10i32)
+  }) | 0x2i32;
+  let mut syntax: libc::c_int = if quoted != 0 { 1i32 } else { 0 };
+  let mut indirect: libc::c_int = (*name as libc::c_int == '!' as i32) as libc::c_int;
+  name = name.offset(indirect as isize);
+  p = crate::libbb::endofname::endofname(name);
+  base = crate::libbb::xfuncs_printf::xstrndup(name, p.offset_from(name) as libc::c_int);
+  if *p as libc::c_int == '[' as i32 {
+    let mut q: *const libc::c_char = std::ptr::null();
+    sub = arr_subscript(p.offset(1), &mut q, 1i32);
+    if q == p.offset(2) && (*sub as libc::c_int == '@' as i32 || *sub as libc::c_int == '*' as i32)
+    {
+      all = *sub as libc::c_int
+    }
+  }
+  if all != 0 {
+    let mut v: *mut *mut libc::c_char = arr_list(base, indirect);
+    let mut ap: *mut *mut libc::c_char = v;
+    if (*v).is_null() {
+      len = -1i32 as ssize_t
+    } else if subtype == 0xai32 {
+      /* ${#arr[@]} */
+      while !(*ap).is_null() {
+        len += 1;
+        ap = ap.offset(1)
+      }
+      discard = 0
+    } else {
+      /* same separators as "$@" and "$*" */
+      let mut sep: libc::c_int = (flags & 0x1i32) << 8i32;
+      let mut sepc: libc::c_char = 0;
+      if !(all == '@' as i32 && quoted != 0 && sep != 0) {
+        let mut c: libc::c_char =
+          (((quoted | !sep) & 0x100i32 == 0) as libc::c_int - 1i32) as libc::c_char;
+        sep &= !quoted;
+        sep |= if (*ash_ptr_to_globals_var).varinit[0].flags & 0x20i32 == 0 {
+          (c as libc::c_int
+            & *(*ash_ptr_to_globals_var).varinit[0]
+              .var_text
+              .offset(4)
+              .offset(0) as libc::c_int) as libc::c_uchar as libc::c_int
+        } else {
+          ' ' as i32
+        }
+      }
+      sepc = sep as libc::c_char;
+      while !(*ap).is_null() {
+        len = (len as libc::c_ulong).wrapping_add(strtodest(*ap, syntax, quotes)) as ssize_t;
+        ap = ap.offset(1);
+        if !(*ap).is_null() && sep != 0 {
+          len += 1;
+          memtodest(&mut sepc, 1i32 as size_t, syntax, quotes);
+        }
+      }
+    }
+    arr_freelist(v);
+  } else {
+    if indirect == 0 {
+      p = arr_get(base, sub)
+    } else if sub.is_null() {
+      p = lookupvar(base);
+      if !p.is_null() {
+        p = arr_deref(p)
+      }
+    } else {
+      raise_error_syntax(b"bad substitution\x00" as *const u8 as *const libc::c_char);
+    }
+    if p.is_null() {
+      len = -1i32 as ssize_t
+    } else {
+      len = strtodest(p, syntax, quotes) as ssize_t;
+      if subtype == 0xai32 && len > 0 {
+        reinit_unicode_for_ash();
+        if UNICODE_ON as libc::c_int == UNICODE_ON as libc::c_int {
+          expdest = expdest.offset(-len as isize);
+          discard = 0;
+          len = crate::libbb::unicode::unicode_strlen(p) as ssize_t
+        }
+      }
+    }
+  }
+  free(sub as *mut libc::c_void);
+  free(base as *mut libc::c_void);
+  if discard != 0 && len > 0 {
+    expdest = expdest.offset(-len as isize)
+  }
+  return len;
+}
+/*
+ * Add the value of a specialized variable to the stack string.
+ * name parameter (examples):
+ * ash -c 'echo $1'      name:'1='
+ * ash -c 'echo $qwe'    name:'qwe='
+ * ash -c 'echo $$'      name:'$='
+ * ash -c 'echo ${$}'    name:'$='
+ * ash -c 'echo ${$##q}' name:'$=q'
+ * ash -c 'echo ${#$}'   name:'$='
+ * note: examples with bad shell syntax:
+ * ash -c 'echo ${#$1}'  name:'$=1'
+ * ash -c 'echo ${#1#}'  name:'1=#'
+ */
+#[inline(never)]
+unsafe extern "C" fn varvalue(
+  mut name: *mut libc::c_char,
+  mut varflags: libc::c_int,
+  mut flags: libc::c_int,
+  mut quoted: libc::c_int,
+) -> ssize_t {
+  let mut ap: *mut *mut libc::c_char = std::ptr::null_mut(); /* number(name) fails on ${N#str} etc */
+  let mut sepc: libc::c_char = 0;
+  let mut c: libc::c_char = 0;
+  let mut current_block: u64;
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut num: libc::c_int = 0;
   let mut i: libc::c_int = 0;
   let mut len: ssize_t = 0 as ssize_t;
   let mut sep: libc::c_int = 0;
@@ -6644,6 +7576,12 @@ unsafe extern "C" fn varvalue(
   let mut syntax: libc::c_int = 0;
   sep = (flags & 0x1i32) << 8i32;
   syntax = if quoted != 0 { 1i32 } else { 0 };
+  #[cfg(feature = "ash-bash-compat")]
+  {
+    if arr_isref(name) != 0 {
+      return arrvalue(name, varflags, flags, quoted);
+    }
+  }
   match *name as libc::c_int {
     36 => {
       num = (*ash_ptr_to_globals_misc).rootpid;
@@ -6924,6 +7862,12 @@ unsafe extern "C" fn evalvar(mut p: *mut libc::c_char, mut flag: libc::c_int) ->
       if quoted != 0 {
         quoted = (*var as libc::c_int == '@' as i32
           && (*ash_ptr_to_globals_var).shellparam.nparam != 0) as libc::c_int;
+        #[cfg(feature = "ash-bash-compat")]
+        {
+          if quoted == 0 {
+            quoted = arr_isat(var)
+          }
+        }
         if quoted == 0 {
           current_block = 8905788842858268286;
         } else {
@@ -8874,13 +9818,31 @@ unsafe extern "C" fn setinteractive(mut on: libc::c_int) {
         FOR_SHELL as libc::c_int | WITH_PATH_LOOKUP as libc::c_int,
       )
     }
+    #[cfg(feature = "ash-bash-compat")]
+    {
+      (*line_input_state).complete_hook = Some(
+        ash_complete as unsafe extern "C" fn(_: *const libc::c_char) -> *mut *mut libc::c_char,
+      )
+    }
   };
 }
 unsafe extern "C" fn optschanged() {
+  /* viflag as last given to line editing */
+  static mut viflag_set: libc::c_char = 0 as libc::c_char;
   setinteractive((*ash_ptr_to_globals_misc).optlist[3] as libc::c_int);
   setjobctl((*ash_ptr_to_globals_misc).optlist[4] as libc::c_int);
-  (*ash_ptr_to_globals_misc).optlist[14] = 0 as libc::c_char;
-  /* forcibly keep the option off */
+  /* Only act on "set [+-]o vi" itself: inputrc or bind
+   * could have changed the editing mode since */
+  if !line_input_state.is_null()
+    && (*ash_ptr_to_globals_misc).optlist[14] as libc::c_int != viflag_set as libc::c_int
+  {
+    viflag_set = (*ash_ptr_to_globals_misc).optlist[14];
+    if viflag_set != 0 {
+      (*line_input_state).flags |= VI_MODE as libc::c_int
+    } else {
+      (*line_input_state).flags &= !(VI_MODE as libc::c_int)
+    }
+  };
 }
 static mut localvar_stack: *mut localvar_list = std::ptr::null_mut();
 /*
@@ -8902,6 +9864,8 @@ unsafe extern "C" fn poplocalvars(mut keep: libc::c_int) {
   ll = localvar_stack;
   localvar_stack = (*ll).next;
   next = (*ll).lv;
+  #[cfg(feature = "ash-bash-compat")]
+  arr_poplocal((*ll).arrays, keep);
   free(ll as *mut libc::c_void);
   loop {
     lvp = next;
@@ -9141,22 +10105,415 @@ unsafe extern "C" fn localcmd(
   mut _argc: libc::c_int,
   mut argv: *mut *mut libc::c_char,
 ) -> libc::c_int {
-  let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   if localvar_stack.is_null() {
     ash_msg_and_raise_error(b"not in a function\x00" as *const u8 as *const libc::c_char);
   }
-  argv = argptr;
+  #[cfg(feature = "ash-bash-compat")]
+  {
+    return declarecmd(_argc, argv);
+  }
+  #[cfg(not(feature = "ash-bash-compat"))]
+  {
+    let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    argv = argptr;
+    loop {
+      let fresh65 = argv;
+      argv = argv.offset(1);
+      name = *fresh65;
+      if name.is_null() {
+        break;
+      }
+      mklocal(name);
+    }
+    return 0;
+  }
+}
+/* One "declare -p" line for NAME; returns 1 if it is not set at all */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_show(mut name: *const libc::c_char) -> libc::c_int {
+  let mut bv: *mut bashvar = bv_lookup(name, strlen(name));
+  let mut vp: *mut var = *findvar(hashvar(name), name);
+  let mut opts: [libc::c_char; 8] = [0; 8];
+  let mut o: *mut libc::c_char = opts.as_mut_ptr();
+  let mut attr: libc::c_int = if bv.is_null() { 0 } else { (*bv).attr };
+  let mut flags: libc::c_int = if vp.is_null() { 0 } else { (*vp).flags };
+  if vp.is_null() && bv.is_null() {
+    return 1i32;
+  }
+  *o = '-' as i32 as libc::c_char;
+  o = o.offset(1);
+  if attr & BV_ARRAY != 0 {
+    *o = 'a' as i32 as libc::c_char;
+    o = o.offset(1)
+  }
+  if attr & BV_ASSOC != 0 {
+    *o = 'A' as i32 as libc::c_char;
+    o = o.offset(1)
+  }
+  if attr & BV_INTEGER != 0 {
+    *o = 'i' as i32 as libc::c_char;
+    o = o.offset(1)
+  }
+  if flags & 0x2i32 != 0 {
+    *o = 'r' as i32 as libc::c_char;
+    o = o.offset(1)
+  }
+  if flags & 0x1i32 != 0 {
+    *o = 'x' as i32 as libc::c_char;
+    o = o.offset(1)
+  }
+  if o == opts.as_mut_ptr().offset(1) {
+    *o = '-' as i32 as libc::c_char
+  }
+  if attr & (BV_ARRAY | BV_ASSOC) != 0 {
+    let mut keys: *mut *mut libc::c_char = arr_list(name, 1i32);
+    let mut vals: *mut *mut libc::c_char = arr_list(name, 0);
+    let mut i: libc::c_int = 0;
+    out1fmt(
+      b"declare %s %s=(\x00" as *const u8 as *const libc::c_char,
+      opts.as_mut_ptr(),
+      name,
+    );
+    while !(*keys.offset(i as isize)).is_null() {
+      out1fmt(
+        b"%s[%s]=%s\x00" as *const u8 as *const libc::c_char,
+        if i != 0 {
+          b" \x00" as *const u8 as *const libc::c_char
+        } else {
+          b"\x00" as *const u8 as *const libc::c_char
+        },
+        *keys.offset(i as isize),
+        single_quote(*vals.offset(i as isize)),
+      );
+      i += 1
+    }
+    out1str(b")\n\x00" as *const u8 as *const libc::c_char);
+    arr_freelist(keys);
+    arr_freelist(vals);
+  } else if !vp.is_null() && (*vp).flags & 0x20i32 == 0 {
+    out1fmt(
+      b"declare %s %s=%s\n\x00" as *const u8 as *const libc::c_char,
+      opts.as_mut_ptr(),
+      name,
+      single_quote(var_end((*vp).var_text)),
+    );
+  } else {
+    out1fmt(
+      b"declare %s %s\n\x00" as *const u8 as *const libc::c_char,
+      opts.as_mut_ptr(),
+      name,
+    );
+  }
+  return 0;
+}
+/* "declare -p" with no names: all variables, and arrays with element 0 unset */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arr_showall(mut attr: libc::c_int) {
+  let mut ep: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut epend: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut bv: *mut bashvar = std::ptr::null_mut();
+  ep = listvars(0, 0x20i32, &mut epend);
+  qsort(
+    ep as *mut libc::c_void,
+    epend.offset_from(ep) as libc::c_long as size_t,
+    ::std::mem::size_of::<*mut libc::c_char>() as libc::c_ulong,
+    Some(
+      vpcmp as unsafe extern "C" fn(_: *const libc::c_void, _: *const libc::c_void) -> libc::c_int,
+    ),
+  );
+  while ep < epend {
+    let mut p: *const libc::c_char = crate::libbb::endofname::endofname(*ep);
+    let mut name: *mut libc::c_char =
+      crate::libbb::xfuncs_printf::xstrndup(*ep, p.offset_from(*ep) as libc::c_int);
+    bv = bv_lookup(name, strlen(name));
+    if attr == 0 || !bv.is_null() && (*bv).attr & attr != 0 {
+      arr_show(name);
+    }
+    free(name as *mut libc::c_void);
+    ep = ep.offset(1)
+  }
+  bv = bashvars;
+  while !bv.is_null() {
+    if lookupvar((*bv).name).is_null() && (attr == 0 || (*bv).attr & attr != 0) {
+      arr_show((*bv).name);
+    }
+    bv = (*bv).next
+  }
+}
+/*
+ * The declare and typeset commands, and local with ash-bash-compat.
+ * Inside a function they make their variables local.
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn declarecmd(
+  mut _argc: libc::c_int,
+  mut argv: *mut *mut libc::c_char,
+) -> libc::c_int {
+  let mut ap: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut attr: libc::c_int = 0;
+  let mut flags: libc::c_int = 0;
+  let mut print: libc::c_int = 0;
+  let mut status: libc::c_int = 0;
+  let mut opt: libc::c_int = 0;
   loop {
-    let fresh65 = argv;
-    argv = argv.offset(1);
-    name = *fresh65;
-    if name.is_null() {
+    opt = nextopt(b"aAiprx\x00" as *const u8 as *const libc::c_char);
+    if opt == 0 {
+      break;
+    }
+    match opt {
+      97 => attr |= BV_ARRAY,
+      65 => attr |= BV_ASSOC,
+      105 => attr |= BV_INTEGER,
+      112 => print = 1i32,
+      114 => flags |= 0x2i32,
+      _ => flags |= 0x1i32,
+    }
+  }
+  if attr & BV_ASSOC != 0 {
+    attr &= !BV_ARRAY
+  }
+  ap = argptr;
+  if (*ap).is_null() {
+    if *(*argv.offset(0)).offset(0) as libc::c_int != 'l' as i32 {
+      arr_showall(attr);
+    }
+    return 0;
+  }
+  if print != 0 {
+    while !(*ap).is_null() {
+      if arr_show(*ap) != 0 {
+        ash_msg(
+          b"%s: not found\x00" as *const u8 as *const libc::c_char,
+          *ap,
+        );
+        status = 1i32
+      }
+      ap = ap.offset(1)
+    }
+    return status;
+  }
+  while !(*ap).is_null() {
+    let mut arg: *mut libc::c_char = *ap;
+    let mut p: *const libc::c_char = crate::libbb::endofname::endofname(arg);
+    let mut name: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut vp: *mut var = std::ptr::null_mut();
+    if !localvar_stack.is_null() && strcmp(arg, b"-\x00" as *const u8 as *const libc::c_char) == 0 {
+      mklocal(arg);
+      ap = ap.offset(1);
+      continue;
+    }
+    if p == arg as *const libc::c_char
+      || *p as libc::c_int != 0
+        && *p as libc::c_int != '=' as i32
+        && *p as libc::c_int != '[' as i32
+        && *p as libc::c_int != '+' as i32
+    {
+      ash_msg_and_raise_error(
+        b"%s: bad variable name\x00" as *const u8 as *const libc::c_char,
+        arg,
+      );
+    }
+    name = sstrdup(arg);
+    *name.offset(p.offset_from(arg) as isize) = '\u{0}' as i32 as libc::c_char;
+    if !localvar_stack.is_null() {
+      arr_mklocal(name);
+      mklocal(name);
+    }
+    if attr != 0 {
+      let mut bv: *mut bashvar = bv_create(name, strlen(name), attr);
+      if attr & BV_ASSOC != 0 {
+        (*bv).attr &= !BV_ARRAY
+      }
+      if attr & BV_INTEGER != 0 && *p == 0 {
+        let mut v: *const libc::c_char = lookupvar(name);
+        if !v.is_null() {
+          let mut n: *mut libc::c_char = arr_newval(bv, std::ptr::null(), v, 0);
+          setvar(name, n, 0);
+          free(n as *mut libc::c_void);
+        }
+      }
+    }
+    if *ap.offset(1) == arrlit_open.as_mut_ptr() {
+      let mut items: *mut *mut libc::c_char = ap.offset(2);
+      ap = items;
+      while *ap != arrlit_close.as_mut_ptr() {
+        ap = ap.offset(1)
+      }
+      arr_assignlit(
+        arg,
+        items,
+        ap.offset_from(items) as libc::c_long as libc::c_int,
+      );
+    } else if *p != 0 {
+      arg = arr_assign(arg);
+      if !arg.is_null() {
+        let mut eq: *mut libc::c_char = strchr(arg, '=' as i32);
+        setvar(name, eq.offset(1), 0);
+      }
+    }
+    if flags != 0 {
+      vp = *findvar(hashvar(name), name);
+      if vp.is_null() {
+        setvar(name, 0 as *const libc::c_char, flags);
+      } else {
+        (*vp).flags |= flags
+      }
+    }
+    ap = ap.offset(1)
+  }
+  return 0;
+}
+/*
+ * The mapfile/readarray command:
+ * mapfile [-t] [-d DELIM] [-n COUNT] [-O ORIGIN] [-s COUNT] [-u FD] [ARRAY]
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn mapfilecmd(
+  mut _argc: libc::c_int,
+  mut _argv: *mut *mut libc::c_char,
+) -> libc::c_int {
+  let mut name: *const libc::c_char = b"MAPFILE\x00" as *const u8 as *const libc::c_char;
+  let mut bv: *mut bashvar = std::ptr::null_mut();
+  let mut line: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut len: size_t = 0;
+  let mut delim: libc::c_char = '\n' as i32 as libc::c_char;
+  let mut trim: libc::c_int = 0;
+  let mut count: libc::c_int = 0;
+  let mut origin: libc::c_int = -1i32;
+  let mut skip: libc::c_int = 0;
+  let mut fd: libc::c_int = 0;
+  let mut n: libc::c_int = 0;
+  let mut opt: libc::c_int = 0;
+  loop {
+    opt = nextopt(b"td:n:O:s:u:\x00" as *const u8 as *const libc::c_char);
+    if opt == 0 {
+      break;
+    }
+    match opt {
+      116 => trim = 1i32,
+      100 => delim = *optionarg,
+      110 => count = number(optionarg),
+      79 => origin = number(optionarg),
+      115 => skip = number(optionarg),
+      _ => fd = number(optionarg),
+    }
+  }
+  if !(*argptr).is_null() {
+    name = *argptr;
+    if *crate::libbb::endofname::endofname(name) as libc::c_int != 0 || *name == 0 {
+      ash_msg_and_raise_error(
+        b"%s: bad variable name\x00" as *const u8 as *const libc::c_char,
+        name,
+      );
+    }
+  }
+  if origin < 0 {
+    unsetvar(name);
+    bv_free(bv_unlink(name, strlen(name)));
+    origin = 0
+  }
+  bv = bv_create(name, strlen(name), BV_ARRAY);
+  line = crate::libbb::xfuncs_printf::xzalloc(1i32 as size_t) as *mut libc::c_char;
+  loop {
+    let mut c: libc::c_char = 0;
+    let mut r: ssize_t = 0;
+    if count != 0 && n >= count {
+      break;
+    }
+    r = libc::read(fd, &mut c as *mut libc::c_char as *mut libc::c_void, 1);
+    if r < 0 && *bb_errno == libc::EINTR {
+      continue;
+    }
+    if r <= 0 && len == 0 {
+      break;
+    }
+    if r > 0 && (c != delim || trim == 0) {
+      line = arr_addstr(line, &mut len, &mut c, 1i32 as size_t)
+    }
+    if r > 0 && c != delim {
+      continue;
+    }
+    /* a whole line, or the last one without a delimiter */
+    if skip > 0 {
+      skip -= 1
+    } else {
+      arr_store(bv, (origin + n) as arith_t, std::ptr::null(), line);
+      n += 1
+    }
+    *line = '\u{0}' as i32 as libc::c_char;
+    len = 0 as size_t;
+    if r <= 0 {
       break;
     }
-    mklocal(name);
   }
+  free(line as *mut libc::c_void);
   return 0;
 }
+/*
+ * Programmable completion: complete/compgen, and the lineedit hook.
+ * -F FUNC and -C CMD run in a subshell, like $(...)
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn compgen_run(mut script: *const libc::c_char) -> *mut libc::c_char {
+  let mut pip: [libc::c_int; 2] = [0; 2];
+  let mut jp: *mut job = std::ptr::null_mut();
+  let mut out: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  if pipe(pip.as_mut_ptr()) < 0 {
+    return std::ptr::null_mut::<libc::c_char>();
+  }
+  jp = makejob(1i32);
+  if forkshell(jp, std::ptr::null_mut(), 2i32) == 0 {
+    /* child */
+    force_int_on();
+    close(pip[0]);
+    if pip[1] != 1i32 {
+      dup2_or_raise(pip[1], 1i32);
+      close(pip[1]);
+    }
+    evalstring(script as *mut libc::c_char, 0);
+    exitshell();
+  }
+  /* parent */
+  close(pip[1]);
+  out = crate::libbb::read_printf::xmalloc_read(pip[0], std::ptr::null_mut()) as *mut libc::c_char;
+  close(pip[0]);
+  waitforjob(jp);
+  return out;
+}
+#[cfg(feature = "ash-bash-compat")]
+static mut compgen_ash: crate::shell::shell_common::compgen_hooks =
+  crate::shell::shell_common::compgen_hooks {
+    run: Some(compgen_run as unsafe extern "C" fn(_: *const libc::c_char) -> *mut libc::c_char),
+    getvar: Some(
+      lookupvar as unsafe extern "C" fn(_: *const libc::c_char) -> *const libc::c_char,
+    ),
+    arrays: 1i32,
+  };
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn ash_complete(mut line: *const libc::c_char) -> *mut *mut libc::c_char {
+  return crate::shell::shell_common::shell_complete_line(line, &mut compgen_ash);
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn completecmd(
+  mut _argc: libc::c_int,
+  mut argv: *mut *mut libc::c_char,
+) -> libc::c_int {
+  return crate::shell::shell_common::shell_builtin_complete(argv, &mut compgen_ash);
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn bindcmd(
+  mut _argc: libc::c_int,
+  mut argv: *mut *mut libc::c_char,
+) -> libc::c_int {
+  return crate::shell::shell_common::shell_builtin_bind(argv, line_input_state);
+}
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn compgencmd(
+  mut _argc: libc::c_int,
+  mut argv: *mut *mut libc::c_char,
+) -> libc::c_int {
+  return crate::shell::shell_common::shell_builtin_compgen(argv, &mut compgen_ash);
+}
 unsafe extern "C" fn falsecmd(
   mut _argc: libc::c_int,
   mut _argv: *mut *mut libc::c_char,
@@ -9648,6 +11005,80 @@ static mut builtintab: [builtincmd; 44] = [
     init
   },
 ];
+/* Builtins of ash-bash-compat, searched after builtintab; sorted as well */
+#[cfg(feature = "ash-bash-compat")]
+static mut bashbuiltintab: [builtincmd; 7] = [
+  {
+    let mut init = builtincmd {
+      name: b"2bind\x00" as *const u8 as *const libc::c_char,
+      builtin: Some(
+        bindcmd
+          as unsafe extern "C" fn(_: libc::c_int, _: *mut *mut libc::c_char) -> libc::c_int,
+      ),
+    };
+    init
+  },
+  {
+    let mut init = builtincmd {
+      name: b"2compgen\x00" as *const u8 as *const libc::c_char,
+      builtin: Some(
+        compgencmd
+          as unsafe extern "C" fn(_: libc::c_int, _: *mut *mut libc::c_char) -> libc::c_int,
+      ),
+    };
+    init
+  },
+  {
+    let mut init = builtincmd {
+      name: b"2complete\x00" as *const u8 as *const libc::c_char,
+      builtin: Some(
+        completecmd
+          as unsafe extern "C" fn(_: libc::c_int, _: *mut *mut libc::c_char) -> libc::c_int,
+      ),
+    };
+    init
+  },
+  {
+    let mut init = builtincmd {
+      name: b"7declare\x00" as *const u8 as *const libc::c_char,
+      builtin: Some(
+        declarecmd
+          as unsafe extern "C" fn(_: libc::c_int, _: *mut *mut libc::c_char) -> libc::c_int,
+      ),
+    };
+    init
+  },
+  {
+    let mut init = builtincmd {
+      name: b"2mapfile\x00" as *const u8 as *const libc::c_char,
+      builtin: Some(
+        mapfilecmd
+          as unsafe extern "C" fn(_: libc::c_int, _: *mut *mut libc::c_char) -> libc::c_int,
+      ),
+    };
+    init
+  },
+  {
+    let mut init = builtincmd {
+      name: b"2readarray\x00" as *const u8 as *const libc::c_char,
+      builtin: Some(
+        mapfilecmd
+          as unsafe extern "C" fn(_: libc::c_int, _: *mut *mut libc::c_char) -> libc::c_int,
+      ),
+    };
+    init
+  },
+  {
+    let mut init = builtincmd {
+      name: b"7typeset\x00" as *const u8 as *const libc::c_char,
+      builtin: Some(
+        declarecmd
+          as unsafe extern "C" fn(_: libc::c_int, _: *mut *mut libc::c_char) -> libc::c_int,
+      ),
+    };
+    init
+  },
+];
 /*
  * Search the table of builtin commands.
  */
@@ -9674,6 +11105,21 @@ unsafe extern "C" fn find_builtin(mut name: *const libc::c_char) -> *mut builtin
         as unsafe extern "C" fn(_: *const libc::c_void, _: *const libc::c_void) -> libc::c_int,
     ),
   ) as *mut builtincmd;
+  #[cfg(feature = "ash-bash-compat")]
+  {
+    if bp.is_null() {
+      bp = bsearch(
+        name as *const libc::c_void,
+        bashbuiltintab.as_ptr() as *const libc::c_void,
+        bashbuiltintab.len() as size_t,
+        ::std::mem::size_of::<builtincmd>() as libc::c_ulong,
+        Some(
+          pstrcmp1
+            as unsafe extern "C" fn(_: *const libc::c_void, _: *const libc::c_void) -> libc::c_int,
+        ),
+      ) as *mut builtincmd
+    }
+  }
   return bp;
 }
 unsafe extern "C" fn isassignment(mut p: *const libc::c_char) -> libc::c_int {
@@ -9681,6 +11127,19 @@ unsafe extern "C" fn isassignment(mut p: *const libc::c_char) -> libc::c_int {
   if p == q {
     return 0;
   }
+  #[cfg(feature = "ash-bash-compat")]
+  {
+    /* name[sub]=, name+= */
+    if *q as libc::c_int == '[' as i32 {
+      q = arr_skipsub(q);
+      if q.is_null() {
+        return 0;
+      }
+    }
+    if *q as libc::c_int == '+' as i32 {
+      q = q.offset(1)
+    }
+  }
   return (*q as libc::c_int == '=' as i32) as libc::c_int;
 }
 unsafe extern "C" fn bltincmd(
@@ -9754,6 +11213,18 @@ unsafe extern "C" fn evalcommand(mut cmd: *mut node, mut flags: libc::c_int) ->
     while !argp.is_null() {
       let mut spp: *mut *mut strlist = std::ptr::null_mut();
       spp = arglist.lastp;
+      #[cfg(feature = "ash-bash-compat")]
+      {
+        if pseudovarflag as libc::c_int != 0 && arr_islit((*argp).narg.text) != 0 {
+          argp = arr_expandlit(argp, &mut arglist);
+          sp = *spp;
+          while !sp.is_null() {
+            argc += 1;
+            sp = (*sp).next
+          }
+          continue;
+        }
+      }
       if pseudovarflag as libc::c_int != 0 && isassignment((*argp).narg.text) != 0 {
         expandarg(argp, &mut arglist, 0x4i32);
       } else {
@@ -9806,7 +11277,25 @@ unsafe extern "C" fn evalcommand(mut cmd: *mut node, mut flags: libc::c_int) ->
     let mut spp_0: *mut *mut strlist = std::ptr::null_mut();
     let mut p: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
     spp_0 = varlist.lastp;
+    #[cfg(feature = "ash-bash-compat")]
+    {
+      if arr_islit((*argp).narg.text) != 0 {
+        argp = arr_evallit(argp);
+        continue;
+      }
+    }
     expandarg(argp, &mut varlist, 0x4i32);
+    #[cfg(feature = "ash-bash-compat")]
+    {
+      (**spp_0).text = arr_assign((**spp_0).text);
+      if (**spp_0).text.is_null() {
+        /* array element: already assigned */
+        *spp_0 = std::ptr::null_mut();
+        varlist.lastp = spp_0;
+        argp = (*argp).narg.next;
+        continue;
+      }
+    }
     mklocal((**spp_0).text);
     /*
      * Modify the command lookup path, if a PATH= assignment
@@ -10303,6 +11792,7 @@ unsafe extern "C" fn preadfd() -> libc::c_int {
         .var_text
         .offset(5);
       reinit_unicode_for_ash();
+      hist_setvars();
       nr = crate::libbb::lineedit::read_line_input(
         line_input_state,
         cmdedit_prompt,
@@ -11591,6 +13081,114 @@ unsafe extern "C" fn parsefname() {
     (*n).nfile.fname = makename()
   };
 }
+/*
+ * Read the "[subscript]" following a name in ${...}.  It is kept unexpanded
+ * (see arr_subscript()).  *CP is '[' on entry and the character after the
+ * closing ']' on return.
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn readsubscript(
+  mut out: *mut libc::c_char,
+  mut cp: *mut libc::c_int,
+) -> *mut libc::c_char {
+  let mut c: libc::c_int = *cp;
+  let mut depth: libc::c_int = 0;
+  loop {
+    /* '=' would end the name part of the CTLVAR text */
+    if c > 255i32 || c == '\n' as i32 || c == '=' as i32 {
+      raise_error_syntax(b"bad subscript\x00" as *const u8 as *const libc::c_char);
+    }
+    if c >= '\u{81}' as i32 && c <= '\u{88}' as i32 {
+      out = _STPUTC('\u{81}' as i32, out)
+    }
+    out = _STPUTC(c, out);
+    if c == '[' as i32 {
+      depth += 1
+    } else if c == ']' as i32 {
+      depth -= 1;
+      if depth == 0 {
+        break;
+      }
+    }
+    c = pgetc_eatbnl()
+  }
+  *cp = pgetc_eatbnl();
+  return out;
+}
+/*
+ * N is the word "name=" (or "name+=") just before a '(': read the words of
+ * the array literal and chain them after it, rewriting N to "name=(" and
+ * ending the list with a ")" word.  Returns the ")" node.
+ */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn parsearraylit(mut n: *mut node) -> *mut node {
+  let mut len: size_t = strlen((*n).narg.text);
+  let mut text: *mut libc::c_char =
+    stalloc(len.wrapping_add(2i32 as libc::c_ulong)) as *mut libc::c_char;
+  strcpy(text, (*n).narg.text);
+  *text.offset(len as isize) = '(' as i32 as libc::c_char;
+  *text.offset(len as isize + 1) = '\u{0}' as i32 as libc::c_char;
+  (*n).narg.text = text;
+  loop {
+    let mut t: libc::c_int = 0;
+    let mut w: *mut node = std::ptr::null_mut();
+    checkkwd = 0x4i32 as smallint;
+    t = readtoken();
+    w = stzalloc(::std::mem::size_of::<narg>() as libc::c_ulong) as *mut node;
+    (*w).type_0 = 15i32 as smallint;
+    if t == TRP as libc::c_int {
+      (*w).narg.text = b")\x00" as *const u8 as *const libc::c_char as *mut libc::c_char;
+      (*n).narg.next = w;
+      return w;
+    }
+    if t != TWORD as libc::c_int {
+      raise_error_unexpected_syntax(TRP as libc::c_int);
+    }
+    (*w).narg.text = wordtext;
+    (*w).narg.backquote = backquotelist;
+    (*n).narg.next = w;
+    n = w
+  }
+}
+/* May N, the last word read by simplecmd(), be followed by an array literal? */
+#[cfg(feature = "ash-bash-compat")]
+unsafe extern "C" fn arraylit_ok(
+  mut n: *mut node,
+  mut args: *mut node,
+  mut vars: libc::c_int,
+) -> libc::c_int {
+  let mut text: *const libc::c_char = std::ptr::null();
+  let mut len: size_t = 0;
+  if n.is_null() || (*n).type_0 as libc::c_int != 15i32 {
+    return 0;
+  }
+  text = (*n).narg.text;
+  len = strlen(text);
+  if len == 0
+    || *text.offset(len as isize - 1) as libc::c_int != '=' as i32
+    || isassignment(text) == 0
+  {
+    return 0;
+  }
+  if vars != 0 {
+    return 1i32;
+  }
+  /* arguments of the declaration builtins */
+  return (!args.is_null()
+    && args != n
+    && (strcmp(
+      (*args).narg.text,
+      b"declare\x00" as *const u8 as *const libc::c_char,
+    ) == 0
+      || strcmp(
+        (*args).narg.text,
+        b"typeset\x00" as *const u8 as *const libc::c_char,
+      ) == 0
+      || strcmp(
+        (*args).narg.text,
+        b"local\x00" as *const u8 as *const libc::c_char,
+      ) == 0)) as libc::c_int;
+}
 unsafe extern "C" fn simplecmd() -> *mut node {
   let mut current_block: u64;
   let mut args: *mut node = std::ptr::null_mut();
@@ -11642,6 +13240,20 @@ unsafe extern "C" fn simplecmd() -> *mut node {
         continue;
       }
       9 => {
+        #[cfg(feature = "ash-bash-compat")]
+        {
+          /* name=(...) */
+          let mut invars: libc::c_int = (!n.is_null() && vpp == &mut (*n).narg.next) as libc::c_int;
+          if arraylit_ok(n, args, invars) != 0 {
+            n = parsearraylit(n);
+            if invars != 0 {
+              vpp = &mut (*n).narg.next
+            } else {
+              app = &mut (*n).narg.next
+            }
+            continue;
+          }
+        }
         current_block = 5423024947874578000;
       }
       _ => {
@@ -12537,6 +14149,15 @@ unsafe extern "C" fn readtoken1(
                     break;
                   }
                 }
+                #[cfg(feature = "ash-bash-compat")]
+                {
+                  /* ${NAME[sub]}, ${#NAME[sub]} */
+                  if c == '[' as i32
+                    && (subtype as libc::c_int == 0 || subtype as libc::c_int == 0xai32)
+                  {
+                    out = readsubscript(out, &mut c)
+                  }
+                }
                 current_block = 168643628589418436;
                 break;
               } else if (c - '0' as i32) as libc::c_uchar as libc::c_int <= 9i32 {
@@ -12559,6 +14180,29 @@ unsafe extern "C" fn readtoken1(
                 /* $[{[#]]<specialchar>[}] */
                 cc = c;
                 c = pgetc_eatbnl();
+                #[cfg(feature = "ash-bash-compat")]
+                {
+                  /* ${!NAME}, ${!NAME[@]} */
+                  if subtype == 0
+                    && cc == '!' as i32
+                    && (c == '_' as i32 || bb_ascii_isalnum(c as libc::c_uchar) != 0)
+                    && (c - '0' as i32) as libc::c_uchar as libc::c_int > 9i32
+                  {
+                    out = _STPUTC('!' as i32, out);
+                    loop {
+                      out = _STPUTC(c, out);
+                      c = pgetc_eatbnl();
+                      if !(c == '_' as i32 || bb_ascii_isalnum(c as libc::c_uchar) != 0) {
+                        break;
+                      }
+                    }
+                    if c == '[' as i32 {
+                      out = readsubscript(out, &mut c)
+                    }
+                    current_block = 168643628589418436;
+                    break;
+                  }
+                }
                 if !(subtype == 0 && cc == '#' as i32) {
                   current_block = 6955753519599442332;
                   break;
@@ -14035,17 +15679,46 @@ unsafe extern "C" fn helpcmd(
     }
     i = i.wrapping_add(1)
   }
+  #[cfg(feature = "ash-bash-compat")]
+  {
+    i = 0 as libc::c_uint;
+    while (i as usize) < bashbuiltintab.len() {
+      col = col.wrapping_add(out1fmt(
+        b"%c%s\x00" as *const u8 as *const libc::c_char,
+        if col == 0 as libc::c_uint {
+          '\t' as i32
+        } else {
+          ' ' as i32
+        },
+        bashbuiltintab[i as usize].name.offset(1),
+      ) as libc::c_uint);
+      if col > 60i32 as libc::c_uint {
+        out1fmt(b"\n\x00" as *const u8 as *const libc::c_char);
+        col = 0 as libc::c_uint
+      }
+      i = i.wrapping_add(1)
+    }
+  }
   newline_and_flush(stdout);
   return 0;
 }
+/* Passes the history variables to lineedit */
+unsafe extern "C" fn hist_setvars() {
+  (*line_input_state).hist_timefmt =
+    lookupvar(b"HISTTIMEFORMAT\x00" as *const u8 as *const libc::c_char);
+  (*line_input_state).hist_control =
+    lookupvar(b"HISTCONTROL\x00" as *const u8 as *const libc::c_char);
+  (*line_input_state).hist_ignore =
+    lookupvar(b"HISTIGNORE\x00" as *const u8 as *const libc::c_char);
+}
 unsafe extern "C" fn historycmd(
   mut _argc: libc::c_int,
-  mut _argv: *mut *mut libc::c_char,
+  mut argv: *mut *mut libc::c_char,
 ) -> libc::c_int {
   if !line_input_state.is_null() {
-    crate::libbb::lineedit::show_history(line_input_state);
+    hist_setvars();
   }
-  return 0;
+  return crate::shell::shell_common::shell_builtin_history(argv, line_input_state);
 }
 /*
  * The export and readonly commands.
@@ -14152,6 +15825,13 @@ unsafe extern "C" fn unsetcmd(
   ap = argptr;
   while !(*ap).is_null() {
     if flag != 'f' as i32 {
+      #[cfg(feature = "ash-bash-compat")]
+      {
+        if arr_unset(*ap) != 0 {
+          ap = ap.offset(1);
+          continue;
+        }
+      }
       unsetvar(*ap);
     } else if flag != 'v' as i32 {
       unsetfunc(*ap);
@@ -14439,6 +16119,15 @@ unsafe extern "C" fn exitshell() -> ! {
       /*free(p); - we'll exit soon */
     }
   }
+  /* HISTFILE: "If unset, the command history is not saved when a shell exits."
+   * Subshells don't save it either */
+  if !line_input_state.is_null()
+    && !lookupvar(b"HISTFILE\x00" as *const u8 as *const libc::c_char).is_null()
+    && getpid() == (*ash_ptr_to_globals_misc).rootpid
+  {
+    hist_setvars();
+    crate::libbb::lineedit::save_history(line_input_state);
+  }
   /* dash wraps setjobctl(0) in "if (setjmp(loc.loc) == 0) {...}".
    * our setjobctl(0) does not panic if tcsetpgrp fails inside it.
    */
@@ -354,7 +354,7 @@ pub struct globals {
   pub saved_tty_pgrp: pid_t,
   pub job_list: *mut pipe,
   pub errexit_depth: libc::c_int,
-  pub o_opt: [libc::c_char; 4],
+  pub o_opt: [libc::c_char; 5],
   pub opt_s: libc::c_char,
   pub opt_c: libc::c_char,
   pub promptmode: smallint,
@@ -381,6 +381,9 @@ pub struct globals {
   pub var_nest_level: libc::c_uint,
   pub func_nest_level: libc::c_uint,
   pub top_func: *mut function,
+  /* the last coproc: its NAME, and its write fd for ${NAME[1]} */
+  pub coproc_name: *mut libc::c_char,
+  pub coproc_wrfd_buf: [libc::c_char; 12],
   pub parse_lineno: libc::c_uint,
   pub execute_lineno: libc::c_uint,
   pub HFILE_list: *mut HFILE,
@@ -453,6 +456,7 @@ pub struct command {
   pub lineno: libc::c_uint,
   pub cmd_type: smallint,
   pub cmd_exitcode: smalluint,
+  pub cmd_coproc: smallint,
   pub group: *mut pipe,
   pub child_func: *mut function,
   pub argv: *mut *mut libc::c_char,
@@ -550,6 +554,7 @@ pub struct save_arg_t {
 }
 pub const OPT_O_XTRACE: C2RustUnnamed_18 = 3;
 pub const OPT_O_NOEXEC: C2RustUnnamed_18 = 1;
+pub const OPT_O_VI: C2RustUnnamed_18 = 4;
 /* "OPTIND=1" */
 /* Builtins */
 
@@ -569,6 +574,7 @@ pub const SCAN_MOVE_FROM_LEFT: C2RustUnnamed_13 = 1;
 pub const SCAN_MATCH_RIGHT_HALF: C2RustUnnamed_13 = 8;
 pub const REDIRFD_TO_FILE: redir_type = -1;
 pub const REDIRFD_CLOSE: redir_type = -3;
+pub const REDIRFD_TO_WORD: redir_type = -4;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -640,7 +646,7 @@ pub const OPT_login: C2RustUnnamed_11 = 1;
 pub type C2RustUnnamed_11 = libc::c_uint;
 pub type C2RustUnnamed_12 = libc::c_uint;
 pub const WITH_PATH_LOOKUP: C2RustUnnamed_12 = 16;
-pub const VI_MODE: C2RustUnnamed_12 = 0;
+pub const VI_MODE: C2RustUnnamed_12 = 8;
 pub const USERNAME_COMPLETION: C2RustUnnamed_12 = 4;
 pub const TAB_COMPLETION: C2RustUnnamed_12 = 2;
 pub const DO_HISTORY: C2RustUnnamed_12 = 1;
@@ -653,7 +659,7 @@ pub type C2RustUnnamed_16 = libc::c_uint;
 pub type C2RustUnnamed_17 = libc::c_uint;
 pub const BC_CONTINUE: C2RustUnnamed_17 = 2;
 pub type C2RustUnnamed_18 = libc::c_uint;
-pub const NUM_OPT_O: C2RustUnnamed_18 = 4;
+pub const NUM_OPT_O: C2RustUnnamed_18 = 5;
 /* Basic theory of signal handling in shell
  * ========================================
  * This does not describe what hush does, rather, it is current understanding
@@ -886,11 +892,11 @@ static mut redir_table: [C2RustUnnamed_10; 5] = [
     init
   },
 ];
-static mut o_opt_strings: [libc::c_char; 32] = [
+static mut o_opt_strings: [libc::c_char; 35] = [
   112, 105, 112, 101, 102, 97, 105, 108, 0, 110, 111, 101, 120, 101, 99, 0, 101, 114, 114, 101,
-  120, 105, 116, 0, 120, 116, 114, 97, 99, 101, 0, 0,
+  120, 105, 116, 0, 120, 116, 114, 97, 99, 101, 0, 118, 105, 0, 0,
 ];
-static mut bltins1: [built_in_command; 31] = {
+static mut bltins1: [built_in_command; 34] = {
   [
     {
       let mut init = built_in_command {
@@ -922,6 +928,16 @@ static mut bltins1: [built_in_command; 31] = {
       };
       init
     },
+    {
+      let mut init = built_in_command {
+        b_cmd: b"bind\x00" as *const u8 as *const libc::c_char,
+        b_function: Some(
+          builtin_bind as unsafe extern "C" fn(_: *mut *mut libc::c_char) -> libc::c_int,
+        ),
+        b_descr: b"Set key bindings\x00" as *const u8 as *const libc::c_char,
+      };
+      init
+    },
     {
       let mut init = built_in_command {
         b_cmd: b"break\x00" as *const u8 as *const libc::c_char,
@@ -942,6 +958,26 @@ static mut bltins1: [built_in_command; 31] = {
       };
       init
     },
+    {
+      let mut init = built_in_command {
+        b_cmd: b"compgen\x00" as *const u8 as *const libc::c_char,
+        b_function: Some(
+          builtin_compgen as unsafe extern "C" fn(_: *mut *mut libc::c_char) -> libc::c_int,
+        ),
+        b_descr: b"Generate completion matches\x00" as *const u8 as *const libc::c_char,
+      };
+      init
+    },
+    {
+      let mut init = built_in_command {
+        b_cmd: b"complete\x00" as *const u8 as *const libc::c_char,
+        b_function: Some(
+          builtin_complete as unsafe extern "C" fn(_: *mut *mut libc::c_char) -> libc::c_int,
+        ),
+        b_descr: b"Set completion for commands\x00" as *const u8 as *const libc::c_char,
+      };
+      init
+    },
     {
       let mut init = built_in_command {
         b_cmd: b"continue\x00" as *const u8 as *const libc::c_char,
@@ -1764,6 +1800,14 @@ unsafe extern "C" fn hush_exit(mut exitcode: libc::c_int) -> ! {
      * in the handler */
     builtin_eval(argv.as_mut_ptr());
   }
+  /* Subshells don't save history */
+  if !(*ptr_to_globals).line_input_state.is_null()
+    && !(*(*ptr_to_globals).line_input_state).hist_file.is_null()
+    && getpid() == (*ptr_to_globals).root_pid
+  {
+    hist_setvars();
+    crate::libbb::lineedit::save_history((*ptr_to_globals).line_input_state);
+  }
   crate::libbb::xfuncs_printf::fflush_all();
   sigexit(-(exitcode & 0xffi32));
 }
@@ -2305,6 +2349,7 @@ unsafe extern "C" fn get_user_input(mut i: *mut in_str) -> libc::c_int {
     }
     /* buglet: SIGINT will not make new prompt to appear _at once_,
      * only after <Enter>. (^C works immediately) */
+    hist_setvars();
     r = crate::libbb::lineedit::read_line_input(
       (*ptr_to_globals).line_input_state,
       prompt_str,
@@ -3568,6 +3613,8 @@ unsafe extern "C" fn done_word(mut ctx: *mut parse_context) -> libc::c_int {
     // <<EOF$t
     // <<EOF$((1))
     // <<EOF`true`  [this case also makes heredoc "quoted", a-la <<"EOF". Probably bash-4.3.43 bug]
+    /* "< <(cmd)" and "> >(cmd)": setup_redirects() expands the word,
+     * which starts cmd and gives its /dev/fd/N name */
     (*(*ctx).pending_redirect).rd_filename = crate::libbb::xfuncs_printf::xstrdup((*ctx).word.data);
     /* Cater for >\file case:
      * >\a creates file a; >\\a, >"\a", >"\\a" create file \a
@@ -3619,6 +3666,19 @@ unsafe extern "C" fn done_word(mut ctx: *mut parse_context) -> libc::c_int {
         o_reset_to_empty_unquoted(&mut (*ctx).word);
         return ((*ctx).ctx_res_w as libc::c_int == RES_SNTX as libc::c_int) as libc::c_int;
       }
+      /* "coproc [NAME] { list; }" or "coproc cmd args":
+       * the keyword itself is not stored, only remembered */
+      if (*command).cmd_coproc == 0
+        && (*ctx).word.has_quoted_part == 0
+        && strcmp(
+          (*ctx).word.data,
+          b"coproc\x00" as *const u8 as *const libc::c_char,
+        ) == 0
+      {
+        (*command).cmd_coproc = 1i32 as smallint;
+        o_reset_to_empty_unquoted(&mut (*ctx).word);
+        return 0;
+      }
       if 0 != 0
         || strcmp(
           (*ctx).word.data,
@@ -3691,6 +3751,7 @@ unsafe extern "C" fn done_word(mut ctx: *mut parse_context) -> libc::c_int {
  * REDIRFD_CLOSE if >&- "close fd" construct is seen,
  * REDIRFD_SYNTAX_ERR if syntax error,
  * REDIRFD_TO_FILE if no & was seen,
+ * REDIRFD_TO_WORD for >&$fd and >&"${fd}", the fd is known at run time,
  * or the number found.
  */
 unsafe extern "C" fn parse_redir_right_fd(mut input: *mut in_str) -> libc::c_int {
@@ -3718,6 +3779,9 @@ unsafe extern "C" fn parse_redir_right_fd(mut input: *mut in_str) -> libc::c_int
   if ok != 0 {
     return d;
   }
+  if ch == '$' as i32 || ch == '"' as i32 {
+    return REDIRFD_TO_WORD as libc::c_int;
+  }
   //TODO: this is the place to catch ">&file" bashism (redirect both fd 1 and 2)
   crate::libbb::verror_msg::bb_simple_error_msg(
     b"ambiguous redirect\x00" as *const u8 as *const libc::c_char,
@@ -3787,7 +3851,8 @@ unsafe extern "C" fn parse_redirect(
   };
   (*redir).rd_dup = dup_num;
   if !(style as libc::c_int != REDIRECT_HEREDOC as libc::c_int
-    && dup_num != REDIRFD_TO_FILE as libc::c_int)
+    && dup_num != REDIRFD_TO_FILE as libc::c_int
+    && dup_num != REDIRFD_TO_WORD as libc::c_int)
   {
     /* Instead we emit error message at run time */
     /* Set ctx->pending_redirect, so we know what to do at the
@@ -4085,6 +4150,34 @@ unsafe extern "C" fn parse_group(
   return heredoc_cnt;
   /* command remains "open", available for possible redirects */
 }
+/* ${NAME[N]}: the [ was just read. A subscript after a variable name
+ * is a part of it: copy it to dest and return the char following the ],
+ * or 0 on syntax error. Other names ($1[, ${#[) get the [ back as is.
+ */
+unsafe extern "C" fn add_var_subscript(
+  mut dest: *mut o_string,
+  mut input: *mut in_str,
+  mut first_ch: libc::c_char,
+) -> libc::c_int {
+  if first_ch as libc::c_int != '_' as i32
+    && (((first_ch as libc::c_int | 0x20i32) - 'a' as i32) as libc::c_uchar as libc::c_int
+      > 'z' as i32 - 'a' as i32)
+  {
+    return '[' as i32;
+  }
+  o_addchr(dest, '[' as i32);
+  loop {
+    let mut ch: libc::c_int = i_getch(input);
+    if ch == -1i32 || ch == '}' as i32 {
+      syntax_error_unterm_ch('[' as i32 as libc::c_char);
+      return 0;
+    }
+    o_addchr(dest, ch);
+    if ch == ']' as i32 {
+      return i_getch(input);
+    }
+  }
+}
 /* Subroutines for copying $(...) and `...` things */
 /* '...' */
 unsafe extern "C" fn add_till_single_quote(
@@ -4336,6 +4429,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -4559,6 +4658,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -4743,6 +4848,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -4927,6 +5038,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -5110,6 +5227,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -5294,6 +5417,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -5481,6 +5610,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -5665,6 +5800,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -5852,6 +5993,12 @@ unsafe extern "C" fn parse_dollar(
                   let mut pos: libc::c_uint = 0;
                   o_addchr(dest, ch);
                   ch = i_getch(input);
+                  if ch == '[' as i32 {
+                    ch = add_var_subscript(dest, input, len_single_ch);
+                    if ch == 0 {
+                      return 0;
+                    }
+                  }
                   if ch == '}' as i32 {
                     current_block_96 = 15587532755333643506;
                     break;
@@ -6233,8 +6380,10 @@ unsafe extern "C" fn parse_stream(
           next = i_peek_and_eat_bkslash_nl(input)
         }
         is_special = b"{}<>;&|()#$\"`\x03\x00" as *const u8 as *const libc::c_char;
-        /* Are { and } special here? */
+        /* Are { and } special here?
+         * "coproc NAME { list; }": yes, after the coproc name */
         if !(*ctx.command).argv.is_null()
+          && ((*ctx.command).cmd_coproc == 0 || !(*(*ctx.command).argv.offset(1)).is_null())
           || ctx.word.length != 0
           || ctx.word.has_quoted_part as libc::c_int != 0
           || next != ';' as i32
@@ -6415,6 +6564,24 @@ unsafe extern "C" fn parse_stream(
                    * an assignment. a=1 2>z b=2: b=2 is still assignment */
                   match ch {
                     62 => {
+                      if next == '(' as i32 {
+                        /* >(cmd) process substitution, also as the target
+                         * of a pending redirect: "cmd > >(cmd2)" */
+                        ch = i_getch(input);
+                        o_addchr(&mut ctx.word, 3i32);
+                        o_addchr(&mut ctx.word, '>' as i32);
+                        if add_till_closing_bracket(
+                          &mut ctx.word,
+                          input,
+                          ')' as i32 as libc::c_uint,
+                        ) == 0
+                        {
+                          current_block = 1907364584679199995;
+                          break;
+                        }
+                        o_addchr(&mut ctx.word, 3i32);
+                        continue;
+                      }
                       current_block = 11456536786442373077;
                       match current_block {
                         8338824944507035481 => {
@@ -6483,6 +6650,24 @@ unsafe extern "C" fn parse_stream(
                       }
                     }
                     60 => {
+                      if next == '(' as i32 {
+                        /* <(cmd) process substitution, also as the target
+                         * of a pending redirect: "done < <(cmd)" */
+                        ch = i_getch(input);
+                        o_addchr(&mut ctx.word, 3i32);
+                        o_addchr(&mut ctx.word, '<' as i32);
+                        if add_till_closing_bracket(
+                          &mut ctx.word,
+                          input,
+                          ')' as i32 as libc::c_uint,
+                        ) == 0
+                        {
+                          current_block = 1907364584679199995;
+                          break;
+                        }
+                        o_addchr(&mut ctx.word, 3i32);
+                        continue;
+                      }
                       current_block = 14429734548985272971;
                       match current_block {
                         8338824944507035481 => {
@@ -7490,6 +7675,28 @@ unsafe extern "C" fn append_str_maybe_ifs_split(
   }
   return n;
 }
+/* ${NAME[N]}: hush has no arrays. As in bash, a variable is an array
+ * of one element: ${NAME[0]} is $NAME, other elements are unset.
+ * The exception is a coproc, NAME[1] is its write fd.
+ */
+unsafe extern "C" fn get_subscripted_var_value(mut name: *mut libc::c_char) -> *const libc::c_char {
+  let mut val: *const libc::c_char = std::ptr::null();
+  let mut sq: *mut libc::c_char = strchr(name, '[' as i32);
+  if sq.is_null() {
+    return get_local_var_value(name);
+  }
+  *sq = '\u{0}' as i32 as libc::c_char;
+  if strcmp(sq.offset(1), b"0]\x00" as *const u8 as *const libc::c_char) == 0 {
+    val = get_local_var_value(name)
+  } else if strcmp(sq.offset(1), b"1]\x00" as *const u8 as *const libc::c_char) == 0
+    && !(*ptr_to_globals).coproc_name.is_null()
+    && strcmp(name, (*ptr_to_globals).coproc_name) == 0
+  {
+    val = (*ptr_to_globals).coproc_wrfd_buf.as_mut_ptr()
+  }
+  *sq = '[' as i32 as libc::c_char;
+  return val;
+}
 /* Handle <SPECIAL_VAR_SYMBOL>varname...<SPECIAL_VAR_SYMBOL> construct.
  */
 #[inline(never)]
@@ -7657,7 +7864,7 @@ unsafe extern "C" fn expand_one_var(
         }
         *cp = '\u{0}' as i32 as libc::c_char
       }
-      _ => val = get_local_var_value(var),
+      _ => val = get_subscripted_var_value(var),
     }
   }
   /* Handle any expansions */
@@ -8110,6 +8317,33 @@ unsafe extern "C" fn expand_vars_to_list(
         n = append_str_maybe_ifs_split(output, n, first_ch as libc::c_int, subst_result.data);
         o_free(&mut subst_result);
       }
+      60 | 62 => {
+        /* <SPECIAL_VAR_SYMBOL><cmd<SPECIAL_VAR_SYMBOL> or >cmd: process substitution */
+        let mut pid: pid_t = 0;
+        let mut fd: libc::c_int = 0;
+        *p = '\u{0}' as i32 as libc::c_char;
+        /* <(cmd): we read what cmd writes to its stdout,
+         * >(cmd): we write to cmd's stdin */
+        fd = generate_stream_from_string(
+          arg.offset(1),
+          &mut pid,
+          (first_ch as libc::c_int & 0x7fi32 == '<' as i32) as libc::c_int,
+        );
+        (*ptr_to_globals).last_bg_pid = pid;
+        procsub_fds = crate::libbb::xfuncs_printf::xrealloc(
+          procsub_fds as *mut libc::c_void,
+          (::std::mem::size_of::<libc::c_int>() as libc::c_ulong)
+            .wrapping_mul((procsub_cnt + 1i32) as libc::c_ulong),
+        ) as *mut libc::c_int;
+        *procsub_fds.offset(procsub_cnt as isize) = fd;
+        procsub_cnt += 1;
+        sprintf(
+          arith_buf.as_mut_ptr(),
+          b"/dev/fd/%d\x00" as *const u8 as *const libc::c_char,
+          fd,
+        );
+        o_addstr(output, arith_buf.as_mut_ptr());
+      }
       43 => {
         /* <SPECIAL_VAR_SYMBOL>+arith<SPECIAL_VAR_SYMBOL> */
         let mut res: arith_t = 0; /* skip '+' */
@@ -8428,9 +8662,22 @@ unsafe extern "C" fn parse_and_run_file(mut fp: *mut HFILE) {
   parse_and_run_stream(&mut input, ';' as i32);
   (*ptr_to_globals).parse_lineno = sv;
 }
+/* Parent ends of <(cmd) and >(cmd) pipes. They must stay open
+ * until the command which got their /dev/fd/N names has run */
+static mut procsub_fds: *mut libc::c_int = 0 as *mut libc::c_int;
+static mut procsub_cnt: libc::c_int = 0;
+unsafe extern "C" fn close_procsub_fds(mut mark: libc::c_int) {
+  while procsub_cnt > mark {
+    procsub_cnt -= 1;
+    close(*procsub_fds.offset(procsub_cnt as isize));
+  }
+}
+/* Runs s in a child whose stdout (child_fd == 1) or stdin (child_fd == 0)
+ * is a pipe. Returns the other end of the pipe. */
 unsafe extern "C" fn generate_stream_from_string(
   mut s: *const libc::c_char,
   mut pid_p: *mut pid_t,
+  mut child_fd: libc::c_int,
 ) -> libc::c_int {
   let mut pid: pid_t = 0;
   let mut channel: [libc::c_int; 2] = [0; 2];
@@ -8462,8 +8709,8 @@ unsafe extern "C" fn generate_stream_from_string(
       0 + (1i32 << 20i32) + (1i32 << 21i32) + (1i32 << 22i32),
       ::std::mem::transmute::<libc::intptr_t, __sighandler_t>(1i32 as libc::intptr_t),
     ); /* NB: close _first_, then move fd! */
-    close(channel[0]);
-    crate::libbb::xfuncs_printf::xmove_fd(channel[1], 1i32);
+    close(channel[1 - child_fd as usize]);
+    crate::libbb::xfuncs_printf::xmove_fd(channel[child_fd as usize], child_fd);
     /* Awful hack for `trap` or $(trap).
      *
      * http://www.opengroup.org/onlinepubs/009695399/utilities/trap.html
@@ -8529,8 +8776,8 @@ unsafe extern "C" fn generate_stream_from_string(
   >(Some(
     restore_ttypgrp_and__exit as unsafe extern "C" fn() -> !,
   ));
-  close(channel[1]);
-  return channel[0];
+  close(channel[child_fd as usize]);
+  return channel[1 - child_fd as usize];
 }
 /* Return code is exit status of the process that is run. */
 unsafe extern "C" fn process_command_subs(
@@ -8542,7 +8789,7 @@ unsafe extern "C" fn process_command_subs(
   let mut status: libc::c_int = 0;
   let mut ch: libc::c_int = 0;
   let mut eol_cnt: libc::c_int = 0;
-  fp = crate::libbb::wfopen::xfdopen_for_read(generate_stream_from_string(s, &mut pid));
+  fp = crate::libbb::wfopen::xfdopen_for_read(generate_stream_from_string(s, &mut pid, 1i32));
   /* Now send results of command back into original context */
   eol_cnt = 0;
   loop {
@@ -8905,6 +9152,27 @@ unsafe extern "C" fn setup_redirects(
           }
           current_block_32 = 15125582407903384992;
         }
+      } else if (*redir).rd_dup == REDIRFD_TO_WORD as libc::c_int {
+        /* "rd_fd>&$var" case: $var must be a number or - */
+        let mut p_0: *mut libc::c_char = expand_string_to_string(
+          (*redir).rd_filename,
+          EXP_FLAG_ESC_GLOB_CHARS as libc::c_int,
+          1i32,
+        );
+        newfd = crate::libbb::bb_strtonum::bb_strtou(p_0, 0 as *mut *mut libc::c_char, 10i32)
+          as libc::c_int;
+        if strcmp(p_0, b"-\x00" as *const u8 as *const libc::c_char) == 0 {
+          newfd = REDIRFD_CLOSE as libc::c_int
+        } else if *bb_errno != 0 || newfd < 0 {
+          crate::libbb::verror_msg::bb_error_msg(
+            b"%s: ambiguous redirect\x00" as *const u8 as *const libc::c_char,
+            p_0,
+          );
+          free(p_0 as *mut libc::c_void);
+          return 1i32;
+        }
+        free(p_0 as *mut libc::c_void);
+        current_block_32 = 15125582407903384992;
       } else {
         /* "rd_fd>&rd_dup" or "rd_fd>&-" case */
         newfd = (*redir).rd_dup;
@@ -9014,7 +9282,7 @@ unsafe extern "C" fn find_builtin1(mut name: *const libc::c_char) -> *const buil
     name,
     bltins1.as_ptr(),
     &*bltins1.as_ptr().offset(
-      (::std::mem::size_of::<[built_in_command; 31]>() as libc::c_ulong)
+      (::std::mem::size_of::<[built_in_command; 34]>() as libc::c_ulong)
         .wrapping_div(::std::mem::size_of::<built_in_command>() as libc::c_ulong)
         as libc::c_uint as isize,
     ),
@@ -9906,6 +10174,8 @@ unsafe extern "C" fn run_pipe(mut pi: *mut pipe) -> libc::c_int {
   let mut argv: *mut *mut libc::c_char = std::ptr::null_mut();
   let mut squirrel: *mut squirrel = std::ptr::null_mut();
   let mut rcode: libc::c_int = 0;
+  let mut coproc_in: fd_pair = fd_pair { rd: 0, wr: 0 };
+  let mut coproc_out: fd_pair = fd_pair { rd: 0, wr: 0 };
   /* Testcase: set -- q w e; (IFS='' echo "$*"; IFS=''; echo "$*"); echo "$*"
    * Result should be 3 lines: q w e, qwe, q w e
    */
@@ -9956,6 +10226,12 @@ unsafe extern "C" fn run_pipe(mut pi: *mut pipe) -> libc::c_int {
   (*pi).stopped_cmds = 0;
   command = &mut *(*pi).cmds.offset(0) as *mut command;
   argv_expanded = std::ptr::null_mut();
+  if (*command).cmd_coproc != 0 {
+    /* coproc is a background job with both ends piped to us */
+    (*pi).followup = PIPE_BG as libc::c_int as smallint;
+    crate::libbb::xfuncs_printf::xpipe(&mut coproc_in.rd);
+    crate::libbb::xfuncs_printf::xpipe(&mut coproc_out.rd);
+  }
   if !((*pi).num_cmds != 1i32
     || (*pi).followup as libc::c_int == PIPE_BG as libc::c_int
     || (*command).cmd_type as libc::c_int == 1i32)
@@ -10221,6 +10497,9 @@ unsafe extern "C" fn run_pipe(mut pi: *mut pipe) -> libc::c_int {
   /* Going to fork a child per each pipe member */
   (*pi).alive_cmds = 0;
   next_infd = 0;
+  if (*(*pi).cmds).cmd_coproc != 0 {
+    next_infd = coproc_in.rd
+  }
   cmd_no = 0;
   while cmd_no < (*pi).num_cmds {
     let mut pipefds: fd_pair = fd_pair { rd: 0, wr: 0 };
@@ -10242,6 +10521,8 @@ unsafe extern "C" fn run_pipe(mut pi: *mut pipe) -> libc::c_int {
     pipefds.wr = 1i32;
     if cmd_no < (*pi).num_cmds {
       crate::libbb::xfuncs_printf::xpipe(&mut pipefds.rd);
+    } else if (*(*pi).cmds).cmd_coproc != 0 {
+      pipefds.wr = coproc_out.wr
     }
     (*ptr_to_globals).execute_lineno = (*command).lineno;
     (*command).pid = if 1i32 != 0 { fork() } else { vfork() };
@@ -10251,6 +10532,14 @@ unsafe extern "C" fn run_pipe(mut pi: *mut pipe) -> libc::c_int {
         Option<unsafe extern "C" fn() -> !>,
         Option<unsafe extern "C" fn() -> ()>,
       >(Some(fflush_and__exit as unsafe extern "C" fn() -> !));
+      if (*(*pi).cmds).cmd_coproc != 0 {
+        close(coproc_in.wr);
+        close(coproc_out.rd);
+        /* "coproc NAME { list; }": NAME is not a command */
+        if !(*command).group.is_null() {
+          (*command).argv = std::ptr::null_mut()
+        }
+      }
       /* pseudo_exec() does not return */
       (*ptr_to_globals).random_gen.galois_LFSR = 0; /* or else $RANDOM repeats in child */
       if (*ptr_to_globals).run_list_level == 1i32 && (*ptr_to_globals).interactive_fd != 0 {
@@ -10272,7 +10561,10 @@ unsafe extern "C" fn run_pipe(mut pi: *mut pipe) -> libc::c_int {
           tcsetpgrp((*ptr_to_globals).interactive_fd, pgrp);
         }
       }
-      if (*pi).alive_cmds == 0 && (*pi).followup as libc::c_int == PIPE_BG as libc::c_int {
+      if (*pi).alive_cmds == 0
+        && (*pi).followup as libc::c_int == PIPE_BG as libc::c_int
+        && (*(*pi).cmds).cmd_coproc == 0
+      {
         /* 1st cmd in backgrounded pipe
          * should have its stdin /dev/null'ed */
         close(0i32);
@@ -10339,11 +10631,59 @@ unsafe extern "C" fn run_pipe(mut pi: *mut pipe) -> libc::c_int {
     /* Pass read (output) pipe end to next iteration */
     next_infd = pipefds.rd
   }
+  if (*(*pi).cmds).cmd_coproc != 0 {
+    close(coproc_in.rd);
+    close(coproc_out.wr);
+    if (*pi).alive_cmds == 0 {
+      close(coproc_in.wr);
+      close(coproc_out.rd);
+    } else {
+      set_coproc_vars(pi, coproc_out.rd, coproc_in.wr, (*command).pid);
+    }
+  }
   if (*pi).alive_cmds == 0 {
     return 1i32;
   }
   return -1i32;
 }
+/* NAME=rdfd (also ${NAME[0]}), ${NAME[1]}=wrfd, NAME_PID=pid */
+unsafe extern "C" fn set_coproc_vars(
+  mut pi: *mut pipe,
+  mut rdfd: libc::c_int,
+  mut wrfd: libc::c_int,
+  mut pid: pid_t,
+) {
+  let mut name: *const libc::c_char = b"COPROC\x00" as *const u8 as *const libc::c_char;
+  if !(*(*pi).cmds).group.is_null() && !(*(*pi).cmds).argv.is_null() {
+    name = *(*(*pi).cmds).argv
+  }
+  /* not inherited by other children: use >&${NAME[1]} to pass them on */
+  crate::libbb::xfuncs::close_on_exec_on(rdfd);
+  crate::libbb::xfuncs::close_on_exec_on(wrfd);
+  set_local_var(
+    crate::libbb::xfuncs_printf::xasprintf(
+      b"%s=%d\x00" as *const u8 as *const libc::c_char,
+      name,
+      rdfd,
+    ),
+    0 as libc::c_uint,
+  );
+  free((*ptr_to_globals).coproc_name as *mut libc::c_void);
+  (*ptr_to_globals).coproc_name = crate::libbb::xfuncs_printf::xstrdup(name);
+  sprintf(
+    (*ptr_to_globals).coproc_wrfd_buf.as_mut_ptr(),
+    b"%d\x00" as *const u8 as *const libc::c_char,
+    wrfd,
+  );
+  set_local_var(
+    crate::libbb::xfuncs_printf::xasprintf(
+      b"%s_PID=%d\x00" as *const u8 as *const libc::c_char,
+      name,
+      pid,
+    ),
+    0 as libc::c_uint,
+  );
+}
 /* NB: called by pseudo_exec, and therefore must not modify any
  * global data until exec/_exit (we can be a child after vfork!) */
 unsafe extern "C" fn run_list(mut pi: *mut pipe) -> libc::c_int {
@@ -10357,6 +10697,7 @@ unsafe extern "C" fn run_list(mut pi: *mut pipe) -> libc::c_int {
   let mut cond_code: smalluint = 0 as smalluint;
   let mut rword: smallint = 0;
   let mut last_rword: smallint = 0;
+  let mut procsub_mark: libc::c_int = 0;
   /* Check syntax for "for" */
   let mut cpipe: *mut pipe = std::ptr::null_mut();
   cpipe = pi;
@@ -10583,7 +10924,10 @@ unsafe extern "C" fn run_list(mut pi: *mut pipe) -> libc::c_int {
                      * after run_pipe to collect any background children,
                      * even if list execution is to be stopped. */
                     (*ptr_to_globals).flag_break_continue = 0 as smallint; /* NB: rcode is a smalluint, r is int */
+                    procsub_mark = procsub_cnt;
                     r = run_pipe(pi);
+                    /* children got their copies of <(cmd) fds by now */
+                    close_procsub_fds(procsub_mark);
                     rcode = r as smalluint;
                     if r != -1i32 {
                       /* We ran a builtin, function, or group.
@@ -10878,6 +11222,13 @@ unsafe extern "C" fn set_mode(
         idx = crate::libbb::compare_string_array::index_in_strings(o_opt_strings.as_ptr(), o_opt);
         if idx >= 0 {
           (*ptr_to_globals).o_opt[idx as usize] = state as libc::c_char;
+          if idx == OPT_O_VI as libc::c_int && !(*ptr_to_globals).line_input_state.is_null() {
+            if state != 0 {
+              (*(*ptr_to_globals).line_input_state).flags |= VI_MODE as libc::c_int
+            } else {
+              (*(*ptr_to_globals).line_input_state).flags &= !(VI_MODE as libc::c_int)
+            }
+          }
           current_block_13 = 11584701595673473500;
         } else {
           current_block_13 = 17170860877845389804;
@@ -11245,6 +11596,13 @@ pub unsafe fn hush_main(mut argc: libc::c_int, mut argv: *mut *mut libc::c_char)
           ));
           (*ptr_to_globals).line_input_state =
             crate::libbb::lineedit::new_line_input_t(FOR_SHELL as libc::c_int);
+          (*(*ptr_to_globals).line_input_state).complete_hook = Some(
+            hush_complete
+              as unsafe extern "C" fn(_: *const libc::c_char) -> *mut *mut libc::c_char,
+          );
+          if (*ptr_to_globals).o_opt[OPT_O_VI as libc::c_int as usize] != 0 {
+            (*(*ptr_to_globals).line_input_state).flags |= VI_MODE as libc::c_int
+          }
           let mut hp: *const libc::c_char =
             get_local_var_value(b"HISTFILE\x00" as *const u8 as *const libc::c_char);
           if hp.is_null() {
@@ -11331,7 +11689,7 @@ unsafe extern "C" fn builtin_help(mut _argv: *mut *mut libc::c_char) -> libc::c_
   x = bltins1.as_ptr();
   while x
     != &*bltins1.as_ptr().offset(
-      (::std::mem::size_of::<[built_in_command; 31]>() as libc::c_ulong)
+      (::std::mem::size_of::<[built_in_command; 34]>() as libc::c_ulong)
         .wrapping_div(::std::mem::size_of::<built_in_command>() as libc::c_ulong)
         as libc::c_uint as isize,
     ) as *const built_in_command
@@ -11347,11 +11705,23 @@ unsafe extern "C" fn builtin_help(mut _argv: *mut *mut libc::c_char) -> libc::c_
   }
   return 0;
 }
-unsafe extern "C" fn builtin_history(mut _argv: *mut *mut libc::c_char) -> libc::c_int {
+/* Passes the history variables to lineedit */
+unsafe extern "C" fn hist_setvars() {
+  let mut st: *mut line_input_t = (*ptr_to_globals).line_input_state;
+  (*st).hist_timefmt =
+    get_local_var_value(b"HISTTIMEFORMAT\x00" as *const u8 as *const libc::c_char);
+  (*st).hist_control =
+    get_local_var_value(b"HISTCONTROL\x00" as *const u8 as *const libc::c_char);
+  (*st).hist_ignore = get_local_var_value(b"HISTIGNORE\x00" as *const u8 as *const libc::c_char);
+}
+unsafe extern "C" fn builtin_history(mut argv: *mut *mut libc::c_char) -> libc::c_int {
   if !(*ptr_to_globals).line_input_state.is_null() {
-    crate::libbb::lineedit::show_history((*ptr_to_globals).line_input_state);
+    hist_setvars();
   }
-  return 0;
+  return crate::shell::shell_common::shell_builtin_history(
+    argv,
+    (*ptr_to_globals).line_input_state,
+  );
 }
 unsafe extern "C" fn skip_dash_dash(mut argv: *mut *mut libc::c_char) -> *mut *mut libc::c_char {
   argv = argv.offset(1);
@@ -11401,6 +11771,37 @@ unsafe extern "C" fn builtin_cd(mut argv: *mut *mut libc::c_char) -> libc::c_int
   set_pwd_var(0i32 as libc::c_uint);
   return 0;
 }
+/* complete/compgen: -F FUNC and -C CMD are run like `...` */
+unsafe extern "C" fn compgen_run(mut script: *const libc::c_char) -> *mut libc::c_char {
+  let mut pid: pid_t = 0;
+  let mut status: libc::c_int = 0;
+  let mut fd: libc::c_int = generate_stream_from_string(script, &mut pid, 1i32);
+  let mut out: *mut libc::c_char =
+    crate::libbb::read_printf::xmalloc_read(fd, std::ptr::null_mut()) as *mut libc::c_char;
+  close(fd);
+  crate::libbb::xfuncs::safe_waitpid(pid, &mut status, 0);
+  return out;
+}
+static mut compgen_hush: crate::shell::shell_common::compgen_hooks =
+  crate::shell::shell_common::compgen_hooks {
+    run: Some(compgen_run as unsafe extern "C" fn(_: *const libc::c_char) -> *mut libc::c_char),
+    getvar: Some(
+      get_local_var_value as unsafe extern "C" fn(_: *const libc::c_char) -> *const libc::c_char,
+    ),
+    arrays: 0,
+  };
+unsafe extern "C" fn hush_complete(mut line: *const libc::c_char) -> *mut *mut libc::c_char {
+  return crate::shell::shell_common::shell_complete_line(line, &mut compgen_hush);
+}
+unsafe extern "C" fn builtin_complete(mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  return crate::shell::shell_common::shell_builtin_complete(argv, &mut compgen_hush);
+}
+unsafe extern "C" fn builtin_bind(mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  return crate::shell::shell_common::shell_builtin_bind(argv, (*ptr_to_globals).line_input_state);
+}
+unsafe extern "C" fn builtin_compgen(mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  return crate::shell::shell_common::shell_builtin_compgen(argv, &mut compgen_hush);
+}
 unsafe extern "C" fn builtin_pwd(mut _argv: *mut *mut libc::c_char) -> libc::c_int {
   puts(get_cwd(0i32));
   return 0;
@@ -964,3 +964,895 @@ pub unsafe extern "C" fn shell_builtin_ulimit(mut argv: *mut *mut libc::c_char)
   }
   return 0;
 }
+/*
+ * Programmable completion: the "complete" and "compgen" builtins.
+ *
+ * complete [-fdcu] [-A ACTION] [-W WORDS] [-F FUNC] [-C CMD] NAME...
+ * complete [-p] [NAME...]
+ * complete -r [NAME...]
+ * compgen [-fdcu] [-A ACTION] [-W WORDS] [-F FUNC] [-C CMD] [WORD]
+ *
+ * The specs are kept here, for both shells. -F and -C need the shell
+ * to run something in a subshell and give back what it printed.
+ */
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct compspec {
+  pub next: *mut compspec,
+  pub cmd: *mut libc::c_char,
+  pub actions: libc::c_uint,
+  pub wordlist: *mut libc::c_char,
+  pub func: *mut libc::c_char,
+  pub command: *mut libc::c_char,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct compgen_hooks {
+  /* Run SCRIPT in a subshell, return what it wrote to stdout (malloced) */
+  pub run: Option<unsafe extern "C" fn(_: *const libc::c_char) -> *mut libc::c_char>,
+  pub getvar: Option<unsafe extern "C" fn(_: *const libc::c_char) -> *const libc::c_char>,
+  /* COMP_WORDS and COMPREPLY are arrays. If not, they are
+   * whitespace-separated strings */
+  pub arrays: libc::c_int,
+}
+pub const CA_FILE: libc::c_uint = 1;
+pub const CA_DIRECTORY: libc::c_uint = 2;
+pub const CA_COMMAND: libc::c_uint = 4;
+pub const CA_USER: libc::c_uint = 8;
+/* -A names, in CA_xxx bit order */
+static mut comp_action_names: [libc::c_char; 29] = unsafe {
+  *::std::mem::transmute::<&[u8; 29], &mut [libc::c_char; 29]>(
+    b"file\x00directory\x00command\x00user\x00\x00",
+  )
+};
+static mut compspecs: *mut compspec = 0 as *mut compspec;
+unsafe extern "C" fn cand_add(
+  mut v: *mut *mut libc::c_char,
+  mut n: *mut libc::c_int,
+  mut s: *mut libc::c_char,
+) -> *mut *mut libc::c_char {
+  v = crate::libbb::xrealloc_vector::xrealloc_vector_helper(
+    v as *mut libc::c_void,
+    ((::std::mem::size_of::<*mut libc::c_char>() as libc::c_ulong) << 8i32)
+      .wrapping_add(4i32 as libc::c_ulong) as libc::c_uint,
+    *n,
+  ) as *mut *mut libc::c_char;
+  *v.offset(*n as isize) = s;
+  *n += 1;
+  return v;
+}
+/* Add the pieces of STR separated by SEPS chars, all of them or
+ * (if WORD is not NULL) those starting with WORD */
+unsafe extern "C" fn cand_split(
+  mut v: *mut *mut libc::c_char,
+  mut n: *mut libc::c_int,
+  mut str: *const libc::c_char,
+  mut seps: *const libc::c_char,
+  mut word: *const libc::c_char,
+) -> *mut *mut libc::c_char {
+  while *str != 0 {
+    let mut len: libc::c_ulong = strcspn(str, seps);
+    if len != 0 {
+      let mut s: *mut libc::c_char =
+        crate::libbb::xfuncs_printf::xstrndup(str, len as libc::c_int);
+      if word.is_null() || !crate::libbb::compare_string_array::is_prefixed_with(s, word).is_null()
+      {
+        v = cand_add(v, n, s)
+      } else {
+        free(s as *mut libc::c_void);
+      }
+      str = str.offset(len as isize)
+    } else {
+      str = str.offset(1)
+    }
+  }
+  return v;
+}
+unsafe extern "C" fn cand_files(
+  mut v: *mut *mut libc::c_char,
+  mut n: *mut libc::c_int,
+  mut word: *const libc::c_char,
+  mut only_dirs: libc::c_int,
+) -> *mut *mut libc::c_char {
+  let mut slash: *const libc::c_char = libc::strrchr(word, '/' as i32);
+  let mut dir: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut base: *const libc::c_char = word;
+  let mut d: *mut libc::DIR = std::ptr::null_mut();
+  let mut de: *mut libc::dirent = std::ptr::null_mut();
+  if !slash.is_null() {
+    dir = crate::libbb::xfuncs_printf::xstrndup(
+      word,
+      (slash.offset_from(word) + 1) as libc::c_int,
+    );
+    base = slash.offset(1)
+  } else {
+    dir = crate::libbb::xfuncs_printf::xstrdup(b"\x00" as *const u8 as *const libc::c_char)
+  }
+  d = libc::opendir(if *dir != 0 {
+    dir as *const libc::c_char
+  } else {
+    b".\x00" as *const u8 as *const libc::c_char
+  });
+  if !d.is_null() {
+    loop {
+      let mut name: *const libc::c_char = std::ptr::null();
+      let mut path: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+      de = libc::readdir(d);
+      if de.is_null() {
+        break;
+      }
+      name = (*de).d_name.as_ptr();
+      /* no dot files unless asked for */
+      if *name as libc::c_int == '.' as i32
+        && (*base as libc::c_int != '.' as i32
+          || *name.offset(1) == 0
+          || *name.offset(1) as libc::c_int == '.' as i32 && *name.offset(2) == 0)
+      {
+        continue;
+      }
+      if crate::libbb::compare_string_array::is_prefixed_with(name, base).is_null() {
+        continue;
+      }
+      path = crate::libbb::xfuncs_printf::xasprintf(
+        b"%s%s\x00" as *const u8 as *const libc::c_char,
+        dir,
+        name,
+      );
+      if only_dirs != 0 && crate::libbb::isdirectory::is_directory(path, 1i32) == 0 {
+        free(path as *mut libc::c_void);
+      } else {
+        v = cand_add(v, n, path)
+      }
+    }
+    libc::closedir(d);
+  }
+  free(dir as *mut libc::c_void);
+  return v;
+}
+unsafe extern "C" fn cand_commands(
+  mut v: *mut *mut libc::c_char,
+  mut n: *mut libc::c_int,
+  mut word: *const libc::c_char,
+  mut hooks: *const compgen_hooks,
+) -> *mut *mut libc::c_char {
+  let mut path: *const libc::c_char = std::ptr::null();
+  let mut buf: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut p: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  if !strchr(word, '/' as i32).is_null() {
+    return cand_files(v, n, word, 0);
+  }
+  if (*hooks).getvar.is_some() {
+    path = (*hooks).getvar.expect("non-null function pointer")(
+      b"PATH\x00" as *const u8 as *const libc::c_char,
+    )
+  }
+  if path.is_null() {
+    return v;
+  }
+  buf = crate::libbb::xfuncs_printf::xstrdup(path);
+  p = buf;
+  while !p.is_null() {
+    let mut dir: *mut libc::c_char = p;
+    let mut d: *mut libc::DIR = std::ptr::null_mut();
+    p = strchr(p, ':' as i32);
+    if !p.is_null() {
+      *p = '\u{0}' as i32 as libc::c_char;
+      p = p.offset(1)
+    }
+    d = libc::opendir(if *dir != 0 {
+      dir as *const libc::c_char
+    } else {
+      b".\x00" as *const u8 as *const libc::c_char
+    });
+    if d.is_null() {
+      continue;
+    }
+    loop {
+      let mut de: *mut libc::dirent = libc::readdir(d);
+      let mut full: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+      if de.is_null() {
+        break;
+      }
+      if (*de).d_name[0] as libc::c_int == '.' as i32
+        || crate::libbb::compare_string_array::is_prefixed_with((*de).d_name.as_ptr(), word)
+          .is_null()
+      {
+        continue;
+      }
+      full = crate::libbb::concat_path_file::concat_path_file(dir, (*de).d_name.as_ptr());
+      if crate::libbb::executable::file_is_executable(full) != 0 {
+        v = cand_add(
+          v,
+          n,
+          crate::libbb::xfuncs_printf::xstrdup((*de).d_name.as_ptr()),
+        )
+      }
+      free(full as *mut libc::c_void);
+    }
+    libc::closedir(d);
+  }
+  free(buf as *mut libc::c_void);
+  return v;
+}
+unsafe extern "C" fn cand_users(
+  mut v: *mut *mut libc::c_char,
+  mut n: *mut libc::c_int,
+  mut word: *const libc::c_char,
+) -> *mut *mut libc::c_char {
+  libc::setpwent();
+  loop {
+    let mut pw: *mut libc::passwd = libc::getpwent();
+    if pw.is_null() {
+      break;
+    }
+    if !crate::libbb::compare_string_array::is_prefixed_with((*pw).pw_name, word).is_null() {
+      v = cand_add(v, n, crate::libbb::xfuncs_printf::xstrdup((*pw).pw_name))
+    }
+  }
+  libc::endpwent();
+  return v;
+}
+/* 'str' with embedded ' as '\'' (malloced) */
+unsafe extern "C" fn comp_quote(mut str: *const libc::c_char) -> *mut libc::c_char {
+  let mut r: *mut libc::c_char =
+    crate::libbb::xfuncs_printf::xmalloc(strlen(str).wrapping_mul(4i32 as libc::c_ulong).wrapping_add(3i32 as libc::c_ulong))
+      as *mut libc::c_char;
+  let mut d: *mut libc::c_char = r;
+  *d = '\'' as i32 as libc::c_char;
+  d = d.offset(1);
+  while *str != 0 {
+    if *str as libc::c_int == '\'' as i32 {
+      libc::strcpy(d, b"'\\''\x00" as *const u8 as *const libc::c_char);
+      d = d.offset(4)
+    } else {
+      *d = *str;
+      d = d.offset(1)
+    }
+    str = str.offset(1)
+  }
+  *d = '\'' as i32 as libc::c_char;
+  *d.offset(1) = '\u{0}' as i32 as libc::c_char;
+  return r;
+}
+/* Append STR (shell-quoted if QUOTE) to the malloced string S */
+unsafe extern "C" fn comp_append(
+  mut s: *mut libc::c_char,
+  mut str: *const libc::c_char,
+  mut quote: libc::c_int,
+) -> *mut libc::c_char {
+  let mut q: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut r: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  if quote != 0 {
+    q = comp_quote(str);
+    str = q
+  }
+  r = crate::libbb::xfuncs_printf::xasprintf(
+    b"%s%s\x00" as *const u8 as *const libc::c_char,
+    s,
+    str,
+  );
+  free(q as *mut libc::c_void);
+  free(s as *mut libc::c_void);
+  return r;
+}
+/*
+ * Split the command line up to the cursor into the words of the
+ * command being typed (quotes removed). The last one, possibly empty,
+ * is the word under the cursor.
+ */
+unsafe extern "C" fn comp_split(
+  mut line: *const libc::c_char,
+  mut np: *mut libc::c_int,
+) -> *mut *mut libc::c_char {
+  let mut v: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut buf: *mut libc::c_char =
+    crate::libbb::xfuncs_printf::xmalloc(strlen(line).wrapping_add(1i32 as libc::c_ulong)) as *mut libc::c_char;
+  let mut len: libc::c_int = 0;
+  let mut in_word: libc::c_int = 0;
+  let mut quote: libc::c_int = 0;
+  *np = 0;
+  while *line != 0 {
+    let mut c: libc::c_int = *line as libc::c_int;
+    line = line.offset(1);
+    if quote != 0 {
+      if c == quote {
+        quote = 0;
+        continue;
+      }
+      if c == '\\' as i32 && quote == '\"' as i32 && *line as libc::c_int != 0 {
+        c = *line as libc::c_int;
+        line = line.offset(1)
+      }
+    } else if c == '\'' as i32 || c == '\"' as i32 {
+      quote = c;
+      in_word = 1;
+      continue;
+    } else if c == '\\' as i32 && *line as libc::c_int != 0 {
+      c = *line as libc::c_int;
+      line = line.offset(1)
+    } else if c == ' ' as i32 || c == '\t' as i32 || c == '\n' as i32 {
+      if in_word != 0 {
+        v = cand_add(v, np, crate::libbb::xfuncs_printf::xstrndup(buf, len));
+        len = 0;
+        in_word = 0
+      }
+      continue;
+    } else if !strchr(b";&|()\x00" as *const u8 as *const libc::c_char, c).is_null() {
+      /* a new command starts */
+      while *np != 0 {
+        *np -= 1;
+        free(*v.offset(*np as isize) as *mut libc::c_void);
+      }
+      len = 0;
+      in_word = 0;
+      continue;
+    }
+    *buf.offset(len as isize) = c as libc::c_char;
+    len += 1;
+    in_word = 1
+  }
+  v = cand_add(v, np, crate::libbb::xfuncs_printf::xstrndup(buf, len));
+  free(buf as *mut libc::c_void);
+  return v;
+}
+unsafe extern "C" fn comp_freelist(mut v: *mut *mut libc::c_char) {
+  let mut p: *mut *mut libc::c_char = v;
+  while !(*p).is_null() {
+    free(*p as *mut libc::c_void);
+    p = p.offset(1)
+  }
+  free(v as *mut libc::c_void);
+}
+/* Candidates for WORDS[CWORD] of LINE according to CS */
+unsafe extern "C" fn compspec_generate(
+  mut cs: *const compspec,
+  mut hooks: *const compgen_hooks,
+  mut words: *mut *mut libc::c_char,
+  mut cword: libc::c_int,
+  mut line: *const libc::c_char,
+  mut np: *mut libc::c_int,
+) -> *mut *mut libc::c_char {
+  let mut v: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut word: *const libc::c_char = *words.offset(cword as isize);
+  let mut prev: *const libc::c_char = if cword > 0 {
+    *words.offset((cword - 1i32) as isize) as *const libc::c_char
+  } else {
+    b"\x00" as *const u8 as *const libc::c_char
+  };
+  *np = 0;
+  if (*cs).actions & CA_FILE != 0 {
+    v = cand_files(v, np, word, 0)
+  }
+  if (*cs).actions & CA_DIRECTORY != 0 {
+    v = cand_files(v, np, word, 1i32)
+  }
+  if (*cs).actions & CA_COMMAND != 0 {
+    v = cand_commands(v, np, word, hooks)
+  }
+  if (*cs).actions & CA_USER != 0 {
+    v = cand_users(v, np, word)
+  }
+  if !(*cs).wordlist.is_null() {
+    v = cand_split(
+      v,
+      np,
+      (*cs).wordlist,
+      b" \t\n\x00" as *const u8 as *const libc::c_char,
+      word,
+    )
+  }
+  if (!(*cs).func.is_null() || !(*cs).command.is_null()) && (*hooks).run.is_some() {
+    let mut i: libc::c_int = 0;
+    let mut out: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut script: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+      b"COMP_POINT=%u COMP_CWORD=%d COMP_LINE=\x00" as *const u8 as *const libc::c_char,
+      strlen(line) as libc::c_uint,
+      cword,
+    );
+    script = comp_append(script, line, 1i32);
+    if !(*cs).func.is_null() {
+      /* COMP_WORDS=(w0 w1 ...) or COMP_WORDS='w0 w1 ...' */
+      script = comp_append(
+        script,
+        if (*hooks).arrays != 0 {
+          b"; COMPREPLY=(); COMP_WORDS=(\x00" as *const u8 as *const libc::c_char
+        } else {
+          b"; COMPREPLY=; COMP_WORDS=\'\x00" as *const u8 as *const libc::c_char
+        },
+        0,
+      );
+      while !(*words.offset(i as isize)).is_null() {
+        if i != 0 {
+          script = comp_append(script, b" \x00" as *const u8 as *const libc::c_char, 0)
+        }
+        if (*hooks).arrays != 0 {
+          script = comp_append(script, *words.offset(i as isize), 1i32)
+        } else {
+          let mut q: *mut libc::c_char = comp_quote(*words.offset(i as isize));
+          /* strip the outer quotes, we are inside '...' already */
+          *q.offset(strlen(q).wrapping_sub(1i32 as libc::c_ulong) as isize) =
+            '\u{0}' as i32 as libc::c_char;
+          script = comp_append(script, q.offset(1), 0);
+          free(q as *mut libc::c_void);
+        }
+        i += 1
+      }
+      script = comp_append(
+        script,
+        if (*hooks).arrays != 0 {
+          b"); \x00" as *const u8 as *const libc::c_char
+        } else {
+          b"\'; \x00" as *const u8 as *const libc::c_char
+        },
+        0,
+      );
+      script = comp_append(script, (*cs).func, 0);
+    } else {
+      script = comp_append(
+        script,
+        b"; export COMP_LINE COMP_POINT; \x00" as *const u8 as *const libc::c_char,
+        0,
+      );
+      script = comp_append(script, (*cs).command, 0);
+    }
+    script = comp_append(script, b" \x00" as *const u8 as *const libc::c_char, 0);
+    script = comp_append(script, *words.offset(0), 1i32);
+    script = comp_append(script, b" \x00" as *const u8 as *const libc::c_char, 0);
+    script = comp_append(script, word, 1i32);
+    script = comp_append(script, b" \x00" as *const u8 as *const libc::c_char, 0);
+    script = comp_append(script, prev, 1i32);
+    if !(*cs).func.is_null() {
+      script = comp_append(
+        script,
+        if (*hooks).arrays != 0 {
+          b"; printf \'%s\\n\' \"${COMPREPLY[@]}\"\x00" as *const u8 as *const libc::c_char
+        } else {
+          b"; printf \'%s\' \"$COMPREPLY\"\x00" as *const u8 as *const libc::c_char
+        },
+        0,
+      )
+    }
+    out = (*hooks).run.expect("non-null function pointer")(script);
+    free(script as *mut libc::c_void);
+    if !out.is_null() {
+      /* One per line, or (no arrays) whitespace-separated.
+       * COMPREPLY and -C output are not filtered by WORD */
+      v = cand_split(
+        v,
+        np,
+        out,
+        if !(*cs).func.is_null() && (*hooks).arrays == 0 {
+          b" \t\n\x00" as *const u8 as *const libc::c_char
+        } else {
+          b"\n\x00" as *const u8 as *const libc::c_char
+        },
+        std::ptr::null(),
+      );
+      free(out as *mut libc::c_void);
+    }
+  }
+  if v.is_null() {
+    v = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<*mut libc::c_char>() as size_t)
+      as *mut *mut libc::c_char
+  }
+  return v;
+}
+unsafe extern "C" fn compspec_find(mut cmd: *const libc::c_char) -> *mut compspec {
+  let mut cs: *mut compspec = compspecs;
+  while !cs.is_null() {
+    if strcmp((*cs).cmd, cmd) == 0 {
+      break;
+    }
+    cs = (*cs).next
+  }
+  return cs;
+}
+unsafe extern "C" fn compspec_free(mut cs: *mut compspec) {
+  free((*cs).cmd as *mut libc::c_void);
+  free((*cs).wordlist as *mut libc::c_void);
+  free((*cs).func as *mut libc::c_void);
+  free((*cs).command as *mut libc::c_void);
+  free(cs as *mut libc::c_void);
+}
+unsafe extern "C" fn compspec_remove(mut cmd: *const libc::c_char) -> libc::c_int {
+  let mut csp: *mut *mut compspec = &mut compspecs;
+  while !(*csp).is_null() {
+    let mut cs: *mut compspec = *csp;
+    if cmd.is_null() || strcmp((*cs).cmd, cmd) == 0 {
+      *csp = (*cs).next;
+      compspec_free(cs);
+      if !cmd.is_null() {
+        return 0;
+      }
+    } else {
+      csp = &mut (*cs).next
+    }
+  }
+  return if cmd.is_null() { 0 } else { 1i32 };
+}
+unsafe extern "C" fn compspec_print(mut cs: *const compspec) {
+  let mut i: libc::c_int = 0;
+  let mut a: *const libc::c_char = comp_action_names.as_ptr();
+  printf(b"complete\x00" as *const u8 as *const libc::c_char);
+  while *a != 0 {
+    if (*cs).actions & (1i32 << i) as libc::c_uint != 0 {
+      printf(b" -A %s\x00" as *const u8 as *const libc::c_char, a);
+    }
+    a = a.offset(strlen(a).wrapping_add(1i32 as libc::c_ulong) as isize);
+    i += 1
+  }
+  if !(*cs).wordlist.is_null() {
+    let mut q: *mut libc::c_char = comp_quote((*cs).wordlist);
+    printf(b" -W %s\x00" as *const u8 as *const libc::c_char, q);
+    free(q as *mut libc::c_void);
+  }
+  if !(*cs).func.is_null() {
+    printf(b" -F %s\x00" as *const u8 as *const libc::c_char, (*cs).func);
+  }
+  if !(*cs).command.is_null() {
+    let mut q_0: *mut libc::c_char = comp_quote((*cs).command);
+    printf(b" -C %s\x00" as *const u8 as *const libc::c_char, q_0);
+    free(q_0 as *mut libc::c_void);
+  }
+  printf(b" %s\n\x00" as *const u8 as *const libc::c_char, (*cs).cmd);
+}
+pub const COMP_OPT_p: libc::c_uint = 1;
+pub const COMP_OPT_r: libc::c_uint = 2;
+/* Parse the options common to complete and compgen into CS.
+ * Returns the index of the first non-option argument, or -1 */
+unsafe extern "C" fn compspec_getopt(
+  mut argv: *mut *mut libc::c_char,
+  mut cs: *mut compspec,
+  mut optstr: *const libc::c_char,
+  mut opts: *mut libc::c_uint,
+) -> libc::c_int {
+  let mut argc: libc::c_int = crate::libbb::appletlib::string_array_len(argv) as libc::c_int;
+  /* In case getopt() was already called:
+   * reset libc getopt() internal state.
+   */
+  optind = 0;
+  loop {
+    let mut c: libc::c_int = getopt(argc, argv, optstr);
+    let mut i: libc::c_int = 0;
+    if c == -1i32 {
+      break;
+    }
+    match c {
+      102 => (*cs).actions |= CA_FILE,
+      100 => (*cs).actions |= CA_DIRECTORY,
+      99 => (*cs).actions |= CA_COMMAND,
+      117 => (*cs).actions |= CA_USER,
+      65 => {
+        /* -A ACTION */
+        i = crate::libbb::compare_string_array::index_in_strings(
+          comp_action_names.as_ptr(),
+          optarg,
+        );
+        if i < 0 {
+          crate::libbb::verror_msg::bb_error_msg(
+            b"%s: invalid action name\x00" as *const u8 as *const libc::c_char,
+            optarg,
+          );
+          return -1i32;
+        }
+        (*cs).actions |= (1i32 << i) as libc::c_uint
+      }
+      87 => {
+        free((*cs).wordlist as *mut libc::c_void);
+        (*cs).wordlist = crate::libbb::xfuncs_printf::xstrdup(optarg)
+      }
+      70 => {
+        free((*cs).func as *mut libc::c_void);
+        (*cs).func = crate::libbb::xfuncs_printf::xstrdup(optarg)
+      }
+      67 => {
+        free((*cs).command as *mut libc::c_void);
+        (*cs).command = crate::libbb::xfuncs_printf::xstrdup(optarg)
+      }
+      112 => *opts |= COMP_OPT_p,
+      114 => *opts |= COMP_OPT_r,
+      _ => return -1i32,
+    }
+  }
+  return optind;
+}
+pub unsafe extern "C" fn shell_builtin_complete(
+  mut argv: *mut *mut libc::c_char,
+  mut _hooks: *const compgen_hooks,
+) -> libc::c_int {
+  let mut cs: compspec = std::mem::zeroed();
+  let mut opts: libc::c_uint = 0 as libc::c_uint;
+  let mut rc: libc::c_int = 0;
+  let mut i: libc::c_int = compspec_getopt(
+    argv,
+    &mut cs,
+    b"fdcuA:W:F:C:pr\x00" as *const u8 as *const libc::c_char,
+    &mut opts,
+  );
+  if i < 0 {
+    free(cs.wordlist as *mut libc::c_void);
+    free(cs.func as *mut libc::c_void);
+    free(cs.command as *mut libc::c_void);
+    return 2i32;
+  }
+  argv = argv.offset(i as isize);
+  if opts & COMP_OPT_r != 0 {
+    if (*argv).is_null() {
+      compspec_remove(std::ptr::null());
+    }
+    while !(*argv).is_null() {
+      rc |= compspec_remove(*argv);
+      argv = argv.offset(1)
+    }
+  } else if (*argv).is_null()
+    || opts & COMP_OPT_p != 0
+    || cs.actions == 0 && cs.wordlist.is_null() && cs.func.is_null() && cs.command.is_null()
+  {
+    /* complete [-p] [NAME...]: show specs */
+    if (*argv).is_null() {
+      let mut p: *mut compspec = compspecs;
+      while !p.is_null() {
+        compspec_print(p);
+        p = (*p).next
+      }
+    }
+    while !(*argv).is_null() {
+      let mut p_0: *mut compspec = compspec_find(*argv);
+      if p_0.is_null() {
+        crate::libbb::verror_msg::bb_error_msg(
+          b"%s: no completion specification\x00" as *const u8 as *const libc::c_char,
+          *argv,
+        );
+        rc = 1i32
+      } else {
+        compspec_print(p_0);
+      }
+      argv = argv.offset(1)
+    }
+  } else {
+    while !(*argv).is_null() {
+      let mut p_1: *mut compspec = std::ptr::null_mut();
+      compspec_remove(*argv);
+      p_1 = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<compspec>() as size_t)
+        as *mut compspec;
+      (*p_1).cmd = crate::libbb::xfuncs_printf::xstrdup(*argv);
+      (*p_1).actions = cs.actions;
+      if !cs.wordlist.is_null() {
+        (*p_1).wordlist = crate::libbb::xfuncs_printf::xstrdup(cs.wordlist)
+      }
+      if !cs.func.is_null() {
+        (*p_1).func = crate::libbb::xfuncs_printf::xstrdup(cs.func)
+      }
+      if !cs.command.is_null() {
+        (*p_1).command = crate::libbb::xfuncs_printf::xstrdup(cs.command)
+      }
+      (*p_1).next = compspecs;
+      compspecs = p_1;
+      argv = argv.offset(1)
+    }
+  }
+  free(cs.wordlist as *mut libc::c_void);
+  free(cs.func as *mut libc::c_void);
+  free(cs.command as *mut libc::c_void);
+  return rc;
+}
+pub unsafe extern "C" fn shell_builtin_compgen(
+  mut argv: *mut *mut libc::c_char,
+  mut hooks: *const compgen_hooks,
+) -> libc::c_int {
+  let mut cs: compspec = std::mem::zeroed();
+  let mut opts: libc::c_uint = 0 as libc::c_uint;
+  let mut words: [*mut libc::c_char; 2] = [0 as *mut libc::c_char; 2];
+  let mut v: *mut *mut libc::c_char = std::ptr::null_mut();
+  let mut n: libc::c_int = 0;
+  let mut i: libc::c_int = compspec_getopt(
+    argv,
+    &mut cs,
+    b"fdcuA:W:F:C:\x00" as *const u8 as *const libc::c_char,
+    &mut opts,
+  );
+  if i >= 0 {
+    words[0] = if !(*argv.offset(i as isize)).is_null() {
+      *argv.offset(i as isize)
+    } else {
+      b"\x00" as *const u8 as *const libc::c_char as *mut libc::c_char
+    };
+    v = compspec_generate(&mut cs, hooks, words.as_mut_ptr(), 0, words[0], &mut n);
+    i = 0;
+    while i < n {
+      puts(*v.offset(i as isize));
+      i += 1
+    }
+    comp_freelist(v);
+  }
+  free(cs.wordlist as *mut libc::c_void);
+  free(cs.func as *mut libc::c_void);
+  free(cs.command as *mut libc::c_void);
+  if i < 0 {
+    return 2i32;
+  }
+  return (n == 0) as libc::c_int;
+}
+/*
+ * Backend of line_input_t::complete_hook: candidates for the word under
+ * the cursor if there is a complete spec for the command being typed.
+ */
+pub unsafe extern "C" fn shell_complete_line(
+  mut line: *const libc::c_char,
+  mut hooks: *const compgen_hooks,
+) -> *mut *mut libc::c_char {
+  let mut n: libc::c_int = 0;
+  let mut words: *mut *mut libc::c_char = comp_split(line, &mut n);
+  let mut cs: *mut compspec = std::ptr::null_mut();
+  let mut v: *mut *mut libc::c_char = std::ptr::null_mut();
+  /* the command name itself is completed as usual */
+  if n > 1i32 {
+    cs = compspec_find(*words.offset(0));
+    if cs.is_null() {
+      cs = compspec_find(crate::libbb::get_last_path_component::bb_basename(
+        *words.offset(0),
+      ))
+    }
+  }
+  if !cs.is_null() {
+    let mut cnt: libc::c_int = 0;
+    v = compspec_generate(cs, hooks, words, n - 1i32, line, &mut cnt)
+  }
+  comp_freelist(words);
+  return v;
+}
+/*
+ * bind [-m KEYMAP] [-lpP] [-f FILE] [-r KEYSEQ] [KEYSEQ:FUNCTION | KEYSEQ:"MACRO" | "set VAR VALUE"]...
+ * The arguments are inputrc lines.
+ */
+pub unsafe extern "C" fn shell_builtin_bind(
+  mut argv: *mut *mut libc::c_char,
+  mut st: *const crate::librb::line_input_t,
+) -> libc::c_int {
+  let mut argc: libc::c_int = crate::libbb::appletlib::string_array_len(argv) as libc::c_int;
+  let mut keymap: *const libc::c_char = std::ptr::null();
+  let mut file: *const libc::c_char = std::ptr::null();
+  let mut unbind: *const libc::c_char = std::ptr::null();
+  let mut opts: libc::c_uint = 0 as libc::c_uint;
+  let mut rc: libc::c_int = 0;
+  /* In case getopt() was already called:
+   * reset libc getopt() internal state.
+   */
+  optind = 0;
+  loop {
+    let mut c: libc::c_int = getopt(
+      argc,
+      argv,
+      b"m:lpPf:r:\x00" as *const u8 as *const libc::c_char,
+    );
+    if c == -1i32 {
+      break;
+    }
+    match c {
+      109 => keymap = optarg,
+      108 => opts |= 1i32 as libc::c_uint,
+      112 => opts |= 2i32 as libc::c_uint,
+      80 => opts |= 4i32 as libc::c_uint,
+      102 => file = optarg,
+      114 => unbind = optarg,
+      _ => return 2i32,
+    }
+  }
+  if crate::libbb::lineedit::lineedit_set_keymap(st, keymap) != 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"%s: invalid keymap name\x00" as *const u8 as *const libc::c_char,
+      keymap,
+    );
+    return 1i32;
+  }
+  if opts & 1i32 as libc::c_uint != 0 {
+    crate::libbb::lineedit::lineedit_list_functions();
+  }
+  if !file.is_null() && crate::libbb::lineedit::lineedit_read_inputrc(file) != 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"can\'t read \'%s\'\x00" as *const u8 as *const libc::c_char,
+      file,
+    );
+    rc = 1i32
+  }
+  if !unbind.is_null() && crate::libbb::lineedit::lineedit_unbind(unbind) != 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"bad key sequence: %s\x00" as *const u8 as *const libc::c_char,
+      unbind,
+    );
+    rc = 1i32
+  }
+  argv = argv.offset(optind as isize);
+  while !(*argv).is_null() {
+    if crate::libbb::lineedit::lineedit_parse_and_bind(*argv) != 0 {
+      rc = 1i32
+    }
+    argv = argv.offset(1)
+  }
+  if opts & 2i32 as libc::c_uint != 0 {
+    crate::libbb::lineedit::lineedit_print_bindings(1i32);
+  }
+  if opts & 4i32 as libc::c_uint != 0 {
+    crate::libbb::lineedit::lineedit_print_bindings(0);
+  }
+  return rc;
+}
+/*
+ * history [-c] [-d OFFSET] [-a | -r | -w] [N]
+ */
+pub unsafe extern "C" fn shell_builtin_history(
+  mut argv: *mut *mut libc::c_char,
+  mut st: *mut crate::librb::line_input_t,
+) -> libc::c_int {
+  let mut argc: libc::c_int = crate::libbb::appletlib::string_array_len(argv) as libc::c_int;
+  let mut del: *const libc::c_char = std::ptr::null();
+  let mut opts: libc::c_uint = 0 as libc::c_uint;
+  /* In case getopt() was already called:
+   * reset libc getopt() internal state.
+   */
+  optind = 0;
+  loop {
+    let mut c: libc::c_int = getopt(argc, argv, b"cd:arw\x00" as *const u8 as *const libc::c_char);
+    if c == -1i32 {
+      break;
+    }
+    match c {
+      99 => opts |= 1i32 as libc::c_uint,
+      100 => del = optarg,
+      97 => opts |= 2i32 as libc::c_uint,
+      114 => opts |= 4i32 as libc::c_uint,
+      119 => opts |= 8i32 as libc::c_uint,
+      _ => return 2i32,
+    }
+  }
+  if st.is_null() {
+    return 0;
+  }
+  if opts & 1i32 as libc::c_uint != 0 {
+    crate::libbb::lineedit::clear_history(st);
+  }
+  if !del.is_null() {
+    let mut n: libc::c_int = crate::libbb::bb_strtonum::bb_strtoi(del, std::ptr::null_mut(), 10i32);
+    /* negative offsets count back from the end */
+    if n < 0 {
+      n += (*st).cnt_history
+    }
+    if *bb_errno != 0 || crate::libbb::lineedit::delete_history(st, n) != 0 {
+      crate::libbb::verror_msg::bb_error_msg(
+        b"%s: history position out of range\x00" as *const u8 as *const libc::c_char,
+        del,
+      );
+      return 1i32;
+    }
+  }
+  if opts & 2i32 as libc::c_uint != 0 {
+    crate::libbb::lineedit::save_history(st);
+  }
+  if opts & 4i32 as libc::c_uint != 0 {
+    crate::libbb::lineedit::read_history(st);
+  }
+  if opts & 8i32 as libc::c_uint != 0 {
+    crate::libbb::lineedit::write_history(st);
+  }
+  if opts != 0 || !del.is_null() {
+    return 0;
+  }
+  if !(*argv.offset(optind as isize)).is_null() {
+    let mut arg: *const libc::c_char = *argv.offset(optind as isize);
+    let mut n_0: libc::c_uint =
+      crate::libbb::bb_strtonum::bb_strtou(arg, std::ptr::null_mut(), 10i32);
+    if *bb_errno != 0 {
+      crate::libbb::verror_msg::bb_error_msg(
+        b"%s: numeric argument required\x00" as *const u8 as *const libc::c_char,
+        arg,
+      );
+      return 1i32;
+    }
+    if n_0 < (*st).cnt_history as libc::c_uint {
+      crate::libbb::lineedit::show_history_from(st, (*st).cnt_history - n_0 as libc::c_int);
+      return 0;
+    }
+  }
+  crate::libbb::lineedit::show_history(st);
+  return 0;
+}
@@ -1,9 +1,41 @@
 use crate::libbb::xfuncs_printf::xmalloc;
 use libc;
 use libc::free;
+use libc::localtime_r;
+use libc::printf;
 use libc::sleep;
+use libc::strcasecmp;
+use libc::strchr;
+use libc::time;
+use libc::time_t;
+use libc::tm;
 extern "C" {
 
+  #[no_mangle]
+  static mut option_mask32: u32;
+
+  #[no_mangle]
+  fn strptime(
+    __s: *const libc::c_char,
+    __fmt: *const libc::c_char,
+    __tp: *mut tm,
+  ) -> *mut libc::c_char;
+
+  #[no_mangle]
+  fn mktime(__tp: *mut tm) -> time_t;
+
+  #[no_mangle]
+  fn strncmp(_: *const libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> libc::c_int;
+
+  #[no_mangle]
+  fn regexec(
+    __preg: *const regex_t,
+    __string: *const libc::c_char,
+    __nmatch: size_t,
+    __pmatch: *mut libc::c_void,
+    __eflags: libc::c_int,
+  ) -> libc::c_int;
+
   #[no_mangle]
   static mut stdout: *mut FILE;
 
@@ -48,8 +80,59 @@ pub struct globals {
   pub SMrup: [sembuf; 1],
   pub SMrdn: [sembuf; 2],
   pub shbuf: *mut shbuf_ds,
+  /* filters */
+  pub max_prio: libc::c_int,
+  pub tag: *const libc::c_char,
+  pub re: regex_t,
+  pub since: time_t,
+  pub last_n: libc::c_int,
+}
+
+pub type regex_t = crate::librb::re_pattern_buffer;
+
+pub type C2RustUnnamed_1 = libc::c_uint;
+pub const OPT_since: C2RustUnnamed_1 = 128;
+pub const OPT_json: C2RustUnnamed_1 = 64;
+pub const OPT_last_n: C2RustUnnamed_1 = 32;
+pub const OPT_regex: C2RustUnnamed_1 = 16;
+pub const OPT_tag: C2RustUnnamed_1 = 8;
+pub const OPT_prio: C2RustUnnamed_1 = 4;
+pub const OPT_F: C2RustUnnamed_1 = 2;
+pub const OPT_f: C2RustUnnamed_1 = 1;
+
+/* A message as stored by syslogd:
+ * "Jan 18 00:11:22 HOST FACILITY.PRIO TAG[PID]: TEXT\n"
+ * or, with syslogd -S, "Jan 18 00:11:22 TEXT\n".
+ * Fields point into the message and are not NUL-terminated. */
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct log_msg {
+  pub stamp: time_t,
+  pub host: *const libc::c_char,
+  pub host_len: libc::c_int,
+  pub facility: *const libc::c_char,
+  pub facility_len: libc::c_int,
+  /* -1 if unknown */
+  pub prio: libc::c_int,
+  pub tag: *const libc::c_char,
+  pub tag_len: libc::c_int,
+  pub pid: *const libc::c_char,
+  pub pid_len: libc::c_int,
+  pub text: *const libc::c_char,
+  pub text_len: libc::c_int,
 }
 
+static prio_names: [&[u8]; 8] = [
+  b"emerg\x00",
+  b"alert\x00",
+  b"crit\x00",
+  b"err\x00",
+  b"warning\x00",
+  b"notice\x00",
+  b"info\x00",
+  b"debug\x00",
+];
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct shbuf_ds {
@@ -115,6 +198,303 @@ unsafe extern "C" fn interrupted(mut sig: libc::c_int) {
   /* shmdt(shbuf); - on Linux, shmdt is not mandatory on exit */
   crate::libbb::signals::kill_myself_with_sig(sig); /* ipc semaphore id */
 }
+unsafe fn prio_by_name(mut name: *const libc::c_char, mut len: libc::c_int) -> libc::c_int {
+  let mut i: libc::c_int = 0;
+  while i < 8i32 {
+    let mut n: *const libc::c_char = prio_names[i as usize].as_ptr() as *const libc::c_char;
+    if strlen(n) == len as size_t && strncmp(n, name, len as libc::c_ulong) == 0 {
+      return i;
+    }
+    i += 1
+  }
+  return -1i32;
+}
+/* -p err, -p 3, also the aliases syslog.conf accepts */
+unsafe fn parse_prio(mut arg: *const libc::c_char) -> libc::c_int {
+  let mut prio: libc::c_int = 0;
+  if (*arg as libc::c_int - '0' as i32) as libc::c_uchar as libc::c_int <= 9i32 {
+    return crate::libbb::xatonum::xatou_range(arg, 0 as libc::c_uint, 7i32 as libc::c_uint)
+      as libc::c_int;
+  }
+  prio = prio_by_name(arg, strlen(arg) as libc::c_int);
+  if prio >= 0 {
+    return prio;
+  }
+  if strcasecmp(arg, b"panic\x00" as *const u8 as *const libc::c_char) == 0 {
+    return 0;
+  }
+  if strcasecmp(arg, b"error\x00" as *const u8 as *const libc::c_char) == 0 {
+    return 3i32;
+  }
+  if strcasecmp(arg, b"warn\x00" as *const u8 as *const libc::c_char) == 0 {
+    return 4i32;
+  }
+  crate::libbb::verror_msg::bb_error_msg_and_die(
+    b"unknown %s name: %s\x00" as *const u8 as *const libc::c_char,
+    b"priority\x00" as *const u8 as *const libc::c_char,
+    arg,
+  );
+}
+/* --since: "-N[smhd]" (ago), or anything date -d takes ("hh:mm", "YYYY-MM-DD hh:mm"...) */
+unsafe fn parse_since(mut arg: *const libc::c_char) -> time_t {
+  let mut now: time_t = time(0 as *mut time_t);
+  let mut tm_time: tm = std::mem::zeroed();
+  if *arg as libc::c_int == '-' as i32 {
+    let mut end: *mut libc::c_char = std::ptr::null_mut();
+    let mut n: libc::c_ulong = libc::strtoul(arg.offset(1), &mut end, 10i32);
+    match *end as u8 {
+      0 | b's' => {}
+      b'm' => n = n.wrapping_mul(60),
+      b'h' => n = n.wrapping_mul(60 * 60),
+      b'd' => n = n.wrapping_mul(24 * 60 * 60),
+      _ => crate::libbb::appletlib::bb_show_usage(),
+    }
+    return now - n as time_t;
+  }
+  localtime_r(&mut now, &mut tm_time);
+  crate::libbb::time::parse_datestr(arg, &mut tm_time);
+  tm_time.tm_isdst = -1i32;
+  return crate::libbb::time::validate_tm_time(arg, &mut tm_time);
+}
+unsafe fn parse_msg(mut line: *const libc::c_char, mut m: *mut log_msg) {
+  let mut tm_time: tm = std::mem::zeroed();
+  let mut now: time_t = time(0 as *mut time_t);
+  let mut p: *const libc::c_char = std::ptr::null();
+  let mut q: *const libc::c_char = std::ptr::null();
+  let mut end: *const libc::c_char = line.offset(strlen(line) as isize);
+  std::ptr::write_bytes(m, 0, 1);
+  (*m).prio = -1i32;
+  if end > line && *end.offset(-1) as libc::c_int == '\n' as i32 {
+    end = end.offset(-1)
+  }
+  (*m).text = line;
+  (*m).text_len = end.offset_from(line) as libc::c_int;
+  /* "Jan 18 00:11:22 " has no year: assume the most recent one */
+  localtime_r(&mut now, &mut tm_time);
+  p = strptime(
+    line,
+    b"%b %d %H:%M:%S \x00" as *const u8 as *const libc::c_char,
+    &mut tm_time,
+  );
+  if p.is_null() {
+    return;
+  }
+  tm_time.tm_isdst = -1i32;
+  (*m).stamp = mktime(&mut tm_time);
+  if (*m).stamp > now + 24 * 60 * 60 {
+    tm_time.tm_year -= 1;
+    tm_time.tm_isdst = -1i32;
+    (*m).stamp = mktime(&mut tm_time)
+  }
+  (*m).text = p;
+  (*m).text_len = end.offset_from(p) as libc::c_int;
+  /* "HOST FACILITY.PRIO " (absent with syslogd -S) */
+  q = strchr(p, ' ' as i32);
+  if q.is_null() || q >= end {
+    return;
+  }
+  let mut fp: *const libc::c_char = q.offset(1);
+  let mut fq: *const libc::c_char = strchr(fp, ' ' as i32);
+  let mut dot: *const libc::c_char = strchr(fp, '.' as i32);
+  if fq.is_null() || fq >= end || dot.is_null() || dot > fq {
+    return;
+  }
+  (*m).prio = prio_by_name(dot.offset(1), fq.offset_from(dot) as libc::c_int - 1i32);
+  if (*m).prio < 0 {
+    return;
+  }
+  (*m).host = p;
+  (*m).host_len = q.offset_from(p) as libc::c_int;
+  (*m).facility = fp;
+  (*m).facility_len = dot.offset_from(fp) as libc::c_int;
+  p = fq.offset(1);
+  (*m).text = p;
+  (*m).text_len = end.offset_from(p) as libc::c_int;
+  /* "TAG[PID]: " */
+  q = p;
+  while q < end
+    && *q as libc::c_int != ':' as i32
+    && *q as libc::c_int != '[' as i32
+    && *q as libc::c_int != ' ' as i32
+  {
+    q = q.offset(1)
+  }
+  if q == p || q >= end {
+    return;
+  }
+  let mut tag_end: *const libc::c_char = q;
+  let mut pid: *const libc::c_char = std::ptr::null();
+  if *q as libc::c_int == '[' as i32 {
+    pid = q.offset(1);
+    q = strchr(pid, ']' as i32);
+    if q.is_null() || q >= end {
+      return;
+    }
+    q = q.offset(1)
+  }
+  if *q as libc::c_int != ':' as i32 {
+    return;
+  }
+  (*m).tag = p;
+  (*m).tag_len = tag_end.offset_from(p) as libc::c_int;
+  if !pid.is_null() {
+    (*m).pid = pid;
+    (*m).pid_len = q.offset_from(pid) as libc::c_int - 1i32
+  }
+  q = q.offset(1);
+  if q < end && *q as libc::c_int == ' ' as i32 {
+    q = q.offset(1)
+  }
+  (*m).text = q;
+  (*m).text_len = end.offset_from(q) as libc::c_int;
+}
+unsafe fn msg_matches(mut line: *mut libc::c_char, mut m: *const log_msg) -> bool {
+  let mut G: *mut globals = bb_common_bufsiz1.as_mut_ptr() as *mut globals;
+  if option_mask32 & OPT_prio as libc::c_uint != 0 && (*m).prio > (*G).max_prio {
+    return false;
+  }
+  if option_mask32 & OPT_tag as libc::c_uint != 0
+    && (strlen((*G).tag) != (*m).tag_len as size_t
+      || strncmp((*G).tag, (*m).tag, (*m).tag_len as libc::c_ulong) != 0)
+  {
+    return false;
+  }
+  if option_mask32 & OPT_since as libc::c_uint != 0 && (*m).stamp < (*G).since {
+    return false;
+  }
+  if option_mask32 & OPT_regex as libc::c_uint != 0 {
+    let mut r: libc::c_int = 0;
+    let mut c: libc::c_char = *(*m).text.offset((*m).text_len as isize);
+    /* match TEXT without the trailing newline */
+    *line.offset((*m).text.offset_from(line) + (*m).text_len as isize) =
+      '\u{0}' as i32 as libc::c_char;
+    r = regexec(
+      &mut (*G).re,
+      (*m).text,
+      0 as size_t,
+      std::ptr::null_mut(),
+      0,
+    );
+    *line.offset((*m).text.offset_from(line) + (*m).text_len as isize) = c;
+    if r != 0 {
+      return false;
+    }
+  }
+  return true;
+}
+unsafe fn put_json_str(
+  mut name: *const libc::c_char,
+  mut s: *const libc::c_char,
+  mut len: libc::c_int,
+) {
+  printf(b",\"%s\":\"\x00" as *const u8 as *const libc::c_char, name);
+  while len > 0 {
+    let mut c: libc::c_uchar = *s as libc::c_uchar;
+    if c as libc::c_int == '"' as i32 || c as libc::c_int == '\\' as i32 {
+      printf(
+        b"\\%c\x00" as *const u8 as *const libc::c_char,
+        c as libc::c_int,
+      );
+    } else if (c as libc::c_int) < 0x20i32 {
+      printf(
+        b"\\u%04x\x00" as *const u8 as *const libc::c_char,
+        c as libc::c_int,
+      );
+    } else {
+      libc::putchar_unlocked(c as libc::c_int);
+    }
+    s = s.offset(1);
+    len -= 1
+  }
+  libc::putchar_unlocked('"' as i32);
+}
+/* One JSON object per line */
+unsafe fn print_json(mut m: *const log_msg) {
+  printf(
+    b"{\"time\":%ld\x00" as *const u8 as *const libc::c_char,
+    (*m).stamp as libc::c_long,
+  );
+  if !(*m).host.is_null() {
+    put_json_str(
+      b"host\x00" as *const u8 as *const libc::c_char,
+      (*m).host,
+      (*m).host_len,
+    );
+    put_json_str(
+      b"facility\x00" as *const u8 as *const libc::c_char,
+      (*m).facility,
+      (*m).facility_len,
+    );
+    printf(
+      b",\"priority\":\"%s\"\x00" as *const u8 as *const libc::c_char,
+      prio_names[(*m).prio as usize].as_ptr() as *const libc::c_char,
+    );
+  }
+  if !(*m).tag.is_null() {
+    put_json_str(
+      b"tag\x00" as *const u8 as *const libc::c_char,
+      (*m).tag,
+      (*m).tag_len,
+    );
+  }
+  if !(*m).pid.is_null() {
+    put_json_str(
+      b"pid\x00" as *const u8 as *const libc::c_char,
+      (*m).pid,
+      (*m).pid_len,
+    );
+  }
+  put_json_str(
+    b"message\x00" as *const u8 as *const libc::c_char,
+    (*m).text,
+    (*m).text_len,
+  );
+  printf(b"}\n\x00" as *const u8 as *const libc::c_char);
+}
+/* Print the NUL-separated messages in copy[0..len_total] which pass the
+ * filters. -n N limits the first (dump) pass to the last N matches. */
+unsafe fn show_messages(mut copy: *mut libc::c_char, mut len_total: libc::c_int, mut first: bool) {
+  let mut m: log_msg = std::mem::zeroed();
+  let mut skip: libc::c_int = 0;
+  let mut i: libc::c_int = 0;
+  if first && option_mask32 & OPT_last_n as libc::c_uint != 0 {
+    let mut count: libc::c_int = 0;
+    while i < len_total {
+      if *copy.offset(i as isize) != 0 {
+        parse_msg(copy.offset(i as isize), &mut m);
+        if msg_matches(copy.offset(i as isize), &m) {
+          count += 1
+        }
+      }
+      i = (i as libc::c_ulong)
+        .wrapping_add(strlen(copy.offset(i as isize)).wrapping_add(1i32 as libc::c_ulong))
+        as libc::c_int
+    }
+    skip = count - (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).last_n;
+    i = 0
+  }
+  while i < len_total {
+    let mut line: *mut libc::c_char = copy.offset(i as isize);
+    i = (i as libc::c_ulong).wrapping_add(strlen(line).wrapping_add(1i32 as libc::c_ulong))
+      as libc::c_int;
+    if *line == 0 {
+      continue;
+    }
+    parse_msg(line, &mut m);
+    if !msg_matches(line, &m) {
+      continue;
+    }
+    if skip > 0 {
+      skip -= 1;
+      continue;
+    }
+    if option_mask32 & OPT_json as libc::c_uint != 0 {
+      print_json(&m);
+    } else {
+      fputs_unlocked(line, stdout);
+    }
+  }
+}
 pub unsafe fn logread_main(
   mut _argc: libc::c_int,
   mut argv: *mut *mut libc::c_char,
@@ -122,9 +502,43 @@ pub unsafe fn logread_main(
   let mut cur: libc::c_uint = 0; /* ipc shared memory id */
   let mut log_semid: libc::c_int = 0;
   let mut log_shmid: libc::c_int = 0;
-  let mut follow: libc::c_int =
-    crate::libbb::getopt32::getopt32(argv, b"fF\x00" as *const u8 as *const libc::c_char)
-      as libc::c_int;
+  let mut first: bool = true;
+  let mut opt_p: *const libc::c_char = std::ptr::null();
+  let mut opt_e: *const libc::c_char = std::ptr::null();
+  let mut opt_since: *const libc::c_char = std::ptr::null();
+  static mut logread_longopts: [u8; 16] = *b"json\x00\x00jsince\x00\x01\xff\x00";
+  let mut follow: libc::c_int = crate::libbb::getopt32::getopt32long(
+    argv,
+    b"^fFp:t:e:n:+j\x00=0\x00" as *const u8 as *const libc::c_char,
+    logread_longopts.as_ptr() as *const libc::c_char,
+    &mut opt_p as *mut *const libc::c_char,
+    &mut (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).tag as *mut *const libc::c_char,
+    &mut opt_e as *mut *const libc::c_char,
+    &mut (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).last_n as *mut libc::c_int,
+    &mut opt_since as *mut *const libc::c_char,
+  ) as libc::c_int;
+  if follow & OPT_prio as libc::c_int != 0 {
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).max_prio = parse_prio(opt_p)
+  }
+  if follow & OPT_regex as libc::c_int != 0 {
+    /* REG_EXTENDED | REG_NOSUB */
+    crate::libbb::xregcomp::xregcomp(
+      &mut (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).re,
+      opt_e,
+      1i32 | 8i32,
+    );
+  }
+  if follow & OPT_since as libc::c_int != 0 {
+    (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).since = parse_since(opt_since)
+  }
+  /* -f -n N: show the last N messages, then follow */
+  if follow & OPT_last_n as libc::c_int != 0 && follow & OPT_f as libc::c_int != 0 {
+    follow |= OPT_F as libc::c_int
+  }
+  follow &= (OPT_f | OPT_F) as libc::c_int;
+  if follow & OPT_F as libc::c_int != 0 {
+    follow = OPT_F as libc::c_int
+  }
   memcpy(
     (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
       .SMrup
@@ -168,7 +582,6 @@ pub unsafe fn logread_main(
     let mut shbuf_size: libc::c_uint = 0; /* for gcc */
     let mut shbuf_tail: libc::c_uint = 0; /* for gcc */
     let mut shbuf_data: *const libc::c_char = std::ptr::null();
-    let mut i: libc::c_int = 0;
     let mut len_first_part: libc::c_int = 0;
     let mut len_total: libc::c_int = 0;
     len_total = len_total;
@@ -271,13 +684,8 @@ pub unsafe fn logread_main(
       15956509640765329438 => {
         /* release the lock on the log chain */
         sem_up(log_semid);
-        i = 0;
-        while i < len_total {
-          fputs_unlocked(copy.offset(i as isize), stdout);
-          i = (i as libc::c_ulong)
-            .wrapping_add(strlen(copy.offset(i as isize)).wrapping_add(1i32 as libc::c_ulong))
-            as libc::c_int as libc::c_int
-        }
+        show_messages(copy, len_total, first);
+        first = false;
         free(copy as *mut libc::c_void);
         crate::libbb::xfuncs_printf::fflush_all();
       }
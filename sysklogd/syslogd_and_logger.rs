@@ -5,6 +5,7 @@ use crate::librb::len_and_sockaddr;
 use crate::librb::signal::__sighandler_t;
 use crate::librb::size_t;
 use crate::librb::smallint;
+use crate::networking::tls::tls_state_t;
 use libc;
 use libc::atoi;
 use libc::chmod;
@@ -27,9 +28,11 @@ use libc::sprintf;
 use libc::ssize_t;
 use libc::stat;
 use libc::strcasecmp;
+use libc::strcat;
 use libc::strchr;
 use libc::strcmp;
 use libc::strcpy;
+use libc::strstr;
 use libc::syslog;
 use libc::time;
 use libc::time_t;
@@ -101,6 +104,34 @@ extern "C" {
 
   #[no_mangle]
   fn ctime(__timer: *const time_t) -> *mut libc::c_char;
+
+  #[no_mangle]
+  fn strftime(
+    __s: *mut libc::c_char,
+    __maxsize: size_t,
+    __format: *const libc::c_char,
+    __tp: *const libc::tm,
+  ) -> size_t;
+
+  #[no_mangle]
+  fn strncmp(_: *const libc::c_char, _: *const libc::c_char, _: libc::c_ulong) -> libc::c_int;
+
+  #[no_mangle]
+  fn regexec(
+    __preg: *const regex_t,
+    __string: *const libc::c_char,
+    __nmatch: size_t,
+    __pmatch: *mut libc::c_void,
+    __eflags: libc::c_int,
+  ) -> libc::c_int;
+
+  #[no_mangle]
+  fn send(
+    __fd: libc::c_int,
+    __buf: *const libc::c_void,
+    __n: size_t,
+    __flags: libc::c_int,
+  ) -> ssize_t;
   /* Some useful definitions */
   /* Macros for min/max.  */
   /* buffer allocation schemes */
@@ -362,6 +393,9 @@ pub struct globals {
   pub recvbuf: [libc::c_char; 512],
   pub parsebuf: [libc::c_char; 512],
   pub printbuf: [libc::c_char; 640],
+  pub fwdbuf: [libc::c_char; 512],
+  pub queue_dir: *mut libc::c_char,
+  pub queue_max: libc::c_uint,
 }
 
 #[repr(C)]
@@ -378,6 +412,16 @@ pub struct logRule_t {
   pub enabled_facility_priomap: [u8; 24],
   pub file: *mut logFile_t,
   pub next: *mut logRule_t,
+  /* "@host"/"@@host" target instead of file */
+  pub remote: *mut remoteHost_t,
+  /* "~" target: drop, and don't look at further rules */
+  pub discard: u8,
+  /* ":PROPERTY, [!]OP, "VALUE"" filter */
+  pub filter_prop: u8,
+  pub filter_op: u8,
+  pub filter_negate: u8,
+  pub filter_value: *mut libc::c_char,
+  pub filter_re: *mut regex_t,
 }
 
 #[repr(C)]
@@ -388,8 +432,24 @@ pub struct logFile_t {
   pub last_log_time: time_t,
   pub size: libc::c_uint,
   pub isRegular: u8,
+  /* rotation policy: -s/-b, or size=/rotate=/age=/compress in syslog.conf */
+  pub compress: u8,
+  pub maxSize: libc::c_uint,
+  pub rotateCnt: libc::c_uint,
+  pub maxAge: libc::c_uint,
+  pub rotate_time: time_t,
 }
 
+pub type regex_t = crate::librb::re_pattern_buffer;
+pub const FILTER_NONE: u8 = 0;
+pub const FILTER_MSG: u8 = 1;
+pub const FILTER_PROGRAMNAME: u8 = 2;
+pub const FILTER_HOSTNAME: u8 = 3;
+pub const FILTER_CONTAINS: u8 = 0;
+pub const FILTER_ISEQUAL: u8 = 1;
+pub const FILTER_STARTSWITH: u8 = 2;
+pub const FILTER_REGEX: u8 = 3;
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct sembuf {
@@ -442,6 +502,17 @@ pub struct ipc_perm {
 pub type C2RustUnnamed_5 = libc::c_uint;
 pub const DNS_WAIT_SEC: C2RustUnnamed_5 = 120;
 pub const MAX_READ: C2RustUnnamed_5 = 256;
+/* stream remotes: reconnect delay doubles from 1 sec up to this */
+pub const RECONNECT_MAX_SEC: C2RustUnnamed_5 = 256;
+/* stream remotes: bytes buffered in memory while the socket is busy */
+pub const OUTBUF_SIZE: C2RustUnnamed_5 = 65536;
+/* default -q, in kbytes */
+pub const QUEUE_MAX_KB: C2RustUnnamed_5 = 1024;
+
+/* -R [udp://|tcp://|tls://]HOST[:PORT][#SHA256] */
+pub const REMOTE_UDP: u8 = 0;
+pub const REMOTE_TCP: u8 = 1;
+pub const REMOTE_TLS: u8 = 2;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -450,6 +521,26 @@ pub struct remoteHost_t {
   pub last_dns_resolve: libc::c_uint,
   pub remoteAddr: *mut len_and_sockaddr,
   pub remoteHostname: *const libc::c_char,
+  pub proto: u8,
+  /* monotonic_sec() before which we don't try to reconnect */
+  pub next_connect: libc::c_uint,
+  pub backoff: libc::c_uint,
+  /* non-blocking connect() in progress */
+  pub connecting: smallint,
+  /* Framed messages not yet written: outbuf[outoff..outlen].
+   * outframe is where the frame being written starts */
+  pub outbuf: *mut libc::c_char,
+  pub outlen: libc::c_int,
+  pub outoff: libc::c_int,
+  pub outframe: libc::c_int,
+  /* -Q DIR/HOST: framed messages not yet delivered */
+  pub queue_path: *mut libc::c_char,
+  pub queue_dropped: libc::c_uint,
+  /* queue_path has messages; the first queue_off bytes are in outbuf or sent */
+  pub queued: smallint,
+  pub queue_off: libc::off_t,
+  /* tls://: SHA-256 of the server's certificate */
+  pub cert_sha256: *mut u8,
 }
 
 #[repr(C)]
@@ -471,6 +562,9 @@ pub struct init_globals {
 /* default shm size */
 /* Options */
 pub type C2RustUnnamed_6 = libc::c_uint;
+pub const OPT_queuemax: C2RustUnnamed_6 = 65536;
+pub const OPT_queue: C2RustUnnamed_6 = 32768;
+pub const OPT_rfc5424: C2RustUnnamed_6 = 16384;
 pub const OPT_kmsg: C2RustUnnamed_6 = 8192;
 pub const OPT_cfg: C2RustUnnamed_6 = 4096;
 pub const OPT_dup: C2RustUnnamed_6 = 2048;
@@ -486,6 +580,12 @@ pub const OPT_outfile: C2RustUnnamed_6 = 4;
 pub const OPT_nofork: C2RustUnnamed_6 = 2;
 // -K
 pub const OPT_mark: C2RustUnnamed_6 = 1;
+// -q
+pub const OPTBIT_queuemax: C2RustUnnamed_6 = 16;
+// -Q
+pub const OPTBIT_queue: C2RustUnnamed_6 = 15;
+// -5
+pub const OPTBIT_rfc5424: C2RustUnnamed_6 = 14;
 // -f
 pub const OPTBIT_kmsg: C2RustUnnamed_6 = 13;
 // -D
@@ -824,14 +924,163 @@ unsafe fn find_by_val(mut val: libc::c_int, mut c_set: *const CODE) -> *const CO
   }
   return std::ptr::null();
 }
+/* Split the next whitespace-delimited word off *pp */
+unsafe fn next_word(mut pp: *mut *mut libc::c_char) -> *mut libc::c_char {
+  let mut p: *mut libc::c_char = crate::libbb::skip_whitespace::skip_whitespace(*pp);
+  let mut word: *mut libc::c_char = p;
+  if *p == 0 {
+    return std::ptr::null_mut();
+  }
+  p = crate::libbb::skip_whitespace::skip_non_whitespace(p);
+  if *p != 0 {
+    *p = '\u{0}' as i32 as libc::c_char;
+    p = p.offset(1)
+  }
+  *pp = p;
+  return word;
+}
+/* "size=KB rotate=N age=N[smhd] compress" */
+unsafe fn parse_file_options(
+  mut file: *mut logFile_t,
+  mut options: *mut libc::c_char,
+) -> libc::c_int {
+  let mut word: *mut libc::c_char = std::ptr::null_mut();
+  loop {
+    let mut end: *mut libc::c_char = std::ptr::null_mut();
+    let mut n: libc::c_uint = 0;
+    word = next_word(&mut options);
+    if word.is_null() {
+      break;
+    }
+    if strcmp(word, b"compress\x00" as *const u8 as *const libc::c_char) == 0 {
+      (*file).compress = 1i32 as u8;
+      continue;
+    }
+    end = strchr(word, '=' as i32);
+    if end.is_null() {
+      return -1i32;
+    }
+    *end = '\u{0}' as i32 as libc::c_char;
+    n = libc::strtoul(end.offset(1), &mut end, 10i32) as libc::c_uint;
+    if strcmp(word, b"size\x00" as *const u8 as *const libc::c_char) == 0 && *end == 0 {
+      (*file).maxSize = n.wrapping_mul(1024i32 as libc::c_uint)
+    } else if strcmp(word, b"rotate\x00" as *const u8 as *const libc::c_char) == 0
+      && *end == 0
+      && n <= 99
+    {
+      (*file).rotateCnt = n
+    } else if strcmp(word, b"age\x00" as *const u8 as *const libc::c_char) == 0 {
+      match *end as u8 {
+        0 | b's' => {}
+        b'm' => n = n.wrapping_mul(60),
+        b'h' => n = n.wrapping_mul(60 * 60),
+        b'd' => n = n.wrapping_mul(24 * 60 * 60),
+        _ => return -1i32,
+      }
+      (*file).maxAge = n
+    } else {
+      return -1i32;
+    }
+  }
+  return 0;
+}
+/* rsyslog-style ":PROPERTY, [!]OP, "VALUE" TARGET [OPTIONS]".
+ * PROPERTY: msg, programname, hostname
+ * OP: contains, isequal, startswith, regex
+ * Returns TARGET (NULL on syntax error), *rest is set to OPTIONS. */
+unsafe fn parse_filter(
+  mut rule: *mut logRule_t,
+  mut prop: *mut libc::c_char,
+  mut op: *mut libc::c_char,
+  mut rest: *mut *mut libc::c_char,
+) -> *mut libc::c_char {
+  let mut p: *mut libc::c_char = *rest;
+  let mut q: *mut libc::c_char = std::ptr::null_mut();
+  let mut target: *mut libc::c_char = std::ptr::null_mut();
+  *strchrnul(prop, ',' as i32) = '\u{0}' as i32 as libc::c_char;
+  *strchrnul(op, ',' as i32) = '\u{0}' as i32 as libc::c_char;
+  if strcasecmp(prop, b"msg\x00" as *const u8 as *const libc::c_char) == 0 {
+    (*rule).filter_prop = FILTER_MSG
+  } else if strcasecmp(prop, b"programname\x00" as *const u8 as *const libc::c_char) == 0 {
+    (*rule).filter_prop = FILTER_PROGRAMNAME
+  } else if strcasecmp(prop, b"hostname\x00" as *const u8 as *const libc::c_char) == 0 {
+    (*rule).filter_prop = FILTER_HOSTNAME
+  } else {
+    return std::ptr::null_mut();
+  }
+  if *op as libc::c_int == '!' as i32 {
+    (*rule).filter_negate = 1i32 as u8;
+    op = op.offset(1)
+  }
+  if strcmp(op, b"contains\x00" as *const u8 as *const libc::c_char) == 0 {
+    (*rule).filter_op = FILTER_CONTAINS
+  } else if strcmp(op, b"isequal\x00" as *const u8 as *const libc::c_char) == 0 {
+    (*rule).filter_op = FILTER_ISEQUAL
+  } else if strcmp(op, b"startswith\x00" as *const u8 as *const libc::c_char) == 0 {
+    (*rule).filter_op = FILTER_STARTSWITH
+  } else if strcmp(op, b"regex\x00" as *const u8 as *const libc::c_char) == 0 {
+    (*rule).filter_op = FILTER_REGEX
+  } else {
+    return std::ptr::null_mut();
+  }
+  /* "VALUE", with \" and \\ escapes */
+  if p.is_null() || *p as libc::c_int != '"' as i32 {
+    return std::ptr::null_mut();
+  }
+  p = p.offset(1);
+  (*rule).filter_value = p;
+  q = p;
+  loop {
+    if *p == 0 {
+      return std::ptr::null_mut();
+    }
+    if *p as libc::c_int == '"' as i32 {
+      break;
+    }
+    if *p as libc::c_int == '\\' as i32 && *p.offset(1) as libc::c_int != 0 {
+      p = p.offset(1)
+    }
+    *q = *p;
+    q = q.offset(1);
+    p = p.offset(1)
+  }
+  *q = '\u{0}' as i32 as libc::c_char;
+  p = p.offset(1);
+  (*rule).filter_value = crate::libbb::xfuncs_printf::xstrdup((*rule).filter_value);
+  if (*rule).filter_op == FILTER_REGEX {
+    (*rule).filter_re =
+      crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<regex_t>() as libc::c_ulong)
+        as *mut regex_t;
+    /* REG_EXTENDED | REG_NOSUB */
+    crate::libbb::xregcomp::xregcomp((*rule).filter_re, (*rule).filter_value, 1i32 | 8i32);
+  }
+  target = next_word(&mut p);
+  p = crate::libbb::skip_whitespace::skip_whitespace(p);
+  *rest = if *p != 0 { p } else { std::ptr::null_mut() };
+  return target;
+}
+/* syslog.conf syntax:
+ *   SELECTOR[;SELECTOR]...  TARGET [OPTIONS]
+ *   :PROPERTY, [!]OP, "VALUE"  TARGET [OPTIONS]
+ * TARGET is a file, "@host[:port]" (UDP), "@@host[:port]" (TCP)
+ * or "~" (discard, stop processing rules).
+ * OPTIONS (files only): size=KB rotate=N age=N[smhd] compress
+ * e.g.
+ *   kern.*                      /var/log/kern.log size=512 rotate=4 compress
+ *   :msg, contains, "segfault"  /var/log/crash.log age=1d
+ *   :programname, isequal, "dropbear"  @@loghost:514
+ *   *.info                      /var/log/messages
+ */
 unsafe fn parse_syslogdcfg(mut file: *const libc::c_char) {
   let mut current_block: u64;
   let mut t: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut pp_rule: *mut *mut logRule_t = std::ptr::null_mut();
-  /* tok[0] set of selectors */
-  /* tok[1] file name */
-  /* tok[2] has to be NULL */
+  /* tok[0] set of selectors, or ":PROPERTY," */
+  /* tok[1] file name, or "[!]OP," */
+  /* tok[2] options, or ""VALUE" TARGET [OPTIONS]" */
   let mut tok: [*mut libc::c_char; 3] = [0 as *mut libc::c_char; 3];
+  let mut target: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut options: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut parser: *mut parser_t = std::ptr::null_mut();
   parser = crate::libbb::parse_config::config_open2(
     if !file.is_null() {
@@ -870,17 +1119,27 @@ unsafe fn parse_syslogdcfg(mut file: *const libc::c_char) {
     }
     let mut cur_selector: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
     let mut cur_rule: *mut logRule_t = std::ptr::null_mut();
-    /* unexpected trailing token? */
-    if !tok[2].is_null() {
-      current_block = 12846980873243673022;
-      break;
-    }
     *pp_rule =
       crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<logRule_t>() as libc::c_ulong)
         as *mut logRule_t;
     cur_rule = *pp_rule;
+    target = tok[1];
+    options = tok[2];
+    if *tok[0] as libc::c_int == ':' as i32 {
+      /* property filter: applies to all facilities and priorities */
+      memset(
+        (*cur_rule).enabled_facility_priomap.as_mut_ptr() as *mut libc::c_void,
+        0xffi32,
+        24i32 as libc::c_ulong,
+      );
+      target = parse_filter(cur_rule, tok[0].offset(1), tok[1], &mut options);
+      if target.is_null() {
+        current_block = 12846980873243673022;
+        break;
+      }
+    }
     cur_selector = tok[0];
-    loop
+    while *cur_selector as libc::c_int != ':' as i32
     /* iterate through selectors: "kern.info;kern.!err;..." */
     {
       let mut code: *const CODE = std::ptr::null(); /* "kern.!err" */
@@ -998,10 +1257,22 @@ unsafe fn parse_syslogdcfg(mut file: *const libc::c_char) {
         break;
       }
     }
-    /* check whether current file name was mentioned in previous rules or
-     * as global logfile (G.logFile).
-     */
-    if strcmp((*ptr_to_globals).logFile.path, tok[1]) == 0 {
+    if strcmp(target, b"~\x00" as *const u8 as *const libc::c_char) == 0 {
+      (*cur_rule).discard = 1i32 as u8
+    } else if *target as libc::c_int == '@' as i32 {
+      /* "@host" is UDP, "@@host" is TCP */
+      (*cur_rule).remote = new_remote_host(if *target.offset(1) as libc::c_int == '@' as i32 {
+        crate::libbb::xfuncs_printf::xasprintf(
+          b"tcp://%s\x00" as *const u8 as *const libc::c_char,
+          target.offset(2),
+        )
+      } else {
+        crate::libbb::xfuncs_printf::xstrdup(target.offset(1))
+      })
+    } else if strcmp((*ptr_to_globals).logFile.path, target) == 0 {
+      /* check whether current file name was mentioned in previous rules or
+       * as global logfile (G.logFile).
+       */
       (*cur_rule).file = &mut (*ptr_to_globals).logFile
     } else {
       /* temporarily use cur_rule as iterator, but *pp_rule still points
@@ -1014,7 +1285,7 @@ unsafe fn parse_syslogdcfg(mut file: *const libc::c_char) {
           current_block = 1352918242886884122;
           break;
         }
-        if strcmp((*(*cur_rule).file).path, tok[1]) == 0 {
+        if !(*cur_rule).file.is_null() && strcmp((*(*cur_rule).file).path, target) == 0 {
           /* found - reuse the same file structure */
           (**pp_rule).file = (*cur_rule).file;
           cur_rule = *pp_rule;
@@ -1031,10 +1302,19 @@ unsafe fn parse_syslogdcfg(mut file: *const libc::c_char) {
             ::std::mem::size_of::<logFile_t>() as libc::c_ulong,
           ) as *mut logFile_t;
           (*(*cur_rule).file).fd = -1i32;
-          (*(*cur_rule).file).path = crate::libbb::xfuncs_printf::xstrdup(tok[1])
+          (*(*cur_rule).file).path = crate::libbb::xfuncs_printf::xstrdup(target);
+          (*(*cur_rule).file).maxSize = (*ptr_to_globals).logFileSize;
+          (*(*cur_rule).file).rotateCnt = (*ptr_to_globals).logFileRotate
         }
       }
     }
+    if !options.is_null() {
+      /* rotation policy only makes sense for files */
+      if (*cur_rule).file.is_null() || parse_file_options((*cur_rule).file, options) != 0 {
+        current_block = 12846980873243673022;
+        break;
+      }
+    }
     pp_rule = &mut (*cur_rule).next
   }
   match current_block {
@@ -1064,6 +1344,11 @@ static mut init_data: init_globals = {
         last_log_time: 0,
         size: 0,
         isRegular: 0,
+        compress: 0,
+        maxSize: 0,
+        rotateCnt: 0,
+        maxAge: 0,
+        rotate_time: 0,
       };
       init
     },
@@ -1330,29 +1615,44 @@ unsafe fn log_locally(mut now: time_t, mut msg: *mut libc::c_char, mut log_file:
           as libc::c_int as u8;
         /* bug (mostly harmless): can wrap around if file > 4gb */
         (*log_file).size = statf.st_size as libc::c_uint;
+        if now == 0 {
+          now = time(0 as *mut time_t)
+        }
+        if (*log_file).rotate_time == 0 {
+          (*log_file).rotate_time = now
+        }
         current_block = 15768484401365413375;
       }
       _ =>
       /* We are logging to stdout: do nothing */
       {
-        if !((*ptr_to_globals).logFileSize != 0
-          && (*log_file).isRegular as libc::c_int != 0
-          && (*log_file).size > (*ptr_to_globals).logFileSize)
-        {
+        if (*log_file).isRegular == 0 {
           break;
         }
-        if (*ptr_to_globals).logFileRotate != 0 {
+        if !((*log_file).maxSize != 0 && (*log_file).size > (*log_file).maxSize) {
+          /* age=: rotate non-empty files which are old enough */
+          if now == 0 {
+            now = time(0 as *mut time_t)
+          }
+          if !((*log_file).maxAge != 0
+            && (*log_file).size != 0
+            && now - (*log_file).rotate_time >= (*log_file).maxAge as time_t)
+          {
+            break;
+          }
+        }
+        (*log_file).rotate_time = now;
+        if (*log_file).rotateCnt != 0 {
           /* always 0..99 */
           let mut i: libc::c_int = strlen((*log_file).path)
+            .wrapping_add(3i32 as libc::c_ulong)
             .wrapping_add(3i32 as libc::c_ulong)
             .wrapping_add(1i32 as libc::c_ulong) as libc::c_int;
           let vla = i as usize;
           let mut oldFile: Vec<libc::c_char> = ::std::vec::from_elem(0, vla);
           let vla_0 = i as usize;
           let mut newFile: Vec<libc::c_char> = ::std::vec::from_elem(0, vla_0);
-          i = (*ptr_to_globals)
-            .logFileRotate
-            .wrapping_sub(1i32 as libc::c_uint) as libc::c_int;
+          i = (*log_file).rotateCnt.wrapping_sub(1i32 as libc::c_uint) as libc::c_int;
           loop
           /* rename: f.8 -> f.9; f.7 -> f.8; ... */
           {
@@ -1374,9 +1674,24 @@ unsafe fn log_locally(mut now: time_t, mut msg: *mut libc::c_char, mut log_file:
             );
             /* ignore errors - file might be missing */
             rename(oldFile.as_mut_ptr(), newFile.as_mut_ptr());
+            if (*log_file).compress != 0 {
+              /* and f.8.gz -> f.9.gz */
+              strcat(
+                oldFile.as_mut_ptr(),
+                b".gz\x00" as *const u8 as *const libc::c_char,
+              );
+              strcat(
+                newFile.as_mut_ptr(),
+                b".gz\x00" as *const u8 as *const libc::c_char,
+              );
+              rename(oldFile.as_mut_ptr(), newFile.as_mut_ptr());
+            }
           }
           /* newFile == "f.0" now */
           rename((*log_file).path, newFile.as_mut_ptr());
+          if (*log_file).compress != 0 {
+            compress_rotated(newFile.as_mut_ptr());
+          }
         }
         /* We may or may not have just renamed the file away;
          * if we didn't rename because we aren't keeping any backlog,
@@ -1401,6 +1716,143 @@ unsafe fn log_locally(mut now: time_t, mut msg: *mut libc::c_char, mut log_file:
     (*log_file).size = (*log_file).size.wrapping_add(len as libc::c_uint)
   };
 }
+unsafe fn reap_children() {
+  while libc::waitpid(-1i32, std::ptr::null_mut(), libc::WNOHANG) > 0 {}
+}
+/* gzip a rotated log in the background: f.0 -> f.0.gz */
+unsafe fn compress_rotated(mut path: *const libc::c_char) {
+  let mut argv: [*mut libc::c_char; 4] = [
+    b"gzip\x00" as *const u8 as *const libc::c_char as *mut libc::c_char,
+    b"-f\x00" as *const u8 as *const libc::c_char as *mut libc::c_char,
+    std::ptr::null_mut(),
+    std::ptr::null_mut(),
+  ];
+  let mut pid: pid_t = 0;
+  reap_children();
+  argv[2] = crate::libbb::xfuncs_printf::xstrdup(path);
+  pid = libc::fork();
+  if pid < 0 {
+    crate::libbb::perror_msg::bb_simple_perror_msg(b"fork\x00" as *const u8 as *const libc::c_char);
+  } else if pid == 0 {
+    /* Child. gzip has its own G, ours is not needed anymore */
+    signal(15i32, None);
+    signal(2i32, None);
+    libc::_exit(crate::archival::gzip::gzip_main(3i32, argv.as_mut_ptr()));
+  }
+  free(argv[2] as *mut libc::c_void);
+}
+/* "TAG[PID]: text" -> "text", *tag_len = length of TAG (0 if none) */
+unsafe fn skip_tag(mut msg: *mut libc::c_char, mut tag_len: *mut libc::c_int) -> *mut libc::c_char {
+  let mut p: *mut libc::c_char = msg;
+  *tag_len = 0;
+  while *p as libc::c_int != 0
+    && *p as libc::c_int != ':' as i32
+    && *p as libc::c_int != '[' as i32
+    && *p as libc::c_int != ' ' as i32
+  {
+    p = p.offset(1)
+  }
+  if p != msg {
+    let mut tag_end: *mut libc::c_char = p;
+    if *p as libc::c_int == '[' as i32 {
+      p = strchr(p, ']' as i32);
+      if !p.is_null() {
+        p = p.offset(1)
+      }
+    }
+    if !p.is_null() && *p as libc::c_int == ':' as i32 {
+      *tag_len = tag_end.offset_from(msg) as libc::c_int;
+      p = p.offset(1);
+      if *p as libc::c_int == ' ' as i32 {
+        p = p.offset(1)
+      }
+      return p;
+    }
+  }
+  return msg;
+}
+unsafe fn filter_matches(mut rule: *const logRule_t, mut msg: *mut libc::c_char) -> bool {
+  let mut name: [libc::c_char; 49] = [0; 49];
+  let mut subject: *const libc::c_char = std::ptr::null();
+  let mut tag_len: libc::c_int = 0;
+  let mut text: *mut libc::c_char = skip_tag(msg, &mut tag_len);
+  let mut r: bool = false;
+  match (*rule).filter_prop {
+    FILTER_NONE => return true,
+    FILTER_MSG => subject = text,
+    FILTER_PROGRAMNAME => {
+      crate::libbb::safe_strncpy::safe_strncpy(
+        name.as_mut_ptr(),
+        msg,
+        (if tag_len > 48i32 { 48i32 } else { tag_len } + 1i32) as libc::c_ulong,
+      );
+      subject = name.as_mut_ptr()
+    }
+    _ => subject = (*ptr_to_globals).hostname,
+  }
+  match (*rule).filter_op {
+    FILTER_CONTAINS => r = !strstr(subject, (*rule).filter_value).is_null(),
+    FILTER_ISEQUAL => r = strcmp(subject, (*rule).filter_value) == 0,
+    FILTER_STARTSWITH => {
+      r = strncmp(subject, (*rule).filter_value, strlen((*rule).filter_value)) == 0
+    }
+    _ => {
+      r = regexec(
+        (*rule).filter_re,
+        subject,
+        0 as size_t,
+        std::ptr::null_mut(),
+        0,
+      ) == 0
+    }
+  }
+  return r != ((*rule).filter_negate != 0);
+}
+/* Send to an "@host"/"@@host" syslog.conf target */
+unsafe fn forward_to_remote(
+  mut rh: *mut remoteHost_t,
+  mut pri: libc::c_int,
+  mut timestamp: *const libc::c_char,
+  mut msg: *const libc::c_char,
+) {
+  let mut buf: [libc::c_char; 700] = [0; 700];
+  let mut buf5424: [libc::c_char; 700] = [0; 700];
+  let mut out: *mut libc::c_char = buf.as_mut_ptr();
+  let mut len: libc::c_int = 0;
+  if option_mask32 & OPT_rfc5424 as libc::c_int as libc::c_uint != 0 {
+    snprintf(
+      buf.as_mut_ptr(),
+      ::std::mem::size_of::<[libc::c_char; 700]>() as libc::c_ulong,
+      b"<%d>%s\x00" as *const u8 as *const libc::c_char,
+      pri,
+      msg,
+    );
+    out = buf5424.as_mut_ptr();
+    len = format_rfc5424(
+      buf.as_mut_ptr(),
+      out,
+      ::std::mem::size_of::<[libc::c_char; 700]>() as libc::c_int,
+    )
+  } else {
+    len = snprintf(
+      buf.as_mut_ptr(),
+      ::std::mem::size_of::<[libc::c_char; 700]>() as libc::c_ulong,
+      b"<%d>%s %s %s\x00" as *const u8 as *const libc::c_char,
+      pri,
+      timestamp,
+      (*ptr_to_globals).hostname,
+      msg,
+    );
+    if len >= ::std::mem::size_of::<[libc::c_char; 700]>() as libc::c_int {
+      len = ::std::mem::size_of::<[libc::c_char; 700]>() as libc::c_int - 1i32
+    }
+  }
+  if (*rh).proto == REMOTE_UDP {
+    send_udp_remote(rh, out, len);
+  } else {
+    send_stream_remote(rh, out, len);
+  }
+}
 unsafe fn parse_fac_prio_20(mut pri: libc::c_int, mut res20: *mut libc::c_char) {
   let mut c_pri: *const CODE = std::ptr::null();
   let mut c_fac: *const CODE = std::ptr::null();
@@ -1489,9 +1941,17 @@ unsafe fn timestamp_and_log(
   while !rule.is_null() {
     if (*rule).enabled_facility_priomap[facility as usize] as libc::c_int & prio_bit as libc::c_int
       != 0
+      && filter_matches(rule, msg)
     {
-      log_locally(now, (*ptr_to_globals).printbuf.as_mut_ptr(), (*rule).file);
-      match_0 = 1i32 != 0
+      match_0 = 1i32 != 0;
+      if (*rule).discard != 0 {
+        break;
+      }
+      if !(*rule).remote.is_null() {
+        forward_to_remote((*rule).remote, pri, timestamp, msg);
+      } else {
+        log_locally(now, (*ptr_to_globals).printbuf.as_mut_ptr(), (*rule).file);
+      }
     }
     rule = (*rule).next
   }
@@ -1539,6 +1999,9 @@ unsafe fn split_escape_and_log(mut tmpbuf: *mut libc::c_char, mut len: libc::c_i
         pri = 1i32 << 3i32 | 5i32
       }
     }
+    if *p as libc::c_int == '1' as i32 && *p.offset(1) as libc::c_int == ' ' as i32 {
+      q = strip_rfc5424_header(&mut p, q)
+    }
     loop {
       let fresh3 = p;
       p = p.offset(1);
@@ -1624,17 +2087,756 @@ unsafe fn try_to_resolve_remote(mut rh: *mut remoteHost_t) -> libc::c_int {
       return -1i32;
     }
     (*rh).last_dns_resolve = now;
-    (*rh).remoteAddr = crate::libbb::xconnect::host2sockaddr((*rh).remoteHostname, 514i32);
+    (*rh).remoteAddr = crate::libbb::xconnect::host2sockaddr(
+      (*rh).remoteHostname,
+      if (*rh).proto == REMOTE_TLS {
+        6514i32
+      } else {
+        514i32
+      },
+    );
     if (*rh).remoteAddr.is_null() {
       return -1i32;
     }
   }
   return crate::libbb::xfuncs_printf::xsocket(
     (*(*rh).remoteAddr).u.sa.sa_family as libc::c_int,
-    SOCK_DGRAM as libc::c_int,
+    if (*rh).proto == REMOTE_UDP {
+      SOCK_DGRAM as libc::c_int
+    } else {
+      SOCK_STREAM as libc::c_int
+    },
     0,
   );
 }
+/* "2020-01-18T00:11:22.123456+01:00" */
+unsafe fn rfc5424_timestamp(mut buf: *mut libc::c_char, mut size: libc::c_int) {
+  let mut tv: libc::timeval = std::mem::zeroed();
+  let mut tm: libc::tm = std::mem::zeroed();
+  let mut len: libc::c_int = 0;
+  libc::gettimeofday(&mut tv, std::ptr::null_mut());
+  libc::localtime_r(&tv.tv_sec, &mut tm);
+  len = strftime(
+    buf,
+    size as size_t,
+    b"%Y-%m-%dT%H:%M:%S\x00" as *const u8 as *const libc::c_char,
+    &tm,
+  ) as libc::c_int;
+  len += snprintf(
+    buf.offset(len as isize),
+    (size - len) as libc::c_ulong,
+    b".%06u\x00" as *const u8 as *const libc::c_char,
+    tv.tv_usec as libc::c_uint,
+  );
+  /* strftime's %z is "+0100", RFC 5424 wants "+01:00" */
+  strftime(
+    buf.offset(len as isize),
+    (size - len) as size_t,
+    b"%z\x00" as *const u8 as *const libc::c_char,
+    &tm,
+  );
+  if strlen(buf.offset(len as isize)) == 5 {
+    *buf.offset((len + 6) as isize) = '\u{0}' as i32 as libc::c_char;
+    *buf.offset((len + 5) as isize) = *buf.offset((len + 4) as isize);
+    *buf.offset((len + 4) as isize) = *buf.offset((len + 3) as isize);
+    *buf.offset((len + 3) as isize) = ':' as i32 as libc::c_char;
+  }
+}
+/* Rewrite a message as received on /dev/log ("<PRI>[TIMESTAMP ]TAG[PID]: MSG")
+ * into "<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID - - MSG".
+ * Messages which are already in RFC 5424 format are passed through.
+ * Returns the length of the result in out. */
+unsafe fn format_rfc5424(
+  mut msg: *mut libc::c_char,
+  mut out: *mut libc::c_char,
+  mut size: libc::c_int,
+) -> libc::c_int {
+  let mut ts: [libc::c_char; 40] = [0; 40];
+  let mut tag: *const libc::c_char = b"-\x00" as *const u8 as *const libc::c_char;
+  let mut tag_len: libc::c_int = 1i32;
+  let mut pid: *const libc::c_char = b"-\x00" as *const u8 as *const libc::c_char;
+  let mut pid_len: libc::c_int = 1i32;
+  let mut p: *mut libc::c_char = std::ptr::null_mut();
+  let mut len: libc::c_int = 0;
+  let mut pri: libc::c_int = 1i32 << 3i32 | 5i32;
+  if *msg as libc::c_int == '<' as i32 {
+    pri = crate::libbb::bb_strtonum::bb_strtou(msg.offset(1), &mut msg, 10i32) as libc::c_int;
+    if *msg as libc::c_int == '>' as i32 {
+      msg = msg.offset(1)
+    }
+    if pri & !(0x3f8i32 | 0x7i32) != 0 {
+      pri = 1i32 << 3i32 | 5i32
+    }
+  }
+  if *msg as libc::c_int == '1' as i32 && *msg.offset(1) as libc::c_int == ' ' as i32 {
+    len = snprintf(
+      out,
+      size as libc::c_ulong,
+      b"<%d>%s\x00" as *const u8 as *const libc::c_char,
+      pri,
+      msg,
+    );
+  } else {
+    /* Jan 18 00:11:22 msg... - we have a better timestamp */
+    if strlen(msg) >= 16
+      && *msg.offset(3) as libc::c_int == ' ' as i32
+      && *msg.offset(6) as libc::c_int == ' ' as i32
+      && *msg.offset(9) as libc::c_int == ':' as i32
+      && *msg.offset(12) as libc::c_int == ':' as i32
+      && *msg.offset(15) as libc::c_int == ' ' as i32
+    {
+      msg = msg.offset(16)
+    }
+    /* "TAG[PID]: text" or "TAG: text" */
+    p = msg;
+    while *p as libc::c_int != 0
+      && *p as libc::c_int != ':' as i32
+      && *p as libc::c_int != '[' as i32
+      && *p as libc::c_int != ' ' as i32
+    {
+      p = p.offset(1)
+    }
+    if p != msg {
+      let mut tag_end: *mut libc::c_char = p;
+      let mut pid_start: *mut libc::c_char = std::ptr::null_mut();
+      if *p as libc::c_int == '[' as i32 {
+        pid_start = p.offset(1);
+        p = strchr(pid_start, ']' as i32);
+        if !p.is_null() {
+          p = p.offset(1)
+        }
+      }
+      if !p.is_null() && *p as libc::c_int == ':' as i32 {
+        tag = msg;
+        tag_len = tag_end.offset_from(msg) as libc::c_int;
+        /* p points past "]" */
+        if !pid_start.is_null() && p.offset_from(pid_start) > 1 {
+          pid = pid_start;
+          pid_len = p.offset_from(pid_start) as libc::c_int - 1i32;
+        }
+        p = p.offset(1);
+        if *p as libc::c_int == ' ' as i32 {
+          p = p.offset(1)
+        }
+        msg = p
+      }
+    }
+    rfc5424_timestamp(
+      ts.as_mut_ptr(),
+      ::std::mem::size_of::<[libc::c_char; 40]>() as libc::c_int,
+    );
+    len = snprintf(
+      out,
+      size as libc::c_ulong,
+      b"<%d>1 %s %s %.*s %.*s - - %s\x00" as *const u8 as *const libc::c_char,
+      pri,
+      ts.as_mut_ptr(),
+      (*ptr_to_globals).hostname,
+      if tag_len > 48i32 { 48i32 } else { tag_len },
+      tag,
+      if pid_len > 128i32 { 128i32 } else { pid_len },
+      pid,
+      msg,
+    );
+  }
+  if len >= size {
+    len = size - 1i32
+  }
+  return len;
+}
+/* Drop the RFC 5424 header (*pp points to "1 "), leaving "APP-NAME[PROCID]: "
+ * as the tag, so that such messages look like the rest in local logs.
+ * Structured data, if any, is kept as part of the message text. */
+unsafe fn strip_rfc5424_header(
+  mut pp: *mut *mut libc::c_char,
+  mut q: *mut libc::c_char,
+) -> *mut libc::c_char {
+  let mut field: [*mut libc::c_char; 5] = [std::ptr::null_mut(); 5];
+  let mut flen: [libc::c_int; 5] = [0; 5];
+  let mut p: *mut libc::c_char = (*pp).offset(2);
+  let mut i: libc::c_int = 0;
+  /* TIMESTAMP HOSTNAME APP-NAME PROCID MSGID */
+  while i < 5i32 {
+    field[i as usize] = p;
+    while *p as libc::c_int != 0 && *p as libc::c_int != ' ' as i32 {
+      p = p.offset(1)
+    }
+    if *p == 0 {
+      /* malformed, log it as is */
+      return q;
+    }
+    flen[i as usize] = p.offset_from(field[i as usize]) as libc::c_int;
+    p = p.offset(1);
+    i += 1
+  }
+  if *field[2].offset(0) as libc::c_int != '-' as i32 || flen[2] != 1i32 {
+    i = 0;
+    while i < flen[2] && i < 48i32 {
+      *q = *field[2].offset(i as isize);
+      q = q.offset(1);
+      i += 1
+    }
+    if *field[3].offset(0) as libc::c_int != '-' as i32 || flen[3] != 1i32 {
+      q = q.offset(sprintf(
+        q,
+        b"[%.*s]\x00" as *const u8 as *const libc::c_char,
+        if flen[3] > 32i32 { 32i32 } else { flen[3] },
+        field[3],
+      ) as isize);
+    }
+    *q = ':' as i32 as libc::c_char;
+    *q.offset(1) = ' ' as i32 as libc::c_char;
+    q = q.offset(2)
+  }
+  /* NILVALUE structured data */
+  if *p as libc::c_int == '-' as i32
+    && (*p.offset(1) as libc::c_int == ' ' as i32 || *p.offset(1) == 0)
+  {
+    p = p.offset(if *p.offset(1) != 0 { 2 } else { 1 })
+  }
+  /* MSG may start with UTF-8 BOM */
+  if *p as u8 == 0xef && *p.offset(1) as u8 == 0xbb && *p.offset(2) as u8 == 0xbf {
+    p = p.offset(3)
+  }
+  *pp = p;
+  return q;
+}
+unsafe fn send_udp_remote(
+  mut rh: *mut remoteHost_t,
+  mut buf: *const libc::c_char,
+  mut len: libc::c_int,
+) {
+  if (*rh).remoteFD == -1i32 {
+    (*rh).remoteFD = try_to_resolve_remote(rh);
+    if (*rh).remoteFD == -1i32 {
+      return;
+    }
+  }
+  /* Send message to remote logger.
+   * On some errors, close and set remoteFD to -1
+   * so that DNS resolution is retried.
+   */
+  if sendto(
+    (*rh).remoteFD,
+    buf as *const libc::c_void,
+    len as size_t,
+    MSG_DONTWAIT as libc::c_int | MSG_NOSIGNAL as libc::c_int,
+    __CONST_SOCKADDR_ARG {
+      __sockaddr__: &mut (*(*rh).remoteAddr).u.sa,
+    },
+    (*(*rh).remoteAddr).len,
+  ) == -1
+  {
+    match *bb_errno {
+      104 | 107 | 32 => {
+        /* paranoia */
+        close((*rh).remoteFD);
+        (*rh).remoteFD = -1i32;
+        free((*rh).remoteAddr as *mut libc::c_void);
+        (*rh).remoteAddr = std::ptr::null_mut()
+      }
+      _ => {}
+    }
+  }
+}
+/* [udp://|tcp://|tls://]HOST[:PORT][#SHA256] */
+unsafe fn new_remote_host(mut spec: *const libc::c_char) -> *mut remoteHost_t {
+  let mut rh: *mut remoteHost_t = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<
+    remoteHost_t,
+  >() as libc::c_ulong) as *mut remoteHost_t;
+  (*rh).remoteHostname = spec;
+  if strncmp(
+    (*rh).remoteHostname,
+    b"tcp://\x00" as *const u8 as *const libc::c_char,
+    6i32 as libc::c_ulong,
+  ) == 0
+  {
+    (*rh).proto = REMOTE_TCP;
+    (*rh).remoteHostname = (*rh).remoteHostname.offset(6)
+  } else if strncmp(
+    (*rh).remoteHostname,
+    b"tls://\x00" as *const u8 as *const libc::c_char,
+    6i32 as libc::c_ulong,
+  ) == 0
+  {
+    (*rh).proto = REMOTE_TLS;
+    (*rh).remoteHostname = (*rh).remoteHostname.offset(6)
+  } else if strncmp(
+    (*rh).remoteHostname,
+    b"udp://\x00" as *const u8 as *const libc::c_char,
+    6i32 as libc::c_ulong,
+  ) == 0
+  {
+    (*rh).remoteHostname = (*rh).remoteHostname.offset(6)
+  }
+  if (*rh).proto == REMOTE_TLS {
+    /* Without a CA store, the server is identified by its certificate:
+     * "openssl x509 -noout -fingerprint -sha256 -in CERT" */
+    let mut host: *mut libc::c_char =
+      crate::libbb::xfuncs_printf::xstrdup((*rh).remoteHostname);
+    let mut fp: *mut libc::c_char = strchr(host, '#' as i32);
+    if fp.is_null() {
+      crate::libbb::verror_msg::bb_error_msg_and_die(
+        b"%s: tls:// needs the server certificate's SHA-256 (HOST#SHA256)\x00" as *const u8
+          as *const libc::c_char,
+        spec,
+      );
+    }
+    *fp = '\u{0}' as i32 as libc::c_char;
+    fp = fp.offset(1);
+    (*rh).cert_sha256 = crate::libbb::xfuncs_printf::xzalloc(32i32 as libc::c_ulong) as *mut u8;
+    if crate::libbb::xfuncs::hex2bin((*rh).cert_sha256 as *mut libc::c_char, fp, 32i32)
+      != ((*rh).cert_sha256 as *mut libc::c_char).offset(32)
+      || *bb_errno != 0
+    {
+      crate::libbb::verror_msg::bb_error_msg_and_die(
+        b"bad certificate fingerprint '%s'\x00" as *const u8 as *const libc::c_char,
+        fp,
+      );
+    }
+    (*rh).remoteHostname = host
+  }
+  if (*rh).proto != REMOTE_UDP && !(*ptr_to_globals).queue_dir.is_null() {
+    let mut st: stat = std::mem::zeroed();
+    (*rh).queue_path = crate::libbb::concat_path_file::concat_path_file(
+      (*ptr_to_globals).queue_dir,
+      (*rh).remoteHostname,
+    );
+    /* left over from the previous run */
+    if libc::stat((*rh).queue_path, &mut st) == 0 && st.st_size > 0 {
+      (*rh).queued = 1 as smallint
+    }
+  }
+  (*rh).remoteFD = -1i32;
+  (*rh).last_dns_resolve = crate::libbb::time::monotonic_sec()
+    .wrapping_sub(DNS_WAIT_SEC as libc::c_int as libc::c_uint)
+    .wrapping_sub(1i32 as libc::c_uint);
+  return rh;
+}
+/* Total length of the "LEN MSG" frame at buf, 0 if its header isn't complete */
+unsafe fn frame_len(mut buf: *const libc::c_char, mut avail: libc::c_int) -> libc::c_int {
+  let mut n: libc::c_int = 0;
+  let mut i: libc::c_int = 0;
+  while i < avail && *buf.offset(i as isize) as libc::c_int != ' ' as i32 {
+    n = n * 10i32 + (*buf.offset(i as isize) as libc::c_int - '0' as i32);
+    i += 1
+  }
+  if i >= avail {
+    return 0;
+  }
+  return i + 1i32 + n;
+}
+unsafe fn close_stream_remote(mut rh: *mut remoteHost_t) {
+  let mut now: libc::c_uint = crate::libbb::time::monotonic_sec();
+  if (*rh).remoteFD >= 0 {
+    close((*rh).remoteFD);
+    (*rh).remoteFD = -1i32
+  }
+  (*rh).connecting = 0 as smallint;
+  /* A frame cut short goes out again whole on the next connection */
+  (*rh).outoff = (*rh).outframe;
+  (*rh).backoff = if (*rh).backoff == 0 {
+    1i32 as libc::c_uint
+  } else if (*rh).backoff < RECONNECT_MAX_SEC as libc::c_int as libc::c_uint / 2 {
+    (*rh).backoff.wrapping_mul(2i32 as libc::c_uint)
+  } else {
+    RECONNECT_MAX_SEC as libc::c_int as libc::c_uint
+  };
+  (*rh).next_connect = now.wrapping_add((*rh).backoff);
+}
+/* Append a framed message to the remote's on-disk queue, unless it is full */
+unsafe fn queue_message(
+  mut rh: *mut remoteHost_t,
+  mut buf: *const libc::c_char,
+  mut len: libc::c_int,
+) {
+  let mut st: stat = std::mem::zeroed();
+  let mut fd: libc::c_int = 0;
+  if (*rh).queue_path.is_null() {
+    (*rh).queue_dropped = (*rh).queue_dropped.wrapping_add(1);
+    return;
+  }
+  fd = open(
+    (*rh).queue_path,
+    libc::O_WRONLY | libc::O_CREAT | libc::O_APPEND | libc::O_NOCTTY,
+    0o600i32,
+  );
+  if fd < 0 {
+    (*rh).queue_dropped = (*rh).queue_dropped.wrapping_add(1);
+    return;
+  }
+  if fstat(fd, &mut st) != 0
+    || st.st_size + len as libc::off_t > (*ptr_to_globals).queue_max as libc::off_t
+    || crate::libbb::full_write::full_write(fd, buf as *const libc::c_void, len as size_t)
+      != len as ssize_t
+  {
+    (*rh).queue_dropped = (*rh).queue_dropped.wrapping_add(1)
+  } else {
+    (*rh).queued = 1 as smallint
+  }
+  close(fd);
+}
+/* Append a framed message to the in-memory send buffer.
+ * Returns -1 if it doesn't fit */
+unsafe fn buffer_message(
+  mut rh: *mut remoteHost_t,
+  mut buf: *const libc::c_char,
+  mut len: libc::c_int,
+) -> libc::c_int {
+  if (*rh).outbuf.is_null() {
+    (*rh).outbuf = crate::libbb::xfuncs_printf::xmalloc(OUTBUF_SIZE as libc::c_int as size_t)
+      as *mut libc::c_char
+  }
+  if (*rh).outlen + len > OUTBUF_SIZE as libc::c_int {
+    /* drop what was already sent */
+    libc::memmove(
+      (*rh).outbuf as *mut libc::c_void,
+      (*rh).outbuf.offset((*rh).outframe as isize) as *const libc::c_void,
+      ((*rh).outlen - (*rh).outframe) as libc::size_t,
+    );
+    (*rh).outlen -= (*rh).outframe;
+    (*rh).outoff -= (*rh).outframe;
+    (*rh).outframe = 0;
+    if (*rh).outlen + len > OUTBUF_SIZE as libc::c_int {
+      return -1i32;
+    }
+  }
+  memcpy(
+    (*rh).outbuf.offset((*rh).outlen as isize) as *mut libc::c_void,
+    buf as *const libc::c_void,
+    len as libc::c_ulong,
+  );
+  (*rh).outlen += len;
+  return 0;
+}
+/* Load the next chunk of the on-disk queue into the (empty) send buffer.
+ * Only whole frames are taken, a frame cut by the end of the chunk
+ * is read again at the start of the next one. */
+unsafe fn refill_from_queue(mut rh: *mut remoteHost_t) {
+  let mut n: ssize_t = -1;
+  let mut len: libc::c_int = 0;
+  let mut fd: libc::c_int = open((*rh).queue_path, libc::O_RDONLY);
+  if fd >= 0 {
+    n = libc::pread(
+      fd,
+      (*rh).outbuf as *mut libc::c_void,
+      OUTBUF_SIZE as libc::c_int as libc::size_t,
+      (*rh).queue_off,
+    );
+    close(fd);
+  }
+  while n > 0 {
+    let mut flen: libc::c_int =
+      frame_len((*rh).outbuf.offset(len as isize), n as libc::c_int - len);
+    if flen == 0 || len + flen > n as libc::c_int {
+      break;
+    }
+    len += flen
+  }
+  if len == 0 {
+    /* all sent: new messages can go straight to the send buffer.
+     * (A frame that doesn't fit the buffer, or was left truncated
+     * by a failed write, is dropped too) */
+    unlink((*rh).queue_path);
+    (*rh).queued = 0 as smallint;
+    (*rh).queue_off = 0;
+    return;
+  }
+  (*rh).outlen = len;
+  (*rh).queue_off += len as libc::off_t;
+}
+/* Write out as much as the remote takes without blocking */
+unsafe fn flush_stream_remote(mut rh: *mut remoteHost_t) {
+  while (*rh).remoteFD >= 0 && (*rh).connecting == 0 {
+    let mut n: ssize_t = 0;
+    if (*rh).outoff == (*rh).outlen {
+      (*rh).outlen = 0;
+      (*rh).outoff = 0;
+      (*rh).outframe = 0;
+      if (*rh).queued != 0 {
+        refill_from_queue(rh);
+      }
+      if (*rh).outlen == 0 {
+        if (*rh).queue_dropped != 0 {
+          let mut msg: [libc::c_char; 128] = [0; 128];
+          snprintf(
+            msg.as_mut_ptr(),
+            ::std::mem::size_of::<[libc::c_char; 128]>() as libc::c_ulong,
+            b"syslogd: %s: queue full, %u messages dropped\x00" as *const u8
+              as *const libc::c_char,
+            (*rh).remoteHostname,
+            (*rh).queue_dropped,
+          );
+          (*rh).queue_dropped = 0;
+          timestamp_and_log_internal(msg.as_mut_ptr());
+        }
+        break;
+      }
+    }
+    n = send(
+      (*rh).remoteFD,
+      (*rh).outbuf.offset((*rh).outoff as isize) as *const libc::c_void,
+      ((*rh).outlen - (*rh).outoff) as size_t,
+      MSG_NOSIGNAL as libc::c_int | MSG_DONTWAIT as libc::c_int,
+    );
+    if n < 0 {
+      if *bb_errno == libc::EINTR {
+        continue;
+      }
+      if *bb_errno != libc::EAGAIN {
+        close_stream_remote(rh);
+      }
+      break;
+    }
+    (*rh).outoff += n as libc::c_int;
+    /* outframe: start of the frame not yet completely sent */
+    loop {
+      let mut flen: libc::c_int = frame_len(
+        (*rh).outbuf.offset((*rh).outframe as isize),
+        (*rh).outlen - (*rh).outframe,
+      );
+      if flen == 0 || (*rh).outframe + flen > (*rh).outoff {
+        break;
+      }
+      (*rh).outframe += flen
+    }
+  }
+}
+/* Hand the connected socket to a child running the TLS client,
+ * we talk plaintext to it over a socketpair */
+unsafe fn spawn_tls_client(mut rh: *mut remoteHost_t, mut network_fd: libc::c_int) -> libc::c_int {
+  let mut sp: [libc::c_int; 2] = [0; 2];
+  let mut pid: pid_t = 0;
+  if libc::socketpair(1i32, SOCK_STREAM as libc::c_int, 0, sp.as_mut_ptr()) != 0 {
+    crate::libbb::perror_msg::bb_simple_perror_msg(
+      b"socketpair\x00" as *const u8 as *const libc::c_char,
+    );
+    close(network_fd);
+    return -1i32;
+  }
+  pid = libc::fork();
+  if pid < 0 {
+    crate::libbb::perror_msg::bb_simple_perror_msg(b"fork\x00" as *const u8 as *const libc::c_char);
+    close(sp[0]);
+    close(sp[1]);
+    close(network_fd);
+    return -1i32;
+  }
+  if pid == 0 {
+    /* Child */
+    let mut tls: *mut tls_state_t = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<
+      tls_state_t,
+    >() as libc::c_ulong) as *mut tls_state_t;
+    let mut sni: *mut libc::c_char = crate::libbb::xfuncs_printf::xstrdup((*rh).remoteHostname);
+    signal(15i32, None);
+    signal(2i32, None);
+    close(sp[0]);
+    crate::libbb::xfuncs_printf::xmove_fd(sp[1], 0);
+    crate::libbb::xfuncs_printf::xdup2(0i32, 1i32);
+    /* "host:port" -> "host"; no SNI for "[ipv6]:port" */
+    *strchrnul(sni, ':' as i32) = '\u{0}' as i32 as libc::c_char;
+    (*tls).ofd = network_fd;
+    (*tls).ifd = network_fd;
+    /* the handshake dies unless the server has the pinned certificate */
+    (*tls).cert_sha256 = (*rh).cert_sha256;
+    crate::networking::tls::tls_handshake(
+      tls,
+      if *sni as libc::c_int == '[' as i32 {
+        std::ptr::null()
+      } else {
+        sni
+      },
+    );
+    /* TLSLOOP_EXIT_ON_LOCAL_EOF */
+    crate::networking::tls::tls_run_copy_loop(tls, 1i32 as libc::c_uint);
+    libc::_exit(0i32);
+  }
+  close(sp[1]);
+  close(network_fd);
+  return sp[0];
+}
+unsafe fn connect_failed(mut rh: *mut remoteHost_t) {
+  /* address may have changed */
+  free((*rh).remoteAddr as *mut libc::c_void);
+  (*rh).remoteAddr = std::ptr::null_mut();
+  (*rh).last_dns_resolve = crate::libbb::time::monotonic_sec()
+    .wrapping_sub(DNS_WAIT_SEC as libc::c_int as libc::c_uint)
+    .wrapping_sub(1i32 as libc::c_uint);
+  close_stream_remote(rh);
+}
+/* Called once the non-blocking connect() is done */
+unsafe fn finish_connect(mut rh: *mut remoteHost_t) {
+  let mut err: libc::c_int = 0;
+  let mut errlen: libc::socklen_t = ::std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+  let mut fd: libc::c_int = (*rh).remoteFD;
+  (*rh).connecting = 0 as smallint;
+  if libc::getsockopt(
+    fd,
+    libc::SOL_SOCKET,
+    libc::SO_ERROR,
+    &mut err as *mut libc::c_int as *mut libc::c_void,
+    &mut errlen,
+  ) != 0
+  {
+    err = *bb_errno
+  }
+  if err != 0 {
+    connect_failed(rh);
+    return;
+  }
+  if (*rh).proto == REMOTE_TLS {
+    /* The helper does blocking I/O on the network side, we don't */
+    crate::libbb::xfuncs::ndelay_off(fd);
+    (*rh).remoteFD = -1i32;
+    fd = spawn_tls_client(rh, fd);
+    if fd < 0 {
+      close_stream_remote(rh);
+      return;
+    }
+    crate::libbb::xfuncs::close_on_exec_on(fd);
+    crate::libbb::xfuncs::ndelay_on(fd);
+    (*rh).remoteFD = fd
+  }
+  (*rh).backoff = 0;
+}
+/* Start connecting a tcp:// or tls:// remote, honoring reconnect backoff.
+ * Never blocks: do_syslogd() polls the socket and calls finish_connect() */
+unsafe fn connect_stream_remote(mut rh: *mut remoteHost_t) {
+  let mut fd: libc::c_int = 0;
+  if (crate::libbb::time::monotonic_sec().wrapping_sub((*rh).next_connect) as libc::c_int) < 0 {
+    return;
+  }
+  /* reap exited TLS helpers */
+  reap_children();
+  fd = try_to_resolve_remote(rh);
+  if fd < 0 {
+    close_stream_remote(rh);
+    return;
+  }
+  crate::libbb::xfuncs::close_on_exec_on(fd);
+  crate::libbb::xfuncs::ndelay_on(fd);
+  (*rh).remoteFD = fd;
+  if libc::connect(fd, &mut (*(*rh).remoteAddr).u.sa, (*(*rh).remoteAddr).len) != 0 {
+    if *bb_errno == libc::EINPROGRESS {
+      (*rh).connecting = 1 as smallint;
+    } else {
+      connect_failed(rh);
+    }
+    return;
+  }
+  finish_connect(rh);
+}
+/* RFC 6587 octet counting: "LEN MSG".
+ * Messages wait in memory while the remote is slow, and in the -Q queue
+ * when memory is full; once anything is queued on disk, later messages
+ * follow it there so that order is kept */
+unsafe fn send_stream_remote(
+  mut rh: *mut remoteHost_t,
+  mut msg: *const libc::c_char,
+  mut len: libc::c_int,
+) {
+  let mut framed: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+    b"%u %.*s\x00" as *const u8 as *const libc::c_char,
+    len as libc::c_uint,
+    len,
+    msg,
+  );
+  let mut flen: libc::c_int = strlen(framed) as libc::c_int;
+  if (*rh).queued != 0 || buffer_message(rh, framed, flen) != 0 {
+    queue_message(rh, framed, flen);
+  }
+  free(framed as *mut libc::c_void);
+  if (*rh).remoteFD == -1i32 {
+    connect_stream_remote(rh);
+  }
+  flush_stream_remote(rh);
+}
+/* Wait for a message on /dev/log. Meanwhile, connect stream remotes
+ * which have something to send and feed them as their sockets drain.
+ * Returns -1 on signal or error */
+unsafe fn wait_for_dev_log() -> libc::c_int {
+  let mut item: *mut llist_t = (*ptr_to_globals).remoteHosts;
+  let mut n: libc::c_int = 1i32;
+  while !item.is_null() {
+    n += 1;
+    item = (*item).link
+  }
+  let mut pfd: *mut libc::pollfd = crate::libbb::xfuncs_printf::xzalloc(
+    (n as libc::c_ulong).wrapping_mul(::std::mem::size_of::<libc::pollfd>() as libc::c_ulong),
+  ) as *mut libc::pollfd;
+  let mut rhs: *mut *mut remoteHost_t = crate::libbb::xfuncs_printf::xzalloc(
+    (n as libc::c_ulong).wrapping_mul(::std::mem::size_of::<*mut remoteHost_t>() as libc::c_ulong),
+  ) as *mut *mut remoteHost_t;
+  let mut ret: libc::c_int = 0;
+  loop {
+    let mut now: libc::c_uint = crate::libbb::time::monotonic_sec();
+    let mut timeout: libc::c_int = -1i32;
+    let mut cnt: libc::c_int = 1i32;
+    let mut i: libc::c_int = 0;
+    (*pfd).fd = 0;
+    (*pfd).events = libc::POLLIN;
+    (*pfd).revents = 0;
+    item = (*ptr_to_globals).remoteHosts;
+    while !item.is_null() {
+      let mut rh: *mut remoteHost_t = (*item).data as *mut remoteHost_t;
+      item = (*item).link;
+      if (*rh).proto == REMOTE_UDP || (*rh).outoff == (*rh).outlen && (*rh).queued == 0 {
+        continue;
+      }
+      if (*rh).remoteFD < 0 {
+        connect_stream_remote(rh);
+        flush_stream_remote(rh);
+      }
+      if (*rh).remoteFD < 0 {
+        /* wake up for the next reconnect attempt */
+        let mut wait: libc::c_int = (*rh).next_connect.wrapping_sub(now) as libc::c_int;
+        if wait < 1 {
+          wait = 1
+        }
+        if timeout < 0 || wait * 1000i32 < timeout {
+          timeout = wait * 1000i32
+        }
+        continue;
+      }
+      if (*rh).connecting != 0 || (*rh).outoff != (*rh).outlen || (*rh).queued != 0 {
+        (*pfd.offset(cnt as isize)).fd = (*rh).remoteFD;
+        (*pfd.offset(cnt as isize)).events = libc::POLLOUT;
+        (*pfd.offset(cnt as isize)).revents = 0;
+        *rhs.offset(cnt as isize) = rh;
+        cnt += 1
+      }
+    }
+    if libc::poll(pfd, cnt as libc::nfds_t, timeout) < 0 {
+      if *bb_errno == libc::EINTR && bb_got_signal == 0 {
+        continue;
+      }
+      if bb_got_signal == 0 {
+        crate::libbb::perror_msg::bb_simple_perror_msg(
+          b"poll\x00" as *const u8 as *const libc::c_char,
+        );
+      }
+      ret = -1i32;
+      break;
+    }
+    i = 1;
+    while i < cnt {
+      if (*pfd.offset(i as isize)).revents != 0 {
+        let mut rh: *mut remoteHost_t = *rhs.offset(i as isize);
+        if (*rh).connecting != 0 {
+          finish_connect(rh);
+        }
+        flush_stream_remote(rh);
+      }
+      i += 1
+    }
+    if (*pfd).revents != 0 {
+      break;
+    }
+  }
+  free(pfd as *mut libc::c_void);
+  free(rhs as *mut libc::c_void);
+  return ret;
+}
 unsafe fn do_syslogd() -> ! {
   let mut item: *mut llist_t = std::ptr::null_mut();
   let mut last_sz: libc::c_int = -1i32;
@@ -1676,6 +2878,9 @@ unsafe fn do_syslogd() -> ! {
     } else {
       recvbuf = (*ptr_to_globals).recvbuf.as_mut_ptr()
     }
+    if wait_for_dev_log() != 0 {
+      break;
+    }
     'c_12157: loop {
       sz = read(
         0,
@@ -1734,55 +2939,41 @@ unsafe fn do_syslogd() -> ! {
       _ => {}
     }
     last_sz = sz as libc::c_int;
+    /* With -5, messages go out reformatted (and without '\n') */
+    let mut fwd: *mut libc::c_char = recvbuf;
+    let mut fwd_len: libc::c_int = sz as libc::c_int + 1i32;
+    if option_mask32 & OPT_rfc5424 as libc::c_int as libc::c_uint != 0
+      && !(*ptr_to_globals).remoteHosts.is_null()
+    {
+      *recvbuf.offset(sz as isize) = '\u{0}' as i32 as libc::c_char;
+      fwd_len = format_rfc5424(
+        recvbuf,
+        (*ptr_to_globals).fwdbuf.as_mut_ptr(),
+        ::std::mem::size_of::<[libc::c_char; 512]>() as libc::c_int,
+      );
+      fwd = (*ptr_to_globals).fwdbuf.as_mut_ptr()
+    }
     /* Stock syslogd sends it '\n'-terminated
      * over network, mimic that */
     *recvbuf.offset(sz as isize) = '\n' as i32 as libc::c_char;
-    let mut current_block_27: u64;
     /* We are not modifying log messages in any way before send */
     /* Remote site cannot trust _us_ anyway and need to do validation again */
     item = (*ptr_to_globals).remoteHosts;
     while !item.is_null() {
       let mut rh: *mut remoteHost_t = (*item).data as *mut remoteHost_t;
-      if (*rh).remoteFD == -1i32 {
-        (*rh).remoteFD = try_to_resolve_remote(rh);
-        if (*rh).remoteFD == -1i32 {
-          current_block_27 = 4775909272756257391;
-        } else {
-          current_block_27 = 9853141518545631134;
-        }
+      if (*rh).proto != REMOTE_UDP {
+        /* octet counting makes the trailing '\n' unnecessary */
+        send_stream_remote(
+          rh,
+          fwd,
+          if fwd == recvbuf {
+            fwd_len - 1i32
+          } else {
+            fwd_len
+          },
+        );
       } else {
-        current_block_27 = 9853141518545631134;
-      }
-      match current_block_27 {
-        9853141518545631134 => {
-          /* Send message to remote logger.
-           * On some errors, close and set remoteFD to -1
-           * so that DNS resolution is retried.
-           */
-          if sendto(
-            (*rh).remoteFD,
-            recvbuf as *const libc::c_void,
-            (sz + 1) as size_t,
-            MSG_DONTWAIT as libc::c_int | MSG_NOSIGNAL as libc::c_int,
-            __CONST_SOCKADDR_ARG {
-              __sockaddr__: &mut (*(*rh).remoteAddr).u.sa,
-            },
-            (*(*rh).remoteAddr).len,
-          ) == -1
-          {
-            match *bb_errno {
-              104 | 107 | 32 => {
-                /* paranoia */
-                close((*rh).remoteFD); /* ensure it *is* NUL terminated */
-                (*rh).remoteFD = -1i32;
-                free((*rh).remoteAddr as *mut libc::c_void);
-                (*rh).remoteAddr = std::ptr::null_mut()
-              }
-              _ => {}
-            }
-          }
-        }
-        _ => {}
+        send_udp_remote(rh, fwd, fwd_len);
       }
       item = (*item).link
     }
@@ -1812,6 +3003,7 @@ pub unsafe fn syslogd_main(
   let mut opt_b: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut opt_C: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut opt_f: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut opt_q: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut remoteAddrList: *mut llist_t = std::ptr::null_mut();
   let ref mut fresh6 = *(not_const_pp(&ptr_to_globals as *const *mut globals as *const libc::c_void)
     as *mut *mut globals);
@@ -1824,7 +3016,7 @@ pub unsafe fn syslogd_main(
   /* No non-option params */
   opts = crate::libbb::getopt32::getopt32(
     argv,
-    b"^m:nO:l:Sts:b:R:*LC::Df:K\x00=0\x00" as *const u8 as *const libc::c_char,
+    b"^m:nO:l:Sts:b:R:*LC::Df:K5Q:q:\x00=0\x00" as *const u8 as *const libc::c_char,
     &mut opt_m as *mut *mut libc::c_char,
     &mut (*ptr_to_globals).logFile.path as *mut *const libc::c_char,
     &mut opt_l as *mut *mut libc::c_char,
@@ -1833,18 +3025,29 @@ pub unsafe fn syslogd_main(
     &mut remoteAddrList as *mut *mut llist_t,
     &mut opt_C as *mut *mut libc::c_char,
     &mut opt_f as *mut *mut libc::c_char,
+    &mut (*ptr_to_globals).queue_dir as *mut *mut libc::c_char,
+    &mut opt_q as *mut *mut libc::c_char,
   ) as libc::c_int;
+  (*ptr_to_globals).queue_max = (QUEUE_MAX_KB as libc::c_int * 1024i32) as libc::c_uint;
+  if opts & OPT_queuemax as libc::c_int != 0 {
+    // -q
+    (*ptr_to_globals).queue_max = crate::libbb::xatonum::xatou_range(
+      opt_q,
+      0 as libc::c_uint,
+      (2147483647i32 / 1024i32) as libc::c_uint,
+    )
+    .wrapping_mul(1024i32 as libc::c_uint)
+  }
+  if !(*ptr_to_globals).queue_dir.is_null() {
+    // -Q
+    libc::mkdir((*ptr_to_globals).queue_dir, 0o700i32 as mode_t);
+  }
   while !remoteAddrList.is_null() {
-    let mut rh: *mut remoteHost_t = crate::libbb::xfuncs_printf::xzalloc(::std::mem::size_of::<
-      remoteHost_t,
-    >() as libc::c_ulong) as *mut remoteHost_t;
-    (*rh).remoteHostname =
-      crate::libbb::llist::llist_pop(&mut remoteAddrList) as *const libc::c_char;
-    (*rh).remoteFD = -1i32;
-    (*rh).last_dns_resolve = crate::libbb::time::monotonic_sec()
-      .wrapping_sub(DNS_WAIT_SEC as libc::c_int as libc::c_uint)
-      .wrapping_sub(1i32 as libc::c_uint);
-    crate::libbb::llist::llist_add_to(&mut (*ptr_to_globals).remoteHosts, rh as *mut libc::c_void);
+    crate::libbb::llist::llist_add_to(
+      &mut (*ptr_to_globals).remoteHosts,
+      new_remote_host(crate::libbb::llist::llist_pop(&mut remoteAddrList) as *const libc::c_char)
+        as *mut libc::c_void,
+    );
   }
   //if (opts & OPT_nofork) // -n
   //if (opts & OPT_outfile) // -O
@@ -1883,6 +3086,8 @@ pub unsafe fn syslogd_main(
     // -R
     option_mask32 |= OPT_locallog as libc::c_int as libc::c_uint
   }
+  (*ptr_to_globals).logFile.maxSize = (*ptr_to_globals).logFileSize;
+  (*ptr_to_globals).logFile.rotateCnt = (*ptr_to_globals).logFileRotate;
   parse_syslogdcfg(opt_f);
   /* Store away localhost's name before the fork */
   (*ptr_to_globals).hostname = crate::libbb::safe_gethostname::safe_gethostname();
@@ -1923,6 +3128,10 @@ pub unsafe fn syslogd_main(
 //usage:     "\n	-s	Log to stderr as well as the system log"
 //usage:     "\n	-t TAG	Log using the specified tag (defaults to user name)"
 //usage:     "\n	-p PRIO	Priority (numeric or facility.level pair)"
+//usage:     "\n	--rfc5424	Send RFC 5424 formatted message"
+//usage:     "\n	--msgid ID	RFC 5424 MSGID"
+//usage:     "\n	--sd-id ID	Add structured data element ID (name@number)"
+//usage:     "\n	--sd-param NAME=VALUE	Add parameter to the last --sd-id"
 //usage:
 //usage:#define logger_example_usage
 //usage:       "$ logger \"hello\"\n"
@@ -1995,26 +3204,215 @@ unsafe fn pencode(mut s: *mut libc::c_char) -> libc::c_int {
   }
   return lev & 0x7i32 | fac & 0x3f8i32;
 }
+/* Append s to the SD-PARAM value at q, escaping '"', '\\' and ']' */
+unsafe fn sd_escape(mut q: *mut libc::c_char, mut s: *const libc::c_char) -> *mut libc::c_char {
+  while *s != 0 {
+    if *s as libc::c_int == '"' as i32
+      || *s as libc::c_int == '\\' as i32
+      || *s as libc::c_int == ']' as i32
+    {
+      *q = '\\' as i32 as libc::c_char;
+      q = q.offset(1)
+    }
+    *q = *s;
+    q = q.offset(1);
+    s = s.offset(1)
+  }
+  return q;
+}
+/* Build "[id1][id2 name="value" ...]" from --sd-id/--sd-param.
+ * All params belong to the last --sd-id given. */
+unsafe fn build_structured_data(
+  mut ids: *mut llist_t,
+  mut params: *mut llist_t,
+) -> *mut libc::c_char {
+  let mut size: libc::c_ulong = 2i32 as libc::c_ulong;
+  let mut item: *mut llist_t = std::ptr::null_mut();
+  let mut sd: *mut libc::c_char = std::ptr::null_mut();
+  let mut q: *mut libc::c_char = std::ptr::null_mut();
+  if ids.is_null() {
+    if !params.is_null() {
+      crate::libbb::verror_msg::bb_simple_error_msg_and_die(
+        b"--sd-param requires --sd-id\x00" as *const u8 as *const libc::c_char,
+      );
+    }
+    return b"-\x00" as *const u8 as *const libc::c_char as *mut libc::c_char;
+  }
+  item = ids;
+  while !item.is_null() {
+    let mut id: *const libc::c_char = (*item).data;
+    /* Private SD-IDs are "name@<private enterprise number>" */
+    if strchr(id, '@' as i32).is_null()
+      && strcmp(id, b"timeQuality\x00" as *const u8 as *const libc::c_char) != 0
+      && strcmp(id, b"origin\x00" as *const u8 as *const libc::c_char) != 0
+      && strcmp(id, b"meta\x00" as *const u8 as *const libc::c_char) != 0
+    {
+      crate::libbb::verror_msg::bb_error_msg_and_die(
+        b"invalid structured data ID: '%s'\x00" as *const u8 as *const libc::c_char,
+        id,
+      );
+    }
+    size = size.wrapping_add(strlen(id).wrapping_add(2i32 as libc::c_ulong));
+    item = (*item).link
+  }
+  item = params;
+  while !item.is_null() {
+    if strchr((*item).data, '=' as i32).is_null() {
+      crate::libbb::verror_msg::bb_error_msg_and_die(
+        b"invalid structured data parameter: '%s'\x00" as *const u8 as *const libc::c_char,
+        (*item).data,
+      );
+    }
+    size = size.wrapping_add(
+      strlen((*item).data)
+        .wrapping_mul(2i32 as libc::c_ulong)
+        .wrapping_add(4i32 as libc::c_ulong),
+    );
+    item = (*item).link
+  }
+  sd = crate::libbb::xfuncs_printf::xmalloc(size) as *mut libc::c_char;
+  q = sd;
+  item = ids;
+  while !item.is_null() {
+    q = q.offset(sprintf(
+      q,
+      b"[%s\x00" as *const u8 as *const libc::c_char,
+      (*item).data,
+    ) as isize);
+    if (*item).link.is_null() {
+      let mut param: *mut llist_t = params;
+      while !param.is_null() {
+        let mut eq: *mut libc::c_char = strchr((*param).data, '=' as i32);
+        let mut val: *mut libc::c_char = eq.offset(1);
+        let mut vlen: libc::c_ulong = strlen(val);
+        *eq = '\u{0}' as i32 as libc::c_char;
+        /* accept both name=value and name="value" */
+        if vlen >= 2
+          && *val as libc::c_int == '"' as i32
+          && *val.offset(vlen as isize - 1) as libc::c_int == '"' as i32
+        {
+          *val.offset(vlen as isize - 1) = '\u{0}' as i32 as libc::c_char;
+          val = val.offset(1)
+        }
+        q = q.offset(sprintf(
+          q,
+          b" %s=\"\x00" as *const u8 as *const libc::c_char,
+          (*param).data,
+        ) as isize);
+        q = sd_escape(q, val);
+        *q = '"' as i32 as libc::c_char;
+        q = q.offset(1);
+        param = (*param).link
+      }
+    }
+    *q = ']' as i32 as libc::c_char;
+    q = q.offset(1);
+    item = (*item).link
+  }
+  *q = '\u{0}' as i32 as libc::c_char;
+  return sd;
+}
+/* --rfc5424: bypass libc's syslog(), which only knows the BSD format */
+unsafe fn send_rfc5424(
+  mut fd: libc::c_int,
+  mut pri: libc::c_int,
+  mut tag: *const libc::c_char,
+  mut msgid: *const libc::c_char,
+  mut sd: *const libc::c_char,
+  mut msg: *const libc::c_char,
+  mut to_stderr: libc::c_int,
+) {
+  let mut ts: [libc::c_char; 40] = [0; 40];
+  let mut hostname: *mut libc::c_char = crate::libbb::safe_gethostname::safe_gethostname();
+  let mut line: *mut libc::c_char = std::ptr::null_mut();
+  rfc5424_timestamp(
+    ts.as_mut_ptr(),
+    ::std::mem::size_of::<[libc::c_char; 40]>() as libc::c_int,
+  );
+  line = crate::libbb::xfuncs_printf::xasprintf(
+    b"<%d>1 %s %s %.48s - %.32s %s %s\x00" as *const u8 as *const libc::c_char,
+    pri,
+    ts.as_mut_ptr(),
+    hostname,
+    tag,
+    msgid,
+    sd,
+    msg,
+  );
+  if send(
+    fd,
+    line as *const libc::c_void,
+    strlen(line),
+    MSG_NOSIGNAL as libc::c_int,
+  ) < 0
+  {
+    crate::libbb::perror_msg::bb_perror_msg(
+      b"can't send to %s\x00" as *const u8 as *const libc::c_char,
+      b"/dev/log\x00" as *const u8 as *const libc::c_char,
+    );
+  }
+  if to_stderr != 0 {
+    crate::libbb::xfuncs::full_write2_str(line);
+    crate::libbb::xfuncs::full_write2_str(b"\n\x00" as *const u8 as *const libc::c_char);
+  }
+  free(line as *mut libc::c_void);
+  free(hostname as *mut libc::c_void);
+}
+unsafe fn open_dev_log() -> libc::c_int {
+  let mut sunx: sockaddr_un = sockaddr_un {
+    sun_family: 1i32 as sa_family_t,
+    sun_path: [0; 108],
+  };
+  let mut fd: libc::c_int =
+    crate::libbb::xfuncs_printf::xsocket(1i32, SOCK_DGRAM as libc::c_int, 0);
+  strcpy(
+    sunx.sun_path.as_mut_ptr(),
+    b"/dev/log\x00" as *const u8 as *const libc::c_char,
+  );
+  crate::libbb::xconnect::xconnect(
+    fd,
+    &mut sunx as *mut sockaddr_un as *mut sockaddr,
+    ::std::mem::size_of::<sockaddr_un>() as libc::c_ulong as socklen_t,
+  );
+  return fd;
+}
 pub unsafe fn logger_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_char) -> libc::c_int {
+  static mut logger_longopts: [u8; 38] =
+    *b"rfc5424\x00\x00\xffsd-id\x00\x01\xfesd-param\x00\x01\xfdmsgid\x00\x01\xfc\x00";
   let mut str_p: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
   let mut str_t: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut str_msgid: *const libc::c_char = b"-\x00" as *const u8 as *const libc::c_char;
+  let mut sd_ids: *mut llist_t = std::ptr::null_mut();
+  let mut sd_params: *mut llist_t = std::ptr::null_mut();
+  let mut sd: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut fd: libc::c_int = -1i32;
   let mut opt: libc::c_int = 0;
   let mut i: libc::c_int = 0;
   /* Fill out the name string early (may be overwritten later) */
   str_t = crate::libbb::bb_pwd::uid2uname_utoa(geteuid());
   /* Parse any options */
-  opt = crate::libbb::getopt32::getopt32(
+  opt = crate::libbb::getopt32::getopt32long(
     argv,
-    b"p:st:\x00" as *const u8 as *const libc::c_char,
+    b"^p:st:\x00\xfe::\xfd::\x00" as *const u8 as *const libc::c_char,
+    logger_longopts.as_ptr() as *const libc::c_char,
     &mut str_p as *mut *mut libc::c_char,
     &mut str_t as *mut *mut libc::c_char,
+    &mut sd_ids as *mut *mut llist_t,
+    &mut sd_params as *mut *mut llist_t,
+    &mut str_msgid as *mut *const libc::c_char,
   ) as libc::c_int;
   if opt & 0x2i32 != 0 {
     /* -s */
     i |= 0x20i32
   }
   //if (opt & 0x4) /* -t */
-  openlog(str_t, i, 0);
+  if opt & 0x8i32 != 0 {
+    /* --rfc5424 */
+    sd = build_structured_data(sd_ids, sd_params);
+    fd = open_dev_log()
+  } else {
+    openlog(str_t, i, 0);
+  }
   i = 1i32 << 3i32 | 5i32;
   if opt & 0x1i32 != 0 {
     /* -p */
@@ -2034,11 +3432,24 @@ pub unsafe fn logger_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_ch
           || *bb_common_bufsiz1.as_mut_ptr().offset(1) as libc::c_int != 0)
       {
         /* Neither "" nor "\n" */
-        syslog(
-          i,
-          b"%s\x00" as *const u8 as *const libc::c_char,
-          bb_common_bufsiz1.as_mut_ptr(),
-        );
+        if fd >= 0 {
+          *strchrnul(bb_common_bufsiz1.as_mut_ptr(), '\n' as i32) = '\u{0}' as i32 as libc::c_char;
+          send_rfc5424(
+            fd,
+            i,
+            str_t,
+            str_msgid,
+            sd,
+            bb_common_bufsiz1.as_mut_ptr(),
+            opt & 0x2i32,
+          );
+        } else {
+          syslog(
+            i,
+            b"%s\x00" as *const u8 as *const libc::c_char,
+            bb_common_bufsiz1.as_mut_ptr(),
+          );
+        }
       }
     }
   } else {
@@ -2062,13 +3473,21 @@ pub unsafe fn logger_main(mut _argc: libc::c_int, mut argv: *mut *mut libc::c_ch
         break;
       }
     }
-    syslog(
-      i,
-      b"%s\x00" as *const u8 as *const libc::c_char,
-      message.offset(1),
-    );
     /* skip leading " " */
+    if fd >= 0 {
+      send_rfc5424(fd, i, str_t, str_msgid, sd, message.offset(1), opt & 0x2i32);
+    } else {
+      syslog(
+        i,
+        b"%s\x00" as *const u8 as *const libc::c_char,
+        message.offset(1),
+      );
+    }
+  }
+  if fd >= 0 {
+    close(fd);
+  } else {
+    closelog();
   }
-  closelog();
   return 0;
 }
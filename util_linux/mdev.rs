@@ -12,6 +12,8 @@ use libc::chdir;
 use libc::chmod;
 use libc::chown;
 use libc::close;
+use libc::closedir;
+use libc::dirent;
 use libc::free;
 use libc::getenv;
 use libc::getpid;
@@ -21,8 +23,10 @@ use libc::mode_t;
 use libc::off64_t;
 use libc::off_t;
 use libc::open;
+use libc::opendir;
 use libc::pid_t;
 use libc::putenv;
+use libc::readdir;
 use libc::sigaddset;
 use libc::sigemptyset;
 use libc::siginfo_t;
@@ -47,7 +51,10 @@ use libc::timespec;
 use libc::timeval;
 use libc::uid_t;
 use libc::umask;
+use libc::uname;
 use libc::unlink;
+use libc::utsname;
+use libc::DIR;
 extern "C" {
 
   #[no_mangle]
@@ -78,6 +85,7 @@ extern "C" {
 
   #[no_mangle]
   fn strlen(__s: *const libc::c_char) -> size_t;
+  fn strsep(__stringp: *mut *mut libc::c_char, __delim: *const libc::c_char) -> *mut libc::c_char;
 
   #[no_mangle]
   fn gettimeofday(__tv: *mut timeval, __tz: __timezone_ptr_t) -> libc::c_int;
@@ -318,6 +326,7 @@ pub struct rule {
 pub struct envmatch {
   pub next: *mut envmatch,
   pub envname: *mut libc::c_char,
+  pub attr: bool,
   pub match_0: regex_t,
 }
 pub type regex_t = re_pattern_buffer;
@@ -397,13 +406,32 @@ unsafe fn parse_envmatch_pfx(mut val: *mut libc::c_char) -> *mut libc::c_char {
   loop {
     let mut e: *mut envmatch = std::ptr::null_mut();
     let mut semicolon: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
-    let mut eq: *mut libc::c_char = strchr(val, '=' as i32);
-    if eq.is_null() {
-      /* || eq == val? */
-      return val;
-    }
-    if crate::libbb::endofname::endofname(val) != eq {
-      return val;
+    let mut name_end: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    let mut attr: bool = false;
+    let mut eq: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    if !crate::libbb::compare_string_array::is_prefixed_with(
+      val,
+      b"ATTR{\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      /* ATTR{file}=regex;... - match contents of /sys/$DEVPATH/file */
+      name_end = strchr(val, '}' as i32);
+      if name_end.is_null() || *name_end.offset(1) as libc::c_int != '=' as i32 {
+        return val;
+      }
+      eq = name_end.offset(1);
+      attr = true
+    } else {
+      eq = strchr(val, '=' as i32);
+      if eq.is_null() {
+        /* || eq == val? */
+        return val;
+      }
+      if crate::libbb::endofname::endofname(val) != eq {
+        return val;
+      }
+      name_end = eq
     }
     semicolon = strchr(eq, ';' as i32);
     if semicolon.is_null() {
@@ -414,9 +442,13 @@ unsafe fn parse_envmatch_pfx(mut val: *mut libc::c_char) -> *mut libc::c_char {
       as *mut envmatch;
     *nextp = e;
     nextp = &mut (*e).next;
+    (*e).attr = attr;
+    if attr {
+      val = val.offset(5)
+    }
     (*e).envname = crate::libbb::xfuncs_printf::xstrndup(
       val,
-      eq.wrapping_offset_from(val) as libc::c_long as libc::c_int,
+      name_end.offset_from(val) as libc::c_long as libc::c_int,
     );
     *semicolon = '\u{0}' as i32 as libc::c_char;
     crate::libbb::xregcomp::xregcomp(&mut (*e).match_0, eq.offset(1), 1i32);
@@ -698,10 +730,43 @@ unsafe fn next_rule() -> *const rule {
   }
   return rule;
 }
-unsafe fn env_matches(mut e: *mut envmatch) -> libc::c_int {
+unsafe fn env_matches(mut e: *mut envmatch, mut path: *const libc::c_char) -> libc::c_int {
   while !e.is_null() {
     let mut r: libc::c_int = 0;
-    let mut val: *mut libc::c_char = getenv((*e).envname);
+    let mut attrbuf: [libc::c_char; 256] = [0; 256];
+    let mut val: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+    if (*e).attr {
+      /* ATTR{file}: sysfs attribute of the device, sans trailing newline */
+      let mut attrpath: *mut libc::c_char =
+        crate::libbb::concat_path_file::concat_path_file(path, (*e).envname);
+      let mut len: ssize_t = crate::libbb::read::open_read_close(
+        attrpath,
+        attrbuf.as_mut_ptr() as *mut libc::c_void,
+        (::std::mem::size_of::<[libc::c_char; 256]>() as libc::c_ulong)
+          .wrapping_sub(1i32 as libc::c_ulong),
+      );
+      free(attrpath as *mut libc::c_void);
+      if len < 0 {
+        return 0;
+      }
+      while len > 0 && attrbuf[(len - 1) as usize] as libc::c_int == '\n' as i32 {
+        len -= 1
+      }
+      attrbuf[len as usize] = '\u{0}' as i32 as libc::c_char;
+      val = attrbuf.as_mut_ptr()
+    } else if strcmp(
+      (*e).envname,
+      b"SUBSYSTEM\x00" as *const u8 as *const libc::c_char,
+    ) == 0
+      && !(*(bb_common_bufsiz1.as_mut_ptr() as *mut globals))
+        .subsystem
+        .is_null()
+    {
+      /* mdev -s knows it from the sysfs "subsystem" link */
+      val = (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).subsystem
+    } else {
+      val = getenv((*e).envname)
+    }
     if val.is_null() {
       return 0;
     }
@@ -754,6 +819,352 @@ unsafe fn build_alias(
   }
   return alias;
 }
+/* Encodes a label the way udev does for /dev/disk/by-label:
+ * anything but [A-Za-z0-9#+-.:=@_] becomes \xNN
+ */
+unsafe fn encode_devnode_name(mut src: *const libc::c_char) -> *mut libc::c_char {
+  let mut dst: *mut libc::c_char = crate::libbb::xfuncs_printf::xzalloc(
+    strlen(src)
+      .wrapping_mul(4i32 as libc::c_ulong)
+      .wrapping_add(1i32 as libc::c_ulong),
+  ) as *mut libc::c_char;
+  let mut p: *mut libc::c_char = dst;
+  while *src != 0 {
+    let mut c: u8 = *src as u8;
+    if c.is_ascii_alphanumeric()
+      || !strchr(
+        b"#+-.:=@_\x00" as *const u8 as *const libc::c_char,
+        c as libc::c_int,
+      )
+      .is_null()
+    {
+      *p = c as libc::c_char;
+      p = p.offset(1)
+    } else {
+      p = p.offset(sprintf(
+        p,
+        b"\\x%02x\x00" as *const u8 as *const libc::c_char,
+        c as libc::c_uint,
+      ) as isize)
+    }
+    src = src.offset(1)
+  }
+  return dst;
+}
+/* Is it "0000:00:1f.2" (PCI domain:bus:slot.func)? */
+unsafe fn is_pci_addr(mut s: *const libc::c_char) -> bool {
+  let mut i: libc::c_int = 0;
+  let mut fmt: *const libc::c_char = b"xxxx:xx:xx.x\x00" as *const u8 as *const libc::c_char;
+  while *fmt.offset(i as isize) != 0 {
+    let mut c: u8 = *s.offset(i as isize) as u8;
+    if *fmt.offset(i as isize) as libc::c_int == 'x' as i32 {
+      if !c.is_ascii_hexdigit() {
+        return false;
+      }
+    } else if c as libc::c_int != *fmt.offset(i as isize) as libc::c_int {
+      return false;
+    }
+    i += 1
+  }
+  return *s.offset(i as isize) as libc::c_int == '\u{0}' as i32;
+}
+/* Is it "H:C:T:L" (SCSI host:channel:target:lun)? */
+unsafe fn is_scsi_hctl(mut s: *const libc::c_char) -> bool {
+  let mut colons: libc::c_int = 0;
+  if !(*s as u8).is_ascii_digit() {
+    return false;
+  }
+  while *s != 0 {
+    if *s as libc::c_int == ':' as i32 {
+      colons += 1
+    } else if !(*s as u8).is_ascii_digit() {
+      return false;
+    }
+    s = s.offset(1)
+  }
+  return colons == 3i32;
+}
+/* Builds a udev-style by-path name ("pci-0000:00:1f.2-ata-1-part2")
+ * from the sysfs path of a block device. Returns NULL for devices
+ * not sitting on a bus we know (loop, ram, dm...).
+ */
+unsafe fn disk_path_id(mut syspath: *const libc::c_char) -> *mut libc::c_char {
+  let mut real: *mut libc::c_char = crate::libbb::xreadlink::xmalloc_realpath(syspath);
+  let mut comp: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut rest: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut pci: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut platform: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut usb: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut ata: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut scsi: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut virtio: bool = false;
+  let mut nvme: bool = false;
+  let mut id: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut buf: [libc::c_char; 32] = [0; 32];
+  let mut s: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut len: ssize_t = 0;
+  if real.is_null() {
+    return std::ptr::null_mut::<libc::c_char>();
+  }
+  rest = real;
+  loop {
+    comp = strsep(&mut rest, b"/\x00" as *const u8 as *const libc::c_char);
+    if comp.is_null() {
+      break;
+    }
+    if is_pci_addr(comp) {
+      pci = comp
+    } else if !crate::libbb::compare_string_array::is_prefixed_with(
+      comp,
+      b"ata\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+      && (*comp.offset(3) as u8).is_ascii_digit()
+    {
+      ata = comp.offset(3)
+    } else if is_scsi_hctl(comp) {
+      scsi = comp
+    } else if !crate::libbb::compare_string_array::is_prefixed_with(
+      comp,
+      b"virtio\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      virtio = true
+    } else if !crate::libbb::compare_string_array::is_prefixed_with(
+      comp,
+      b"nvme\x00" as *const u8 as *const libc::c_char,
+    )
+    .is_null()
+    {
+      nvme = true
+    } else if (*comp as u8).is_ascii_digit() && !strchr(comp, '-' as i32).is_null() {
+      /* USB interface "1-1.2:1.0": keep the part after "bus-" */
+      s = strchr(comp, ':' as i32);
+      if !s.is_null() && !strchr(s, '.' as i32).is_null() {
+        usb = strchr(comp, '-' as i32).offset(1)
+      }
+    } else if pci.is_null()
+      && !strchr(comp, '.' as i32).is_null()
+      && strchr(comp, ':' as i32).is_null()
+    {
+      /* "fe340000.mmc" under /sys/devices/platform */
+      platform = comp
+    }
+  }
+  if !pci.is_null() {
+    id = crate::libbb::xfuncs_printf::xasprintf(
+      b"%spci-%s\x00" as *const u8 as *const libc::c_char,
+      if virtio {
+        b"virtio-\x00" as *const u8 as *const libc::c_char
+      } else {
+        b"\x00" as *const u8 as *const libc::c_char
+      },
+      pci,
+    )
+  } else if !platform.is_null() {
+    id = crate::libbb::xfuncs_printf::xasprintf(
+      b"platform-%s\x00" as *const u8 as *const libc::c_char,
+      platform,
+    )
+  } else {
+    free(real as *mut libc::c_void);
+    return std::ptr::null_mut::<libc::c_char>();
+  }
+  s = id;
+  if !usb.is_null() {
+    id = crate::libbb::xfuncs_printf::xasprintf(
+      b"%s-usb-0:%s\x00" as *const u8 as *const libc::c_char,
+      s,
+      usb,
+    )
+  } else if !ata.is_null() {
+    id = crate::libbb::xfuncs_printf::xasprintf(
+      b"%s-ata-%s\x00" as *const u8 as *const libc::c_char,
+      s,
+      ata,
+    )
+  }
+  if id != s {
+    free(s as *mut libc::c_void);
+    s = id
+  }
+  if ata.is_null() && !scsi.is_null() {
+    id = crate::libbb::xfuncs_printf::xasprintf(
+      b"%s-scsi-%s\x00" as *const u8 as *const libc::c_char,
+      s,
+      scsi,
+    );
+    free(s as *mut libc::c_void);
+    s = id
+  }
+  free(real as *mut libc::c_void);
+  /* Partition? Its parent dir is the whole disk */
+  let mut attr: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(
+    syspath,
+    b"partition\x00" as *const u8 as *const libc::c_char,
+  );
+  len = crate::libbb::read::open_read_close(
+    attr,
+    buf.as_mut_ptr() as *mut libc::c_void,
+    (::std::mem::size_of::<[libc::c_char; 32]>() as libc::c_ulong)
+      .wrapping_sub(1i32 as libc::c_ulong),
+  );
+  free(attr as *mut libc::c_void);
+  let mut partition: libc::c_int = if len > 0 {
+    buf[len as usize] = '\u{0}' as i32 as libc::c_char;
+    atoll(buf.as_mut_ptr()) as libc::c_int
+  } else {
+    0
+  };
+  if nvme {
+    attr = crate::libbb::xfuncs_printf::xasprintf(
+      if partition != 0 {
+        b"%s/../nsid\x00" as *const u8 as *const libc::c_char
+      } else {
+        b"%s/nsid\x00" as *const u8 as *const libc::c_char
+      },
+      syspath,
+    );
+    len = crate::libbb::read::open_read_close(
+      attr,
+      buf.as_mut_ptr() as *mut libc::c_void,
+      (::std::mem::size_of::<[libc::c_char; 32]>() as libc::c_ulong)
+        .wrapping_sub(1i32 as libc::c_ulong),
+    );
+    free(attr as *mut libc::c_void);
+    if len > 0 {
+      buf[len as usize] = '\u{0}' as i32 as libc::c_char;
+      id = crate::libbb::xfuncs_printf::xasprintf(
+        b"%s-nvme-%u\x00" as *const u8 as *const libc::c_char,
+        s,
+        atoll(buf.as_mut_ptr()) as libc::c_uint,
+      );
+      free(s as *mut libc::c_void);
+      s = id
+    }
+  }
+  if partition != 0 {
+    id = crate::libbb::xfuncs_printf::xasprintf(
+      b"%s-part%u\x00" as *const u8 as *const libc::c_char,
+      s,
+      partition as libc::c_uint,
+    );
+    free(s as *mut libc::c_void);
+    s = id
+  }
+  return s;
+}
+static mut disk_link_dirs: [u8; 41] = *b"disk/by-uuid\x00disk/by-label\x00disk/by-path\x00\x00";
+/* Removes /dev/disk/by-* links pointing to node_name */
+unsafe fn remove_disk_links(mut node_name: *const libc::c_char) {
+  let mut dirname: *const libc::c_char = disk_link_dirs.as_ptr() as *const libc::c_char;
+  while *dirname != 0 {
+    let mut dir: *mut DIR = opendir(dirname);
+    if !dir.is_null() {
+      loop {
+        let mut de: *mut dirent = readdir(dir);
+        let mut link: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+        let mut target: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+        if de.is_null() {
+          break;
+        }
+        if (*de).d_name[0] as libc::c_int == '.' as i32 {
+          continue;
+        }
+        link = crate::libbb::concat_path_file::concat_path_file(dirname, (*de).d_name.as_ptr());
+        target = crate::libbb::xreadlink::xmalloc_readlink(link);
+        if !target.is_null() {
+          let mut p: *mut libc::c_char = crate::libbb::compare_string_array::is_prefixed_with(
+            target,
+            b"../../\x00" as *const u8 as *const libc::c_char,
+          );
+          if !p.is_null() && strcmp(p, node_name) == 0 {
+            unlink(link);
+          }
+          free(target as *mut libc::c_void);
+        }
+        free(link as *mut libc::c_void);
+      }
+      closedir(dir);
+    }
+    dirname = dirname.offset(strlen(dirname).wrapping_add(1i32 as libc::c_ulong) as isize)
+  }
+}
+unsafe fn add_disk_link(
+  mut dirname: *const libc::c_char,
+  mut name: *const libc::c_char,
+  mut node_name: *const libc::c_char,
+) {
+  let mut link: *mut libc::c_char = crate::libbb::concat_path_file::concat_path_file(dirname, name);
+  let mut target: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+    b"../../%s\x00" as *const u8 as *const libc::c_char,
+    node_name,
+  );
+  mkdir_recursive(dirname as *mut libc::c_char);
+  unlink(link);
+  if (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).verbose != 0 {
+    crate::libbb::verror_msg::bb_error_msg(
+      b"symlink: %s -> %s\x00" as *const u8 as *const libc::c_char,
+      link,
+      target,
+    );
+  }
+  symlink(target, link);
+  free(target as *mut libc::c_void);
+  free(link as *mut libc::c_void);
+}
+/* Creates /dev/disk/by-{uuid,label,path}/ links for block device node_name.
+ * UUID and label come from the volume_id filesystem probes.
+ */
+unsafe fn add_disk_links(mut node_name: *const libc::c_char, mut syspath: *const libc::c_char) {
+  let mut label: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut uuid: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut type_0: *const libc::c_char = std::ptr::null();
+  let mut path_id: *mut libc::c_char = std::ptr::null_mut::<libc::c_char>();
+  let mut fd: libc::c_int = 0;
+  remove_disk_links(node_name);
+  fd = open(node_name, 0 | 0o4000i32 | 0o2000000i32);
+  if fd >= 0
+    && crate::util_linux::volume_id::get_devname::get_label_uuid(
+      fd,
+      &mut label,
+      &mut uuid,
+      &mut type_0,
+    ) == 0
+  {
+    /* get_label_uuid closed fd */
+    if *uuid.offset(0) != 0 {
+      let mut enc: *mut libc::c_char = encode_devnode_name(uuid);
+      add_disk_link(
+        b"disk/by-uuid\x00" as *const u8 as *const libc::c_char,
+        enc,
+        node_name,
+      );
+      free(enc as *mut libc::c_void);
+    }
+    if *label.offset(0) != 0 {
+      let mut enc_0: *mut libc::c_char = encode_devnode_name(label);
+      add_disk_link(
+        b"disk/by-label\x00" as *const u8 as *const libc::c_char,
+        enc_0,
+        node_name,
+      );
+      free(enc_0 as *mut libc::c_void);
+    }
+    free(label as *mut libc::c_void);
+    free(uuid as *mut libc::c_void);
+  }
+  path_id = disk_path_id(syspath);
+  if !path_id.is_null() {
+    add_disk_link(
+      b"disk/by-path\x00" as *const u8 as *const libc::c_char,
+      path_id,
+      node_name,
+    );
+    free(path_id as *mut libc::c_void);
+  }
+}
 /* mknod in /dev based on a path like "/sys/block/hda/hda1"
  * NB1: path parameter needs to have SCRATCH_SIZE scratch bytes
  * after NUL, but we promise to not mangle it (IOW: to restore NUL if needed).
@@ -881,7 +1292,7 @@ unsafe fn make_device(
     let mut rule: *const rule = std::ptr::null();
     str_to_match = device_name;
     rule = next_rule();
-    if env_matches((*rule).envmatch) == 0 {
+    if env_matches((*rule).envmatch, path) == 0 {
       continue;
     }
     if (*rule).maj >= 0 {
@@ -1065,6 +1476,9 @@ unsafe fn make_device(
           symlink(node_name, device_name);
         }
       }
+      if type_0 == 0o60000i32 {
+        add_disk_links(node_name, path);
+      }
     }
     if 1i32 != 0 && !command.is_null() {
       /* setenv will leak memory, use putenv/unsetenv/free */
@@ -1106,6 +1520,9 @@ unsafe fn make_device(
           node_name,
         );
       }
+      if type_0 == 0o60000i32 {
+        remove_disk_links(node_name);
+      }
       unlink(node_name);
     }
     free(alias as *mut libc::c_void);
@@ -1209,6 +1626,58 @@ unsafe fn dirAction(
 ) -> libc::c_int {
   return if depth >= 3i32 { 2i32 } else { 1i32 };
 }
+/* Search the same places the kernel's own loader does:
+ * /lib/firmware/updates/$(uname -r), /lib/firmware/updates,
+ * /lib/firmware/$(uname -r), /lib/firmware.
+ * FIRMWARE.xz is tried too and unpacked on the fly.
+ */
+unsafe fn open_firmware(mut firmware: *const libc::c_char) -> libc::c_int {
+  let mut uts: utsname = std::mem::zeroed();
+  let mut dirs: [*mut libc::c_char; 4] = [std::ptr::null_mut::<libc::c_char>(); 4];
+  let mut fd: libc::c_int = -1i32;
+  let mut i: libc::c_int = 0;
+  uname(&mut uts);
+  dirs[0] = crate::libbb::xfuncs_printf::xasprintf(
+    b"/lib/firmware/updates/%s\x00" as *const u8 as *const libc::c_char,
+    uts.release.as_mut_ptr(),
+  );
+  dirs[1] = crate::libbb::xfuncs_printf::xstrdup(
+    b"/lib/firmware/updates\x00" as *const u8 as *const libc::c_char,
+  );
+  dirs[2] = crate::libbb::xfuncs_printf::xasprintf(
+    b"/lib/firmware/%s\x00" as *const u8 as *const libc::c_char,
+    uts.release.as_mut_ptr(),
+  );
+  dirs[3] =
+    crate::libbb::xfuncs_printf::xstrdup(b"/lib/firmware\x00" as *const u8 as *const libc::c_char);
+  while i < 4i32 && fd < 0 {
+    if chdir(dirs[i as usize]) == 0 {
+      fd = open(firmware, 0);
+      if fd < 0 {
+        let mut compressed: *mut libc::c_char = crate::libbb::xfuncs_printf::xasprintf(
+          b"%s.xz\x00" as *const u8 as *const libc::c_char,
+          firmware,
+        );
+        fd = crate::archival::libarchive::open_transformer::open_zipped(compressed, 0);
+        free(compressed as *mut libc::c_void);
+      }
+      if fd >= 0 && (*(bb_common_bufsiz1.as_mut_ptr() as *mut globals)).verbose != 0 {
+        crate::libbb::verror_msg::bb_error_msg(
+          b"firmware: %s/%s\x00" as *const u8 as *const libc::c_char,
+          dirs[i as usize],
+          firmware,
+        );
+      }
+    }
+    i += 1
+  }
+  i = 0;
+  while i < 4i32 {
+    free(dirs[i as usize] as *mut libc::c_void);
+    i += 1
+  }
+  return fd;
+}
 /* For the full gory details, see linux/Documentation/firmware_class/README
  *
  * Firmware loading works like this:
@@ -1226,10 +1695,7 @@ unsafe fn load_firmware(mut firmware: *const libc::c_char, mut sysfs_path: *cons
   let mut firmware_fd: libc::c_int = 0;
   let mut loading_fd: libc::c_int = 0;
   /* check for /lib/firmware/$FIRMWARE */
-  firmware_fd = -1i32; /* can fail */
-  if chdir(b"/lib/firmware\x00" as *const u8 as *const libc::c_char) == 0 {
-    firmware_fd = open(firmware, 0)
-  }
+  firmware_fd = open_firmware(firmware);
   /* check for /sys/$DEVPATH/loading ... give 30 seconds to appear */
   crate::libbb::xfuncs_printf::xchdir(sysfs_path);
   cnt = 0;
@@ -54,7 +54,7 @@ static mut uuidCache: *mut uuidCache_s = std::ptr::null_mut();
  * Otherwise, returns malloc'ed strings for label and uuid
  * (and they can't be NULL, although they can be "").
  * NB: closes fd. */
-unsafe fn get_label_uuid(
+pub unsafe fn get_label_uuid(
   mut fd: libc::c_int,
   mut label: *mut *mut libc::c_char,
   mut uuid: *mut *mut libc::c_char,